@@ -22,9 +22,46 @@ local function translate_equip_data_code(text)
     end)
 end
 
+-- Dependency tracking for the extraction cache.
+-- While enabled, every entry that is accessed is recorded.
+local tracked_deps = nil
+
+local function track_dep(name, index)
+    if tracked_deps then
+        tracked_deps[#tracked_deps + 1] = { name, index }
+    end
+end
+
+function begin_track_deps()
+    tracked_deps = {}
+end
+
+function end_track_deps()
+    local deps = tracked_deps
+    tracked_deps = nil
+    return deps
+end
+
+-- Loaded entries are kept out of the lookup tables themselves, so that
+-- every access goes through the lazy loader and can be tracked.
+local loaded_entries = {}
+local raw_entries = {}
+
 local function lazy_load(mode, allow_name_code)
     return function(args, index)
         local name = args.__name;
+        track_dep(name, index)
+
+        local loaded = loaded_entries[args]
+        if loaded == nil then
+            loaded = {}
+            loaded_entries[args] = loaded
+            raw_entries[args] = {}
+        end
+
+        if loaded[index] ~= nil then
+            return loaded[index]
+        end
 
         if mode == 1 and cs[name][index] then
             -- The sharecfgdata files are separate from the main game script.
@@ -60,7 +97,7 @@ local function lazy_load(mode, allow_name_code)
 
         local base_id = rawget(data, "base")
         if base_id ~= nil then
-            args[index] = setmetatable(real, {
+            loaded[index] = setmetatable(real, {
                 __index = function (self, key)
                     local raw = data[key]
                     if raw == nil then
@@ -71,12 +108,13 @@ local function lazy_load(mode, allow_name_code)
                 end
             })
         else
-            args[index] = setmetatable(real, {
+            loaded[index] = setmetatable(real, {
                 __index = data
             })
         end
 
-        return args[index]
+        raw_entries[args][index] = data
+        return loaded[index]
     end
 end
 
@@ -131,13 +169,32 @@ end
 
 -- Used by our code to load a buff/skill.
 function require_buff(id)
+    track_dep("gamecfg.buff", id)
     return require("gamecfg.buff.buff_" .. id)
 end
 
 function require_skill(id)
+    track_dep("gamecfg.skill", id)
     return require("gamecfg.skill.skill_" .. id)
 end
 
+-- Gets the untranslated value of a tracked dependency.
+function get_dep_value(name, index)
+    if name == "gamecfg.buff" then
+        return require_buff(index)
+    elseif name == "gamecfg.skill" then
+        return require_skill(index)
+    end
+
+    local args = pg[name]
+    if args == nil or args[index] == nil then
+        return nil
+    end
+
+    local raw = raw_entries[args]
+    return raw and raw[index]
+end
+
 -- Helper for augment parsing
 function get_augment_ship_types(kind)
     local sp = pg.spweapon_type[kind]
@@ -196,6 +253,7 @@ function get_juustagram_chat(chat_id)
                 return {
                     flag = option[1],
                     value = option[2],
+                    favor = option[3],
                 }
             end)
         end
@@ -211,12 +269,28 @@ function get_juustagram_chat(chat_id)
     local chat = pg.activity_ins_chat_group[chat_id]
     assert(chat, "chat not found: " .. chat_id)
 
+    local function load_unlock()
+        local param = chat.trigger_param
+        if type(param) == "table" then
+            param = param[1]
+        end
+
+        if chat.trigger_type == 1 and type(param) == "number" then
+            return { Affinity = { amount = param } }
+        elseif chat.trigger_type == 2 and type(param) == "number" then
+            return { Skin = { skin_id = param } }
+        elseif chat.trigger_type and chat.trigger_type ~= 0 then
+            return { Other = { kind = chat.trigger_type } }
+        end
+    end
+
     local content = _map(chat.content, load_chat_content)
     return {
         chat_id = chat.id,
         group_id = chat.ship_group,
         name = chat.name,
         unlock_desc = chat.unlock_desc,
+        unlock = load_unlock(),
         entries = content,
     }
 end
@@ -0,0 +1,346 @@
+//! Provides the incremental extraction cache.
+//!
+//! While an entry is built, the Lua state records every game data entry it
+//! accesses. These dependencies and a hash of their values are stored alongside
+//! the built entry. On the next run, the entry is reused as long as the hash
+//! of the recorded dependencies is unchanged.
+
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+use std::{fs, io};
+
+use azur_lane::equip::*;
+use azur_lane::ship::*;
+use mlua::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// The cache for all inputs.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Cache {
+    /// Identifies the collector version and config the cache was built with.
+    version: u64,
+    /// The cache for each input path.
+    inputs: HashMap<String, InputCache>,
+    /// Whether the cache is in use.
+    #[serde(skip)]
+    enabled: bool,
+}
+
+/// The cache for a single input.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct InputCache {
+    pub ships: EntryCache<ShipData>,
+    pub equips: EntryCache<Equip>,
+    pub augments: EntryCache<Augment>,
+}
+
+/// The cached entries of one kind.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EntryCache<T> {
+    /// The entries loaded from the cache file.
+    entries: HashMap<u32, CacheEntry<T>>,
+    /// The entries used during this run.
+    #[serde(skip)]
+    used: HashMap<u32, CacheEntry<T>>,
+    #[serde(skip)]
+    enabled: bool,
+    #[serde(skip)]
+    hits: usize,
+}
+
+/// A single cached entry.
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheEntry<T> {
+    /// An additional hash provided by the caller.
+    seed: u64,
+    /// The hash of the values of all dependencies.
+    hash: u64,
+    /// The game data entries accessed while building this entry.
+    deps: Vec<Dep>,
+    /// The built data.
+    data: T,
+}
+
+/// A dependency on a game data entry.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+struct Dep(String, DepKey);
+
+/// The key of a dependency within its table.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(untagged)]
+enum DepKey {
+    Int(i64),
+    Str(String),
+}
+
+impl Cache {
+    /// Loads the cache from a file.
+    ///
+    /// If the file is missing, unreadable, or was created by a different
+    /// version, returns an empty cache.
+    pub fn load(path: &str) -> Self {
+        let version = current_version();
+        let cache = fs::File::open(path)
+            .ok()
+            .and_then(|f| serde_json::from_reader::<_, Self>(io::BufReader::new(f)).ok())
+            .filter(|c| c.version == version);
+
+        Self {
+            version,
+            enabled: true,
+            ..cache.unwrap_or_default()
+        }
+    }
+
+    /// Creates an empty cache that is only kept in memory.
+    pub fn in_memory() -> Self {
+        Self {
+            version: current_version(),
+            enabled: true,
+            ..Self::default()
+        }
+    }
+
+    /// Saves the entries used during this run to a file.
+    ///
+    /// Like [`Self::end_run`], this discards all other entries.
+    pub fn save(&mut self, path: &str) -> anyhow::Result<()> {
+        if let Some(parent) = Path::new(path).parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        self.end_run();
+
+        let file = io::BufWriter::new(fs::File::create(path)?);
+        serde_json::to_writer(file, self)?;
+        Ok(())
+    }
+
+    /// Discards the entries that weren't used during this run, readying the
+    /// cache for the next run.
+    pub fn end_run(&mut self) {
+        for input in self.inputs.values_mut() {
+            input.ships.retain_used();
+            input.equips.retain_used();
+            input.augments.retain_used();
+        }
+    }
+
+    /// Whether the cache is in use.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Gets the cache for a specific input.
+    pub fn input(&mut self, input: &str) -> &mut InputCache {
+        let enabled = self.enabled;
+        let cache = self.inputs.entry(input.to_owned()).or_default();
+        cache.ships.enabled = enabled;
+        cache.equips.enabled = enabled;
+        cache.augments.enabled = enabled;
+        cache
+    }
+}
+
+impl<T> Default for EntryCache<T> {
+    fn default() -> Self {
+        Self {
+            entries: HashMap::new(),
+            used: HashMap::new(),
+            enabled: false,
+            hits: 0,
+        }
+    }
+}
+
+impl<T: Clone> EntryCache<T> {
+    /// Gets a cached entry or builds it.
+    ///
+    /// The entry is reused if neither the `seed` nor any of the game data
+    /// it accessed changed. `seed` should cover any inputs that are not
+    /// accessed through the tracked game data tables.
+    pub fn get_or_build(
+        &mut self,
+        lua: &Lua,
+        id: u32,
+        seed: u64,
+        build: impl FnOnce() -> anyhow::Result<T>,
+    ) -> anyhow::Result<T> {
+        if !self.enabled {
+            return build();
+        }
+
+        if let Some(entry) = self.entries.remove(&id) {
+            if entry.seed == seed && hash_deps(lua, &entry.deps).ok() == Some(entry.hash) {
+                let data = entry.data.clone();
+                self.hits += 1;
+                self.used.insert(id, entry);
+                return Ok(data);
+            }
+        }
+
+        let globals = lua.globals();
+        globals.call_function::<()>("begin_track_deps", ())?;
+        let data = build();
+        let deps: Vec<Dep> = globals.call_function("end_track_deps", ())?;
+        let data = data?;
+
+        let mut deps = deps;
+        deps.sort_unstable();
+        deps.dedup();
+
+        let hash = hash_deps(lua, &deps)?;
+        self.used.insert(
+            id,
+            CacheEntry {
+                seed,
+                hash,
+                deps,
+                data: data.clone(),
+            },
+        );
+
+        Ok(data)
+    }
+
+    /// Moves the entries with the given IDs into a separate cache.
+    ///
+    /// This allows building entries on another thread. Use [`Self::merge`] to
+    /// add the results back to this cache.
+    pub fn split_off(&mut self, ids: impl IntoIterator<Item = u32>) -> Self {
+        let entries = ids
+            .into_iter()
+            .filter_map(|id| self.entries.remove_entry(&id))
+            .collect();
+
+        Self {
+            entries,
+            enabled: self.enabled,
+            ..Self::default()
+        }
+    }
+
+    /// Merges a cache created with [`Self::split_off`] back into this one.
+    pub fn merge(&mut self, other: Self) {
+        self.entries.extend(other.entries);
+        self.used.extend(other.used);
+        self.hits += other.hits;
+    }
+
+    /// The amount of entries reused from the cache so far.
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    fn retain_used(&mut self) {
+        self.entries = std::mem::take(&mut self.used);
+        self.hits = 0;
+    }
+}
+
+/// Hashes a value in a way that is stable across runs.
+pub fn stable_hash(value: impl Hash) -> u64 {
+    let mut hasher = Fnv1a::default();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn current_version() -> u64 {
+    stable_hash((
+        env!("CARGO_PKG_VERSION"),
+        option_env!("GIT_HASH"),
+        include_str!("../assets/config.json"),
+        crate::config().file_source.as_deref(),
+        include_str!("../assets/lua_init.lua"),
+    ))
+}
+
+fn hash_deps(lua: &Lua, deps: &[Dep]) -> LuaResult<u64> {
+    let mut hasher = Fnv1a::default();
+    for Dep(name, key) in deps {
+        let value: LuaValue = lua
+            .globals()
+            .call_function("get_dep_value", (name.as_str(), key.clone()))?;
+        hasher.write_u64(hash_lua_value(&value, 0)?);
+    }
+
+    Ok(hasher.finish())
+}
+
+/// Hashes a Lua value. Tables are hashed independent of their iteration order.
+fn hash_lua_value(value: &LuaValue, depth: u32) -> LuaResult<u64> {
+    const MAX_DEPTH: u32 = 64;
+
+    let mut hasher = Fnv1a::default();
+    match value {
+        LuaValue::Nil => hasher.write_u8(0),
+        LuaValue::Boolean(b) => (1u8, b).hash(&mut hasher),
+        LuaValue::Integer(i) => (2u8, i).hash(&mut hasher),
+        LuaValue::Number(n) => (3u8, n.to_bits()).hash(&mut hasher),
+        LuaValue::String(s) => (4u8, &*s.as_bytes()).hash(&mut hasher),
+        LuaValue::Table(t) if depth < MAX_DEPTH => {
+            let mut pairs = Vec::new();
+            t.for_each(|k: LuaValue, v: LuaValue| {
+                pairs.push((
+                    hash_lua_value(&k, depth + 1)?,
+                    hash_lua_value(&v, depth + 1)?,
+                ));
+                Ok(())
+            })?;
+
+            pairs.sort_unstable();
+            (5u8, pairs).hash(&mut hasher);
+        },
+        other => (6u8, other.type_name()).hash(&mut hasher),
+    }
+
+    Ok(hasher.finish())
+}
+
+/// 64-bit FNV-1a. Unlike the std hashers, its output is stable.
+struct Fnv1a(u64);
+
+impl Default for Fnv1a {
+    fn default() -> Self {
+        Self(0xCBF2_9CE4_8422_2325)
+    }
+}
+
+impl Hasher for Fnv1a {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.0 ^= u64::from(b);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01B3);
+        }
+    }
+}
+
+impl FromLua for Dep {
+    fn from_lua(value: LuaValue, lua: &Lua) -> LuaResult<Self> {
+        let table = LuaTable::from_lua(value, lua)?;
+        Ok(Self(table.get(1)?, table.get(2)?))
+    }
+}
+
+impl FromLua for DepKey {
+    fn from_lua(value: LuaValue, lua: &Lua) -> LuaResult<Self> {
+        match value {
+            LuaValue::Integer(i) => Ok(Self::Int(i)),
+            other => String::from_lua(other, lua).map(Self::Str),
+        }
+    }
+}
+
+impl IntoLua for DepKey {
+    fn into_lua(self, lua: &Lua) -> LuaResult<LuaValue> {
+        match self {
+            Self::Int(i) => i.into_lua(lua),
+            Self::Str(s) => s.into_lua(lua),
+        }
+    }
+}
@@ -0,0 +1,208 @@
+//! Implements the `--check` mode, verifying the integrity of the built data.
+//!
+//! None of these checks should fail for correctly parsed data, so any issue
+//! found likely points to a parser regression or a game data change the
+//! collector doesn't handle yet.
+
+use std::collections::{HashMap, HashSet};
+
+use azur_lane::equip::*;
+use azur_lane::ship::*;
+use azur_lane::{DefinitionData, RewardKind};
+
+/// Checks the data and returns a description of every issue found.
+pub fn check(data: &DefinitionData) -> Vec<String> {
+    let mut issues = Vec::new();
+
+    let ships = unique_ids(&mut issues, "ship", &data.ships, |s| s.group_id);
+    unique_ids(&mut issues, "equip", &data.equips, |e| e.equip_id);
+    unique_ids(&mut issues, "augment", &data.augments, |a| a.augment_id);
+    unique_ids(
+        &mut issues,
+        "juustagram chat",
+        &data.juustagram_chats,
+        |c| c.chat_id,
+    );
+    unique_ids(&mut issues, "commission", &data.commissions, |c| {
+        c.commission_id
+    });
+    let stages = unique_ids(&mut issues, "stage", &data.stages, |s| s.stage_id);
+
+    let mut skin_owners = HashMap::new();
+    for ship in &data.ships {
+        check_ship(&mut issues, data, ship);
+
+        if !ship.skins.iter().any(|s| s.skin_id == ship.default_skin_id) {
+            issues.push(format!(
+                "{}: default skin {} does not exist",
+                ship_label(ship),
+                ship.default_skin_id
+            ));
+        }
+
+        for skin in &ship.skins {
+            if let Some(owner) = skin_owners.insert(skin.skin_id, ship.group_id) {
+                issues.push(format!(
+                    "{}: skin {} is also used by ship [{owner}]",
+                    ship_label(ship),
+                    skin.skin_id
+                ));
+            }
+        }
+    }
+
+    for augment in &data.augments {
+        check_augment(&mut issues, &ships, augment);
+    }
+
+    for chat in &data.juustagram_chats {
+        if !ships.contains_key(&chat.group_id) {
+            issues.push(format!(
+                "juustagram chat [{}]: ship [{}] does not exist",
+                chat.chat_id, chat.group_id
+            ));
+        }
+    }
+
+    for stage in &data.stages {
+        if let Some(required) = stage.required_stage_id {
+            if !stages.contains_key(&required) {
+                issues.push(format!(
+                    "stage {} [{}]: required stage [{required}] does not exist",
+                    stage.code, stage.stage_id
+                ));
+            }
+        }
+
+        for drop in stage.drops.iter().filter(|d| d.kind == RewardKind::Ship) {
+            if !ships.contains_key(&drop.id) {
+                issues.push(format!(
+                    "stage {} [{}]: dropped ship [{}] does not exist",
+                    stage.code, stage.stage_id, drop.id
+                ));
+            }
+        }
+    }
+
+    issues
+}
+
+/// Checks a ship and its retrofits.
+fn check_ship(issues: &mut Vec<String>, data: &DefinitionData, ship: &ShipData) {
+    for params in [
+        StatParams::new(1, Affinity::Neutral).with_limit_break(0),
+        StatParams::new(125, Affinity::Oath),
+    ] {
+        let stats = ship.stats.compute(params);
+        if !stats.hp.is_finite() || stats.hp <= 0.0 {
+            issues.push(format!(
+                "{}: HP is not positive at level {}",
+                ship_label(ship),
+                params.level
+            ));
+        }
+
+        for kind in [StatKind::FP, StatKind::TRP, StatKind::AA, StatKind::AVI] {
+            let value = stats.get(kind);
+            if !value.is_finite() || value < 0.0 {
+                issues.push(format!(
+                    "{}: {} is {value} at level {}",
+                    ship_label(ship),
+                    kind.name(),
+                    params.level
+                ));
+            }
+        }
+    }
+
+    let mut buff_ids = HashSet::new();
+    for skill in &ship.skills {
+        if skill.buff_id == 0 {
+            issues.push(format!(
+                "{}: skill `{}` has no buff id",
+                ship_label(ship),
+                skill.name
+            ));
+        } else if !buff_ids.insert(skill.buff_id) {
+            issues.push(format!(
+                "{}: skill {} is listed multiple times",
+                ship_label(ship),
+                skill.buff_id
+            ));
+        }
+    }
+
+    for (index, slot) in ship.equip_slots.iter().enumerate() {
+        if slot.allowed.is_empty() {
+            issues.push(format!(
+                "{}: equip slot {} allows no equipment",
+                ship_label(ship),
+                index + 1
+            ));
+        } else if !data.equips.iter().any(|e| can_equip(ship, slot, e)) {
+            issues.push(format!(
+                "{}: no equipment fits slot {} for hull type {}",
+                ship_label(ship),
+                index + 1,
+                ship.hull_type.designation()
+            ));
+        }
+    }
+
+    for retrofit in &ship.retrofits {
+        check_ship(issues, data, retrofit);
+    }
+}
+
+/// Checks that an augment refers to existing ships and skills.
+fn check_augment(issues: &mut Vec<String>, ships: &HashMap<u32, &ShipData>, augment: &Augment) {
+    let Some(ship_id) = augment.usability.unique_ship_id() else {
+        return;
+    };
+
+    let Some(ship) = ships.get(&ship_id) else {
+        issues.push(format!(
+            "augment {} [{}]: unique ship [{ship_id}] does not exist",
+            augment.name, augment.augment_id
+        ));
+        return;
+    };
+
+    if let Some(upgrade) = &augment.skill_upgrade {
+        let has_skill = |s: &ShipData| s.skills.iter().any(|s| s.buff_id == upgrade.original_id);
+        if !has_skill(ship) && !ship.retrofits.iter().any(has_skill) {
+            issues.push(format!(
+                "augment {} [{}]: upgraded skill {} is not a skill of {}",
+                augment.name,
+                augment.augment_id,
+                upgrade.original_id,
+                ship_label(ship)
+            ));
+        }
+    }
+}
+
+/// Reports duplicate IDs and returns the entries by their ID.
+fn unique_ids<'a, T>(
+    issues: &mut Vec<String>,
+    kind: &str,
+    items: &'a [T],
+    id: impl Fn(&T) -> u32,
+) -> HashMap<u32, &'a T> {
+    let mut map = HashMap::with_capacity(items.len());
+    for item in items {
+        if map.insert(id(item), item).is_some() {
+            issues.push(format!("{kind} [{}]: id is not unique", id(item)));
+        }
+    }
+
+    map
+}
+
+fn can_equip(ship: &ShipData, slot: &EquipSlot, equip: &Equip) -> bool {
+    slot.allowed.contains(&equip.kind) && !equip.hull_disallowed.contains(&ship.hull_type)
+}
+
+fn ship_label(ship: &ShipData) -> String {
+    format!("ship {} [{}]", ship.name, ship.group_id)
+}
@@ -6,7 +6,7 @@
 use azur_lane::equip::*;
 use azur_lane::ship::*;
 use azur_lane::skill::*;
-use azur_lane::Faction;
+use azur_lane::{Faction, Locale, RewardKind};
 
 /// Converts an ID to a faction.
 pub fn to_faction(num: u32) -> Faction {
@@ -40,6 +40,18 @@ pub fn to_faction(num: u32) -> Faction {
     }
 }
 
+/// Converts a `PLATFORM_CODE` to a locale.
+///
+/// Returns [`None`] for regions that aren't tracked.
+pub fn to_locale(num: u32) -> Option<Locale> {
+    match num {
+        1 => Some(Locale::CN),
+        2 => Some(Locale::JP),
+        4 => Some(Locale::EN),
+        3 | 5 | _ => None, // KR & TW
+    }
+}
+
 /// Converts an ID to a rarity.
 pub fn to_rarity(num: u32) -> ShipRarity {
     match num {
@@ -279,3 +291,15 @@ pub fn to_dive_filter(num: u32) -> BulletFlags {
         _ => BulletFlags::empty(),
     }
 }
+
+/// Converts a drop type to a reward kind.
+pub fn to_reward_kind(num: u32) -> RewardKind {
+    match num {
+        1 => RewardKind::Resource,
+        2 => RewardKind::Item,
+        3 => RewardKind::Equip,
+        4 => RewardKind::Ship,
+        5 => RewardKind::Furniture,
+        _ => RewardKind::Other,
+    }
+}
@@ -0,0 +1,167 @@
+//! Implements the `diff` subcommand, comparing two data files.
+
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+use std::path::Path;
+
+use azur_lane::DefinitionData;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::log;
+
+/// Compares two data files and prints the differences.
+#[derive(Debug, clap::Args)]
+pub struct DiffArgs {
+    /// The older data file or output directory.
+    old: String,
+
+    /// The newer data file or output directory.
+    new: String,
+}
+
+/// A single entry to compare, keyed by its ID.
+struct Entry {
+    name: String,
+    value: Value,
+}
+
+pub fn run(args: &DiffArgs) -> anyhow::Result<()> {
+    let old = load(&args.old)?;
+    let new = load(&args.new)?;
+
+    let mut out = io::stdout().lock();
+
+    diff_section(
+        &mut out,
+        "Ships",
+        entries(&old.ships, |s| (s.group_id, s.name.clone()))?,
+        entries(&new.ships, |s| (s.group_id, s.name.clone()))?,
+        // skins are listed separately
+        &["skins"],
+    )?;
+    diff_section(
+        &mut out,
+        "Skins",
+        skin_entries(&old)?,
+        skin_entries(&new)?,
+        &[],
+    )?;
+    diff_section(
+        &mut out,
+        "Equipment",
+        entries(&old.equips, |e| (e.equip_id, e.name.clone()))?,
+        entries(&new.equips, |e| (e.equip_id, e.name.clone()))?,
+        &[],
+    )?;
+    diff_section(
+        &mut out,
+        "Augments",
+        entries(&old.augments, |a| (a.augment_id, a.name.clone()))?,
+        entries(&new.augments, |a| (a.augment_id, a.name.clone()))?,
+        &[],
+    )?;
+
+    Ok(())
+}
+
+fn load(path: &str) -> anyhow::Result<DefinitionData> {
+    let action = log::action!("Loading `{path}`.").start();
+    let data = DefinitionData::load_from_path(Path::new(path))?;
+    action.finish();
+    Ok(data)
+}
+
+fn entries<T: Serialize>(
+    items: &[T],
+    key: impl Fn(&T) -> (u32, String),
+) -> serde_json::Result<BTreeMap<u32, Entry>> {
+    items
+        .iter()
+        .map(|item| {
+            let (id, name) = key(item);
+            let value = serde_json::to_value(item)?;
+            Ok((id, Entry { name, value }))
+        })
+        .collect()
+}
+
+fn skin_entries(data: &DefinitionData) -> serde_json::Result<BTreeMap<u32, Entry>> {
+    data.ships
+        .iter()
+        .flat_map(|ship| ship.skins.iter().map(move |skin| (ship, skin)))
+        .map(|(ship, skin)| {
+            let name = format!("{} ({})", skin.name, ship.name);
+            let value = serde_json::to_value(skin)?;
+            Ok((skin.skin_id, Entry { name, value }))
+        })
+        .collect()
+}
+
+fn diff_section(
+    out: &mut impl Write,
+    title: &str,
+    old: BTreeMap<u32, Entry>,
+    mut new: BTreeMap<u32, Entry>,
+    ignore_fields: &[&str],
+) -> io::Result<()> {
+    let mut added = Vec::new();
+    let mut removed = Vec::new();
+    let mut changed = Vec::new();
+
+    for (id, old) in old {
+        match new.remove(&id) {
+            Some(new) => {
+                let fields = changed_fields(&old.value, &new.value, ignore_fields);
+                if !fields.is_empty() {
+                    changed.push((id, new.name, fields));
+                }
+            },
+            None => removed.push((id, old.name)),
+        }
+    }
+
+    added.extend(new.into_iter().map(|(id, e)| (id, e.name)));
+
+    if added.is_empty() && removed.is_empty() && changed.is_empty() {
+        return Ok(());
+    }
+
+    writeln!(out, "## {title}")?;
+    for (id, name) in added {
+        writeln!(out, "+ {name} [{id}]")?;
+    }
+
+    for (id, name) in removed {
+        writeln!(out, "- {name} [{id}]")?;
+    }
+
+    for (id, name, fields) in changed {
+        writeln!(out, "~ {name} [{id}]: {}", fields.join(", "))?;
+    }
+
+    writeln!(out)
+}
+
+/// Gets the names of the top-level fields that differ between two values.
+fn changed_fields(old: &Value, new: &Value, ignore: &[&str]) -> Vec<String> {
+    let (Value::Object(old), Value::Object(new)) = (old, new) else {
+        return if old == new {
+            Vec::new()
+        } else {
+            vec!["value".to_owned()]
+        };
+    };
+
+    let mut fields: Vec<String> = old
+        .keys()
+        .chain(new.keys())
+        .filter(|k| !ignore.contains(&k.as_str()))
+        .filter(|k| old.get(*k) != new.get(*k))
+        .cloned()
+        .collect();
+
+    fields.sort_unstable();
+    fields.dedup();
+    fields
+}
@@ -0,0 +1,106 @@
+//! Implements the `--lenient` mode, skipping entries that fail to build.
+//!
+//! The collector usually aborts on the first error. In lenient mode, errors
+//! while building a single entry are instead recorded into a report and the
+//! entry is left out of the data.
+
+use std::path::Path;
+use std::{fmt, fs, io};
+
+use serde::Serialize;
+
+/// Collects the errors of entries that failed to build.
+#[derive(Debug, Default)]
+pub struct ErrorReport {
+    /// Whether errors are recorded rather than returned.
+    enabled: bool,
+    /// The input currently being loaded.
+    input: String,
+    /// The recorded errors.
+    errors: Vec<EntryError>,
+}
+
+/// The error of a single entry that failed to build.
+#[derive(Debug, Serialize)]
+struct EntryError {
+    /// The input the entry was loaded from.
+    input: String,
+    /// The kind of entry, f.e. `ship`.
+    kind: &'static str,
+    /// The entry's ID.
+    id: u32,
+    /// The error message, including its context.
+    error: String,
+}
+
+impl ErrorReport {
+    /// Creates a new report. If not `enabled`, errors are returned as-is.
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            ..Self::default()
+        }
+    }
+
+    /// Sets the input that subsequent errors are attributed to.
+    pub fn set_input(&mut self, input: &str) {
+        input.clone_into(&mut self.input);
+    }
+
+    /// Handles the result of building a single entry.
+    ///
+    /// In lenient mode, an error is recorded and [`None`] is returned.
+    /// Otherwise, the error is returned.
+    pub fn handle<T, E: fmt::Display>(
+        &mut self,
+        kind: &'static str,
+        id: u32,
+        result: Result<T, E>,
+    ) -> Result<Option<T>, E> {
+        match result {
+            Ok(value) => Ok(Some(value)),
+            Err(err) if self.enabled => {
+                self.errors.push(EntryError {
+                    input: self.input.clone(),
+                    kind,
+                    id,
+                    error: format!("{err:#}"),
+                });
+                Ok(None)
+            },
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Creates an empty report for the same input, f.e. to use on another
+    /// thread. Use [`Self::merge`] to add its errors back to this report.
+    pub fn fork(&self) -> Self {
+        Self {
+            enabled: self.enabled,
+            input: self.input.clone(),
+            errors: Vec::new(),
+        }
+    }
+
+    /// Adds the errors of a forked report to this one.
+    pub fn merge(&mut self, other: Self) {
+        self.errors.extend(other.errors);
+    }
+
+    /// Whether lenient mode is enabled.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// The amount of recorded errors.
+    pub fn error_count(&self) -> usize {
+        self.errors.len()
+    }
+
+    /// Saves the recorded errors to a JSON file.
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        let file = io::BufWriter::new(fs::File::create(path)?);
+        serde_json::to_writer_pretty(file, &self.errors)?;
+        Ok(())
+    }
+}
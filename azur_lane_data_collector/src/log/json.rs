@@ -0,0 +1,65 @@
+//! Machine-readable output, writing one JSON object per event and line.
+
+use std::fmt;
+use std::io::{self, Write};
+use std::time::SystemTime;
+
+use serde::Serialize;
+
+use super::{ActionInner, ProgressKind};
+
+/// The kind of a logged event.
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventKind {
+    Info,
+    Start,
+    Progress,
+    Finish,
+}
+
+#[derive(Debug, Serialize)]
+struct Event<'a> {
+    time: String,
+    event: EventKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    action: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    current: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    total: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    elapsed_secs: Option<f64>,
+}
+
+/// Writes a single event, optionally associated with an action.
+pub fn write_event<W: Write>(
+    mut writer: W,
+    event: EventKind,
+    action: Option<&ActionInner>,
+    message: Option<fmt::Arguments<'_>>,
+) -> io::Result<()> {
+    let progress = action.map(|a| &a.progress);
+    let current = progress
+        .filter(|p| !matches!(p.kind, ProgressKind::NotApplicable))
+        .map(|p| p.current);
+    let total = progress.and_then(|p| match p.kind {
+        ProgressKind::Bounded { total } => Some(total),
+        _ => None,
+    });
+
+    let event = Event {
+        time: humantime::format_rfc3339_millis(SystemTime::now()).to_string(),
+        event,
+        action: action.map(|a| a.name.as_str()),
+        message: message.map(|m| m.to_string()),
+        current,
+        total,
+        elapsed_secs: action.map(|a| a.start.instant.elapsed().as_secs_f64()),
+    };
+
+    serde_json::to_writer(&mut writer, &event)?;
+    writeln!(writer)
+}
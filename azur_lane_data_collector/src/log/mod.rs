@@ -4,6 +4,7 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::{Instant, SystemTime};
 
 mod buf;
+mod json;
 mod write;
 
 /// Creates an action builder with the given label.
@@ -20,18 +21,53 @@ macro_rules! info {
     };
 }
 
+pub(crate) use action;
+pub(crate) use info;
 pub(crate) use write::ActionWrite;
-pub(crate) use {action, info};
 
 /// When false, uses simplified output.
 static USE_ANSI: AtomicBool = AtomicBool::new(false);
 
+/// When true, only prints finished actions and info messages.
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// When true, prints JSON events instead of text.
+static USE_JSON: AtomicBool = AtomicBool::new(false);
+
+/// The format of the terminal output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum LogFormat {
+    /// Human-readable text, with progress updated in-place if supported.
+    Text,
+    /// One JSON object per line for every event.
+    Json,
+}
+
 /// Sets whether colors are printed.
 pub fn use_color(force: Option<bool>) {
-    let value = force.unwrap_or_else(|| utils::term::supports_ansi_escapes(&io::stderr()));
+    let value = !USE_JSON.load(Ordering::Relaxed)
+        && force.unwrap_or_else(|| utils::term::supports_ansi_escapes(&io::stderr()));
     USE_ANSI.store(value, Ordering::Relaxed);
 }
 
+/// Sets whether action starts and progress updates are suppressed.
+pub fn use_quiet(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+}
+
+/// Sets the output format. Call this before [`use_color`].
+pub fn use_format(format: LogFormat) {
+    USE_JSON.store(format == LogFormat::Json, Ordering::Relaxed);
+}
+
+fn is_quiet() -> bool {
+    QUIET.load(Ordering::Relaxed)
+}
+
+fn is_json() -> bool {
+    USE_JSON.load(Ordering::Relaxed)
+}
+
 fn lock_output() -> impl io::Write {
     buf::buf_stderr()
 }
@@ -63,7 +99,16 @@ impl fmt::Display for Ansi {
 
 #[doc(hidden)]
 pub fn __info(args: fmt::Arguments<'_>) {
-    ioerr(writeln_args(lock_output(), args));
+    if is_json() {
+        ioerr(json::write_event(
+            lock_output(),
+            json::EventKind::Info,
+            None,
+            Some(args),
+        ));
+    } else {
+        ioerr(writeln_args(lock_output(), args));
+    }
 }
 
 fn writeln_args<W: io::Write>(mut writer: W, args: fmt::Arguments<'_>) -> io::Result<()> {
@@ -166,11 +211,27 @@ struct ActionInner {
 
 impl ActionInner {
     fn print_init(&self) -> io::Result<()> {
+        if is_quiet() {
+            return Ok(());
+        }
+
         let mut out = lock_output();
-        writeln!(out, "{self}")
+        if is_json() {
+            json::write_event(out, json::EventKind::Start, Some(self), None)
+        } else {
+            writeln!(out, "{self}")
+        }
     }
 
     fn print_update(&self) -> io::Result<()> {
+        if is_quiet() {
+            return Ok(());
+        }
+
+        if is_json() {
+            return json::write_event(lock_output(), json::EventKind::Progress, Some(self), None);
+        }
+
         only_ansi(|| {
             let mut out = lock_output();
             writeln!(out, "{UNDO_LINE}{self}")
@@ -179,7 +240,9 @@ impl ActionInner {
 
     fn print_info(&self, args: fmt::Arguments<'_>) -> io::Result<()> {
         let mut out = lock_output();
-        if USE_ANSI.load(Ordering::Relaxed) {
+        if is_json() {
+            json::write_event(out, json::EventKind::Info, Some(self), Some(args))
+        } else if USE_ANSI.load(Ordering::Relaxed) && !is_quiet() {
             write!(out, "{UNDO_LINE}")?;
             writeln_args(&mut out, args)?;
             writeln!(out, "{self}")
@@ -190,7 +253,14 @@ impl ActionInner {
 
     fn finish(&self) -> io::Result<()> {
         let mut out = lock_output();
-        writeln!(out, "{UNDO_LINE}{self} {DONE_STYLE}Done!{RESET}")
+        if is_json() {
+            json::write_event(out, json::EventKind::Finish, Some(self), None)
+        } else if is_quiet() {
+            // nothing to replace since the start wasn't printed
+            writeln!(out, "{self} {DONE_STYLE}Done!{RESET}")
+        } else {
+            writeln!(out, "{UNDO_LINE}{self} {DONE_STYLE}Done!{RESET}")
+        }
     }
 }
 
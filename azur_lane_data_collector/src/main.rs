@@ -1,26 +1,41 @@
 use std::collections::HashMap;
 use std::io::Write;
+use std::num::NonZeroUsize;
 use std::path::Path;
-use std::{fs, io};
+use std::sync::mpsc;
+use std::{fs, io, panic, thread};
 
+use azur_lane::commission::*;
+use azur_lane::data_file::{DataFileKind, DataFormat};
 use azur_lane::equip::*;
 use azur_lane::secretary::*;
 use azur_lane::ship::*;
-use azur_lane::{juustagram, DefinitionData};
+use azur_lane::stage::*;
+use azur_lane::{juustagram, DefinitionData, Locale};
 use clap::Parser;
 use mlua::prelude::*;
 
+mod cache;
+mod check;
 mod convert_al;
+mod diff;
 mod enhance;
+mod lenient;
 mod log;
 mod macros;
 mod model;
 mod parse;
+mod schema;
+mod watch;
 
 use model::*;
 
 #[derive(Debug, Parser)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// The path that the game scripts live in.
     ///
     /// This is the directory that contains, among others, `config.lua`.
@@ -39,13 +54,94 @@ struct Cli {
     /// The path that holds the game assets.
     ///
     /// This essentially points to the game's `AssetBundles` directory.
-    /// Currently, only chibis (`shipmodels`) are loaded.
+    /// By default, only chibis (`shipmodels`) are loaded.
     ///
     /// If not specified, no resources will be loaded.
     #[arg(long)]
     assets: Option<String>,
 
+    /// Also extract full skin paintings (`painting`) from the assets.
+    ///
+    /// Has no effect unless `--assets` is also specified.
+    #[arg(long)]
+    paintings: bool,
+
+    /// Also extract alternative expressions (`paintingface`) from the assets.
+    ///
+    /// The available expressions are recorded in the data regardless.
+    /// Has no effect unless `--assets` is also specified.
+    #[arg(long)]
+    faces: bool,
+
+    /// Also extract voice line audio (`cue`) from the assets.
+    ///
+    /// Has no effect unless `--assets` is also specified.
+    #[arg(long)]
+    voices: bool,
+
+    /// Also extract equipment (`equips`) and skill (`skillicon`) icons from
+    /// the assets.
+    ///
+    /// Has no effect unless `--assets` is also specified.
+    #[arg(long)]
+    icons: bool,
+
+    /// The format of the output data file.
+    #[arg(long, value_enum, default_value_t = OutputFormat::Json)]
+    format: OutputFormat,
+
+    /// Compress the output data file with gzip.
+    #[arg(long)]
+    compress: bool,
+
+    /// The path of a TOML config file with additional data overrides.
+    ///
+    /// Its entries are applied on top of the config embedded in the program,
+    /// so corrections to the data don't require rebuilding the collector.
+    #[arg(long)]
+    config: Option<String>,
+
+    /// The path of the incremental extraction cache file.
+    ///
+    /// If specified, ships, equipment, and augments whose source data is
+    /// unchanged since the last run are reused from this file instead of
+    /// being rebuilt.
+    #[arg(long)]
+    cache: Option<String>,
+
+    /// The amount of threads used to build ships.
+    ///
+    /// Every thread loads its own copy of the game scripts, so more threads
+    /// also need more memory. Defaults to the amount of available cores.
+    #[arg(short, long)]
+    jobs: Option<NonZeroUsize>,
+
+    /// Verify the integrity of the built data before writing it.
+    ///
+    /// If any issues are found, they are reported, nothing is written, and
+    /// the program exits with an error.
+    #[arg(long)]
+    check: bool,
+
+    /// Skip entries that fail to build instead of aborting.
+    ///
+    /// The errors are written to `errors.json` in the output directory, and
+    /// the data file only contains the entries that were built successfully.
+    #[arg(long)]
+    lenient: bool,
+
+    /// Keep running and repeat the extraction whenever files in the inputs
+    /// change.
+    ///
+    /// Entries whose source data is unchanged are reused between runs, even
+    /// if `--cache` isn't specified. Errors don't stop the program; instead,
+    /// it waits for the next change.
+    #[arg(long)]
+    watch: bool,
+
     /// Minimize the output JSON file.
+    ///
+    /// Has no effect unless the format is `json`.
     #[arg(short, long)]
     minimize: bool,
 
@@ -53,12 +149,43 @@ struct Cli {
     ///
     /// Auto-detection is performed, but in case it is wrong, you may use this
     /// to override the default.
-    #[arg(long)]
+    #[arg(long, global = true)]
     color: Option<bool>,
+
+    /// Only print finished steps and messages, without progress updates.
+    #[arg(short, long, global = true)]
+    quiet: bool,
+
+    /// The format of the terminal output.
+    ///
+    /// `json` prints every message and progress update as a JSON object on
+    /// its own line, which is useful when the output is processed by other
+    /// tools.
+    #[arg(long, value_enum, global = true, default_value_t = log::LogFormat::Text)]
+    log_format: log::LogFormat,
+}
+
+#[derive(Debug, clap::Subcommand)]
+enum Command {
+    /// Compare two data files and print the added, removed, and changed
+    /// entries.
+    Diff(diff::DiffArgs),
+    /// Export the JSON schema of the data file.
+    Schema(schema::SchemaArgs),
+}
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+enum OutputFormat {
+    /// Human-readable JSON.
+    Json,
+    /// Compact binary data.
+    Steph,
 }
 
 fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
+    log::use_format(cli.log_format);
+    log::use_quiet(cli.quiet);
     log::use_color(cli.color);
 
     match option_env!("GIT_HASH") {
@@ -66,37 +193,107 @@ fn main() -> anyhow::Result<()> {
         None => log::info!("Azur Lane Data Collector [Unknown Commit]"),
     };
 
-    let out_data = {
+    match &cli.command {
+        Some(Command::Diff(args)) => return diff::run(args),
+        Some(Command::Schema(args)) => return schema::run(args),
+        None => {},
+    }
+
+    if let Some(path) = cli.config.as_deref() {
+        init_config(path)?;
+        log::info!("Using config `{path}`.");
+    }
+
+    let mut cache = match cli.cache.as_deref() {
+        Some(path) => cache::Cache::load(path),
+        // without a cache file, watch mode still reuses entries between runs
+        None if cli.watch => cache::Cache::in_memory(),
+        None => cache::Cache::default(),
+    };
+
+    if cli.watch {
+        watch::run(&cli.inputs, || extract(&cli, &mut cache))
+    } else {
+        extract(&cli, &mut cache)
+    }
+}
+
+/// Runs the extraction with all steps requested by the arguments.
+fn extract(cli: &Cli, cache: &mut cache::Cache) -> anyhow::Result<()> {
+    let mut errors = lenient::ErrorReport::new(cli.lenient);
+    let mut out_data = {
+        let jobs = cli
+            .jobs
+            .or_else(|| thread::available_parallelism().ok())
+            .map_or(1, NonZeroUsize::get);
+
         // Expect at least 1 input
-        let mut out_data = load_definition(&cli.inputs[0])?;
+        let mut out_data = load_definition(&cli.inputs[0], jobs, cache, &mut errors)?;
         for input in cli.inputs.iter().skip(1) {
-            let next = load_definition(input)?;
+            let next = load_definition(input, jobs, cache, &mut errors)?;
             merge_out_data(&mut out_data, next);
         }
 
+        match cli.cache.as_deref() {
+            Some(path) => cache.save(path)?,
+            None => cache.end_run(),
+        }
+
         out_data
     };
 
+    if cli.check {
+        let action = log::action!("Checking data.").start();
+        let issues = check::check(&out_data);
+        action.finish();
+
+        for issue in &issues {
+            log::info!("{issue}");
+        }
+
+        anyhow::ensure!(
+            issues.is_empty(),
+            "found {} issue(s) in the data",
+            issues.len()
+        );
+        log::info!("No issues found.");
+    }
+
     let out_dir = cli.out.as_deref().unwrap_or("azur_lane_data");
+    if let Some(assets) = cli.assets.as_deref() {
+        // The available faces are part of the data, so load them before writing it.
+        load_faces(out_dir, assets, &mut out_data, cli.faces)?;
+    }
+
     {
-        let action = log::action!("Writing `main.json`.")
+        let format = match cli.format {
+            OutputFormat::Json => DataFormat::Json,
+            OutputFormat::Steph => DataFormat::Steph,
+        };
+
+        let kind = DataFileKind::new(format, cli.compress);
+        let action = log::action!("Writing `{}`.", kind.file_name())
             .unbounded()
             .suffix(" KB")
             .start();
 
         fs::create_dir_all(out_dir)?;
-        let file = fs::File::create(Path::new(out_dir).join("main.json"))?;
+        let file = fs::File::create(Path::new(out_dir).join(kind.file_name()))?;
         let file = io::BufWriter::new(file);
         let mut action = log::ActionWrite::new(action, file);
-        if cli.minimize {
-            serde_json::to_writer(&mut action, &out_data)?;
-        } else {
-            serde_json::to_writer_pretty(&mut action, &out_data)?;
-        }
+        out_data.save(&mut action, kind, !cli.minimize)?;
 
         action.finish();
     }
 
+    if errors.is_enabled() {
+        errors.save(&Path::new(out_dir).join("errors.json"))?;
+        log::info!(
+            "{} entry(s) failed to build. See `errors.json` for details.",
+            errors.error_count()
+        );
+    }
+
     if let Some(assets) = cli.assets.as_deref() {
         // Extract and save chibis for all skins.
         fs::create_dir_all(Path::new(out_dir).join("chibi"))?;
@@ -126,33 +323,258 @@ fn main() -> anyhow::Result<()> {
 
         action.finish();
         log::info!("{new_count} new chibi(s).");
+
+        if cli.paintings {
+            extract_paintings(out_dir, assets, &out_data)?;
+        }
+
+        if cli.voices {
+            extract_voices(out_dir, assets, &out_data)?;
+        }
+
+        if cli.icons {
+            extract_icons(out_dir, assets, &out_data)?;
+        }
     }
 
     Ok(())
 }
 
-fn load_definition(input: &str) -> anyhow::Result<DefinitionData> {
+fn load_faces(
+    out_dir: &str,
+    assets: &str,
+    out_data: &mut DefinitionData,
+    extract: bool,
+) -> anyhow::Result<()> {
+    // Record the faces available for all skins and, if requested, save them.
+    // Faces are small, so like chibis, they are decoded every time.
+    let total_count = out_data.ships.iter().map(|s| s.skins.len()).sum();
+    let mut action = log::action!("Loading faces.")
+        .bounded_total(total_count)
+        .start();
+
+    let mut new_count = 0usize;
+
+    for skin in out_data.ships.iter_mut().flat_map(|s| s.skins.iter_mut()) {
+        let faces = parse::image::load_face_images(assets, &skin.image_key, extract)?;
+        if extract && !faces.is_empty() {
+            fs::create_dir_all(utils::join_path!(out_dir, "face", &skin.image_key))?;
+        }
+
+        for face in faces {
+            if let Some(data) = face.data {
+                let path = utils::join_path!(out_dir, "face", &skin.image_key, &face.name; "webp");
+                if let Ok(mut f) = fs::File::create_new(path) {
+                    new_count += 1;
+
+                    f.write_all(&data)?;
+                }
+            }
+
+            skin.faces.push(face.name);
+        }
+
+        action.inc_amount();
+    }
+
+    action.finish();
+    if extract {
+        log::info!("{new_count} new face(s).");
+    }
+
+    Ok(())
+}
+
+fn extract_paintings(out_dir: &str, assets: &str, out_data: &DefinitionData) -> anyhow::Result<()> {
+    // Extract and save full paintings for all skins.
+    // Unlike chibis, these are expensive to reassemble, so skip existing ones.
+    fs::create_dir_all(Path::new(out_dir).join("painting"))?;
+
+    let total_count = out_data.ships.iter().map(|s| s.skins.len()).sum();
+    let mut action = log::action!("Extracting paintings.")
+        .bounded_total(total_count)
+        .start();
+
+    let mut new_count = 0usize;
+
+    for skin in out_data.ships.iter().flat_map(|s| s.skins.iter()) {
+        let path = utils::join_path!(out_dir, "painting", &skin.image_key; "webp");
+        if !path.exists() {
+            if let Some(image) =
+                parse::image::load_painting_image(&action, assets, &skin.image_key)?
+            {
+                new_count += 1;
+
+                fs::write(path, image)?;
+            }
+        }
+
+        action.inc_amount();
+    }
+
+    action.finish();
+    log::info!("{new_count} new painting(s).");
+    Ok(())
+}
+
+fn extract_voices(out_dir: &str, assets: &str, out_data: &DefinitionData) -> anyhow::Result<()> {
+    // Extract and save voice clips for all voice keys.
+    // Skins may share voice keys, so deduplicate those first.
+    let mut voice_keys: Vec<&str> = out_data
+        .ships
+        .iter()
+        .flat_map(|s| s.skins.iter())
+        .flat_map(|s| [Some(&s.words), s.words_extra.as_deref()])
+        .flatten()
+        .filter_map(|w| w.voice_key.as_deref())
+        .collect();
+
+    voice_keys.sort_unstable();
+    voice_keys.dedup();
+
+    let mut action = log::action!("Extracting voices.")
+        .bounded_total(voice_keys.len())
+        .start();
+
+    let mut new_count = 0usize;
+
+    for voice_key in voice_keys {
+        let clips = parse::audio::load_voice_clips(&action, assets, voice_key)?;
+        if !clips.is_empty() {
+            fs::create_dir_all(utils::join_path!(out_dir, "voice", voice_key))?;
+        }
+
+        for (name, data) in clips {
+            let path = utils::join_path!(out_dir, "voice", voice_key, &name; "fsb");
+            if let Ok(mut f) = fs::File::create_new(path) {
+                new_count += 1;
+
+                f.write_all(&data)?;
+            }
+        }
+
+        action.inc_amount();
+    }
+
+    action.finish();
+    log::info!("{new_count} new voice clip(s).");
+    Ok(())
+}
+
+fn extract_icons(out_dir: &str, assets: &str, out_data: &DefinitionData) -> anyhow::Result<()> {
+    // Extract and save the icons for all equipment and skills.
+    // Many entries share icons, so deduplicate those first.
+    let mut equip_icons: Vec<&str> = out_data
+        .equips
+        .iter()
+        .map(|e| e.icon.as_str())
+        .filter(|i| !i.is_empty())
+        .collect();
+
+    let mut skill_icons: Vec<&str> = out_data
+        .ships
+        .iter()
+        .flat_map(|s| std::iter::once(s).chain(&s.retrofits))
+        .flat_map(|s| &s.skills)
+        .chain(out_data.equips.iter().flat_map(|e| &e.skills))
+        .filter_map(|s| s.icon.as_deref())
+        .collect();
+
+    equip_icons.sort_unstable();
+    equip_icons.dedup();
+    skill_icons.sort_unstable();
+    skill_icons.dedup();
+
+    let mut action = log::action!("Extracting icons.")
+        .bounded_total(equip_icons.len() + skill_icons.len())
+        .start();
+
+    let mut new_count = 0usize;
+
+    for (kind, dir, icons) in [
+        ("equips", "equip_icon", equip_icons),
+        ("skillicon", "skill_icon", skill_icons),
+    ] {
+        fs::create_dir_all(Path::new(out_dir).join(dir))?;
+
+        for icon in icons {
+            let path = utils::join_path!(out_dir, dir, icon; "webp");
+            if !path.exists() {
+                if let Some(image) = parse::image::load_icon_image(&action, assets, kind, icon)? {
+                    new_count += 1;
+
+                    fs::write(path, image)?;
+                }
+            }
+
+            action.inc_amount();
+        }
+    }
+
+    action.finish();
+    log::info!("{new_count} new icon(s).");
+    Ok(())
+}
+
+fn load_definition(
+    input: &str,
+    jobs: usize,
+    cache: &mut cache::Cache,
+    errors: &mut lenient::ErrorReport,
+) -> anyhow::Result<DefinitionData> {
+    errors.set_input(input);
+
     let lua = init_lua(input)?;
     let pg: LuaTable = lua.globals().get("pg").context("global pg")?;
+    let locale = load_locale(&lua)?;
+
+    let enabled = cache.is_enabled();
+    let cache = cache.input(input);
+    let ships = load_ships(&lua, &pg, input, jobs, &mut cache.ships, errors)?;
+    let equips = load_equips(&lua, &pg, &mut cache.equips, errors)?;
+    let augments = load_augments(&lua, &pg, &mut cache.augments, errors)?;
+
+    if enabled {
+        log::info!(
+            "Reused {} ship(s), {} equip(s), and {} augment(s) from the cache.",
+            cache.ships.hits(),
+            cache.equips.hits(),
+            cache.augments.hits(),
+        );
+    }
 
-    let ships = load_ships(&lua, &pg)?;
-    let equips = load_equips(&lua, &pg)?;
-    let augments = load_augments(&lua, &pg)?;
-    let juustagram_chats = load_juustagram_chats(&lua, &pg)?;
-    let special_secretaries = load_special_secretaries(&lua, &pg)?;
+    let juustagram_chats = load_juustagram_chats(&lua, &pg, errors)?;
+    let special_secretaries = load_special_secretaries(&lua, &pg, errors)?;
+    let commissions = load_commissions(&lua, &pg, errors)?;
+    let stages = load_stages(&lua, &pg, errors)?;
+    let ship_levels = load_ship_levels(&pg)?;
 
-    Ok(DefinitionData {
+    let mut data = DefinitionData {
         ships,
         equips,
         augments,
         juustagram_chats,
         special_secretaries,
-    })
+        commissions,
+        stages,
+        ship_levels,
+    };
+
+    if let Some(locale) = locale {
+        add_localized_names(&mut data, locale);
+    }
+
+    Ok(data)
 }
 
 fn init_lua(input: &str) -> anyhow::Result<Lua> {
     let action = log::action!("Initializing Lua for: `{input}`").start();
+    let lua = create_lua(input)?;
+    action.finish();
+    Ok(lua)
+}
 
+fn create_lua(input: &str) -> LuaResult<Lua> {
     let lua = Lua::new();
 
     lua.globals().raw_set("AZUR_LANE_DATA_PATH", input)?;
@@ -161,66 +583,54 @@ fn init_lua(input: &str) -> anyhow::Result<Lua> {
         .set_mode(mlua::ChunkMode::Text)
         .exec()?;
 
-    action.finish();
     Ok(lua)
 }
 
-fn load_ships(lua: &Lua, pg: &LuaTable) -> anyhow::Result<Vec<ShipData>> {
-    let ship_data_template: LuaTable = pg
-        .get("ship_data_template")
-        .context("global pg.ship_data_template")?;
-    let ship_data_template_all: LuaTable = ship_data_template
-        .get("all")
-        .context("global pg.ship_data_template.all")?;
-    let ship_data_statistics: LuaTable = pg
-        .get("ship_data_statistics")
-        .context("global pg.ship_data_statistics")?;
+fn load_locale(lua: &Lua) -> anyhow::Result<Option<Locale>> {
+    let platform_code: Option<u32> = lua
+        .globals()
+        .get("PLATFORM_CODE")
+        .context("global PLATFORM_CODE")?;
 
-    // Normal enhancement data (may be present even if not used for that ship):
-    let ship_data_strengthen: LuaTable = pg
-        .get("ship_data_strengthen")
-        .context("global pg.ship_data_strengthen")?;
+    let locale = platform_code.and_then(convert_al::to_locale);
+    match locale {
+        Some(locale) => log::info!("Detected locale: {}", locale.name()),
+        None => log::info!("Unknown locale. Localized names will not be included."),
+    }
 
-    // Blueprint/Research ship data:
-    let ship_data_blueprint: LuaTable = pg
-        .get("ship_data_blueprint")
-        .context("global pg.ship_data_blueprint")?;
-    let ship_strengthen_blueprint: LuaTable = pg
-        .get("ship_strengthen_blueprint")
-        .context("global pg.ship_strengthen_blueprint")?;
+    Ok(locale)
+}
 
-    // META ship data:
-    let ship_strengthen_meta: LuaTable = pg
-        .get("ship_strengthen_meta")
-        .context("global pg.ship_strengthen_meta")?;
-    let ship_meta_repair: LuaTable = pg
-        .get("ship_meta_repair")
-        .context("global pg.ship_meta_repair")?;
-    let ship_meta_repair_effect: LuaTable = pg
-        .get("ship_meta_repair_effect")
-        .context("global pg.ship_meta_repair_effect")?;
+fn add_localized_names(data: &mut DefinitionData, locale: Locale) {
+    fn add_ship_names(ship: &mut ShipData, locale: Locale) {
+        ship.names.set(locale, ship.name.clone());
+        for retrofit in &mut ship.retrofits {
+            add_ship_names(retrofit, locale);
+        }
+    }
 
-    // Retrofit data:
-    let ship_data_trans: LuaTable = pg
-        .get("ship_data_trans")
-        .context("global pg.ship_data_trans")?;
-    let transform_data_template: LuaTable = pg
-        .get("transform_data_template")
-        .context("global pg.transform_data_template")?;
+    for ship in &mut data.ships {
+        add_ship_names(ship, locale);
+    }
 
-    // Skin/word data:
-    let ship_skin_template: LuaTable = pg
-        .get("ship_skin_template")
-        .context("global pg.ship_skin_template")?;
-    let ship_skin_template_get_id_list_by_ship_group: LuaTable = ship_skin_template
-        .get("get_id_list_by_ship_group")
-        .context("global pg.ship_skin_template.get_id_list_by_ship_group")?;
-    let ship_skin_words: LuaTable = pg
-        .get("ship_skin_words")
-        .context("global pg.ship_skin_words")?;
-    let ship_skin_words_extra: LuaTable = pg
-        .get("ship_skin_words_extra")
-        .context("global pg.ship_skin_words_extra")?;
+    for equip in &mut data.equips {
+        equip.names.set(locale, equip.name.clone());
+    }
+
+    for augment in &mut data.augments {
+        augment.names.set(locale, augment.name.clone());
+    }
+}
+
+fn load_ships(
+    lua: &Lua,
+    pg: &LuaTable,
+    input: &str,
+    jobs: usize,
+    cache: &mut cache::EntryCache<ShipData>,
+    errors: &mut lenient::ErrorReport,
+) -> anyhow::Result<Vec<ShipData>> {
+    let tables = ShipTables::new(pg)?;
 
     let mut action = log::action!("Finding ship groups.")
         .unbounded()
@@ -228,12 +638,13 @@ fn load_ships(lua: &Lua, pg: &LuaTable) -> anyhow::Result<Vec<ShipData>> {
         .start();
 
     let mut groups = HashMap::new();
-    ship_data_template_all.for_each(|_: u32, id: u32| {
+    tables.ship_data_template_all.for_each(|_: u32, id: u32| {
         if (900000..=900999).contains(&id) {
             return Ok(());
         }
 
-        let template: LuaTable = ship_data_template
+        let template: LuaTable = tables
+            .ship_data_template
             .get(id)
             .with_context(context!("ship_data_template with id {id}"))?;
         let group_id: u32 = template
@@ -258,11 +669,290 @@ fn load_ships(lua: &Lua, pg: &LuaTable) -> anyhow::Result<Vec<ShipData>> {
     let total = action.amount();
     action.finish();
 
-    let make_ship_set = |id: u32| -> LuaResult<ShipSet<'_>> {
-        let template: LuaTable = ship_data_template
+    let mut groups: Vec<ShipGroup> = groups.into_values().collect();
+    groups.sort_unstable_by_key(|g| g.id);
+
+    let mut action = log::action!("Building ship groups.")
+        .bounded_total(total)
+        .start();
+
+    let jobs = jobs.min(groups.len());
+    let mut ships = if jobs <= 1 {
+        build_ship_groups(lua, &tables, groups, cache, errors, || action.inc_amount())?
+    } else {
+        build_ship_groups_parallel(input, jobs, groups, cache, errors, &mut action)?
+    };
+
+    action.finish();
+
+    ships.sort_unstable_by_key(|t| t.group_id);
+    Ok(ships)
+}
+
+/// Builds the ship groups on multiple threads.
+///
+/// Lua states cannot be used from multiple threads at once, so every worker
+/// initializes its own state for the same input and builds a share of the
+/// groups with it.
+fn build_ship_groups_parallel(
+    input: &str,
+    jobs: usize,
+    groups: Vec<ShipGroup>,
+    cache: &mut cache::EntryCache<ShipData>,
+    errors: &mut lenient::ErrorReport,
+    action: &mut log::Action,
+) -> anyhow::Result<Vec<ShipData>> {
+    // Distribute the groups round-robin so every worker gets a similar mix of
+    // older and newer ships.
+    let mut chunks: Vec<Vec<ShipGroup>> = (0..jobs).map(|_| Vec::new()).collect();
+    for (index, group) in groups.into_iter().enumerate() {
+        chunks[index % jobs].push(group);
+    }
+
+    let (tx, rx) = mpsc::channel();
+    thread::scope(|scope| {
+        let workers: Vec<_> = chunks
+            .into_iter()
+            .map(|chunk| {
+                let mut cache = cache.split_off(chunk.iter().map(|g| g.id));
+                let mut errors = errors.fork();
+                let tx = tx.clone();
+                scope.spawn(move || {
+                    let lua = create_lua(input)?;
+                    let pg: LuaTable = lua.globals().get("pg").context("global pg")?;
+                    let tables = ShipTables::new(&pg)?;
+                    let ships =
+                        build_ship_groups(&lua, &tables, chunk, &mut cache, &mut errors, || {
+                            _ = tx.send(());
+                        })?;
+
+                    anyhow::Ok((ships, cache, errors))
+                })
+            })
+            .collect();
+
+        // Each worker holds a sender, so this ends once all of them are done.
+        drop(tx);
+        for () in rx {
+            action.inc_amount();
+        }
+
+        let mut ships = Vec::new();
+        for worker in workers {
+            let (part, part_cache, part_errors) =
+                worker.join().unwrap_or_else(|p| panic::resume_unwind(p))?;
+            ships.extend(part);
+            cache.merge(part_cache);
+            errors.merge(part_errors);
+        }
+
+        Ok(ships)
+    })
+}
+
+fn build_ship_groups(
+    lua: &Lua,
+    tables: &ShipTables,
+    groups: Vec<ShipGroup>,
+    cache: &mut cache::EntryCache<ShipData>,
+    errors: &mut lenient::ErrorReport,
+    mut on_built: impl FnMut(),
+) -> anyhow::Result<Vec<ShipData>> {
+    groups
+        .into_iter()
+        .map(|group| -> anyhow::Result<Option<ShipData>> {
+            let id = group.id;
+            let ship = build_cached_ship_group(lua, tables, group, cache);
+
+            on_built();
+            errors.handle("ship", id, ship)
+        })
+        .filter_map(Result::transpose)
+        .collect()
+}
+
+fn build_cached_ship_group(
+    lua: &Lua,
+    tables: &ShipTables,
+    mut group: ShipGroup,
+    cache: &mut cache::EntryCache<ShipData>,
+) -> anyhow::Result<ShipData> {
+    // The member and skin lists aren't tracked by the cache, so they
+    // need to be part of the seed instead.
+    group.members.sort_unstable();
+    let skin_ids: Vec<u32> = tables
+        .ship_skin_template_get_id_list_by_ship_group
+        .get(group.id)
+        .with_context(context!("skin ids for ship with id {}", group.id))?;
+
+    let seed = cache::stable_hash((&group.members, &skin_ids));
+    cache.get_or_build(lua, group.id, seed, || {
+        build_ship_group(lua, tables, group, skin_ids)
+    })
+}
+
+fn build_ship_group(
+    lua: &Lua,
+    tables: &ShipTables,
+    group: ShipGroup,
+    skin_ids: Vec<u32>,
+) -> anyhow::Result<ShipData> {
+    let members = group
+        .members
+        .into_iter()
+        .map(|id| tables.make_ship_set(id))
+        .collect::<LuaResult<Vec<_>>>()?;
+
+    let mlb_max_id = group.id * 10 + 4;
+    let raw_mlb = members
+        .iter()
+        .filter(|t| t.id <= mlb_max_id)
+        .max_by_key(|t| t.id)
+        .ok_or_else(|| {
+            LuaError::external(DataError::NoMlb)
+                .context(format!("no mlb for ship with id {}", group.id))
+        })?;
+
+    let raw_retrofits: Vec<&ShipSet<'_>> = members.iter().filter(|t| t.id > raw_mlb.id).collect();
+
+    let raw_skins = skin_ids
+        .into_iter()
+        .map(|skin_id| tables.make_skin_set(group.id, skin_id))
+        .collect::<LuaResult<Vec<_>>>()?;
+
+    let mut mlb = parse::ship::load_ship_data(lua, raw_mlb)?;
+    if let Some(name_override) = config().name_overrides.get(&mlb.group_id) {
+        mlb.name.clone_from(name_override);
+    }
+
+    let mut raw_breakouts: Vec<&ShipSet<'_>> =
+        members.iter().filter(|t| t.id < raw_mlb.id).collect();
+    raw_breakouts.sort_unstable_by_key(|t| t.id);
+    mlb.limit_break_costs = parse::ship::load_limit_break_costs(&raw_breakouts)?;
+
+    if let Some(retrofit_data) = &raw_mlb.retrofit_data {
+        for retrofit_set in raw_retrofits {
+            let mut retrofit = parse::ship::load_ship_data(lua, retrofit_set)?;
+            enhance::retrofit::apply_retrofit(lua, &mut retrofit, retrofit_data)?;
+
+            fix_up_retrofitted_data(&mut retrofit, retrofit_set)?;
+            mlb.retrofits.push(retrofit);
+        }
+
+        if mlb.retrofits.is_empty() {
+            let mut retrofit = mlb.clone();
+            enhance::retrofit::apply_retrofit(lua, &mut retrofit, retrofit_data)?;
+
+            fix_up_retrofitted_data(&mut retrofit, raw_mlb)?;
+            mlb.retrofits.push(retrofit);
+        }
+    }
+
+    for raw_skin in raw_skins {
+        mlb.skins.push(parse::skin::load_skin(&raw_skin)?);
+    }
+
+    Ok(mlb)
+}
+
+/// The game data tables needed to build ships.
+struct ShipTables {
+    ship_data_template: LuaTable,
+    ship_data_template_all: LuaTable,
+    ship_data_statistics: LuaTable,
+
+    // Normal enhancement data (may be present even if not used for that ship):
+    ship_data_strengthen: LuaTable,
+
+    // Blueprint/Research ship data:
+    ship_data_blueprint: LuaTable,
+    ship_strengthen_blueprint: LuaTable,
+
+    // META ship data:
+    ship_strengthen_meta: LuaTable,
+    ship_meta_repair: LuaTable,
+    ship_meta_repair_effect: LuaTable,
+
+    // Limit break data:
+    ship_data_breakout: LuaTable,
+
+    // Retrofit data:
+    ship_data_trans: LuaTable,
+    transform_data_template: LuaTable,
+
+    // Skin/word data:
+    ship_skin_template: LuaTable,
+    ship_skin_template_get_id_list_by_ship_group: LuaTable,
+    ship_skin_words: LuaTable,
+    ship_skin_words_extra: LuaTable,
+}
+
+impl ShipTables {
+    fn new(pg: &LuaTable) -> anyhow::Result<Self> {
+        let ship_data_template: LuaTable = pg
+            .get("ship_data_template")
+            .context("global pg.ship_data_template")?;
+        let ship_data_template_all: LuaTable = ship_data_template
+            .get("all")
+            .context("global pg.ship_data_template.all")?;
+        let ship_skin_template: LuaTable = pg
+            .get("ship_skin_template")
+            .context("global pg.ship_skin_template")?;
+        let ship_skin_template_get_id_list_by_ship_group: LuaTable = ship_skin_template
+            .get("get_id_list_by_ship_group")
+            .context("global pg.ship_skin_template.get_id_list_by_ship_group")?;
+
+        Ok(Self {
+            ship_data_template,
+            ship_data_template_all,
+            ship_data_statistics: pg
+                .get("ship_data_statistics")
+                .context("global pg.ship_data_statistics")?,
+            ship_data_strengthen: pg
+                .get("ship_data_strengthen")
+                .context("global pg.ship_data_strengthen")?,
+            ship_data_blueprint: pg
+                .get("ship_data_blueprint")
+                .context("global pg.ship_data_blueprint")?,
+            ship_strengthen_blueprint: pg
+                .get("ship_strengthen_blueprint")
+                .context("global pg.ship_strengthen_blueprint")?,
+            ship_strengthen_meta: pg
+                .get("ship_strengthen_meta")
+                .context("global pg.ship_strengthen_meta")?,
+            ship_meta_repair: pg
+                .get("ship_meta_repair")
+                .context("global pg.ship_meta_repair")?,
+            ship_meta_repair_effect: pg
+                .get("ship_meta_repair_effect")
+                .context("global pg.ship_meta_repair_effect")?,
+            ship_data_breakout: pg
+                .get("ship_data_breakout")
+                .context("global pg.ship_data_breakout")?,
+            ship_data_trans: pg
+                .get("ship_data_trans")
+                .context("global pg.ship_data_trans")?,
+            transform_data_template: pg
+                .get("transform_data_template")
+                .context("global pg.transform_data_template")?,
+            ship_skin_template,
+            ship_skin_template_get_id_list_by_ship_group,
+            ship_skin_words: pg
+                .get("ship_skin_words")
+                .context("global pg.ship_skin_words")?,
+            ship_skin_words_extra: pg
+                .get("ship_skin_words_extra")
+                .context("global pg.ship_skin_words_extra")?,
+        })
+    }
+
+    fn make_ship_set(&self, id: u32) -> LuaResult<ShipSet<'_>> {
+        let template: LuaTable = self
+            .ship_data_template
             .get(id)
             .with_context(context!("!ship_data_template with id {id}"))?;
-        let statistics: LuaTable = ship_data_statistics
+        let statistics: LuaTable = self
+            .ship_data_statistics
             .get(id)
             .with_context(context!("ship_data_statistics with id {id}"))?;
 
@@ -273,143 +963,88 @@ fn load_ships(lua: &Lua, pg: &LuaTable) -> anyhow::Result<Vec<ShipData>> {
             .get("id")
             .with_context(context!("id of ship_data_template with id {id}"))?;
 
-        let enhance: Option<LuaTable> = ship_data_strengthen
+        let enhance: Option<LuaTable> = self
+            .ship_data_strengthen
             .get(strengthen_id)
             .with_context(context!("ship_data_strengthen with {id}"))?;
-        let blueprint: Option<LuaTable> = ship_data_blueprint
+        let blueprint: Option<LuaTable> = self
+            .ship_data_blueprint
             .get(strengthen_id)
             .with_context(context!("ship_data_blueprint with {id}"))?;
-        let meta: Option<LuaTable> = ship_strengthen_meta
+        let meta: Option<LuaTable> = self
+            .ship_strengthen_meta
             .get(strengthen_id)
             .with_context(context!("ship_strengthen_meta with {id}"))?;
 
         let strengthen = match (enhance, blueprint, meta) {
             (_, Some(data), _) => Strengthen::Blueprint(BlueprintStrengthen {
                 data,
-                effect_lookup: &ship_strengthen_blueprint,
+                effect_lookup: &self.ship_strengthen_blueprint,
             }),
             (_, _, Some(data)) => Strengthen::Meta(MetaStrengthen {
                 data,
-                repair_lookup: &ship_meta_repair,
-                repair_effect_lookup: &ship_meta_repair_effect,
+                repair_lookup: &self.ship_meta_repair,
+                repair_effect_lookup: &self.ship_meta_repair_effect,
             }),
             (Some(data), _, _) => Strengthen::Normal(data),
             _ => Err(LuaError::external(DataError::NoStrengthen))?,
         };
 
-        let retrofit: Option<LuaTable> = ship_data_trans
+        let retrofit: Option<LuaTable> = self
+            .ship_data_trans
             .get(strengthen_id)
             .with_context(context!("ship_data_trans with {id}"))?;
         let retrofit = retrofit.map(|r| Retrofit {
             data: r,
-            list_lookup: &transform_data_template,
+            list_lookup: &self.transform_data_template,
         });
 
+        let breakout: Option<LuaTable> = self
+            .ship_data_breakout
+            .get(id)
+            .with_context(context!("ship_data_breakout with {id}"))?;
+
         Ok(ShipSet {
             id,
             template,
             statistics,
             strengthen,
             retrofit_data: retrofit,
+            breakout,
         })
-    };
-
-    let mut action = log::action!("Building ship groups.")
-        .bounded_total(total)
-        .start();
-
-    let config = &*CONFIG;
-    let make_ship_from_group = |group: ShipGroup| {
-        let members = group
-            .members
-            .into_iter()
-            .map(make_ship_set)
-            .collect::<LuaResult<Vec<_>>>()?;
+    }
 
-        let mlb_max_id = group.id * 10 + 4;
-        let raw_mlb = members
-            .iter()
-            .filter(|t| t.id <= mlb_max_id)
-            .max_by_key(|t| t.id)
-            .ok_or_else(|| {
-                LuaError::external(DataError::NoMlb)
-                    .context(format!("no mlb for ship with id {}", group.id))
-            })?;
-
-        let raw_retrofits: Vec<&ShipSet<'_>> =
-            members.iter().filter(|t| t.id > raw_mlb.id).collect();
-
-        let make_skin = |skin_id| -> LuaResult<SkinSet> {
-            Ok(SkinSet {
+    fn make_skin_set(&self, group_id: u32, skin_id: u32) -> LuaResult<SkinSet> {
+        Ok(SkinSet {
+            skin_id,
+            template: self.ship_skin_template.get(skin_id).with_context(context!(
+                "skin template {} for ship {}",
                 skin_id,
-                template: ship_skin_template.get(skin_id).with_context(context!(
-                    "skin template {} for ship {}",
-                    skin_id,
-                    group.id
-                ))?,
-                words: ship_skin_words.get(skin_id).with_context(context!(
-                    "skin words {} for ship {}",
-                    skin_id,
-                    group.id
-                ))?,
-                words_extra: ship_skin_words_extra.get(skin_id).with_context(context!(
+                group_id
+            ))?,
+            words: self.ship_skin_words.get(skin_id).with_context(context!(
+                "skin words {} for ship {}",
+                skin_id,
+                group_id
+            ))?,
+            words_extra: self
+                .ship_skin_words_extra
+                .get(skin_id)
+                .with_context(context!(
                     "skin words extra {} for ship {}",
                     skin_id,
-                    group.id
+                    group_id
                 ))?,
-            })
-        };
-
-        let raw_skins = ship_skin_template_get_id_list_by_ship_group
-            .get::<Vec<u32>>(group.id)
-            .with_context(context!("skin ids for ship with id {}", group.id))?
-            .into_iter()
-            .map(make_skin)
-            .collect::<LuaResult<Vec<_>>>()?;
-
-        let mut mlb = parse::ship::load_ship_data(lua, raw_mlb)?;
-        if let Some(name_override) = config.name_overrides.get(&mlb.group_id) {
-            mlb.name.clone_from(name_override);
-        }
-
-        if let Some(retrofit_data) = &raw_mlb.retrofit_data {
-            for retrofit_set in raw_retrofits {
-                let mut retrofit = parse::ship::load_ship_data(lua, retrofit_set)?;
-                enhance::retrofit::apply_retrofit(lua, &mut retrofit, retrofit_data)?;
-
-                fix_up_retrofitted_data(&mut retrofit, retrofit_set)?;
-                mlb.retrofits.push(retrofit);
-            }
-
-            if mlb.retrofits.is_empty() {
-                let mut retrofit = mlb.clone();
-                enhance::retrofit::apply_retrofit(lua, &mut retrofit, retrofit_data)?;
-
-                fix_up_retrofitted_data(&mut retrofit, raw_mlb)?;
-                mlb.retrofits.push(retrofit);
-            }
-        }
-
-        for raw_skin in raw_skins {
-            mlb.skins.push(parse::skin::load_skin(&raw_skin)?);
-        }
-
-        action.inc_amount();
-        Ok(mlb)
-    };
-
-    let mut ships = groups
-        .into_values()
-        .map(make_ship_from_group)
-        .collect::<anyhow::Result<Vec<_>>>()?;
-
-    action.finish();
-
-    ships.sort_unstable_by_key(|t| t.group_id);
-    Ok(ships)
+        })
+    }
 }
 
-fn load_equips(lua: &Lua, pg: &LuaTable) -> anyhow::Result<Vec<Equip>> {
+fn load_equips(
+    lua: &Lua,
+    pg: &LuaTable,
+    cache: &mut cache::EntryCache<Equip>,
+    errors: &mut lenient::ErrorReport,
+) -> anyhow::Result<Vec<Equip>> {
     let equip_data_template: LuaTable = pg
         .get("equip_data_template")
         .context("global pg.equip_data_template")?;
@@ -455,16 +1090,17 @@ fn load_equips(lua: &Lua, pg: &LuaTable) -> anyhow::Result<Vec<Equip>> {
         .bounded_total(total)
         .start();
 
-    let make_equip = |id| {
-        let equip = parse::skill::load_equip(lua, id)?;
+    let make_equip = |id| -> anyhow::Result<Option<Equip>> {
+        let equip = cache.get_or_build(lua, id, 0, || Ok(parse::skill::load_equip(lua, id)?));
         action.inc_amount();
-        Ok(equip)
+        errors.handle("equip", id, equip)
     };
 
     let mut equips = equips
         .into_iter()
         .map(make_equip)
-        .collect::<LuaResult<Vec<_>>>()?;
+        .filter_map(Result::transpose)
+        .collect::<anyhow::Result<Vec<_>>>()?;
 
     action.finish();
 
@@ -472,7 +1108,12 @@ fn load_equips(lua: &Lua, pg: &LuaTable) -> anyhow::Result<Vec<Equip>> {
     Ok(equips)
 }
 
-fn load_augments(lua: &Lua, pg: &LuaTable) -> anyhow::Result<Vec<Augment>> {
+fn load_augments(
+    lua: &Lua,
+    pg: &LuaTable,
+    cache: &mut cache::EntryCache<Augment>,
+    errors: &mut lenient::ErrorReport,
+) -> anyhow::Result<Vec<Augment>> {
     let spweapon_data_statistics: LuaTable = pg
         .get("spweapon_data_statistics")
         .context("global pg.spweapon_data_statistics")?;
@@ -485,7 +1126,7 @@ fn load_augments(lua: &Lua, pg: &LuaTable) -> anyhow::Result<Vec<Augment>> {
         .suffix("..")
         .start();
 
-    let mut groups: HashMap<u32, u32> = HashMap::new();
+    let mut groups: HashMap<u32, Vec<u32>> = HashMap::new();
     spweapon_data_statistics_all.for_each(|_: u32, id: u32| {
         let statistics: LuaTable = spweapon_data_statistics
             .get(id)
@@ -498,15 +1139,11 @@ fn load_augments(lua: &Lua, pg: &LuaTable) -> anyhow::Result<Vec<Augment>> {
 
         groups
             .entry(base_id)
-            .and_modify(|e| {
-                if *e < id {
-                    *e = id
-                }
-            })
             .or_insert_with(|| {
                 action.inc_amount();
-                id
-            });
+                Vec::new()
+            })
+            .push(id);
 
         Ok(())
     })?;
@@ -518,20 +1155,39 @@ fn load_augments(lua: &Lua, pg: &LuaTable) -> anyhow::Result<Vec<Augment>> {
         .bounded_total(total)
         .start();
 
-    let make_augment = |id| {
+    let make_set = |id: u32| -> LuaResult<AugmentSet> {
         let statistics: LuaTable = spweapon_data_statistics
             .get(id)
             .with_context(context!("spweapon_data_statistics with id {id}"))?;
-        let data = AugmentSet { id, statistics };
-        let augment = parse::augment::load_augment(lua, &data)?;
+        Ok(AugmentSet { id, statistics })
+    };
+
+    let make_augment = |mut ids: Vec<u32>| -> anyhow::Result<Option<Augment>> {
+        // Each enhancement level has its own ID, ascending with the level.
+        ids.sort_unstable();
+        let id = *ids.last().expect("groups are never empty");
+
+        // The level list isn't tracked by the cache, so it needs to be part of the
+        // seed instead.
+        let seed = cache::stable_hash(&ids);
+        let augment = cache.get_or_build(lua, id, seed, || {
+            let mut augment = parse::augment::load_augment(lua, &make_set(id)?)?;
+            augment.levels = ids
+                .iter()
+                .map(|&id| parse::augment::load_augment_level(lua, &make_set(id)?))
+                .collect::<LuaResult<Vec<_>>>()?;
+
+            Ok(augment)
+        });
         action.inc_amount();
-        Ok(augment)
+        errors.handle("augment", id, augment)
     };
 
     let mut augments = groups
         .into_values()
         .map(make_augment)
-        .collect::<LuaResult<Vec<_>>>()?;
+        .filter_map(Result::transpose)
+        .collect::<anyhow::Result<Vec<_>>>()?;
 
     action.finish();
 
@@ -539,7 +1195,11 @@ fn load_augments(lua: &Lua, pg: &LuaTable) -> anyhow::Result<Vec<Augment>> {
     Ok(augments)
 }
 
-fn load_juustagram_chats(lua: &Lua, pg: &LuaTable) -> anyhow::Result<Vec<juustagram::Chat>> {
+fn load_juustagram_chats(
+    lua: &Lua,
+    pg: &LuaTable,
+    errors: &mut lenient::ErrorReport,
+) -> anyhow::Result<Vec<juustagram::Chat>> {
     let activity_ins_chat_group: LuaTable = pg
         .get("activity_ins_chat_group")
         .context("global pg.activity_ins_chat_group")?;
@@ -555,11 +1215,15 @@ fn load_juustagram_chats(lua: &Lua, pg: &LuaTable) -> anyhow::Result<Vec<juustag
         .start();
 
     activity_ins_chat_group_all.for_each(|_: u32, id: u32| {
-        let chat: LuaValue = lua
+        let chat = lua
             .globals()
             .call_function("get_juustagram_chat", id)
-            .with_context(context!("activity_ins_chat_group with id {id}"))?;
-        chats.push(lua.from_value(chat)?);
+            .with_context(context!("activity_ins_chat_group with id {id}"))
+            .and_then(|chat: LuaValue| lua.from_value(chat));
+        if let Some(chat) = errors.handle("juustagram chat", id, chat)? {
+            chats.push(chat);
+        }
+
         action.inc_amount();
         Ok(())
     })?;
@@ -568,7 +1232,11 @@ fn load_juustagram_chats(lua: &Lua, pg: &LuaTable) -> anyhow::Result<Vec<juustag
     Ok(chats)
 }
 
-fn load_special_secretaries(lua: &Lua, pg: &LuaTable) -> anyhow::Result<Vec<SpecialSecretary>> {
+fn load_special_secretaries(
+    lua: &Lua,
+    pg: &LuaTable,
+    errors: &mut lenient::ErrorReport,
+) -> anyhow::Result<Vec<SpecialSecretary>> {
     let secretary_special_ship: LuaTable = pg
         .get("secretary_special_ship")
         .context("global pg.secretary_special_ship")?;
@@ -593,7 +1261,7 @@ fn load_special_secretaries(lua: &Lua, pg: &LuaTable) -> anyhow::Result<Vec<Spec
 
         if kind != 0 {
             action.inc_amount();
-            ships.push(template);
+            ships.push((id, template));
         }
 
         Ok(())
@@ -606,15 +1274,16 @@ fn load_special_secretaries(lua: &Lua, pg: &LuaTable) -> anyhow::Result<Vec<Spec
         .bounded_total(total)
         .start();
 
-    let make_secretary = |data| {
-        let equip = parse::secretary::load_special_secretary(lua, &data)?;
+    let make_secretary = |(id, data)| {
+        let secretary = parse::secretary::load_special_secretary(lua, &data);
         action.inc_amount();
-        Ok(equip)
+        errors.handle("special secretary", id, secretary)
     };
 
     let mut ships = ships
         .into_iter()
         .map(make_secretary)
+        .filter_map(Result::transpose)
         .collect::<LuaResult<Vec<_>>>()?;
 
     action.finish();
@@ -623,6 +1292,115 @@ fn load_special_secretaries(lua: &Lua, pg: &LuaTable) -> anyhow::Result<Vec<Spec
     Ok(ships)
 }
 
+fn load_commissions(
+    lua: &Lua,
+    pg: &LuaTable,
+    errors: &mut lenient::ErrorReport,
+) -> anyhow::Result<Vec<Commission>> {
+    let expedition_data_template: LuaTable = pg
+        .get("expedition_data_template")
+        .context("global pg.expedition_data_template")?;
+    let expedition_data_template_all: LuaTable = expedition_data_template
+        .get("all")
+        .context("global pg.expedition_data_template.all")?;
+
+    let total = expedition_data_template_all.len()?;
+    let mut action = log::action!("Building commissions.")
+        .bounded_total(total.try_into()?)
+        .start();
+
+    let mut commissions = Vec::new();
+    expedition_data_template_all.for_each(|_: u32, id: u32| {
+        let commission = expedition_data_template
+            .get(id)
+            .with_context(context!("expedition_data_template with id {id}"))
+            .and_then(|data: LuaTable| parse::commission::load_commission(lua, id, &data));
+        if let Some(commission) = errors.handle("commission", id, commission)? {
+            commissions.push(commission);
+        }
+
+        action.inc_amount();
+        Ok(())
+    })?;
+
+    action.finish();
+
+    commissions.sort_unstable_by_key(|c| c.commission_id);
+    Ok(commissions)
+}
+
+fn load_stages(
+    lua: &Lua,
+    pg: &LuaTable,
+    errors: &mut lenient::ErrorReport,
+) -> anyhow::Result<Vec<Stage>> {
+    let chapter_template: LuaTable = pg
+        .get("chapter_template")
+        .context("global pg.chapter_template")?;
+    let chapter_template_all: LuaTable = chapter_template
+        .get("all")
+        .context("global pg.chapter_template.all")?;
+
+    let total = chapter_template_all.len()?;
+    let mut action = log::action!("Building stages.")
+        .bounded_total(total.try_into()?)
+        .start();
+
+    let mut stages = Vec::new();
+    chapter_template_all.for_each(|_: u32, id: u32| {
+        let stage = chapter_template
+            .get(id)
+            .with_context(context!("chapter_template with id {id}"))
+            .and_then(|data: LuaTable| parse::stage::load_stage(lua, id, &data));
+        if let Some(stage) = errors.handle("stage", id, stage)? {
+            stages.push(stage);
+        }
+
+        action.inc_amount();
+        Ok(())
+    })?;
+
+    action.finish();
+
+    stages.sort_unstable_by_key(|s| s.stage_id);
+    Ok(stages)
+}
+
+fn load_ship_levels(pg: &LuaTable) -> anyhow::Result<Vec<ShipLevel>> {
+    let ship_level: LuaTable = pg.get("ship_level").context("global pg.ship_level")?;
+    let ship_level_all: LuaTable = ship_level.get("all").context("global pg.ship_level.all")?;
+
+    let total = ship_level_all.len()?;
+    let mut action = log::action!("Building ship levels.")
+        .bounded_total(total.try_into()?)
+        .start();
+
+    let mut levels = Vec::new();
+    ship_level_all.for_each(|_: u32, level: u32| {
+        let data: LuaTable = ship_level
+            .get(level)
+            .with_context(context!("ship_level with level {level}"))?;
+
+        levels.push(ShipLevel {
+            level,
+            exp: data
+                .get("exp")
+                .with_context(context!("exp of ship_level with level {level}"))?,
+            exp_ur: data
+                .get("exp_ur")
+                .with_context(context!("exp_ur of ship_level with level {level}"))?,
+        });
+
+        action.inc_amount();
+        Ok(())
+    })?;
+
+    action.finish();
+
+    levels.sort_unstable_by_key(|l| l.level);
+    Ok(levels)
+}
+
 fn fix_up_retrofitted_data(ship: &mut ShipData, set: &ShipSet<'_>) -> LuaResult<()> {
     let buff_list_display: Vec<u32> = set.template.get("buff_list_display")?;
     ship.skills.sort_by_key(|s| {
@@ -646,9 +1424,13 @@ fn merge_out_data(main: &mut DefinitionData, next: DefinitionData) {
             .iter_mut()
             .find(|s| s.group_id == next_ship.group_id)
         {
-            add_missing(&mut main_ship.retrofits, next_ship.retrofits, |a, b| {
-                a.default_skin_id == b.default_skin_id
-            });
+            main_ship.names.add_missing(next_ship.names);
+            merge_or_add(
+                &mut main_ship.retrofits,
+                next_ship.retrofits,
+                |a, b| a.default_skin_id == b.default_skin_id,
+                |a, b| a.names.add_missing(b.names),
+            );
             add_missing(&mut main_ship.skins, next_ship.skins, |a, b| {
                 a.skin_id == b.skin_id
             });
@@ -657,15 +1439,30 @@ fn merge_out_data(main: &mut DefinitionData, next: DefinitionData) {
         }
     }
 
-    add_missing(&mut main.augments, next.augments, |a, b| {
-        a.augment_id == b.augment_id
-    });
-    add_missing(&mut main.equips, next.equips, |a, b| {
-        a.equip_id == b.equip_id
-    });
+    merge_or_add(
+        &mut main.augments,
+        next.augments,
+        |a, b| a.augment_id == b.augment_id,
+        |a, b| a.names.add_missing(b.names),
+    );
+    merge_or_add(
+        &mut main.equips,
+        next.equips,
+        |a, b| a.equip_id == b.equip_id,
+        |a, b| a.names.add_missing(b.names),
+    );
     add_missing(&mut main.juustagram_chats, next.juustagram_chats, |a, b| {
         a.chat_id == b.chat_id
     });
+    add_missing(&mut main.commissions, next.commissions, |a, b| {
+        a.commission_id == b.commission_id
+    });
+    add_missing(&mut main.stages, next.stages, |a, b| {
+        a.stage_id == b.stage_id
+    });
+    add_missing(&mut main.ship_levels, next.ship_levels, |a, b| {
+        a.level == b.level
+    });
 
     action.finish();
 }
@@ -677,3 +1474,17 @@ fn add_missing<T>(main: &mut Vec<T>, next: Vec<T>, matches: impl Fn(&T, &T) -> b
         }
     }
 }
+
+fn merge_or_add<T>(
+    main: &mut Vec<T>,
+    next: Vec<T>,
+    matches: impl Fn(&T, &T) -> bool,
+    merge: impl Fn(&mut T, T),
+) {
+    for new in next {
+        match main.iter_mut().find(|old| matches(old, &new)) {
+            Some(old) => merge(old, new),
+            None => main.push(new),
+        }
+    }
+}
@@ -2,26 +2,106 @@
 
 use std::collections::HashMap;
 use std::error::Error;
-use std::fmt;
-use std::sync::LazyLock;
+use std::sync::OnceLock;
+use std::{fmt, fs};
 
 use azur_lane::skill::*;
 use mlua::prelude::*;
+use serde::{Deserialize, Deserializer};
 
 /// The config model.
-#[derive(Debug, serde::Deserialize)]
+#[derive(Debug, Deserialize)]
 pub struct Config {
     /// Overrides for ship names based on their group ID.
+    #[serde(deserialize_with = "deserialize_id_map")]
     pub name_overrides: HashMap<u32, String>,
     /// Names for the special secretary kinds.
     pub special_secretary_kinds: Vec<String>,
     /// Overrides for skills based on their buff ID.
+    #[serde(deserialize_with = "deserialize_id_map")]
     pub predefined_skills: HashMap<u32, Skill>,
+    /// The text of the config file applied on top of the embedded config.
+    #[serde(skip)]
+    pub file_source: Option<String>,
 }
 
-/// The app config. Statically embed as JSON.
-pub static CONFIG: LazyLock<Config> =
-    LazyLock::new(|| serde_json::from_str(include_str!("../assets/config.json")).unwrap());
+/// The additions to the config that may be specified with `--config`.
+///
+/// Entries in the maps are added to or replace the embedded ones, while
+/// lists replace the embedded ones entirely.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ConfigFile {
+    #[serde(deserialize_with = "deserialize_id_map")]
+    name_overrides: HashMap<u32, String>,
+    special_secretary_kinds: Option<Vec<String>>,
+    #[serde(deserialize_with = "deserialize_id_map")]
+    predefined_skills: HashMap<u32, Skill>,
+}
+
+/// The app config. Initialized by [`init_config`] or on first access.
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+/// The embedded config, used as the base for the app config.
+const EMBEDDED_CONFIG: &str = include_str!("../assets/config.json");
+
+/// Gets the app config.
+///
+/// If [`init_config`] wasn't called first, this is just the embedded config.
+pub fn config() -> &'static Config {
+    CONFIG.get_or_init(Config::embedded)
+}
+
+/// Initializes the app config, applying a TOML config file on top of the
+/// embedded config.
+///
+/// Must be called before the first call to [`config`].
+pub fn init_config(path: &str) -> anyhow::Result<()> {
+    use anyhow::Context as _;
+
+    let source =
+        fs::read_to_string(path).with_context(|| format!("cannot read config `{path}`"))?;
+    let file: ConfigFile =
+        toml::from_str(&source).with_context(|| format!("invalid config `{path}`"))?;
+
+    let mut config = Config::embedded();
+    config.name_overrides.extend(file.name_overrides);
+    config.predefined_skills.extend(file.predefined_skills);
+    if let Some(kinds) = file.special_secretary_kinds {
+        config.special_secretary_kinds = kinds;
+    }
+
+    config.file_source = Some(source);
+    CONFIG
+        .set(config)
+        .map_err(|_| anyhow::anyhow!("config already initialized"))
+}
+
+impl Config {
+    fn embedded() -> Self {
+        serde_json::from_str(EMBEDDED_CONFIG).expect("embedded config must be valid")
+    }
+}
+
+/// Deserializes a map keyed by IDs.
+///
+/// Keys in JSON and TOML are always strings, which not every format will
+/// convert to integers by itself.
+fn deserialize_id_map<'de, D, T>(deserializer: D) -> Result<HashMap<u32, T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    use serde::de::Error as _;
+
+    HashMap::<String, T>::deserialize(deserializer)?
+        .into_iter()
+        .map(|(key, value)| match key.parse() {
+            Ok(id) => Ok((id, value)),
+            Err(_) => Err(D::Error::custom(format_args!("invalid id `{key}`"))),
+        })
+        .collect()
+}
 
 /// A group of ships.
 #[derive(Debug)]
@@ -47,6 +127,9 @@ pub struct ShipSet<'a> {
     pub strengthen: Strengthen<'a>,
     /// The associated retrofit data.
     pub retrofit_data: Option<Retrofit<'a>>,
+    /// The "ship_data_breakout" entry, describing the limit break from this
+    /// state.
+    pub breakout: Option<LuaTable>,
 }
 
 /// A set of data from which [`ShipSkin`] can be constructed.
@@ -136,10 +219,33 @@ impl fmt::Display for DataError {
 
 #[cfg(test)]
 mod test {
-    use std::sync::LazyLock;
+    use super::*;
 
     #[test]
     fn static_config() {
-        LazyLock::force(&super::CONFIG);
+        Config::embedded();
+    }
+
+    #[test]
+    fn config_file() {
+        let file: ConfigFile = toml::from_str(
+            r#"
+            special_secretary_kinds = ["Unknown"]
+
+            [name_overrides]
+            20232 = "Enterprise (Royal Navy)"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            file.name_overrides.get(&20232).map(String::as_str),
+            Some("Enterprise (Royal Navy)")
+        );
+        assert_eq!(
+            file.special_secretary_kinds.as_deref(),
+            Some(&["Unknown".to_owned()][..])
+        );
+        assert!(file.predefined_skills.is_empty());
     }
 }
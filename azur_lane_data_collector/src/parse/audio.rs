@@ -0,0 +1,48 @@
+use std::fs;
+
+use unity_read::classes::{AudioClip, ClassID};
+use unity_read::unity_fs::{UnityFsData, UnityFsFile};
+
+use crate::log::Action;
+
+// cue: voice lines, 1 bundle per voice key
+// - each clip is named after the line key, f.e. `login` or `main_2`
+
+/// Loads all voice line clips for a voice key.
+///
+/// Returns pairs of the clip name and its raw data.
+pub fn load_voice_clips(
+    action: &Action,
+    dir: &str,
+    key: &str,
+) -> anyhow::Result<Vec<(String, Vec<u8>)>> {
+    let key = key.to_ascii_lowercase();
+    let Ok(mut file) = fs::File::open(utils::join_path!(dir, "cue", &key)) else {
+        action.print_info(format_args!("Voice cue file {key} not found."));
+        return Ok(Vec::new());
+    };
+
+    let mut clips = Vec::new();
+
+    let unity_fs = UnityFsFile::open(&mut file)?;
+    for entry in unity_fs.entries() {
+        if let UnityFsData::SerializedFile(ser_file) = entry.read()? {
+            let audio_clips = ser_file
+                .objects()
+                .filter_map(Result::ok)
+                .filter(|o| o.class_id() == ClassID::AudioClip)
+                .filter_map(|o| o.try_into_class::<AudioClip>().ok());
+
+            for clip in audio_clips {
+                let data = clip.read_data(&unity_fs)?;
+                clips.push((clip.name.to_ascii_lowercase(), data.data().to_vec()));
+            }
+        }
+    }
+
+    if clips.is_empty() {
+        action.print_info(format_args!("Voice cue file {key} has no clips."));
+    }
+
+    Ok(clips)
+}
@@ -1,4 +1,5 @@
 use azur_lane::equip::*;
+use azur_lane::LocalizedNames;
 use mlua::prelude::*;
 
 use crate::model::*;
@@ -17,14 +18,6 @@ pub fn load_augment(lua: &Lua, set: &AugmentSet) -> LuaResult<Augment> {
         };
     }
 
-    /// Reads a [`azur_lane::ship::StatKind`] from the statistics.
-    macro_rules! read_stat {
-        ($field:expr) => {{
-            let temp: String = read!($field);
-            convert_al::to_stat_kind(&temp)
-        }};
-    }
-
     // The effect is the first skill on the augment, f.e. a slash attack.
     // This field is always present, but 0 indicates that no effect is used.
     let effect: u32 = read!("effect_id");
@@ -78,21 +71,78 @@ pub fn load_augment(lua: &Lua, set: &AugmentSet) -> LuaResult<Augment> {
     Ok(Augment {
         augment_id: set.id,
         name: read!("name"),
+        names: LocalizedNames::default(), // Added by caller.
         rarity: convert_al::to_augment_rarity(read!("rarity")),
-        stat_bonuses: vec![
-            AugmentStatBonus {
-                stat_kind: read_stat!("attribute_1"),
-                amount: read!("value_1"),
-                random: read!("value_1_random"),
-            },
-            AugmentStatBonus {
-                stat_kind: read_stat!("attribute_2"),
-                amount: read!("value_2"),
-                random: read!("value_2_random"),
-            },
-        ],
+        stat_bonuses: load_stat_bonuses(set)?,
         usability,
         effect,
         skill_upgrade,
+        levels: Vec::new(), // Added by caller.
     })
 }
+
+/// Construct the data for a single enhancement level from this set.
+pub fn load_augment_level(lua: &Lua, set: &AugmentSet) -> LuaResult<AugmentLevel> {
+    let effect: u32 = set
+        .statistics
+        .get("effect_id")
+        .with_context(context!("effect_id of augment with id {}", set.id))?;
+    let effect_description = match effect {
+        0 => None,
+        _ => Some(parse::skill::load_skill_description(lua, effect)?),
+    };
+
+    let skill_upgrade: Vec<LuaTable> = set
+        .statistics
+        .get("skill_upgrade")
+        .with_context(context!("skill_upgrade of augment with id {}", set.id))?;
+    let skill_upgrade_description = match skill_upgrade.into_iter().next() {
+        Some(skill_upgrade) => {
+            let skill_id: u32 = skill_upgrade
+                .get(2)
+                .with_context(context!("skill_upgrade id for augment {}", set.id))?;
+            Some(parse::skill::load_skill_description(lua, skill_id)?)
+        },
+        None => None,
+    };
+
+    Ok(AugmentLevel {
+        stat_bonuses: load_stat_bonuses(set)?,
+        effect_description,
+        skill_upgrade_description,
+    })
+}
+
+fn load_stat_bonuses(set: &AugmentSet) -> LuaResult<Vec<AugmentStatBonus>> {
+    /// Reads a value from the statistics; target-typed.
+    macro_rules! read {
+        ($field:expr) => {
+            set.statistics.get($field).with_context(context!(
+                "{} of augment with id {}",
+                $field,
+                set.id
+            ))?
+        };
+    }
+
+    /// Reads a [`azur_lane::ship::StatKind`] from the statistics.
+    macro_rules! read_stat {
+        ($field:expr) => {{
+            let temp: String = read!($field);
+            convert_al::to_stat_kind(&temp)
+        }};
+    }
+
+    Ok(vec![
+        AugmentStatBonus {
+            stat_kind: read_stat!("attribute_1"),
+            amount: read!("value_1"),
+            random: read!("value_1_random"),
+        },
+        AugmentStatBonus {
+            stat_kind: read_stat!("attribute_2"),
+            amount: read!("value_2"),
+            random: read!("value_2_random"),
+        },
+    ])
+}
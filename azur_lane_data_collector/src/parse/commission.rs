@@ -0,0 +1,42 @@
+use azur_lane::commission::*;
+use mlua::prelude::*;
+
+use crate::{context, convert_al, parse};
+
+/// Construct commission data from its "expedition_data_template" entry.
+pub fn load_commission(lua: &Lua, id: u32, data: &LuaTable) -> LuaResult<Commission> {
+    /// Reads a value from the template; target-typed.
+    macro_rules! read {
+        ($field:expr) => {
+            data.get($field)
+                .with_context(context!("{} of commission with id {}", $field, id))?
+        };
+    }
+
+    // Unrestricted commissions may not have a table here.
+    let hull_types: LuaValue = read!("ship_type");
+    let hull_types = match hull_types {
+        LuaValue::Table(t) => t
+            .sequence_values::<u32>()
+            .filter_map(|h| h.map(convert_al::to_known_hull_type).transpose())
+            .collect::<LuaResult<Vec<_>>>()?,
+        _ => Vec::new(),
+    };
+
+    let drop_display: Vec<LuaTable> = read!("drop_display");
+    let rewards = parse::reward::load_rewards(lua, drop_display)
+        .with_context(context!("drop_display of commission with id {id}"))?;
+
+    Ok(Commission {
+        commission_id: id,
+        name: read!("name"),
+        duration: read!("time"),
+        oil_cost: read!("oil"),
+        requirements: CommissionRequirements {
+            ship_count: read!("ship_num"),
+            flagship_level: read!("ship_lv"),
+            hull_types,
+        },
+        rewards,
+    })
+}
@@ -1,8 +1,8 @@
 use std::fs;
 use std::io::Cursor;
 
-use image::{imageops, ImageFormat};
-use unity_read::classes::{ClassID, Texture2D};
+use image::{imageops, GenericImageView, ImageFormat, RgbaImage};
+use unity_read::classes::{ClassID, Mesh, ResolvedMesh, Texture2D};
 use unity_read::unity_fs::{UnityFsData, UnityFsFile};
 
 use crate::log::Action;
@@ -12,6 +12,9 @@ use crate::log::Action;
 // painting:
 // - tex: full sprite, background 1:1
 // - n_tex: full sprite, no background 0/1:1
+// - the bundle without suffix may hold a mesh to reassemble a sliced tex
+// equips: equipment icons, 1:1
+// skillicon: skill icons, 1:1
 
 pub fn load_chibi_image(action: &Action, dir: &str, name: &str) -> anyhow::Result<Option<Vec<u8>>> {
     let name = name.to_ascii_lowercase();
@@ -44,3 +47,231 @@ pub fn load_chibi_image(action: &Action, dir: &str, name: &str) -> anyhow::Resul
     action.print_info(format_args!("Skin shipmodels image {name} not present."));
     Ok(None)
 }
+
+pub fn load_painting_image(
+    action: &Action,
+    dir: &str,
+    name: &str,
+) -> anyhow::Result<Option<Vec<u8>>> {
+    let name = name.to_ascii_lowercase();
+    let tex_name = format!("{name}_tex");
+    let Ok(mut tex_file) = fs::File::open(utils::join_path!(dir, "painting", &tex_name)) else {
+        action.print_info(format_args!("Skin painting file {tex_name} not found."));
+        return Ok(None);
+    };
+
+    let tex_fs = UnityFsFile::open(&mut tex_file)?;
+    let Some(image) = find_texture(&tex_fs, &name)? else {
+        action.print_info(format_args!("Skin painting image {name} not present."));
+        return Ok(None);
+    };
+
+    // Paintings may be sliced to save texture space. In that case, the painting
+    // bundle holds a mesh whose vertices place parts of the texture.
+    let mut image = match fs::File::open(utils::join_path!(dir, "painting", &name)) {
+        Ok(mut mesh_file) => {
+            let mesh_fs = UnityFsFile::open(&mut mesh_file)?;
+            match find_mesh(&mesh_fs, &format!("{name}-mesh"))? {
+                Some(meshes) => reassemble_painting(&image, &meshes),
+                None => image,
+            }
+        },
+        Err(_) => image,
+    };
+
+    imageops::flip_vertical_in_place(&mut image);
+
+    let mut writer = Cursor::new(Vec::with_capacity(512 * 1024));
+    image.write_to(&mut writer, ImageFormat::WebP)?;
+    Ok(Some(writer.into_inner()))
+}
+
+/// Loads an icon from a bundle directory, such as `equips` or `skillicon`.
+///
+/// Each icon has its own bundle holding a texture of the same name.
+pub fn load_icon_image(
+    action: &Action,
+    dir: &str,
+    kind: &str,
+    name: &str,
+) -> anyhow::Result<Option<Vec<u8>>> {
+    let name = name.to_ascii_lowercase();
+    let Ok(mut file) = fs::File::open(utils::join_path!(dir, kind, &name)) else {
+        action.print_info(format_args!("Icon {kind} file {name} not found."));
+        return Ok(None);
+    };
+
+    let unity_fs = UnityFsFile::open(&mut file)?;
+    let Some(mut image) = find_texture(&unity_fs, &name)? else {
+        action.print_info(format_args!("Icon {kind} image {name} not present."));
+        return Ok(None);
+    };
+
+    imageops::flip_vertical_in_place(&mut image);
+
+    let mut writer = Cursor::new(Vec::with_capacity(16 * 1024));
+    image.write_to(&mut writer, ImageFormat::WebP)?;
+    Ok(Some(writer.into_inner()))
+}
+
+/// An expression image of a skin.
+pub struct FaceImage {
+    /// The name of the face texture.
+    pub name: String,
+    /// The WebP image data, if requested.
+    pub data: Option<Vec<u8>>,
+}
+
+/// Loads the expressions (`paintingface`) available for a skin.
+///
+/// The faces are returned in natural order of their names. The images are only
+/// decoded if `decode` is set.
+pub fn load_face_images(dir: &str, name: &str, decode: bool) -> anyhow::Result<Vec<FaceImage>> {
+    let name = name.to_ascii_lowercase();
+    let Ok(mut file) = fs::File::open(utils::join_path!(dir, "paintingface", &name)) else {
+        // many skins don't have any alternative faces
+        return Ok(Vec::new());
+    };
+
+    let unity_fs = UnityFsFile::open(&mut file)?;
+    let mut faces = Vec::new();
+    for entry in unity_fs.entries() {
+        if let UnityFsData::SerializedFile(ser_file) = entry.read()? {
+            let textures = ser_file
+                .objects()
+                .filter_map(Result::ok)
+                .filter(|o| o.class_id() == ClassID::Texture2D)
+                .filter_map(|o| o.try_into_class::<Texture2D>().ok());
+
+            for texture in textures {
+                let data = if decode {
+                    let mut image = texture.read_data(&unity_fs)?.decode()?;
+                    imageops::flip_vertical_in_place(&mut image);
+
+                    let mut writer = Cursor::new(Vec::with_capacity(32 * 1024));
+                    image.write_to(&mut writer, ImageFormat::WebP)?;
+                    Some(writer.into_inner())
+                } else {
+                    None
+                };
+
+                faces.push(FaceImage {
+                    name: texture.name.to_ascii_lowercase(),
+                    data,
+                });
+            }
+        }
+    }
+
+    // faces are usually numbered, so sort "10" after "9"
+    faces.sort_by(|a, b| {
+        let key = |f: &FaceImage| (f.name.parse::<u32>().unwrap_or(u32::MAX), f.name.clone());
+        key(a).cmp(&key(b))
+    });
+    faces.dedup_by(|a, b| a.name == b.name);
+    Ok(faces)
+}
+
+/// Finds and decodes a texture by name. The image is not flipped.
+fn find_texture<'a>(
+    unity_fs: &'a UnityFsFile<'a>,
+    name: &str,
+) -> anyhow::Result<Option<RgbaImage>> {
+    for entry in unity_fs.entries() {
+        if let UnityFsData::SerializedFile(ser_file) = entry.read()? {
+            let texture = ser_file
+                .objects()
+                .filter_map(Result::ok)
+                .filter(|o| o.class_id() == ClassID::Texture2D)
+                .filter_map(|o| o.try_into_class::<Texture2D>().ok())
+                .find(|t| t.name.to_ascii_lowercase() == name);
+
+            if let Some(texture) = texture {
+                return Ok(Some(texture.read_data(unity_fs)?.decode()?));
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+/// Finds a mesh by name and resolves it.
+fn find_mesh<'a>(
+    unity_fs: &'a UnityFsFile<'a>,
+    name: &str,
+) -> anyhow::Result<Option<Vec<ResolvedMesh>>> {
+    for entry in unity_fs.entries() {
+        if let UnityFsData::SerializedFile(ser_file) = entry.read()? {
+            let mesh = ser_file
+                .objects()
+                .filter_map(Result::ok)
+                .filter(|o| o.class_id() == ClassID::Mesh)
+                .filter_map(|o| o.try_into_class::<Mesh>().ok())
+                .find(|m| m.name.to_ascii_lowercase() == name);
+
+            if let Some(mesh) = mesh {
+                return Ok(Some(mesh.read_vertex_data(unity_fs)?.resolve_meshes()?));
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+/// Reassembles a sliced painting from its texture and meshes.
+///
+/// The mesh positions are in pixels of the final image and the UVs point into
+/// the texture. Both are in Unity's bottom-up coordinates, as is the output.
+fn reassemble_painting(texture: &RgbaImage, meshes: &[ResolvedMesh]) -> RgbaImage {
+    let vertices = || meshes.iter().flat_map(|m| m.vertices());
+
+    let min_x = vertices().map(|v| v.pos.x).fold(f32::INFINITY, f32::min);
+    let min_y = vertices().map(|v| v.pos.y).fold(f32::INFINITY, f32::min);
+    let max_x = vertices()
+        .map(|v| v.pos.x)
+        .fold(f32::NEG_INFINITY, f32::max);
+    let max_y = vertices()
+        .map(|v| v.pos.y)
+        .fold(f32::NEG_INFINITY, f32::max);
+
+    if min_x >= max_x || min_y >= max_y {
+        // degenerate or empty mesh, just use the texture as-is
+        return texture.clone();
+    }
+
+    // the values are small and positive after the checks above
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let to_px = |f: f32| f.round().max(0.0) as u32;
+
+    let tex_w = texture.width() as f32;
+    let tex_h = texture.height() as f32;
+    let mut canvas = RgbaImage::new(to_px(max_x - min_x), to_px(max_y - min_y));
+
+    // The slices are axis-aligned quads, split into 2 triangles each.
+    // Copying the bounding rectangle of each triangle covers the whole quad.
+    for (a, b, c) in meshes.iter().flat_map(|m| m.triangles()) {
+        let pos_x = a.pos.x.min(b.pos.x).min(c.pos.x) - min_x;
+        let pos_y = a.pos.y.min(b.pos.y).min(c.pos.y) - min_y;
+        let uv_x = a.uv.x.min(b.uv.x).min(c.uv.x) * tex_w;
+        let uv_y = a.uv.y.min(b.uv.y).min(c.uv.y) * tex_h;
+        let uv_w = a.uv.x.max(b.uv.x).max(c.uv.x) * tex_w - uv_x;
+        let uv_h = a.uv.y.max(b.uv.y).max(c.uv.y) * tex_h - uv_y;
+
+        let (src_x, src_y) = (to_px(uv_x), to_px(uv_y));
+        let width = to_px(uv_w).min(texture.width().saturating_sub(src_x));
+        let height = to_px(uv_h).min(texture.height().saturating_sub(src_y));
+        if width == 0 || height == 0 {
+            continue;
+        }
+
+        let part = texture.view(src_x, src_y, width, height);
+        imageops::replace(
+            &mut canvas,
+            &*part,
+            i64::from(to_px(pos_x)),
+            i64::from(to_px(pos_y)),
+        );
+    }
+
+    canvas
+}
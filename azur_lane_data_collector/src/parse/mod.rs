@@ -1,8 +1,12 @@
 //! Helper methods for loading/parsing data.
 
+pub mod audio;
 pub mod augment;
+pub mod commission;
 pub mod image;
+pub mod reward;
 pub mod secretary;
 pub mod ship;
 pub mod skill;
 pub mod skin;
+pub mod stage;
@@ -0,0 +1,77 @@
+use azur_lane::{Reward, RewardKind};
+use mlua::prelude::*;
+
+use crate::{context, convert_al};
+
+/// Loads rewards from a list of `{type, id, ...}` tables.
+///
+/// Ship rewards refer to a specific ship template and equipment rewards to
+/// the unenhanced equipment, so these are mapped to the group ID and the
+/// fully enhanced ID respectively to match the rest of the data.
+pub fn load_rewards(lua: &Lua, list: Vec<LuaTable>) -> LuaResult<Vec<Reward>> {
+    let pg: LuaTable = lua.globals().get("pg").context("global pg")?;
+
+    let mut rewards = Vec::with_capacity(list.len());
+    for entry in list {
+        let kind = convert_al::to_reward_kind(entry.get(1)?);
+        let id: u32 = entry.get(2)?;
+
+        let id = match kind {
+            RewardKind::Ship => to_ship_group_id(&pg, id)?,
+            RewardKind::Equip => to_final_equip_id(&pg, id)?,
+            _ => id,
+        };
+
+        // the same ship may be listed in several rarities or states
+        if !rewards
+            .iter()
+            .any(|r: &Reward| r.kind == kind && r.id == id)
+        {
+            rewards.push(Reward { kind, id });
+        }
+    }
+
+    Ok(rewards)
+}
+
+fn to_ship_group_id(pg: &LuaTable, id: u32) -> LuaResult<u32> {
+    let template: Option<LuaTable> = pg
+        .get::<LuaTable>("ship_data_template")
+        .context("global pg.ship_data_template")?
+        .get(id)
+        .with_context(context!("ship_data_template with id {id}"))?;
+
+    match template {
+        Some(t) => t
+            .get("group_type")
+            .with_context(context!("group_type of ship_data_template with id {id}")),
+        None => Ok(id),
+    }
+}
+
+fn to_final_equip_id(pg: &LuaTable, mut id: u32) -> LuaResult<u32> {
+    let equip_data_template: LuaTable = pg
+        .get("equip_data_template")
+        .context("global pg.equip_data_template")?;
+
+    // follow the enhancement chain, guarding against cycles in broken data
+    for _ in 0..32 {
+        let template: Option<LuaTable> = equip_data_template
+            .get(id)
+            .with_context(context!("equip_data_template with id {id}"))?;
+        let next: u32 = match template {
+            Some(t) => t
+                .get("next")
+                .with_context(context!("next of equip_data_template with id {id}"))?,
+            None => 0,
+        };
+
+        if next == 0 {
+            break;
+        }
+
+        id = next;
+    }
+
+    Ok(id)
+}
@@ -2,7 +2,7 @@ use azur_lane::secretary::*;
 use mlua::prelude::*;
 
 use super::skin::to_main_screen;
-use crate::{context, CONFIG};
+use crate::{config, context};
 
 pub fn load_special_secretary(lua: &Lua, data: &LuaTable) -> LuaResult<SpecialSecretary> {
     let id: u32 = data.get("id")?;
@@ -24,7 +24,7 @@ pub fn load_special_secretary(lua: &Lua, data: &LuaTable) -> LuaResult<SpecialSe
         // otherwise, take them from the configuration list. i didn't figure out how the
         // game gets them -- if it does at all. there isn't really a need for the game
         // to have some way to map the type to a string name after all
-        CONFIG
+        config()
             .special_secretary_kinds
             .get(data.get::<usize>("type")?)
             .cloned()
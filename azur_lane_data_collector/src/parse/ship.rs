@@ -1,4 +1,5 @@
 use azur_lane::ship::*;
+use azur_lane::LocalizedNames;
 use mlua::prelude::*;
 
 use crate::model::*;
@@ -84,6 +85,7 @@ pub fn load_ship_data(lua: &Lua, set: &ShipSet<'_>) -> LuaResult<ShipData> {
     let mut ship = ShipData {
         group_id: read!(set.template, "group_type"),
         name: read!(set.statistics, "name"),
+        names: LocalizedNames::default(), // Added by caller.
         rarity: convert_al::to_rarity(read!(set.statistics, "rarity")),
         faction: convert_al::to_faction(read!(set.statistics, "nationality")),
         hull_type: convert_al::to_hull_type(read!(set.statistics, "type")),
@@ -129,9 +131,12 @@ pub fn load_ship_data(lua: &Lua, set: &ShipSet<'_>) -> LuaResult<ShipData> {
             })
             .collect::<LuaResult<Vec<_>>>()?,
         depth_charges: parse::skill::load_equips(lua, read!(set.statistics, "depth_charge_list"))?,
+        default_equips: load_default_equips(lua, set)?,
         skills: parse::skill::load_skills(lua, buff_list)?,
-        retrofits: Vec::new(), // Added by caller.
-        skins: Vec::new(),     // Added by caller.
+        limit_break_costs: Vec::new(), // Added by caller.
+        enhance_cost: None,            // Set below.
+        retrofits: Vec::new(),         // Added by caller.
+        skins: Vec::new(),             // Added by caller.
     };
 
     if ship.hull_type.team_type() == TeamType::Submarine {
@@ -157,21 +162,52 @@ pub fn load_ship_data(lua: &Lua, set: &ShipSet<'_>) -> LuaResult<ShipData> {
             ship.stats.aa += b(extra.get(3)?);
             ship.stats.avi += b(extra.get(4)?);
             ship.stats.rld += b(extra.get(5)?);
+
+            // The EXP needed per stat, in the same order.
+            let attr_exp: LuaTable = read!(data, "attr_exp");
+            ship.enhance_cost = Some(EnhanceCost::Normal(
+                [
+                    StatKind::FP,
+                    StatKind::TRP,
+                    StatKind::AA,
+                    StatKind::AVI,
+                    StatKind::RLD,
+                ]
+                .into_iter()
+                .enumerate()
+                .map(|(index, stat_kind)| {
+                    Ok(EnhanceStatExp {
+                        stat_kind,
+                        exp: attr_exp.get(index + 1)?,
+                    })
+                })
+                .collect::<LuaResult<_>>()?,
+            ));
         },
         Strengthen::Blueprint(ex) => {
             // ship_data_blueprint
             ship.enhance_kind = EnhanceKind::Research;
 
             let mut effects: Vec<u32> = read!(ex.data, "strengthen_effect");
+            let dev_levels = effects.len();
             effects.append(&mut read!(ex.data, "fate_strengthen"));
 
-            for id in effects {
-                enhance::blueprint::add_blueprint_effect(
-                    lua,
-                    &mut ship,
-                    &read!(ex.effect_lookup, id),
-                )?;
+            let mut level_costs = Vec::with_capacity(dev_levels);
+            for (index, id) in effects.into_iter().enumerate() {
+                let effect: LuaTable = read!(ex.effect_lookup, id);
+                enhance::blueprint::add_blueprint_effect(lua, &mut ship, &effect)?;
+
+                // Only the dev levels consume blueprint EXP.
+                // Fate Simulation has its own costs.
+                if index < dev_levels {
+                    level_costs.push(ResearchLevelCost {
+                        level: read!(effect, "lv"),
+                        exp: read!(effect, "need_exp"),
+                    });
+                }
             }
+
+            ship.enhance_cost = Some(EnhanceCost::Research(level_costs));
         },
         Strengthen::Meta(ex) => {
             // ship_strengthen_meta
@@ -205,3 +241,65 @@ pub fn load_ship_data(lua: &Lua, set: &ShipSet<'_>) -> LuaResult<ShipData> {
 
     Ok(ship)
 }
+
+/// Constructs the limit break costs from the sets of the pre-MLB states.
+///
+/// The sets are expected to be ordered by their ID.
+pub fn load_limit_break_costs(sets: &[&ShipSet<'_>]) -> LuaResult<Vec<LimitBreakCost>> {
+    let mut costs = Vec::new();
+    for set in sets {
+        let Some(breakout) = &set.breakout else {
+            continue;
+        };
+
+        macro_rules! read {
+            ($field:expr) => {
+                breakout.get($field).with_context(context!(
+                    "{} of ship_data_breakout with id {}",
+                    $field,
+                    set.id
+                ))?
+            };
+        }
+
+        let items: Vec<LuaTable> = read!("use_item");
+        let items = items
+            .into_iter()
+            .map(|item| {
+                Ok(ItemCost {
+                    item_id: item.get(1)?,
+                    amount: item.get(2)?,
+                })
+            })
+            .collect::<LuaResult<_>>()?;
+
+        costs.push(LimitBreakCost {
+            level: read!("level"),
+            coins: read!("use_gold"),
+            ships: read!("use_char_num"),
+            items,
+        });
+    }
+
+    Ok(costs)
+}
+
+/// Loads the equipment the ship comes with from `equip_id_1` to `equip_id_3`.
+fn load_default_equips(lua: &Lua, set: &ShipSet<'_>) -> LuaResult<Vec<DefaultEquip>> {
+    let mut equips = Vec::new();
+    for (slot, key) in [(0u8, "equip_id_1"), (1, "equip_id_2"), (2, "equip_id_3")] {
+        // 0 indicates an empty slot.
+        let equip_id: u32 =
+            set.template
+                .get(key)
+                .with_context(context!("{} of ship with id {}", key, set.id))?;
+        if equip_id != 0 {
+            equips.push(DefaultEquip {
+                slot,
+                equip: parse::skill::load_equip(lua, equip_id)?,
+            });
+        }
+    }
+
+    Ok(equips)
+}
@@ -3,9 +3,10 @@ use std::collections::{HashMap, HashSet};
 use azur_lane::equip::*;
 use azur_lane::ship::*;
 use azur_lane::skill::*;
+use azur_lane::LocalizedNames;
 use mlua::prelude::*;
 
-use crate::{context, convert_al, CONFIG};
+use crate::{config, context, convert_al};
 
 /// Loads a skill from the Lua state.
 pub fn load_skill(lua: &Lua, skill_id: u32) -> LuaResult<Skill> {
@@ -20,23 +21,9 @@ pub fn load_skill(lua: &Lua, skill_id: u32) -> LuaResult<Skill> {
     let name: String = skill
         .get("name")
         .with_context(context!("name of skill with id {}", skill_id))?;
-    let mut desc: String = skill
-        .get("desc")
-        .with_context(context!("desc of skill with id {}", skill_id))?;
-    let desc_add: Vec<Vec<Vec<String>>> = skill
-        .get("desc_add")
-        .with_context(context!("desc_add of skill with id {}", skill_id))?;
-
-    for (slot, data_set) in desc_add.iter().enumerate() {
-        if let Some(last) = data_set.last() {
-            if let Some(text) = last.first() {
-                let placeholder = format!("${}", slot + 1);
-                desc = desc.replace(&placeholder, text);
-            }
-        }
-    }
+    let desc = read_skill_description(&skill, skill_id)?;
 
-    if let Some(skill) = CONFIG.predefined_skills.get(&skill_id) {
+    if let Some(skill) = config().predefined_skills.get(&skill_id) {
         let mut skill = skill.clone();
         skill.name = name;
         skill.description = desc;
@@ -50,6 +37,10 @@ pub fn load_skill(lua: &Lua, skill_id: u32) -> LuaResult<Skill> {
     let category = convert_al::to_skill_category(category);
 
     let buff = require_buff_data(lua, skill_id)?;
+    let icon: Option<String> = buff
+        .get("icon")
+        .with_context(context!("icon of buff with id {skill_id}"))?;
+
     let mut context = ReferencedWeaponsContext::default();
     search_referenced_weapons(
         &mut context,
@@ -66,11 +57,46 @@ pub fn load_skill(lua: &Lua, skill_id: u32) -> LuaResult<Skill> {
         category,
         name,
         description: desc,
+        icon,
         barrages: context.barrages,
         new_weapons: context.new_weapons,
     })
 }
 
+/// Loads only the description of a skill from the Lua state.
+pub fn load_skill_description(lua: &Lua, skill_id: u32) -> LuaResult<String> {
+    let pg: LuaTable = lua.globals().get("pg").context("global pg")?;
+    let skill_data_template: LuaTable = pg
+        .get("skill_data_template")
+        .context("global pg.skill_data_template")?;
+
+    let skill: LuaTable = skill_data_template
+        .get(skill_id)
+        .with_context(context!("skill with id {}", skill_id))?;
+    read_skill_description(&skill, skill_id)
+}
+
+/// Reads the description of a skill, filled in with its max level values.
+fn read_skill_description(skill: &LuaTable, skill_id: u32) -> LuaResult<String> {
+    let mut desc: String = skill
+        .get("desc")
+        .with_context(context!("desc of skill with id {}", skill_id))?;
+    let desc_add: Vec<Vec<Vec<String>>> = skill
+        .get("desc_add")
+        .with_context(context!("desc_add of skill with id {}", skill_id))?;
+
+    for (slot, data_set) in desc_add.iter().enumerate() {
+        if let Some(last) = data_set.last() {
+            if let Some(text) = last.first() {
+                let placeholder = format!("${}", slot + 1);
+                desc = desc.replace(&placeholder, text);
+            }
+        }
+    }
+
+    Ok(desc)
+}
+
 /// Loads skills from the Lua state.
 pub fn load_skills(lua: &Lua, skill_ids: Vec<u32>) -> LuaResult<Vec<Skill>> {
     skill_ids
@@ -109,6 +135,9 @@ pub fn load_equip(lua: &Lua, equip_id: u32) -> LuaResult<Equip> {
     let description: String = statistics
         .get("descrip")
         .with_context(context!("descrip for equip with id {equip_id}"))?;
+    let icon: String = statistics
+        .get("icon")
+        .with_context(context!("icon for equip with id {equip_id}"))?;
 
     let mut weapons = Vec::new();
     for weapon_id in weapon_ids {
@@ -160,7 +189,9 @@ pub fn load_equip(lua: &Lua, equip_id: u32) -> LuaResult<Equip> {
     Ok(Equip {
         equip_id,
         name,
+        names: LocalizedNames::default(), // Added by caller.
         description,
+        icon,
         rarity: convert_al::to_equip_rarity(
             statistics
                 .get("rarity")
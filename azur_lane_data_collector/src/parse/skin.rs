@@ -22,6 +22,7 @@ pub fn load_skin(set: &SkinSet) -> LuaResult<ShipSkin> {
         description: get!("desc"),
         words: load_words(set)?,
         words_extra: None, // loaded below
+        faces: Vec::new(), // filled from the assets later
     };
 
     if let Some(extra) = &set.words_extra {
@@ -48,6 +49,7 @@ fn load_words(set: &SkinSet) -> LuaResult<ShipSkinWords> {
     }
 
     Ok(ShipSkinWords {
+        voice_key: load_voice_key(set)?,
         description: get!("drop_descrip"),
         introduction: get!("profile"),
         acquisition: get!("unlock"),
@@ -119,6 +121,7 @@ fn load_words_extra(
     }));
 
     Ok(ShipSkinWords {
+        voice_key: load_voice_key_extra(set, base)?,
         description: get!("drop_descrip"),
         introduction: get!("profile"),
         acquisition: get!("unlock"),
@@ -149,6 +152,60 @@ fn load_words_extra(
     })
 }
 
+/// A voice key as specified by the skin template.
+enum VoiceKey {
+    /// Use the default key for the ship group.
+    Default,
+    /// The skin isn't voiced.
+    Unvoiced,
+    /// A specific key.
+    Key(String),
+}
+
+/// Reads a voice key from the skin template.
+///
+/// The game uses `0` to mean that the ship group's default key is used and `-1`
+/// when the skin isn't voiced at all. Otherwise it's the key itself.
+fn read_voice_key(set: &SkinSet, key: &str) -> LuaResult<VoiceKey> {
+    let value: LuaValue = set.template.get(key).with_context(context!(
+        "skin template {} for skin {}",
+        key,
+        set.skin_id
+    ))?;
+
+    Ok(match value {
+        LuaValue::Integer(0) => VoiceKey::Default,
+        LuaValue::Integer(n) if n > 0 => VoiceKey::Key(format!("cv-{n}")),
+        LuaValue::String(s) => match s.to_string_lossy() {
+            s if s.is_empty() => VoiceKey::Default,
+            s => VoiceKey::Key(format!("cv-{s}")),
+        },
+        _ => VoiceKey::Unvoiced,
+    })
+}
+
+fn load_voice_key(set: &SkinSet) -> LuaResult<Option<String>> {
+    Ok(match read_voice_key(set, "voice_key")? {
+        VoiceKey::Default => {
+            let group_id: u32 = set.template.get("ship_group").with_context(context!(
+                "skin template ship_group for skin {}",
+                set.skin_id
+            ))?;
+            Some(format!("cv-{group_id}"))
+        },
+        VoiceKey::Unvoiced => None,
+        VoiceKey::Key(key) => Some(key),
+    })
+}
+
+fn load_voice_key_extra(set: &SkinSet, base: &ShipSkinWords) -> LuaResult<Option<String>> {
+    // the extra lines may use a separate key, otherwise it's the same as the base
+    Ok(match read_voice_key(set, "voice_key_2")? {
+        VoiceKey::Key(key) => Some(key),
+        _ => base.voice_key.clone(),
+    })
+}
+
 pub fn to_main_screen(raw: Option<&str>) -> impl Iterator<Item = ShipMainScreenLine> + '_ {
     raw.into_iter()
         .flat_map(|s| s.split('|'))
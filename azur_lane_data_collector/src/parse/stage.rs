@@ -0,0 +1,32 @@
+use azur_lane::stage::*;
+use mlua::prelude::*;
+
+use crate::{context, parse};
+
+/// Construct stage data from its "chapter_template" entry.
+pub fn load_stage(lua: &Lua, id: u32, data: &LuaTable) -> LuaResult<Stage> {
+    /// Reads a value from the template; target-typed.
+    macro_rules! read {
+        ($field:expr) => {
+            data.get($field)
+                .with_context(context!("{} of stage with id {}", $field, id))?
+        };
+    }
+
+    // 0 indicates that no other stage needs to be cleared.
+    let required_stage_id: u32 = read!("pre_chapter");
+    let required_stage_id = (required_stage_id != 0).then_some(required_stage_id);
+
+    let awards: Vec<LuaTable> = read!("awards");
+    let drops = parse::reward::load_rewards(lua, awards)
+        .with_context(context!("awards of stage with id {id}"))?;
+
+    Ok(Stage {
+        stage_id: id,
+        code: read!("chapter_name"),
+        name: read!("name"),
+        required_stage_id,
+        commander_level: read!("unlocklevel"),
+        drops,
+    })
+}
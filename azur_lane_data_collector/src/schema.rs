@@ -0,0 +1,34 @@
+//! Implements the `schema` subcommand, exporting the data file's JSON schema.
+
+use std::fs;
+use std::io::{self, Write};
+
+use azur_lane::DefinitionData;
+
+use crate::log;
+
+/// Exports the JSON schema of the data file.
+#[derive(Debug, clap::Args)]
+pub struct SchemaArgs {
+    /// The file to write the schema to.
+    ///
+    /// If not specified, the schema is printed to stdout.
+    #[arg(short, long)]
+    out: Option<String>,
+}
+
+pub fn run(args: &SchemaArgs) -> anyhow::Result<()> {
+    let schema = DefinitionData::json_schema();
+
+    if let Some(path) = args.out.as_deref() {
+        let file = io::BufWriter::new(fs::File::create(path)?);
+        serde_json::to_writer_pretty(file, &schema)?;
+        log::info!("Wrote schema to `{path}`.");
+    } else {
+        let mut out = io::stdout().lock();
+        serde_json::to_writer_pretty(&mut out, &schema)?;
+        writeln!(out)?;
+    }
+
+    Ok(())
+}
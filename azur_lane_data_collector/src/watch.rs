@@ -0,0 +1,115 @@
+//! Implements the `--watch` mode, repeating the extraction when inputs change.
+//!
+//! The inputs are polled for changes to the modification time or size of
+//! their files. This avoids platform-specific file system notifications, and
+//! the game scripts are rarely modified more than a few times per minute.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use std::{fs, thread};
+
+use anyhow::Context as _;
+
+use crate::log;
+
+/// How often to check the inputs for changes.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The state of every file within the inputs.
+type Snapshot = HashMap<PathBuf, FileState>;
+
+/// The state of a single file used to detect changes.
+#[derive(Debug, PartialEq, Eq)]
+struct FileState {
+    modified: Option<SystemTime>,
+    len: u64,
+}
+
+/// Runs `extract` and then repeats it every time any file in the inputs
+/// changes.
+///
+/// Errors returned by `extract` are reported, but don't stop watching. This
+/// only returns if the inputs cannot be read at all.
+pub fn run(
+    inputs: &[String],
+    mut extract: impl FnMut() -> anyhow::Result<()>,
+) -> anyhow::Result<()> {
+    // take the snapshot before extracting so changes made during the
+    // extraction cause another run
+    let mut snapshot = take_snapshot(inputs)?;
+
+    loop {
+        if let Err(err) = extract() {
+            log::info!("Extraction failed: {err:#}");
+        }
+
+        log::info!("Watching {} file(s) for changes.", snapshot.len());
+        snapshot = wait_for_change(inputs, &snapshot)?;
+        log::info!("Inputs changed. Extracting again.");
+    }
+}
+
+/// Waits until the files differ from `last` and then no longer change for one
+/// poll interval, and returns the new state.
+///
+/// Waiting for the files to settle avoids starting a run while files are
+/// still being copied or written.
+fn wait_for_change(inputs: &[String], last: &Snapshot) -> anyhow::Result<Snapshot> {
+    let mut current = loop {
+        thread::sleep(POLL_INTERVAL);
+        let next = take_snapshot(inputs)?;
+        if next != *last {
+            break next;
+        }
+    };
+
+    loop {
+        thread::sleep(POLL_INTERVAL);
+        let next = take_snapshot(inputs)?;
+        if next == current {
+            return Ok(current);
+        }
+
+        current = next;
+    }
+}
+
+/// Records the state of all files within the inputs.
+fn take_snapshot(inputs: &[String]) -> anyhow::Result<Snapshot> {
+    let mut snapshot = HashMap::new();
+    for input in inputs {
+        // the input itself must be readable, unlike files within it
+        fs::read_dir(input).with_context(|| format!("cannot read input `{input}`"))?;
+        add_dir(&mut snapshot, Path::new(input));
+    }
+
+    Ok(snapshot)
+}
+
+/// Recursively adds the files in a directory to the snapshot.
+///
+/// Entries that can't be read are skipped. They may be in the process of
+/// being replaced, which will be noticed on the next poll.
+fn add_dir(snapshot: &mut Snapshot, dir: &Path) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let Ok(meta) = entry.metadata() else {
+            continue;
+        };
+
+        if meta.is_dir() {
+            add_dir(snapshot, &entry.path());
+        } else {
+            let state = FileState {
+                modified: meta.modified().ok(),
+                len: meta.len(),
+            };
+
+            snapshot.insert(entry.path(), state);
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use std::sync::atomic::{AtomicBool, Ordering};
 
+use houston_cmd::{ModalFieldError, ModalFields};
 use serenity::http::Http;
 use serenity::prelude::*;
 
@@ -59,7 +60,6 @@ impl<I: InteractionImpl> GenericContext<'_, I> {
     }
 
     /// Replies to the interaction with a new message.
-    #[allow(dead_code, reason = "i might use this later")]
     pub async fn reply(&self, create: CreateReply<'_>) -> Result {
         let has_sent = self.reply_state.load(Ordering::Relaxed);
 
@@ -123,6 +123,30 @@ impl ButtonContext<'_> {
     }
 }
 
+impl ModalContext<'_> {
+    /// Parses the submitted text inputs into `T`.
+    ///
+    /// Errors are converted into messages that can be shown to the user.
+    pub fn parse_fields<T: ModalFields>(&self) -> Result<T, HArgError> {
+        T::from_modal(&self.interaction.data).map_err(|err| match err {
+            ModalFieldError::Missing { custom_id } => {
+                HArgError::new(format!("`{custom_id}` is required."))
+            },
+            ModalFieldError::Length {
+                custom_id,
+                min,
+                max,
+            } => HArgError::new(format!(
+                "`{custom_id}` must be {min} to {max} characters long."
+            )),
+            ModalFieldError::Parse { custom_id, error } => match error.downcast::<HArgError>() {
+                Ok(err) => err,
+                Err(error) => HArgError::new(format!("`{custom_id}` is invalid: {error}")),
+            },
+        })
+    }
+}
+
 /// Helper trait so code can be shared between different [`GenericContext`]
 /// instatiations.
 ///
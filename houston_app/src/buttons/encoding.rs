@@ -1,6 +1,11 @@
-use std::io;
+use std::future::Future;
+use std::sync::Arc;
+use std::{fmt, io};
 
 use arrayvec::ArrayVec;
+use chrono::{TimeDelta, Utc};
+use hmac::{Hmac, Mac as _};
+use sha2::Sha256;
 use smallvec::SmallVec;
 use utils::str_as_data::b20bit;
 
@@ -15,28 +20,261 @@ pub type StackBuf = ArrayVec<u8, STACK>;
 /// Buffer used for coding when inline size is important.
 pub type Buf = SmallVec<[u8; 16]>;
 
+/// Length of the signature appended to custom IDs.
+///
+/// This is truncated to keep custom IDs short.
+const TAG_LEN: usize = 8;
+
+/// Length of the header of expiring custom IDs.
+///
+/// This is the marker, the flags, and the expiry timestamp.
+const HEADER_LEN: usize = 2 + size_of::<u32>();
+
+/// Maximum length of the encoded [`ButtonArgs`].
+///
+/// This leaves room for the header and signature so expiring, signed custom
+/// IDs still fit into Discord's limit of 100 characters.
+pub const MAX_PAYLOAD_LEN: usize = STACK - HEADER_LEN - TAG_LEN;
+
+/// Buffer used for encoding the [`ButtonArgs`] on-stack.
+type PayloadBuf = ArrayVec<u8, MAX_PAYLOAD_LEN>;
+
+tokio::task_local! {
+    /// Signer used by [`to_custom_id`] and [`from_custom_id`] in the current
+    /// task. Set via [`scope`].
+    static SIGNER: Option<Arc<Signer>>;
+}
+
+/// Signs and verifies custom IDs.
+///
+/// The first key signs new custom IDs. Every key is accepted when verifying,
+/// so previous keys can be kept around while rotating the secret.
+#[derive(Clone)]
+pub struct Signer {
+    keys: Vec<Hmac<Sha256>>,
+}
+
+impl fmt::Debug for Signer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Signer")
+            .field("keys", &self.keys.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Signer {
+    /// Creates a signer that signs with the given secret.
+    #[must_use]
+    pub fn new(secret: &[u8]) -> Self {
+        Self {
+            keys: vec![Self::key(secret)],
+        }
+    }
+
+    /// Additionally accepts custom IDs signed with a previous secret.
+    #[must_use]
+    pub fn with_previous(mut self, secret: &[u8]) -> Self {
+        self.keys.push(Self::key(secret));
+        self
+    }
+
+    fn key(secret: &[u8]) -> Hmac<Sha256> {
+        Hmac::new_from_slice(secret).expect("hmac accepts keys of any length")
+    }
+
+    /// Computes the truncated signature for a buffer.
+    fn sign(&self, slice: &[u8]) -> [u8; TAG_LEN] {
+        let mut key = self.keys[0].clone();
+        key.update(slice);
+
+        let mut tag = [0u8; TAG_LEN];
+        tag.copy_from_slice(&key.finalize().into_bytes()[..TAG_LEN]);
+        tag
+    }
+
+    /// Verifies and strips the signature from a decoded custom ID.
+    fn verify<'a>(&self, slice: &'a [u8]) -> Result<&'a [u8], InvalidSignature> {
+        let split = slice.len().checked_sub(TAG_LEN).ok_or(InvalidSignature)?;
+        let (data, tag) = slice.split_at(split);
+
+        let valid = self.keys.iter().any(|key| {
+            let mut key = key.clone();
+            key.update(data);
+            key.verify_truncated_left(tag).is_ok()
+        });
+
+        if valid {
+            Ok(data)
+        } else {
+            Err(InvalidSignature)
+        }
+    }
+}
+
+/// Error returned when a custom ID's signature is missing or doesn't match.
+///
+/// This happens for custom IDs that were tampered with or that were created
+/// before the signing key was changed.
+#[derive(Debug, thiserror::Error)]
+#[error("custom id signature is invalid")]
+pub struct InvalidSignature;
+
+/// Marks custom IDs that start with a header.
+///
+/// Custom IDs without a header start with the [`ButtonArgs`] variant index,
+/// which is a single byte below `0x80`, so this never collides with them.
+const HEADER_MARKER: u8 = 0xFF;
+
+/// Header flag: An expiry timestamp follows the header.
+const FLAG_EXPIRES: u8 = 1;
+
+/// Error returned when a custom ID has expired.
+#[derive(Debug, thiserror::Error)]
+#[error("custom id has expired")]
+pub struct Expired;
+
+/// Runs a future with the signer used to encode and decode custom IDs.
+///
+/// Outside of this scope, custom IDs are neither signed nor verified.
+pub async fn scope<F: Future>(signer: Option<Arc<Signer>>, f: F) -> F::Output {
+    SIGNER.scope(signer, f).await
+}
+
+/// Gets the signer for the current task, if any.
+fn current_signer() -> Option<Arc<Signer>> {
+    SIGNER.try_with(Option::clone).ok().flatten()
+}
+
+/// Checks and strips the header from a decoded custom ID.
+///
+/// Custom IDs without a header are returned unchanged.
+fn check_header(slice: &[u8]) -> Result<&[u8]> {
+    let [HEADER_MARKER, flags, rest @ ..] = slice else {
+        return Ok(slice);
+    };
+
+    anyhow::ensure!(flags & !FLAG_EXPIRES == 0, "custom id has unknown flags");
+
+    let mut rest = rest;
+    if flags & FLAG_EXPIRES != 0 {
+        let (expires_at, data) = rest.split_first_chunk().context("custom id lacks expiry")?;
+
+        let expires_at = u32::from_le_bytes(*expires_at);
+        if i64::from(expires_at) < Utc::now().timestamp() {
+            return Err(Expired.into());
+        }
+
+        rest = data;
+    }
+
+    Ok(rest)
+}
+
 /// Encodes a [`ButtonArgsRef`] as a custom ID.
-pub fn to_custom_id(args: ButtonArgsRef<'_>) -> String {
-    let mut buf = StackBuf::new();
+///
+/// If a `ttl` is given, the custom ID expires after that time.
+pub fn to_custom_id(args: ButtonArgsRef<'_>, ttl: Option<TimeDelta>) -> String {
+    let mut buf = PayloadBuf::new();
     write_button_args(&mut buf, args);
-    encode_custom_id(&buf)
+    encode_custom_id(&buf, ttl)
 }
 
 /// Decodes a [`ButtonArgs`] from a custom ID.
 pub fn from_custom_id(id: &str) -> Result<ButtonArgs> {
+    from_custom_id_with(id, current_signer().as_deref())
+}
+
+/// Decodes a [`ButtonArgs`] from a custom ID, verifying it with a specific
+/// signer.
+pub fn from_custom_id_with(id: &str, signer: Option<&Signer>) -> Result<ButtonArgs> {
     let mut data = StackBuf::new();
     b20bit::decode(&mut data, id)?;
-    read_button_args(&data)
+
+    let data = match signer {
+        Some(signer) => signer.verify(&data)?,
+        None => &data[..],
+    };
+
+    let data = check_header(data)?;
+    read_button_args(data)
 }
 
 /// Encodes a [`super::CustomData`] buffer as a custom ID.
-pub fn encode_custom_id(slice: &[u8]) -> String {
+///
+/// If a `ttl` is given, the custom ID expires after that time.
+pub fn encode_custom_id(slice: &[u8], ttl: Option<TimeDelta>) -> String {
+    encode_custom_id_with(slice, ttl, current_signer().as_deref())
+}
+
+/// Encodes a [`super::CustomData`] buffer as a custom ID, signing it with a
+/// specific signer.
+///
+/// If a `ttl` is given, a header with the expiry timestamp is prepended.
+/// If a signer is given, the signature is appended.
+///
+/// Without a signer, users can freely edit the expiry timestamp. In that case
+/// it only guards against stale components, not against crafted ones.
+pub fn encode_custom_id_with(
+    slice: &[u8],
+    ttl: Option<TimeDelta>,
+    signer: Option<&Signer>,
+) -> String {
+    if ttl.is_none() && signer.is_none() {
+        return encode_checked(slice);
+    }
+
+    let mut buf = Buf::with_capacity(HEADER_LEN + slice.len() + TAG_LEN);
+    if let Some(ttl) = ttl {
+        // saturate rather than wrap around for absurd ttls
+        let expires_at = Utc::now()
+            .checked_add_signed(ttl)
+            .and_then(|t| u32::try_from(t.timestamp()).ok())
+            .unwrap_or(u32::MAX);
+
+        buf.extend_from_slice(&[HEADER_MARKER, FLAG_EXPIRES]);
+        buf.extend_from_slice(&expires_at.to_le_bytes());
+    }
+
+    buf.extend_from_slice(slice);
+    if let Some(signer) = signer {
+        let tag = signer.sign(&buf);
+        buf.extend_from_slice(&tag);
+    }
+
+    encode_checked(&buf)
+}
+
+/// Encodes the final custom ID.
+///
+/// This logs an error if the result is longer than Discord accepts.
+fn encode_checked(slice: &[u8]) -> String {
+    if slice.len() > STACK {
+        log::error!(
+            "Custom ID is {} bytes, but at most {STACK} fit into 100 characters.",
+            slice.len()
+        );
+    }
+
     b20bit::to_string(slice)
 }
 
 /// Reads a [`super::CustomData`] buffer as a [`ButtonArgs`].
+///
+/// Custom IDs can be crafted by users, so this applies tighter limits than
+/// the defaults.
 pub fn read_button_args(slice: &[u8]) -> Result<ButtonArgs> {
-    Ok(serde_steph::from_slice(slice)?)
+    use serde::Deserialize as _;
+    use serde_steph::{Deserializer, DeserializerOptions};
+
+    const OPTIONS: DeserializerOptions = DeserializerOptions::new()
+        .max_depth(16)
+        .max_seq_len(STACK)
+        .max_bytes_len(STACK);
+
+    let mut de = Deserializer::from_slice(slice).with_options(OPTIONS);
+    let args = ButtonArgs::deserialize(&mut de)?;
+    de.end()?;
+    Ok(args)
 }
 
 /// Encodes a [`ButtonArgsRef`] into a buffer.
@@ -1,9 +1,10 @@
 use std::mem::swap;
 use std::ptr;
 
+use chrono::TimeDelta;
 use serenity::prelude::*;
 
-use crate::modules::{azur, core as core_mod, minigame, perks, starboard};
+use crate::modules::{azur, core as core_mod, minigame, perks, polls, profile, starboard};
 use crate::prelude::*;
 
 mod context;
@@ -12,6 +13,7 @@ mod encoding;
 mod test;
 
 pub use context::{ButtonContext, ModalContext};
+pub use encoding::Signer;
 
 pub mod prelude {
     pub use super::{
@@ -65,6 +67,15 @@ macro_rules! define_button_args {
         }
 
         impl ButtonArgs {
+            /// Gets the name of the variant.
+            fn name(&self) -> &'static str {
+                match self {
+                    $(
+                        Self::$name(_) => stringify!($name),
+                    )*
+                }
+            }
+
             async fn reply(self, ctx: ButtonContext<'_>) -> Result {
                 match self {
                     $(
@@ -133,6 +144,40 @@ define_button_args! {
     AzurSpecialSecretary(azur::buttons::special_secretary::View),
     /// Open the special secretary search.
     AzurSearchSpecialSecretary(azur::buttons::search_special_secretary::View),
+    /// Open the ship comparison.
+    AzurCompare(azur::buttons::compare::View),
+    /// Open the ship stat calculator.
+    AzurStats(azur::buttons::stats::View),
+    /// Open the loadout builder.
+    AzurLoadout(azur::buttons::loadout::View),
+    /// Open the drop location list.
+    AzurDrops(azur::buttons::drops::View),
+    /// Open the skill search.
+    AzurSearchSkill(azur::buttons::search_skill::View),
+    /// Open the skin painting gallery.
+    AzurSkins(azur::buttons::skins::View),
+    /// Play the next Othello turn.
+    MinigameOthello(minigame::buttons::othello::View),
+    /// Play the next blackjack action.
+    MinigameBlackjack(minigame::buttons::blackjack::View),
+    /// Guess the daily Wordle word.
+    MinigameWordle(minigame::buttons::wordle::View),
+    /// Answer a trivia question.
+    MinigameTriviaAnswer(minigame::buttons::trivia::Answer),
+    /// Play the next Minesweeper move.
+    MinigameMinesweeper(minigame::buttons::minesweeper::View),
+    /// Start a rematch of a finished minigame.
+    MinigameRematch(minigame::buttons::rematch::Rematch),
+    /// Show another random starboard post.
+    StarboardRandom(starboard::buttons::random::View),
+    /// Confirm gifting items to another member.
+    PerksGift(perks::buttons::gift::Confirm),
+    /// View a page of a member's wallet history.
+    PerksHistory(perks::buttons::history::View),
+    /// Open the modal to edit your server profile.
+    ProfileEdit(profile::buttons::Edit),
+    /// Vote on a poll.
+    PollVote(polls::buttons::Vote),
 }
 
 impl ButtonArgs {
@@ -145,16 +190,25 @@ impl ButtonArgs {
 /// Event handler for custom button menus.
 pub mod handler {
     use std::sync::atomic::AtomicBool;
+    use std::time::Instant;
 
     use super::*;
 
     /// To be called in [`EventHandler::interaction_create`].
     pub async fn interaction_create(ctx: Context, interaction: Interaction) {
+        let data = ctx.data_ref::<HContextData>();
+        scope(data, dispatch(ctx.clone(), interaction)).await;
+    }
+
+    async fn dispatch(ctx: Context, interaction: Interaction) {
+        let start = Instant::now();
         match interaction {
             Interaction::Component(interaction) => dispatch_component(ctx, interaction).await,
             Interaction::Modal(interaction) => dispatch_modal(ctx, interaction).await,
-            _ => {}, // we only handle component and modal interactions
+            _ => return, // we only handle component and modal interactions
         }
+
+        crate::metrics::record_button_dispatch(start.elapsed());
     }
 
     async fn dispatch_component(ctx: Context, interaction: ComponentInteraction) {
@@ -188,13 +242,18 @@ pub mod handler {
         let args = ButtonArgs::from_custom_id(custom_id)?;
         log::trace!("{}: {:?}", interaction.user.name, args);
 
-        args.reply(ButtonContext {
-            reply_state,
-            serenity: ctx,
-            interaction,
-            data: ctx.data_ref::<HContextData>(),
-        })
-        .await
+        let name = args.name();
+        let res = args
+            .reply(ButtonContext {
+                reply_state,
+                serenity: ctx,
+                interaction,
+                data: ctx.data_ref::<HContextData>(),
+            })
+            .await;
+
+        record_usage(ctx, interaction.guild_id, interaction.id, name, res.is_ok());
+        res
     }
 
     async fn dispatch_modal(ctx: Context, interaction: ModalInteraction) {
@@ -220,13 +279,40 @@ pub mod handler {
         let args = ButtonArgs::from_custom_id(&interaction.data.custom_id)?;
         log::trace!("{}: {:?}", interaction.user.name, args);
 
-        args.modal_reply(ModalContext {
-            reply_state,
-            serenity: ctx,
-            interaction,
-            data: ctx.data_ref::<HContextData>(),
-        })
-        .await
+        let name = args.name();
+        let res = args
+            .modal_reply(ModalContext {
+                reply_state,
+                serenity: ctx,
+                interaction,
+                data: ctx.data_ref::<HContextData>(),
+            })
+            .await;
+
+        record_usage(ctx, interaction.guild_id, interaction.id, name, res.is_ok());
+        res
+    }
+
+    /// Records the usage of a button or modal for analytics.
+    fn record_usage(
+        ctx: &Context,
+        guild_id: Option<GuildId>,
+        interaction_id: InteractionId,
+        name: &str,
+        success: bool,
+    ) {
+        use crate::modules::analytics::model::UsageKind;
+        use crate::modules::analytics::{self};
+
+        let latency = analytics::interaction_latency(interaction_id);
+        analytics::record(
+            ctx,
+            guild_id,
+            UsageKind::Button,
+            name.to_owned(),
+            success,
+            latency,
+        );
     }
 
     #[cold]
@@ -245,6 +331,14 @@ pub mod handler {
 
         let err_text = match err.downcast::<HArgError>() {
             Ok(err) => err.msg,
+            Err(err) if err.is::<encoding::Expired>() => {
+                let data = ctx.data_ref::<HContextData>();
+                data.button_expired_notice().to_owned().into()
+            },
+            Err(err) if err.is::<encoding::InvalidSignature>() => {
+                log::debug!("Rejected component: {err:?}");
+                "This component is outdated or invalid. Run the command again.".into()
+            },
             Err(err) => {
                 log::warn!("Component error: {err:?}");
                 format!("Button error: ```{err}```").into()
@@ -277,6 +371,14 @@ pub mod handler {
     }
 }
 
+/// Runs a future with the bot's custom ID signer.
+///
+/// Custom IDs are only signed and verified within this scope, so any task that
+/// creates or reads components needs to run inside it.
+pub async fn scope<F: std::future::Future>(data: &HBotData, f: F) -> F::Output {
+    encoding::scope(data.button_signer(), f).await
+}
+
 /// Provides a way to convert an object into a component custom ID.
 ///
 /// This is auto-implemented for every type held by [`ButtonArgs`].
@@ -285,6 +387,15 @@ pub trait ToCustomData {
     #[must_use]
     fn to_custom_id(&self) -> String;
 
+    /// Converts this instance to a component custom ID that expires after
+    /// `ttl`.
+    ///
+    /// Use this for components that act on state that can go stale, such as
+    /// confirmations. The expiry is only tamper-proof when custom IDs are
+    /// signed, so don't rely on it for anything users shouldn't bypass.
+    #[must_use]
+    fn to_expiring_custom_id(&self, ttl: TimeDelta) -> String;
+
     /// Converts this instance to custom data.
     #[must_use]
     fn to_custom_data(&self) -> CustomData;
@@ -356,7 +467,11 @@ where
     for<'a> &'a T: Into<ButtonArgsRef<'a>>,
 {
     fn to_custom_id(&self) -> String {
-        encoding::to_custom_id(self.into())
+        encoding::to_custom_id(self.into(), None)
+    }
+
+    fn to_expiring_custom_id(&self, ttl: TimeDelta) -> String {
+        encoding::to_custom_id(self.into(), Some(ttl))
     }
 
     fn to_custom_data(&self) -> CustomData {
@@ -424,7 +539,7 @@ impl CustomData {
     /// Converts this instance to a component custom ID.
     #[must_use]
     pub fn to_custom_id(&self) -> String {
-        encoding::encode_custom_id(&self.0)
+        encoding::encode_custom_id(&self.0, None)
     }
 
     /// Converts this instance to [`ButtonArgs`].
@@ -33,9 +33,182 @@ round_trip_test!(round_trip_args_augment, AzurAugment => azur::buttons::augment:
 round_trip_test!(round_trip_args_skill, AzurSkill => { use azur::buttons::skill::*; View::with_back(ViewSource::Augment(1), CustomData::EMPTY) });
 round_trip_test!(round_trip_args_lines, AzurLines => azur::buttons::lines::View::with_back(9999, CustomData::EMPTY));
 round_trip_test!(round_trip_args_equip, AzurEquip => azur::buttons::equip::View::new(9999));
+round_trip_test!(round_trip_args_compare, AzurCompare => azur::buttons::compare::View::new(9999, 8888));
+round_trip_test!(round_trip_args_stats, AzurStats => azur::buttons::stats::View::new(9999));
+round_trip_test!(round_trip_args_loadout, AzurLoadout => { let mut view = azur::buttons::loadout::View::new(9999); view.equips[0] = Some(123_456); view.editing = Some(1); view });
+round_trip_test!(round_trip_args_drops, AzurDrops => { use azur::buttons::drops::*; View::new(DropSource::Equip(9999)) });
+round_trip_test!(round_trip_args_skins, AzurSkins => azur::buttons::skins::View::with_back(9999, CustomData::EMPTY));
+round_trip_test!(round_trip_args_othello, MinigameOthello => minigame::buttons::othello::View::new([UserId::new(1), UserId::new(2)]));
+round_trip_test!(round_trip_args_minesweeper, MinigameMinesweeper => minigame::buttons::minesweeper::View::new(UserId::new(1), minigame::buttons::minesweeper::Size::Large));
+round_trip_test!(round_trip_args_wordle, MinigameWordle => minigame::buttons::wordle::View::new(GuildId::new(1)));
+round_trip_test!(round_trip_args_rematch, MinigameRematch => minigame::buttons::rematch::Rematch::new(minigame::stats::GameKind::Chess, [UserId::new(1), UserId::new(2)]));
+round_trip_test!(round_trip_args_poll_vote, PollVote => polls::buttons::Vote::new(3));
 
 #[test]
 fn eq_direct_to_custom_id() {
     let view = azur::buttons::ship::View::new(9999);
     assert_eq!(view.to_custom_id(), view.to_custom_data().to_custom_id());
 }
+
+mod signing {
+    use utils::str_as_data::b20bit;
+
+    use super::*;
+    use crate::buttons::encoding::{self, InvalidSignature, Signer, StackBuf};
+
+    fn signed_id(signer: &Signer) -> String {
+        let data = azur::buttons::ship::View::new(9999).to_custom_data();
+        encoding::encode_custom_id_with(&data.0, None, Some(signer))
+    }
+
+    pub(super) fn decode(id: &str) -> StackBuf {
+        let mut buf = StackBuf::new();
+        b20bit::decode(&mut buf, id).expect("must be valid b20bit");
+        buf
+    }
+
+    fn is_invalid_signature(res: Result<ButtonArgs>) -> bool {
+        res.is_err_and(|e| e.is::<InvalidSignature>())
+    }
+
+    #[test]
+    fn accepts_valid() {
+        let signer = Signer::new(b"secret");
+        let id = signed_id(&signer);
+
+        let args = encoding::from_custom_id_with(&id, Some(&signer)).expect("must be valid");
+        assert_eq!(
+            args,
+            ButtonArgs::AzurShip(azur::buttons::ship::View::new(9999))
+        );
+    }
+
+    #[test]
+    fn accepts_previous_key() {
+        let old = Signer::new(b"old");
+        let id = signed_id(&old);
+
+        let new = Signer::new(b"new").with_previous(b"old");
+        assert!(encoding::from_custom_id_with(&id, Some(&new)).is_ok());
+    }
+
+    #[test]
+    fn rejects_other_key() {
+        let id = signed_id(&Signer::new(b"secret"));
+        let other = Signer::new(b"other");
+        assert!(is_invalid_signature(encoding::from_custom_id_with(
+            &id,
+            Some(&other)
+        )));
+    }
+
+    #[test]
+    fn rejects_tampered() {
+        let signer = Signer::new(b"secret");
+        let mut data = decode(&signed_id(&signer));
+        data[1] ^= 1;
+
+        let id = b20bit::to_string(&data);
+        assert!(is_invalid_signature(encoding::from_custom_id_with(
+            &id,
+            Some(&signer)
+        )));
+    }
+
+    #[test]
+    fn rejects_truncated() {
+        let signer = Signer::new(b"secret");
+        let data = decode(&signed_id(&signer));
+
+        for len in 0..data.len() {
+            let id = b20bit::to_string(&data[..len]);
+            assert!(is_invalid_signature(encoding::from_custom_id_with(
+                &id,
+                Some(&signer)
+            )));
+        }
+    }
+
+    #[test]
+    fn max_payload_fits() {
+        let signer = Signer::new(b"secret");
+        let data = [0x7F; encoding::MAX_PAYLOAD_LEN];
+        let id = encoding::encode_custom_id_with(&data, None, Some(&signer));
+
+        assert!(id.chars().count() <= 100, "custom id too long: {id}");
+        assert_eq!(decode(&id).len(), encoding::MAX_PAYLOAD_LEN + 8);
+    }
+
+    #[test]
+    fn rejects_unsigned() {
+        let signer = Signer::new(b"secret");
+        let data = azur::buttons::ship::View::new(9999).to_custom_data();
+        let id = encoding::encode_custom_id_with(&data.0, None, None);
+
+        assert!(is_invalid_signature(encoding::from_custom_id_with(
+            &id,
+            Some(&signer)
+        )));
+    }
+}
+
+mod expiry {
+    use chrono::TimeDelta;
+
+    use super::signing::decode;
+    use super::*;
+    use crate::buttons::encoding::{self, Expired, Signer};
+
+    fn args() -> azur::buttons::ship::View {
+        azur::buttons::ship::View::new(9999)
+    }
+
+    #[test]
+    fn accepts_unexpired() {
+        let id = args().to_expiring_custom_id(TimeDelta::hours(1));
+        let re_args = ButtonArgs::from_custom_id(&id).expect("must be valid");
+        assert_eq!(re_args, ButtonArgs::AzurShip(args()));
+    }
+
+    #[test]
+    fn rejects_expired() {
+        let id = args().to_expiring_custom_id(TimeDelta::seconds(-10));
+        let res = ButtonArgs::from_custom_id(&id);
+        assert!(res.is_err_and(|e| e.is::<Expired>()));
+    }
+
+    #[test]
+    fn no_expiry_unchanged() {
+        // custom ids without expiry keep the format from before expiry existed
+        let id = args().to_custom_id();
+        assert_eq!(id, args().to_custom_data().to_custom_id());
+
+        let re_args = ButtonArgs::from_custom_id(&id).expect("must be valid");
+        assert_eq!(re_args, ButtonArgs::AzurShip(args()));
+    }
+
+    #[test]
+    fn max_payload_fits() {
+        let signer = Signer::new(b"secret");
+        let data = [0x7F; encoding::MAX_PAYLOAD_LEN];
+        let id = encoding::encode_custom_id_with(&data, Some(TimeDelta::hours(1)), Some(&signer));
+
+        assert!(id.chars().count() <= 100, "custom id too long: {id}");
+        assert_eq!(decode(&id).len(), encoding::MAX_PAYLOAD_LEN + 6 + 8);
+    }
+
+    #[test]
+    fn signed_expiry() {
+        let signer = Signer::new(b"secret");
+        let data = args().to_custom_data();
+
+        let id = encoding::encode_custom_id_with(&data.0, Some(TimeDelta::hours(1)), Some(&signer));
+        let re_args = encoding::from_custom_id_with(&id, Some(&signer)).expect("must be valid");
+        assert_eq!(re_args, ButtonArgs::AzurShip(args()));
+
+        let id =
+            encoding::encode_custom_id_with(&data.0, Some(TimeDelta::seconds(-10)), Some(&signer));
+        let res = encoding::from_custom_id_with(&id, Some(&signer));
+        assert!(res.is_err_and(|e| e.is::<Expired>()));
+    }
+}
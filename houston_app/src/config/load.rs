@@ -0,0 +1,41 @@
+use std::borrow::Cow;
+use std::env::var;
+use std::env::VarError::NotPresent;
+
+use anyhow::Context as _;
+
+use super::HConfig;
+
+fn profile() -> anyhow::Result<Cow<'static, str>> {
+    match var("HOUSTON_PROFILE") {
+        Ok(value) => Ok(value.into()),
+        Err(NotPresent) => Ok("release".into()),
+        Err(err) => Err(err).context("cannot load HOUSTON_PROFILE env variable"),
+    }
+}
+
+/// Builds the config from the config files and environment variables.
+pub fn build_config() -> anyhow::Result<HConfig> {
+    use config_rs::{Config, Environment, File, FileFormat};
+
+    let profile = profile()?;
+    let profile_config = format!("houston_app.{profile}.toml");
+
+    let config = Config::builder()
+        .add_source(File::new("houston_app.toml", FileFormat::Toml).required(false))
+        .add_source(File::new(&profile_config, FileFormat::Toml).required(false))
+        .add_source(Environment::default().separator("__"))
+        // defaults for logging
+        .set_default("log.root.level", "warn")?
+        .set_default("log.root.appenders[0]", "default")?
+        .set_default("log.appenders.default.kind", "default")?
+        .set_default("log.appenders.default.encoder.kind", "default")?
+        .set_default("log.loggers.houston_app.level", "trace")?
+        .set_default("log.loggers.houston_cmd.level", "trace")?
+        .build()
+        .context("cannot build config")?
+        .try_deserialize()
+        .context("cannot deserialize config")?;
+
+    Ok(config)
+}
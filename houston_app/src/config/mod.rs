@@ -1,4 +1,5 @@
 #![allow(dead_code, reason = "config might be partly unused")]
+use std::num::NonZero;
 use std::path::PathBuf;
 
 use serde::Deserialize;
@@ -6,14 +7,18 @@ use serenity::model::Color;
 use serenity::secrets::Token;
 
 pub mod azur_lane;
+mod load;
 mod token_parse;
 
+pub use load::build_config;
+
 #[derive(Debug, Deserialize)]
 pub struct HConfig {
     pub discord: HDiscordConfig,
     pub bot: HBotConfig,
     #[serde(default)]
     pub log: HLogConfig,
+    pub metrics: Option<crate::metrics::Config>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -21,6 +26,15 @@ pub struct HDiscordConfig {
     #[serde(with = "token_parse")]
     pub token: Token,
     pub status: Option<String>,
+    /// The total shard count. If unset, Discord's recommended count is used.
+    pub shards: Option<NonZero<u16>>,
+    /// Secret used to sign component custom IDs. If unset, they are unsigned.
+    pub button_secret: Option<String>,
+    /// Previous secrets whose signatures are still accepted while rotating.
+    #[serde(default)]
+    pub button_previous_secrets: Vec<String>,
+    /// The notice shown when an expired component is used.
+    pub button_expired_notice: Option<String>,
 }
 
 const fn default_embed_color() -> Color {
@@ -36,15 +50,69 @@ pub struct HBotConfig {
     #[serde(default)]
     pub media_react: crate::modules::media_react::Config,
     #[serde(default)]
+    pub guild_log: crate::modules::guild_log::Config,
+    #[serde(default)]
+    pub welcome: crate::modules::welcome::Config,
+    #[serde(default)]
+    pub auto_publish: crate::modules::auto_publish::Config,
+    #[serde(default)]
     pub starboard: crate::modules::starboard::Config,
     pub perks: Option<crate::modules::perks::Config>,
+    #[serde(default)]
+    pub minigame: crate::modules::minigame::Config,
+    pub tags: Option<crate::modules::tags::Config>,
+    pub polls: Option<crate::modules::polls::Config>,
+    pub moderation: Option<crate::modules::moderation::Config>,
+    pub quotes: Option<crate::modules::quotes::Config>,
+    pub sticky: Option<crate::modules::sticky::Config>,
+    pub translate: Option<crate::modules::translate::Config>,
+    pub analytics: Option<crate::modules::analytics::Config>,
 }
 
 impl HBotConfig {
+    /// Carries runtime state over from a previously loaded config.
+    pub fn carry_state_from(&mut self, previous: &Self) {
+        if let (Some(perks), Some(previous)) = (&mut self.perks, &previous.perks) {
+            perks.carry_state_from(previous);
+        }
+    }
+
     pub fn perks(&self) -> anyhow::Result<&crate::modules::perks::Config> {
         use anyhow::Context as _;
         self.perks.as_ref().context("perks must be enabled")
     }
+
+    pub fn tags(&self) -> anyhow::Result<&crate::modules::tags::Config> {
+        use anyhow::Context as _;
+        self.tags.as_ref().context("tags must be enabled")
+    }
+
+    pub fn polls(&self) -> anyhow::Result<&crate::modules::polls::Config> {
+        use anyhow::Context as _;
+        self.polls.as_ref().context("polls must be enabled")
+    }
+
+    pub fn moderation(&self) -> anyhow::Result<&crate::modules::moderation::Config> {
+        use anyhow::Context as _;
+        self.moderation
+            .as_ref()
+            .context("moderation must be enabled")
+    }
+
+    pub fn quotes(&self) -> anyhow::Result<&crate::modules::quotes::Config> {
+        use anyhow::Context as _;
+        self.quotes.as_ref().context("quotes must be enabled")
+    }
+
+    pub fn sticky(&self) -> anyhow::Result<&crate::modules::sticky::Config> {
+        use anyhow::Context as _;
+        self.sticky.as_ref().context("sticky must be enabled")
+    }
+
+    pub fn translate(&self) -> anyhow::Result<&crate::modules::translate::Config> {
+        use anyhow::Context as _;
+        self.translate.as_ref().context("translate must be enabled")
+    }
 }
 
 #[derive(Debug, Default, Deserialize)]
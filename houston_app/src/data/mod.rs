@@ -1,5 +1,7 @@
-use std::sync::{LazyLock, OnceLock};
+use std::sync::{Arc, LazyLock, OnceLock};
 
+use arc_swap::{ArcSwap, ArcSwapOption};
+use serenity::gateway::ShardManager;
 use serenity::http::Http;
 
 use crate::config::HBotConfig;
@@ -40,15 +42,25 @@ impl HArgError {
 #[derive(Debug)]
 pub struct HBotData {
     /// The bot configuration.
-    config: HBotConfig,
+    ///
+    /// Replaced by [`Self::reload_config`].
+    config: ArcSwap<HBotConfig>,
     /// The current bot user.
     current_user: OnceLock<CurrentUser>,
     /// The loaded application emojis.
     app_emojis: OnceLock<app_emojis::HAppEmojiStore>,
     /// Lazily initialized Azur Lane data.
-    azur_lane: LazyLock<HAzurLane, Box<dyn Send + FnOnce() -> HAzurLane>>,
+    ///
+    /// Replaced by [`Self::reload_azur_lane`].
+    azur_lane: LazyLock<ArcSwap<HAzurLane>, Box<dyn Send + FnOnce() -> ArcSwap<HAzurLane>>>,
     /// Database connection.
     database: OnceLock<mongodb::Database>,
+    /// The shard manager of the running client.
+    shard_manager: OnceLock<Arc<ShardManager>>,
+    /// Signer for component custom IDs, if any.
+    button_signer: ArcSwapOption<crate::buttons::Signer>,
+    /// Notice shown when an expired component is used.
+    button_expired_notice: OnceLock<String>,
 }
 
 impl HBotData {
@@ -57,14 +69,19 @@ impl HBotData {
     pub fn new(config: HBotConfig) -> Self {
         let data_path = config.azur_lane_data.clone();
         Self {
-            config,
+            config: ArcSwap::from_pointee(config),
             current_user: OnceLock::new(),
             app_emojis: OnceLock::new(),
             azur_lane: LazyLock::new(match data_path {
-                Some(data_path) => Box::new(move || HAzurLane::load_from(data_path)),
-                None => Box::new(HAzurLane::default),
+                Some(data_path) => {
+                    Box::new(move || ArcSwap::from_pointee(HAzurLane::load_from(data_path)))
+                },
+                None => Box::new(|| ArcSwap::from_pointee(HAzurLane::default())),
             }),
             database: OnceLock::new(),
+            shard_manager: OnceLock::new(),
+            button_signer: ArcSwapOption::empty(),
+            button_expired_notice: OnceLock::new(),
         }
     }
 
@@ -74,9 +91,62 @@ impl HBotData {
     }
 
     /// Gets the bot configuration.
+    ///
+    /// This is a snapshot that stays valid even if the config is reloaded.
+    /// Hold onto it for as long as anything borrows from it.
     #[must_use]
-    pub fn config(&self) -> &HBotConfig {
-        &self.config
+    pub fn config(&self) -> Arc<HBotConfig> {
+        self.config.load_full()
+    }
+
+    /// Replaces the current configuration with a newly loaded one.
+    ///
+    /// Only settings that are read at runtime can be replaced. If the new
+    /// config requires a restart to apply, f.e. because modules are enabled
+    /// or commands change, it is rejected with a list of these changes.
+    ///
+    /// Runtime state held by the config, such as when the rainbow role was
+    /// last updated, is carried over to the new config. The previous config
+    /// is dropped once the last snapshot of it is released.
+    pub fn reload_config(&self, mut config: HBotConfig) -> Result<Arc<HBotConfig>> {
+        use crate::modules::Info;
+
+        let current = self.config();
+
+        let mut current_init = Info::new();
+        current_init.load(&current)?;
+
+        let mut new_init = Info::new();
+        new_init
+            .load(&config)
+            .context("the new config is invalid")?;
+
+        let mut changes = current_init.restart_changes(&new_init);
+
+        if current.mongodb_uri != config.mongodb_uri {
+            changes.push("The MongoDB URI changed.".to_owned());
+        }
+
+        if current.azur_lane_data != config.azur_lane_data {
+            changes.push("The Azur Lane data path changed.".to_owned());
+        }
+
+        if !changes.is_empty() {
+            let mut msg =
+                "The config cannot be reloaded. These changes require a restart:".to_owned();
+            for change in changes {
+                msg.push_str("\n- ");
+                msg.push_str(&change);
+            }
+
+            return Err(HArgError::new(msg).into());
+        }
+
+        config.carry_state_from(&current);
+
+        let config = Arc::new(config);
+        self.config.store(Arc::clone(&config));
+        Ok(config)
     }
 
     /// Gets the loaded app emojis.
@@ -92,7 +162,7 @@ impl HBotData {
         if self.app_emojis.get().is_none() {
             _ = self
                 .app_emojis
-                .set(app_emojis::HAppEmojiStore::load_and_update(&self.config, ctx).await?);
+                .set(app_emojis::HAppEmojiStore::load_and_update(&self.config(), ctx).await?);
             log::info!("Loaded App Emojis.");
         }
 
@@ -111,14 +181,33 @@ impl HBotData {
     }
 
     /// Gets the Azur Lane game data.
+    ///
+    /// This is a snapshot that stays valid even if the data is reloaded.
+    /// Hold onto it for as long as anything borrows from it.
     #[must_use]
-    pub fn azur_lane(&self) -> &HAzurLane {
-        &self.azur_lane
+    pub fn azur_lane(&self) -> Arc<HAzurLane> {
+        self.azur_lane.load_full()
+    }
+
+    /// Reloads the Azur Lane game data from disk and replaces the current
+    /// data with it.
+    ///
+    /// The previous data is dropped once the last snapshot of it is released.
+    pub fn reload_azur_lane(&self) -> Result<Arc<HAzurLane>> {
+        let data_path = self
+            .config()
+            .azur_lane_data
+            .clone()
+            .context("azur lane data is not configured")?;
+
+        let data = Arc::new(HAzurLane::try_load_from(data_path)?);
+        self.azur_lane.store(Arc::clone(&data));
+        Ok(data)
     }
 
     /// Connects to the database and other needed services.
     pub async fn connect(&self, init: &crate::modules::Info) -> Result {
-        if let Some(uri) = &self.config.mongodb_uri {
+        if let Some(uri) = &self.config().mongodb_uri {
             let client = mongodb::Client::with_uri_str(uri)
                 .await
                 .context("failed to connect to database cluster")?;
@@ -145,6 +234,46 @@ impl HBotData {
     pub fn database(&self) -> Result<&mongodb::Database> {
         self.database.get().context("database is not yet connected")
     }
+
+    /// Gets the shard manager of the running client.
+    pub fn shard_manager(&self) -> Result<&Arc<ShardManager>> {
+        self.shard_manager.get().context("shard manager not set")
+    }
+
+    /// Sets the shard manager of the running client.
+    pub fn set_shard_manager(&self, shard_manager: Arc<ShardManager>) {
+        _ = self.shard_manager.set(shard_manager);
+    }
+
+    /// Gets the signer for component custom IDs, if any.
+    #[must_use]
+    pub fn button_signer(&self) -> Option<Arc<crate::buttons::Signer>> {
+        self.button_signer.load_full()
+    }
+
+    /// Replaces the signer for component custom IDs.
+    ///
+    /// Components created with a previous secret are rejected unless the new
+    /// signer still accepts it via [`Signer::with_previous`].
+    ///
+    /// [`Signer::with_previous`]: crate::buttons::Signer::with_previous
+    pub fn set_button_signer(&self, signer: Option<crate::buttons::Signer>) {
+        self.button_signer.store(signer.map(Arc::new));
+    }
+
+    /// Gets the notice shown when an expired component is used.
+    #[must_use]
+    pub fn button_expired_notice(&self) -> &str {
+        self.button_expired_notice.get().map_or(
+            "This menu has expired. Run the command again.",
+            String::as_str,
+        )
+    }
+
+    /// Sets the notice shown when an expired component is used.
+    pub fn set_button_expired_notice(&self, notice: String) {
+        _ = self.button_expired_notice.set(notice);
+    }
 }
 
 pub struct Ephemeral;
@@ -9,7 +9,7 @@ pub struct EquipStats<'a>(&'a Equip);
 
 /// Implements [`Display`] to nicely format a augment stats.
 #[must_use]
-pub struct AugmentStats<'a>(&'a Augment);
+pub struct AugmentStats<'a>(&'a [AugmentStatBonus]);
 
 impl<'a> EquipStats<'a> {
     pub fn new(equip: &'a Equip) -> Self {
@@ -19,7 +19,11 @@ impl<'a> EquipStats<'a> {
 
 impl<'a> AugmentStats<'a> {
     pub fn new(augment: &'a Augment) -> Self {
-        Self(augment)
+        Self(&augment.stat_bonuses)
+    }
+
+    pub fn with_level(level: &'a AugmentLevel) -> Self {
+        Self(&level.stat_bonuses)
     }
 }
 
@@ -31,11 +35,7 @@ impl Display for EquipStats<'_> {
 
 impl Display for AugmentStats<'_> {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
-        write_stats(
-            &self.0.stat_bonuses,
-            |i| (i.stat_kind, i.amount + i.random),
-            f,
-        )
+        write_stats(self.0, |i| (i.stat_kind, i.amount + i.random), f)
     }
 }
 
@@ -59,19 +59,8 @@ impl Display for Details<'_> {
 }
 
 fn format_fire_rate(weapon: &Weapon, f: &mut Formatter<'_>) -> Result {
-    let salvo_time = match &weapon.data {
-        WeaponData::Bullets(b) => b.salvo_time,
-        _ => 0.0,
-    };
-
-    let weapon_kind_reload_mult = if weapon.kind == WeaponKind::StrikeAircraft {
-        2.2
-    } else {
-        1.0
-    };
-
-    let reload_time = weapon.reload_time * weapon_kind_reload_mult;
-    let fixed_delay = weapon.fixed_delay + salvo_time;
+    let reload_time = weapon.reload_time_at(100.0);
+    let fixed_delay = weapon.total_fixed_delay();
     writeln!(
         f,
         "**FR:** {:.2} +{:.2}s (~{:.1}/min)",
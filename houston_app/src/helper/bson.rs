@@ -24,7 +24,8 @@ macro_rules! doc_object_id {
     };
 }
 
-pub(crate) use {bson_id, doc_object_id};
+pub(crate) use bson_id;
+pub(crate) use doc_object_id;
 
 /// Creates the specified indices.
 ///
@@ -87,6 +88,18 @@ where
     }
 }
 
+/// Checks whether an error is due to a unique index conflict.
+pub fn is_duplicate_key(err: &mongodb::error::Error) -> bool {
+    use mongodb::error::{CommandError, ErrorKind, WriteError, WriteFailure};
+
+    // code 11000 `DuplicateKey`
+    match &*err.kind {
+        ErrorKind::Command(CommandError { code, .. }) => *code == 11000,
+        ErrorKind::Write(WriteFailure::WriteError(WriteError { code, .. })) => *code == 11000,
+        _ => false,
+    }
+}
+
 /// Serializes a Discord ID as an [`i64`].
 pub mod id_as_i64 {
     use serde::de::Error;
@@ -115,3 +128,39 @@ pub mod id_as_i64 {
         int.serialize(serializer)
     }
 }
+
+/// Serializes an optional Discord ID as an [`i64`] or null.
+///
+/// Fields using this should also be marked `#[serde(default)]`.
+pub mod opt_id_as_i64 {
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn deserialize<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: From<u64>,
+    {
+        #[allow(clippy::cast_sign_loss)]
+        match Option::<i64>::deserialize(deserializer)?.map(|i| i as u64) {
+            Some(u64::MAX) => Err(D::Error::custom("invalid discord id")),
+            Some(int) => Ok(Some(T::from(int))),
+            None => Ok(None),
+        }
+    }
+
+    #[allow(clippy::ref_option, reason = "serde passes fields by reference")]
+    pub fn serialize<S, T>(val: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        T: Into<i64> + Copy,
+    {
+        match *val {
+            Some(val) => {
+                let int: i64 = val.into();
+                serializer.serialize_some(&int)
+            },
+            None => serializer.serialize_none(),
+        }
+    }
+}
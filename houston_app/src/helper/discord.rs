@@ -51,6 +51,78 @@ impl WithPartial for Member {
     type Partial = PartialMember;
 }
 
+/// Checks whether a result is a Discord error with the code 10008 (Unknown
+/// Message).
+pub fn is_unknown_message<T>(result: &Result<T, serenity::Error>) -> bool {
+    use serenity::http::{HttpError, JsonErrorCode as J};
+
+    matches!(
+        result,
+        Err(serenity::Error::Http(HttpError::UnsuccessfulRequest(why)))
+            if why.error.code == J::UnknownMessage
+    )
+}
+
+/// Gets the failed request's response if the error is a Discord error with the
+/// code 50013 (Missing Permissions).
+pub fn missing_permissions(err: &anyhow::Error) -> Option<&serenity::http::ErrorResponse> {
+    err.downcast_ref::<serenity::Error>()
+        .and_then(missing_permissions_response)
+}
+
+fn missing_permissions_response(err: &serenity::Error) -> Option<&serenity::http::ErrorResponse> {
+    use serenity::http::{HttpError, JsonErrorCode as J};
+
+    match err {
+        serenity::Error::Http(HttpError::UnsuccessfulRequest(why))
+            if why.error.code == J::MissingPermissions =>
+        {
+            Some(why)
+        },
+        _ => None,
+    }
+}
+
+/// Logs an error from an event handler.
+///
+/// If the bot lacks the permissions for a request, this is logged as a warning
+/// naming the request instead, since only the server's moderators can fix it.
+macro_rules! log_event_error {
+    ($err:expr, $($context:tt)+) => {
+        match $crate::helper::discord::missing_permissions(&$err) {
+            Some(response) => ::log::warn!(
+                "{}: missing permissions for {} {}",
+                format_args!($($context)+),
+                response.method,
+                response.url,
+            ),
+            None => ::log::error!("{}: {:?}", format_args!($($context)+), $err),
+        }
+    };
+}
+
+pub(crate) use log_event_error;
+
+/// Ensures the bot has all the `needed` permissions in a channel.
+///
+/// `app_permissions` should be the bot's permissions in the channel, as sent
+/// along with interactions. Returns a user-facing error naming the missing
+/// permissions otherwise.
+pub fn require_permissions(
+    app_permissions: Permissions,
+    channel_id: ChannelId,
+    needed: Permissions,
+) -> Result {
+    let missing = needed.difference(app_permissions);
+    if missing.is_empty() {
+        return Ok(());
+    }
+
+    let names = missing.get_permission_names().join(", ");
+    let msg = format!("I'm missing **{names}** in <#{channel_id}>.");
+    Err(HArgError::new(msg).into())
+}
+
 /// Serializes a Discord ID as an [`u64`].
 pub mod id_as_u64 {
     // LEB128 isn't really efficient for Discord IDs so circumvent that by encoding
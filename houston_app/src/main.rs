@@ -5,6 +5,7 @@ mod data;
 mod fmt;
 mod helper;
 mod logging;
+mod metrics;
 mod modules;
 mod prelude;
 mod slashies;
@@ -16,7 +17,8 @@ async fn main() -> anyhow::Result<()> {
     use std::sync::Arc;
 
     use houston_cmd::Framework;
-    use serenity::gateway::ActivityData;
+    use serenity::gateway::client::FullEvent;
+    use serenity::gateway::{ActivityData, ConnectionStage, ShardStageUpdateEvent};
     use serenity::prelude::*;
 
     use crate::build::{GIT_HASH, VERSION};
@@ -39,7 +41,7 @@ async fn main() -> anyhow::Result<()> {
             crate::helper::time::mark_startup_time();
         }
 
-        let config = build_config()?;
+        let config = config::build_config()?;
         init_logging(config.log.log4rs)?;
 
         if config.log.panic {
@@ -55,8 +57,25 @@ async fn main() -> anyhow::Result<()> {
         let bot_data = Arc::new(HBotData::new(config.bot));
 
         bot_data.connect(&init).await?;
+        if let Some(metrics) = &config.metrics {
+            metrics::spawn_server(metrics, Arc::clone(&bot_data)).await?;
+        }
+
         tokio::task::spawn(load_azur_lane(Arc::clone(&bot_data)));
 
+        if let Some(secret) = &config.discord.button_secret {
+            let mut signer = buttons::Signer::new(secret.as_bytes());
+            for previous in &config.discord.button_previous_secrets {
+                signer = signer.with_previous(previous.as_bytes());
+            }
+
+            bot_data.set_button_signer(Some(signer));
+        }
+
+        if let Some(notice) = config.discord.button_expired_notice {
+            bot_data.set_button_expired_notice(notice);
+        }
+
         let event_handler = HEventHandler {
             ready: OnceReset::new(),
         };
@@ -64,6 +83,7 @@ async fn main() -> anyhow::Result<()> {
         let framework = Framework::new()
             .commands(init.commands)
             .pre_command(|ctx| Box::pin(slashies::pre_command(ctx)))
+            .post_command(|ctx, success| Box::pin(slashies::post_command(ctx, success)))
             .on_error(|err| Box::pin(slashies::error_handler(err)))
             .auto_register();
 
@@ -73,15 +93,22 @@ async fn main() -> anyhow::Result<()> {
                 config.discord.status.unwrap_or_else(|| VERSION.to_owned()),
             ))
             .data(Arc::clone(&bot_data))
-            .framework(framework)
+            .framework(HFramework(framework))
             .event_handler(event_handler)
             .await
             .context("failed to build discord client")?;
 
-        client
-            .start()
-            .await
-            .context("discord client shut down unexpectedly")
+        bot_data.set_shard_manager(Arc::clone(&client.shard_manager));
+
+        let res = match config.discord.shards {
+            Some(shards) => {
+                log::info!("Starting with {shards} shard(s).");
+                client.start_shards(shards.get().into()).await
+            },
+            None => client.start_autosharded().await,
+        };
+
+        res.context("discord client shut down unexpectedly")
     }
 
     /// Custom panic handler that writes the panic to the logger and flushes it.
@@ -102,6 +129,17 @@ async fn main() -> anyhow::Result<()> {
         log::logger().flush();
     }
 
+    /// Wraps the command framework so commands run with the custom ID signer.
+    struct HFramework(Framework);
+
+    #[serenity::async_trait]
+    impl serenity::framework::Framework for HFramework {
+        async fn dispatch(&self, ctx: &Context, event: &FullEvent) {
+            let data = ctx.data_ref::<HContextData>();
+            buttons::scope(data, self.0.dispatch(ctx, event)).await;
+        }
+    }
+
     /// Type to handle various Discord events.
     struct HEventHandler {
         ready: OnceReset,
@@ -111,7 +149,13 @@ async fn main() -> anyhow::Result<()> {
     impl EventHandler for HEventHandler {
         async fn ready(&self, ctx: Context, ready: Ready) {
             let discriminator = ready.user.discriminator.map_or(0u16, NonZero::get);
-            log::info!("Logged in as: {}#{:04}", ready.user.name, discriminator);
+            log::info!(
+                "[Shard {}] Logged in as: {}#{:04}",
+                ctx.shard_id,
+                ready.user.name,
+                discriminator
+            );
+            metrics::set_shard_connected(ctx.shard_id, true);
 
             if self.ready.set() {
                 let data = ctx.data_ref::<HContextData>();
@@ -119,21 +163,46 @@ async fn main() -> anyhow::Result<()> {
 
                 if let Err(why) = ready_setup(&ctx, data).await {
                     self.ready.reset();
-                    log::error!("Failure in ready: {why:?}");
+                    log::error!("[Shard {}] Failure in ready: {why:?}", ctx.shard_id);
                 }
             }
         }
 
+        async fn resume(&self, ctx: Context, _event: ResumedEvent) {
+            log::info!("[Shard {}] Resumed session.", ctx.shard_id);
+            metrics::set_shard_connected(ctx.shard_id, true);
+        }
+
+        async fn shard_stage_update(&self, _ctx: Context, event: ShardStageUpdateEvent) {
+            log::debug!(
+                "[Shard {}] Connection stage: {} -> {}",
+                event.shard_id,
+                event.old,
+                event.new
+            );
+            metrics::set_shard_connected(event.shard_id, event.new == ConnectionStage::Connected);
+        }
+
         async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
-            modules::perks::dispatch_check_perks(&ctx);
             buttons::handler::interaction_create(ctx, interaction).await;
         }
 
         async fn message(&self, ctx: Context, new_message: Message) {
-            modules::perks::dispatch_check_perks(&ctx);
+            modules::sticky::message(&ctx, &new_message);
+            modules::auto_publish::message(&ctx, &new_message).await;
             modules::media_react::message(ctx, new_message).await;
         }
 
+        async fn message_update(
+            &self,
+            ctx: Context,
+            _old_if_available: Option<Message>,
+            _new: Option<Message>,
+            event: MessageUpdateEvent,
+        ) {
+            modules::guild_log::message_update(&ctx, &event);
+        }
+
         async fn message_delete(
             &self,
             ctx: Context,
@@ -141,17 +210,65 @@ async fn main() -> anyhow::Result<()> {
             message_id: MessageId,
             guild_id: Option<GuildId>,
         ) {
+            modules::guild_log::message_delete(&ctx, channel_id, message_id, guild_id);
             modules::starboard::message_delete(ctx, channel_id, message_id, guild_id).await;
         }
 
         async fn reaction_add(&self, ctx: Context, reaction: Reaction) {
-            modules::perks::dispatch_check_perks(&ctx);
             modules::starboard::reaction_add(ctx, reaction).await;
         }
+
+        async fn reaction_remove(&self, ctx: Context, removed_reaction: Reaction) {
+            modules::starboard::reaction_remove(ctx, removed_reaction).await;
+        }
+
+        async fn guild_member_addition(&self, ctx: Context, new_member: Member) {
+            modules::guild_log::guild_member_addition(&ctx, &new_member);
+            modules::welcome::guild_member_addition(ctx, new_member).await;
+        }
+
+        async fn guild_member_removal(
+            &self,
+            ctx: Context,
+            guild_id: GuildId,
+            user: User,
+            _member_data_if_available: Option<Member>,
+        ) {
+            modules::guild_log::guild_member_removal(&ctx, guild_id, &user);
+            modules::welcome::guild_member_removal(ctx, guild_id, user).await;
+        }
+
+        async fn guild_role_create(&self, ctx: Context, new: Role) {
+            modules::guild_log::guild_role_create(&ctx, &new);
+        }
+
+        async fn guild_role_update(
+            &self,
+            ctx: Context,
+            _old_data_if_available: Option<Role>,
+            new: Role,
+        ) {
+            modules::guild_log::guild_role_update(&ctx, &new);
+        }
+
+        async fn guild_role_delete(
+            &self,
+            ctx: Context,
+            guild_id: GuildId,
+            removed_role_id: RoleId,
+            _removed_role_data_if_available: Option<Role>,
+        ) {
+            modules::guild_log::guild_role_delete(&ctx, guild_id, removed_role_id);
+        }
     }
 
     async fn ready_setup(ctx: &Context, data: &HBotData) -> Result {
         data.load_app_emojis(&ctx.http).await?;
+        modules::sticky::load_stickies(ctx).await?;
+        modules::perks::register_jobs(ctx).await?;
+        modules::perks::recover_transfers(ctx).await?;
+        modules::analytics::register_jobs(ctx).await?;
+        modules::scheduler::spawn_scheduler(ctx);
         Ok(())
     }
 
@@ -164,42 +281,6 @@ async fn main() -> anyhow::Result<()> {
         }
     }
 
-    fn profile() -> Result<Cow<'static, str>> {
-        use std::env::var;
-        use std::env::VarError::NotPresent;
-
-        match var("HOUSTON_PROFILE") {
-            Ok(value) => Ok(value.into()),
-            Err(NotPresent) => Ok("release".into()),
-            Err(err) => Err(err).context("cannot load HOUSTON_PROFILE env variable"),
-        }
-    }
-
-    fn build_config() -> Result<config::HConfig> {
-        use config_rs::{Config, Environment, File, FileFormat};
-
-        let profile = profile()?;
-        let profile_config = format!("houston_app.{profile}.toml");
-
-        let config = Config::builder()
-            .add_source(File::new("houston_app.toml", FileFormat::Toml).required(false))
-            .add_source(File::new(&profile_config, FileFormat::Toml).required(false))
-            .add_source(Environment::default().separator("__"))
-            // defaults for logging
-            .set_default("log.root.level", "warn")?
-            .set_default("log.root.appenders[0]", "default")?
-            .set_default("log.appenders.default.kind", "default")?
-            .set_default("log.appenders.default.encoder.kind", "default")?
-            .set_default("log.loggers.houston_app.level", "trace")?
-            .set_default("log.loggers.houston_cmd.level", "trace")?
-            .build()
-            .context("cannot build config")?
-            .try_deserialize()
-            .context("cannot deserialize config")?;
-
-        Ok(config)
-    }
-
     fn init_logging(config: log4rs::config::RawConfig) -> anyhow::Result<()> {
         let (appenders, errors) = config.appenders_lossy(&logging::deserializers());
         if !errors.is_empty() {
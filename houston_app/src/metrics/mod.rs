@@ -0,0 +1,173 @@
+//! Optional HTTP endpoint for health checks and Prometheus metrics.
+//!
+//! The counters are always collected since they are cheap, but they are only
+//! exposed if the server is configured.
+
+use std::fmt::Write as _;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::LazyLock;
+use std::time::Duration;
+
+use dashmap::DashMap;
+
+use crate::prelude::*;
+
+mod server;
+
+pub use server::spawn_server;
+
+#[derive(Debug, serde::Deserialize)]
+pub struct Config {
+    /// The address to listen on, f.e. `127.0.0.1:9090`.
+    pub bind: SocketAddr,
+}
+
+/// Whether each shard's gateway is currently connected.
+static SHARDS: LazyLock<DashMap<ShardId, bool>> = LazyLock::new(DashMap::new);
+
+/// Invocations per command name.
+static COMMANDS: LazyLock<DashMap<String, u64>> = LazyLock::new(DashMap::new);
+
+/// Total count and time of button and modal dispatches.
+static BUTTON_COUNT: AtomicU64 = AtomicU64::new(0);
+static BUTTON_NANOS: AtomicU64 = AtomicU64::new(0);
+
+/// Sets whether a shard's gateway is connected.
+pub fn set_shard_connected(shard: ShardId, connected: bool) {
+    SHARDS.insert(shard, connected);
+}
+
+/// Gets whether the gateway is connected on all known shards.
+pub fn gateway_connected() -> bool {
+    !SHARDS.is_empty() && SHARDS.iter().all(|s| *s.value())
+}
+
+/// Counts an invocation of a command.
+pub fn record_command(name: &str) {
+    if let Some(mut count) = COMMANDS.get_mut(name) {
+        *count += 1;
+    } else {
+        *COMMANDS.entry(name.to_owned()).or_default() += 1;
+    }
+}
+
+/// Records how long a button or modal dispatch took.
+pub fn record_button_dispatch(elapsed: Duration) {
+    let nanos = u64::try_from(elapsed.as_nanos()).unwrap_or(u64::MAX);
+    BUTTON_COUNT.fetch_add(1, Ordering::Relaxed);
+    BUTTON_NANOS.fetch_add(nanos, Ordering::Relaxed);
+}
+
+/// Renders all metrics in the Prometheus text format.
+fn render() -> String {
+    use crate::modules::{guild_log, sticky};
+
+    let mut out = String::new();
+
+    write_metric_header(
+        &mut out,
+        "houston_gateway_connected",
+        "gauge",
+        "Whether the gateway of each shard is connected.",
+    );
+    let mut shards: Vec<_> = SHARDS.iter().map(|e| (*e.key(), *e.value())).collect();
+    shards.sort_unstable_by_key(|(shard, _)| *shard);
+    for (shard, connected) in shards {
+        _ = writeln!(
+            out,
+            "houston_gateway_connected{{shard=\"{shard}\"}} {}",
+            u8::from(connected)
+        );
+    }
+
+    write_metric_header(
+        &mut out,
+        "houston_commands_total",
+        "counter",
+        "Command invocations.",
+    );
+    let mut commands: Vec<_> = COMMANDS
+        .iter()
+        .map(|e| (e.key().clone(), *e.value()))
+        .collect();
+    commands.sort_unstable();
+    for (name, count) in commands {
+        _ = writeln!(
+            out,
+            "houston_commands_total{{command=\"{}\"}} {count}",
+            LabelValue(&name)
+        );
+    }
+
+    #[allow(
+        clippy::cast_precision_loss,
+        reason = "precision loss is fine for metrics"
+    )]
+    let button_secs = BUTTON_NANOS.load(Ordering::Relaxed) as f64 / 1e9;
+    write_metric_header(
+        &mut out,
+        "houston_button_dispatch_seconds",
+        "summary",
+        "Time spent dispatching buttons and modals.",
+    );
+    _ = writeln!(out, "houston_button_dispatch_seconds_sum {button_secs}");
+    _ = writeln!(
+        out,
+        "houston_button_dispatch_seconds_count {}",
+        BUTTON_COUNT.load(Ordering::Relaxed)
+    );
+
+    write_metric_header(
+        &mut out,
+        "houston_cache_entries",
+        "gauge",
+        "Entries held in memory.",
+    );
+    _ = writeln!(
+        out,
+        "houston_cache_entries{{cache=\"sticky\"}} {}",
+        sticky::cache_len()
+    );
+    _ = writeln!(
+        out,
+        "houston_cache_entries{{cache=\"guild_log\"}} {}",
+        guild_log::queue_len()
+    );
+
+    out
+}
+
+fn write_metric_header(out: &mut String, name: &str, kind: &str, help: &str) {
+    _ = writeln!(out, "# HELP {name} {help}");
+    _ = writeln!(out, "# TYPE {name} {kind}");
+}
+
+/// Escapes a label value for the Prometheus text format.
+struct LabelValue<'a>(&'a str);
+
+impl std::fmt::Display for LabelValue<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for c in self.0.chars() {
+            match c {
+                '\\' => f.write_str("\\\\")?,
+                '"' => f.write_str("\\\"")?,
+                '\n' => f.write_str("\\n")?,
+                _ => f.write_char(c)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LabelValue;
+
+    #[test]
+    fn label_value_escapes() {
+        let value = LabelValue("a \"b\" \\ c\nd").to_string();
+        assert_eq!(value, r#"a \"b\" \\ c\nd"#);
+    }
+}
@@ -0,0 +1,157 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use bson::doc;
+use tokio::io::{AsyncReadExt as _, AsyncWriteExt as _};
+use tokio::net::{TcpListener, TcpStream};
+
+use super::Config;
+use crate::prelude::*;
+
+/// The most bytes read for a request head.
+const MAX_REQUEST_LEN: usize = 8 * 1024;
+
+/// How long to wait for the client to send the request.
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How long the database may take to answer the health check.
+const PING_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Binds the server and spawns a task to accept connections.
+pub async fn spawn_server(config: &Config, data: Arc<HBotData>) -> Result {
+    let listener = TcpListener::bind(config.bind)
+        .await
+        .with_context(|| format!("cannot bind metrics server to {}", config.bind))?;
+
+    log::info!("Serving metrics on {}.", config.bind);
+
+    tokio::task::spawn(async move {
+        loop {
+            match listener.accept().await {
+                Ok((stream, _)) => {
+                    tokio::task::spawn(handle_connection(stream, Arc::clone(&data)));
+                },
+                Err(why) => log::warn!("Failed to accept metrics connection: {why:?}"),
+            }
+        }
+    });
+
+    Ok(())
+}
+
+async fn handle_connection(mut stream: TcpStream, data: Arc<HBotData>) {
+    let response = match tokio::time::timeout(READ_TIMEOUT, read_path(&mut stream)).await {
+        Ok(Some(path)) => respond(&path, &data).await,
+        Ok(None) => Response::new("400 Bad Request", "bad request\n".to_owned()),
+        Err(_) => return,
+    };
+
+    let head = format!(
+        "HTTP/1.1 {}\r\n\
+         Content-Type: {}\r\n\
+         Content-Length: {}\r\n\
+         Connection: close\r\n\r\n",
+        response.status,
+        response.content_type,
+        response.body.len(),
+    );
+
+    if let Err(why) = write_response(&mut stream, &head, &response.body).await {
+        log::debug!("Failed to write metrics response: {why:?}");
+    }
+}
+
+async fn write_response(stream: &mut TcpStream, head: &str, body: &str) -> std::io::Result<()> {
+    stream.write_all(head.as_bytes()).await?;
+    stream.write_all(body.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+/// Reads the request head and returns the path of a `GET` request.
+async fn read_path(stream: &mut TcpStream) -> Option<String> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 1024];
+
+    while !buf.windows(4).any(|w| w == b"\r\n\r\n") {
+        let read = stream.read(&mut chunk).await.ok()?;
+        if read == 0 || buf.len() + read > MAX_REQUEST_LEN {
+            return None;
+        }
+
+        buf.extend_from_slice(&chunk[..read]);
+    }
+
+    let head = std::str::from_utf8(&buf).ok()?;
+    let target = head.strip_prefix("GET ")?.split(' ').next()?;
+    let path = target.split('?').next()?;
+    Some(path.to_owned())
+}
+
+struct Response {
+    status: &'static str,
+    content_type: &'static str,
+    body: String,
+}
+
+impl Response {
+    fn new(status: &'static str, body: String) -> Self {
+        Self {
+            status,
+            content_type: "text/plain; charset=utf-8",
+            body,
+        }
+    }
+}
+
+async fn respond(path: &str, data: &HBotData) -> Response {
+    match path {
+        "/healthz" => health(data).await,
+        "/metrics" => Response {
+            content_type: "text/plain; version=0.0.4; charset=utf-8",
+            ..Response::new("200 OK", super::render())
+        },
+        _ => Response::new("404 Not Found", "not found\n".to_owned()),
+    }
+}
+
+async fn health(data: &HBotData) -> Response {
+    let gateway = super::gateway_connected();
+
+    // the database is only checked if one is configured
+    let database = match data.database() {
+        Ok(db) => Some(ping_database(db).await),
+        Err(_) => None,
+    };
+
+    let mut body = format!("gateway: {}\n", if gateway { "ok" } else { "disconnected" });
+    if let Some(database) = database {
+        body.push_str(if database {
+            "database: ok\n"
+        } else {
+            "database: unreachable\n"
+        });
+    }
+
+    let healthy = gateway && database != Some(false);
+    let status = if healthy {
+        "200 OK"
+    } else {
+        "503 Service Unavailable"
+    };
+    Response::new(status, body)
+}
+
+async fn ping_database(db: &mongodb::Database) -> bool {
+    let ping = db.run_command(doc! { "ping": 1 });
+    match tokio::time::timeout(PING_TIMEOUT, ping).await {
+        Ok(Ok(_)) => true,
+        Ok(Err(why)) => {
+            log::warn!("Database health check failed: {why:?}");
+            false
+        },
+        Err(_) => {
+            log::warn!("Database health check timed out.");
+            false
+        },
+    }
+}
@@ -0,0 +1,14 @@
+use chrono::TimeDelta;
+
+use crate::helper::time::serde_time_delta;
+
+fn default_retention() -> TimeDelta {
+    const { TimeDelta::days(90) }
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct Config {
+    /// How long daily usage records are kept.
+    #[serde(with = "serde_time_delta", default = "default_retention")]
+    pub retention: TimeDelta,
+}
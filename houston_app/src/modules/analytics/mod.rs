@@ -0,0 +1,227 @@
+//! Records how often commands and buttons are used.
+//!
+//! Uses are counted per guild and day, along with errors and a rough latency
+//! histogram. No information about the users is stored.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use chrono::prelude::*;
+use chrono::TimeDelta;
+
+use super::prelude::*;
+
+pub mod config;
+pub mod model;
+
+pub use config::Config;
+use model::*;
+
+/// Upper bounds of the latency buckets, in milliseconds.
+pub const LATENCY_BUCKETS: [u64; 8] = [100, 250, 500, 1000, 2500, 5000, 10000, 15000];
+
+pub struct Module;
+
+impl super::Module for Module {
+    fn enabled(&self, config: &HBotConfig) -> bool {
+        config.analytics.is_some()
+    }
+
+    fn db_init(db: &mongodb::Database) -> mongodb::BoxFuture<'_, Result> {
+        use crate::helper::bson::update_indices;
+        Box::pin(async move {
+            update_indices(Usage::collection(db), Usage::indices()).await?;
+            Ok(())
+        })
+    }
+
+    fn validate(&self, config: &HBotConfig) -> Result {
+        anyhow::ensure!(
+            config.mongodb_uri.is_some(),
+            "analytics requires a mongodb_uri"
+        );
+
+        let analytics = config.analytics.as_ref().unwrap();
+        anyhow::ensure!(
+            analytics.retention >= TimeDelta::days(1),
+            "analytics.retention must be at least 1 day"
+        );
+        Ok(())
+    }
+}
+
+/// Gets the start of the UTC day of the time.
+pub fn start_of_day(time: DateTime<Utc>) -> DateTime<Utc> {
+    time.date_naive().and_time(NaiveTime::MIN).and_utc()
+}
+
+/// Records a use of a command or button, if analytics are enabled.
+pub fn record(
+    ctx: &Context,
+    guild: Option<GuildId>,
+    kind: UsageKind,
+    name: String,
+    success: bool,
+    latency: Duration,
+) {
+    let data = ctx.data_ref::<HContextData>();
+    if !Module.enabled(&data.config()) {
+        return;
+    }
+
+    let Ok(db) = data.database() else {
+        return;
+    };
+
+    let usage = Usage::collection(db);
+    tokio::task::spawn(async move {
+        let day = start_of_day(Utc::now());
+        let res = usage
+            .record(guild, day, kind, &name, success, latency)
+            .await;
+
+        if let Err(why) = res {
+            log::warn!("Failed to record usage of {name}: {why:?}");
+        }
+    });
+}
+
+/// Gets the time since the interaction was created.
+pub fn interaction_latency(id: InteractionId) -> Duration {
+    (Utc::now() - *id.created_at()).to_std().unwrap_or_default()
+}
+
+/// Registers the recurring cleanup of old records with the scheduler.
+pub async fn register_jobs(ctx: &Context) -> Result {
+    use super::scheduler::{cancel_recurring, schedule_recurring, JobKind};
+
+    const KEY: &str = "analytics.prune";
+
+    let data = ctx.data_ref::<HContextData>();
+    let Ok(db) = data.database() else {
+        return Ok(());
+    };
+
+    if Module.enabled(&data.config()) {
+        schedule_recurring(db, KEY, &JobKind::AnalyticsPrune, TimeDelta::days(1)).await
+    } else {
+        cancel_recurring(db, KEY).await
+    }
+}
+
+/// Deletes records older than the configured retention.
+///
+/// This is run by the scheduler.
+pub async fn prune(ctx: &Context) -> Result {
+    let data = ctx.data_ref::<HContextData>();
+    let config = data.config();
+    let Some(analytics) = &config.analytics else {
+        return Ok(());
+    };
+
+    let before = start_of_day(Utc::now() - analytics.retention);
+    let deleted = Usage::collection(data.database()?)
+        .delete_before(before)
+        .await?;
+
+    log::trace!("Pruned {deleted} usage record(s).");
+    Ok(())
+}
+
+/// Usage of a command or button, summed across guilds and days.
+#[derive(Debug)]
+pub struct UsageSummary {
+    pub kind: UsageKind,
+    pub name: String,
+    pub uses: i64,
+    pub errors: i64,
+    pub latency: HashMap<String, i64>,
+}
+
+/// Sums up the records per command and button, sorted by most used first.
+pub fn summarize(records: Vec<Usage>) -> Vec<UsageSummary> {
+    let mut map: HashMap<(UsageKind, String), UsageSummary> = HashMap::new();
+
+    for record in records {
+        let summary = map
+            .entry((record.kind, record.name.clone()))
+            .or_insert_with(|| UsageSummary {
+                kind: record.kind,
+                name: record.name,
+                uses: 0,
+                errors: 0,
+                latency: HashMap::new(),
+            });
+
+        summary.uses += record.uses;
+        summary.errors += record.errors;
+        for (bucket, count) in record.latency {
+            *summary.latency.entry(bucket).or_default() += count;
+        }
+    }
+
+    let mut summaries: Vec<_> = map.into_values().collect();
+    summaries.sort_unstable_by(|a, b| b.uses.cmp(&a.uses).then_with(|| a.name.cmp(&b.name)));
+    summaries
+}
+
+/// Gets the latency bucket that contains the given percentile.
+///
+/// Returns [`None`] if there are no uses.
+pub fn latency_percentile(latency: &HashMap<String, i64>, percent: i64) -> Option<String> {
+    let total: i64 = latency.values().sum();
+    if total <= 0 {
+        return None;
+    }
+
+    // the rank of the use at the percentile, rounded up
+    let target = (total * percent + 99) / 100;
+
+    let keys = LATENCY_BUCKETS
+        .iter()
+        .map(u64::to_string)
+        .chain([String::from("inf")]);
+
+    let mut seen = 0;
+    for key in keys {
+        seen += latency.get(&key).copied().unwrap_or(0);
+        if seen >= target {
+            return Some(key);
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::time::Duration;
+
+    use super::latency_percentile;
+    use super::model::latency_bucket;
+
+    #[test]
+    fn bucket_bounds() {
+        assert_eq!(latency_bucket(Duration::from_millis(0)), "100");
+        assert_eq!(latency_bucket(Duration::from_millis(100)), "100");
+        assert_eq!(latency_bucket(Duration::from_millis(101)), "250");
+        assert_eq!(latency_bucket(Duration::from_secs(15)), "15000");
+        assert_eq!(latency_bucket(Duration::from_secs(20)), "inf");
+    }
+
+    #[test]
+    fn percentiles() {
+        let latency = HashMap::from([
+            ("100".to_owned(), 50),
+            ("500".to_owned(), 45),
+            ("inf".to_owned(), 5),
+        ]);
+
+        assert_eq!(latency_percentile(&latency, 50).as_deref(), Some("100"));
+        assert_eq!(latency_percentile(&latency, 51).as_deref(), Some("500"));
+        assert_eq!(latency_percentile(&latency, 95).as_deref(), Some("500"));
+        assert_eq!(latency_percentile(&latency, 99).as_deref(), Some("inf"));
+        assert_eq!(latency_percentile(&HashMap::new(), 50), None);
+    }
+}
@@ -0,0 +1,142 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use super::LATENCY_BUCKETS;
+use crate::modules::model_prelude::*;
+
+/// What kind of interaction was used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UsageKind {
+    Command,
+    Button,
+}
+
+/// Usage of a single command or button in a guild on a single day.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Usage {
+    pub _id: ObjectId,
+    #[serde(with = "opt_id_as_i64", default)]
+    pub guild: Option<GuildId>,
+    /// The start of the day, in UTC.
+    #[serde(with = "chrono_datetime_as_bson_datetime")]
+    pub day: DateTime<Utc>,
+    pub kind: UsageKind,
+    pub name: String,
+    #[serde(default)]
+    pub uses: i64,
+    #[serde(default)]
+    pub errors: i64,
+    /// Uses per latency bucket, keyed by [`latency_bucket`].
+    #[serde(default)]
+    pub latency: HashMap<String, i64>,
+}
+
+impl Usage {
+    pub fn collection(db: &Database) -> Collection<Self> {
+        db.collection("analytics.usage")
+    }
+
+    pub fn indices() -> Vec<IndexModel> {
+        vec![IndexModel::builder()
+            .options(
+                IndexOptions::builder()
+                    .name("day-guild-kind-name".to_owned())
+                    .unique(true)
+                    .build(),
+            )
+            .keys(doc! {
+                "day": 1,
+                "guild": 1,
+                "kind": 1,
+                "name": 1,
+            })
+            .build()]
+    }
+}
+
+/// Gets the key of the latency bucket for the duration.
+///
+/// This is the upper bound in milliseconds, or `inf` if it exceeds all
+/// buckets.
+pub fn latency_bucket(latency: Duration) -> String {
+    let millis = latency.as_millis();
+    match LATENCY_BUCKETS.iter().find(|&&b| millis <= u128::from(b)) {
+        Some(bound) => bound.to_string(),
+        None => "inf".to_owned(),
+    }
+}
+
+pub trait UsageExt {
+    /// Counts a single use.
+    async fn record(
+        &self,
+        guild: Option<GuildId>,
+        day: DateTime<Utc>,
+        kind: UsageKind,
+        name: &str,
+        success: bool,
+        latency: Duration,
+    ) -> Result;
+
+    /// Loads all records since the given day.
+    async fn find_since(&self, day: DateTime<Utc>) -> Result<Vec<Usage>>;
+
+    /// Deletes all records before the given day.
+    async fn delete_before(&self, day: DateTime<Utc>) -> Result<u64>;
+}
+
+impl UsageExt for Collection<Usage> {
+    async fn record(
+        &self,
+        guild: Option<GuildId>,
+        day: DateTime<Utc>,
+        kind: UsageKind,
+        name: &str,
+        success: bool,
+        latency: Duration,
+    ) -> Result {
+        let filter = doc! {
+            "day": Bson::DateTime(day.into()),
+            "guild": guild.map(|g| bson_id!(g)),
+            "kind": bson::to_bson(&kind)?,
+            "name": name,
+        };
+
+        let mut inc = doc! {
+            "uses": 1,
+            "errors": i32::from(!success),
+        };
+
+        inc.insert(format!("latency.{}", latency_bucket(latency)), 1);
+
+        let update = doc! {
+            "$inc": inc,
+        };
+
+        self.update_one(filter, update).upsert(true).await?;
+        Ok(())
+    }
+
+    async fn find_since(&self, day: DateTime<Utc>) -> Result<Vec<Usage>> {
+        let filter = doc! {
+            "day": {
+                "$gte": Bson::DateTime(day.into()),
+            },
+        };
+
+        let records = self.find(filter).await?.try_collect().await?;
+        Ok(records)
+    }
+
+    async fn delete_before(&self, day: DateTime<Utc>) -> Result<u64> {
+        let filter = doc! {
+            "day": {
+                "$lt": Bson::DateTime(day.into()),
+            },
+        };
+
+        let result = self.delete_many(filter).await?;
+        Ok(result.deleted_count)
+    }
+}
@@ -0,0 +1,14 @@
+use std::collections::HashMap;
+
+use crate::prelude::*;
+
+pub type Config = HashMap<ChannelId, AutoPublishEntry>;
+
+/// Configures auto-publishing for an announcement channel.
+#[derive(Debug, Default, serde::Deserialize)]
+pub struct AutoPublishEntry {
+    /// If not empty, only messages by members with one of these roles are
+    /// published.
+    #[serde(default)]
+    pub roles: Vec<RoleId>,
+}
@@ -0,0 +1,120 @@
+//! Publishes messages in announcement channels automatically.
+//!
+//! Discord only allows a few publishes per channel each hour. When publishing
+//! fails, the channel is skipped for a while, doubling that time with each
+//! further failure.
+
+use std::sync::LazyLock;
+
+use chrono::{DateTime, TimeDelta, Utc};
+use dashmap::DashMap;
+
+use super::prelude::*;
+
+pub mod config;
+
+pub use config::Config;
+
+/// How long to wait after the first failure.
+const MIN_BACKOFF: TimeDelta = TimeDelta::minutes(1);
+
+/// The longest to wait after repeated failures.
+const MAX_BACKOFF: TimeDelta = TimeDelta::hours(1);
+
+/// Channels where publishing recently failed.
+static BACKOFF: LazyLock<DashMap<ChannelId, Backoff>> = LazyLock::new(DashMap::new);
+
+struct Backoff {
+    until: DateTime<Utc>,
+    delay: TimeDelta,
+}
+
+pub struct Module;
+
+impl super::Module for Module {
+    fn enabled(&self, config: &HBotConfig) -> bool {
+        !config.auto_publish.is_empty()
+    }
+
+    fn intents(&self, _config: &HBotConfig) -> GatewayIntents {
+        GatewayIntents::GUILD_MESSAGES
+    }
+
+    fn validate(&self, config: &HBotConfig) -> Result {
+        log::info!(
+            "Auto-publish is enabled: {} channel(s)",
+            config.auto_publish.len()
+        );
+
+        Ok(())
+    }
+}
+
+pub async fn message(ctx: &Context, message: &Message) {
+    if let Err(why) = message_inner(ctx, message).await {
+        log::warn!(
+            "Failed to publish {} in {}: {why:?}",
+            message.id,
+            message.channel_id
+        );
+    }
+}
+
+async fn message_inner(ctx: &Context, message: &Message) -> Result {
+    let data = ctx.data_ref::<HContextData>();
+    let config = data.config();
+    let Some(entry) = config.auto_publish.get(&message.channel_id) else {
+        return Ok(());
+    };
+
+    if !matches!(
+        message.kind,
+        MessageType::Regular | MessageType::InlineReply
+    ) {
+        return Ok(());
+    }
+
+    if !entry.roles.is_empty() {
+        let has_role = message
+            .member
+            .as_ref()
+            .is_some_and(|m| m.roles.iter().any(|r| entry.roles.contains(r)));
+
+        if !has_role {
+            return Ok(());
+        }
+    }
+
+    let now = Utc::now();
+    if BACKOFF
+        .get(&message.channel_id)
+        .is_some_and(|b| b.until > now)
+    {
+        log::trace!("Skipped publishing {} due to backoff.", message.id);
+        return Ok(());
+    }
+
+    match message.channel_id.crosspost(&ctx.http, message.id).await {
+        Ok(_) => {
+            BACKOFF.remove(&message.channel_id);
+            Ok(())
+        },
+        Err(why) => {
+            let mut backoff = BACKOFF.entry(message.channel_id).or_insert(Backoff {
+                until: now,
+                delay: TimeDelta::zero(),
+            });
+
+            backoff.delay = (backoff.delay * 2).clamp(MIN_BACKOFF, MAX_BACKOFF);
+            backoff.until = now + backoff.delay;
+
+            log::info!(
+                "Backing off publishing in {} for {}s.",
+                message.channel_id,
+                backoff.delay.num_seconds()
+            );
+
+            Err(why.into())
+        },
+    }
+}
@@ -1,14 +1,20 @@
 use azur_lane::equip::*;
 use azur_lane::skill::*;
+use azur_lane::Locale;
 use utils::text::truncate;
 
-use super::AzurParseError;
+use super::{AzurButtonMessage, AzurParseError};
 use crate::buttons::prelude::*;
+use crate::modules::azur::data::HAzurLane;
 
 /// Views an augment.
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct View {
     pub augment_id: u32,
+    /// The enhancement level to show. [`None`] shows the max level.
+    pub level: Option<u8>,
+    /// The locale to display names in.
+    pub locale: Option<Locale>,
     pub back: Option<CustomData>,
 }
 
@@ -17,6 +23,8 @@ impl View {
     pub fn new(augment_id: u32) -> Self {
         Self {
             augment_id,
+            level: None,
+            locale: None,
             back: None,
         }
     }
@@ -27,24 +35,50 @@ impl View {
         self
     }
 
+    /// Sets the locale to display names in.
+    pub fn locale(mut self, locale: Option<Locale>) -> Self {
+        self.locale = locale;
+        self
+    }
+
     /// Modifies the create-reply with a preresolved augment.
     pub fn create_with_augment<'a>(
-        self,
-        data: &'a HBotData,
+        mut self,
+        azur: &'a HAzurLane,
         augment: &'a Augment,
     ) -> CreateReply<'a> {
-        let description = crate::fmt::azur::AugmentStats::new(augment).to_string();
+        let level = self.level.and_then(|l| augment.levels.get(usize::from(l)));
+
+        let name = super::localized_name(&augment.name, &augment.names, self.locale);
+        let (author, description) = match (self.level, level) {
+            (Some(index), Some(level)) => (
+                format!("{name} +{index}"),
+                crate::fmt::azur::AugmentStats::with_level(level).to_string(),
+            ),
+            _ => (
+                name.to_owned(),
+                crate::fmt::azur::AugmentStats::new(augment).to_string(),
+            ),
+        };
 
         let embed = CreateEmbed::new()
-            .author(CreateEmbedAuthor::new(&augment.name))
+            .author(CreateEmbedAuthor::new(author))
             .description(description)
             .color(augment.rarity.color_rgb())
-            .fields(self.get_skill_field("Effect", augment.effect.as_ref()))
+            .fields(self.get_skill_field(
+                "Effect",
+                augment.effect.as_ref(),
+                level.and_then(|l| l.effect_description.as_deref()),
+            ))
             .fields(self.get_skill_field(
                 "Skill Upgrade",
                 augment.skill_upgrade.as_ref().map(|s| &s.skill),
+                level.and_then(|l| l.skill_upgrade_description.as_deref()),
             ));
 
+        let mut rows = Vec::new();
+        self.add_level_row(augment, &mut rows);
+
         let mut components = Vec::new();
 
         if let Some(back) = &self.back {
@@ -76,9 +110,12 @@ impl View {
                     .disabled(true)
             },
             AugmentUsability::UniqueShipId(ship_id) => {
-                if let Some(ship) = data.azur_lane().ship_by_id(*ship_id) {
-                    let view = super::ship::View::new(ship.group_id).back(self.to_custom_data());
-                    let label = format!("For: {}", ship.name);
+                if let Some(ship) = azur.ship_by_id(*ship_id) {
+                    let view = super::ship::View::new(ship.group_id)
+                        .locale(self.locale)
+                        .back(self.to_custom_data());
+                    let ship_name = super::localized_name(&ship.name, &ship.names, self.locale);
+                    let label = format!("For: {ship_name}");
                     CreateButton::new(view.to_custom_id()).label(truncate(label, 80))
                 } else {
                     CreateButton::new("=dummy-usability")
@@ -88,9 +125,34 @@ impl View {
             },
         });
 
-        CreateReply::new()
-            .embed(embed)
-            .components(vec![CreateActionRow::buttons(components)])
+        rows.push(CreateActionRow::buttons(components));
+        CreateReply::new().embed(embed).components(rows)
+    }
+
+    /// Adds a row to switch between enhancement levels, if there are several.
+    fn add_level_row(&mut self, augment: &Augment, rows: &mut Vec<CreateActionRow<'_>>) {
+        let max = augment.levels.len().saturating_sub(1);
+        let max = u8::try_from(max).unwrap_or(u8::MAX);
+        if max == 0 {
+            return;
+        }
+
+        // normalize so the buttons get disabled at either end
+        let current = self.level.unwrap_or(max).min(max);
+        self.level = Some(current);
+
+        let prev = current.saturating_sub(1);
+        let next = current.saturating_add(1).min(max);
+
+        rows.push(CreateActionRow::buttons(vec![
+            self.new_button(|s| &mut s.level, Some(prev), |_| 1)
+                .emoji('◀'),
+            CreateButton::new("=dummy-level")
+                .label(format!("+{current}"))
+                .disabled(true),
+            self.new_button(|s| &mut s.level, Some(next), |_| 2)
+                .emoji('▶'),
+        ]));
     }
 
     /// Creates the field for a skill summary.
@@ -98,24 +160,27 @@ impl View {
         &self,
         label: &'a str,
         skill: Option<&Skill>,
+        description: Option<&str>,
     ) -> Option<SimpleEmbedFieldCreate<'a>> {
         skill.map(|s| {
-            (
-                label,
-                format!("{} **{}**", s.category.emoji(), s.name),
-                false,
-            )
+            let mut value = format!("{} **{}**", s.category.emoji(), s.name);
+            if let Some(description) = description {
+                value.push('\n');
+                value.push_str(description);
+            }
+
+            (label, truncate(value, 1024), false)
         })
     }
 }
 
-impl ButtonMessage for View {
-    fn edit_reply(self, ctx: ButtonContext<'_>) -> Result<EditReply<'_>> {
-        let augment = ctx
-            .data
-            .azur_lane()
+impl AzurButtonMessage for View {
+    fn edit_reply<'a>(self, _ctx: ButtonContext<'a>, azur: &'a HAzurLane) -> Result<EditReply<'a>> {
+        let augment = azur
             .augment_by_id(self.augment_id)
             .ok_or(AzurParseError::Augment)?;
-        Ok(self.create_with_augment(ctx.data, augment).into())
+        Ok(self.create_with_augment(azur, augment).into())
     }
 }
+
+super::azur_button_reply!(View);
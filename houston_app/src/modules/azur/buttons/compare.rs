@@ -0,0 +1,218 @@
+use azur_lane::ship::*;
+use utils::text::truncate;
+use utils::text::write_str::*;
+
+use super::ship::ViewAffinity;
+use super::{AzurButtonMessage, AzurParseError};
+use crate::buttons::prelude::*;
+use crate::modules::azur::data::HAzurLane;
+
+/// Compares the stats and skills of two ships side by side.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct View {
+    pub ship_ids: [u32; 2],
+    pub level: u8,
+    pub affinity: ViewAffinity,
+    /// Whether to compare the ships' retrofits, where available.
+    pub retrofit: bool,
+}
+
+/// The stats listed in the comparison, in order.
+const STATS: [StatKind; 11] = [
+    StatKind::HP,
+    StatKind::FP,
+    StatKind::TRP,
+    StatKind::AA,
+    StatKind::AVI,
+    StatKind::RLD,
+    StatKind::EVA,
+    StatKind::ACC,
+    StatKind::ASW,
+    StatKind::SPD,
+    StatKind::LCK,
+];
+
+impl View {
+    /// Creates a new instance.
+    pub fn new(left_id: u32, right_id: u32) -> Self {
+        Self {
+            ship_ids: [left_id, right_id],
+            level: 120,
+            affinity: ViewAffinity::Love,
+            retrofit: false,
+        }
+    }
+
+    /// Modifies the create-reply with preresolved ship data.
+    pub fn create_with_ships<'a>(
+        mut self,
+        data: &'a HBotData,
+        ships: [&'a ShipData; 2],
+    ) -> CreateReply<'a> {
+        let base_ships = ships;
+        let has_retrofit = ships.iter().any(|s| !s.retrofits.is_empty());
+        let ships = ships.map(|ship| match ship.retrofits.first() {
+            Some(retrofit) if self.retrofit => retrofit,
+            _ => ship,
+        });
+
+        let params = StatParams::new(u32::from(self.level), self.affinity.to_affinity());
+        let [left, right] = ships.map(|s| s.stats.compute(params));
+
+        let affinity = match self.affinity {
+            ViewAffinity::Neutral => "Neutral",
+            ViewAffinity::Love => "\u{2764} 100",
+            ViewAffinity::Oath => "\u{1F497} 200",
+        };
+
+        let embed = CreateEmbed::new()
+            .author(CreateEmbedAuthor::new(format!(
+                "{} vs. {}",
+                ships[0].name, ships[1].name
+            )))
+            .description(format!("Lv.{} \u{2E31} {affinity}", self.level))
+            .color(data.config().embed_color)
+            .field(
+                &ships[0].name,
+                get_ship_text(data, ships[0], &left, &right),
+                true,
+            )
+            .field(
+                &ships[1].name,
+                get_ship_text(data, ships[1], &right, &left),
+                true,
+            );
+
+        let mut rows = Vec::new();
+        self.add_upgrade_row(&mut rows);
+        if has_retrofit {
+            self.add_retrofit_row(&mut rows);
+        }
+        self.add_nav_row(base_ships, &mut rows);
+
+        CreateReply::new().embed(embed).components(rows)
+    }
+
+    fn add_upgrade_row(&mut self, rows: &mut Vec<CreateActionRow<'_>>) {
+        rows.push(CreateActionRow::buttons(vec![
+            self.button_with_level(120).label("Lv.120"),
+            self.button_with_level(125).label("Lv.125"),
+            self.button_with_affinity(ViewAffinity::Love)
+                .emoji('❤')
+                .label("100"),
+            self.button_with_affinity(ViewAffinity::Oath)
+                .emoji('💗')
+                .label("200"),
+        ]));
+    }
+
+    fn add_retrofit_row(&mut self, rows: &mut Vec<CreateActionRow<'_>>) {
+        rows.push(CreateActionRow::buttons(vec![
+            self.button_with_retrofit(false).label("Base"),
+            self.button_with_retrofit(true).label("Retrofit"),
+        ]));
+    }
+
+    fn add_nav_row(&self, base_ships: [&ShipData; 2], rows: &mut Vec<CreateActionRow<'_>>) {
+        let back = self.to_custom_data();
+        let buttons = base_ships.into_iter().map(|ship| {
+            let mut view = super::ship::View::new(ship.group_id).back(back.clone());
+            view.level = self.level;
+            view.affinity = self.affinity;
+            if self.retrofit && !ship.retrofits.is_empty() {
+                view.retrofit = Some(0);
+            }
+
+            CreateButton::new(view.to_custom_id())
+                .label(truncate(&ship.name, 80))
+                .style(ButtonStyle::Secondary)
+        });
+
+        rows.push(CreateActionRow::buttons(buttons.collect::<Vec<_>>()));
+    }
+
+    /// Gets a button that redirects to a different level.
+    fn button_with_level<'a>(&mut self, level: u8) -> CreateButton<'a> {
+        self.new_button(|s| &mut s.level, level, u8::into)
+    }
+
+    /// Gets a button that redirects to a different affinity.
+    fn button_with_affinity<'a>(&mut self, affinity: ViewAffinity) -> CreateButton<'a> {
+        self.new_button(|s| &mut s.affinity, affinity, |u| u as u16)
+    }
+
+    /// Gets a button that toggles whether retrofits are compared.
+    fn button_with_retrofit<'a>(&mut self, retrofit: bool) -> CreateButton<'a> {
+        self.new_button(|s| &mut s.retrofit, retrofit, bool::into)
+    }
+}
+
+/// Creates the text for one side of the comparison.
+///
+/// Stats higher than the other ship's are marked.
+fn get_ship_text(
+    data: &HBotData,
+    ship: &ShipData,
+    stats: &ComputedStats,
+    other: &ComputedStats,
+) -> String {
+    #[allow(clippy::cast_sign_loss)]
+    #[allow(clippy::cast_possible_truncation)]
+    fn f(n: f64) -> u32 {
+        n.floor() as u32
+    }
+
+    let mut text = format!(
+        "[{}] {} {}\n-# {} {}",
+        ship.rarity.name(),
+        super::hull_emoji(ship.hull_type, data),
+        ship.hull_type.designation(),
+        ship.faction.name(),
+        ship.stats.armor.name(),
+    );
+
+    for kind in STATS {
+        let value = f(stats.get(kind));
+        let mark = if value > f(other.get(kind)) {
+            " ▲"
+        } else {
+            ""
+        };
+        write_str!(
+            text,
+            "\n**`{}:{: <pad$}`**`{: >5}`{mark}",
+            kind.name(),
+            "",
+            value,
+            pad = 3 - kind.name().len(),
+        );
+    }
+
+    write_str!(text, "\n**`Cost:`**`{: >4}`", ship.stats.cost);
+
+    if !ship.skills.is_empty() {
+        text.push('\n');
+        for skill in &ship.skills {
+            write_str!(
+                text,
+                "\n{} {}",
+                skill.category.emoji(),
+                truncate(&skill.name, 40)
+            );
+        }
+    }
+
+    text
+}
+
+impl AzurButtonMessage for View {
+    fn edit_reply<'a>(self, ctx: ButtonContext<'a>, azur: &'a HAzurLane) -> Result<EditReply<'a>> {
+        let [left, right] = self
+            .ship_ids
+            .map(|id| azur.ship_by_id(id).ok_or(AzurParseError::Ship));
+
+        Ok(self.create_with_ships(ctx.data, [left?, right?]).into())
+    }
+}
+
+super::azur_button_reply!(View);
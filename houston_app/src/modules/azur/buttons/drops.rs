@@ -0,0 +1,123 @@
+use azur_lane::stage::Stage;
+use utils::text::write_str::*;
+
+use super::{AzurButtonMessage, AzurParseError};
+use crate::buttons::prelude::*;
+use crate::modules::azur::data::HAzurLane;
+use crate::modules::core::buttons::ToPage;
+
+/// Lists the stages a ship or equipment drops in.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct View {
+    pub source: DropSource,
+    pub page: u16,
+    pub back: Option<CustomData>,
+}
+
+/// What to list the drop locations for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum DropSource {
+    /// A ship by its group ID.
+    Ship(u32),
+    /// Equipment by its ID.
+    Equip(u32),
+}
+
+const PAGE_SIZE: usize = 15;
+
+impl View {
+    /// Creates a new instance.
+    pub fn new(source: DropSource) -> Self {
+        Self {
+            source,
+            page: 0,
+            back: None,
+        }
+    }
+
+    /// Sets the back button target.
+    pub fn back(mut self, back: CustomData) -> Self {
+        self.back = Some(back);
+        self
+    }
+
+    /// Creates the reply for the current state.
+    pub fn create<'a>(
+        mut self,
+        data: &'a HBotData,
+        azur: &'a HAzurLane,
+    ) -> Result<CreateReply<'a>> {
+        let (author, stages): (_, Vec<&Stage>) = match self.source {
+            DropSource::Ship(id) => {
+                let ship = azur.ship_by_id(id).ok_or(AzurParseError::Ship)?;
+                let author = super::get_ship_wiki_url(ship);
+                (author, azur.stages_by_ship_drop(id).collect())
+            },
+            DropSource::Equip(id) => {
+                let equip = azur.equip_by_id(id).ok_or(AzurParseError::Equip)?;
+                let author = CreateEmbedAuthor::new(&equip.name);
+                (author, azur.stages_by_equip_drop(id).collect())
+            },
+        };
+
+        #[allow(clippy::cast_possible_truncation)]
+        let page_count = stages.len().div_ceil(PAGE_SIZE).max(1) as u16;
+        self.page = self.page.min(page_count - 1);
+
+        let mut desc = String::new();
+        for stage in stages
+            .iter()
+            .skip(PAGE_SIZE * usize::from(self.page))
+            .take(PAGE_SIZE)
+        {
+            writeln_str!(desc, "- **{}** {}", stage.code, stage.name);
+        }
+
+        if desc.is_empty() {
+            desc.push_str("Doesn't drop in any known stage.");
+        }
+
+        let embed = CreateEmbed::new()
+            .author(author)
+            .description(desc)
+            .footer(CreateEmbedFooter::new(
+                "Only stage drops are listed. Construction pools and shops aren't included.",
+            ))
+            .color(data.config().embed_color);
+
+        let mut rows = Vec::new();
+        if let Some(pagination) = ToPage::build_row(&mut self, |s| &mut s.page)
+            .exact_page_count(page_count)
+            .end()
+        {
+            rows.push(pagination);
+        }
+
+        if let Some(back) = &self.back {
+            rows.push(CreateActionRow::buttons(vec![CreateButton::new(
+                back.to_custom_id(),
+            )
+            .emoji('⏪')
+            .label("Back")]));
+        }
+
+        Ok(CreateReply::new().embed(embed).components(rows))
+    }
+}
+
+impl AzurButtonMessage for View {
+    fn edit_reply<'a>(self, ctx: ButtonContext<'a>, azur: &'a HAzurLane) -> Result<EditReply<'a>> {
+        self.create(ctx.data, azur).map(EditReply::from)
+    }
+
+    fn edit_modal_reply<'a>(
+        mut self,
+        ctx: ModalContext<'a>,
+        azur: &'a HAzurLane,
+    ) -> Result<EditReply<'a>> {
+        ToPage::set_page_from(&mut self.page, ctx.interaction);
+        self.create(ctx.data, azur).map(EditReply::from)
+    }
+}
+
+super::azur_button_reply!(View);
@@ -1,13 +1,17 @@
 use azur_lane::equip::*;
+use azur_lane::Locale;
 use utils::text::truncate;
 
-use super::AzurParseError;
+use super::{AzurButtonMessage, AzurParseError};
 use crate::buttons::prelude::*;
+use crate::modules::azur::data::HAzurLane;
 
 /// Views an augment.
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct View {
     pub equip_id: u32,
+    /// The locale to display names in.
+    pub locale: Option<Locale>,
     pub back: Option<CustomData>,
 }
 
@@ -16,6 +20,7 @@ impl View {
     pub fn new(equip_id: u32) -> Self {
         Self {
             equip_id,
+            locale: None,
             back: None,
         }
     }
@@ -26,17 +31,24 @@ impl View {
         self
     }
 
+    /// Sets the locale to display names in.
+    pub fn locale(mut self, locale: Option<Locale>) -> Self {
+        self.locale = locale;
+        self
+    }
+
     /// Modifies the create-reply with a preresolved equipment.
-    pub fn create_with_equip(self, equip: &Equip) -> CreateReply<'_> {
+    pub fn create_with_equip<'a>(self, azur: &HAzurLane, equip: &'a Equip) -> CreateReply<'a> {
         let description = format!(
             "**{}**\n{}",
             equip.kind.name(),
             crate::fmt::azur::EquipStats::new(equip)
         );
 
-        let embed = CreateEmbed::new()
+        let name = super::localized_name(&equip.name, &equip.names, self.locale);
+        let mut embed = CreateEmbed::new()
             .color(equip.rarity.color_rgb())
-            .author(CreateEmbedAuthor::new(&equip.name))
+            .author(CreateEmbedAuthor::new(name))
             .description(description)
             .fields(equip.weapons.iter().map(|weapon| {
                 (
@@ -64,7 +76,15 @@ impl View {
             None => vec![],
         };
 
-        CreateReply::new().embed(embed).components(components)
+        let mut create = CreateReply::new();
+
+        if let Some(image_data) = azur.get_equip_icon(&equip.icon) {
+            let filename = format!("equip_{}.webp", equip.icon);
+            embed = embed.thumbnail(format!("attachment://{filename}"));
+            create = create.attachment(CreateAttachment::bytes(image_data, filename));
+        }
+
+        create.embed(embed).components(components)
     }
 
     fn get_disallowed_field<'a>(&self, equip: &Equip) -> Option<SimpleEmbedFieldCreate<'a>> {
@@ -80,13 +100,13 @@ impl View {
     }
 }
 
-impl ButtonMessage for View {
-    fn edit_reply(self, ctx: ButtonContext<'_>) -> Result<EditReply<'_>> {
-        let equip = ctx
-            .data
-            .azur_lane()
+impl AzurButtonMessage for View {
+    fn edit_reply<'a>(self, _ctx: ButtonContext<'a>, azur: &'a HAzurLane) -> Result<EditReply<'a>> {
+        let equip = azur
             .equip_by_id(self.equip_id)
             .ok_or(AzurParseError::Equip)?;
-        Ok(self.create_with_equip(equip).into())
+        Ok(self.create_with_equip(azur, equip).into())
     }
 }
+
+super::azur_button_reply!(View);
@@ -3,14 +3,19 @@ use azur_lane::juustagram::*;
 use utils::text::truncate;
 use utils::text::write_str::*;
 
-use super::AzurParseError;
+use super::{AzurButtonMessage, AzurParseError};
 use crate::buttons::prelude::*;
 use crate::fmt::discord::escape_markdown;
+use crate::helper::discord::create_string_select_menu_row;
+use crate::modules::azur::data::HAzurLane;
 
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct View {
     chat_id: u32,
+    /// The flags of the chosen replies, in order.
     flags: ArrayVec<u8, 20>,
+    /// The index of the first message group to show.
+    group: u8,
     back: Option<CustomData>,
 }
 
@@ -22,6 +27,7 @@ impl View {
         Self {
             chat_id,
             flags,
+            group: 0,
             back: None,
         }
     }
@@ -32,7 +38,12 @@ impl View {
     }
 
     /// Modifies the create-reply with preresolved ship data.
-    pub fn create_with_chat<'a>(mut self, data: &'a HBotData, chat: &'a Chat) -> CreateReply<'a> {
+    pub fn create_with_chat<'a>(
+        mut self,
+        data: &'a HBotData,
+        azur: &'a HAzurLane,
+        chat: &'a Chat,
+    ) -> CreateReply<'a> {
         let mut content = String::new();
         let mut components = Vec::new();
 
@@ -59,78 +70,143 @@ impl View {
             components.push(CreateActionRow::buttons(nav_row));
         }
 
-        fn get_sender_name(data: &HBotData, sender_id: u32) -> &str {
+        fn get_sender_name(azur: &HAzurLane, sender_id: u32) -> &str {
             if sender_id == 0 {
                 return "<You>";
             }
 
-            data.azur_lane()
-                .ship_by_id(sender_id)
-                .map_or("<unknown>", |s| &s.name)
+            azur.ship_by_id(sender_id).map_or("<unknown>", |s| &s.name)
         }
 
+        // split the reachable entries into groups that end at each chosen reply
+        let mut groups: Vec<Vec<&ChatEntry>> = vec![Vec::new()];
+        let mut pending_options = None;
         for entry in &chat.entries {
             // if the chat entry does not have the right flag, we skip it
             if !self.flags.contains(&entry.flag) {
                 continue;
             }
 
+            groups.last_mut().expect("always has a group").push(entry);
+
+            // if there are options, we stop if we hold the flag for neither of them
+            if let Some(options) = &entry.options {
+                if options.iter().all(|o| !self.flags.contains(&o.flag)) {
+                    pending_options = Some(options);
+                    break;
+                }
+
+                groups.push(Vec::new());
+            }
+        }
+
+        if groups.last().is_some_and(Vec::is_empty) {
+            _ = groups.pop();
+        }
+
+        // the group may be out of range after undoing a reply
+        let group_count = u8::try_from(groups.len()).unwrap_or(u8::MAX);
+        self.group = self.group.min(group_count.saturating_sub(1));
+
+        if self.group != 0 {
+            writeln_str!(content, "-# *Skipped {} earlier part(s).*", self.group);
+        }
+
+        for entry in groups.iter().skip(usize::from(self.group)).flatten() {
             // print the content of the chat entry
             match &entry.content {
                 ChatContent::Message { sender_id, text } => writeln_str!(
                     content,
                     "- **{}:** {}",
-                    get_sender_name(data, *sender_id),
+                    get_sender_name(azur, *sender_id),
                     escape_markdown(text)
                 ),
                 ChatContent::Sticker { sender_id, label } => writeln_str!(
                     content,
                     "- **{}:** {}",
-                    get_sender_name(data, *sender_id),
+                    get_sender_name(azur, *sender_id),
                     label
                 ),
                 ChatContent::System { text } => writeln_str!(content, "- [{}]", text),
             }
+        }
 
-            // if there are options, we stop if we hold the flag for neither of them
-            if let Some(options) = &entry.options {
-                if options.iter().all(|o| !self.flags.contains(&o.flag)) {
-                    for option in options {
-                        let mut new_flags = self.flags.clone();
-                        _ = new_flags.try_push(option.flag);
+        if groups.len() > 1 {
+            let options: Vec<_> = groups
+                .iter()
+                .take(25)
+                .enumerate()
+                .filter_map(|(index, group)| {
+                    let first = group.first()?;
+                    let preview = match &first.content {
+                        ChatContent::Message { text, .. } => text.as_str(),
+                        ChatContent::Sticker { label, .. } => label.as_str(),
+                        ChatContent::System { text } => text.as_str(),
+                    };
+
+                    let index = u8::try_from(index).ok()?;
+                    let option = self
+                        .new_select_option(format!("Part {}", index + 1), |s| &mut s.group, index)
+                        .description(truncate(preview, 100));
+
+                    Some(option)
+                })
+                .collect();
+
+            components.push(create_string_select_menu_row(
+                self.to_custom_id(),
+                options,
+                "Jump to part...",
+            ));
+        }
 
-                        let button = self
-                            .new_button(|s| &mut s.flags, new_flags, |_| option.flag.into())
-                            .label(truncate(&option.value, 80))
-                            .style(ButtonStyle::Secondary);
+        for option in pending_options.into_iter().flatten() {
+            let mut new_flags = self.flags.clone();
+            _ = new_flags.try_push(option.flag);
+
+            // show affinity gains so users can pick the best answer
+            let (label, style) = match option.favor {
+                0 => (truncate(&option.value, 80).into(), ButtonStyle::Secondary),
+                favor => (
+                    format!("{} [{favor:+} ♡]", truncate(&option.value, 68)),
+                    ButtonStyle::Success,
+                ),
+            };
 
-                        components.push(CreateActionRow::buttons(vec![button]));
-                    }
+            let button = self
+                .new_button(|s| &mut s.flags, new_flags, |_| option.flag.into())
+                .label(label)
+                .style(style);
 
-                    break;
-                }
-            }
+            components.push(CreateActionRow::buttons(vec![button]));
         }
 
-        let embed = CreateEmbed::new()
+        let mut embed = CreateEmbed::new()
             .title(&chat.name)
             // this may be janky, possibly rework the limit
             .description(truncate(content, 4000))
             .color(data.config().embed_color);
 
+        if !chat.unlock_desc.is_empty() {
+            embed = embed.footer(CreateEmbedFooter::new(format!(
+                "Unlock: {}",
+                chat.unlock_desc
+            )));
+        }
+
         CreateReply::new().embed(embed).components(components)
     }
 }
 
-impl ButtonMessage for View {
-    fn edit_reply(self, ctx: ButtonContext<'_>) -> Result<EditReply<'_>> {
-        let chat = ctx
-            .data
-            .azur_lane()
+impl AzurButtonMessage for View {
+    fn edit_reply<'a>(self, ctx: ButtonContext<'a>, azur: &'a HAzurLane) -> Result<EditReply<'a>> {
+        let chat = azur
             .juustagram_chat_by_id(self.chat_id)
             .ok_or(AzurParseError::JuustagramChat)?;
 
-        let create = self.create_with_chat(ctx.data, chat);
+        let create = self.create_with_chat(ctx.data, azur, chat);
         Ok(create.into())
     }
 }
+
+super::azur_button_reply!(View);
@@ -1,18 +1,24 @@
+use std::ptr;
+
 use azur_lane::ship::*;
 use utils::text::write_str::*;
 
-use super::AzurParseError;
+use super::{AzurButtonMessage, AzurParseError};
 use crate::buttons::prelude::*;
 use crate::fmt::JoinNatural;
 use crate::helper::discord::create_string_select_menu_row;
+use crate::modules::azur::data::HAzurLane;
 
 /// Views ship lines.
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct View {
     pub ship_id: u32,
     pub skin_index: u8,
+    pub face_index: Option<u8>,
     pub part: ViewPart,
     pub extra: bool,
+    /// The index of the voice line to attach the audio for, if any.
+    pub voice_index: Option<u8>,
     pub back: CustomData,
 }
 
@@ -33,8 +39,10 @@ impl View {
         Self {
             ship_id,
             skin_index: 0,
+            face_index: None,
             part: ViewPart::Info,
             extra: false,
+            voice_index: None,
             back,
         }
     }
@@ -42,32 +50,62 @@ impl View {
     fn edit_with_ship<'a>(
         self,
         ctx: &ButtonContext<'a>,
+        azur: &'a HAzurLane,
         ship: &'a ShipData,
         skin: &'a ShipSkin,
     ) -> EditReply<'a> {
-        let (mut embed, components) = self.with_ship(ctx.data, ship, skin);
+        let face = self.face_index.and_then(|i| skin.faces.get(usize::from(i)));
+
+        let (mut embed, components, voice) = self.with_ship(azur, ship, skin);
         let mut create = EditReply::new();
 
-        if let Some(image_data) = ctx.data.azur_lane().get_chibi_image(&skin.image_key) {
-            embed = embed.thumbnail(format!("attachment://{}.webp", skin.image_key));
+        let image = match face {
+            Some(face) => azur
+                .get_face_image(&skin.image_key, face)
+                .map(|data| (format!("{}_{face}", skin.image_key), data)),
+            None => azur
+                .get_chibi_image(&skin.image_key)
+                .map(|data| (skin.image_key.clone(), data)),
+        };
 
-            if Some(skin.image_key.as_str()) != super::get_ship_preview_name(ctx) {
-                let filename = format!("{}.webp", skin.image_key);
-                create = create.new_attachment(CreateAttachment::bytes(image_data, filename));
-            }
+        if let Some((name, image_data)) = image {
+            let filename = format!("{name}.webp");
+            embed = embed.thumbnail(format!("attachment://{filename}"));
+
+            // keep the image if it's already there, but drop any other
+            // attachments, such as a previously attached voice line
+            let existing = ctx
+                .interaction
+                .message
+                .attachments
+                .iter()
+                .find(|a| *a.filename == *filename);
+
+            create = match existing {
+                Some(existing) => create.keep_existing_attachment(existing.id),
+                None => create.new_attachment(CreateAttachment::bytes(image_data, filename)),
+            };
         } else {
             create = create.clear_attachments();
         }
 
+        if let Some(voice) = voice {
+            create = create.new_attachment(voice);
+        }
+
         create.embed(embed).components(components)
     }
 
     fn with_ship<'a>(
         mut self,
-        data: &'a HBotData,
+        azur: &'a HAzurLane,
         ship: &'a ShipData,
         skin: &'a ShipSkin,
-    ) -> (CreateEmbed<'a>, Vec<CreateActionRow<'a>>) {
+    ) -> (
+        CreateEmbed<'a>,
+        Vec<CreateActionRow<'a>>,
+        Option<CreateAttachment<'a>>,
+    ) {
         let words = match &skin.words_extra {
             Some(words) if self.extra => words,
             _ => {
@@ -76,10 +114,44 @@ impl View {
             },
         };
 
-        let embed = CreateEmbed::new()
+        let mut embed = CreateEmbed::new()
             .color(ship.rarity.color_rgb())
             .author(super::get_ship_wiki_url(ship))
-            .description(self.part.get_description(data, words));
+            .description(self.part.get_description(azur, words));
+
+        // only offer voice lines if any audio was extracted for this skin
+        let voice_key = words
+            .voice_key
+            .as_deref()
+            .filter(|k| azur.has_voice_clips(k));
+
+        let voice_lines = match voice_key {
+            Some(_) => self.part.get_voice_lines(words),
+            None => Vec::new(),
+        };
+
+        let mut voice = None;
+        let selected = self
+            .voice_index
+            .and_then(|i| voice_lines.get(usize::from(i)));
+
+        if let (Some(voice_key), Some((label, line_key))) = (voice_key, selected) {
+            match azur.get_voice_clip(voice_key, line_key) {
+                Some(clip) => {
+                    let filename = format!("{voice_key}_{line_key}.fsb");
+                    voice = Some(CreateAttachment::bytes(clip, filename));
+                    embed = embed.footer(CreateEmbedFooter::new(format!("Attached: {label}")));
+                },
+                None => {
+                    embed = embed.footer(CreateEmbedFooter::new(format!(
+                        "No audio was extracted for {label}."
+                    )));
+                },
+            }
+        }
+
+        // any other navigation clears the selected voice line
+        self.voice_index = None;
 
         let mut components = Vec::new();
 
@@ -93,6 +165,22 @@ impl View {
             top_row.push(self.button_with_extra(true).label("EX"));
         }
 
+        if !skin.faces.is_empty() {
+            top_row.push(self.button_with_next_face(skin));
+        }
+
+        {
+            let mut view = super::skins::View::with_back(self.ship_id, self.to_custom_data());
+            view.page = u16::from(self.skin_index);
+
+            let button = CreateButton::new(view.to_custom_id())
+                .emoji('🖼')
+                .label("Painting")
+                .style(ButtonStyle::Secondary);
+
+            top_row.push(button);
+        }
+
         if !top_row.is_empty() {
             components.push(CreateActionRow::buttons(top_row));
         }
@@ -116,6 +204,9 @@ impl View {
         ]));
 
         if ship.skins.len() > 1 {
+            // other skins have different faces
+            self.face_index = None;
+
             let options: Vec<_> = ship
                 .skins
                 .iter()
@@ -131,7 +222,29 @@ impl View {
             ));
         }
 
-        (embed, components)
+        if !voice_lines.is_empty() {
+            use crate::modules::core::buttons::None;
+
+            let options: Vec<_> = voice_lines
+                .into_iter()
+                .take(25)
+                .enumerate()
+                .map(|(index, (label, _))| self.select_with_voice_index(label, index))
+                .collect();
+
+            // the skin select may already use this view's custom ID, and
+            // custom IDs must be unique within a message
+            let key = ptr::from_ref(&self.voice_index) as u16;
+            let custom_id = None::new(key, 0).to_custom_id();
+
+            components.push(create_string_select_menu_row(
+                custom_id,
+                options,
+                "Attach voice line audio...",
+            ));
+        }
+
+        (embed, components, voice)
     }
 
     /// Creates a button that redirects to a different Base/EX state.
@@ -139,6 +252,26 @@ impl View {
         self.new_button(|s| &mut s.extra, extra, bool::into)
     }
 
+    /// Creates a button that cycles to the skin's next expression.
+    ///
+    /// After the last expression, this returns to the default image.
+    fn button_with_next_face<'a>(&mut self, skin: &ShipSkin) -> CreateButton<'a> {
+        let next = match self.face_index {
+            Some(index) if usize::from(index) + 1 < skin.faces.len() => Some(index + 1),
+            Some(_) => None,
+            None => Some(0),
+        };
+
+        let label = match next {
+            Some(index) => format!("Face {}/{}", index + 1, skin.faces.len()),
+            None => "Default".to_owned(),
+        };
+
+        self.new_button(|s| &mut s.face_index, next, |_| 0)
+            .emoji('🙂')
+            .label(label)
+    }
+
     /// Creates a button that redirects to a different viewed part.
     fn button_with_part<'a>(&mut self, part: ViewPart, words: &ShipSkinWords) -> CreateButton<'a> {
         let disabled = self.part == part || !part.has_texts(words);
@@ -158,12 +291,19 @@ impl View {
         self.new_select_option(&skin.name, |s| &mut s.skin_index, index as u8)
     }
 
-    fn resolve<'a>(&self, ctx: &ButtonContext<'a>) -> Result<(&'a ShipData, &'a ShipSkin)> {
-        let ship = ctx
-            .data
-            .azur_lane()
-            .ship_by_id(self.ship_id)
-            .ok_or(AzurParseError::Ship)?;
+    /// Creates a select option that attaches the audio of a voice line.
+    fn select_with_voice_index<'a>(
+        &mut self,
+        label: Cow<'static, str>,
+        index: usize,
+    ) -> CreateSelectMenuOption<'a> {
+        // at most 25 options are created, so this won't truncate
+        #[allow(clippy::cast_possible_truncation)]
+        self.new_select_option(label, |s| &mut s.voice_index, Some(index as u8))
+    }
+
+    fn resolve<'a>(&self, azur: &'a HAzurLane) -> Result<(&'a ShipData, &'a ShipSkin)> {
+        let ship = azur.ship_by_id(self.ship_id).ok_or(AzurParseError::Ship)?;
 
         let skin = ship
             .skins
@@ -175,48 +315,50 @@ impl View {
 }
 
 /// Higher-order macro to share code logic for [`ViewPart`] functions.
+///
+/// Each line is passed with its label, field, and the game's key for it.
 macro_rules! impl_view_part_fn {
     ($self:expr, $words:expr, $add:ident) => {
         match $self {
             ViewPart::Info => {
-                $add!("Description", description);
-                $add!("Profile", introduction);
-                $add!("Acquisition", acquisition);
+                $add!("Description", description, "drop_descrip");
+                $add!("Profile", introduction, "profile");
+                $add!("Acquisition", acquisition, "unlock");
             }
             ViewPart::Main1 => {
-                $add!("Login", login);
+                $add!("Login", login, "login");
 
                 for line in &$words.main_screen {
                     $add!(main line);
                 }
 
-                $add!("Touch", touch);
-                $add!("Special Touch", special_touch);
-                $add!("Rub", rub);
+                $add!("Touch", touch, "touch");
+                $add!("Special Touch", special_touch, "touch2");
+                $add!("Rub", rub, "headtouch");
             }
             ViewPart::Main2 => {
-                $add!("Mission Reminder", mission_reminder);
-                $add!("Mission Complete", mission_complete);
-                $add!("Mail Reminder", mail_reminder);
-                $add!("Return to Port", return_to_port);
-                $add!("Commission Complete", commission_complete);
+                $add!("Mission Reminder", mission_reminder, "mission");
+                $add!("Mission Complete", mission_complete, "mission_complete");
+                $add!("Mail Reminder", mail_reminder, "mail");
+                $add!("Return to Port", return_to_port, "home");
+                $add!("Commission Complete", commission_complete, "expedition");
             }
             ViewPart::Affinity => {
-                $add!("Details", details);
-                $add!("Disappointed", disappointed);
-                $add!("Stranger", stranger);
-                $add!("Friendly", friendly);
-                $add!("Crush", crush);
-                $add!("Love", love);
-                $add!("Oath", oath);
+                $add!("Details", details, "detail");
+                $add!("Disappointed", disappointed, "feeling1");
+                $add!("Stranger", stranger, "feeling2");
+                $add!("Friendly", friendly, "feeling3");
+                $add!("Crush", crush, "feeling4");
+                $add!("Love", love, "feeling5");
+                $add!("Oath", oath, "propose");
             }
             ViewPart::Combat => {
-                $add!("Enhance", enhance);
-                $add!("Flagship Fight", flagship_fight);
-                $add!("Victory", victory);
-                $add!("Defeat", defeat);
-                $add!("Skill", skill);
-                $add!("Low Health", low_health);
+                $add!("Enhance", enhance, "upgrade");
+                $add!("Flagship Fight", flagship_fight, "battle");
+                $add!("Victory", victory, "win_mvp");
+                $add!("Defeat", defeat, "lose");
+                $add!("Skill", skill, "skill");
+                $add!("Low Health", low_health, "hp_warning");
 
                 for opt in &$words.couple_encourage {
                     $add!(couple opt);
@@ -228,13 +370,13 @@ macro_rules! impl_view_part_fn {
 
 impl ViewPart {
     /// Creates the embed description for the current state.
-    fn get_description(self, data: &HBotData, words: &ShipSkinWords) -> String {
+    fn get_description(self, azur: &HAzurLane, words: &ShipSkinWords) -> String {
         use crate::fmt::discord::escape_markdown;
 
         let mut result = String::new();
 
         macro_rules! add {
-            ($label:literal, $key:ident) => {
+            ($label:literal, $key:ident, $_:literal) => {
                 if let Some(text) = &words.$key {
                     write_str!(
                         result,
@@ -255,7 +397,7 @@ impl ViewPart {
                 write_str!(
                     result,
                     "- **{}:** {}\n",
-                    get_label_for_ship_couple_encourage(data, $opt),
+                    get_label_for_ship_couple_encourage(azur, $opt),
                     escape_markdown(&$opt.line),
                 );
             };
@@ -273,7 +415,7 @@ impl ViewPart {
     /// Determines whether this part shows any lines.
     fn has_texts(self, words: &ShipSkinWords) -> bool {
         macro_rules! check {
-            ($_:literal, $key:ident) => {
+            ($_:literal, $key:ident, $__:literal) => {
                 if words.$key.is_some() {
                     return true;
                 }
@@ -288,17 +430,49 @@ impl ViewPart {
         impl_view_part_fn!(self, words, check);
         false
     }
+
+    /// Gets the labels and voice clip keys of the lines shown for this part.
+    ///
+    /// The clip keys are the game's keys for the lines, which the collector
+    /// uses as the file names of the extracted audio.
+    fn get_voice_lines(self, words: &ShipSkinWords) -> Vec<(Cow<'static, str>, Cow<'static, str>)> {
+        let mut result = Vec::new();
+
+        macro_rules! add {
+            ($label:literal, $key:ident, $clip:literal) => {
+                if words.$key.is_some() {
+                    result.push((Cow::Borrowed($label), Cow::Borrowed($clip)));
+                }
+            };
+            (main $line:expr) => {
+                let index = $line.index() + 1;
+                result.push((
+                    Cow::Owned(format!("Main Screen {index}")),
+                    Cow::Owned(format!("main_{index}")),
+                ));
+            };
+            (couple $opt:expr) => {
+                // the clips for these aren't distinguishable by key
+                _ = $opt;
+            };
+        }
+
+        impl_view_part_fn!(self, words, add);
+        result
+    }
 }
 
-impl ButtonMessage for View {
-    fn edit_reply(self, ctx: ButtonContext<'_>) -> Result<EditReply<'_>> {
-        let (ship, skin) = self.resolve(&ctx)?;
-        Ok(self.edit_with_ship(&ctx, ship, skin))
+impl AzurButtonMessage for View {
+    fn edit_reply<'a>(self, ctx: ButtonContext<'a>, azur: &'a HAzurLane) -> Result<EditReply<'a>> {
+        let (ship, skin) = self.resolve(azur)?;
+        Ok(self.edit_with_ship(&ctx, azur, ship, skin))
     }
 }
 
+super::azur_button_reply!(View);
+
 /// Creates a label for a couple line.
-fn get_label_for_ship_couple_encourage(data: &HBotData, opt: &ShipCoupleEncourage) -> String {
+fn get_label_for_ship_couple_encourage(azur: &HAzurLane, opt: &ShipCoupleEncourage) -> String {
     fn fmt_sortie_count<'a>(
         label: &str,
         amount: u32,
@@ -326,7 +500,7 @@ fn get_label_for_ship_couple_encourage(data: &HBotData, opt: &ShipCoupleEncourag
         ShipCouple::ShipGroup(ship_ids) => {
             let ships = ship_ids
                 .iter()
-                .filter_map(|&id| data.azur_lane().ship_by_id(id))
+                .filter_map(|&id| azur.ship_by_id(id))
                 .map(|ship| ship.name.as_str());
 
             if ship_ids.len() == opt.amount.try_into().unwrap_or(0) {
@@ -0,0 +1,312 @@
+use std::cmp::Reverse;
+
+use azur_lane::equip::*;
+use azur_lane::ship::*;
+use utils::text::truncate;
+use utils::text::write_str::*;
+
+use super::ship::ViewAffinity;
+use super::{AzurButtonMessage, AzurParseError};
+use crate::buttons::prelude::*;
+use crate::helper::discord::create_string_select_menu_row;
+use crate::modules::azur::data::HAzurLane;
+use crate::modules::core::buttons::ToPage;
+
+/// The maximum amount of equip slots in a loadout.
+pub const SLOT_COUNT: usize = 5;
+
+/// The amount of equipment listed per page while picking gear for a slot.
+const PAGE_SIZE: usize = 24;
+
+/// Builds a loadout for a ship and shows the resulting stats.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct View {
+    pub ship_id: u32,
+    pub level: u8,
+    pub affinity: ViewAffinity,
+    pub retrofit: Option<u8>,
+    /// The equipment IDs per slot.
+    pub equips: [Option<u32>; SLOT_COUNT],
+    /// The slot gear is currently being picked for, if any.
+    pub editing: Option<u8>,
+    pub page: u16,
+    pub back: Option<CustomData>,
+}
+
+impl View {
+    /// Creates a new instance with an empty loadout.
+    pub fn new(ship_id: u32) -> Self {
+        Self {
+            ship_id,
+            level: 120,
+            affinity: ViewAffinity::Love,
+            retrofit: None,
+            equips: [None; SLOT_COUNT],
+            editing: None,
+            page: 0,
+            back: None,
+        }
+    }
+
+    /// Sets the back button target.
+    pub fn back(mut self, back: CustomData) -> Self {
+        self.back = Some(back);
+        self
+    }
+
+    /// Modifies the create-reply with preresolved ship data.
+    pub fn create_with_ship<'a>(
+        mut self,
+        azur: &'a HAzurLane,
+        ship: &'a ShipData,
+    ) -> CreateReply<'a> {
+        let equips = self.equips.map(|id| id.and_then(|id| azur.equip_by_id(id)));
+
+        let params = StatParams::new(u32::from(self.level), self.affinity.to_affinity());
+        let mut computed = ship.stats.compute(params);
+        let mut equip_text = String::new();
+
+        for (index, slot) in ship.equip_slots.iter().take(SLOT_COUNT).enumerate() {
+            if !equip_text.is_empty() {
+                equip_text.push('\n');
+            }
+
+            write_str!(equip_text, "{}. ", index + 1);
+            match equips[index] {
+                Some(equip) if can_equip(ship, slot, equip) => {
+                    write_str!(
+                        equip_text,
+                        "**{}** [{} {}]",
+                        equip.name,
+                        equip.rarity.name(),
+                        equip.kind.name()
+                    );
+
+                    for bonus in &equip.stat_bonuses {
+                        *computed.get_mut(bonus.stat_kind) += bonus.amount;
+                    }
+                },
+                Some(equip) => write_str!(equip_text, "~~{}~~ *(doesn't fit)*", equip.name),
+                None => equip_text.push_str("*Empty*"),
+            }
+        }
+
+        let affinity = match self.affinity {
+            ViewAffinity::Neutral => "Neutral",
+            ViewAffinity::Love => "\u{2764} 100",
+            ViewAffinity::Oath => "\u{1F497} 200",
+        };
+
+        let embed = CreateEmbed::new()
+            .author(super::get_ship_wiki_url(ship))
+            .description(format!("Lv.{} \u{2E31} {affinity}", self.level))
+            .color(ship.rarity.color_rgb())
+            .field("Equipment", equip_text, false)
+            .field(
+                "Stats",
+                super::stats::get_stats_text(ship, &computed),
+                false,
+            );
+
+        let rows = match self.editing.filter(|&s| usize::from(s) < SLOT_COUNT) {
+            Some(slot) => self.picker_rows(azur, ship, slot),
+            None => self.main_rows(ship, equips),
+        };
+
+        CreateReply::new().embed(embed).components(rows)
+    }
+
+    /// Creates the rows while no slot is being edited.
+    fn main_rows<'a>(
+        &mut self,
+        ship: &ShipData,
+        equips: [Option<&Equip>; SLOT_COUNT],
+    ) -> Vec<CreateActionRow<'a>> {
+        let mut rows = Vec::new();
+
+        let options: Vec<_> = ship
+            .equip_slots
+            .iter()
+            .take(SLOT_COUNT)
+            .zip(equips)
+            .enumerate()
+            .filter_map(|(index, (slot, equip))| {
+                let index = u8::try_from(index).ok()?;
+                let label = format!(
+                    "Slot {}: {}",
+                    index + 1,
+                    equip.map_or("Empty", |e| e.name.as_str())
+                );
+
+                let kinds = slot
+                    .allowed
+                    .iter()
+                    .map(|k| k.name())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                let option = CreateSelectMenuOption::new(
+                    truncate(label, 100),
+                    self.with_editing(Some(index)).to_custom_id(),
+                )
+                .description(truncate(kinds, 100));
+
+                Some(option)
+            })
+            .collect();
+
+        if !options.is_empty() {
+            rows.push(create_string_select_menu_row(
+                self.to_custom_id(),
+                options,
+                "Change equipment...",
+            ));
+        }
+
+        let mut row = vec![
+            self.button_with_level(120).label("Lv.120"),
+            self.button_with_level(125).label("Lv.125"),
+            self.button_with_affinity(ViewAffinity::Love)
+                .emoji('❤')
+                .label("100"),
+            self.button_with_affinity(ViewAffinity::Oath)
+                .emoji('💗')
+                .label("200"),
+        ];
+
+        if let Some(back) = &self.back {
+            row.insert(
+                0,
+                CreateButton::new(back.to_custom_id())
+                    .emoji('⏪')
+                    .label("Back"),
+            );
+        }
+
+        rows.push(CreateActionRow::buttons(row));
+        rows
+    }
+
+    /// Creates the rows to pick the equipment for a slot.
+    fn picker_rows<'a>(
+        &mut self,
+        azur: &'a HAzurLane,
+        ship: &ShipData,
+        slot_index: u8,
+    ) -> Vec<CreateActionRow<'a>> {
+        let mut rows = Vec::new();
+        let index = usize::from(slot_index);
+
+        let mut fitting: Vec<&Equip> = match ship.equip_slots.get(index) {
+            Some(slot) => azur
+                .equips()
+                .iter()
+                .filter(|e| can_equip(ship, slot, e))
+                .collect(),
+            None => Vec::new(),
+        };
+
+        fitting.sort_by_key(|e| Reverse(e.rarity));
+
+        #[allow(clippy::cast_possible_truncation)]
+        let page_count = fitting.len().div_ceil(PAGE_SIZE).max(1) as u16;
+        self.page = self.page.min(page_count - 1);
+        let page = usize::from(self.page);
+
+        let mut options = Vec::new();
+        if page == 0 {
+            let mut view = self.with_editing(None);
+            view.equips[index] = None;
+            options.push(CreateSelectMenuOption::new("Empty", view.to_custom_id()));
+        }
+
+        for equip in fitting.iter().skip(PAGE_SIZE * page).take(PAGE_SIZE) {
+            let mut view = self.with_editing(None);
+            view.equips[index] = Some(equip.equip_id);
+
+            let description = format!(
+                "{} {} {}",
+                equip.rarity.name(),
+                equip.faction.prefix().unwrap_or("Col."),
+                equip.kind.name()
+            );
+
+            options.push(
+                CreateSelectMenuOption::new(truncate(&equip.name, 100), view.to_custom_id())
+                    .description(description)
+                    .default_selection(self.equips[index] == Some(equip.equip_id)),
+            );
+        }
+
+        let pagination = ToPage::build_row(self, |s| &mut s.page).exact_page_count(page_count);
+
+        if let Some(pagination) = pagination.end() {
+            rows.push(pagination);
+        }
+
+        rows.push(create_string_select_menu_row(
+            self.to_custom_id(),
+            options,
+            format!("Pick equipment for slot {}...", index + 1),
+        ));
+
+        rows.push(CreateActionRow::buttons(vec![CreateButton::new(
+            self.with_editing(None).to_custom_id(),
+        )
+        .label("Cancel")
+        .style(ButtonStyle::Secondary)]));
+
+        rows
+    }
+
+    /// Creates a copy that edits a different slot, starting at the first page.
+    fn with_editing(&self, editing: Option<u8>) -> Self {
+        Self {
+            editing,
+            page: 0,
+            ..self.clone()
+        }
+    }
+
+    /// Gets a button that redirects to a different level.
+    fn button_with_level<'a>(&mut self, level: u8) -> CreateButton<'a> {
+        self.new_button(|s| &mut s.level, level, u8::into)
+    }
+
+    /// Gets a button that redirects to a different affinity.
+    fn button_with_affinity<'a>(&mut self, affinity: ViewAffinity) -> CreateButton<'a> {
+        self.new_button(|s| &mut s.affinity, affinity, |u| u as u16)
+    }
+
+    fn resolve<'a>(&self, azur: &'a HAzurLane) -> Result<&'a ShipData> {
+        let ship = azur.ship_by_id(self.ship_id).ok_or(AzurParseError::Ship)?;
+
+        Ok(self
+            .retrofit
+            .and_then(|index| ship.retrofits.get(usize::from(index)))
+            .unwrap_or(ship))
+    }
+}
+
+impl AzurButtonMessage for View {
+    fn edit_reply<'a>(self, _ctx: ButtonContext<'a>, azur: &'a HAzurLane) -> Result<EditReply<'a>> {
+        let ship = self.resolve(azur)?;
+        Ok(self.create_with_ship(azur, ship).into())
+    }
+
+    fn edit_modal_reply<'a>(
+        mut self,
+        ctx: ModalContext<'a>,
+        azur: &'a HAzurLane,
+    ) -> Result<EditReply<'a>> {
+        ToPage::set_page_from(&mut self.page, ctx.interaction);
+        let ship = self.resolve(azur)?;
+        Ok(self.create_with_ship(azur, ship).into())
+    }
+}
+
+super::azur_button_reply!(View);
+
+fn can_equip(ship: &ShipData, slot: &EquipSlot, equip: &Equip) -> bool {
+    slot.allowed.contains(&equip.kind) && !equip.hull_disallowed.contains(&ship.hull_type)
+}
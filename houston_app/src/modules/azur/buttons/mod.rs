@@ -1,20 +1,28 @@
 use azur_lane::ship::{HullType, ShipData};
+use azur_lane::{Locale, LocalizedNames};
 
+use super::data::HAzurLane;
 use crate::buttons::prelude::*;
 
 pub mod augment;
+pub mod compare;
+pub mod drops;
 pub mod equip;
 pub mod juustagram_chat;
 pub mod lines;
+pub mod loadout;
 pub mod search_augment;
 pub mod search_equip;
 pub mod search_juustagram_chat;
 pub mod search_ship;
+pub mod search_skill;
 pub mod search_special_secretary;
 pub mod shadow_equip;
 pub mod ship;
 pub mod skill;
+pub mod skins;
 pub mod special_secretary;
+pub mod stats;
 
 #[derive(Debug, thiserror::Error)]
 enum AzurParseError {
@@ -49,6 +57,18 @@ fn get_thumbnail_filename(embed: &Embed) -> Option<&str> {
     Some(name.split_once('.').map_or(name, |a| a.0))
 }
 
+/// Gets the name to display for a locale.
+///
+/// Falls back to the default `name` if no locale is set or the name for that
+/// locale isn't known.
+pub fn localized_name<'a>(
+    name: &'a str,
+    names: &'a LocalizedNames,
+    locale: Option<Locale>,
+) -> &'a str {
+    locale.and_then(|l| names.get(l)).unwrap_or(name)
+}
+
 pub fn hull_emoji(hull_type: HullType, data: &HBotData) -> &ReactionType {
     let e = data.app_emojis();
     match hull_type {
@@ -75,6 +95,71 @@ pub fn hull_emoji(hull_type: HullType, data: &HBotData) -> &ReactionType {
     }
 }
 
+/// Like [`ButtonMessage`], but the reply may borrow from a snapshot of the
+/// game data.
+///
+/// Use [`azur_button_reply`] to implement [`ButtonArgsReply`] for the type.
+pub trait AzurButtonMessage: Sized + Send {
+    /// Creates an edit-reply payload.
+    fn edit_reply<'a>(self, ctx: ButtonContext<'a>, azur: &'a HAzurLane) -> Result<EditReply<'a>>;
+
+    /// Creates an edit-reply payload.
+    fn edit_modal_reply<'a>(
+        self,
+        ctx: ModalContext<'a>,
+        azur: &'a HAzurLane,
+    ) -> Result<EditReply<'a>> {
+        _ = (ctx, azur);
+        anyhow::bail!("this button args type does not support modals");
+    }
+}
+
+/// Implements [`ButtonArgsReply`] for an [`AzurButtonMessage`].
+///
+/// The game data snapshot is held until the reply has been sent, so a reload
+/// in the meantime doesn't affect it.
+macro_rules! azur_button_reply {
+    ($Ty:ty) => {
+        impl $crate::buttons::ButtonArgsReply for $Ty {
+            async fn reply(
+                self,
+                ctx: $crate::buttons::ButtonContext<'_>,
+            ) -> $crate::prelude::Result {
+                use $crate::modules::azur::buttons::AzurButtonMessage as _;
+
+                let azur = ctx.data.azur_lane();
+                self.edit_reply(ctx.clone(), &azur)?
+                    .execute_as_response(
+                        &ctx.serenity.http,
+                        ctx.interaction.id,
+                        &ctx.interaction.token,
+                    )
+                    .await?;
+                Ok(())
+            }
+
+            async fn modal_reply(
+                self,
+                ctx: $crate::buttons::ModalContext<'_>,
+            ) -> $crate::prelude::Result {
+                use $crate::modules::azur::buttons::AzurButtonMessage as _;
+
+                let azur = ctx.data.azur_lane();
+                self.edit_modal_reply(ctx.clone(), &azur)?
+                    .execute_as_response(
+                        &ctx.serenity.http,
+                        ctx.interaction.id,
+                        &ctx.interaction.token,
+                    )
+                    .await?;
+                Ok(())
+            }
+        }
+    };
+}
+
+pub(crate) use azur_button_reply;
+
 macro_rules! pagination {
     ($obj:expr, $options:expr, $iter:expr, $label:expr) => {{
         if $options.is_empty() {
@@ -1,7 +1,9 @@
 use azur_lane::equip::*;
 use azur_lane::ship::*;
+use azur_lane::Locale;
 use utils::text::write_str::*;
 
+use super::AzurButtonMessage;
 use crate::buttons::prelude::*;
 use crate::modules::azur::data::HAzurLane;
 use crate::modules::core::buttons::ToPage;
@@ -10,6 +12,7 @@ use crate::modules::core::buttons::ToPage;
 pub struct View {
     page: u16,
     filter: Filter,
+    locale: Option<Locale>,
 }
 
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
@@ -24,7 +27,17 @@ const PAGE_SIZE: usize = 15;
 
 impl View {
     pub fn new(filter: Filter) -> Self {
-        Self { page: 0, filter }
+        Self {
+            page: 0,
+            filter,
+            locale: None,
+        }
+    }
+
+    /// Sets the locale to display names in.
+    pub fn locale(mut self, locale: Option<Locale>) -> Self {
+        self.locale = locale;
+        self
     }
 
     pub fn create_with_iter<'a>(
@@ -36,13 +49,13 @@ impl View {
         let mut options = Vec::new();
 
         for augment in iter.by_ref().take(PAGE_SIZE) {
-            writeln_str!(desc, "- **{}** [{}]", augment.name, augment.rarity.name());
+            let name = super::localized_name(&augment.name, &augment.names, self.locale);
+            writeln_str!(desc, "- **{name}** [{}]", augment.rarity.name());
 
-            let view = super::augment::View::new(augment.augment_id).back(self.to_custom_data());
-            options.push(CreateSelectMenuOption::new(
-                &augment.name,
-                view.to_custom_id(),
-            ));
+            let view = super::augment::View::new(augment.augment_id)
+                .locale(self.locale)
+                .back(self.to_custom_data());
+            options.push(CreateSelectMenuOption::new(name, view.to_custom_id()));
         }
 
         let rows = super::pagination!(self, options, iter, "View augment module...");
@@ -58,27 +71,33 @@ impl View {
         Ok(CreateReply::new().embed(embed).components(rows))
     }
 
-    pub fn create(self, data: &HBotData) -> Result<CreateReply<'_>> {
+    pub fn create<'a>(self, data: &'a HBotData, azur: &'a HAzurLane) -> Result<CreateReply<'a>> {
         let filtered = self
             .filter
-            .iterate(data.azur_lane())
+            .iterate(azur)
             .skip(PAGE_SIZE * usize::from(self.page));
 
         self.create_with_iter(data, filtered)
     }
 }
 
-impl ButtonMessage for View {
-    fn edit_reply(self, ctx: ButtonContext<'_>) -> Result<EditReply<'_>> {
-        self.create(ctx.data).map(EditReply::from)
+impl AzurButtonMessage for View {
+    fn edit_reply<'a>(self, ctx: ButtonContext<'a>, azur: &'a HAzurLane) -> Result<EditReply<'a>> {
+        self.create(ctx.data, azur).map(EditReply::from)
     }
 
-    fn edit_modal_reply(mut self, ctx: ModalContext<'_>) -> Result<EditReply<'_>> {
+    fn edit_modal_reply<'a>(
+        mut self,
+        ctx: ModalContext<'a>,
+        azur: &'a HAzurLane,
+    ) -> Result<EditReply<'a>> {
         ToPage::set_page_from(&mut self.page, ctx.interaction);
-        self.create(ctx.data).map(EditReply::from)
+        self.create(ctx.data, azur).map(EditReply::from)
     }
 }
 
+super::azur_button_reply!(View);
+
 type FIter<'a> = Box<dyn Iterator<Item = &'a Augment> + 'a>;
 
 impl Filter {
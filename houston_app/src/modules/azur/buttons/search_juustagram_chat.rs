@@ -2,6 +2,7 @@ use azur_lane::juustagram::*;
 use utils::text::truncate;
 use utils::text::write_str::*;
 
+use super::AzurButtonMessage;
 use crate::buttons::prelude::*;
 use crate::modules::azur::data::HAzurLane;
 use crate::modules::core::buttons::ToPage;
@@ -27,6 +28,7 @@ impl View {
     pub fn create_with_iter<'a>(
         mut self,
         data: &'a HBotData,
+        azur: &'a HAzurLane,
         mut iter: impl Iterator<Item = &'a Chat>,
     ) -> Result<CreateReply<'a>> {
         let mut desc = String::new();
@@ -34,7 +36,7 @@ impl View {
 
         for chat in iter.by_ref().take(PAGE_SIZE) {
             let chat_name: Cow<'_, str>;
-            if let Some(ship) = data.azur_lane().ship_by_id(chat.group_id) {
+            if let Some(ship) = azur.ship_by_id(chat.group_id) {
                 writeln_str!(desc, "- **{}** [{}]", chat.name, ship.name);
                 chat_name = format!("{} [{}]", chat.name, ship.name).into();
             } else {
@@ -62,27 +64,33 @@ impl View {
         Ok(CreateReply::new().embed(embed).components(rows))
     }
 
-    pub fn create(self, data: &HBotData) -> Result<CreateReply<'_>> {
+    pub fn create<'a>(self, data: &'a HBotData, azur: &'a HAzurLane) -> Result<CreateReply<'a>> {
         let filtered = self
             .filter
-            .iterate(data.azur_lane())
+            .iterate(azur)
             .skip(PAGE_SIZE * usize::from(self.page));
 
-        self.create_with_iter(data, filtered)
+        self.create_with_iter(data, azur, filtered)
     }
 }
 
-impl ButtonMessage for View {
-    fn edit_reply(self, ctx: ButtonContext<'_>) -> Result<EditReply<'_>> {
-        self.create(ctx.data).map(EditReply::from)
+impl AzurButtonMessage for View {
+    fn edit_reply<'a>(self, ctx: ButtonContext<'a>, azur: &'a HAzurLane) -> Result<EditReply<'a>> {
+        self.create(ctx.data, azur).map(EditReply::from)
     }
 
-    fn edit_modal_reply(mut self, ctx: ModalContext<'_>) -> Result<EditReply<'_>> {
+    fn edit_modal_reply<'a>(
+        mut self,
+        ctx: ModalContext<'a>,
+        azur: &'a HAzurLane,
+    ) -> Result<EditReply<'a>> {
         ToPage::set_page_from(&mut self.page, ctx.interaction);
-        self.create(ctx.data).map(EditReply::from)
+        self.create(ctx.data, azur).map(EditReply::from)
     }
 }
 
+super::azur_button_reply!(View);
+
 impl Filter {
     fn iterate<'a>(&self, data: &'a HAzurLane) -> Box<dyn Iterator<Item = &'a Chat> + 'a> {
         match &self.ship {
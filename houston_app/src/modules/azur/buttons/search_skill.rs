@@ -0,0 +1,114 @@
+use azur_lane::skill::Skill;
+use utils::text::truncate;
+use utils::text::write_str::*;
+
+use super::AzurButtonMessage;
+use crate::buttons::prelude::*;
+use crate::modules::azur::data::{HAzurLane, SkillSource};
+use crate::modules::core::buttons::ToPage;
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct View {
+    page: u16,
+    filter: Filter,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Filter {
+    pub text: String,
+}
+
+const PAGE_SIZE: usize = 15;
+
+impl View {
+    pub fn new(filter: Filter) -> Self {
+        Self { page: 0, filter }
+    }
+
+    pub fn create_with_iter<'a>(
+        mut self,
+        data: &'a HBotData,
+        azur: &'a HAzurLane,
+        mut iter: impl Iterator<Item = (SkillSource, &'a Skill)>,
+    ) -> Result<CreateReply<'a>> {
+        let mut desc = String::new();
+        let mut options = Vec::new();
+
+        for (source, skill) in iter.by_ref().take(PAGE_SIZE) {
+            let (owner, custom_id) = match source {
+                SkillSource::Ship { group_id, retrofit } => {
+                    let Some(ship) = azur.ship_by_id(group_id) else {
+                        continue;
+                    };
+
+                    let mut view = super::ship::View::new(group_id).back(self.to_custom_data());
+                    view.retrofit = retrofit;
+                    (ship.name.as_str(), view.to_custom_id())
+                },
+                SkillSource::Augment(augment_id) => {
+                    let Some(augment) = azur.augment_by_id(augment_id) else {
+                        continue;
+                    };
+
+                    let view = super::augment::View::new(augment_id).back(self.to_custom_data());
+                    (augment.name.as_str(), view.to_custom_id())
+                },
+            };
+
+            writeln_str!(
+                desc,
+                "- {} **{}** [{}]",
+                skill.category.emoji(),
+                skill.name,
+                owner
+            );
+
+            let label = format!("{} [{}]", skill.name, owner);
+            options.push(CreateSelectMenuOption::new(truncate(label, 100), custom_id));
+        }
+
+        let rows = super::pagination!(self, options, iter, "View owner...");
+
+        let embed = CreateEmbed::new()
+            .author(CreateEmbedAuthor::new("Skills"))
+            .description(desc)
+            .color(data.config().embed_color);
+
+        Ok(CreateReply::new().embed(embed).components(rows))
+    }
+
+    pub fn create<'a>(self, data: &'a HBotData, azur: &'a HAzurLane) -> Result<CreateReply<'a>> {
+        let filtered = self
+            .filter
+            .iterate(azur)
+            .skip(PAGE_SIZE * usize::from(self.page));
+
+        self.create_with_iter(data, azur, filtered)
+    }
+}
+
+impl AzurButtonMessage for View {
+    fn edit_reply<'a>(self, ctx: ButtonContext<'a>, azur: &'a HAzurLane) -> Result<EditReply<'a>> {
+        self.create(ctx.data, azur).map(EditReply::from)
+    }
+
+    fn edit_modal_reply<'a>(
+        mut self,
+        ctx: ModalContext<'a>,
+        azur: &'a HAzurLane,
+    ) -> Result<EditReply<'a>> {
+        ToPage::set_page_from(&mut self.page, ctx.interaction);
+        self.create(ctx.data, azur).map(EditReply::from)
+    }
+}
+
+super::azur_button_reply!(View);
+
+impl Filter {
+    fn iterate<'a>(
+        &self,
+        data: &'a HAzurLane,
+    ) -> impl Iterator<Item = (SkillSource, &'a Skill)> + use<'a> {
+        data.skills_by_text(&self.text)
+    }
+}
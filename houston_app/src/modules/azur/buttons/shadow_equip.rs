@@ -3,8 +3,9 @@ use azur_lane::ship::*;
 use utils::text::write_str::*;
 
 use super::ship::View as ShipView;
-use super::AzurParseError;
+use super::{AzurButtonMessage, AzurParseError};
 use crate::buttons::prelude::*;
+use crate::modules::azur::data::HAzurLane;
 
 /// View a ship's shadow equip.
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
@@ -57,6 +58,23 @@ impl View {
             }
         }
 
+        for default in &ship.default_equips {
+            let mut value = crate::fmt::azur::EquipStats::new(&default.equip).to_string();
+            if let Some(weapons) = format_weapons(&default.equip.weapons) {
+                write_str!(value, "\n{weapons}");
+            }
+
+            if value.trim().is_empty() {
+                continue;
+            }
+
+            embed = embed.field(
+                format!("**`Slot {}:`** {}", default.slot + 1, default.equip.name),
+                value,
+                true,
+            );
+        }
+
         let components = vec![CreateActionRow::buttons(vec![{
             let back = self.inner.to_custom_id();
             CreateButton::new(back).emoji('⏪').label("Back")
@@ -66,11 +84,9 @@ impl View {
     }
 }
 
-impl ButtonMessage for View {
-    fn edit_reply(self, ctx: ButtonContext<'_>) -> Result<EditReply<'_>> {
-        let ship = ctx
-            .data
-            .azur_lane()
+impl AzurButtonMessage for View {
+    fn edit_reply<'a>(self, _ctx: ButtonContext<'a>, azur: &'a HAzurLane) -> Result<EditReply<'a>> {
+        let ship = azur
             .ship_by_id(self.inner.ship_id)
             .ok_or(AzurParseError::Ship)?;
         Ok(
@@ -85,3 +101,5 @@ impl ButtonMessage for View {
         )
     }
 }
+
+super::azur_button_reply!(View);
@@ -2,11 +2,13 @@ use std::iter;
 
 use azur_lane::equip::*;
 use azur_lane::ship::*;
+use azur_lane::Locale;
 use utils::join;
 use utils::text::write_str::*;
 
-use super::AzurParseError;
+use super::{AzurButtonMessage, AzurParseError};
 use crate::buttons::prelude::*;
+use crate::modules::azur::data::HAzurLane;
 
 /// View general ship details.
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
@@ -15,6 +17,8 @@ pub struct View {
     pub level: u8,
     pub affinity: ViewAffinity,
     pub retrofit: Option<u8>,
+    /// The locale to display names in.
+    pub locale: Option<Locale>,
     pub back: Option<CustomData>,
 }
 
@@ -36,6 +40,7 @@ impl View {
             level: 120,
             affinity: ViewAffinity::Love,
             retrofit: None,
+            locale: None,
             back: None,
         }
     }
@@ -46,20 +51,27 @@ impl View {
         self
     }
 
+    /// Sets the locale to display names in.
+    pub fn locale(mut self, locale: Option<Locale>) -> Self {
+        self.locale = locale;
+        self
+    }
+
     /// Modifies the create-reply with preresolved ship data.
     pub fn create_with_ship<'a>(
         self,
         data: &'a HBotData,
+        azur: &'a HAzurLane,
         ship: &'a ShipData,
         base_ship: Option<&'a ShipData>,
     ) -> CreateReply<'a> {
         let base_ship = base_ship.unwrap_or(ship);
-        let (mut embed, rows) = self.with_ship(data, ship, base_ship);
+        let (mut embed, rows) = self.with_ship(data, azur, ship, base_ship);
 
         let mut create = CreateReply::new();
 
         if let Some(skin) = base_ship.skin_by_id(ship.default_skin_id) {
-            if let Some(image_data) = data.azur_lane().get_chibi_image(&skin.image_key) {
+            if let Some(image_data) = azur.get_chibi_image(&skin.image_key) {
                 let filename = format!("{}.webp", skin.image_key);
                 embed = embed.thumbnail(format!("attachment://{}", filename));
                 create = create.attachment(CreateAttachment::bytes(image_data, filename));
@@ -72,17 +84,18 @@ impl View {
     fn edit_with_ship<'a>(
         self,
         ctx: &ButtonContext<'a>,
+        azur: &'a HAzurLane,
         ship: &'a ShipData,
         base_ship: Option<&'a ShipData>,
     ) -> EditReply<'a> {
         let base_ship = base_ship.unwrap_or(ship);
-        let (mut embed, rows) = self.with_ship(ctx.data, ship, base_ship);
+        let (mut embed, rows) = self.with_ship(ctx.data, azur, ship, base_ship);
         let mut create = EditReply::new();
 
         // try expressions when
         let base_skin = || {
             let skin = base_ship.skin_by_id(ship.default_skin_id)?;
-            let image = ctx.data.azur_lane().get_chibi_image(&skin.image_key)?;
+            let image = azur.get_chibi_image(&skin.image_key)?;
             Some((skin, image))
         };
 
@@ -105,10 +118,11 @@ impl View {
     fn with_ship<'a>(
         mut self,
         data: &'a HBotData,
+        azur: &'a HAzurLane,
         ship: &'a ShipData,
         base_ship: &'a ShipData,
     ) -> (CreateEmbed<'a>, Vec<CreateActionRow<'a>>) {
-        let description = format!(
+        let mut description = format!(
             "[{}] {:★<star_pad$}\n{} {} {}",
             ship.rarity.name(),
             '★',
@@ -118,13 +132,33 @@ impl View {
             star_pad = usize::from(ship.stars)
         );
 
+        // list names from other locales, if they differ
+        let shown_name = super::localized_name(&ship.name, &ship.names, self.locale);
+        for (locale, name) in ship.names.iter().filter(|n| n.1 != shown_name) {
+            write_str!(description, "\n-# {}: {}", locale.name(), name);
+        }
+
+        // list where the ship drops, if anywhere
+        let mut stages = azur.stages_by_ship_drop(base_ship.group_id);
+        if let Some(first) = stages.next() {
+            write_str!(description, "\n-# Drops: {}", first.code);
+            for stage in stages.by_ref().take(9) {
+                write_str!(description, ", {}", stage.code);
+            }
+
+            if stages.next().is_some() {
+                description.push_str(", ...");
+            }
+        }
+
+        let author_name = super::localized_name(&base_ship.name, &base_ship.names, self.locale);
         let embed = CreateEmbed::new()
-            .author(super::get_ship_wiki_url(base_ship))
+            .author(super::get_ship_wiki_url(base_ship).name(author_name))
             .description(description)
             .color(ship.rarity.color_rgb())
             .fields(self.get_stats_field(ship))
             .fields(self.get_equip_field(ship))
-            .fields(self.get_skills_field(data, ship));
+            .fields(self.get_skills_field(azur, ship));
 
         let mut rows = Vec::new();
         self.add_upgrade_row(&mut rows);
@@ -175,7 +209,36 @@ impl View {
             row.push(button);
         }
 
-        if !ship.shadow_equip.is_empty() || !ship.depth_charges.is_empty() {
+        {
+            let mut view = super::stats::View::new(self.ship_id).back(self_custom_data.clone());
+            view.level = self.level;
+            view.affinity = self.affinity.to_affinity();
+            view.retrofit = self.retrofit;
+
+            let button = CreateButton::new(view.to_custom_id())
+                .label("Stats")
+                .style(ButtonStyle::Secondary);
+
+            row.push(button);
+        }
+
+        if !ship.equip_slots.is_empty() {
+            let mut view = super::loadout::View::new(self.ship_id).back(self_custom_data.clone());
+            view.level = self.level;
+            view.affinity = self.affinity;
+            view.retrofit = self.retrofit;
+
+            let button = CreateButton::new(view.to_custom_id())
+                .label("Loadout")
+                .style(ButtonStyle::Secondary);
+
+            row.push(button);
+        }
+
+        if !ship.shadow_equip.is_empty()
+            || !ship.depth_charges.is_empty()
+            || !ship.default_equips.is_empty()
+        {
             let view = super::shadow_equip::View::new(self.clone());
             let button = CreateButton::new(view.to_custom_id())
                 .label("Shadow Equip")
@@ -257,66 +320,13 @@ impl View {
 
     /// Creates the embed field that display the stats.
     fn get_stats_field<'a>(&self, ship: &ShipData) -> [SimpleEmbedFieldCreate<'a>; 1] {
-        let stats = &ship.stats;
-        let affinity = self.affinity.to_mult();
-
-        #[allow(clippy::cast_sign_loss)]
-        #[allow(clippy::cast_possible_truncation)]
-        fn f(n: f64) -> u32 {
-            n.floor() as u32
-        }
-        macro_rules! s {
-            ($val:expr) => {{
-                f($val.calc(u32::from(self.level), affinity))
-            }};
-        }
-
-        let content = if ship.hull_type.team_type() != TeamType::Submarine {
-            format!(
-                "**`HP:`**`{: >5}` \u{2E31} **`{: <7}`**` ` \u{2E31} **`RLD:`**`{: >4}`\n\
-                 **`FP:`**`{: >5}` \u{2E31} **`TRP:`**`{: >4}` \u{2E31} **`EVA:`**`{: >4}`\n\
-                 **`AA:`**`{: >5}` \u{2E31} **`AVI:`**`{: >4}` \u{2E31} **`ACC:`**`{: >4}`\n\
-                 **`ASW:`**`{: >4}` \u{2E31} **`SPD:`**`{: >4}`\n\
-                 **`LCK:`**`{: >4}` \u{2E31} **`Cost:`**`{: >3}`",
-                s!(stats.hp),
-                stats.armor.name(),
-                s!(stats.rld),
-                s!(stats.fp),
-                s!(stats.trp),
-                s!(stats.eva),
-                s!(stats.aa),
-                s!(stats.avi),
-                s!(stats.acc),
-                s!(stats.asw),
-                f(stats.spd),
-                f(stats.lck),
-                stats.cost
-            )
-        } else {
-            format!(
-                "**`HP:`**`{: >5}` \u{2E31} **`{: <7}`**` ` \u{2E31} **`RLD:`**`{: >4}`\n\
-                 **`FP:`**`{: >5}` \u{2E31} **`TRP:`**`{: >4}` \u{2E31} **`EVA:`**`{: >4}`\n\
-                 **`AA:`**`{: >5}` \u{2E31} **`AVI:`**`{: >4}` \u{2E31} **`ACC:`**`{: >4}`\n\
-                 **`OXY:`**`{: >4}` \u{2E31} **`AMO:`**`{: >4}` \u{2E31} **`SPD:`**`{: >4}`\n\
-                 **`LCK:`**`{: >4}` \u{2E31} **`Cost:`**`{: >3}`",
-                s!(stats.hp),
-                stats.armor.name(),
-                s!(stats.rld),
-                s!(stats.fp),
-                s!(stats.trp),
-                s!(stats.eva),
-                s!(stats.aa),
-                s!(stats.avi),
-                s!(stats.acc),
-                stats.oxy,
-                stats.amo,
-                f(stats.spd),
-                f(stats.lck),
-                stats.cost
-            )
-        };
-
-        [("Stats", content, false)]
+        let params = StatParams::new(u32::from(self.level), self.affinity.to_affinity());
+        let computed = ship.stats.compute(params);
+        [(
+            "Stats",
+            super::stats::get_stats_text(ship, &computed),
+            false,
+        )]
     }
 
     /// Creates the embed field that displays the weapon equipment slots.
@@ -380,7 +390,7 @@ impl View {
     /// Creates the embed field that display the skill summary.
     fn get_skills_field<'a>(
         &self,
-        data: &HBotData,
+        azur: &HAzurLane,
         ship: &ShipData,
     ) -> Option<SimpleEmbedFieldCreate<'a>> {
         // There isn't any way a unique augment can do anything if there are no skills
@@ -395,7 +405,7 @@ impl View {
                 write_str!(text, "{} **{}**", s.category.emoji(), s.name);
             }
 
-            let augments = data.azur_lane().augments_by_ship_id(ship.group_id);
+            let augments = azur.augments_by_ship_id(ship.group_id);
             for augment in augments {
                 if !text.is_empty() {
                     text.push('\n');
@@ -408,33 +418,31 @@ impl View {
     }
 }
 
-impl ButtonMessage for View {
-    fn edit_reply(self, ctx: ButtonContext<'_>) -> Result<EditReply<'_>> {
-        let ship = ctx
-            .data
-            .azur_lane()
-            .ship_by_id(self.ship_id)
-            .ok_or(AzurParseError::Ship)?;
+impl AzurButtonMessage for View {
+    fn edit_reply<'a>(self, ctx: ButtonContext<'a>, azur: &'a HAzurLane) -> Result<EditReply<'a>> {
+        let ship = azur.ship_by_id(self.ship_id).ok_or(AzurParseError::Ship)?;
 
         let edit = match self
             .retrofit
             .and_then(|index| ship.retrofits.get(usize::from(index)))
         {
-            None => self.edit_with_ship(&ctx, ship, None),
-            Some(retrofit) => self.edit_with_ship(&ctx, retrofit, Some(ship)),
+            None => self.edit_with_ship(&ctx, azur, ship, None),
+            Some(retrofit) => self.edit_with_ship(&ctx, azur, retrofit, Some(ship)),
         };
 
         Ok(edit)
     }
 }
 
+super::azur_button_reply!(View);
+
 impl ViewAffinity {
-    /// Converts the affinity to a stat multiplier.
-    fn to_mult(self) -> f64 {
+    /// Converts the view state to the affinity used for stat computation.
+    pub fn to_affinity(self) -> Affinity {
         match self {
-            Self::Neutral => 1.0,
-            Self::Love => 1.06,
-            Self::Oath => 1.12,
+            Self::Neutral => Affinity::Neutral,
+            Self::Love => Affinity::Love,
+            Self::Oath => Affinity::Oath,
         }
     }
 }
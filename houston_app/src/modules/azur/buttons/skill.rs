@@ -3,8 +3,9 @@ use azur_lane::ship::*;
 use azur_lane::skill::*;
 use utils::text::truncate;
 
-use super::AzurParseError;
+use super::{AzurButtonMessage, AzurParseError};
 use crate::buttons::prelude::*;
+use crate::modules::azur::data::HAzurLane;
 
 /// View skill details of a ship or augment.
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
@@ -61,14 +62,16 @@ impl View {
         mut self,
         iterator: impl Iterator<Item = &'a Skill>,
         mut embed: CreateEmbed<'a>,
-    ) -> (CreateEmbed<'a>, CreateActionRow<'a>) {
+    ) -> (CreateEmbed<'a>, CreateActionRow<'a>, Option<&'a Skill>) {
         let mut components = Vec::new();
+        let mut selected = None;
 
         for (t_index, skill) in iterator.enumerate().take(5) {
             #[allow(clippy::cast_possible_truncation)]
             let t_index = Some(t_index as u8);
 
             if t_index == self.skill_index {
+                selected = Some(skill);
                 embed = embed
                     .color(skill.category.color_rgb())
                     .fields(self.create_ex_skill_fields(skill));
@@ -86,13 +89,13 @@ impl View {
             }
         }
 
-        (embed, CreateActionRow::buttons(components))
+        (embed, CreateActionRow::buttons(components), selected)
     }
 
     /// Modifies the create-reply with preresolved ship data.
     fn edit_with_ship<'a>(
         mut self,
-        data: &'a HBotData,
+        azur: &'a HAzurLane,
         ship: &'a ShipData,
         base_ship: Option<&'a ShipData>,
     ) -> EditReply<'a> {
@@ -108,12 +111,7 @@ impl View {
             .label("Back");
         let mut components = vec![components];
 
-        for (a_index, augment) in data
-            .azur_lane()
-            .augments_by_ship_id(ship.group_id)
-            .enumerate()
-            .take(4)
-        {
+        for (a_index, augment) in azur.augments_by_ship_id(ship.group_id).enumerate().take(4) {
             if a_index == 0 {
                 components.push(self.button_with_augment(None).label("Default"));
             }
@@ -148,17 +146,15 @@ impl View {
             }
         }
 
-        let (embed, row) = self.edit_with_skills(skills.into_iter(), embed);
-        EditReply::clear()
-            .embed(embed)
-            .components(rows_without_empty([
-                CreateActionRow::buttons(components),
-                row,
-            ]))
+        let (embed, row, selected) = self.edit_with_skills(skills.into_iter(), embed);
+        clear_with_skill_icon(azur, embed, selected).components(rows_without_empty([
+            CreateActionRow::buttons(components),
+            row,
+        ]))
     }
 
     /// Modifies the create-reply with preresolved augment data.
-    fn edit_with_augment(self, augment: &Augment) -> EditReply<'_> {
+    fn edit_with_augment<'a>(self, azur: &HAzurLane, augment: &'a Augment) -> EditReply<'a> {
         let embed = CreateEmbed::new()
             .color(augment.rarity.color_rgb())
             .author(CreateEmbedAuthor::new(&augment.name));
@@ -172,10 +168,8 @@ impl View {
             .emoji('⏪')
             .label("Back")]);
 
-        let (embed, row) = self.edit_with_skills(skills, embed);
-        EditReply::clear()
-            .embed(embed)
-            .components(rows_without_empty([nav_row, row]))
+        let (embed, row, selected) = self.edit_with_skills(skills, embed);
+        clear_with_skill_icon(azur, embed, selected).components(rows_without_empty([nav_row, row]))
     }
 
     /// Creates a button that redirects to a skill index.
@@ -249,6 +243,26 @@ impl View {
     }
 }
 
+/// Creates a cleared edit with the embed, using the selected skill's icon as
+/// its thumbnail, if available.
+fn clear_with_skill_icon<'a>(
+    azur: &HAzurLane,
+    embed: CreateEmbed<'a>,
+    selected: Option<&Skill>,
+) -> EditReply<'a> {
+    let reply = EditReply::clear();
+    if let Some(icon) = selected.and_then(|s| s.icon.as_deref()) {
+        if let Some(image_data) = azur.get_skill_icon(icon) {
+            let filename = format!("skill_{icon}.webp");
+            return reply
+                .new_attachment(CreateAttachment::bytes(image_data, filename.clone()))
+                .embed(embed.thumbnail(format!("attachment://{filename}")));
+        }
+    }
+
+    reply.embed(embed)
+}
+
 fn rows_without_empty<'a, I>(rows: I) -> Vec<CreateActionRow<'a>>
 where
     I: IntoIterator<Item = CreateActionRow<'a>>,
@@ -258,33 +272,31 @@ where
         .collect()
 }
 
-impl ButtonMessage for View {
-    fn edit_reply(self, ctx: ButtonContext<'_>) -> Result<EditReply<'_>> {
+impl AzurButtonMessage for View {
+    fn edit_reply<'a>(self, _ctx: ButtonContext<'a>, azur: &'a HAzurLane) -> Result<EditReply<'a>> {
         match &self.source {
             ViewSource::Ship(source) => {
-                let base_ship = ctx
-                    .data
-                    .azur_lane()
+                let base_ship = azur
                     .ship_by_id(source.ship_id)
                     .ok_or(AzurParseError::Ship)?;
                 let ship = source
                     .retrofit
                     .and_then(|i| base_ship.retrofits.get(usize::from(i)))
                     .unwrap_or(base_ship);
-                Ok(self.edit_with_ship(ctx.data, ship, Some(base_ship)))
+                Ok(self.edit_with_ship(azur, ship, Some(base_ship)))
             },
             ViewSource::Augment(augment_id) => {
-                let augment = ctx
-                    .data
-                    .azur_lane()
+                let augment = azur
                     .augment_by_id(*augment_id)
                     .ok_or(AzurParseError::Augment)?;
-                Ok(self.edit_with_augment(augment))
+                Ok(self.edit_with_augment(azur, augment))
             },
         }
     }
 }
 
+super::azur_button_reply!(View);
+
 /// Constructs skill barrage display data.
 fn get_skills_extra_summary(skill: &Skill) -> String {
     use utils::text::write_str::*;
@@ -0,0 +1,132 @@
+use azur_lane::ship::*;
+
+use super::{AzurButtonMessage, AzurParseError};
+use crate::buttons::prelude::*;
+use crate::helper::discord::create_string_select_menu_row;
+use crate::modules::azur::data::HAzurLane;
+use crate::modules::core::buttons::ToPage;
+
+/// Pages through the full paintings of a ship's skins.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct View {
+    pub ship_id: u32,
+    /// The index of the shown skin.
+    pub page: u16,
+    pub back: CustomData,
+}
+
+impl View {
+    /// Creates a new instance including a button to go back with some custom
+    /// ID.
+    pub fn with_back(ship_id: u32, back: CustomData) -> Self {
+        Self {
+            ship_id,
+            page: 0,
+            back,
+        }
+    }
+
+    /// Modifies the create-reply with preresolved ship data.
+    pub fn create_with_ship<'a>(
+        mut self,
+        azur: &'a HAzurLane,
+        ship: &'a ShipData,
+    ) -> Result<CreateReply<'a>> {
+        #[allow(clippy::cast_possible_truncation)]
+        let page_count = ship.skins.len().max(1) as u16;
+        self.page = self.page.min(page_count - 1);
+
+        let skin = ship
+            .skins
+            .get(usize::from(self.page))
+            .ok_or(AzurParseError::Ship)?;
+
+        let mut embed = CreateEmbed::new()
+            .color(ship.rarity.color_rgb())
+            .author(super::get_ship_wiki_url(ship))
+            .title(&skin.name);
+
+        let mut create = CreateReply::new();
+
+        if let Some(image_data) = azur.get_painting_image(&skin.image_key) {
+            let filename = format!("{}_painting.webp", skin.image_key);
+            embed = embed.image(format!("attachment://{filename}"));
+            create = create.attachment(CreateAttachment::bytes(image_data, filename));
+        } else {
+            embed = embed.description("-# No painting is available for this skin.");
+            if let Some(image_data) = azur.get_chibi_image(&skin.image_key) {
+                let filename = format!("{}.webp", skin.image_key);
+                embed = embed.thumbnail(format!("attachment://{filename}"));
+                create = create.attachment(CreateAttachment::bytes(image_data, filename));
+            }
+        }
+
+        let mut rows = Vec::new();
+
+        let pagination = ToPage::build_row(&mut self, |s| &mut s.page).exact_page_count(page_count);
+        if let Some(pagination) = pagination.end() {
+            rows.push(pagination);
+        }
+
+        if ship.skins.len() > 1 {
+            let options: Vec<_> = ship
+                .skins
+                .iter()
+                .take(25)
+                .enumerate()
+                .map(|(index, skin)| self.select_with_page(skin, index))
+                .collect();
+
+            rows.push(create_string_select_menu_row(
+                self.to_custom_id(),
+                options,
+                &skin.name,
+            ));
+        }
+
+        rows.push(CreateActionRow::buttons(vec![CreateButton::new(
+            self.back.to_custom_id(),
+        )
+        .emoji('⏪')
+        .label("Back")]));
+
+        Ok(create.embed(embed).components(rows))
+    }
+
+    /// Creates a select option that redirects to a different skin.
+    fn select_with_page<'a>(
+        &mut self,
+        skin: &'a ShipSkin,
+        index: usize,
+    ) -> CreateSelectMenuOption<'a> {
+        // Just as-cast the index to u16 since we'd have problems long before an
+        // overflow.
+        #[allow(clippy::cast_possible_truncation)]
+        self.new_select_option(&skin.name, |s| &mut s.page, index as u16)
+    }
+
+    fn resolve<'a>(&self, azur: &'a HAzurLane) -> Result<&'a ShipData> {
+        let ship = azur.ship_by_id(self.ship_id).ok_or(AzurParseError::Ship)?;
+
+        Ok(ship)
+    }
+}
+
+impl AzurButtonMessage for View {
+    fn edit_reply<'a>(self, _ctx: ButtonContext<'a>, azur: &'a HAzurLane) -> Result<EditReply<'a>> {
+        let ship = self.resolve(azur)?;
+        self.create_with_ship(azur, ship).map(EditReply::from)
+    }
+
+    fn edit_modal_reply<'a>(
+        mut self,
+        ctx: ModalContext<'a>,
+        azur: &'a HAzurLane,
+    ) -> Result<EditReply<'a>> {
+        ToPage::set_page_from(&mut self.page, ctx.interaction);
+        let ship = self.resolve(azur)?;
+        self.create_with_ship(azur, ship).map(EditReply::from)
+    }
+}
+
+super::azur_button_reply!(View);
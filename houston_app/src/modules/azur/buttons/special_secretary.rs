@@ -1,8 +1,9 @@
 use azur_lane::secretary::*;
 use utils::text::write_str::*;
 
-use super::AzurParseError;
+use super::{AzurButtonMessage, AzurParseError};
 use crate::buttons::prelude::*;
+use crate::modules::azur::data::HAzurLane;
 
 /// Views ship lines.
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
@@ -93,10 +94,8 @@ impl View {
             .disabled(disabled)
     }
 
-    fn resolve<'a>(&self, ctx: &ButtonContext<'a>) -> Result<&'a SpecialSecretary> {
-        let secretary = ctx
-            .data
-            .azur_lane()
+    fn resolve<'a>(&self, azur: &'a HAzurLane) -> Result<&'a SpecialSecretary> {
+        let secretary = azur
             .special_secretary_by_id(self.secretary_id)
             .ok_or(AzurParseError::SpecialSecretary)?;
 
@@ -216,9 +215,11 @@ impl ViewPart {
     }
 }
 
-impl ButtonMessage for View {
-    fn edit_reply(self, ctx: ButtonContext<'_>) -> Result<EditReply<'_>> {
-        let ship = self.resolve(&ctx)?;
+impl AzurButtonMessage for View {
+    fn edit_reply<'a>(self, ctx: ButtonContext<'a>, azur: &'a HAzurLane) -> Result<EditReply<'a>> {
+        let ship = self.resolve(azur)?;
         Ok(self.create_with_sectary(ctx.data, ship).into())
     }
 }
+
+super::azur_button_reply!(View);
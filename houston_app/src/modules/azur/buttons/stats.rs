@@ -0,0 +1,288 @@
+use std::iter;
+
+use azur_lane::ship::*;
+use utils::text::write_str::*;
+
+use super::{AzurButtonMessage, AzurParseError};
+use crate::buttons::prelude::*;
+use crate::helper::discord::create_string_select_menu_row;
+use crate::modules::azur::data::HAzurLane;
+
+/// The highest level that can be selected.
+pub const MAX_LEVEL: u8 = 125;
+
+/// The levels offered in the level selection.
+const LEVEL_PRESETS: [u8; 16] = [
+    1, 10, 20, 30, 40, 50, 60, 70, 80, 90, 100, 105, 110, 115, 120, 125,
+];
+
+/// View a ship's stats at an arbitrary level, affinity, and limit break state.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct View {
+    pub ship_id: u32,
+    pub level: u8,
+    pub affinity: Affinity,
+    pub limit_break: u8,
+    pub retrofit: Option<u8>,
+    pub back: Option<CustomData>,
+}
+
+impl View {
+    /// Creates a new instance.
+    pub fn new(ship_id: u32) -> Self {
+        Self {
+            ship_id,
+            level: 120,
+            affinity: Affinity::Love,
+            limit_break: StatParams::MAX_LIMIT_BREAK,
+            retrofit: None,
+            back: None,
+        }
+    }
+
+    /// Sets the back button target.
+    pub fn back(mut self, back: CustomData) -> Self {
+        self.back = Some(back);
+        self
+    }
+
+    /// Modifies the create-reply with preresolved ship data.
+    pub fn create_with_ship<'a>(
+        mut self,
+        ship: &'a ShipData,
+        base_ship: Option<&'a ShipData>,
+    ) -> CreateReply<'a> {
+        let base_ship = base_ship.unwrap_or(ship);
+        let params = self.params();
+
+        let mut description = format!(
+            "Lv.{} \u{2E31} {} \u{2E31} LB{}",
+            self.level,
+            self.affinity.name(),
+            self.limit_break
+        );
+
+        if params.effective_level() != u32::from(self.level) {
+            write_str!(
+                description,
+                "\n-# Capped at Lv.{} with {} limit breaks.",
+                params.level_cap(),
+                self.limit_break
+            );
+        }
+
+        let embed = CreateEmbed::new()
+            .author(super::get_ship_wiki_url(base_ship))
+            .description(description)
+            .color(ship.rarity.color_rgb())
+            .field(
+                "Stats",
+                get_stats_text(ship, &ship.stats.compute(params)),
+                false,
+            );
+
+        let mut rows = Vec::new();
+        self.add_level_rows(&mut rows);
+        self.add_affinity_row(&mut rows);
+        self.add_limit_break_row(&mut rows);
+        self.add_retro_state_row(base_ship, &mut rows);
+
+        CreateReply::new().embed(embed).components(rows)
+    }
+
+    /// The parameters to compute the stats with.
+    fn params(&self) -> StatParams {
+        StatParams::new(u32::from(self.level), self.affinity).with_limit_break(self.limit_break)
+    }
+
+    fn add_level_rows(&mut self, rows: &mut Vec<CreateActionRow<'_>>) {
+        let level = self.level;
+        let mut row = vec![
+            self.button_with_level(level.saturating_sub(10).max(1), 1)
+                .label("-10"),
+            self.button_with_level(level.saturating_sub(1).max(1), 2)
+                .label("-1"),
+            self.button_with_level(level.saturating_add(1).min(MAX_LEVEL), 3)
+                .label("+1"),
+            self.button_with_level(level.saturating_add(10).min(MAX_LEVEL), 4)
+                .label("+10"),
+        ];
+
+        if let Some(back) = &self.back {
+            row.insert(
+                0,
+                CreateButton::new(back.to_custom_id())
+                    .emoji('⏪')
+                    .label("Back"),
+            );
+        }
+
+        rows.push(CreateActionRow::buttons(row));
+
+        let options: Vec<_> = LEVEL_PRESETS
+            .into_iter()
+            .map(|level| self.new_select_option(format!("Lv.{level}"), |s| &mut s.level, level))
+            .collect();
+
+        rows.push(create_string_select_menu_row(
+            self.to_custom_id(),
+            options,
+            format!("Lv.{}", self.level),
+        ));
+    }
+
+    fn add_affinity_row(&mut self, rows: &mut Vec<CreateActionRow<'_>>) {
+        let buttons: Vec<_> = Affinity::ALL
+            .iter()
+            .map(|&affinity| self.button_with_affinity(affinity).label(affinity.name()))
+            .collect();
+
+        rows.push(CreateActionRow::buttons(buttons));
+    }
+
+    fn add_limit_break_row(&mut self, rows: &mut Vec<CreateActionRow<'_>>) {
+        let buttons: Vec<_> = (0..=StatParams::MAX_LIMIT_BREAK)
+            .map(|limit_break| {
+                self.button_with_limit_break(limit_break)
+                    .label(format!("LB{limit_break}"))
+            })
+            .collect();
+
+        rows.push(CreateActionRow::buttons(buttons));
+    }
+
+    fn add_retro_state_row(&mut self, base_ship: &ShipData, rows: &mut Vec<CreateActionRow<'_>>) {
+        if base_ship.retrofits.is_empty() {
+            return;
+        }
+
+        let base_button = self.button_with_retrofit(None).label("Base");
+        let multi = base_ship.retrofits.len() > 1;
+        let retro_buttons = base_ship
+            .retrofits
+            .iter()
+            .enumerate()
+            .filter_map(|(index, retro)| {
+                let index = u8::try_from(index).ok()?;
+                let button = self.button_with_retrofit(Some(index));
+
+                // same labels as the ship view
+                let result = if multi {
+                    button.label(format!("Retrofit ({})", retro.hull_type.team_type().name()))
+                } else {
+                    button.label("Retrofit")
+                };
+
+                Some(result)
+            });
+
+        rows.push(CreateActionRow::buttons(
+            iter::once(base_button)
+                .chain(retro_buttons)
+                .collect::<Vec<_>>(),
+        ));
+    }
+
+    /// Gets a button that redirects to a different level.
+    fn button_with_level<'a>(&mut self, level: u8, sentinel: u16) -> CreateButton<'a> {
+        self.new_button(|s| &mut s.level, level, |_| sentinel)
+    }
+
+    /// Gets a button that redirects to a different affinity.
+    fn button_with_affinity<'a>(&mut self, affinity: Affinity) -> CreateButton<'a> {
+        self.new_button(|s| &mut s.affinity, affinity, |u| u as u16)
+    }
+
+    /// Gets a button that redirects to a different limit break state.
+    fn button_with_limit_break<'a>(&mut self, limit_break: u8) -> CreateButton<'a> {
+        self.new_button(|s| &mut s.limit_break, limit_break, u8::into)
+    }
+
+    /// Creates a button that redirects to a retrofit state.
+    fn button_with_retrofit<'a>(&mut self, retrofit: Option<u8>) -> CreateButton<'a> {
+        self.new_button(
+            |s| &mut s.retrofit,
+            retrofit,
+            |u| u.map_or(u16::MAX, u16::from),
+        )
+    }
+}
+
+impl AzurButtonMessage for View {
+    fn edit_reply<'a>(self, _ctx: ButtonContext<'a>, azur: &'a HAzurLane) -> Result<EditReply<'a>> {
+        let ship = azur.ship_by_id(self.ship_id).ok_or(AzurParseError::Ship)?;
+
+        let create = match self
+            .retrofit
+            .and_then(|index| ship.retrofits.get(usize::from(index)))
+        {
+            None => self.create_with_ship(ship, None),
+            Some(retrofit) => self.create_with_ship(retrofit, Some(ship)),
+        };
+
+        Ok(create.into())
+    }
+}
+
+super::azur_button_reply!(View);
+
+/// Creates the text listing a ship's computed stats.
+pub fn get_stats_text(ship: &ShipData, computed: &ComputedStats) -> String {
+    let stats = &ship.stats;
+
+    #[allow(clippy::cast_sign_loss)]
+    #[allow(clippy::cast_possible_truncation)]
+    fn f(n: f64) -> u32 {
+        n.floor() as u32
+    }
+    macro_rules! s {
+        ($val:ident) => {{
+            f(computed.$val)
+        }};
+    }
+
+    if ship.hull_type.team_type() != TeamType::Submarine {
+        format!(
+            "**`HP:`**`{: >5}` \u{2E31} **`{: <7}`**` ` \u{2E31} **`RLD:`**`{: >4}`\n\
+             **`FP:`**`{: >5}` \u{2E31} **`TRP:`**`{: >4}` \u{2E31} **`EVA:`**`{: >4}`\n\
+             **`AA:`**`{: >5}` \u{2E31} **`AVI:`**`{: >4}` \u{2E31} **`ACC:`**`{: >4}`\n\
+             **`ASW:`**`{: >4}` \u{2E31} **`SPD:`**`{: >4}`\n\
+             **`LCK:`**`{: >4}` \u{2E31} **`Cost:`**`{: >3}`",
+            s!(hp),
+            stats.armor.name(),
+            s!(rld),
+            s!(fp),
+            s!(trp),
+            s!(eva),
+            s!(aa),
+            s!(avi),
+            s!(acc),
+            s!(asw),
+            s!(spd),
+            s!(lck),
+            stats.cost
+        )
+    } else {
+        format!(
+            "**`HP:`**`{: >5}` \u{2E31} **`{: <7}`**` ` \u{2E31} **`RLD:`**`{: >4}`\n\
+             **`FP:`**`{: >5}` \u{2E31} **`TRP:`**`{: >4}` \u{2E31} **`EVA:`**`{: >4}`\n\
+             **`AA:`**`{: >5}` \u{2E31} **`AVI:`**`{: >4}` \u{2E31} **`ACC:`**`{: >4}`\n\
+             **`OXY:`**`{: >4}` \u{2E31} **`AMO:`**`{: >4}` \u{2E31} **`SPD:`**`{: >4}`\n\
+             **`LCK:`**`{: >4}` \u{2E31} **`Cost:`**`{: >3}`",
+            s!(hp),
+            stats.armor.name(),
+            s!(rld),
+            s!(fp),
+            s!(trp),
+            s!(eva),
+            s!(aa),
+            s!(avi),
+            s!(acc),
+            stats.oxy,
+            stats.amo,
+            s!(spd),
+            s!(lck),
+            stats.cost
+        )
+    }
+}
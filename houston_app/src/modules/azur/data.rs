@@ -1,11 +1,14 @@
 use std::collections::{HashMap, HashSet};
 use std::path::{Component, Path, PathBuf};
-use std::{fs, io};
+use std::{fs, iter};
 
 use azur_lane::equip::*;
 use azur_lane::juustagram::*;
 use azur_lane::secretary::*;
 use azur_lane::ship::*;
+use azur_lane::skill::*;
+use azur_lane::stage::*;
+use azur_lane::{LocalizedNames, RewardKind};
 use bytes::Bytes;
 use dashmap::DashMap;
 use smallvec::{smallvec, SmallVec};
@@ -13,6 +16,30 @@ use utils::fuzzy::Search;
 
 type IndexVec = SmallVec<[usize; 2]>;
 
+/// Where a skill found by [`HAzurLane::skills_by_text`] comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SkillSource {
+    /// A ship's skill.
+    ///
+    /// If only a retrofit has the skill, `retrofit` holds its index.
+    Ship { group_id: u32, retrofit: Option<u8> },
+    /// An augment module's effect or upgraded skill.
+    Augment(u32),
+}
+
+/// The minimum match score used when searching by fuzzy names.
+///
+/// This is lower than the default to still suggest the intended entry for
+/// misspelled names.
+const FUZZY_MIN_MATCH_SCORE: f64 = 0.25;
+
+/// Identifies a skill in the skill search.
+#[derive(Debug, Clone, Copy)]
+struct SkillKey {
+    source: SkillSource,
+    buff_id: u32,
+}
+
 /// Extended Azur Lane game data for quicker access.
 #[derive(Debug, Default)]
 pub struct HAzurLane {
@@ -36,22 +63,46 @@ pub struct HAzurLane {
     special_secretary_id_to_index: HashMap<u32, usize>,
     special_secretary_simsearch: Search<()>,
 
+    skill_simsearch: Search<SkillKey>,
+
+    stages: Vec<Stage>,
+    ship_id_to_stage_indices: HashMap<u32, IndexVec>,
+    equip_id_to_stage_indices: HashMap<u32, IndexVec>,
+
     // use Bytes to avoid copying the data redundantly
     chibi_sprite_cache: DashMap<String, Option<Bytes>>,
+    face_sprite_cache: DashMap<(String, String), Option<Bytes>>,
+    equip_icon_cache: DashMap<String, Option<Bytes>>,
+    skill_icon_cache: DashMap<String, Option<Bytes>>,
 }
 
 impl HAzurLane {
     /// Constructs extended data from definitions.
+    ///
+    /// If the data cannot be loaded, the error is logged and the returned
+    /// data is empty.
     #[must_use]
     pub fn load_from(data_path: PathBuf) -> Self {
+        match Self::try_load_from(data_path) {
+            Ok(data) => data,
+            Err(err) => {
+                log::error!("No Azur Lane data: {err:?}");
+                Self::default()
+            },
+        }
+    }
+
+    /// Constructs extended data from definitions.
+    ///
+    /// Unlike [`Self::load_from`], this returns an error if the data cannot be
+    /// loaded.
+    pub fn try_load_from(data_path: PathBuf) -> anyhow::Result<Self> {
         // loads the actual definition file from disk
         // the error is just a short description of the error
         fn load_definitions(data_path: &Path) -> anyhow::Result<azur_lane::DefinitionData> {
             use anyhow::Context as _;
-            let f = fs::File::open(data_path.join("main.json"))
-                .context("Failed to read Azur Lane data.")?;
-            let f = io::BufReader::new(f);
-            let data = serde_json::from_reader(f).context("Failed to parse Azur Lane data.")?;
+            let data = azur_lane::DefinitionData::load_from_dir(data_path)
+                .context("Failed to load Azur Lane data.")?;
             Ok(data)
         }
 
@@ -75,16 +126,36 @@ impl HAzurLane {
                         skin.name,
                     );
                 }
+
+                let voice_keys = iter::once(&skin.words)
+                    .chain(skin.words_extra.as_deref())
+                    .filter_map(|w| w.voice_key.as_deref());
+
+                for voice_key in voice_keys {
+                    if is_path_sus(Path::new(voice_key)) {
+                        log::warn!(
+                            "voice_key '{}' for ship skin {} ({}) may be part of path traversal attack",
+                            voice_key,
+                            skin.skin_id,
+                            skin.name,
+                        );
+                    }
+                }
             }
         }
 
-        let data = match load_definitions(&data_path) {
-            Ok(data) => data,
-            Err(err) => {
-                log::error!("No Azur Lane data: {err:?}");
-                return Self::default();
-            },
-        };
+        // joins the display name with all differing localized names,
+        // so searching by any of them finds the entry
+        fn search_names(name: &str, names: &LocalizedNames) -> String {
+            let mut result = name.to_owned();
+            for (_, local) in names.iter().filter(|n| n.1 != name) {
+                result.push(' ');
+                result.push_str(local);
+            }
+            result
+        }
+
+        let data = load_definitions(&data_path)?;
 
         let mut this = Self {
             data_path,
@@ -100,6 +171,7 @@ impl HAzurLane {
             augments: data.augments,
             juustagram_chats: data.juustagram_chats,
             special_secretaries: data.special_secretaries,
+            stages: data.stages,
             ..Self::default()
         };
 
@@ -124,7 +196,8 @@ impl HAzurLane {
             verify_ship(data);
 
             this.ship_id_to_index.insert(data.group_id, index);
-            this.ship_simsearch.insert(&data.name, ());
+            this.ship_simsearch
+                .insert(&search_names(&data.name, &data.names), ());
 
             // collect known "equip & hull" pairs
             insert_equip_exist(&mut actual_equip_exist, data);
@@ -135,7 +208,7 @@ impl HAzurLane {
             this.equip_simsearch.insert(
                 &format!(
                     "{} {} {} {} {}",
-                    data.name,
+                    search_names(&data.name, &data.names),
                     data.faction.name(),
                     data.faction.prefix().unwrap_or("EX"),
                     data.kind.name(),
@@ -151,7 +224,8 @@ impl HAzurLane {
 
         for (index, data) in this.augments.iter().enumerate() {
             this.augment_id_to_index.insert(data.augment_id, index);
-            this.augment_simsearch.insert(&data.name, ());
+            this.augment_simsearch
+                .insert(&search_names(&data.name, &data.names), ());
 
             if let Some(ship_id) = data.usability.unique_ship_id() {
                 this.ship_id_to_augment_indices
@@ -175,11 +249,79 @@ impl HAzurLane {
             this.special_secretary_simsearch.insert(&data.name, ());
         }
 
+        // skill names and descriptions are searched as one text,
+        // so require most of the query to match to limit the noise
+        this.skill_simsearch = Search::new().with_min_match_score(0.8);
+        for ship in &this.ships {
+            let mut insert = |skill: &Skill, retrofit: Option<u8>| {
+                let key = SkillKey {
+                    source: SkillSource::Ship {
+                        group_id: ship.group_id,
+                        retrofit,
+                    },
+                    buff_id: skill.buff_id,
+                };
+
+                let text = format!("{} {}", skill.name, skill.description);
+                this.skill_simsearch.insert(&text, key);
+            };
+
+            for skill in &ship.skills {
+                insert(skill, None);
+            }
+
+            // only add skills that the base ship doesn't have
+            for (index, retrofit) in ship.retrofits.iter().enumerate() {
+                let Ok(index) = u8::try_from(index) else {
+                    continue;
+                };
+
+                for skill in &retrofit.skills {
+                    if !ship.skills.iter().any(|s| s.buff_id == skill.buff_id) {
+                        insert(skill, Some(index));
+                    }
+                }
+            }
+        }
+
+        for augment in &this.augments {
+            let skills = augment
+                .effect
+                .iter()
+                .chain(augment.skill_upgrade.as_ref().map(|u| &u.skill));
+
+            for skill in skills {
+                let key = SkillKey {
+                    source: SkillSource::Augment(augment.augment_id),
+                    buff_id: skill.buff_id,
+                };
+
+                let text = format!("{} {}", skill.name, skill.description);
+                this.skill_simsearch.insert(&text, key);
+            }
+        }
+
+        for (index, data) in this.stages.iter().enumerate() {
+            for drop in &data.drops {
+                let indices = match drop.kind {
+                    RewardKind::Ship => &mut this.ship_id_to_stage_indices,
+                    RewardKind::Equip => &mut this.equip_id_to_stage_indices,
+                    _ => continue,
+                };
+
+                indices
+                    .entry(drop.id)
+                    .and_modify(|v| v.push(index))
+                    .or_insert_with(|| smallvec![index]);
+            }
+        }
+
         this.ship_simsearch.shrink_to_fit();
         this.equip_simsearch.shrink_to_fit();
         this.augment_simsearch.shrink_to_fit();
+        this.skill_simsearch.shrink_to_fit();
         this.special_secretaries.shrink_to_fit();
-        this
+        Ok(this)
     }
 
     /// Gets all known ships.
@@ -221,6 +363,16 @@ impl HAzurLane {
             .filter_map(|i| self.ships.get(i.index))
     }
 
+    /// Gets ships by a possibly misspelled name, best matches first.
+    ///
+    /// This is more lenient than [`Self::ships_by_prefix`] and may return
+    /// loosely related entries.
+    pub fn ships_by_fuzzy_name(&self, name: &str) -> impl Iterator<Item = &ShipData> + use<'_> {
+        self.ship_simsearch
+            .search_with_min_score(name, FUZZY_MIN_MATCH_SCORE)
+            .filter_map(|i| self.ships.get(i.index))
+    }
+
     /// Gets an equip by its ID.
     #[must_use]
     pub fn equip_by_id(&self, id: u32) -> Option<&Equip> {
@@ -235,6 +387,16 @@ impl HAzurLane {
             .filter_map(|i| self.equips.get(i.index))
     }
 
+    /// Gets equips by a possibly misspelled name, best matches first.
+    ///
+    /// This is more lenient than [`Self::equips_by_prefix`] and may return
+    /// loosely related entries.
+    pub fn equips_by_fuzzy_name(&self, name: &str) -> impl Iterator<Item = &Equip> + use<'_> {
+        self.equip_simsearch
+            .search_with_min_score(name, FUZZY_MIN_MATCH_SCORE)
+            .filter_map(|i| self.equips.get(i.index))
+    }
+
     /// Gets an augment by its ID.
     #[must_use]
     pub fn augment_by_id(&self, id: u32) -> Option<&Augment> {
@@ -249,6 +411,16 @@ impl HAzurLane {
             .filter_map(|i| self.augments.get(i.index))
     }
 
+    /// Gets augments by a possibly misspelled name, best matches first.
+    ///
+    /// This is more lenient than [`Self::augments_by_prefix`] and may return
+    /// loosely related entries.
+    pub fn augments_by_fuzzy_name(&self, name: &str) -> impl Iterator<Item = &Augment> + use<'_> {
+        self.augment_simsearch
+            .search_with_min_score(name, FUZZY_MIN_MATCH_SCORE)
+            .filter_map(|i| self.augments.get(i.index))
+    }
+
     /// Gets unique augments by their associated ship ID.
     pub fn augments_by_ship_id(&self, ship_id: u32) -> impl Iterator<Item = &Augment> {
         self.ship_id_to_augment_indices
@@ -258,7 +430,42 @@ impl HAzurLane {
             .filter_map(|i| self.augments.get(*i))
     }
 
-    /// Gets a Juustagram chat by its ID.
+    /// Searches for skills of ships and augment modules by their name and
+    /// description.
+    pub fn skills_by_text(
+        &self,
+        text: &str,
+    ) -> impl Iterator<Item = (SkillSource, &Skill)> + use<'_> {
+        self.skill_simsearch
+            .search(text)
+            .filter_map(|m| Some((m.data.source, self.skill_by_key(m.data)?)))
+    }
+
+    fn skill_by_key(&self, key: &SkillKey) -> Option<&Skill> {
+        let mut skills: Box<dyn Iterator<Item = &Skill>> = match key.source {
+            SkillSource::Ship { group_id, retrofit } => {
+                let ship = self.ship_by_id(group_id)?;
+                let ship = match retrofit {
+                    Some(index) => ship.retrofits.get(usize::from(index))?,
+                    None => ship,
+                };
+
+                Box::new(ship.skills.iter())
+            },
+            SkillSource::Augment(augment_id) => {
+                let augment = self.augment_by_id(augment_id)?;
+                Box::new(
+                    augment
+                        .effect
+                        .iter()
+                        .chain(augment.skill_upgrade.as_ref().map(|u| &u.skill)),
+                )
+            },
+        };
+
+        skills.find(|s| s.buff_id == key.buff_id)
+    }
+
     pub fn juustagram_chat_by_id(&self, chat_id: u32) -> Option<&Chat> {
         let index = *self.juustagram_chat_id_to_index.get(&chat_id)?;
         self.juustagram_chats.get(index)
@@ -273,6 +480,24 @@ impl HAzurLane {
             .filter_map(|i| self.juustagram_chats.get(*i))
     }
 
+    /// Gets all stages the ship with the given ID drops in.
+    pub fn stages_by_ship_drop(&self, ship_id: u32) -> impl Iterator<Item = &Stage> {
+        self.ship_id_to_stage_indices
+            .get(&ship_id)
+            .into_iter()
+            .flatten()
+            .filter_map(|i| self.stages.get(*i))
+    }
+
+    /// Gets all stages the equipment with the given ID drops in.
+    pub fn stages_by_equip_drop(&self, equip_id: u32) -> impl Iterator<Item = &Stage> {
+        self.equip_id_to_stage_indices
+            .get(&equip_id)
+            .into_iter()
+            .flatten()
+            .filter_map(|i| self.stages.get(*i))
+    }
+
     /// Gets a special secretary by its ID.
     pub fn special_secretary_by_id(&self, id: u32) -> Option<&SpecialSecretary> {
         let index = *self.special_secretary_id_to_index.get(&id)?;
@@ -289,6 +514,20 @@ impl HAzurLane {
             .filter_map(|i| self.special_secretaries.get(i.index))
     }
 
+    /// Gets special secretaries by a possibly misspelled name, best matches
+    /// first.
+    ///
+    /// This is more lenient than [`Self::special_secretaries_by_prefix`] and
+    /// may return loosely related entries.
+    pub fn special_secretaries_by_fuzzy_name(
+        &self,
+        name: &str,
+    ) -> impl Iterator<Item = &SpecialSecretary> + use<'_> {
+        self.special_secretary_simsearch
+            .search_with_min_score(name, FUZZY_MIN_MATCH_SCORE)
+            .filter_map(|i| self.special_secretaries.get(i.index))
+    }
+
     /// Gets a chibi's image data.
     #[must_use]
     pub fn get_chibi_image(&self, image_key: &str) -> Option<Bytes> {
@@ -297,49 +536,129 @@ impl HAzurLane {
         // but not found.
         match self.chibi_sprite_cache.get(image_key) {
             Some(entry) => entry.clone(),
-            None => self.load_and_cache_chibi_image(image_key),
+            None => {
+                // IMPORTANT: the right-hand side of join may be absolute or relative and can
+                // therefore read files outside of `data_path`. Currently, this doesn't
+                // take user-input, but this should be considered for the future.
+                let path = utils::join_path!(&self.data_path, "chibi", image_key; "webp");
+                load_and_cache_image(&self.chibi_sprite_cache, image_key.to_owned(), &path)
+            },
         }
     }
 
-    #[cold]
-    fn load_and_cache_chibi_image(&self, image_key: &str) -> Option<Bytes> {
-        // IMPORTANT: the right-hand side of join may be absolute or relative and can
-        // therefore read files outside of `data_path`. Currently, this doesn't
-        // take user-input, but this should be considered for the future.
-        let path = utils::join_path!(&self.data_path, "chibi", image_key; "webp");
-        match fs::read(path) {
-            Ok(data) => {
-                // File read successfully, cache the data.
-                use dashmap::mapref::entry::Entry;
-
-                match self.chibi_sprite_cache.entry(image_key.to_owned()) {
-                    // data race: loaded concurrently, too slow here. drop the newly read data.
-                    Entry::Occupied(entry) => entry.get().clone(),
-                    // still empty: wrap the current data and return it
-                    Entry::Vacant(entry) => (*entry.insert(Some(Bytes::from(data)))).clone(),
-                }
+    /// Gets the image data of a skin's alternative expression.
+    #[must_use]
+    pub fn get_face_image(&self, image_key: &str, face: &str) -> Option<Bytes> {
+        let key = (image_key.to_owned(), face.to_owned());
+        match self.face_sprite_cache.get(&key) {
+            Some(entry) => entry.clone(),
+            None => {
+                // same caveat as with chibis. faces come from the data file.
+                let path = utils::join_path!(&self.data_path, "face", image_key, face; "webp");
+                load_and_cache_image(&self.face_sprite_cache, key, &path)
             },
-            Err(err) => {
-                // Reading failed. Check the error kind.
-                use std::io::ErrorKind::*;
-
-                match err.kind() {
-                    // Most errors aren't interesting and may be transient issues.
-                    // However, these ones imply permanent problems. Store None to prevent repeated
-                    // attempts at loading the file.
-                    NotFound | PermissionDenied => {
-                        // insert, but do not replace a present entry
-                        self.chibi_sprite_cache
-                            .entry(image_key.to_owned())
-                            .or_default();
-                    },
-                    _ => {
-                        log::warn!("Failed to load chibi sprite '{image_key}': {err:?}");
-                    },
-                };
+        }
+    }
+
+    /// Gets the image data of a skin's full painting.
+    ///
+    /// Unlike other images, paintings are large and rarely requested, so they
+    /// are read from disk every time instead of being cached.
+    #[must_use]
+    pub fn get_painting_image(&self, image_key: &str) -> Option<Bytes> {
+        // same caveat as with chibis. image keys come from the data file.
+        let path = utils::join_path!(&self.data_path, "painting", image_key; "webp");
+        load_uncached(&path)
+    }
+
+    /// Determines whether any voice clips were extracted for a voice key.
+    #[must_use]
+    pub fn has_voice_clips(&self, voice_key: &str) -> bool {
+        utils::join_path!(&self.data_path, "voice", voice_key).is_dir()
+    }
+
+    /// Gets the audio data of a voice line.
+    ///
+    /// Like paintings, these aren't cached.
+    #[must_use]
+    pub fn get_voice_clip(&self, voice_key: &str, line_key: &str) -> Option<Bytes> {
+        // same caveat as with chibis. keys come from the data file.
+        let path = utils::join_path!(&self.data_path, "voice", voice_key, line_key; "fsb");
+        load_uncached(&path)
+    }
 
-                None
+    /// Gets the image data of an equipment icon.
+    #[must_use]
+    pub fn get_equip_icon(&self, icon: &str) -> Option<Bytes> {
+        match self.equip_icon_cache.get(icon) {
+            Some(entry) => entry.clone(),
+            None => {
+                // same caveat as with chibis. icons come from the data file.
+                let path = utils::join_path!(&self.data_path, "equip_icon", icon; "webp");
+                load_and_cache_image(&self.equip_icon_cache, icon.to_owned(), &path)
+            },
+        }
+    }
+
+    /// Gets the image data of a skill icon.
+    #[must_use]
+    pub fn get_skill_icon(&self, icon: &str) -> Option<Bytes> {
+        match self.skill_icon_cache.get(icon) {
+            Some(entry) => entry.clone(),
+            None => {
+                let path = utils::join_path!(&self.data_path, "skill_icon", icon; "webp");
+                load_and_cache_image(&self.skill_icon_cache, icon.to_owned(), &path)
             },
         }
     }
 }
+
+fn load_uncached(path: &Path) -> Option<Bytes> {
+    match fs::read(path) {
+        Ok(data) => Some(Bytes::from(data)),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => None,
+        Err(err) => {
+            log::warn!("Failed to load file {path:?}: {err:?}");
+            None
+        },
+    }
+}
+
+#[cold]
+fn load_and_cache_image<K>(cache: &DashMap<K, Option<Bytes>>, key: K, path: &Path) -> Option<Bytes>
+where
+    K: Eq + std::hash::Hash + std::fmt::Debug,
+{
+    match fs::read(path) {
+        Ok(data) => {
+            // File read successfully, cache the data.
+            use dashmap::mapref::entry::Entry;
+
+            match cache.entry(key) {
+                // data race: loaded concurrently, too slow here. drop the newly read data.
+                Entry::Occupied(entry) => entry.get().clone(),
+                // still empty: wrap the current data and return it
+                Entry::Vacant(entry) => (*entry.insert(Some(Bytes::from(data)))).clone(),
+            }
+        },
+        Err(err) => {
+            // Reading failed. Check the error kind.
+            use std::io::ErrorKind::*;
+
+            match err.kind() {
+                // Most errors aren't interesting and may be transient issues.
+                // However, these ones imply permanent problems. Store None to prevent repeated
+                // attempts at loading the file.
+                NotFound | PermissionDenied => {
+                    // insert, but do not replace a present entry
+                    cache.entry(key).or_default();
+                },
+                _ => {
+                    log::warn!("Failed to load sprite {key:?}: {err:?}");
+                },
+            };
+
+            None
+        },
+    }
+}
@@ -2,6 +2,7 @@ use super::prelude::*;
 
 pub mod buttons;
 pub mod data;
+pub mod model;
 mod slashies;
 
 pub struct Module;
@@ -14,4 +15,14 @@ impl super::Module for Module {
     fn commands(&self, _config: &HBotConfig) -> impl IntoIterator<Item = HCommand> {
         [slashies::azur()]
     }
+
+    fn db_init(db: &mongodb::Database) -> mongodb::BoxFuture<'_, Result> {
+        use crate::helper::bson::update_indices;
+        Box::pin(async move {
+            use model::*;
+            update_indices(Fleet::collection(db), Fleet::indices()).await?;
+            update_indices(UserLocale::collection(db), UserLocale::indices()).await?;
+            Ok(())
+        })
+    }
 }
@@ -0,0 +1,216 @@
+use azur_lane::Locale;
+
+use super::buttons::loadout::SLOT_COUNT;
+use crate::modules::model_prelude::*;
+
+/// The maximum amount of fleets a single user may save.
+pub const MAX_FLEETS_PER_USER: i32 = 25;
+
+/// How often saving a new fleet is tried when it conflicts with another save.
+const MAX_SAVE_ATTEMPTS: usize = 3;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Fleet {
+    pub _id: ObjectId,
+    #[serde(with = "id_as_i64")]
+    pub user: UserId,
+    pub name: String,
+    /// Which of the user's [`MAX_FLEETS_PER_USER`] slots this fleet takes.
+    pub slot: i32,
+    #[serde(default)]
+    pub ships: Vec<FleetShip>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FleetShip {
+    pub ship_id: u32,
+    #[serde(default)]
+    pub equips: [Option<u32>; SLOT_COUNT],
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserLocale {
+    pub _id: ObjectId,
+    #[serde(with = "id_as_i64")]
+    pub user: UserId,
+    pub locale: Locale,
+}
+
+fn name(name: &str) -> IndexOptions {
+    IndexOptions::builder().name(name.to_owned()).build()
+}
+
+fn unique(name: &str) -> IndexOptions {
+    IndexOptions::builder()
+        .name(name.to_owned())
+        .unique(true)
+        .build()
+}
+
+impl Fleet {
+    pub fn collection(db: &Database) -> Collection<Self> {
+        db.collection("azur.fleets")
+    }
+
+    pub fn indices() -> Vec<IndexModel> {
+        vec![
+            IndexModel::builder()
+                .options(unique("user-name"))
+                .keys(doc! {
+                    "user": 1,
+                    "name": 1,
+                })
+                .build(),
+            // keeps users from going over the fleet limit
+            IndexModel::builder()
+                .options(unique("user-slot"))
+                .keys(doc! {
+                    "user": 1,
+                    "slot": 1,
+                })
+                .build(),
+        ]
+    }
+}
+
+impl UserLocale {
+    pub fn collection(db: &Database) -> Collection<Self> {
+        db.collection("azur.user_locales")
+    }
+
+    pub fn indices() -> Vec<IndexModel> {
+        vec![IndexModel::builder()
+            .options(name("user"))
+            .keys(doc! {
+                "user": 1,
+            })
+            .build()]
+    }
+}
+
+pub trait FleetExt {
+    async fn save_fleet(&self, user_id: UserId, name: &str, ships: &[FleetShip]) -> Result;
+
+    async fn find_fleet(&self, user_id: UserId, name: &str) -> Result<Option<Fleet>>;
+
+    async fn delete_fleet(&self, user_id: UserId, name: &str) -> Result<bool>;
+}
+
+impl FleetExt for Collection<Fleet> {
+    async fn save_fleet(&self, user_id: UserId, name: &str, ships: &[FleetShip]) -> Result {
+        use crate::helper::bson::is_duplicate_key;
+
+        let filter = doc! {
+            "user": bson_id!(user_id),
+            "name": name,
+        };
+
+        let update = doc! {
+            "$set": {
+                "ships": bson::ser::to_bson(ships)?,
+            },
+        };
+
+        for _ in 0..MAX_SAVE_ATTEMPTS {
+            // replacing an existing fleet is always fine
+            let res = self.update_one(filter.clone(), update.clone()).await?;
+            if res.matched_count != 0 {
+                return Ok(());
+            }
+
+            // a new fleet needs a free slot, the unique index on the slots makes
+            // sure concurrent saves can't both take the last one
+            let used = self
+                .distinct("slot", doc! { "user": bson_id!(user_id) })
+                .await?;
+
+            let Some(slot) =
+                (0..MAX_FLEETS_PER_USER).find(|s| !used.iter().any(|u| u.as_i32() == Some(*s)))
+            else {
+                return Err(HArgError::new(format!(
+                    "You can only save up to {MAX_FLEETS_PER_USER} fleets. Delete one first."
+                ))
+                .into());
+            };
+
+            let fleet = Fleet {
+                _id: ObjectId::new(),
+                user: user_id,
+                name: name.to_owned(),
+                slot,
+                ships: ships.to_vec(),
+            };
+
+            match self.insert_one(fleet).await {
+                Ok(_) => return Ok(()),
+                // another save took the name or slot first, so look again
+                Err(why) if is_duplicate_key(&why) => {},
+                Err(why) => return Err(why.into()),
+            }
+        }
+
+        Err(
+            HArgError::new_const("The fleet was changed at the same time. Please try again.")
+                .into(),
+        )
+    }
+
+    async fn find_fleet(&self, user_id: UserId, name: &str) -> Result<Option<Fleet>> {
+        let filter = doc! {
+            "user": bson_id!(user_id),
+            "name": name,
+        };
+
+        let doc = self.find_one(filter).await?;
+        Ok(doc)
+    }
+
+    async fn delete_fleet(&self, user_id: UserId, name: &str) -> Result<bool> {
+        let filter = doc! {
+            "user": bson_id!(user_id),
+            "name": name,
+        };
+
+        let result = self.delete_one(filter).await?;
+        Ok(result.deleted_count != 0)
+    }
+}
+
+pub trait UserLocaleExt {
+    async fn set_user_locale(&self, user_id: UserId, locale: Option<Locale>) -> Result;
+
+    async fn find_user_locale(&self, user_id: UserId) -> Result<Option<Locale>>;
+}
+
+impl UserLocaleExt for Collection<UserLocale> {
+    async fn set_user_locale(&self, user_id: UserId, locale: Option<Locale>) -> Result {
+        let filter = doc! {
+            "user": bson_id!(user_id),
+        };
+
+        // no override means following the user's discord language again
+        let Some(locale) = locale else {
+            self.delete_one(filter).await?;
+            return Ok(());
+        };
+
+        let update = doc! {
+            "$setOnInsert": filter.clone(),
+            "$set": {
+                "locale": bson::ser::to_bson(&locale)?,
+            },
+        };
+
+        self.update_one(filter, update).upsert(true).await?;
+        Ok(())
+    }
+
+    async fn find_user_locale(&self, user_id: UserId) -> Result<Option<Locale>> {
+        let filter = doc! {
+            "user": bson_id!(user_id),
+        };
+
+        let doc = self.find_one(filter).await?;
+        Ok(doc.map(|d| d.locale))
+    }
+}
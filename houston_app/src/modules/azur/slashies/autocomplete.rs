@@ -1,18 +1,55 @@
+use azur_lane::equip::{Augment, Equip};
+use azur_lane::secretary::SpecialSecretary;
+use azur_lane::ship::ShipData;
+use azur_lane::Locale;
+use bson::doc;
+
+use crate::helper::bson::bson_id;
+use crate::modules::azur::buttons::localized_name;
+use crate::modules::azur::model::Fleet;
 use crate::slashies::prelude::*;
 
+/// Provides the name shown for an autocomplete choice.
+trait ChoiceName {
+    fn choice_name(&self, locale: Option<Locale>) -> &str;
+}
+
+macro_rules! impl_localized_choice_name {
+    ($($T:ty),*) => {
+        $(
+            impl ChoiceName for $T {
+                fn choice_name(&self, locale: Option<Locale>) -> &str {
+                    localized_name(&self.name, &self.names, locale)
+                }
+            }
+        )*
+    };
+}
+
+impl_localized_choice_name!(ShipData, Equip, Augment);
+
+impl ChoiceName for SpecialSecretary {
+    fn choice_name(&self, _locale: Option<Locale>) -> &str {
+        &self.name
+    }
+}
+
 macro_rules! make_autocomplete {
-    ($fn_name:ident, $by_prefix:ident, $id:ident) => {
+    ($fn_name:ident, $by_name:ident, $id:ident) => {
         pub async fn $fn_name<'a>(
             ctx: Context<'a>,
             partial: &'a str,
         ) -> CreateAutocompleteResponse<'a> {
-            let choices: Vec<_> = ctx
-                .data_ref()
-                .azur_lane()
-                .$by_prefix(partial)
+            let locale = super::resolve_locale(ctx).await;
+            let azur = ctx.data_ref().azur_lane();
+            let choices: Vec<_> = azur
+                .$by_name(partial)
                 .take(25)
                 .map(|e| {
-                    AutocompleteChoice::new(e.name.as_str(), Cow::Owned(format!("/id:{}", e.$id)))
+                    AutocompleteChoice::new(
+                        Cow::Owned(e.choice_name(locale).to_owned()),
+                        Cow::Owned(format!("/id:{}", e.$id)),
+                    )
                 })
                 .collect();
 
@@ -21,29 +58,69 @@ macro_rules! make_autocomplete {
     };
 }
 
-make_autocomplete!(ship_name, ships_by_prefix, group_id);
-make_autocomplete!(equip_name, equips_by_prefix, equip_id);
-make_autocomplete!(augment_name, augments_by_prefix, augment_id);
-make_autocomplete!(special_secretary_name, special_secretaries_by_prefix, id);
+make_autocomplete!(ship_name, ships_by_fuzzy_name, group_id);
+make_autocomplete!(equip_name, equips_by_fuzzy_name, equip_id);
+make_autocomplete!(augment_name, augments_by_fuzzy_name, augment_id);
+make_autocomplete!(
+    special_secretary_name,
+    special_secretaries_by_fuzzy_name,
+    id
+);
 
 pub async fn ship_name_juustagram_chats<'a>(
     ctx: Context<'a>,
     partial: &'a str,
 ) -> CreateAutocompleteResponse<'a> {
-    let data = ctx.data_ref().azur_lane();
+    let locale = super::resolve_locale(ctx).await;
+    let azur = ctx.data_ref().azur_lane();
 
-    let choices: Vec<_> = data
-        .ships_by_prefix(partial)
+    let choices: Vec<_> = azur
+        .ships_by_fuzzy_name(partial)
         .filter(|s| {
-            data.juustagram_chats_by_ship_id(s.group_id)
+            azur.juustagram_chats_by_ship_id(s.group_id)
                 .next()
                 .is_some()
         })
         .take(25)
         .map(|e| {
-            AutocompleteChoice::new(e.name.as_str(), Cow::Owned(format!("/id:{}", e.group_id)))
+            AutocompleteChoice::new(
+                Cow::Owned(e.choice_name(locale).to_owned()),
+                Cow::Owned(format!("/id:{}", e.group_id)),
+            )
         })
         .collect();
 
     CreateAutocompleteResponse::new().set_choices(choices)
 }
+
+pub async fn fleet_name<'a>(ctx: Context<'a>, partial: &'a str) -> CreateAutocompleteResponse<'a> {
+    async fn inner<'a>(ctx: Context<'a>, partial: &str) -> Result<Vec<AutocompleteChoice<'a>>> {
+        let db = ctx.data_ref().database()?;
+        let filter = doc! {
+            "user": bson_id!(ctx.user().id),
+        };
+
+        let mut query = Fleet::collection(db).find(filter).await?;
+        let partial = partial.to_lowercase();
+
+        let mut choices = Vec::new();
+        while let Some(fleet) = query.try_next().await? {
+            if fleet.name.to_lowercase().contains(&partial) {
+                choices.push(AutocompleteChoice::new(
+                    fleet.name.clone(),
+                    Cow::Owned(fleet.name),
+                ));
+            }
+        }
+
+        Ok(choices)
+    }
+
+    match inner(ctx, partial).await {
+        Ok(choices) => CreateAutocompleteResponse::new().set_choices(choices),
+        Err(why) => {
+            log::warn!("Fleet autocomplete failed: {why:?}");
+            CreateAutocompleteResponse::new()
+        },
+    }
+}
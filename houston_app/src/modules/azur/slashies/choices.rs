@@ -1,6 +1,6 @@
 use azur_lane::equip::{AugmentRarity, EquipKind, EquipRarity};
-use azur_lane::ship::{HullType, ShipRarity};
-use azur_lane::Faction;
+use azur_lane::ship::{Affinity, HullType, ShipRarity};
+use azur_lane::{Faction, Locale};
 
 macro_rules! make_choice {
     ($NewType:ident for $OrigType:ident { $($(#[$attr:meta])* $name:ident),* $(,)? }) => {
@@ -114,3 +114,13 @@ make_choice!(EAugmentRarity for AugmentRarity {
     #[name = "3* Elite"] E,
     #[name = "4* SR"] SR,
 });
+
+make_choice!(EAffinity for Affinity {
+    Neutral, Friendly, Crush, Love, Oath,
+});
+
+make_choice!(ELocale for Locale {
+    #[name = "English"] EN,
+    #[name = "Japanese"] JP,
+    #[name = "Chinese"] CN,
+});
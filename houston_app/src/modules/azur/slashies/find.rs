@@ -2,6 +2,7 @@ use azur_lane::equip::{Augment, Equip};
 use azur_lane::secretary::SpecialSecretary;
 use azur_lane::ship::ShipData;
 
+use crate::modules::azur::data::HAzurLane;
 use crate::slashies::prelude::*;
 
 fn parse_id_input(input: &str) -> Option<u32> {
@@ -10,11 +11,10 @@ fn parse_id_input(input: &str) -> Option<u32> {
 
 macro_rules! make_find {
     ($fn_name:ident -> $T:ty, $by_id:ident, $by_prefix:ident, $error:literal) => {
-        pub fn $fn_name<'a>(data: &'a HBotData, name: &str) -> Result<&'a $T> {
-            let azur_lane = data.azur_lane();
+        pub fn $fn_name<'a>(azur: &'a HAzurLane, name: &str) -> Result<&'a $T> {
             parse_id_input(name)
-                .map(|id| azur_lane.$by_id(id))
-                .unwrap_or_else(|| azur_lane.$by_prefix(name).next())
+                .map(|id| azur.$by_id(id))
+                .unwrap_or_else(|| azur.$by_prefix(name).next())
                 .ok_or(HArgError::new_const($error).into())
         }
     };
@@ -0,0 +1,125 @@
+use azur_lane::ship::ShipData;
+use utils::text::truncate;
+use utils::text::write_str::*;
+
+use super::buttons::loadout::SLOT_COUNT;
+use super::{buttons, find};
+use crate::buttons::ToCustomData as _;
+use crate::modules::azur::data::HAzurLane;
+use crate::modules::azur::model::{Fleet, FleetShip};
+use crate::slashies::prelude::*;
+
+/// The maximum amount of ships in a fleet.
+const MAX_SHIPS: usize = 6;
+
+/// Parses the fleet text entered by a user.
+///
+/// Ships are separated by `;` or new lines. Each ship may be followed by a `:`
+/// and a `,`-separated list of equipment, in slot order. Slots may be left
+/// empty, f.e. `Belfast: , Quadruple 40mm Bofors`.
+pub fn parse_ships(azur: &HAzurLane, text: &str) -> Result<Vec<FleetShip>> {
+    let mut ships = Vec::new();
+
+    for entry in text.split([';', '\n']).map(str::trim) {
+        if entry.is_empty() {
+            continue;
+        }
+
+        if ships.len() >= MAX_SHIPS {
+            let msg = format!("A fleet can have at most {MAX_SHIPS} ships.");
+            return Err(HArgError::new(msg).into());
+        }
+
+        let (ship_name, gear) = entry.split_once(':').unwrap_or((entry, ""));
+        let ship = find::ship(azur, ship_name.trim())
+            .map_err(|_| HArgError::new(format!("Unknown ship `{}`.", ship_name.trim())))?;
+
+        let mut equips = [None; SLOT_COUNT];
+        let gear = gear.split(',').map(str::trim);
+        for (slot, equip_name) in equips.iter_mut().zip(gear) {
+            if !equip_name.is_empty() {
+                let equip = find::equip(azur, equip_name)
+                    .map_err(|_| HArgError::new(format!("Unknown equipment `{equip_name}`.")))?;
+
+                *slot = Some(equip.equip_id);
+            }
+        }
+
+        ships.push(FleetShip {
+            ship_id: ship.group_id,
+            equips,
+        });
+    }
+
+    if ships.is_empty() {
+        return Err(HArgError::new_const("A fleet needs at least one ship.").into());
+    }
+
+    Ok(ships)
+}
+
+/// Creates the reply showing a saved fleet.
+pub fn create_fleet_reply<'a>(
+    data: &'a HBotData,
+    azur: &'a HAzurLane,
+    fleet: &Fleet,
+) -> CreateReply<'a> {
+    let mut embed = CreateEmbed::new()
+        .title(fleet.name.clone())
+        .color(data.config().embed_color);
+
+    let mut nav = Vec::new();
+
+    for entry in &fleet.ships {
+        let Some(ship) = azur.ship_by_id(entry.ship_id) else {
+            embed = embed.field("<Unknown Ship>", "-# This ship no longer exists.", true);
+            continue;
+        };
+
+        embed = embed.field(&ship.name, get_ship_text(data, azur, ship, entry), true);
+
+        let mut view = buttons::loadout::View::new(ship.group_id);
+        view.equips = entry.equips;
+
+        nav.push(
+            CreateButton::new(view.to_custom_id())
+                .label(truncate(&ship.name, 80))
+                .style(ButtonStyle::Secondary),
+        );
+    }
+
+    let rows: Vec<_> = nav
+        .chunks(5)
+        .map(|c| CreateActionRow::buttons(c.to_vec()))
+        .collect();
+
+    CreateReply::new().embed(embed).components(rows)
+}
+
+/// Creates the field text for a single ship in a fleet.
+fn get_ship_text(data: &HBotData, azur: &HAzurLane, ship: &ShipData, entry: &FleetShip) -> String {
+    let mut text = format!(
+        "[{}] {} {}",
+        ship.rarity.name(),
+        buttons::hull_emoji(ship.hull_type, data),
+        ship.hull_type.designation(),
+    );
+
+    let equips = entry
+        .equips
+        .iter()
+        .enumerate()
+        .filter_map(|(index, id)| Some((index, azur.equip_by_id((*id)?)?)));
+
+    let mut any = false;
+    for (index, equip) in equips {
+        write_str!(text, "\n-# {}. {}", index + 1, truncate(&equip.name, 40));
+        any = true;
+    }
+
+    if !any {
+        text.push_str("\n-# No gear.");
+    }
+
+    text
+}
@@ -1,12 +1,46 @@
+use azur_lane::Locale;
+
 use super::buttons;
+use crate::fmt::discord::escape_markdown;
 use crate::slashies::prelude::*;
 
 mod autocomplete;
 mod choices;
 mod find;
+mod fleet;
 
 use choices::*;
 
+/// Maps a Discord locale to the game locale with the matching names.
+fn locale_from_discord(locale: &str) -> Option<Locale> {
+    match locale.split_once('-').map_or(locale, |l| l.0) {
+        "en" => Some(Locale::EN),
+        "ja" => Some(Locale::JP),
+        "zh" => Some(Locale::CN),
+        _ => None,
+    }
+}
+
+/// Resolves the locale to display names in for the invoking user.
+///
+/// The user's saved override takes precedence over their Discord language.
+async fn resolve_locale(ctx: Context<'_>) -> Option<Locale> {
+    use crate::modules::azur::model::*;
+
+    if let Ok(db) = ctx.data_ref().database() {
+        match UserLocale::collection(db)
+            .find_user_locale(ctx.user().id)
+            .await
+        {
+            Ok(Some(locale)) => return Some(locale),
+            Ok(None) => {},
+            Err(why) => log::warn!("Failed to load user locale: {why:?}"),
+        }
+    }
+
+    locale_from_discord(ctx.interaction.locale.as_str())
+}
+
 /// Information about mobile game Azur Lane.
 #[chat_command(
     contexts = "Guild | BotDm | PrivateChannel",
@@ -24,11 +58,76 @@ pub mod azur {
         ephemeral: Option<bool>,
     ) -> Result {
         let data = ctx.data_ref();
-        let ship = find::ship(data, name)?;
+        let azur = data.azur_lane();
+        let ship = find::ship(&azur, name)?;
 
-        let view = buttons::ship::View::new(ship.group_id);
+        let view = buttons::ship::View::new(ship.group_id).locale(resolve_locale(ctx).await);
         ctx.send(
-            view.create_with_ship(data, ship, None)
+            view.create_with_ship(data, &azur, ship, None)
+                .ephemeral(ephemeral.into_ephemeral()),
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Compares the stats of two ships side by side.
+    #[sub_command]
+    async fn compare(
+        ctx: Context<'_>,
+        /// The first ship's name. This supports auto completion.
+        #[autocomplete = "autocomplete::ship_name"]
+        first: &str,
+        /// The second ship's name. This supports auto completion.
+        #[autocomplete = "autocomplete::ship_name"]
+        second: &str,
+        /// Whether to show the response only to yourself.
+        ephemeral: Option<bool>,
+    ) -> Result {
+        let data = ctx.data_ref();
+        let azur = data.azur_lane();
+        let first = find::ship(&azur, first)?;
+        let second = find::ship(&azur, second)?;
+
+        let view = buttons::compare::View::new(first.group_id, second.group_id);
+        ctx.send(
+            view.create_with_ships(data, [first, second])
+                .ephemeral(ephemeral.into_ephemeral()),
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Calculates a ship's stats at a specific level and affinity.
+    #[sub_command]
+    async fn stats(
+        ctx: Context<'_>,
+        /// The ship's name. This supports auto completion.
+        #[autocomplete = "autocomplete::ship_name"]
+        name: &str,
+        /// The ship's level. Defaults to 120.
+        #[min = 1]
+        #[max = 125]
+        level: Option<u8>,
+        /// The ship's affinity. Defaults to Love.
+        affinity: Option<EAffinity>,
+        /// The amount of limit breaks. Defaults to 3.
+        #[min = 0]
+        #[max = 3]
+        #[name = "limit-break"]
+        limit_break: Option<u8>,
+        /// Whether to show the response only to yourself.
+        ephemeral: Option<bool>,
+    ) -> Result {
+        let azur = ctx.data_ref().azur_lane();
+        let ship = find::ship(&azur, name)?;
+
+        let mut view = buttons::stats::View::new(ship.group_id);
+        view.level = level.unwrap_or(view.level);
+        view.affinity = affinity.map_or(view.affinity, EAffinity::convert);
+        view.limit_break = limit_break.unwrap_or(view.limit_break);
+
+        ctx.send(
+            view.create_with_ship(ship, None)
                 .ephemeral(ephemeral.into_ephemeral()),
         )
         .await?;
@@ -45,12 +144,12 @@ pub mod azur {
         /// Whether to show the response only to yourself.
         ephemeral: Option<bool>,
     ) -> Result {
-        let data = ctx.data_ref();
-        let equip = find::equip(data, name)?;
+        let azur = ctx.data_ref().azur_lane();
+        let equip = find::equip(&azur, name)?;
 
-        let view = buttons::equip::View::new(equip.equip_id);
+        let view = buttons::equip::View::new(equip.equip_id).locale(resolve_locale(ctx).await);
         ctx.send(
-            view.create_with_equip(equip)
+            view.create_with_equip(&azur, equip)
                 .ephemeral(ephemeral.into_ephemeral()),
         )
         .await?;
@@ -67,12 +166,13 @@ pub mod azur {
         /// Whether to show the response only to yourself.
         ephemeral: Option<bool>,
     ) -> Result {
-        let data = ctx.data_ref();
-        let augment = find::augment(data, name)?;
+        let azur = ctx.data_ref().azur_lane();
+        let augment = find::augment(&azur, name)?;
 
-        let view = buttons::augment::View::new(augment.augment_id);
+        let view =
+            buttons::augment::View::new(augment.augment_id).locale(resolve_locale(ctx).await);
         ctx.send(
-            view.create_with_augment(data, augment)
+            view.create_with_augment(&azur, augment)
                 .ephemeral(ephemeral.into_ephemeral()),
         )
         .await?;
@@ -90,7 +190,8 @@ pub mod azur {
         ephemeral: Option<bool>,
     ) -> Result {
         let data = ctx.data_ref();
-        let secretary = find::special_secretary(data, name)?;
+        let azur = data.azur_lane();
+        let secretary = find::special_secretary(&azur, name)?;
 
         let view = buttons::special_secretary::View::new(secretary.id);
         ctx.send(
@@ -114,17 +215,21 @@ pub mod azur {
         use buttons::search_juustagram_chat::*;
 
         let data = ctx.data_ref();
+        let azur = data.azur_lane();
 
         let filter = Filter {
             ship: match ship {
-                Some(ship) => Some(find::ship(data, ship)?.group_id),
+                Some(ship) => Some(find::ship(&azur, ship)?.group_id),
                 None => None,
             },
         };
 
         let view = View::new(filter);
-        ctx.send(view.create(data)?.ephemeral(ephemeral.into_ephemeral()))
-            .await?;
+        ctx.send(
+            view.create(data, &azur)?
+                .ephemeral(ephemeral.into_ephemeral()),
+        )
+        .await?;
 
         Ok(())
     }
@@ -145,7 +250,7 @@ pub mod azur {
         /// Whether to show the response only to yourself.
         ephemeral: Option<bool>,
     ) -> Result {
-        let reload_time = (200.0 / (100.0 + rld)).sqrt() * weapon_reload;
+        let reload_time = azur_lane::combat::reload_mult(rld) * weapon_reload;
 
         let description = format!(
             "-# **Base Weapon FR:** {weapon_reload:.2}s \u{2E31} **`RLD:`**`{rld: >4}`\n\
@@ -160,6 +265,217 @@ pub mod azur {
         Ok(())
     }
 
+    /// Sets the language to show names in, overriding your Discord language.
+    #[sub_command]
+    async fn locale(
+        ctx: Context<'_>,
+        /// The language to use. Leave empty to follow your Discord language.
+        locale: Option<ELocale>,
+    ) -> Result {
+        use crate::modules::azur::model::*;
+
+        let data = ctx.data_ref();
+        let db = data.database()?;
+        let locale = locale.map(ELocale::convert);
+
+        ctx.defer_as(Ephemeral).await?;
+
+        UserLocale::collection(db)
+            .set_user_locale(ctx.user().id, locale)
+            .await?;
+
+        let description = match locale {
+            Some(locale) => format!("Names will be shown in **{}** where known.", locale.name()),
+            None => "Names will follow your Discord language.".to_owned(),
+        };
+
+        let embed = CreateEmbed::new()
+            .color(data.config().embed_color)
+            .description(description);
+
+        ctx.send(CreateReply::new().embed(embed)).await?;
+        Ok(())
+    }
+
+    /// Reloads the game data. Only usable by the bot owner.
+    #[sub_command]
+    async fn reload(ctx: Context<'_>) -> Result {
+        if !ctx.is_owner().await? {
+            return Err(HArgError::new_const("Only the bot owner can reload the data.").into());
+        }
+
+        ctx.defer_as(Ephemeral).await?;
+
+        let data = ctx.data_ref();
+        let azur = tokio::task::block_in_place(|| data.reload_azur_lane())?;
+
+        let description = format!(
+            "Reloaded the data.\n\
+            -# {} ships \u{2E31} {} equips \u{2E31} {} augments",
+            azur.ships().len(),
+            azur.equips().len(),
+            azur.augments().len(),
+        );
+
+        let embed = CreateEmbed::new()
+            .color(data.config().embed_color)
+            .description(description);
+
+        ctx.send(CreateReply::new().embed(embed)).await?;
+        Ok(())
+    }
+
+    /// Lists where ships or equipment drop.
+    #[sub_command]
+    mod r#where {
+        /// Lists the stages a ship drops in.
+        #[sub_command]
+        async fn ship(
+            ctx: Context<'_>,
+            /// The ship's name. This supports auto completion.
+            #[autocomplete = "autocomplete::ship_name"]
+            name: &str,
+            /// Whether to show the response only to yourself.
+            ephemeral: Option<bool>,
+        ) -> Result {
+            use buttons::drops::*;
+
+            let data = ctx.data_ref();
+            let azur = data.azur_lane();
+            let ship = find::ship(&azur, name)?;
+
+            let view = View::new(DropSource::Ship(ship.group_id));
+            ctx.send(
+                view.create(data, &azur)?
+                    .ephemeral(ephemeral.into_ephemeral()),
+            )
+            .await?;
+
+            Ok(())
+        }
+
+        /// Lists the stages equipment drops in.
+        #[sub_command]
+        async fn equip(
+            ctx: Context<'_>,
+            /// The equipment name. This supports auto completion.
+            #[autocomplete = "autocomplete::equip_name"]
+            name: &str,
+            /// Whether to show the response only to yourself.
+            ephemeral: Option<bool>,
+        ) -> Result {
+            use buttons::drops::*;
+
+            let data = ctx.data_ref();
+            let azur = data.azur_lane();
+            let equip = find::equip(&azur, name)?;
+
+            let view = View::new(DropSource::Equip(equip.equip_id));
+            ctx.send(
+                view.create(data, &azur)?
+                    .ephemeral(ephemeral.into_ephemeral()),
+            )
+            .await?;
+
+            Ok(())
+        }
+    }
+
+    /// Save and share fleets.
+    #[sub_command]
+    mod fleet {
+        /// Saves a fleet under a name, replacing any fleet with the same name.
+        #[sub_command]
+        async fn save(
+            ctx: Context<'_>,
+            /// The name to save the fleet as.
+            #[max_length = 50]
+            name: &str,
+            /// Ships separated by ";". Add gear after a ":", separated by ",".
+            ships: &str,
+        ) -> Result {
+            use crate::modules::azur::model::*;
+
+            let data = ctx.data_ref();
+            let db = data.database()?;
+            let ships = fleet::parse_ships(&data.azur_lane(), ships)?;
+
+            ctx.defer_as(Ephemeral).await?;
+
+            Fleet::collection(db)
+                .save_fleet(ctx.user().id, name, &ships)
+                .await?;
+
+            let embed = CreateEmbed::new()
+                .color(data.config().embed_color)
+                .description(format!("Saved fleet **{}**.", escape_markdown(name)));
+
+            ctx.send(CreateReply::new().embed(embed)).await?;
+            Ok(())
+        }
+
+        /// Shows one of your saved fleets.
+        #[sub_command]
+        async fn show(
+            ctx: Context<'_>,
+            /// The fleet's name.
+            #[autocomplete = "autocomplete::fleet_name"]
+            name: &str,
+            /// Whether to show the response only to yourself.
+            ephemeral: Option<bool>,
+        ) -> Result {
+            use crate::modules::azur::model::*;
+
+            let data = ctx.data_ref();
+            let db = data.database()?;
+
+            let fleet = Fleet::collection(db)
+                .find_fleet(ctx.user().id, name)
+                .await?
+                .ok_or(HArgError::new_const("You have no fleet with that name."))?;
+
+            let azur = data.azur_lane();
+            ctx.send(
+                fleet::create_fleet_reply(data, &azur, &fleet)
+                    .ephemeral(ephemeral.into_ephemeral()),
+            )
+            .await?;
+
+            Ok(())
+        }
+
+        /// Deletes one of your saved fleets.
+        #[sub_command]
+        async fn delete(
+            ctx: Context<'_>,
+            /// The fleet's name.
+            #[autocomplete = "autocomplete::fleet_name"]
+            name: &str,
+        ) -> Result {
+            use crate::modules::azur::model::*;
+
+            let data = ctx.data_ref();
+            let db = data.database()?;
+
+            ctx.defer_as(Ephemeral).await?;
+
+            let deleted = Fleet::collection(db)
+                .delete_fleet(ctx.user().id, name)
+                .await?;
+
+            if !deleted {
+                return Err(HArgError::new_const("You have no fleet with that name.").into());
+            }
+
+            let embed = CreateEmbed::new()
+                .color(data.config().embed_color)
+                .description(format!("Deleted fleet **{}**.", escape_markdown(name)));
+
+            ctx.send(CreateReply::new().embed(embed)).await?;
+            Ok(())
+        }
+    }
+
     /// Search for information.
     #[sub_command]
     mod search {
@@ -185,6 +501,7 @@ pub mod azur {
             use buttons::search_ship::*;
 
             let data = ctx.data_ref();
+            let azur = data.azur_lane();
 
             let filter = Filter {
                 name: name.map(str::to_owned),
@@ -194,9 +511,12 @@ pub mod azur {
                 has_augment,
             };
 
-            let view = View::new(filter);
-            ctx.send(view.create(data)?.ephemeral(ephemeral.into_ephemeral()))
-                .await?;
+            let view = View::new(filter).locale(resolve_locale(ctx).await);
+            ctx.send(
+                view.create(data, &azur)?
+                    .ephemeral(ephemeral.into_ephemeral()),
+            )
+            .await?;
 
             Ok(())
         }
@@ -219,6 +539,7 @@ pub mod azur {
             use buttons::search_equip::*;
 
             let data = ctx.data_ref();
+            let azur = data.azur_lane();
 
             let filter = Filter {
                 name: name.map(str::to_owned),
@@ -227,9 +548,12 @@ pub mod azur {
                 rarity: rarity.map(EEquipRarity::convert),
             };
 
-            let view = View::new(filter);
-            ctx.send(view.create(data)?.ephemeral(ephemeral.into_ephemeral()))
-                .await?;
+            let view = View::new(filter).locale(resolve_locale(ctx).await);
+            ctx.send(
+                view.create(data, &azur)?
+                    .ephemeral(ephemeral.into_ephemeral()),
+            )
+            .await?;
 
             Ok(())
         }
@@ -255,9 +579,10 @@ pub mod azur {
             use buttons::search_augment::*;
 
             let data = ctx.data_ref();
+            let azur = data.azur_lane();
 
             let unique_ship_id = match for_ship {
-                Some(for_ship) => Some(find::ship(data, for_ship)?.group_id),
+                Some(for_ship) => Some(find::ship(&azur, for_ship)?.group_id),
                 None => None,
             };
 
@@ -268,9 +593,40 @@ pub mod azur {
                 unique_ship_id,
             };
 
+            let view = View::new(filter).locale(resolve_locale(ctx).await);
+            ctx.send(
+                view.create(data, &azur)?
+                    .ephemeral(ephemeral.into_ephemeral()),
+            )
+            .await?;
+
+            Ok(())
+        }
+
+        /// Searches for skills of ships and augment modules.
+        #[sub_command]
+        async fn skill(
+            ctx: Context<'_>,
+            /// Text to search for in skill names and descriptions.
+            text: &str,
+            /// Whether to show the response only to yourself.
+            ephemeral: Option<bool>,
+        ) -> Result {
+            use buttons::search_skill::*;
+
+            let data = ctx.data_ref();
+            let azur = data.azur_lane();
+
+            let filter = Filter {
+                text: text.to_owned(),
+            };
+
             let view = View::new(filter);
-            ctx.send(view.create(data)?.ephemeral(ephemeral.into_ephemeral()))
-                .await?;
+            ctx.send(
+                view.create(data, &azur)?
+                    .ephemeral(ephemeral.into_ephemeral()),
+            )
+            .await?;
 
             Ok(())
         }
@@ -287,14 +643,18 @@ pub mod azur {
             use buttons::search_special_secretary::*;
 
             let data = ctx.data_ref();
+            let azur = data.azur_lane();
 
             let filter = Filter {
                 name: name.map(str::to_owned),
             };
 
             let view = View::new(filter);
-            ctx.send(view.create(data)?.ephemeral(ephemeral.into_ephemeral()))
-                .await?;
+            ctx.send(
+                view.create(data, &azur)?
+                    .ephemeral(ephemeral.into_ephemeral()),
+            )
+            .await?;
 
             Ok(())
         }
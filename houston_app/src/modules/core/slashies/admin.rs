@@ -0,0 +1,176 @@
+use utils::text::write_str::*;
+
+use crate::slashies::prelude::*;
+
+/// Manages the bot. Only usable by the bot owner.
+#[chat_command(contexts = "Guild | BotDm", integration_types = "Guild | User")]
+pub mod admin {
+    /// Reloads the bot config and applies changes that don't need a restart.
+    #[sub_command(name = "reload-config")]
+    async fn reload_config(ctx: Context<'_>) -> Result {
+        if !ctx.is_owner().await? {
+            return Err(HArgError::new_const("Only the bot owner can reload the config.").into());
+        }
+
+        ctx.defer_as(Ephemeral).await?;
+
+        let data = ctx.data_ref();
+        let config = tokio::task::block_in_place(|| -> Result<_> {
+            let config = crate::config::build_config()?;
+            data.reload_config(config.bot)
+        })?;
+
+        log::info!("Reloaded the bot config.");
+
+        let embed = CreateEmbed::new().color(config.embed_color).description(
+            "Reloaded the config.\n\
+                 -# Changes outside the `bot` section still require a restart.",
+        );
+
+        ctx.send(CreateReply::new().embed(embed)).await?;
+        Ok(())
+    }
+
+    /// Shows the latency and status of each shard.
+    #[sub_command]
+    async fn shards(ctx: Context<'_>) -> Result {
+        if !ctx.is_owner().await? {
+            return Err(HArgError::new_const("Only the bot owner can view the shards.").into());
+        }
+
+        ctx.defer_as(Ephemeral).await?;
+
+        let data = ctx.data_ref();
+        let shard_manager = data.shard_manager()?;
+
+        let mut shards: Vec<_> = shard_manager
+            .runners
+            .lock()
+            .await
+            .iter()
+            .map(|(id, info)| (*id, info.latency, info.stage))
+            .collect();
+
+        shards.sort_unstable_by_key(|(id, ..)| *id);
+
+        let mut description = String::new();
+        for (id, latency, stage) in shards {
+            let current = if id == ctx.serenity.shard_id {
+                " (current)"
+            } else {
+                ""
+            };
+            match latency {
+                Some(latency) => write_str!(
+                    description,
+                    "- **Shard {id}**{current}: {stage}, {} ms\n",
+                    latency.as_millis()
+                ),
+                None => write_str!(description, "- **Shard {id}**{current}: {stage}\n"),
+            }
+        }
+
+        if description.is_empty() {
+            description.push_str("No shards are running.");
+        }
+
+        let embed = CreateEmbed::new()
+            .title("Shards")
+            .color(data.config().embed_color)
+            .description(description);
+
+        ctx.send(CreateReply::new().embed(embed)).await?;
+        Ok(())
+    }
+
+    /// Shows the most used commands and buttons.
+    #[sub_command]
+    async fn stats(
+        ctx: Context<'_>,
+        /// How many days to include. Defaults to 7.
+        #[min = 1]
+        #[max = 90]
+        days: Option<u32>,
+    ) -> Result {
+        if !ctx.is_owner().await? {
+            return Err(HArgError::new_const("Only the bot owner can view the stats.").into());
+        }
+
+        let data = ctx.data_ref();
+        anyhow::ensure!(
+            data.config().analytics.is_some(),
+            HArgError::new_const("Analytics are not enabled.")
+        );
+
+        ctx.defer_as(Ephemeral).await?;
+
+        let days = days.unwrap_or(7);
+        let description = stats_description(data, days).await?;
+
+        let embed = CreateEmbed::new()
+            .title(format!("Usage in the last {days} day(s)"))
+            .color(data.config().embed_color)
+            .description(description);
+
+        ctx.send(CreateReply::new().embed(embed)).await?;
+        Ok(())
+    }
+}
+
+/// The most entries shown by `/admin stats`.
+const MAX_STATS_ENTRIES: usize = 15;
+
+async fn stats_description(data: &HBotData, days: u32) -> Result<String> {
+    use chrono::{TimeDelta, Utc};
+
+    use crate::modules::analytics::model::{Usage, UsageExt as _, UsageKind};
+    use crate::modules::analytics::{latency_percentile, start_of_day, summarize, LATENCY_BUCKETS};
+
+    let since = start_of_day(Utc::now()) - TimeDelta::days(i64::from(days) - 1);
+    let records = Usage::collection(data.database()?)
+        .find_since(since)
+        .await?;
+
+    let summaries = summarize(records);
+    if summaries.is_empty() {
+        return Ok("Nothing was used yet.".to_owned());
+    }
+
+    let fmt_latency = |bucket: Option<String>| match bucket.as_deref() {
+        None => "?".to_owned(),
+        Some("inf") => format!(">{} ms", LATENCY_BUCKETS[LATENCY_BUCKETS.len() - 1]),
+        Some(bound) => format!("≤{bound} ms"),
+    };
+
+    let mut description = String::new();
+    for summary in summaries.iter().take(MAX_STATS_ENTRIES) {
+        let prefix = match summary.kind {
+            UsageKind::Command => "/",
+            UsageKind::Button => "Button: ",
+        };
+
+        // error rate in tenths of a percent
+        let errors = summary.errors * 1000 / summary.uses.max(1);
+
+        writeln_str!(
+            description,
+            "- **{prefix}{}**: {} use(s), {}.{}% errors, p50 {}, p95 {}",
+            summary.name,
+            summary.uses,
+            errors / 10,
+            errors % 10,
+            fmt_latency(latency_percentile(&summary.latency, 50)),
+            fmt_latency(latency_percentile(&summary.latency, 95)),
+        );
+    }
+
+    if summaries.len() > MAX_STATS_ENTRIES {
+        writeln_str!(
+            description,
+            "-# ...and {} more.",
+            summaries.len() - MAX_STATS_ENTRIES
+        );
+    }
+
+    Ok(description)
+}
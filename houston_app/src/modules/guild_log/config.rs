@@ -0,0 +1,31 @@
+use std::collections::HashMap;
+
+use crate::prelude::*;
+
+pub type Config = HashMap<GuildId, GuildLogEntry>;
+
+fn default_true() -> bool {
+    true
+}
+
+/// Configures which events are logged for a guild.
+#[derive(Debug, Clone, Copy, serde::Deserialize)]
+pub struct GuildLogEntry {
+    /// The channel to write the log to.
+    pub channel: ChannelId,
+    /// Whether to log deleted messages.
+    #[serde(default = "default_true")]
+    pub message_delete: bool,
+    /// Whether to log edited messages.
+    #[serde(default = "default_true")]
+    pub message_edit: bool,
+    /// Whether to log members joining.
+    #[serde(default = "default_true")]
+    pub member_join: bool,
+    /// Whether to log members leaving.
+    #[serde(default = "default_true")]
+    pub member_leave: bool,
+    /// Whether to log roles being created, updated, or deleted.
+    #[serde(default = "default_true")]
+    pub role_changes: bool,
+}
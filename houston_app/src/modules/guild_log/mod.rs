@@ -0,0 +1,273 @@
+//! Writes server events to a log channel.
+//!
+//! Entries are queued and sent in batches to stay clear of rate limits, so
+//! they may show up a few seconds late. The queue is only kept in memory, so
+//! entries still queued when the bot stops are lost.
+
+use std::sync::LazyLock;
+use std::time::Duration;
+
+use dashmap::DashMap;
+use utils::text::truncate;
+
+use super::prelude::*;
+use crate::fmt::discord::escape_markdown;
+
+pub mod config;
+
+pub use config::Config;
+use config::GuildLogEntry;
+
+/// How long entries are queued before they are sent.
+const FLUSH_INTERVAL: Duration = Duration::from_secs(5);
+
+/// The most embeds Discord allows per message.
+const MAX_EMBEDS: usize = 10;
+
+/// The most embed text to send per message.
+///
+/// Discord allows 6000 characters across all embeds, this leaves some room
+/// for the parts that aren't counted here.
+const MAX_MESSAGE_LEN: usize = 5000;
+
+/// The most message content shown in a single entry.
+const MAX_CONTENT_LEN: usize = 1000;
+
+/// The queued entries, by the channel they are sent to.
+static QUEUE: LazyLock<DashMap<ChannelId, Vec<LogEntry>>> = LazyLock::new(DashMap::new);
+
+struct LogEntry {
+    embed: CreateEmbed<'static>,
+    len: usize,
+}
+
+pub struct Module;
+
+impl super::Module for Module {
+    fn enabled(&self, config: &HBotConfig) -> bool {
+        !config.guild_log.is_empty()
+    }
+
+    fn intents(&self, _config: &HBotConfig) -> GatewayIntents {
+        GatewayIntents::GUILDS
+            | GatewayIntents::GUILD_MEMBERS
+            | GatewayIntents::GUILD_MESSAGES
+            | GatewayIntents::MESSAGE_CONTENT
+    }
+
+    fn validate(&self, config: &HBotConfig) -> Result {
+        log::info!("Guild log is enabled: {} guild(s)", config.guild_log.len());
+        Ok(())
+    }
+}
+
+/// Gets the amount of entries waiting to be sent.
+pub fn queue_len() -> usize {
+    QUEUE.iter().map(|e| e.value().len()).sum()
+}
+
+/// Sends the entries queued for a channel once the flush interval has passed.
+fn spawn_flush(ctx: &Context, channel: ChannelId) {
+    let ctx = ctx.clone();
+    tokio::task::spawn(async move {
+        tokio::time::sleep(FLUSH_INTERVAL).await;
+        flush(&ctx, channel).await;
+    });
+}
+
+async fn flush(ctx: &Context, channel: ChannelId) {
+    let Some((_, entries)) = QUEUE.remove(&channel) else {
+        return;
+    };
+
+    let mut batch = Vec::new();
+    let mut batch_len = 0;
+
+    for entry in entries {
+        if batch.len() >= MAX_EMBEDS || batch_len + entry.len > MAX_MESSAGE_LEN {
+            send_batch(ctx, channel, std::mem::take(&mut batch)).await;
+            batch_len = 0;
+        }
+
+        batch_len += entry.len;
+        batch.push(entry.embed);
+    }
+
+    if !batch.is_empty() {
+        send_batch(ctx, channel, batch).await;
+    }
+}
+
+async fn send_batch(ctx: &Context, channel: ChannelId, embeds: Vec<CreateEmbed<'static>>) {
+    let message = CreateMessage::new()
+        .embeds(embeds)
+        .allowed_mentions(CreateAllowedMentions::new());
+
+    if let Err(why) = channel.send_message(&ctx.http, message).await {
+        log::warn!("Failed to write guild log to {channel}: {why:?}");
+    }
+}
+
+/// Gets the log config for a guild if the event is enabled.
+fn get_entry(
+    ctx: &Context,
+    guild_id: GuildId,
+    enabled: impl FnOnce(&GuildLogEntry) -> bool,
+) -> Option<GuildLogEntry> {
+    let data = ctx.data_ref::<HContextData>();
+    data.config()
+        .guild_log
+        .get(&guild_id)
+        .filter(|e| enabled(e))
+        .copied()
+}
+
+/// Queues an entry to be sent to the log channel.
+fn push_entry(ctx: &Context, entry: GuildLogEntry, title: &'static str, description: String) {
+    let data = ctx.data_ref::<HContextData>();
+    let len = title.len() + description.len();
+
+    let embed = CreateEmbed::new()
+        .title(title)
+        .description(description)
+        .color(data.config().embed_color)
+        .timestamp(Timestamp::now());
+
+    let mut queue = QUEUE.entry(entry.channel).or_default();
+    let first = queue.is_empty();
+    queue.push(LogEntry { embed, len });
+    drop(queue);
+
+    // the first entry starts the wait, later ones are sent along with it
+    if first {
+        spawn_flush(ctx, entry.channel);
+    }
+}
+
+pub fn message_delete(
+    ctx: &Context,
+    channel_id: ChannelId,
+    message_id: MessageId,
+    guild_id: Option<GuildId>,
+) {
+    let Some(guild_id) = guild_id else {
+        return;
+    };
+
+    let Some(entry) = get_entry(ctx, guild_id, |e| e.message_delete) else {
+        return;
+    };
+
+    if entry.channel == channel_id {
+        return;
+    }
+
+    // without a cache, the content of deleted messages is unknown
+    let description = format!("In <#{channel_id}>\n-# Message ID: {message_id}");
+    push_entry(ctx, entry, "Message deleted", description);
+}
+
+pub fn message_update(ctx: &Context, event: &MessageUpdateEvent) {
+    let Some(guild_id) = event.guild_id else {
+        return;
+    };
+
+    // updates without content are usually just embeds being resolved
+    let (Some(content), Some(author)) = (&event.content, &event.author) else {
+        return;
+    };
+
+    if author.bot() {
+        return;
+    }
+
+    let Some(entry) = get_entry(ctx, guild_id, |e| e.message_edit) else {
+        return;
+    };
+
+    if entry.channel == event.channel_id {
+        return;
+    }
+
+    let description = format!(
+        "By <@{}> in https://discord.com/channels/{guild_id}/{}/{}\n{}",
+        author.id,
+        event.channel_id,
+        event.id,
+        truncate(content.as_str(), MAX_CONTENT_LEN),
+    );
+
+    push_entry(ctx, entry, "Message edited", description);
+}
+
+pub fn guild_member_addition(ctx: &Context, member: &Member) {
+    let Some(entry) = get_entry(ctx, member.guild_id, |e| e.member_join) else {
+        return;
+    };
+
+    let description = format!(
+        "<@{}> ({})",
+        member.user.id,
+        escape_markdown(&member.user.name)
+    );
+
+    push_entry(ctx, entry, "Member joined", description);
+}
+
+pub fn guild_member_removal(ctx: &Context, guild_id: GuildId, user: &User) {
+    let Some(entry) = get_entry(ctx, guild_id, |e| e.member_leave) else {
+        return;
+    };
+
+    let description = format!("<@{}> ({})", user.id, escape_markdown(&user.name));
+    push_entry(ctx, entry, "Member left", description);
+}
+
+pub fn guild_role_create(ctx: &Context, role: &Role) {
+    let Some(entry) = get_entry(ctx, role.guild_id, |e| e.role_changes) else {
+        return;
+    };
+
+    let description = format!("<@&{}> ({})", role.id, escape_markdown(&role.name));
+    push_entry(ctx, entry, "Role created", description);
+}
+
+pub fn guild_role_update(ctx: &Context, role: &Role) {
+    let Some(entry) = get_entry(ctx, role.guild_id, |e| e.role_changes) else {
+        return;
+    };
+
+    // the rainbow role changes color every few minutes
+    if is_rainbow_role(ctx.data_ref::<HContextData>(), role.guild_id, role.id) {
+        return;
+    }
+
+    let description = format!(
+        "<@&{}> ({})\n-# Color: #{:06X}, Permissions: {}",
+        role.id,
+        escape_markdown(&role.name),
+        role.colour.0,
+        role.permissions.bits(),
+    );
+
+    push_entry(ctx, entry, "Role updated", description);
+}
+
+/// Checks whether a role is the rainbow role from perks.
+fn is_rainbow_role(data: &HBotData, guild_id: GuildId, role_id: RoleId) -> bool {
+    data.config()
+        .perks
+        .as_ref()
+        .and_then(|p| p.rainbow.as_ref())
+        .and_then(|r| r.guilds.get(&guild_id))
+        .is_some_and(|e| e.role == role_id)
+}
+
+pub fn guild_role_delete(ctx: &Context, guild_id: GuildId, role_id: RoleId) {
+    let Some(entry) = get_entry(ctx, guild_id, |e| e.role_changes) else {
+        return;
+    };
+
+    let description = format!("Role ID: {role_id}");
+    push_entry(ctx, entry, "Role deleted", description);
+}
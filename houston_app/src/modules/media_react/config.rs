@@ -0,0 +1,227 @@
+use std::collections::HashMap;
+
+use chrono::TimeDelta;
+use regex::Regex;
+
+use crate::helper::time::serde_time_delta;
+use crate::modules::starboard::config::StarboardEmoji;
+use crate::prelude::*;
+
+pub type Config = HashMap<ChannelId, MediaChannelEntry>;
+
+#[derive(Debug, serde::Deserialize)]
+pub struct MediaChannelEntry {
+    pub emojis: Vec<MediaReactEntry>,
+    /// How long to wait before reacting.
+    #[serde(with = "serde_time_delta", default)]
+    pub delay: TimeDelta,
+    /// If set, only messages with media are allowed.
+    pub enforce: Option<MediaEnforceConfig>,
+}
+
+fn default_enforce_grace() -> TimeDelta {
+    const { TimeDelta::minutes(1) }
+}
+
+/// Configures how media-only channels are enforced.
+#[derive(Debug, serde::Deserialize)]
+pub struct MediaEnforceConfig {
+    /// What to do with messages without media.
+    #[serde(default)]
+    pub action: EnforceAction,
+    /// How long the author has to add media or delete the message themselves.
+    #[serde(with = "serde_time_delta", default = "default_enforce_grace")]
+    pub grace: TimeDelta,
+    /// The notice to DM to the author. Empty to not send any.
+    ///
+    /// `{user}`, `{channel}`, and `{link}` are replaced.
+    pub notice: Option<String>,
+}
+
+impl MediaEnforceConfig {
+    /// Gets the notice to DM, falling back to one fitting the action.
+    pub fn notice(&self) -> &str {
+        match (&self.notice, self.action) {
+            (Some(notice), _) => notice,
+            (None, EnforceAction::Delete) => {
+                "Your message in {channel} was removed because only media is allowed there."
+            },
+            (None, EnforceAction::Flag) => {
+                "Your message in {channel} was flagged because only media is allowed there: {link}"
+            },
+        }
+    }
+}
+
+/// What to do with messages without media in media-only channels.
+#[derive(Debug, Default, Clone, Copy, serde::Deserialize)]
+pub enum EnforceAction {
+    /// Delete the message.
+    #[default]
+    Delete,
+    /// React to the message with a warning sign.
+    Flag,
+}
+
+/// An emoji to react with, along with which media it applies to.
+///
+/// May be specified as just the emoji, in which case it applies to all media.
+#[derive(Debug, serde::Deserialize)]
+#[serde(from = "MediaReactEntryRepr")]
+pub struct MediaReactEntry {
+    pub emoji: StarboardEmoji,
+    pub check: MediaCheck,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+enum MediaReactEntryRepr {
+    Emoji(StarboardEmoji),
+    Full {
+        emoji: StarboardEmoji,
+        #[serde(flatten)]
+        check: MediaCheck,
+    },
+}
+
+impl From<MediaReactEntryRepr> for MediaReactEntry {
+    fn from(value: MediaReactEntryRepr) -> Self {
+        match value {
+            MediaReactEntryRepr::Emoji(emoji) => Self {
+                emoji,
+                check: MediaCheck::default(),
+            },
+            MediaReactEntryRepr::Full { emoji, check } => Self { emoji, check },
+        }
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Determines which media a reaction emoji applies to.
+#[derive(Debug, serde::Deserialize)]
+pub struct MediaCheck {
+    /// Whether attachments and forwarded messages count.
+    #[serde(default = "default_true")]
+    pub attachments: bool,
+    /// Whether links in the message content count.
+    #[serde(default = "default_true")]
+    pub links: bool,
+    /// If not empty, only links to these domains or their subdomains count.
+    #[serde(default)]
+    pub allow_domains: Vec<String>,
+    /// Links to these domains or their subdomains never count.
+    #[serde(default)]
+    pub block_domains: Vec<String>,
+    /// If set, only links matching this pattern count.
+    #[serde(default, deserialize_with = "deserialize_regex")]
+    pub pattern: Option<Regex>,
+}
+
+impl Default for MediaCheck {
+    fn default() -> Self {
+        Self {
+            attachments: true,
+            links: true,
+            allow_domains: Vec::new(),
+            block_domains: Vec::new(),
+            pattern: None,
+        }
+    }
+}
+
+impl MediaCheck {
+    /// Checks whether a link counts for this emoji.
+    ///
+    /// The link should include the schema.
+    pub fn is_link_match(&self, link: &str) -> bool {
+        if !self.links {
+            return false;
+        }
+
+        if !self.allow_domains.is_empty() || !self.block_domains.is_empty() {
+            let Ok(url) = url::Url::parse(link) else {
+                return false;
+            };
+
+            let Some(host) = url.host_str() else {
+                return false;
+            };
+
+            if !self.allow_domains.is_empty()
+                && !self.allow_domains.iter().any(|d| is_domain_match(host, d))
+            {
+                return false;
+            }
+
+            if self.block_domains.iter().any(|d| is_domain_match(host, d)) {
+                return false;
+            }
+        }
+
+        self.pattern.as_ref().is_none_or(|p| p.is_match(link))
+    }
+}
+
+/// Checks whether a host is the domain or one of its subdomains.
+fn is_domain_match(host: &str, domain: &str) -> bool {
+    host.strip_suffix(domain)
+        .is_some_and(|rest| rest.is_empty() || rest.ends_with('.'))
+}
+
+fn deserialize_regex<'de, D>(deserializer: D) -> Result<Option<Regex>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::de::Error as _;
+    use serde::Deserialize as _;
+
+    let Some(pattern) = Option::<String>::deserialize(deserializer)? else {
+        return Ok(None);
+    };
+
+    Regex::new(&pattern).map(Some).map_err(D::Error::custom)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MediaCheck;
+
+    #[test]
+    fn domain_filters() {
+        let check = MediaCheck {
+            allow_domains: vec!["pixiv.net".to_owned(), "twitter.com".to_owned()],
+            block_domains: vec!["sketch.pixiv.net".to_owned()],
+            ..MediaCheck::default()
+        };
+
+        assert!(check.is_link_match("https://www.pixiv.net/en/artworks/123"));
+        assert!(check.is_link_match("https://twitter.com/user/status/456"));
+        assert!(!check.is_link_match("https://sketch.pixiv.net/items/789"));
+        assert!(!check.is_link_match("https://notpixiv.net/artworks/123"));
+        assert!(!check.is_link_match("https://imgur.com/gallery/IpNHG9c"));
+    }
+
+    #[test]
+    fn pattern_filter() {
+        let check = MediaCheck {
+            pattern: Some(regex::Regex::new(r"\.(png|jpe?g)$").unwrap()),
+            ..MediaCheck::default()
+        };
+
+        assert!(check.is_link_match("https://example.com/image.png"));
+        assert!(!check.is_link_match("https://example.com/video.mp4"));
+    }
+
+    #[test]
+    fn no_links() {
+        let check = MediaCheck {
+            links: false,
+            ..MediaCheck::default()
+        };
+
+        assert!(!check.is_link_match("https://example.com/image.png"));
+    }
+}
@@ -1,7 +1,15 @@
-use std::collections::HashMap;
+use chrono::TimeDelta;
+use serenity::futures::stream::{self, StreamExt as _};
+use utils::text::write_str::*;
 
 use super::prelude::*;
-use super::starboard::config::StarboardEmoji;
+use crate::fmt::replace_holes;
+use crate::helper::discord::{is_unknown_message, log_event_error};
+
+pub mod config;
+
+pub use config::Config;
+use config::{EnforceAction, MediaEnforceConfig};
 
 pub struct Module;
 
@@ -13,18 +21,38 @@ impl super::Module for Module {
     fn intents(&self, _config: &config::HBotConfig) -> GatewayIntents {
         GatewayIntents::MESSAGE_CONTENT
     }
-}
 
-pub type Config = HashMap<ChannelId, MediaChannelEntry>;
+    fn validate(&self, config: &config::HBotConfig) -> Result {
+        for (channel, entry) in &config.media_react {
+            anyhow::ensure!(
+                (TimeDelta::zero()..=MAX_DELAY).contains(&entry.delay),
+                "media_react delay for {channel} must be between 0 and 60 seconds",
+            );
+
+            if let Some(enforce) = &entry.enforce {
+                anyhow::ensure!(
+                    (TimeDelta::zero()..=MAX_GRACE).contains(&enforce.grace),
+                    "media_react enforce grace for {channel} must be between 0 and 10 minutes",
+                );
+            }
+        }
 
-#[derive(Debug, serde::Deserialize)]
-pub struct MediaChannelEntry {
-    pub emojis: Vec<StarboardEmoji>,
+        Ok(())
+    }
 }
 
+/// The longest a channel may delay its reactions.
+const MAX_DELAY: TimeDelta = TimeDelta::seconds(60);
+
+/// The longest grace period before enforcing media-only channels.
+const MAX_GRACE: TimeDelta = TimeDelta::minutes(10);
+
+/// How many reactions to add to one message at the same time.
+const MAX_CONCURRENT_REACTS: usize = 3;
+
 pub async fn message(ctx: Context, new_message: Message) {
     if let Err(why) = message_inner(ctx, new_message).await {
-        log::error!("Message handling failed: {why:?}");
+        log_event_error!(why, "Message handling failed");
     }
 }
 
@@ -41,69 +69,197 @@ async fn message_inner(ctx: Context, new_message: Message) -> Result {
     let data = ctx.data_ref::<HContextData>();
 
     // grab the config for the current channel
-    let channel_config = data.config().media_react.get(&new_message.channel_id);
+    let config = data.config();
+    let channel_config = config.media_react.get(&new_message.channel_id);
 
     let Some(channel_config) = channel_config else {
         return Ok(());
     };
 
-    // if there is an attachment or the content has media links,
-    // attach the emoji to the message
-    // CMBK: check message snapshots when forwarding is fully implemented
-    let has_media = !new_message.attachments.is_empty()
-        || new_message
-            .message_reference
-            .as_ref()
-            .is_some_and(|m| m.kind == MessageReferenceKind::Forward)
-        || has_media_content(&new_message.content);
+    // attachments and forwards count as media for every emoji that allows them
+    let has_attachments = has_attachments(&new_message);
+    let links: Vec<&str> = media_links(&new_message.content).collect();
+    if !has_attachments && links.is_empty() {
+        if let Some(enforce) = &channel_config.enforce {
+            enforce_media(&ctx, enforce, &new_message).await?;
+        }
 
-    if !has_media {
         return Ok(());
     }
 
-    for emoji in &channel_config.emojis {
-        new_message
-            .react(&ctx.http, emoji.as_emoji().clone())
-            .await?;
+    // find every emoji whose check matches the media
+    let emojis: Vec<_> = channel_config
+        .emojis
+        .iter()
+        .filter(|e| {
+            (has_attachments && e.check.attachments)
+                || links.iter().any(|l| e.check.is_link_match(l))
+        })
+        .map(|e| e.emoji.as_emoji())
+        .collect();
+
+    if emojis.is_empty() {
+        return Ok(());
+    }
+
+    // give people a moment to delete messages they didn't mean to send,
+    // then make sure the message is still there
+    if channel_config.delay > TimeDelta::zero() {
+        tokio::time::sleep(channel_config.delay.to_std()?).await;
+
+        let result = new_message
+            .channel_id
+            .message(&ctx.http, new_message.id)
+            .await;
+
+        if is_unknown_message(&result) {
+            return Ok(());
+        }
+
+        result?;
+    }
+
+    let mut reacts = stream::iter(emojis)
+        .map(|emoji| new_message.react(&ctx.http, emoji.clone()))
+        .buffer_unordered(MAX_CONCURRENT_REACTS);
+
+    while let Some(result) = reacts.next().await {
+        // the message may still be deleted while we're reacting
+        if is_unknown_message(&result) {
+            return Ok(());
+        }
+
+        result?;
     }
 
     Ok(())
 }
 
+/// Deletes or flags a message without media once the grace period is over.
+///
+/// The author is notified via DM, if possible.
+async fn enforce_media(ctx: &Context, enforce: &MediaEnforceConfig, message: &Message) -> Result {
+    tokio::time::sleep(enforce.grace.to_std()?).await;
+
+    // the author may have deleted the message or edited in media by now
+    let result = message.channel_id.message(&ctx.http, message.id).await;
+    if is_unknown_message(&result) {
+        return Ok(());
+    }
+
+    let message = result?;
+    if has_attachments(&message) || media_links(&message.content).next().is_some() {
+        return Ok(());
+    }
+
+    match enforce.action {
+        EnforceAction::Delete => {
+            let result = message
+                .channel_id
+                .delete_message(&ctx.http, message.id, Some("media-only channel"))
+                .await;
+
+            if is_unknown_message(&result) {
+                return Ok(());
+            }
+
+            result?;
+        },
+        EnforceAction::Flag => {
+            let result = message.react(&ctx.http, '⚠').await;
+            if is_unknown_message(&result) {
+                return Ok(());
+            }
+
+            result?;
+        },
+    }
+
+    log::trace!(
+        "Enforced media-only in {} on message {} by {}.",
+        message.channel_id,
+        message.id,
+        message.author.id
+    );
+
+    let notice = enforce.notice();
+    if notice.is_empty() {
+        return Ok(());
+    }
+
+    let notice = replace_holes(notice, |out, n| match n {
+        "user" => write_str!(out, "<@{}>", message.author.id),
+        "channel" => write_str!(out, "<#{}>", message.channel_id),
+        "link" => write_str!(out, "{}", message.link()),
+        _ => out.push(char::REPLACEMENT_CHARACTER),
+    });
+
+    let embed = CreateEmbed::new()
+        .description(notice)
+        .color(ctx.data_ref::<HContextData>().config().embed_color);
+
+    let dm = CreateMessage::new().embed(embed);
+
+    // users may have their DMs closed, which is fine
+    if let Err(why) = message.author.id.direct_message(&ctx.http, dm).await {
+        log::trace!("Could not DM {} media notice: {why:?}", message.author.id);
+    }
+
+    Ok(())
+}
+
+/// Checks whether a message has attachments or forwards another message.
+fn has_attachments(message: &Message) -> bool {
+    // CMBK: check message snapshots when forwarding is fully implemented
+    !message.attachments.is_empty()
+        || message
+            .message_reference
+            .as_ref()
+            .is_some_and(|m| m.kind == MessageReferenceKind::Forward)
+}
+
 fn is_normal_message(kind: MessageType) -> bool {
     matches!(kind, MessageType::Regular | MessageType::InlineReply)
 }
 
-fn has_media_content(content: &str) -> bool {
-    fn includes_media_link(content: &str, prefix: &str) -> bool {
-        content
-            .match_indices(prefix)
-            .any(|(index, _)| is_media_link_match(content, prefix, index))
-    }
-
-    fn is_media_link_match(content: &str, prefix: &str, index: usize) -> bool {
+/// Finds all links in the content that may point to media.
+///
+/// Masked links and links to Discord itself are ignored.
+fn media_links(content: &str) -> impl Iterator<Item = &str> {
+    fn media_link_at(content: &str, index: usize) -> Option<&str> {
         // if a '<' comes first, this is masked and we ignore it
         if index != 0 && content.as_bytes()[index - 1] == b'<' {
-            return false;
+            return None;
         }
 
-        // cut out the link itself, without the schema
-        let index = index + prefix.len();
-        let Some(content) = content.get(index..) else {
-            return false;
-        };
+        // cut out the link itself, up to the next whitespace
+        let link = content.get(index..)?;
+        let link = link
+            .find(char::is_whitespace)
+            .map_or(link, |end| &link[..end]);
+
+        let rest = link
+            .strip_prefix("https://")
+            .or_else(|| link.strip_prefix("http://"))?;
 
         // ignore certain domains
         // cdn links would be `cdn.discord.com`, so those should be unaffected
-        !content.starts_with("discord.com") && !content.starts_with("discord.gg")
+        let is_media = !rest.starts_with("discord.com") && !rest.starts_with("discord.gg");
+        is_media.then_some(link)
     }
 
-    includes_media_link(content, "http://") || includes_media_link(content, "https://")
+    content
+        .match_indices("http")
+        .filter_map(|(index, _)| media_link_at(content, index))
 }
 
 #[cfg(test)]
 mod tests {
-    use super::has_media_content;
+    use super::media_links;
+
+    fn has_media_content(content: &str) -> bool {
+        media_links(content).next().is_some()
+    }
 
     #[test]
     fn has_media() {
@@ -130,4 +286,11 @@ mod tests {
         ));
         assert!(!has_media_content("https://discord.gg/invite/abcdef"));
     }
+
+    #[test]
+    fn media_link_bounds() {
+        let links: Vec<_> =
+            media_links("see https://a.com/x.png and http://b.com/y <https://c.com>").collect();
+        assert_eq!(links, ["https://a.com/x.png", "http://b.com/y"]);
+    }
 }
@@ -0,0 +1,557 @@
+//! ## Blackjack against the house.
+//!
+//! Cards are drawn from an infinite shoe at the time they are needed, so the
+//! button state never holds any cards that haven't been revealed yet. In turn,
+//! the dealer only draws their second card once the player stands.
+//!
+//! The dealer stands on all 17s. Blackjack pays 3:2.
+//!
+//! Wagers are stored in the database until they are paid out. Every action on
+//! a game with a wager claims it first, so double-clicks and concurrent
+//! actions can't take or pay out cash twice. Games left idle for too long are
+//! closed by a scheduled job, which refunds the stake.
+
+use std::fmt;
+
+use arrayvec::ArrayVec;
+use bson::oid::ObjectId;
+use chrono::{DateTime, TimeDelta, Utc};
+use rand::prelude::*;
+use serenity::prelude::Context;
+use utils::text::write_str::*;
+
+use crate::buttons::prelude::*;
+use crate::helper::discord::id_as_u64;
+use crate::modules::minigame::model::{BlackjackWager, BlackjackWagerExt as _};
+use crate::modules::minigame::stats::{self, GameKind};
+use crate::modules::perks::model::{TxReason, Wallet, WalletExt as _};
+use crate::modules::perks::Item;
+use crate::modules::scheduler::{self, JobKind};
+
+/// How long an action keeps a game claimed if it never releases it, f.e.
+/// because the bot stopped.
+const CLAIM_LEASE: TimeDelta = TimeDelta::minutes(1);
+
+/// How long a game with a wager may sit idle before its stake is refunded.
+const WAGER_EXPIRY: TimeDelta = TimeDelta::hours(1);
+
+// every card is worth at least 1, so no hand can have more than 21 cards
+// before the game ends. this is all but impossible, but it's not a crash.
+type Hand = ArrayVec<Card, 21>;
+
+#[derive(Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct View {
+    #[serde(with = "id_as_u64")]
+    user: UserId,
+    wager: Option<Wager>,
+    player: Hand,
+    dealer: Hand,
+    doubled: bool,
+    action: Action,
+}
+
+utils::impl_debug!(struct View { user, wager, action, .. });
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+struct Wager {
+    #[serde(with = "id_as_u64")]
+    guild: GuildId,
+    amount: u32,
+    game_id: i64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+enum Action {
+    Idle,
+    Hit,
+    Stand,
+    Double,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+struct Card(u8);
+
+impl Card {
+    fn draw() -> Self {
+        Self(thread_rng().gen_range(0..52))
+    }
+
+    fn rank(self) -> u8 {
+        self.0 % 13 + 1
+    }
+}
+
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const SUITS: [char; 4] = ['♠', '♥', '♦', '♣'];
+        let suit = SUITS[usize::from(self.0 / 13) % SUITS.len()];
+        match self.rank() {
+            1 => write!(f, "`A{suit}`"),
+            11 => write!(f, "`J{suit}`"),
+            12 => write!(f, "`Q{suit}`"),
+            13 => write!(f, "`K{suit}`"),
+            rank => write!(f, "`{rank}{suit}`"),
+        }
+    }
+}
+
+/// Gets the best value of a hand, counting aces as 11 where that doesn't bust.
+fn hand_value(hand: &[Card]) -> u8 {
+    let mut total = 0u8;
+    let mut aces = 0u8;
+    for card in hand {
+        total += match card.rank() {
+            1 => {
+                aces += 1;
+                11
+            },
+            rank @ 2..=10 => rank,
+            _ => 10,
+        };
+    }
+
+    while total > 21 && aces > 0 {
+        total -= 10;
+        aces -= 1;
+    }
+
+    total
+}
+
+fn is_blackjack(hand: &[Card]) -> bool {
+    hand.len() == 2 && hand_value(hand) == 21
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Outcome {
+    Bust,
+    Lose,
+    Push,
+    Win,
+    Blackjack,
+}
+
+impl Outcome {
+    fn of(player: &[Card], dealer: &[Card]) -> Self {
+        let player_value = hand_value(player);
+        let dealer_value = hand_value(dealer);
+
+        if player_value > 21 {
+            return Self::Bust;
+        }
+
+        match (is_blackjack(player), is_blackjack(dealer)) {
+            (true, true) => Self::Push,
+            (true, false) => Self::Blackjack,
+            (false, true) => Self::Lose,
+            _ if dealer_value > 21 || player_value > dealer_value => Self::Win,
+            _ if player_value == dealer_value => Self::Push,
+            _ => Self::Lose,
+        }
+    }
+
+    /// Gets the cash paid back for a stake.
+    fn payout(self, stake: i64) -> i64 {
+        match self {
+            Self::Bust | Self::Lose => 0,
+            Self::Push => stake,
+            Self::Win => stake * 2,
+            Self::Blackjack => stake + stake * 3 / 2,
+        }
+    }
+
+    /// Gets the matching result for the stats.
+    fn result(self) -> stats::Outcome {
+        match self {
+            Self::Bust | Self::Lose => stats::Outcome::Loss,
+            Self::Push => stats::Outcome::Draw,
+            Self::Win | Self::Blackjack => stats::Outcome::Win,
+        }
+    }
+
+    fn title(self) -> &'static str {
+        match self {
+            Self::Bust => "Bust!",
+            Self::Lose => "The house wins!",
+            Self::Push => "Push!",
+            Self::Win => "You win!",
+            Self::Blackjack => "Blackjack!",
+        }
+    }
+}
+
+impl View {
+    /// Starts a new game, taking the wager from the user's wallet first.
+    ///
+    /// If the player is dealt a blackjack, the game ends right away.
+    pub async fn start(
+        data: &HBotData,
+        user: UserId,
+        wager: Option<(GuildId, u32)>,
+    ) -> Result<CreateReply<'_>> {
+        let wager = match wager {
+            Some((guild, amount)) => {
+                let config = data.config();
+                let perks = config.perks()?;
+                let db = data.database()?;
+                let wagers = BlackjackWager::collection(db);
+
+                // record the wager first so the stake is never taken for a game
+                // that doesn't exist
+                let game_id = thread_rng().gen();
+                wagers
+                    .insert_one(BlackjackWager {
+                        _id: ObjectId::new(),
+                        game: game_id,
+                        guild,
+                        user,
+                        stake: amount.into(),
+                        claimed_until: Utc::now(),
+                    })
+                    .await?;
+
+                // if taking the stake fails, the job finds nothing to refund
+                let job = JobKind::BlackjackExpire { game: game_id };
+                scheduler::schedule(db, &job, Utc::now() + WAGER_EXPIRY).await?;
+
+                let taken = Wallet::collection(db)
+                    .take_items(
+                        guild,
+                        user,
+                        Item::Cash,
+                        amount.into(),
+                        TxReason::new("minigame", "blackjack wager"),
+                        perks,
+                    )
+                    .await;
+
+                if let Err(why) = taken {
+                    wagers.close(game_id).await?;
+                    return Err(why);
+                }
+
+                Some(Wager {
+                    guild,
+                    amount,
+                    game_id,
+                })
+            },
+            None => None,
+        };
+
+        let this = Self {
+            user,
+            wager,
+            player: [Card::draw(), Card::draw()].into_iter().collect(),
+            dealer: [Card::draw()].into_iter().collect(),
+            doubled: false,
+            action: Action::Idle,
+        };
+
+        if is_blackjack(&this.player) {
+            return this.finish(data, Utc::now()).await;
+        }
+
+        Ok(this.create_next_reply(data))
+    }
+
+    fn stake(&self) -> i64 {
+        let amount = self.wager.as_ref().map_or(0, |w| i64::from(w.amount));
+        if self.doubled {
+            amount * 2
+        } else {
+            amount
+        }
+    }
+
+    fn hands_text(&self) -> String {
+        let mut text = String::new();
+
+        write_str!(text, "**Dealer** `{}`\n", hand_value(&self.dealer));
+        for card in &self.dealer {
+            write_str!(text, "{card} ");
+        }
+
+        write_str!(
+            text,
+            "\n**<@{}>** `{}`\n",
+            self.user,
+            hand_value(&self.player)
+        );
+        for card in &self.player {
+            write_str!(text, "{card} ");
+        }
+
+        text
+    }
+
+    fn wager_text(&self, data: &HBotData) -> Option<String> {
+        let config = data.config();
+        let perks = config.perks.as_ref()?;
+        self.wager
+            .is_some()
+            .then(|| format!("-# Wager: {} {}", self.stake(), Item::Cash.info(perks).name))
+    }
+
+    fn create_next_reply(mut self, data: &HBotData) -> CreateReply<'_> {
+        let mut description = self.hands_text();
+        if let Some(wager) = self.wager_text(data) {
+            write_str!(description, "\n{wager}");
+        }
+
+        let mut row = vec![
+            self.new_button(|s| &mut s.action, Action::Hit, |_| 0)
+                .label("Hit")
+                .style(ButtonStyle::Primary),
+            self.new_button(|s| &mut s.action, Action::Stand, |_| 1)
+                .label("Stand")
+                .style(ButtonStyle::Secondary),
+        ];
+
+        if self.player.len() == 2 {
+            row.push(
+                self.new_button(|s| &mut s.action, Action::Double, |_| 2)
+                    .label("Double")
+                    .style(ButtonStyle::Secondary),
+            );
+        }
+
+        let embed = CreateEmbed::new()
+            .title("Blackjack")
+            .description(description)
+            .color(data.config().embed_color);
+
+        CreateReply::new()
+            .embed(embed)
+            .components(vec![CreateActionRow::buttons(row)])
+    }
+
+    /// Plays out the dealer's hand and pays out the wager.
+    async fn finish(mut self, data: &HBotData, started: DateTime<Utc>) -> Result<CreateReply<'_>> {
+        // the dealer doesn't need to draw if the player already lost
+        if hand_value(&self.player) <= 21 {
+            while hand_value(&self.dealer) < 17 && !self.dealer.is_full() {
+                self.dealer.push(Card::draw());
+            }
+        }
+
+        let outcome = Outcome::of(&self.player, &self.dealer);
+        let mut payout = 0;
+
+        if let Some(wager) = &self.wager {
+            let db = data.database()?;
+            let closed = BlackjackWager::collection(db)
+                .close(wager.game_id)
+                .await?
+                .ok_or(HArgError::new_const("This game is already over."))?;
+
+            // pay out what was actually taken, not what the buttons claim
+            payout = outcome.payout(closed.stake);
+            if payout != 0 {
+                Wallet::collection(db)
+                    .add_items(
+                        wager.guild,
+                        self.user,
+                        Item::Cash,
+                        payout,
+                        TxReason::new("minigame", "blackjack payout"),
+                    )
+                    .await?;
+            }
+        }
+
+        let result = outcome.result();
+        stats::record_solo(data, GameKind::Blackjack, self.user, result, started).await;
+
+        let mut description = format!("## {}\n{}", outcome.title(), self.hands_text());
+        if let Some(wager) = self.wager_text(data) {
+            write_str!(description, "\n{wager} \u{2E31} Payout: {payout}");
+        }
+
+        let embed = CreateEmbed::new()
+            .title("Blackjack")
+            .description(description)
+            .color(data.config().embed_color);
+
+        Ok(CreateReply::new().embed(embed).components(vec![]))
+    }
+}
+
+impl View {
+    /// Plays the chosen action and creates the reply.
+    ///
+    /// If the game has a wager, it must already be claimed.
+    async fn play<'a>(
+        mut self,
+        data: &'a HBotData,
+        started: DateTime<Utc>,
+    ) -> Result<CreateReply<'a>> {
+        let reply = match self.action {
+            Action::Idle => self.create_next_reply(data),
+            Action::Hit => {
+                self.action = Action::Idle;
+                self.player.push(Card::draw());
+
+                if hand_value(&self.player) >= 21 {
+                    self.finish(data, started).await?
+                } else {
+                    self.create_next_reply(data)
+                }
+            },
+            Action::Stand => self.finish(data, started).await?,
+            Action::Double => {
+                anyhow::ensure!(
+                    self.player.len() == 2,
+                    HArgError::new_const("You can only double down on your first two cards.")
+                );
+
+                if let Some(wager) = &self.wager {
+                    let config = data.config();
+                    let perks = config.perks()?;
+                    let db = data.database()?;
+
+                    Wallet::collection(db)
+                        .take_items(
+                            wager.guild,
+                            self.user,
+                            Item::Cash,
+                            wager.amount.into(),
+                            TxReason::new("minigame", "blackjack double down"),
+                            perks,
+                        )
+                        .await?;
+
+                    BlackjackWager::collection(db)
+                        .add_stake(wager.game_id, wager.amount.into())
+                        .await?;
+                }
+
+                self.doubled = true;
+                self.player.push(Card::draw());
+                self.finish(data, started).await?
+            },
+        };
+
+        Ok(reply)
+    }
+}
+
+impl ButtonArgsReply for View {
+    async fn reply(self, ctx: ButtonContext<'_>) -> Result {
+        anyhow::ensure!(
+            ctx.interaction.user.id == self.user,
+            HArgError::new_const("This isn't your game.")
+        );
+
+        // claim the game before any cash moves, so that f.e. double-clicking
+        // "Double" can't take the stake twice
+        let game_id = self.wager.as_ref().map(|w| w.game_id);
+        if let Some(game_id) = game_id {
+            let db = ctx.data.database()?;
+            let now = Utc::now();
+            let claimed = BlackjackWager::collection(db)
+                .claim(game_id, now, now + CLAIM_LEASE)
+                .await?;
+
+            anyhow::ensure!(claimed, HArgError::new_const("This game is already over."));
+        }
+
+        let started = super::started_at(&ctx);
+        let reply = self.play(ctx.data, started).await;
+
+        // finished games are already closed, so this only releases games
+        // that are still running or where the action failed
+        if let Some(game_id) = game_id {
+            let db = ctx.data.database()?;
+            BlackjackWager::collection(db).release(game_id).await?;
+        }
+
+        ctx.edit(reply?.into()).await
+    }
+}
+
+/// Refunds the stake of a game that has been idle for too long.
+///
+/// This is run by the scheduler. If the game was played in the meantime, it is
+/// scheduled again for when it would next expire.
+pub async fn expire_wager(ctx: &Context, game: i64) -> Result {
+    let data = ctx.data_ref::<HContextData>();
+    let db = data.database()?;
+    let wagers = BlackjackWager::collection(db);
+
+    let Some(wager) = wagers.take_expired(game, Utc::now() - WAGER_EXPIRY).await? else {
+        if let Some(wager) = wagers.find_game(game).await? {
+            let job = JobKind::BlackjackExpire { game };
+            scheduler::schedule(db, &job, wager.claimed_until + WAGER_EXPIRY).await?;
+        }
+
+        return Ok(());
+    };
+
+    Wallet::collection(db)
+        .add_items(
+            wager.guild,
+            wager.user,
+            Item::Cash,
+            wager.stake,
+            TxReason::new("minigame", "blackjack refund"),
+        )
+        .await?;
+
+    log::info!(
+        "Refunded {} to {} for idle blackjack game {game}.",
+        wager.stake,
+        wager.user
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hand(ranks: &[u8]) -> Vec<Card> {
+        ranks.iter().map(|r| Card(r - 1)).collect()
+    }
+
+    #[test]
+    fn hand_value_aces() {
+        assert_eq!(hand_value(&hand(&[1, 13])), 21);
+        assert_eq!(hand_value(&hand(&[1, 1])), 12);
+        assert_eq!(hand_value(&hand(&[1, 6, 10])), 17);
+        assert_eq!(hand_value(&hand(&[1, 1, 1, 1, 10, 10])), 24);
+    }
+
+    #[test]
+    fn outcomes() {
+        assert_eq!(
+            Outcome::of(&hand(&[10, 10, 5]), &hand(&[10])),
+            Outcome::Bust
+        );
+        assert_eq!(
+            Outcome::of(&hand(&[1, 12]), &hand(&[10, 7])),
+            Outcome::Blackjack
+        );
+        assert_eq!(Outcome::of(&hand(&[1, 12]), &hand(&[1, 11])), Outcome::Push);
+        assert_eq!(Outcome::of(&hand(&[10, 9]), &hand(&[1, 11])), Outcome::Lose);
+        assert_eq!(
+            Outcome::of(&hand(&[10, 9]), &hand(&[10, 6, 8])),
+            Outcome::Win
+        );
+        assert_eq!(Outcome::of(&hand(&[10, 7]), &hand(&[10, 7])), Outcome::Push);
+        assert_eq!(
+            Outcome::of(&hand(&[5, 5, 7]), &hand(&[10, 7])),
+            Outcome::Push
+        );
+        assert_eq!(Outcome::of(&hand(&[10, 6]), &hand(&[10, 7])), Outcome::Lose);
+    }
+
+    #[test]
+    fn payouts() {
+        assert_eq!(Outcome::Bust.payout(10), 0);
+        assert_eq!(Outcome::Push.payout(10), 10);
+        assert_eq!(Outcome::Win.payout(10), 20);
+        assert_eq!(Outcome::Blackjack.payout(10), 25);
+    }
+}
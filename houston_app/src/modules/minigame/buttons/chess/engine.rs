@@ -0,0 +1,115 @@
+//! A small alpha-beta search used for the bot's moves.
+//!
+//! The evaluation only counts material. Like the game itself, a player without
+//! any legal move loses, whether they are in check or not.
+
+use rand::prelude::*;
+
+use super::game::{is_home_row, Board, Piece, Pos};
+use super::Player;
+
+/// The score of a won position, well above any material difference.
+const WIN: i32 = 100_000;
+
+fn value(piece: Piece) -> i32 {
+    match piece {
+        Piece::Pawn => 100,
+        Piece::Knight | Piece::Bishop => 300,
+        Piece::Rook => 500,
+        Piece::Queen => 900,
+        Piece::King => 0,
+    }
+}
+
+/// Applies a move, promoting pawns that reach the enemy home row.
+pub fn make_move(board: &mut Board, src: Pos, dst: Pos) {
+    let mut tile = board.get_mut(src).and_then(Option::take);
+    if let Some(tile) = &mut tile {
+        if tile.piece == Piece::Pawn && is_home_row(dst, tile.player.next()) {
+            tile.piece = Piece::Queen;
+        }
+    }
+
+    if let Some(slot) = board.get_mut(dst) {
+        *slot = tile;
+    }
+}
+
+/// Gets all moves that don't leave the player's own king in check.
+fn legal_moves(board: &Board, player: Player) -> Vec<(Pos, Pos)> {
+    let mut moves = Vec::new();
+    if board.king_at(player).is_none() {
+        return moves;
+    }
+
+    for (src, piece) in board.iter_pieces(player) {
+        let mask = piece.get_move().target_mask(board, src, player);
+        for dst in mask.iter_true() {
+            let mut next = *board;
+            make_move(&mut next, src, dst);
+
+            let king_at = next.king_at(player).expect("king must still be there");
+            if !next.is_player_in_check(player, king_at) {
+                moves.push((src, dst));
+            }
+        }
+    }
+
+    moves
+}
+
+/// Counts the material from the view of `player`.
+fn evaluate(board: &Board, player: Player) -> i32 {
+    let own: i32 = board.iter_pieces(player).map(|t| value(t.1)).sum();
+    let other: i32 = board.iter_pieces(player.next()).map(|t| value(t.1)).sum();
+    own - other
+}
+
+/// Scores the board from the view of `player`, who is about to move.
+fn search(board: &Board, player: Player, depth: i32, mut alpha: i32, beta: i32) -> i32 {
+    let moves = legal_moves(board, player);
+    if moves.is_empty() {
+        // losing later is better than losing right away
+        return -WIN - depth;
+    }
+
+    if depth == 0 {
+        return evaluate(board, player);
+    }
+
+    for (src, dst) in moves {
+        let mut next = *board;
+        make_move(&mut next, src, dst);
+
+        let score = -search(&next, player.next(), depth - 1, -beta, -alpha);
+        alpha = alpha.max(score);
+        if alpha >= beta {
+            break;
+        }
+    }
+
+    alpha
+}
+
+/// Finds the best move for `player`, looking `depth` moves ahead.
+///
+/// Among equally good moves, a random one is picked.
+pub fn best_move(board: &Board, player: Player, depth: i32) -> Option<(Pos, Pos)> {
+    let mut moves = legal_moves(board, player);
+    moves.shuffle(&mut thread_rng());
+
+    let mut best = None;
+    let mut alpha = -WIN * 2;
+    for (src, dst) in moves {
+        let mut next = *board;
+        make_move(&mut next, src, dst);
+
+        let score = -search(&next, player.next(), depth - 1, -WIN * 2, -alpha);
+        if best.is_none() || score > alpha {
+            best = Some((src, dst));
+            alpha = score;
+        }
+    }
+
+    best
+}
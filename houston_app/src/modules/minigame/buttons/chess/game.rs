@@ -375,7 +375,7 @@ impl<D: MoveDirs> Move for D {
 }
 
 impl Grid<bool> {
-    fn iter_true(&self) -> impl Iterator<Item = Pos> + use<'_> {
+    pub fn iter_true(&self) -> impl Iterator<Item = Pos> + use<'_> {
         self.iter_grid().filter(|t| *t.1).map(|t| t.0)
     }
 }
@@ -415,7 +415,7 @@ impl Board {
         true
     }
 
-    fn iter_pieces(&self, player: Player) -> impl Iterator<Item = (Pos, Piece)> + use<'_> {
+    pub fn iter_pieces(&self, player: Player) -> impl Iterator<Item = (Pos, Piece)> + use<'_> {
         self.array.iter().enumerate().flat_map(move |(x, row)| {
             row.iter()
                 .enumerate()
@@ -17,9 +17,14 @@
 
 use std::ptr;
 
-use super::{Player, PlayerState};
+use utils::text::write_str::*;
+
+use super::timeout::{self, TimedGame};
+use super::{Difficulty, Player, PlayerState};
 use crate::buttons::prelude::*;
+use crate::modules::minigame::stats::GameKind;
 
+mod engine;
 mod game;
 #[cfg(test)]
 mod tests;
@@ -49,8 +54,17 @@ fn flat_index(pos: Pos) -> u16 {
 
 impl View {
     pub fn new(players: [UserId; 2]) -> Self {
-        Self {
-            players: PlayerState::new(players),
+        Self::with_players(PlayerState::new(players))
+    }
+
+    /// Creates a game of `user` against the bot.
+    pub fn new_vs_bot(user: UserId, bot_user: UserId, difficulty: Difficulty) -> Self {
+        Self::with_players(PlayerState::vs_bot(user, bot_user, difficulty))
+    }
+
+    pub(super) fn with_players(players: PlayerState) -> Self {
+        let mut this = Self {
+            players,
             action: Action::Idle,
             board: new_board!(
                 [b R, b k, b B, b Q, b K]
@@ -59,7 +73,43 @@ impl View {
                 [w p, w p, w p, w p, w p]
                 [w R, w k, w B, w Q, w K]
             ),
+        };
+
+        // the game can't be over after a single move
+        this.play_bot_turn();
+        this
+    }
+
+    pub(super) fn players(&self) -> &PlayerState {
+        &self.players
+    }
+
+    /// Makes the bot's move if it is its turn.
+    ///
+    /// Returns whether the bot won with that move. Otherwise, the turn passes
+    /// back to the user.
+    fn play_bot_turn(&mut self) -> bool {
+        let Some(difficulty) = self.players.bot_turn() else {
+            return false;
+        };
+
+        let depth = match difficulty {
+            Difficulty::Easy => 1,
+            Difficulty::Normal => 2,
+            Difficulty::Hard => 4,
+        };
+
+        let Some((src, dst)) = engine::best_move(&self.board, self.players.turn, depth) else {
+            return false;
+        };
+
+        engine::make_move(&mut self.board, src, dst);
+        if self.is_inactive_player_in_checkmate() {
+            return true;
         }
+
+        self.players.next_turn();
+        false
     }
 
     fn board_buttons<'a>(&mut self, data: &'a HBotData) -> Vec<CreateActionRow<'a>> {
@@ -118,11 +168,14 @@ impl View {
         components
     }
 
-    fn no_act_board_buttons<'a>(&self, data: &'a HBotData) -> Vec<CreateActionRow<'a>> {
-        let mut components = Vec::with_capacity(5);
+    /// Draws the board into text.
+    ///
+    /// Used once the game is over, since the board buttons would leave no room
+    /// for any other components.
+    fn board_text(&self, data: &HBotData) -> String {
+        let mut text = String::new();
 
         for y in 0..N {
-            let mut row = Vec::with_capacity(5);
             for x in 0..N {
                 let pos = Pos::new_trunc(x, y);
                 let tile = self.board.get(pos).expect("must be in range");
@@ -132,21 +185,13 @@ impl View {
                     None => data.app_emojis().empty(),
                 };
 
-                row.push({
-                    use crate::modules::core::buttons::None;
-
-                    let value = flat_index(pos);
-                    CreateButton::new(None::new(0, value).to_custom_id())
-                        .disabled(true)
-                        .emoji(icon.clone())
-                        .style(ButtonStyle::Secondary)
-                });
+                write_str!(text, "{icon}");
             }
 
-            components.push(CreateActionRow::buttons(row));
+            text.push('\n');
         }
 
-        components
+        text
     }
 
     fn is_active_player_in_check(&self) -> bool {
@@ -190,16 +235,42 @@ impl View {
         let description = format!(
             "## <@{winner_id}> wins!\n\
              -# ⬜ <@{p1}>\n\
-             -# ⬛ <@{p2}>",
+             -# ⬛ <@{p2}>\n\n\
+             {board}",
             p1 = self.players.p1,
             p2 = self.players.p2,
+            board = self.board_text(data),
         );
 
         let embed = CreateEmbed::new()
             .description(description)
             .color(data.config().embed_color);
 
-        let components = self.no_act_board_buttons(data);
+        let components = vec![self.players.rematch_row(GameKind::Chess)];
+
+        CreateReply::new().embed(embed).components(components)
+    }
+
+    /// Creates the reply for when the active player ran out of time.
+    pub(super) fn create_timeout_reply(self, data: &HBotData) -> CreateReply<'_> {
+        let description = format!(
+            "## <@{winner}> wins!\n\
+             <@{loser}> ran out of time.\n\
+             -# ⬜ <@{p1}>\n\
+             -# ⬛ <@{p2}>\n\n\
+             {board}",
+            winner = self.players.user_id(self.players.turn.next()),
+            loser = self.players.turn_user_id(),
+            p1 = self.players.p1,
+            p2 = self.players.p2,
+            board = self.board_text(data),
+        );
+
+        let embed = CreateEmbed::new()
+            .description(description)
+            .color(data.config().embed_color);
+
+        let components = vec![self.players.rematch_row(GameKind::Chess)];
 
         CreateReply::new().embed(embed).components(components)
     }
@@ -237,14 +308,31 @@ impl ButtonArgsReply for View {
 
             // check for checkmate
             if self.is_inactive_player_in_checkmate() {
+                let winner = Some(self.players.turn);
+                timeout::untrack_interacted(&ctx).await;
+                self.players
+                    .record_result(&ctx, GameKind::Chess, winner)
+                    .await;
+
                 let reply = self.create_win_reply(ctx.data);
                 return ctx.edit(reply.into()).await;
             }
 
             self.action = Action::Idle;
             self.players.next_turn();
+
+            if self.play_bot_turn() {
+                timeout::untrack_interacted(&ctx).await;
+                self.players
+                    .record_result(&ctx, GameKind::Chess, Some(self.players.turn))
+                    .await;
+
+                let reply = self.create_win_reply(ctx.data);
+                return ctx.edit(reply.into()).await;
+            }
         }
 
+        timeout::track_interacted(&ctx, TimedGame::Chess(self.clone())).await;
         let reply = self.create_next_reply(ctx.data);
         ctx.edit(reply.into()).await
     }
@@ -359,3 +359,42 @@ fn knight_moves() {
         mask2
     );
 }
+
+#[test]
+fn engine_finds_mate() {
+    let mut board = new_board!(
+        [- -, - -, - -, - -, b K]
+        [- -, - -, - -, - -, - -]
+        [- -, - -, - -, - -, - -]
+        [- -, - -, w Q, - -, - -]
+        [w K, - -, - -, w R, - -]
+    );
+
+    let (src, dst) = engine::best_move(&board, Player::P1, 1).expect("must have a move");
+    engine::make_move(&mut board, src, dst);
+
+    let king_at = board.king_at(Player::P2).expect("king present");
+    assert!(
+        board.is_player_in_checkmate(Player::P2, king_at),
+        "must be checkmated"
+    )
+}
+
+#[test]
+fn engine_takes_queen() {
+    let mut board = new_board!(
+        [- -, b K, - -, - -, - -]
+        [- -, - -, - -, - -, - -]
+        [b Q, - -, - -, - -, w R]
+        [- -, - -, - -, - -, - -]
+        [- -, - -, - -, - -, w K]
+    );
+
+    let (src, dst) = engine::best_move(&board, Player::P1, 2).expect("must have a move");
+    engine::make_move(&mut board, src, dst);
+
+    assert!(
+        board.iter_pieces(Player::P2).all(|t| t.1 != Piece::Queen),
+        "must capture the queen"
+    )
+}
@@ -0,0 +1,446 @@
+//! ## Single-player Minesweeper.
+//!
+//! The board is drawn into the embed and cells are picked from select menus,
+//! since an 8x8 board doesn't fit into buttons. A button toggles between
+//! revealing cells and flagging them.
+//!
+//! Mines aren't stored directly. Instead, they are derived from a seed and the
+//! first revealed cell, which along with its neighbors never holds a mine.
+
+use utils::text::write_str::*;
+
+use super::{splitmix64, GOLDEN_GAMMA};
+use crate::buttons::prelude::*;
+use crate::helper::discord::{create_string_select_menu_row, id_as_u64};
+use crate::modules::minigame::stats::{self, GameKind, Outcome};
+
+#[derive(Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct View {
+    #[serde(with = "id_as_u64")]
+    user: UserId,
+    size: Size,
+    seed: u64,
+    start: Option<u8>,
+    revealed: u64,
+    flagged: u64,
+    flag_mode: bool,
+    action: Action,
+}
+
+utils::impl_debug!(struct View { user, size, start, flag_mode, action, .. });
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize, houston_cmd::ChoiceArg,
+)]
+pub enum Size {
+    #[name = "5x5"]
+    Small,
+    #[name = "8x8"]
+    Large,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+enum Action {
+    Idle,
+    Reveal(u8),
+    Flag(u8),
+}
+
+impl Size {
+    /// The width and height of the board.
+    const fn len(self) -> u8 {
+        match self {
+            Self::Small => 5,
+            Self::Large => 8,
+        }
+    }
+
+    const fn cells(self) -> u8 {
+        self.len() * self.len()
+    }
+
+    const fn mine_count(self) -> u8 {
+        match self {
+            Self::Small => 4,
+            Self::Large => 10,
+        }
+    }
+
+    /// Iterates over the neighbors of a cell.
+    fn neighbors(self, cell: u8) -> impl Iterator<Item = u8> {
+        let n = self.len();
+        let (x, y) = (cell % n, cell / n);
+        let xs = x.saturating_sub(1)..=(x + 1).min(n - 1);
+        let ys = y.saturating_sub(1)..=(y + 1).min(n - 1);
+
+        ys.flat_map(move |ny| xs.clone().map(move |nx| ny * n + nx))
+            .filter(move |&c| c != cell)
+    }
+
+    /// Formats a cell's position, f.e. as "C4".
+    fn name(self, cell: u8) -> String {
+        let n = self.len();
+        format!("{}{}", char::from(b'A' + cell % n), cell / n + 1)
+    }
+}
+
+const fn bit(cell: u8) -> u64 {
+    1 << cell
+}
+
+/// Places the mines for a board whose first revealed cell is `start`.
+fn place_mines(size: Size, seed: u64, start: u8) -> u64 {
+    let safe = size.neighbors(start).fold(bit(start), |m, c| m | bit(c));
+    let mut candidates: Vec<u8> = (0..size.cells()).filter(|&c| safe & bit(c) == 0).collect();
+
+    let mut state = seed;
+    let mut mines = 0;
+    for _ in 0..size.mine_count() {
+        state = state.wrapping_add(GOLDEN_GAMMA);
+        let index = splitmix64(state) % candidates.len() as u64;
+        let index = usize::try_from(index).expect("index must be in range");
+        mines |= bit(candidates.swap_remove(index));
+    }
+
+    mines
+}
+
+impl View {
+    pub fn new(user: UserId, size: Size) -> Self {
+        use rand::prelude::*;
+
+        Self {
+            user,
+            size,
+            seed: thread_rng().gen(),
+            start: None,
+            revealed: 0,
+            flagged: 0,
+            flag_mode: false,
+            action: Action::Idle,
+        }
+    }
+
+    fn mines(&self) -> u64 {
+        self.start
+            .map_or(0, |start| place_mines(self.size, self.seed, start))
+    }
+
+    fn adjacent_mines(&self, mines: u64, cell: u8) -> usize {
+        self.size
+            .neighbors(cell)
+            .filter(|&c| mines & bit(c) != 0)
+            .count()
+    }
+
+    /// Reveals a cell, flood-filling across cells without adjacent mines.
+    fn reveal(&mut self, mines: u64, cell: u8) {
+        let mut stack = vec![cell];
+        while let Some(cell) = stack.pop() {
+            let b = bit(cell);
+            if (self.revealed | self.flagged) & b != 0 {
+                continue;
+            }
+
+            self.revealed |= b;
+            if mines & b == 0 && self.adjacent_mines(mines, cell) == 0 {
+                stack.extend(self.size.neighbors(cell));
+            }
+        }
+    }
+
+    fn is_won(&self) -> bool {
+        self.revealed.count_ones() == u32::from(self.size.cells() - self.size.mine_count())
+    }
+
+    /// Draws the board. Once the game is over, all mines are shown too.
+    fn board_text(&self, mines: u64, exploded: Option<u8>, game_over: bool) -> String {
+        let n = self.size.len();
+
+        // zero-width spaces keep the regional indicators from turning into flags
+        let mut text = "⬛".to_owned();
+        for x in 0..n {
+            text.push('\u{200B}');
+            text.push(char::from_u32(0x1F1E6 + u32::from(x)).expect("must be valid"));
+        }
+
+        for y in 0..n {
+            write_str!(text, "\n{}\u{FE0F}\u{20E3}", y + 1);
+            for x in 0..n {
+                let cell = y * n + x;
+                let b = bit(cell);
+                let is_mine = mines & b != 0;
+                let is_flagged = self.flagged & b != 0;
+
+                if exploded == Some(cell) {
+                    text.push('💥');
+                } else if game_over && is_mine && !is_flagged {
+                    text.push('💣');
+                } else if game_over && is_flagged && !is_mine {
+                    text.push('❌');
+                } else if is_flagged {
+                    text.push('🚩');
+                } else if self.revealed & b == 0 {
+                    text.push('🟦');
+                } else {
+                    match self.adjacent_mines(mines, cell) {
+                        0 => text.push('⬜'),
+                        count => write_str!(text, "{count}\u{FE0F}\u{20E3}"),
+                    }
+                }
+            }
+        }
+
+        text
+    }
+
+    pub fn create_next_reply(mut self, data: &HBotData) -> CreateReply<'_> {
+        let mines = self.mines();
+        let mut description = self.board_text(mines, None, false);
+
+        write_str!(
+            description,
+            "\n\n💣 {} \u{2E31} 🚩 {}\n-# Mode: {}",
+            self.size.mine_count(),
+            self.flagged.count_ones(),
+            if self.flag_mode { "Flag" } else { "Reveal" },
+        );
+
+        // only covered cells can be acted on, and flagged ones can't be revealed
+        let hidden = if self.flag_mode {
+            !self.revealed
+        } else {
+            !(self.revealed | self.flagged)
+        };
+
+        let cells: Vec<u8> = (0..self.size.cells())
+            .filter(|&c| hidden & bit(c) != 0)
+            .collect();
+
+        let mut components = Vec::new();
+        for (index, chunk) in (0u16..).zip(cells.chunks(25)) {
+            let options: Vec<_> = chunk
+                .iter()
+                .map(|&cell| {
+                    let name = self.size.name(cell);
+                    if !self.flag_mode {
+                        self.new_select_option(name, |s| &mut s.action, Action::Reveal(cell))
+                    } else if self.flagged & bit(cell) != 0 {
+                        self.new_select_option(
+                            format!("Unflag {name}"),
+                            |s| &mut s.action,
+                            Action::Flag(cell),
+                        )
+                    } else {
+                        self.new_select_option(
+                            format!("Flag {name}"),
+                            |s| &mut s.action,
+                            Action::Flag(cell),
+                        )
+                    }
+                })
+                .collect();
+
+            let placeholder = format!(
+                "{}: {} to {}",
+                if self.flag_mode { "Flag" } else { "Reveal" },
+                self.size.name(chunk[0]),
+                self.size.name(chunk[chunk.len() - 1]),
+            );
+
+            // every select needs its own custom ID
+            let custom_id = {
+                use crate::modules::core::buttons::None;
+
+                let key = std::ptr::from_ref(&self.action) as u16;
+                None::new(key, index).to_custom_id()
+            };
+
+            components.push(create_string_select_menu_row(
+                custom_id,
+                options,
+                placeholder,
+            ));
+        }
+
+        let toggle = self
+            .new_button(|s| &mut s.flag_mode, !self.flag_mode, u16::from)
+            .label(if self.flag_mode {
+                "Switch to Reveal"
+            } else {
+                "Switch to Flag"
+            })
+            .emoji(if self.flag_mode { '⛏' } else { '🚩' })
+            .style(ButtonStyle::Secondary);
+
+        components.push(CreateActionRow::buttons(vec![toggle]));
+
+        let embed = CreateEmbed::new()
+            .title("Minesweeper")
+            .description(description)
+            .color(data.config().embed_color);
+
+        CreateReply::new().embed(embed).components(components)
+    }
+
+    async fn record_result(&self, ctx: &ButtonContext<'_>, outcome: Outcome) {
+        let started = super::started_at(ctx);
+        stats::record_solo(ctx.data, GameKind::Minesweeper, self.user, outcome, started).await;
+    }
+
+    fn create_end_reply(
+        self,
+        data: &HBotData,
+        mines: u64,
+        exploded: Option<u8>,
+    ) -> CreateReply<'_> {
+        let title = if exploded.is_some() {
+            "Boom!"
+        } else {
+            "You win!"
+        };
+
+        let description = format!("## {title}\n{}", self.board_text(mines, exploded, true));
+
+        let embed = CreateEmbed::new()
+            .title("Minesweeper")
+            .description(description)
+            .color(data.config().embed_color);
+
+        CreateReply::new().embed(embed).components(vec![])
+    }
+}
+
+impl ButtonArgsReply for View {
+    async fn reply(mut self, ctx: ButtonContext<'_>) -> Result {
+        anyhow::ensure!(
+            ctx.interaction.user.id == self.user,
+            HArgError::new_const("This isn't your game.")
+        );
+
+        let action = self.action;
+        self.action = Action::Idle;
+
+        let reply = match action {
+            Action::Idle => self.create_next_reply(ctx.data),
+            Action::Flag(cell) => {
+                anyhow::ensure!(
+                    cell < self.size.cells() && self.revealed & bit(cell) == 0,
+                    HArgError::new_const("You can't flag that cell.")
+                );
+
+                self.flagged ^= bit(cell);
+                self.create_next_reply(ctx.data)
+            },
+            Action::Reveal(cell) => {
+                anyhow::ensure!(
+                    cell < self.size.cells() && self.flagged & bit(cell) == 0,
+                    HArgError::new_const("You can't reveal that cell.")
+                );
+
+                if self.start.is_none() {
+                    self.start = Some(cell);
+                }
+
+                let mines = self.mines();
+                if mines & bit(cell) != 0 {
+                    self.record_result(&ctx, Outcome::Loss).await;
+                    self.create_end_reply(ctx.data, mines, Some(cell))
+                } else {
+                    self.reveal(mines, cell);
+                    if self.is_won() {
+                        self.record_result(&ctx, Outcome::Win).await;
+                        self.create_end_reply(ctx.data, mines, None)
+                    } else {
+                        self.create_next_reply(ctx.data)
+                    }
+                }
+            },
+        };
+
+        ctx.edit(reply.into()).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn view(size: Size, start: u8) -> View {
+        let mut view = View::new(UserId::new(1), size);
+        view.start = Some(start);
+        view
+    }
+
+    #[test]
+    fn neighbors() {
+        let mut corner: Vec<_> = Size::Small.neighbors(0).collect();
+        corner.sort_unstable();
+        assert_eq!(corner, [1, 5, 6]);
+
+        assert_eq!(Size::Small.neighbors(12).count(), 8);
+        assert_eq!(Size::Large.neighbors(63).count(), 3);
+        // no wrapping to the next row
+        assert!(!Size::Small.neighbors(4).any(|c| c == 5));
+    }
+
+    #[test]
+    fn mines_avoid_start() {
+        for seed in 0..200 {
+            for size in [Size::Small, Size::Large] {
+                let start = size.cells() / 2;
+                let mines = place_mines(size, seed, start);
+
+                assert_eq!(mines.count_ones(), u32::from(size.mine_count()));
+                assert_eq!(mines & bit(start), 0, "start must be safe");
+                for cell in size.neighbors(start) {
+                    assert_eq!(mines & bit(cell), 0, "start neighbors must be safe");
+                }
+
+                assert_eq!(
+                    mines,
+                    place_mines(size, seed, start),
+                    "must be deterministic"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn reveal_flood_fills() {
+        let mut view = view(Size::Small, 12);
+        let mines = view.mines();
+        view.reveal(mines, 12);
+
+        // the start cell has no adjacent mines, so its neighbors open too
+        assert_ne!(view.revealed & bit(12), 0);
+        for cell in Size::Small.neighbors(12) {
+            assert_ne!(view.revealed & bit(cell), 0);
+        }
+
+        assert_eq!(view.revealed & mines, 0);
+    }
+
+    #[test]
+    fn reveal_stops_at_flags() {
+        let mut view = view(Size::Small, 12);
+        view.flagged = bit(13);
+        let mines = view.mines();
+        view.reveal(mines, 12);
+
+        assert_eq!(view.revealed & bit(13), 0);
+    }
+
+    #[test]
+    fn win_after_all_safe_cells() {
+        let mut view = view(Size::Large, 0);
+        let mines = view.mines();
+        for cell in 0..Size::Large.cells() {
+            if mines & bit(cell) == 0 {
+                view.reveal(mines, cell);
+            }
+        }
+
+        assert!(view.is_won());
+    }
+}
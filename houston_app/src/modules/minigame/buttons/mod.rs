@@ -1,14 +1,24 @@
 use std::fmt;
 
+use chrono::{DateTime, Utc};
+use serenity::builder::CreateActionRow;
 use serenity::model::id::UserId;
 
 use crate::buttons::ButtonContext;
-use crate::data::HArgError;
+use crate::data::{HArgError, HBotData};
 use crate::helper::discord::id_as_u64;
+use crate::modules::minigame::stats::{self, GameKind, Outcome};
 
+pub mod blackjack;
 pub mod chess;
+pub mod minesweeper;
+pub mod othello;
+pub mod rematch;
 pub mod rock_paper_scissors;
 pub mod tic_tac_toe;
+pub mod timeout;
+pub mod trivia;
+pub mod wordle;
 
 #[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
 enum Player {
@@ -25,6 +35,23 @@ impl Player {
     }
 }
 
+/// How well the bot plays.
+#[derive(
+    Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize, houston_cmd::ChoiceArg,
+)]
+pub enum Difficulty {
+    Easy,
+    Normal,
+    Hard,
+}
+
+/// The bot taking one of the players' places.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+struct Bot {
+    player: Player,
+    difficulty: Difficulty,
+}
+
 #[derive(Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 struct PlayerState {
     #[serde(with = "id_as_u64")]
@@ -32,17 +59,40 @@ struct PlayerState {
     #[serde(with = "id_as_u64")]
     p2: UserId,
     turn: Player,
+    bot: Option<Bot>,
 }
 
 impl PlayerState {
     fn new(players: [UserId; 2]) -> Self {
+        Self::with_bot(players, None)
+    }
+
+    fn with_bot(players: [UserId; 2], bot: Option<Bot>) -> Self {
         Self {
             p1: players[0],
             p2: players[1],
             turn: Player::P1,
+            bot,
         }
     }
 
+    /// Creates the state for a game of `user` against the bot, who goes second.
+    fn vs_bot(user: UserId, bot_user: UserId, difficulty: Difficulty) -> Self {
+        let bot = Bot {
+            player: Player::P2,
+            difficulty,
+        };
+
+        Self::with_bot([user, bot_user], Some(bot))
+    }
+
+    /// Gets the bot's difficulty if it is the bot's turn.
+    fn bot_turn(&self) -> Option<Difficulty> {
+        self.bot
+            .filter(|b| b.player == self.turn)
+            .map(|b| b.difficulty)
+    }
+
     fn next_turn(&mut self) {
         self.turn = self.turn.next();
     }
@@ -58,6 +108,43 @@ impl PlayerState {
         self.user_id(self.turn)
     }
 
+    /// Records the result of the game. No winner means it was a draw.
+    ///
+    /// Games against the bot aren't tracked.
+    async fn record_result(&self, ctx: &ButtonContext<'_>, kind: GameKind, winner: Option<Player>) {
+        self.record_result_at(ctx.data, kind, winner, started_at(ctx))
+            .await;
+    }
+
+    /// Records the result of a game that started at `started`.
+    async fn record_result_at(
+        &self,
+        data: &HBotData,
+        kind: GameKind,
+        winner: Option<Player>,
+        started: DateTime<Utc>,
+    ) {
+        if self.bot.is_some() {
+            return;
+        }
+
+        let outcome = match winner {
+            Some(Player::P1) => Outcome::Win,
+            Some(Player::P2) => Outcome::Loss,
+            None => Outcome::Draw,
+        };
+
+        let players = [self.p1, self.p2];
+        stats::record_versus(data, kind, players, outcome, started).await;
+    }
+
+    /// Creates the action row offering a rematch of this game.
+    fn rematch_row(&self, kind: GameKind) -> CreateActionRow<'static> {
+        rematch::Rematch::new(kind, [self.p1, self.p2])
+            .with_bot(self.bot)
+            .create_row()
+    }
+
     fn check_turn(&self, ctx: &ButtonContext<'_>) -> Result<(), HArgError> {
         let interacting = ctx.interaction.user.id;
         let current_turn = self.turn_user_id();
@@ -74,9 +161,36 @@ impl PlayerState {
 impl fmt::Debug for PlayerState {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         if self.turn == Player::P1 {
-            write!(f, "([{}] vs {})", self.p1, self.p2)
+            write!(f, "([{}] vs {})", self.p1, self.p2)?;
         } else {
-            write!(f, "({} vs [{}])", self.p1, self.p2)
+            write!(f, "({} vs [{}])", self.p1, self.p2)?;
+        }
+
+        if let Some(bot) = self.bot {
+            write!(f, " {:?} bot as {:?}", bot.difficulty, bot.player)?;
         }
+
+        Ok(())
     }
 }
+
+/// Gets when the game on the interacted message started.
+///
+/// Games are only ever edited into their original message, so this is just
+/// the message's creation time.
+fn started_at(ctx: &ButtonContext<'_>) -> DateTime<Utc> {
+    *ctx.interaction.message.timestamp
+}
+
+/// The increment of the `splitmix64` generator.
+const GOLDEN_GAMMA: u64 = 0x9E37_79B9_7F4A_7C15;
+
+/// Mixes the bits of a value with the `splitmix64` finalizer.
+///
+/// Unlike the generators in `rand`, this is guaranteed to stay the same across
+/// versions, so it's safe to derive persisted game state from it.
+fn splitmix64(mut z: u64) -> u64 {
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
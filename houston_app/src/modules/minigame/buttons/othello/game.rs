@@ -0,0 +1,182 @@
+//! Model and core game logic like legal moves and flipping.
+
+use std::fmt;
+
+use super::Player;
+
+/// The width and height of the board.
+pub const N: u8 = 8;
+
+/// The offsets to every neighboring tile.
+const DIRECTIONS: [(i8, i8); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Pos {
+    pub x: u8,
+    pub y: u8,
+}
+
+impl Pos {
+    pub const fn new(x: u8, y: u8) -> Self {
+        Self { x, y }
+    }
+
+    /// Iterates over all positions on the board, row by row.
+    pub fn all() -> impl Iterator<Item = Self> {
+        (0..N).flat_map(|y| (0..N).map(move |x| Self::new(x, y)))
+    }
+
+    fn bit(self) -> u64 {
+        debug_assert!(self.x < N && self.y < N, "pos out of range");
+        1 << (self.y * N + self.x)
+    }
+
+    fn offset(self, dx: i8, dy: i8) -> Option<Self> {
+        let x = self.x.checked_add_signed(dx)?;
+        let y = self.y.checked_add_signed(dy)?;
+        (x < N && y < N).then_some(Self::new(x, y))
+    }
+}
+
+impl fmt::Display for Pos {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", char::from(b'A' + self.x), self.y + 1)
+    }
+}
+
+/// The board, stored as one bit mask per player.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Board {
+    p1: u64,
+    p2: u64,
+}
+
+impl Board {
+    /// Creates a board with the starting layout.
+    ///
+    /// [`Player::P1`] plays black and moves first.
+    pub fn new() -> Self {
+        let mut board = Self::empty();
+        board.p2 = Pos::new(3, 3).bit() | Pos::new(4, 4).bit();
+        board.p1 = Pos::new(4, 3).bit() | Pos::new(3, 4).bit();
+        board
+    }
+
+    /// Creates an empty board.
+    pub const fn empty() -> Self {
+        Self { p1: 0, p2: 0 }
+    }
+
+    /// Gets the owner of the disc at a position, if any.
+    pub fn get(&self, pos: Pos) -> Option<Player> {
+        let bit = pos.bit();
+        if self.p1 & bit != 0 {
+            Some(Player::P1)
+        } else if self.p2 & bit != 0 {
+            Some(Player::P2)
+        } else {
+            None
+        }
+    }
+
+    /// Places a disc without flipping any others.
+    #[cfg(test)]
+    pub fn set(&mut self, pos: Pos, player: Player) {
+        let bit = pos.bit();
+        let (own, other) = self.masks_mut(player);
+        *own |= bit;
+        *other &= !bit;
+    }
+
+    /// Counts the discs a player holds.
+    pub fn count(&self, player: Player) -> u32 {
+        self.mask(player).count_ones()
+    }
+
+    /// Gets the mask of discs that would be flipped by the player placing a
+    /// disc at the position.
+    ///
+    /// The move is legal exactly when this is non-zero.
+    pub fn flips(&self, player: Player, pos: Pos) -> u64 {
+        if self.get(pos).is_some() {
+            return 0;
+        }
+
+        let own = self.mask(player);
+        let other = self.mask(player.next());
+
+        let mut result = 0;
+        for (dx, dy) in DIRECTIONS {
+            let mut line = 0;
+            let mut next = pos.offset(dx, dy);
+            while let Some(at) = next {
+                let bit = at.bit();
+                if other & bit != 0 {
+                    line |= bit;
+                    next = at.offset(dx, dy);
+                } else {
+                    // only flip the line if it is closed off by an own disc
+                    if own & bit != 0 {
+                        result |= line;
+                    }
+                    break;
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Iterates over the positions the player may place a disc at.
+    pub fn legal_moves(&self, player: Player) -> impl Iterator<Item = Pos> + use<'_> {
+        Pos::all().filter(move |&pos| self.flips(player, pos) != 0)
+    }
+
+    /// Whether the player has any legal move.
+    pub fn has_legal_move(&self, player: Player) -> bool {
+        self.legal_moves(player).next().is_some()
+    }
+
+    /// Places a disc for the player and flips the enclosed discs.
+    ///
+    /// Returns `false` and leaves the board unchanged if the move is illegal.
+    pub fn place(&mut self, player: Player, pos: Pos) -> bool {
+        let flips = self.flips(player, pos);
+        if flips == 0 {
+            return false;
+        }
+
+        let (own, other) = self.masks_mut(player);
+        *own |= flips | pos.bit();
+        *other &= !flips;
+        true
+    }
+
+    /// Whether neither player can move anymore.
+    pub fn is_game_over(&self) -> bool {
+        !self.has_legal_move(Player::P1) && !self.has_legal_move(Player::P2)
+    }
+
+    fn mask(&self, player: Player) -> u64 {
+        match player {
+            Player::P1 => self.p1,
+            Player::P2 => self.p2,
+        }
+    }
+
+    fn masks_mut(&mut self, player: Player) -> (&mut u64, &mut u64) {
+        match player {
+            Player::P1 => (&mut self.p1, &mut self.p2),
+            Player::P2 => (&mut self.p2, &mut self.p1),
+        }
+    }
+}
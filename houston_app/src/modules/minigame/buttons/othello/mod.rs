@@ -0,0 +1,246 @@
+//! ## Othello on the usual 8x8 board.
+//!
+//! Discord only allows 25 buttons per message, so the board is drawn into the
+//! embed and moves are chosen from a select menu instead.
+//!
+//! Black moves first. A player without a legal move has to pass, and the game
+//! ends once neither player can move. Whoever holds more discs then wins.
+
+use utils::text::write_str::*;
+
+use super::timeout::{self, TimedGame};
+use super::{Player, PlayerState};
+use crate::buttons::prelude::*;
+use crate::helper::discord::create_string_select_menu_row;
+use crate::modules::minigame::stats::GameKind;
+
+mod game;
+#[cfg(test)]
+mod tests;
+
+use game::{Board, Pos, N};
+
+#[derive(Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct View {
+    players: PlayerState,
+    board: Board,
+    action: Action,
+}
+
+utils::impl_debug!(struct View { players, action, .. });
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+enum Action {
+    Idle,
+    Place(Pos),
+    Pass,
+}
+
+const fn disc(player: Player) -> &'static str {
+    match player {
+        Player::P1 => "⚫",
+        Player::P2 => "⚪",
+    }
+}
+
+impl View {
+    pub fn new(players: [UserId; 2]) -> Self {
+        Self {
+            players: PlayerState::new(players),
+            board: Board::new(),
+            action: Action::Idle,
+        }
+    }
+
+    pub(super) fn players(&self) -> &PlayerState {
+        &self.players
+    }
+
+    /// Draws the board, highlighting the legal moves for `moves_for`.
+    fn board_text(&self, moves_for: Option<Player>) -> String {
+        // zero-width spaces keep the regional indicators from turning into flags
+        let mut text = "⬛".to_owned();
+        for x in 0..N {
+            text.push('\u{200B}');
+            text.push(char::from_u32(0x1F1E6 + u32::from(x)).expect("must be valid"));
+        }
+
+        for y in 0..N {
+            write_str!(text, "\n{}\u{FE0F}\u{20E3}", y + 1);
+            for x in 0..N {
+                let pos = Pos::new(x, y);
+                let tile = match self.board.get(pos) {
+                    Some(player) => disc(player),
+                    None if moves_for.is_some_and(|p| self.board.flips(p, pos) != 0) => "🟨",
+                    None => "🟩",
+                };
+
+                text.push_str(tile);
+            }
+        }
+
+        text
+    }
+
+    fn score_line(&self, player: Player) -> String {
+        format!(
+            "{} <@{}> `{}`",
+            disc(player),
+            self.players.user_id(player),
+            self.board.count(player),
+        )
+    }
+
+    pub fn create_next_reply(mut self, data: &HBotData) -> CreateReply<'_> {
+        let turn = self.players.turn;
+        let mut description = match turn {
+            Player::P1 => format!(
+                "> **{}**\n-# {}",
+                self.score_line(Player::P1),
+                self.score_line(Player::P2)
+            ),
+            Player::P2 => format!(
+                "-# {}\n> **{}**",
+                self.score_line(Player::P1),
+                self.score_line(Player::P2)
+            ),
+        };
+
+        write_str!(description, "\n\n{}", self.board_text(Some(turn)));
+
+        let moves: Vec<_> = self.board.legal_moves(turn).collect();
+        let component = if moves.is_empty() {
+            write_str!(
+                description,
+                "\n-# <@{}> has no legal moves and has to pass.",
+                self.players.turn_user_id()
+            );
+
+            let button = self
+                .new_button(|s| &mut s.action, Action::Pass, |_| 0)
+                .label("Pass")
+                .style(ButtonStyle::Primary);
+
+            CreateActionRow::buttons(vec![button])
+        } else {
+            // there are practically never more than 25 legal moves at once
+            let options: Vec<_> = moves
+                .into_iter()
+                .take(25)
+                .map(|pos| {
+                    let flips = self.board.flips(turn, pos).count_ones();
+                    self.new_select_option(pos.to_string(), |s| &mut s.action, Action::Place(pos))
+                        .description(format!("Flips {flips} disc(s)"))
+                })
+                .collect();
+
+            create_string_select_menu_row(self.to_custom_id(), options, "Place a disc...")
+        };
+
+        let embed = CreateEmbed::new()
+            .description(description)
+            .color(data.config().embed_color);
+
+        CreateReply::new().embed(embed).components(vec![component])
+    }
+
+    /// Gets the player holding more discs, if any.
+    fn winner(&self) -> Option<Player> {
+        let p1 = self.board.count(Player::P1);
+        let p2 = self.board.count(Player::P2);
+
+        match p1.cmp(&p2) {
+            std::cmp::Ordering::Greater => Some(Player::P1),
+            std::cmp::Ordering::Less => Some(Player::P2),
+            std::cmp::Ordering::Equal => None,
+        }
+    }
+
+    fn create_end_reply(self, data: &HBotData) -> CreateReply<'_> {
+        let title = match self.winner() {
+            Some(winner) => format!("<@{}> wins!", self.players.user_id(winner)),
+            None => "Draw!".to_owned(),
+        };
+
+        let description = format!(
+            "## {title}\n\
+             -# {}\n\
+             -# {}\n\n\
+             {}",
+            self.score_line(Player::P1),
+            self.score_line(Player::P2),
+            self.board_text(None),
+        );
+
+        let embed = CreateEmbed::new()
+            .description(description)
+            .color(data.config().embed_color);
+
+        let components = vec![self.players.rematch_row(GameKind::Othello)];
+        CreateReply::new().embed(embed).components(components)
+    }
+
+    /// Creates the reply for when the active player ran out of time.
+    pub(super) fn create_timeout_reply(self, data: &HBotData) -> CreateReply<'_> {
+        let description = format!(
+            "## <@{}> wins!\n\
+             <@{}> ran out of time.\n\
+             -# {}\n\
+             -# {}\n\n\
+             {}",
+            self.players.user_id(self.players.turn.next()),
+            self.players.turn_user_id(),
+            self.score_line(Player::P1),
+            self.score_line(Player::P2),
+            self.board_text(None),
+        );
+
+        let embed = CreateEmbed::new()
+            .description(description)
+            .color(data.config().embed_color);
+
+        let components = vec![self.players.rematch_row(GameKind::Othello)];
+        CreateReply::new().embed(embed).components(components)
+    }
+}
+
+impl ButtonArgsReply for View {
+    async fn reply(mut self, ctx: ButtonContext<'_>) -> Result {
+        self.players.check_turn(&ctx)?;
+
+        match self.action {
+            Action::Idle => {},
+            Action::Place(pos) => {
+                anyhow::ensure!(
+                    self.board.place(self.players.turn, pos),
+                    HArgError::new_const("You can't place a disc there.")
+                );
+
+                self.players.next_turn();
+            },
+            Action::Pass => {
+                anyhow::ensure!(
+                    !self.board.has_legal_move(self.players.turn),
+                    HArgError::new_const("You can only pass without any legal moves.")
+                );
+
+                self.players.next_turn();
+            },
+        }
+
+        self.action = Action::Idle;
+
+        let reply = if self.board.is_game_over() {
+            timeout::untrack_interacted(&ctx).await;
+            self.players
+                .record_result(&ctx, GameKind::Othello, self.winner())
+                .await;
+            self.create_end_reply(ctx.data)
+        } else {
+            timeout::track_interacted(&ctx, TimedGame::Othello(self.clone())).await;
+            self.create_next_reply(ctx.data)
+        };
+
+        ctx.edit(reply.into()).await
+    }
+}
@@ -0,0 +1,101 @@
+use super::game::*;
+use super::*;
+
+fn pos(name: &str) -> Pos {
+    let &[x, y] = name.as_bytes() else {
+        panic!("invalid pos: {name}");
+    };
+
+    Pos::new(x - b'A', y - b'1')
+}
+
+fn legal_moves(board: &Board, player: Player) -> Vec<String> {
+    board.legal_moves(player).map(|p| p.to_string()).collect()
+}
+
+#[test]
+fn start_layout() {
+    let board = Board::new();
+
+    assert_eq!(board.count(Player::P1), 2);
+    assert_eq!(board.count(Player::P2), 2);
+    assert_eq!(board.get(pos("D4")), Some(Player::P2));
+    assert_eq!(board.get(pos("E4")), Some(Player::P1));
+    assert_eq!(board.get(pos("D5")), Some(Player::P1));
+    assert_eq!(board.get(pos("E5")), Some(Player::P2));
+}
+
+#[test]
+fn start_legal_moves() {
+    let board = Board::new();
+
+    assert_eq!(legal_moves(&board, Player::P1), ["D3", "C4", "F5", "E6"]);
+    assert_eq!(legal_moves(&board, Player::P2), ["E3", "F4", "C5", "D6"]);
+}
+
+#[test]
+fn place_flips() {
+    let mut board = Board::new();
+
+    assert!(board.place(Player::P1, pos("D3")));
+    assert_eq!(board.get(pos("D3")), Some(Player::P1));
+    assert_eq!(board.get(pos("D4")), Some(Player::P1));
+    assert_eq!(board.count(Player::P1), 4);
+    assert_eq!(board.count(Player::P2), 1);
+}
+
+#[test]
+fn place_flips_multiple_lines() {
+    let mut board = Board::empty();
+    board.set(pos("B1"), Player::P2);
+    board.set(pos("C1"), Player::P1);
+    board.set(pos("A2"), Player::P2);
+    board.set(pos("A3"), Player::P1);
+    board.set(pos("B2"), Player::P2);
+    board.set(pos("C3"), Player::P1);
+
+    assert_eq!(board.flips(Player::P1, pos("A1")).count_ones(), 3);
+    assert!(board.place(Player::P1, pos("A1")));
+    assert_eq!(board.count(Player::P1), 7);
+    assert_eq!(board.count(Player::P2), 0);
+}
+
+#[test]
+fn place_illegal() {
+    let mut board = Board::new();
+    let before = board;
+
+    // occupied tile
+    assert!(!board.place(Player::P1, pos("D4")));
+    // does not enclose anything
+    assert!(!board.place(Player::P1, pos("A1")));
+    // line isn't closed off by an own disc
+    assert!(!board.place(Player::P1, pos("F4")));
+
+    assert_eq!(board, before);
+}
+
+#[test]
+fn no_wrap_around() {
+    let mut board = Board::empty();
+    board.set(pos("H1"), Player::P2);
+    board.set(pos("A2"), Player::P1);
+
+    assert!(!board.place(Player::P1, pos("G1")));
+}
+
+#[test]
+fn pass_and_game_over() {
+    let mut board = Board::empty();
+    board.set(pos("A1"), Player::P2);
+    board.set(pos("B1"), Player::P1);
+
+    // the corner disc can't be enclosed, so only P2 can move
+    assert!(!board.has_legal_move(Player::P1));
+    assert!(board.has_legal_move(Player::P2));
+    assert!(!board.is_game_over());
+
+    assert!(board.place(Player::P2, pos("C1")));
+    assert_eq!(board.count(Player::P1), 0);
+    assert!(board.is_game_over());
+}
@@ -0,0 +1,86 @@
+//! ## Rematches for finished versus games.
+//!
+//! Either player may start the rematch, which is posted as a new message so
+//! the result of the previous game stays visible. The players switch sides, so
+//! whoever went second before goes first.
+
+use super::timeout::{self, TimedGame};
+use super::{chess, othello, rock_paper_scissors, tic_tac_toe, Bot, PlayerState};
+use crate::buttons::prelude::*;
+use crate::helper::discord::id_as_u64;
+use crate::modules::minigame::stats::GameKind;
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Rematch {
+    game: GameKind,
+    #[serde(with = "id_as_u64")]
+    p1: UserId,
+    #[serde(with = "id_as_u64")]
+    p2: UserId,
+    bot: Option<Bot>,
+}
+
+impl Rematch {
+    /// Creates a rematch for a game between `players`, in the order they
+    /// played that game.
+    pub fn new(game: GameKind, players: [UserId; 2]) -> Self {
+        Self {
+            game,
+            p1: players[1],
+            p2: players[0],
+            bot: None,
+        }
+    }
+
+    /// Sets the bot that played the previous game.
+    pub(super) fn with_bot(mut self, bot: Option<Bot>) -> Self {
+        // the bot switches sides along with the user
+        self.bot = bot.map(|b| Bot {
+            player: b.player.next(),
+            ..b
+        });
+        self
+    }
+
+    /// Creates the action row holding the rematch button.
+    pub fn create_row(&self) -> CreateActionRow<'static> {
+        let button = CreateButton::new(self.to_custom_id())
+            .label("Rematch")
+            .style(ButtonStyle::Primary);
+
+        CreateActionRow::buttons(vec![button])
+    }
+}
+
+impl ButtonArgsReply for Rematch {
+    async fn reply(self, ctx: ButtonContext<'_>) -> Result {
+        let user = ctx.interaction.user.id;
+        anyhow::ensure!(
+            user == self.p1 || user == self.p2,
+            HArgError::new_const("You weren't part of this game.")
+        );
+
+        let players = [self.p1, self.p2];
+        let state = PlayerState::with_bot(players, self.bot);
+        let game = match self.game {
+            GameKind::TicTacToe => TimedGame::TicTacToe(tic_tac_toe::View::with_players(state)),
+            GameKind::RockPaperScissors => {
+                let reply = rock_paper_scissors::View::new(players).create_next_reply(ctx.data);
+                return ctx.reply(reply).await;
+            },
+            GameKind::Chess => TimedGame::Chess(chess::View::with_players(state)),
+            GameKind::Othello => TimedGame::Othello(othello::View::new(players)),
+            GameKind::Blackjack | GameKind::Minesweeper => {
+                anyhow::bail!("rematches are only supported for versus games")
+            },
+        };
+
+        let reply = game.clone().create_next_reply(ctx.data);
+        ctx.reply(reply).await?;
+
+        // the rematch is the response to this interaction
+        let message = ctx.interaction.get_response(&ctx.serenity.http).await?;
+        timeout::track(ctx.data, &message, &ctx.interaction.token, game).await;
+        Ok(())
+    }
+}
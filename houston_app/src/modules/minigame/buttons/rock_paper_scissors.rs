@@ -1,7 +1,9 @@
 use utils::text::write_str::*;
 
+use super::rematch::Rematch;
 use crate::buttons::prelude::*;
 use crate::helper::discord::{id_as_u64, unicode_emoji};
+use crate::modules::minigame::stats::{self, GameKind, Outcome};
 
 #[derive(Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct View {
@@ -134,7 +136,12 @@ impl View {
             .description(description)
             .color(data.config().embed_color);
 
-        CreateReply::new().embed(embed)
+        let players = self.states.each_ref().map(|s| s.user);
+        let rematch = Rematch::new(GameKind::RockPaperScissors, players);
+
+        CreateReply::new()
+            .embed(embed)
+            .components(vec![rematch.create_row()])
     }
 
     fn new_action_button<'new>(&mut self, choice: Choice) -> CreateButton<'new> {
@@ -153,6 +160,22 @@ impl ButtonArgsReply for View {
         state.choice = action;
 
         let reply = if let Some(ready) = self.ready() {
+            let outcome = match ready {
+                Ready::Winner(user) if user == self.states[0].user => Outcome::Win,
+                Ready::Winner(_) => Outcome::Loss,
+                Ready::Draw => Outcome::Draw,
+            };
+
+            let players = self.states.each_ref().map(|s| s.user);
+            let started = super::started_at(&ctx);
+            stats::record_versus(
+                ctx.data,
+                GameKind::RockPaperScissors,
+                players,
+                outcome,
+                started,
+            )
+            .await;
             self.create_ready_reply(ctx.data, ready)
         } else {
             self.create_next_reply(ctx.data)
@@ -1,6 +1,10 @@
-use super::{Player, PlayerState};
+use rand::prelude::*;
+
+use super::timeout::{self, TimedGame};
+use super::{Difficulty, Player, PlayerState};
 use crate::buttons::prelude::*;
 use crate::helper::discord::unicode_emoji;
+use crate::modules::minigame::stats::GameKind;
 
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct View {
@@ -41,16 +45,105 @@ impl WinLine {
 
 impl View {
     pub fn new(players: [UserId; 2]) -> Self {
-        Self {
-            players: PlayerState::new(players),
+        Self::with_players(PlayerState::new(players))
+    }
+
+    /// Creates a game of `user` against the bot.
+    pub fn new_vs_bot(user: UserId, bot_user: UserId, difficulty: Difficulty) -> Self {
+        Self::with_players(PlayerState::vs_bot(user, bot_user, difficulty))
+    }
+
+    pub(super) fn with_players(players: PlayerState) -> Self {
+        let mut this = Self {
+            players,
             board: Default::default(),
-        }
+        };
+
+        this.play_bot_turn();
+        this
+    }
+
+    pub(super) fn players(&self) -> &PlayerState {
+        &self.players
     }
 
     fn is_full(&self) -> bool {
         !self.board.as_flattened().contains(&None)
     }
 
+    fn is_over(&self) -> bool {
+        self.winner().is_some() || self.is_full()
+    }
+
+    /// Places the bot's mark if it is its turn and passes the turn back.
+    fn play_bot_turn(&mut self) {
+        let Some(difficulty) = self.players.bot_turn() else {
+            return;
+        };
+
+        let depth = match difficulty {
+            Difficulty::Easy => 0,
+            Difficulty::Normal => 2,
+            Difficulty::Hard => 9,
+        };
+
+        let player = self.players.turn;
+        let mut cells: Vec<_> = (0..3)
+            .flat_map(|x| (0..3).map(move |y| (x, y)))
+            .filter(|&(x, y)| self.board[x][y].is_none())
+            .collect();
+
+        // shuffling first picks a random one among equally good cells
+        cells.shuffle(&mut thread_rng());
+
+        let mut best = None;
+        let mut best_score = i32::MIN;
+        for (x, y) in cells {
+            self.board[x][y] = Some(player);
+            let score = -self.negamax(player.next(), depth);
+            self.board[x][y] = None;
+
+            if score > best_score {
+                best = Some((x, y));
+                best_score = score;
+            }
+        }
+
+        if let Some((x, y)) = best {
+            self.board[x][y] = Some(player);
+            if !self.is_over() {
+                self.players.next_turn();
+            }
+        }
+    }
+
+    /// Scores the board from the view of `player`, who is about to move.
+    ///
+    /// Quicker wins score higher, and slower losses score less badly.
+    fn negamax(&mut self, player: Player, depth: i32) -> i32 {
+        if self.winner().is_some() {
+            // only the previous move can have completed a line
+            return -10 - depth;
+        }
+
+        if depth == 0 || self.is_full() {
+            return 0;
+        }
+
+        let mut best = i32::MIN;
+        for x in 0..3 {
+            for y in 0..3 {
+                if self.board[x][y].is_none() {
+                    self.board[x][y] = Some(player);
+                    best = best.max(-self.negamax(player.next(), depth - 1));
+                    self.board[x][y] = None;
+                }
+            }
+        }
+
+        best
+    }
+
     fn winner(&self) -> Option<(Player, WinLine)> {
         fn counts(iter: impl IntoIterator<Item = Option<Player>>) -> (usize, usize) {
             let mut counts = (0, 0);
@@ -180,7 +273,7 @@ impl View {
             .description(description)
             .color(data.config().embed_color);
 
-        let components = self.board_buttons(data, Player::P1, |b, x, y, _| {
+        let mut components = self.board_buttons(data, Player::P1, |b, x, y, _| {
             b.disabled(true).style(if win_line.is_match(x, y) {
                 ButtonStyle::Success
             } else {
@@ -188,6 +281,34 @@ impl View {
             })
         });
 
+        components.push(self.players.rematch_row(GameKind::TicTacToe));
+
+        CreateReply::new().embed(embed).components(components)
+    }
+
+    /// Creates the reply for when the active player ran out of time.
+    pub(super) fn create_timeout_reply(mut self, data: &HBotData) -> CreateReply<'_> {
+        let description = format!(
+            "## <@{winner}> wins!\n\
+             <@{loser}> ran out of time.\n\
+             -# ❌ <@{p1}>\n\
+             -# ⭕ <@{p2}>",
+            winner = self.players.user_id(self.players.turn.next()),
+            loser = self.players.turn_user_id(),
+            p1 = self.players.p1,
+            p2 = self.players.p2,
+        );
+
+        let embed = CreateEmbed::new()
+            .description(description)
+            .color(data.config().embed_color);
+
+        let mut components = self.board_buttons(data, Player::P1, |b, _, _, _| {
+            b.disabled(true).style(ButtonStyle::Secondary)
+        });
+
+        components.push(self.players.rematch_row(GameKind::TicTacToe));
+
         CreateReply::new().embed(embed).components(components)
     }
 
@@ -204,10 +325,12 @@ impl View {
             .description(embed)
             .color(data.config().embed_color);
 
-        let components = self.board_buttons(data, Player::P1, |b, _, _, _| {
+        let mut components = self.board_buttons(data, Player::P1, |b, _, _, _| {
             b.disabled(true).style(ButtonStyle::Danger)
         });
 
+        components.push(self.players.rematch_row(GameKind::TicTacToe));
+
         CreateReply::new().embed(description).components(components)
     }
 }
@@ -216,15 +339,64 @@ impl ButtonArgsReply for View {
     async fn reply(mut self, ctx: ButtonContext<'_>) -> Result {
         self.players.check_turn(&ctx)?;
 
+        // the bot answers right away, unless the game is already over
+        if !self.is_over() {
+            self.players.next_turn();
+            self.play_bot_turn();
+        }
+
         let reply = if let Some((winner, line)) = self.winner() {
+            timeout::untrack_interacted(&ctx).await;
+            self.players
+                .record_result(&ctx, GameKind::TicTacToe, Some(winner))
+                .await;
             self.create_win_reply(ctx.data, winner, line)
         } else if self.is_full() {
+            timeout::untrack_interacted(&ctx).await;
+            self.players
+                .record_result(&ctx, GameKind::TicTacToe, None)
+                .await;
             self.create_draw_reply(ctx.data)
         } else {
-            self.players.next_turn();
+            timeout::track_interacted(&ctx, TimedGame::TicTacToe(self.clone())).await;
             self.create_next_reply(ctx.data)
         };
 
         ctx.edit(reply.into()).await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bot_view(difficulty: Difficulty) -> View {
+        let mut view = View::new_vs_bot(UserId::new(1), UserId::new(2), difficulty);
+        view.players.turn = Player::P2;
+        view
+    }
+
+    #[test]
+    fn bot_completes_line() {
+        let mut view = bot_view(Difficulty::Normal);
+        view.board[0][0] = Some(Player::P2);
+        view.board[1][0] = Some(Player::P2);
+        view.board[0][1] = Some(Player::P1);
+        view.board[1][1] = Some(Player::P1);
+
+        view.play_bot_turn();
+        assert!(matches!(view.winner(), Some((Player::P2, _))));
+    }
+
+    #[test]
+    fn bot_blocks_line() {
+        let mut view = bot_view(Difficulty::Hard);
+        view.board[0][0] = Some(Player::P1);
+        view.board[1][1] = Some(Player::P1);
+        view.board[2][0] = Some(Player::P2);
+
+        view.play_bot_turn();
+        assert_eq!(view.board[2][2], Some(Player::P2));
+        assert_eq!(view.players.turn, Player::P1);
+    }
+}
@@ -0,0 +1,169 @@
+//! ## Forfeits turn-based games whose active player stopped moving.
+//!
+//! Running games are stored in the database along with their turn deadline,
+//! and a scheduled job forfeits them once it has passed. Without a database,
+//! games don't time out. Timed out games are edited with the token of the last
+//! interaction with them, which is only valid for 15 minutes.
+//!
+//! Rock-paper-scissors has no turns, so it isn't tracked.
+
+use chrono::{DateTime, Utc};
+use serenity::prelude::Context;
+
+use super::{chess, othello, tic_tac_toe, PlayerState};
+use crate::buttons::prelude::*;
+use crate::modules::minigame::model::{TimedGameState, TimedGameStateExt as _};
+use crate::modules::minigame::stats::GameKind;
+use crate::modules::scheduler::{self, JobKind};
+
+/// A game that can time out.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub enum TimedGame {
+    TicTacToe(tic_tac_toe::View),
+    Chess(chess::View),
+    Othello(othello::View),
+}
+
+impl TimedGame {
+    fn kind(&self) -> GameKind {
+        match self {
+            Self::TicTacToe(_) => GameKind::TicTacToe,
+            Self::Chess(_) => GameKind::Chess,
+            Self::Othello(_) => GameKind::Othello,
+        }
+    }
+
+    fn players(&self) -> &PlayerState {
+        match self {
+            Self::TicTacToe(view) => view.players(),
+            Self::Chess(view) => view.players(),
+            Self::Othello(view) => view.players(),
+        }
+    }
+
+    /// Creates the reply for the game's current turn.
+    pub fn create_next_reply(self, data: &HBotData) -> CreateReply<'_> {
+        match self {
+            Self::TicTacToe(view) => view.create_next_reply(data),
+            Self::Chess(view) => view.create_next_reply(data),
+            Self::Othello(view) => view.create_next_reply(data),
+        }
+    }
+
+    fn create_timeout_reply(self, data: &HBotData) -> CreateReply<'_> {
+        match self {
+            Self::TicTacToe(view) => view.create_timeout_reply(data),
+            Self::Chess(view) => view.create_timeout_reply(data),
+            Self::Othello(view) => view.create_timeout_reply(data),
+        }
+    }
+}
+
+/// Tracks a running game shown on `message`.
+///
+/// `token` must be the token of the interaction that last edited or sent the
+/// message. The turn timer only restarts once the turn passes on.
+pub async fn track(data: &HBotData, message: &Message, token: &str, game: TimedGame) {
+    if let Err(why) = track_core(data, message, token, game).await {
+        log::warn!("Failed to track game {}: {why:?}", message.id);
+    }
+}
+
+async fn track_core(data: &HBotData, message: &Message, token: &str, game: TimedGame) -> Result {
+    if data.config().mongodb_uri.is_none() {
+        return Ok(());
+    }
+
+    let db = data.database()?;
+    let turn = game.players().turn_user_id();
+    let game = bson::Binary {
+        subtype: bson::spec::BinarySubtype::Generic,
+        bytes: serde_steph::to_vec(&game)?,
+    };
+
+    let started: DateTime<Utc> = *message.timestamp;
+    let deadline = Utc::now() + data.config().minigame.turn_timeout;
+
+    let inserted = TimedGameState::collection(db)
+        .save_state(message.id, game, token, turn, started, deadline)
+        .await?;
+
+    // later deadlines are picked up by the job itself
+    if inserted {
+        let job = JobKind::GameTimeout {
+            message: message.id,
+        };
+        scheduler::schedule(db, &job, deadline).await?;
+    }
+
+    Ok(())
+}
+
+/// Tracks the game on the interacted message.
+pub(super) async fn track_interacted(ctx: &ButtonContext<'_>, game: TimedGame) {
+    track(
+        ctx.data,
+        &ctx.interaction.message,
+        &ctx.interaction.token,
+        game,
+    )
+    .await;
+}
+
+/// Stops tracking the game on the interacted message, f.e. since it is over.
+pub(super) async fn untrack_interacted(ctx: &ButtonContext<'_>) {
+    let message = ctx.interaction.message.id;
+    if let Err(why) = untrack(ctx.data, message).await {
+        log::warn!("Failed to untrack game {message}: {why:?}");
+    }
+}
+
+async fn untrack(data: &HBotData, message: MessageId) -> Result {
+    if data.config().mongodb_uri.is_none() {
+        return Ok(());
+    }
+
+    let db = data.database()?;
+    TimedGameState::collection(db).remove(message).await
+}
+
+/// Forfeits the game on `message` if its active player has run out of time.
+///
+/// This is run by the scheduler. If the player moved in the meantime, it is
+/// scheduled again for the new deadline.
+pub async fn check_timeout(ctx: &Context, message: MessageId) -> Result {
+    let data = ctx.data_ref::<HContextData>();
+    let games = TimedGameState::collection(data.database()?);
+
+    let Some(state) = games.take_expired(message, Utc::now()).await? else {
+        if let Some(state) = games.find_state(message).await? {
+            let job = JobKind::GameTimeout { message };
+            scheduler::schedule(data.database()?, &job, state.deadline).await?;
+        }
+
+        return Ok(());
+    };
+
+    let game: TimedGame = match serde_steph::from_slice(&state.game.bytes) {
+        Ok(game) => game,
+        Err(why) => {
+            log::warn!("Dropping timed out game {message} that cannot be loaded: {why:?}");
+            return Ok(());
+        },
+    };
+
+    let kind = game.kind();
+    let players = game.players();
+    let winner = players.turn.next();
+    players
+        .record_result_at(data, kind, Some(winner), state.started)
+        .await;
+
+    let reply = game.create_timeout_reply(data);
+    reply
+        .into_interaction_edit()
+        .execute(&ctx.http, &state.token)
+        .await?;
+
+    Ok(())
+}
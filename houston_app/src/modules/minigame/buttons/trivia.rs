@@ -0,0 +1,269 @@
+//! ## Multiple-choice trivia for everyone in the channel.
+//!
+//! Questions come from the bot config. Each round stays open for the
+//! configured answer time, and anyone may lock in one answer per round.
+//! Every correct answer scores a point.
+//!
+//! The open rounds are only tracked in memory, so games don't survive a
+//! restart.
+
+use std::collections::HashMap;
+use std::sync::LazyLock;
+use std::time::Duration;
+
+use chrono::Utc;
+use dashmap::DashMap;
+use houston_cmd::ReplyHandle;
+use indexmap::IndexMap;
+use rand::prelude::*;
+use utils::text::write_str::*;
+
+use crate::buttons::prelude::*;
+use crate::fmt::discord::TimeMentionable as _;
+use crate::modules::minigame::config::{TriviaCategory, TriviaConfig, TriviaQuestion};
+
+/// How long the correct answer is shown before the next round starts.
+const REVEAL_TIME: Duration = Duration::from_secs(5);
+
+/// The answers for the currently open round of each running game.
+static OPEN_ROUNDS: LazyLock<DashMap<u64, OpenRound>> = LazyLock::new(DashMap::new);
+
+#[derive(Debug)]
+struct OpenRound {
+    round: u8,
+    answers: HashMap<UserId, u8>,
+}
+
+/// Locks in an answer for the current round.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Answer {
+    game: u64,
+    round: u8,
+    choice: u8,
+}
+
+fn letter(index: u8) -> char {
+    char::from_u32(0x1F1E6 + u32::from(index)).expect("must be valid")
+}
+
+/// Finds a category by its config key, or picks a random one.
+pub fn find_category<'a>(
+    config: &'a TriviaConfig,
+    key: Option<&str>,
+) -> Result<&'a TriviaCategory> {
+    match key {
+        Some(key) => config
+            .categories
+            .get(key)
+            .ok_or(HArgError::new_const("Unknown trivia category.").into()),
+        None => config
+            .categories
+            .values()
+            .choose(&mut thread_rng())
+            .context("trivia has no categories"),
+    }
+}
+
+/// A running game of trivia.
+pub struct Game<'a> {
+    id: u64,
+    data: &'a HBotData,
+    config: &'a TriviaConfig,
+    category: &'a TriviaCategory,
+    questions: Vec<&'a TriviaQuestion>,
+    scores: IndexMap<UserId, u32>,
+}
+
+impl Drop for Game<'_> {
+    fn drop(&mut self) {
+        // close the round if the game ends early, f.e. due to an error
+        OPEN_ROUNDS.remove(&self.id);
+    }
+}
+
+impl<'a> Game<'a> {
+    pub fn new(
+        data: &'a HBotData,
+        config: &'a TriviaConfig,
+        category: &'a TriviaCategory,
+        rounds: u8,
+    ) -> Self {
+        let mut rng = thread_rng();
+        let questions = category
+            .questions
+            .choose_multiple(&mut rng, rounds.into())
+            .collect();
+
+        Self {
+            id: rng.gen(),
+            data,
+            config,
+            category,
+            questions,
+            scores: IndexMap::new(),
+        }
+    }
+
+    /// Creates the reply shown before the first question.
+    pub fn create_start_reply(&self) -> CreateReply<'a> {
+        let description = format!(
+            "Get ready! {} question(s) are coming up.",
+            self.questions.len()
+        );
+
+        self.create_reply(description)
+    }
+
+    /// Plays every round, editing the message behind `handle` as it goes.
+    pub async fn run(mut self, handle: ReplyHandle<'_>) -> Result {
+        let questions = std::mem::take(&mut self.questions);
+        let count = questions.len();
+        let answer_time = self.config.answer_time.to_std()?;
+
+        for (round, question) in (1u8..).zip(questions) {
+            let answers: Vec<&str> = question.answers().collect();
+            let mut order: Vec<usize> = (0..answers.len()).collect();
+            order.shuffle(&mut thread_rng());
+
+            OPEN_ROUNDS.insert(
+                self.id,
+                OpenRound {
+                    round,
+                    answers: HashMap::new(),
+                },
+            );
+
+            let header = format!("**Question {round}/{count}**\n{}\n", question.question);
+            let ends = Utc::now() + self.config.answer_time;
+
+            let mut description = header.clone();
+            for (index, &answer) in (0u8..).zip(&order) {
+                write_str!(description, "\n{} {}", letter(index), answers[answer]);
+            }
+
+            write_str!(description, "\n\n-# Answers close {}.", ends.relative());
+
+            let buttons = (0u8..)
+                .take(order.len())
+                .map(|choice| {
+                    let args = Answer {
+                        game: self.id,
+                        round,
+                        choice,
+                    };
+
+                    CreateButton::new(args.to_custom_id())
+                        .emoji(letter(choice))
+                        .style(ButtonStyle::Secondary)
+                })
+                .collect();
+
+            let reply = self
+                .create_reply(description)
+                .components(vec![CreateActionRow::buttons(buttons)]);
+            handle.edit(reply.into()).await?;
+
+            tokio::time::sleep(answer_time).await;
+
+            let given = OPEN_ROUNDS
+                .remove(&self.id)
+                .map(|(_, r)| r.answers)
+                .unwrap_or_default();
+
+            // the correct answer is always at index 0 before shuffling
+            let correct = order.iter().position(|&a| a == 0).unwrap_or_default();
+
+            let mut winners = Vec::new();
+            for (user, choice) in given {
+                let score = self.scores.entry(user).or_insert(0);
+                if usize::from(choice) == correct {
+                    *score += 1;
+                    winners.push(user);
+                }
+            }
+
+            let mut description = header;
+            for (index, &answer) in (0u8..).zip(&order) {
+                if answer == 0 {
+                    write_str!(
+                        description,
+                        "\n{} **{}** ✅",
+                        letter(index),
+                        answers[answer]
+                    );
+                } else {
+                    write_str!(description, "\n-# {} {}", letter(index), answers[answer]);
+                }
+            }
+
+            if winners.is_empty() {
+                description.push_str("\n\nNobody got it right.");
+            } else {
+                description.push_str("\n\nCorrect:");
+                for user in winners {
+                    write_str!(description, " <@{user}>");
+                }
+            }
+
+            let reply = self.create_reply(description).components(vec![]);
+            handle.edit(reply.into()).await?;
+
+            if usize::from(round) < count {
+                tokio::time::sleep(REVEAL_TIME).await;
+            }
+        }
+
+        let reply = self.create_end_reply();
+        handle.edit(reply.into()).await?;
+        Ok(())
+    }
+
+    fn create_reply(&self, description: String) -> CreateReply<'a> {
+        let embed = CreateEmbed::new()
+            .title(format!("Trivia: {}", self.category.name))
+            .description(description)
+            .color(self.data.config().embed_color);
+
+        CreateReply::new().embed(embed)
+    }
+
+    fn create_end_reply(&mut self) -> CreateReply<'a> {
+        self.scores.sort_by(|_, a, _, b| b.cmp(a));
+
+        let mut description = "## Final Scores\n".to_owned();
+        for (index, (user, score)) in self.scores.iter().enumerate() {
+            writeln_str!(description, "{}. <@{user}>: {score}", index + 1);
+        }
+
+        if self.scores.is_empty() {
+            description.push_str("Nobody played.");
+        }
+
+        self.create_reply(description).components(vec![])
+    }
+}
+
+impl ButtonArgsReply for Answer {
+    async fn reply(self, ctx: ButtonContext<'_>) -> Result {
+        let user = ctx.interaction.user.id;
+
+        // the map guard must not be held across the reply
+        let locked = {
+            let mut open = OPEN_ROUNDS
+                .get_mut(&self.game)
+                .filter(|r| r.round == self.round)
+                .ok_or(HArgError::new_const("This question is already closed."))?;
+
+            *open.answers.entry(user).or_insert(self.choice)
+        };
+
+        let content = if locked == self.choice {
+            format!("You answered {}.", letter(locked))
+        } else {
+            format!("You already answered {}.", letter(locked))
+        };
+
+        ctx.reply(CreateReply::new().content(content).ephemeral(true))
+            .await
+    }
+}
@@ -0,0 +1,388 @@
+//! ## A daily Wordle-style word puzzle.
+//!
+//! Every server gets one shared word per UTC day. Guesses are entered via a
+//! modal and stored in the database, so each user can only play each day's
+//! puzzle once. Any 5 letters are accepted as a guess.
+
+use std::sync::LazyLock;
+
+use bson::doc;
+use chrono::{Datelike as _, NaiveDate, Utc};
+use utils::text::write_str::*;
+
+use super::{splitmix64, GOLDEN_GAMMA};
+use crate::buttons::prelude::*;
+use crate::helper::bson::bson_id;
+use crate::helper::discord::id_as_u64;
+use crate::modules::minigame::model::*;
+
+const WORD_LEN: usize = 5;
+
+static ANSWERS: LazyLock<Vec<&str>> = LazyLock::new(|| {
+    include_str!("../../../../assets/wordle/answers.txt")
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .collect()
+});
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct View {
+    #[serde(with = "id_as_u64")]
+    guild: GuildId,
+    day: i32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Mark {
+    Absent,
+    Present,
+    Correct,
+}
+
+impl Mark {
+    const fn emoji(self) -> &'static str {
+        match self {
+            Self::Absent => "⬛",
+            Self::Present => "🟨",
+            Self::Correct => "🟩",
+        }
+    }
+}
+
+/// Gets the current puzzle day.
+fn today() -> i32 {
+    Utc::now().date_naive().num_days_from_ce()
+}
+
+/// Picks the answer for a server's puzzle on a given day.
+fn answer(guild: GuildId, day: i32) -> &'static str {
+    let z = guild.get() ^ u64::from(day.unsigned_abs()).wrapping_mul(GOLDEN_GAMMA);
+    let z = splitmix64(z);
+
+    let index = z % ANSWERS.len() as u64;
+    ANSWERS[usize::try_from(index).expect("index must be in range")]
+}
+
+/// Scores a guess against the answer.
+///
+/// Letters that aren't in the right spot are only marked as present as often
+/// as they appear in the rest of the answer.
+fn score(guess: &[u8], answer: &[u8]) -> [Mark; WORD_LEN] {
+    let mut marks = [Mark::Absent; WORD_LEN];
+    let mut unmatched = [0u8; 26];
+
+    for ((mark, &g), &a) in marks.iter_mut().zip(guess).zip(answer) {
+        if g == a {
+            *mark = Mark::Correct;
+        } else {
+            unmatched[usize::from(a - b'a')] += 1;
+        }
+    }
+
+    for (mark, &g) in marks.iter_mut().zip(guess) {
+        let count = &mut unmatched[usize::from(g - b'a')];
+        if *mark == Mark::Absent && *count > 0 {
+            *mark = Mark::Present;
+            *count -= 1;
+        }
+    }
+
+    marks
+}
+
+/// Normalizes a guess to lowercase, if it is valid at all.
+fn parse_guess(value: &str) -> Option<String> {
+    let value = value.trim();
+    let valid = value.len() == WORD_LEN && value.bytes().all(|b| b.is_ascii_alphabetic());
+    valid.then(|| value.to_ascii_lowercase())
+}
+
+fn get_guess(interaction: &ModalInteraction) -> Option<String> {
+    let component = interaction.data.components.first()?.components.first()?;
+
+    let ActionRowComponent::InputText(InputText {
+        value: Some(value),
+        custom_id,
+        ..
+    }) = component
+    else {
+        return None;
+    };
+
+    if custom_id.as_str() != "guess" {
+        return None;
+    }
+
+    parse_guess(value)
+}
+
+impl View {
+    /// Creates a view for today's puzzle.
+    pub fn new(guild: GuildId) -> Self {
+        Self {
+            guild,
+            day: today(),
+        }
+    }
+
+    fn check_day(&self) -> Result {
+        anyhow::ensure!(
+            self.day == today(),
+            HArgError::new_const("This puzzle is over. Play today's with `/minigame wordle play`.")
+        );
+        Ok(())
+    }
+
+    /// Loads the user's game and creates the reply for it.
+    pub async fn create_reply(self, data: &HBotData, user: UserId) -> Result<CreateReply<'_>> {
+        let db = data.database()?;
+        let game = WordleGame::collection(db)
+            .find_game(self.guild, user, self.day)
+            .await?;
+
+        let stats = match &game {
+            Some(game) if game.done => {
+                WordleStats::collection(db)
+                    .find_stats(self.guild, user)
+                    .await?
+            },
+            _ => None,
+        };
+
+        Ok(self.create_game_reply(data, game.as_ref(), stats.as_ref()))
+    }
+
+    fn create_game_reply(
+        self,
+        data: &HBotData,
+        game: Option<&WordleGame>,
+        stats: Option<&WordleStats>,
+    ) -> CreateReply<'_> {
+        let answer = answer(self.guild, self.day);
+        let guesses = game.map_or(&[][..], |g| g.guesses.as_slice());
+
+        let mut description = String::new();
+        for guess in guesses {
+            for mark in score(guess.as_bytes(), answer.as_bytes()) {
+                description.push_str(mark.emoji());
+            }
+
+            writeln_str!(description, " `{}`", guess.to_ascii_uppercase());
+        }
+
+        for _ in guesses.len()..MAX_GUESSES {
+            description.push_str("⬜⬜⬜⬜⬜\n");
+        }
+
+        let won = guesses.last().is_some_and(|g| g == answer);
+        let done = game.is_some_and(|g| g.done);
+
+        if won {
+            write_str!(
+                description,
+                "\n**Solved in {}/{MAX_GUESSES}!**",
+                guesses.len()
+            );
+        } else if done {
+            write_str!(
+                description,
+                "\nThe word was **{}**.",
+                answer.to_ascii_uppercase()
+            );
+        } else {
+            write_str!(
+                description,
+                "\n-# Guess {}/{MAX_GUESSES}. Everyone in this server gets the same word.",
+                guesses.len() + 1
+            );
+        }
+
+        if let Some(stats) = stats {
+            write_str!(
+                description,
+                "\n-# Streak: {} \u{2E31} Best: {} \u{2E31} Wins: {}/{}",
+                stats.current_streak(self.day),
+                stats.max_streak,
+                stats.wins,
+                stats.played,
+            );
+        }
+
+        let title = match NaiveDate::from_num_days_from_ce_opt(self.day) {
+            Some(date) => format!("Wordle {date}"),
+            None => "Wordle".to_owned(),
+        };
+
+        let embed = CreateEmbed::new()
+            .title(title)
+            .description(description)
+            .color(data.config().embed_color);
+
+        let components = if done {
+            vec![]
+        } else {
+            let button = CreateButton::new(self.to_custom_id())
+                .label("Guess")
+                .style(ButtonStyle::Primary);
+
+            vec![CreateActionRow::buttons(vec![button])]
+        };
+
+        CreateReply::new().embed(embed).components(components)
+    }
+}
+
+impl ButtonArgsReply for View {
+    async fn reply(self, ctx: ButtonContext<'_>) -> Result {
+        self.check_day()?;
+
+        let input_text = CreateInputText::new(InputTextStyle::Short, "Guess", "guess")
+            .min_length(5)
+            .max_length(5)
+            .placeholder("Enter a 5-letter word...")
+            .required(true);
+
+        let components = vec![CreateActionRow::input_text(input_text)];
+
+        let custom_id = self.to_custom_id();
+        let modal = CreateModal::new(custom_id, "Guess the word").components(components);
+
+        ctx.modal(modal).await
+    }
+
+    async fn modal_reply(self, ctx: ModalContext<'_>) -> Result {
+        self.check_day()?;
+
+        let guess = get_guess(ctx.interaction).ok_or(HArgError::new_const(
+            "Your guess must be exactly 5 letters.",
+        ))?;
+
+        ctx.acknowledge().await?;
+
+        let user = ctx.interaction.user.id;
+        let db = ctx.data.database()?;
+        let games = WordleGame::collection(db);
+
+        let mut game = games
+            .push_guess(self.guild, user, self.day, &guess)
+            .await?
+            .ok_or(HArgError::new_const("You already finished today's puzzle."))?;
+
+        let won = guess == answer(self.guild, self.day);
+        let mut stats = None;
+
+        if won || game.guesses.len() >= MAX_GUESSES {
+            // only the request that actually ends the game may count it
+            let collection = WordleStats::collection(db);
+            stats = if games.set_done(game._id).await? {
+                Some(
+                    collection
+                        .record_result(self.guild, user, self.day, won)
+                        .await?,
+                )
+            } else {
+                collection.find_stats(self.guild, user).await?
+            };
+
+            game.done = true;
+        }
+
+        let reply = self.create_game_reply(ctx.data, Some(&game), stats.as_ref());
+        ctx.edit(reply.into()).await
+    }
+}
+
+/// Creates the reply for a server's Wordle leaderboard.
+pub async fn create_top_reply(data: &HBotData, guild: GuildId) -> Result<CreateReply<'_>> {
+    const TOP_COUNT: i64 = 15;
+
+    let db = data.database()?;
+
+    let filter = doc! {
+        "guild": bson_id!(guild),
+    };
+
+    let sort = doc! {
+        "wins": -1,
+        "max_streak": -1,
+    };
+
+    let mut cursor = WordleStats::collection(db)
+        .find(filter)
+        .sort(sort)
+        .limit(TOP_COUNT)
+        .await?;
+
+    let mut description = String::new();
+    let mut index = 0u32;
+    let day = today();
+
+    while let Some(item) = cursor.try_next().await? {
+        index += 1;
+        writeln_str!(
+            description,
+            "{index}. <@{}>: {} win(s) \u{2E31} Streak: {} \u{2E31} Best: {}",
+            item.user,
+            item.wins,
+            item.current_streak(day),
+            item.max_streak,
+        );
+    }
+
+    let description = crate::fmt::written_or(description, "<None>");
+
+    let embed = CreateEmbed::new()
+        .title("Wordle Leaderboards")
+        .color(data.config().embed_color)
+        .description(description);
+
+    Ok(CreateReply::new().embed(embed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn marks(guess: &str, answer: &str) -> String {
+        score(guess.as_bytes(), answer.as_bytes())
+            .into_iter()
+            .map(Mark::emoji)
+            .collect()
+    }
+
+    #[test]
+    fn score_exact() {
+        assert_eq!(marks("crane", "crane"), "🟩🟩🟩🟩🟩");
+        assert_eq!(marks("cloud", "crane"), "🟩⬛⬛⬛⬛");
+        assert_eq!(marks("nacre", "crane"), "🟨🟨🟨🟨🟩");
+    }
+
+    #[test]
+    fn score_duplicate_letters() {
+        // only one "e" in the answer, and it is already matched
+        assert_eq!(marks("eerie", "crane"), "⬛⬛🟨⬛🟩");
+        // the correct match takes precedence over earlier ones
+        assert_eq!(marks("nanny", "crane"), "⬛🟨⬛🟩⬛");
+        // only the first of two "e" is marked
+        assert_eq!(marks("speed", "abide"), "⬛⬛🟨⬛🟨");
+        // both "l" are in the answer
+        assert_eq!(marks("llama", "hello"), "🟨🟨⬛⬛⬛");
+    }
+
+    #[test]
+    fn parse_guesses() {
+        assert_eq!(parse_guess(" Crane ").as_deref(), Some("crane"));
+        assert_eq!(parse_guess("cran"), None);
+        assert_eq!(parse_guess("cranes"), None);
+        assert_eq!(parse_guess("cr4ne"), None);
+    }
+
+    #[test]
+    fn answers_are_valid() {
+        assert!(ANSWERS.len() > 100, "expected a decent amount of answers");
+        for word in ANSWERS.iter() {
+            assert_eq!(parse_guess(word).as_deref(), Some(*word), "invalid answer");
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use chrono::TimeDelta;
+use indexmap::IndexMap;
+
+use crate::helper::time::serde_time_delta;
+use crate::prelude::*;
+
+#[derive(Debug, serde::Deserialize)]
+pub struct Config {
+    #[serde(with = "serde_time_delta", default = "default_turn_timeout")]
+    pub turn_timeout: TimeDelta,
+    pub trivia: Option<TriviaConfig>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            turn_timeout: default_turn_timeout(),
+            trivia: None,
+        }
+    }
+}
+
+fn default_turn_timeout() -> TimeDelta {
+    const { TimeDelta::minutes(5) }
+}
+
+fn default_answer_time() -> TimeDelta {
+    const { TimeDelta::seconds(20) }
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct TriviaConfig {
+    #[serde(with = "serde_time_delta", default = "default_answer_time")]
+    pub answer_time: TimeDelta,
+    pub categories: IndexMap<String, TriviaCategory>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct TriviaCategory {
+    pub name: String,
+    pub questions: Vec<TriviaQuestion>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct TriviaQuestion {
+    pub question: String,
+    pub correct: String,
+    pub wrong: Vec<String>,
+}
+
+impl Config {
+    /// The longest allowed turn timeout.
+    ///
+    /// Timed out games are edited with the token of the last interaction,
+    /// which is only valid for 15 minutes, so this has to leave some leeway.
+    pub const MAX_TURN_TIMEOUT: TimeDelta = TimeDelta::minutes(14);
+    /// The shortest allowed turn timeout.
+    pub const MIN_TURN_TIMEOUT: TimeDelta = TimeDelta::minutes(1);
+
+    pub fn validate(&self) -> Result {
+        anyhow::ensure!(
+            (Self::MIN_TURN_TIMEOUT..=Self::MAX_TURN_TIMEOUT).contains(&self.turn_timeout),
+            "minigame turn_timeout must be between 1 and 14 minutes",
+        );
+
+        if let Some(trivia) = &self.trivia {
+            trivia.validate()?;
+        }
+
+        Ok(())
+    }
+}
+
+impl TriviaConfig {
+    /// The longest allowed answer time.
+    ///
+    /// The game runs on the command's interaction token, which is only valid
+    /// for 15 minutes, so this has to stay small enough for every round.
+    pub const MAX_ANSWER_TIME: TimeDelta = TimeDelta::seconds(60);
+    /// The shortest allowed answer time.
+    pub const MIN_ANSWER_TIME: TimeDelta = TimeDelta::seconds(5);
+
+    pub fn validate(&self) -> Result {
+        anyhow::ensure!(
+            (Self::MIN_ANSWER_TIME..=Self::MAX_ANSWER_TIME).contains(&self.answer_time),
+            "trivia answer_time must be between 5 and 60 seconds",
+        );
+
+        anyhow::ensure!(
+            !self.categories.is_empty(),
+            "trivia needs at least one category",
+        );
+
+        for (key, category) in &self.categories {
+            anyhow::ensure!(
+                !category.questions.is_empty(),
+                "trivia category `{key}` has no questions",
+            );
+
+            for question in &category.questions {
+                anyhow::ensure!(
+                    (1..=4).contains(&question.wrong.len()),
+                    "trivia question `{}` in `{key}` must have 1 to 4 wrong answers",
+                    question.question,
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl TriviaQuestion {
+    /// Gets all answers. The correct answer is always the first.
+    pub fn answers(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.correct.as_str()).chain(self.wrong.iter().map(String::as_str))
+    }
+}
@@ -1,7 +1,12 @@
 use super::prelude::*;
 
 pub mod buttons;
+pub mod config;
+pub mod model;
 mod slashies;
+pub mod stats;
+
+pub use config::Config;
 
 pub struct Module;
 
@@ -10,7 +15,42 @@ impl super::Module for Module {
         true
     }
 
-    fn commands(&self, _config: &HBotConfig) -> impl IntoIterator<Item = super::HCommand> {
-        [slashies::minigame()]
+    fn commands(&self, config: &HBotConfig) -> impl IntoIterator<Item = super::HCommand> {
+        use houston_cmd::model::CommandOptionData;
+
+        let mut minigame = slashies::minigame();
+
+        // trivia needs questions from the config to work at all
+        if config.minigame.trivia.is_none() {
+            if let CommandOptionData::Group(group) = &mut minigame.data.data {
+                group.sub_commands.to_mut().retain(|c| c.name != "trivia");
+            }
+        }
+
+        [minigame]
+    }
+
+    fn db_init(db: &mongodb::Database) -> mongodb::BoxFuture<'_, Result> {
+        use crate::helper::bson::update_indices;
+        Box::pin(async move {
+            use model::*;
+            update_indices(WordleGame::collection(db), WordleGame::indices()).await?;
+            update_indices(WordleStats::collection(db), WordleStats::indices()).await?;
+            update_indices(GameStats::collection(db), GameStats::indices()).await?;
+            update_indices(GameRecord::collection(db), GameRecord::indices()).await?;
+            update_indices(BlackjackWager::collection(db), BlackjackWager::indices()).await?;
+            update_indices(TimedGameState::collection(db), TimedGameState::indices()).await?;
+            Ok(())
+        })
+    }
+
+    fn validate(&self, config: &HBotConfig) -> Result {
+        config.minigame.validate()?;
+
+        if let Some(trivia) = &config.minigame.trivia {
+            log::info!("Trivia is enabled: {} category(s)", trivia.categories.len());
+        }
+
+        Ok(())
     }
 }
@@ -0,0 +1,764 @@
+use super::stats::{GameKind, Outcome, DEFAULT_RATING};
+use crate::modules::model_prelude::*;
+
+/// The amount of guesses a user gets for each daily puzzle.
+pub const MAX_GUESSES: usize = 6;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WordleGame {
+    pub _id: ObjectId,
+    #[serde(with = "id_as_i64")]
+    pub guild: GuildId,
+    #[serde(with = "id_as_i64")]
+    pub user: UserId,
+    pub day: i32,
+    #[serde(default)]
+    pub guesses: Vec<String>,
+    #[serde(default)]
+    pub done: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WordleStats {
+    pub _id: ObjectId,
+    #[serde(with = "id_as_i64")]
+    pub guild: GuildId,
+    #[serde(with = "id_as_i64")]
+    pub user: UserId,
+    #[serde(default)]
+    pub played: i32,
+    #[serde(default)]
+    pub wins: i32,
+    #[serde(default)]
+    pub streak: i32,
+    #[serde(default)]
+    pub max_streak: i32,
+    pub last_win_day: Option<i32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameStats {
+    pub _id: ObjectId,
+    #[serde(with = "id_as_i64")]
+    pub user: UserId,
+    pub game: GameKind,
+    #[serde(default)]
+    pub wins: i32,
+    #[serde(default)]
+    pub losses: i32,
+    #[serde(default)]
+    pub draws: i32,
+    pub rating: Option<i32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameRecord {
+    pub _id: ObjectId,
+    pub game: GameKind,
+    pub players: Vec<GameRecordPlayer>,
+    #[serde(with = "chrono_datetime_as_bson_datetime")]
+    pub started: DateTime<Utc>,
+    #[serde(with = "chrono_datetime_as_bson_datetime")]
+    pub ended: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameRecordPlayer {
+    #[serde(with = "id_as_i64")]
+    pub user: UserId,
+    pub outcome: Outcome,
+}
+
+/// A blackjack wager that hasn't been paid out yet.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlackjackWager {
+    pub _id: ObjectId,
+    /// The ID of the game, as held by its buttons.
+    pub game: i64,
+    #[serde(with = "id_as_i64")]
+    pub guild: GuildId,
+    #[serde(with = "id_as_i64")]
+    pub user: UserId,
+    /// The cash taken from the user so far.
+    pub stake: i64,
+    /// Until when an action on the game is in progress.
+    ///
+    /// In the past if no action is in progress.
+    #[serde(with = "chrono_datetime_as_bson_datetime")]
+    pub claimed_until: DateTime<Utc>,
+}
+
+/// A turn-based game waiting on its active player.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimedGameState {
+    pub _id: ObjectId,
+    #[serde(with = "id_as_i64")]
+    pub message: MessageId,
+    /// The [`TimedGame`](super::buttons::timeout::TimedGame), encoded like
+    /// button arguments.
+    pub game: bson::Binary,
+    /// The token of the interaction that last edited the message.
+    pub token: String,
+    /// The user whose turn it is.
+    #[serde(with = "id_as_i64")]
+    pub turn: UserId,
+    #[serde(with = "chrono_datetime_as_bson_datetime")]
+    pub started: DateTime<Utc>,
+    /// When the active player runs out of time.
+    #[serde(with = "chrono_datetime_as_bson_datetime")]
+    pub deadline: DateTime<Utc>,
+}
+
+fn name(name: &str) -> IndexOptions {
+    IndexOptions::builder().name(name.to_owned()).build()
+}
+
+impl WordleGame {
+    pub fn collection(db: &Database) -> Collection<Self> {
+        db.collection("minigame.wordle_games")
+    }
+
+    pub fn indices() -> Vec<IndexModel> {
+        vec![IndexModel::builder()
+            .options(
+                IndexOptions::builder()
+                    .name("guild-user-day".to_owned())
+                    .unique(true)
+                    .build(),
+            )
+            .keys(doc! {
+                "guild": 1,
+                "user": 1,
+                "day": 1,
+            })
+            .build()]
+    }
+}
+
+impl WordleStats {
+    pub fn collection(db: &Database) -> Collection<Self> {
+        db.collection("minigame.wordle_stats")
+    }
+
+    pub fn indices() -> Vec<IndexModel> {
+        vec![
+            IndexModel::builder()
+                .options(name("guild-user"))
+                .keys(doc! {
+                    "guild": 1,
+                    "user": 1,
+                })
+                .build(),
+            IndexModel::builder()
+                .options(name("guild-wins"))
+                .keys(doc! {
+                    "guild": 1,
+                    "wins": -1,
+                    "max_streak": -1,
+                })
+                .build(),
+        ]
+    }
+
+    /// Gets the streak as of `day`.
+    ///
+    /// The stored streak is only updated when a game ends, so it is broken
+    /// once a whole day passes without a win.
+    pub fn current_streak(&self, day: i32) -> i32 {
+        match self.last_win_day {
+            Some(last) if last >= day - 1 => self.streak,
+            _ => 0,
+        }
+    }
+
+    fn record(&mut self, day: i32, won: bool) {
+        self.played += 1;
+        if won {
+            self.wins += 1;
+            self.streak = self.current_streak(day) + 1;
+            self.max_streak = self.max_streak.max(self.streak);
+            self.last_win_day = Some(day);
+        } else {
+            self.streak = 0;
+        }
+    }
+}
+
+impl GameStats {
+    pub fn collection(db: &Database) -> Collection<Self> {
+        db.collection("minigame.stats")
+    }
+
+    pub fn indices() -> Vec<IndexModel> {
+        vec![
+            IndexModel::builder()
+                .options(
+                    IndexOptions::builder()
+                        .name("user-game".to_owned())
+                        .unique(true)
+                        .build(),
+                )
+                .keys(doc! {
+                    "user": 1,
+                    "game": 1,
+                })
+                .build(),
+            IndexModel::builder()
+                .options(name("game-rating"))
+                .keys(doc! {
+                    "game": 1,
+                    "rating": -1,
+                    "wins": -1,
+                })
+                .build(),
+            IndexModel::builder()
+                .options(name("game-wins"))
+                .keys(doc! {
+                    "game": 1,
+                    "wins": -1,
+                    "losses": 1,
+                })
+                .build(),
+        ]
+    }
+}
+
+impl GameRecord {
+    pub fn collection(db: &Database) -> Collection<Self> {
+        db.collection("minigame.history")
+    }
+
+    pub fn indices() -> Vec<IndexModel> {
+        vec![IndexModel::builder()
+            .options(name("user-ended"))
+            .keys(doc! {
+                "players.user": 1,
+                "ended": -1,
+            })
+            .build()]
+    }
+}
+
+impl BlackjackWager {
+    pub fn collection(db: &Database) -> Collection<Self> {
+        db.collection("minigame.blackjack_wagers")
+    }
+
+    pub fn indices() -> Vec<IndexModel> {
+        vec![IndexModel::builder()
+            .options(
+                IndexOptions::builder()
+                    .name("game".to_owned())
+                    .unique(true)
+                    .build(),
+            )
+            .keys(doc! {
+                "game": 1,
+            })
+            .build()]
+    }
+}
+
+impl TimedGameState {
+    pub fn collection(db: &Database) -> Collection<Self> {
+        db.collection("minigame.timed_games")
+    }
+
+    pub fn indices() -> Vec<IndexModel> {
+        vec![IndexModel::builder()
+            .options(
+                IndexOptions::builder()
+                    .name("message".to_owned())
+                    .unique(true)
+                    .build(),
+            )
+            .keys(doc! {
+                "message": 1,
+            })
+            .build()]
+    }
+}
+
+pub trait WordleGameExt {
+    async fn find_game(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        day: i32,
+    ) -> Result<Option<WordleGame>>;
+
+    /// Adds a guess to the game, creating it first if needed.
+    ///
+    /// Returns [`None`] if the game is already over.
+    async fn push_guess(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        day: i32,
+        guess: &str,
+    ) -> Result<Option<WordleGame>>;
+
+    /// Marks the game as done.
+    ///
+    /// Returns whether the game was still ongoing before this call.
+    async fn set_done(&self, game_id: ObjectId) -> Result<bool>;
+}
+
+impl WordleGameExt for Collection<WordleGame> {
+    async fn find_game(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        day: i32,
+    ) -> Result<Option<WordleGame>> {
+        let filter = doc! {
+            "guild": bson_id!(guild_id),
+            "user": bson_id!(user_id),
+            "day": day,
+        };
+
+        let doc = self.find_one(filter).await?;
+        Ok(doc)
+    }
+
+    async fn push_guess(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        day: i32,
+        guess: &str,
+    ) -> Result<Option<WordleGame>> {
+        let filter = doc! {
+            "guild": bson_id!(guild_id),
+            "user": bson_id!(user_id),
+            "day": day,
+        };
+
+        let create = doc! {
+            "$setOnInsert": filter.clone(),
+        };
+
+        self.update_one(filter.clone(), create).upsert(true).await?;
+
+        // checking the guess count in the filter keeps quick double-submits
+        // from going over the limit
+        let mut filter = filter;
+        filter.insert("done", false);
+        filter.insert(
+            format!("guesses.{}", MAX_GUESSES - 1),
+            doc! { "$exists": false },
+        );
+
+        let update = doc! {
+            "$push": {
+                "guesses": guess,
+            },
+        };
+
+        let doc = self
+            .find_one_and_update(filter, update)
+            .return_document(ReturnDocument::After)
+            .await?;
+
+        Ok(doc)
+    }
+
+    async fn set_done(&self, game_id: ObjectId) -> Result<bool> {
+        let filter = doc! {
+            "_id": game_id,
+            "done": false,
+        };
+
+        let update = doc! {
+            "$set": {
+                "done": true,
+            },
+        };
+
+        let result = self.update_one(filter, update).await?;
+        Ok(result.modified_count != 0)
+    }
+}
+
+pub trait WordleStatsExt {
+    async fn find_stats(&self, guild_id: GuildId, user_id: UserId) -> Result<Option<WordleStats>>;
+
+    async fn record_result(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        day: i32,
+        won: bool,
+    ) -> Result<WordleStats>;
+}
+
+impl WordleStatsExt for Collection<WordleStats> {
+    async fn find_stats(&self, guild_id: GuildId, user_id: UserId) -> Result<Option<WordleStats>> {
+        let filter = doc! {
+            "guild": bson_id!(guild_id),
+            "user": bson_id!(user_id),
+        };
+
+        let doc = self.find_one(filter).await?;
+        Ok(doc)
+    }
+
+    async fn record_result(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        day: i32,
+        won: bool,
+    ) -> Result<WordleStats> {
+        let filter = doc! {
+            "guild": bson_id!(guild_id),
+            "user": bson_id!(user_id),
+        };
+
+        let mut stats = self.find_one(filter.clone()).await?.unwrap_or_default();
+        stats.record(day, won);
+
+        let update = doc! {
+            "$setOnInsert": filter.clone(),
+            "$set": {
+                "played": stats.played,
+                "wins": stats.wins,
+                "streak": stats.streak,
+                "max_streak": stats.max_streak,
+                "last_win_day": stats.last_win_day,
+            },
+        };
+
+        self.update_one(filter, update).upsert(true).await?;
+        Ok(stats)
+    }
+}
+
+pub trait GameStatsExt {
+    async fn find_user_stats(&self, user_id: UserId) -> Result<Vec<GameStats>>;
+
+    /// Gets the user's rating in a game, or the default if they have none yet.
+    async fn find_rating(&self, user_id: UserId, game: GameKind) -> Result<i32>;
+
+    /// Counts a game's outcome for a user and sets their new rating, if any.
+    async fn record_result(
+        &self,
+        user_id: UserId,
+        game: GameKind,
+        outcome: Outcome,
+        rating: Option<i32>,
+    ) -> Result;
+}
+
+impl GameStatsExt for Collection<GameStats> {
+    async fn find_user_stats(&self, user_id: UserId) -> Result<Vec<GameStats>> {
+        let filter = doc! {
+            "user": bson_id!(user_id),
+        };
+
+        let docs = self.find(filter).await?.try_collect().await?;
+        Ok(docs)
+    }
+
+    async fn find_rating(&self, user_id: UserId, game: GameKind) -> Result<i32> {
+        let filter = doc! {
+            "user": bson_id!(user_id),
+            "game": bson::ser::to_bson(&game)?,
+        };
+
+        let doc = self.find_one(filter).await?;
+        Ok(doc.and_then(|s| s.rating).unwrap_or(DEFAULT_RATING))
+    }
+
+    async fn record_result(
+        &self,
+        user_id: UserId,
+        game: GameKind,
+        outcome: Outcome,
+        rating: Option<i32>,
+    ) -> Result {
+        let filter = doc! {
+            "user": bson_id!(user_id),
+            "game": bson::ser::to_bson(&game)?,
+        };
+
+        let field = match outcome {
+            Outcome::Win => "wins",
+            Outcome::Loss => "losses",
+            Outcome::Draw => "draws",
+        };
+
+        let mut update = doc! {
+            "$setOnInsert": filter.clone(),
+            "$inc": {
+                field: 1,
+            },
+        };
+
+        if let Some(rating) = rating {
+            update.insert("$set", doc! { "rating": rating });
+        }
+
+        self.update_one(filter, update).upsert(true).await?;
+        Ok(())
+    }
+}
+
+pub trait GameRecordExt {
+    /// Gets the user's most recent games, newest first.
+    async fn find_history(&self, user_id: UserId, limit: i64) -> Result<Vec<GameRecord>>;
+}
+
+impl GameRecordExt for Collection<GameRecord> {
+    async fn find_history(&self, user_id: UserId, limit: i64) -> Result<Vec<GameRecord>> {
+        let filter = doc! {
+            "players.user": bson_id!(user_id),
+        };
+
+        let sort = doc! {
+            "ended": -1,
+        };
+
+        let docs = self
+            .find(filter)
+            .sort(sort)
+            .limit(limit)
+            .await?
+            .try_collect()
+            .await?;
+
+        Ok(docs)
+    }
+}
+
+pub trait BlackjackWagerExt {
+    /// Claims a game for an action until `lease_until`.
+    ///
+    /// Returns `false` if the game is over or another action on it is still in
+    /// progress.
+    async fn claim(
+        &self,
+        game: i64,
+        now: DateTime<Utc>,
+        lease_until: DateTime<Utc>,
+    ) -> Result<bool>;
+
+    /// Releases the claim on a game, if it is still open.
+    async fn release(&self, game: i64) -> Result;
+
+    /// Adds to the cash taken for a game.
+    async fn add_stake(&self, game: i64, amount: i64) -> Result;
+
+    /// Removes a game so it can be paid out.
+    ///
+    /// Returns the wager if the game was still open before this call.
+    async fn close(&self, game: i64) -> Result<Option<BlackjackWager>>;
+
+    /// Finds the wager of a game that is still open.
+    async fn find_game(&self, game: i64) -> Result<Option<BlackjackWager>>;
+
+    /// Removes a game if no action on it happened since `idle_since`.
+    async fn take_expired(
+        &self,
+        game: i64,
+        idle_since: DateTime<Utc>,
+    ) -> Result<Option<BlackjackWager>>;
+}
+
+impl BlackjackWagerExt for Collection<BlackjackWager> {
+    async fn claim(
+        &self,
+        game: i64,
+        now: DateTime<Utc>,
+        lease_until: DateTime<Utc>,
+    ) -> Result<bool> {
+        let filter = doc! {
+            "game": game,
+            "claimed_until": {
+                "$lte": Bson::DateTime(now.into()),
+            },
+        };
+
+        let update = doc! {
+            "$set": {
+                "claimed_until": Bson::DateTime(lease_until.into()),
+            },
+        };
+
+        let res = self.update_one(filter, update).await?;
+        Ok(res.modified_count != 0)
+    }
+
+    async fn release(&self, game: i64) -> Result {
+        let update = doc! {
+            "$set": {
+                "claimed_until": Bson::DateTime(bson::DateTime::now()),
+            },
+        };
+
+        self.update_one(doc! { "game": game }, update).await?;
+        Ok(())
+    }
+
+    async fn add_stake(&self, game: i64, amount: i64) -> Result {
+        let update = doc! {
+            "$inc": {
+                "stake": amount,
+            },
+        };
+
+        self.update_one(doc! { "game": game }, update).await?;
+        Ok(())
+    }
+
+    async fn close(&self, game: i64) -> Result<Option<BlackjackWager>> {
+        let wager = self.find_one_and_delete(doc! { "game": game }).await?;
+        Ok(wager)
+    }
+
+    async fn find_game(&self, game: i64) -> Result<Option<BlackjackWager>> {
+        let wager = self.find_one(doc! { "game": game }).await?;
+        Ok(wager)
+    }
+
+    async fn take_expired(
+        &self,
+        game: i64,
+        idle_since: DateTime<Utc>,
+    ) -> Result<Option<BlackjackWager>> {
+        // claims move into the past when released, so this also skips games
+        // with an action in progress
+        let filter = doc! {
+            "game": game,
+            "claimed_until": {
+                "$lte": Bson::DateTime(idle_since.into()),
+            },
+        };
+
+        let wager = self.find_one_and_delete(filter).await?;
+        Ok(wager)
+    }
+}
+
+pub trait TimedGameStateExt {
+    /// Stores the current state of a game.
+    ///
+    /// The deadline only moves to `deadline` when the turn passed on to
+    /// another user. Returns whether the game wasn't stored yet.
+    async fn save_state(
+        &self,
+        message: MessageId,
+        game: bson::Binary,
+        token: &str,
+        turn: UserId,
+        started: DateTime<Utc>,
+        deadline: DateTime<Utc>,
+    ) -> Result<bool>;
+
+    /// Finds the stored state of a game.
+    async fn find_state(&self, message: MessageId) -> Result<Option<TimedGameState>>;
+
+    /// Removes a game if its active player has run out of time.
+    async fn take_expired(
+        &self,
+        message: MessageId,
+        now: DateTime<Utc>,
+    ) -> Result<Option<TimedGameState>>;
+
+    /// Removes a game, f.e. since it is over.
+    async fn remove(&self, message: MessageId) -> Result;
+}
+
+impl TimedGameStateExt for Collection<TimedGameState> {
+    async fn save_state(
+        &self,
+        message: MessageId,
+        game: bson::Binary,
+        token: &str,
+        turn: UserId,
+        started: DateTime<Utc>,
+        deadline: DateTime<Utc>,
+    ) -> Result<bool> {
+        let filter = doc! {
+            "message": bson_id!(message),
+        };
+
+        // a pipeline, so the deadline can depend on the stored turn
+        let turn = bson_id!(turn);
+        let update = vec![doc! {
+            "$set": {
+                "game": game,
+                "token": { "$literal": token },
+                "deadline": {
+                    "$cond": [
+                        { "$eq": ["$turn", turn.clone()] },
+                        "$deadline",
+                        Bson::DateTime(deadline.into()),
+                    ],
+                },
+                "turn": turn,
+                "started": { "$ifNull": ["$started", Bson::DateTime(started.into())] },
+            },
+        }];
+
+        let res = self.update_one(filter, update).upsert(true).await?;
+        Ok(res.upserted_id.is_some())
+    }
+
+    async fn find_state(&self, message: MessageId) -> Result<Option<TimedGameState>> {
+        let filter = doc! {
+            "message": bson_id!(message),
+        };
+
+        let game = self.find_one(filter).await?;
+        Ok(game)
+    }
+
+    async fn take_expired(
+        &self,
+        message: MessageId,
+        now: DateTime<Utc>,
+    ) -> Result<Option<TimedGameState>> {
+        let filter = doc! {
+            "message": bson_id!(message),
+            "deadline": {
+                "$lte": Bson::DateTime(now.into()),
+            },
+        };
+
+        let game = self.find_one_and_delete(filter).await?;
+        Ok(game)
+    }
+
+    async fn remove(&self, message: MessageId) -> Result {
+        self.delete_one(doc! { "message": bson_id!(message) })
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn streaks() {
+        let mut stats = WordleStats::default();
+
+        stats.record(10, true);
+        stats.record(11, true);
+        assert_eq!(stats.streak, 2);
+        assert_eq!(stats.current_streak(12), 2);
+
+        // skipping a day breaks the streak
+        assert_eq!(stats.current_streak(13), 0);
+        stats.record(13, true);
+        assert_eq!(stats.streak, 1);
+        assert_eq!(stats.max_streak, 2);
+
+        stats.record(14, false);
+        assert_eq!(stats.streak, 0);
+        assert_eq!(stats.current_streak(14), 0);
+        assert_eq!((stats.played, stats.wins), (4, 3));
+    }
+}
@@ -1,3 +1,7 @@
+use crate::modules::minigame::buttons::minesweeper::Size;
+use crate::modules::minigame::buttons::timeout::{self, TimedGame};
+use crate::modules::minigame::buttons::Difficulty;
+use crate::modules::minigame::stats::GameKind;
 use crate::slashies::prelude::*;
 
 /// Play games.
@@ -6,20 +10,25 @@ use crate::slashies::prelude::*;
     integration_types = "Guild | User"
 )]
 pub mod minigame {
-    /// Play tic-tac-toe with someone else.
+    /// Play tic-tac-toe with someone else or the bot.
     #[sub_command(name = "tic-tac-toe")]
     async fn tic_tac_toe(
         ctx: Context<'_>,
-        /// The user to play against.
-        opponent: &User,
+        /// The user to play against. Play against the bot if not set.
+        opponent: Option<&User>,
+        /// How well the bot plays. Defaults to normal.
+        difficulty: Option<Difficulty>,
     ) -> Result {
         use crate::modules::minigame::buttons::tic_tac_toe::View;
 
-        check_user(&ctx, opponent)?;
-        let players = [ctx.user().id, opponent.id];
-        let reply = View::new(players).create_next_reply(ctx.data_ref());
-        ctx.send(reply).await?;
-        Ok(())
+        let view = match get_opponent(&ctx, opponent, difficulty)? {
+            Opponent::User(players) => View::new(players),
+            Opponent::Bot(bot_user, difficulty) => {
+                View::new_vs_bot(ctx.user().id, bot_user, difficulty)
+            },
+        };
+
+        send_timed(ctx, TimedGame::TicTacToe(view)).await
     }
 
     /// Play rock-paper-scissors with someone else.
@@ -38,21 +47,226 @@ pub mod minigame {
         Ok(())
     }
 
-    /// Play, uh, "chess" with someone else.
+    /// Play, uh, "chess" with someone else or the bot.
     #[sub_command(name = "chess")]
     async fn chess(
+        ctx: Context<'_>,
+        /// The user to play against. Play against the bot if not set.
+        opponent: Option<&User>,
+        /// How well the bot plays. Defaults to normal.
+        difficulty: Option<Difficulty>,
+    ) -> Result {
+        use crate::modules::minigame::buttons::chess::View;
+
+        let view = match get_opponent(&ctx, opponent, difficulty)? {
+            Opponent::User(players) => View::new(players),
+            Opponent::Bot(bot_user, difficulty) => {
+                View::new_vs_bot(ctx.user().id, bot_user, difficulty)
+            },
+        };
+
+        send_timed(ctx, TimedGame::Chess(view)).await
+    }
+
+    /// Play Othello with someone else.
+    #[sub_command(name = "othello")]
+    async fn othello(
         ctx: Context<'_>,
         /// The user to play against.
         opponent: &User,
     ) -> Result {
-        use crate::modules::minigame::buttons::chess::View;
+        use crate::modules::minigame::buttons::othello::View;
 
         check_user(&ctx, opponent)?;
         let players = [ctx.user().id, opponent.id];
-        let reply = View::new(players).create_next_reply(ctx.data_ref());
+        send_timed(ctx, TimedGame::Othello(View::new(players))).await
+    }
+
+    /// Play blackjack against the house.
+    #[sub_command(name = "blackjack")]
+    async fn blackjack(
+        ctx: Context<'_>,
+        /// The amount of cash to bet. Only available in servers with perks.
+        #[min = 1]
+        wager: Option<u32>,
+    ) -> Result {
+        use crate::modules::minigame::buttons::blackjack::View;
+
+        let wager = match wager {
+            Some(wager) => Some((ctx.require_guild_id()?, wager)),
+            None => None,
+        };
+
+        let reply = View::start(ctx.data_ref(), ctx.user().id, wager).await?;
+        ctx.send(reply).await?;
+        Ok(())
+    }
+
+    /// Play Minesweeper by yourself.
+    #[sub_command]
+    async fn minesweeper(
+        ctx: Context<'_>,
+        /// The size of the board. Defaults to 5x5.
+        size: Option<Size>,
+    ) -> Result {
+        use crate::modules::minigame::buttons::minesweeper::View;
+
+        let size = size.unwrap_or(Size::Small);
+        let reply = View::new(ctx.user().id, size).create_next_reply(ctx.data_ref());
+        ctx.send(reply).await?;
+        Ok(())
+    }
+
+    /// Play trivia with everyone in the channel.
+    #[sub_command]
+    async fn trivia(
+        ctx: Context<'_>,
+        /// The question category. Picks a random one if not set.
+        #[autocomplete = "autocomplete_trivia_category"]
+        category: Option<&str>,
+        /// How many questions to ask. Defaults to 5.
+        #[min = 1]
+        #[max = 10]
+        rounds: Option<u8>,
+    ) -> Result {
+        use crate::modules::minigame::buttons::trivia::{find_category, Game};
+
+        let data = ctx.data_ref();
+        let config = data.config();
+        let trivia = config
+            .minigame
+            .trivia
+            .as_ref()
+            .context("trivia must be configured")?;
+
+        let category = find_category(trivia, category)?;
+        let game = Game::new(data, trivia, category, rounds.unwrap_or(5));
+
+        let handle = ctx.send(game.create_start_reply()).await?;
+        game.run(handle).await
+    }
+
+    /// Shows a user's minigame results.
+    #[sub_command]
+    async fn stats(
+        ctx: Context<'_>,
+        /// The user to view. Defaults to yourself.
+        user: Option<&User>,
+        /// Whether to show the response only to yourself.
+        ephemeral: Option<bool>,
+    ) -> Result {
+        use crate::modules::minigame::stats::create_stats_reply;
+
+        let user = user.unwrap_or(ctx.user());
+        ctx.defer_as(ephemeral).await?;
+
+        let reply = create_stats_reply(ctx.data_ref(), user).await?;
         ctx.send(reply).await?;
         Ok(())
     }
+
+    /// Shows a user's most recent minigames.
+    #[sub_command]
+    async fn history(
+        ctx: Context<'_>,
+        /// The user to view. Defaults to yourself.
+        user: Option<&User>,
+        /// Whether to show the response only to yourself.
+        ephemeral: Option<bool>,
+    ) -> Result {
+        use crate::modules::minigame::stats::create_history_reply;
+
+        let user = user.unwrap_or(ctx.user());
+        ctx.defer_as(ephemeral).await?;
+
+        let reply = create_history_reply(ctx.data_ref(), user).await?;
+        ctx.send(reply).await?;
+        Ok(())
+    }
+
+    /// Shows the best players of a minigame.
+    #[sub_command]
+    async fn leaderboard(
+        ctx: Context<'_>,
+        /// The game to show the leaderboard for.
+        game: GameKind,
+        /// Whether to show the response only to yourself.
+        ephemeral: Option<bool>,
+    ) -> Result {
+        use crate::modules::minigame::stats::create_leaderboard_reply;
+
+        ctx.defer_as(ephemeral).await?;
+
+        let reply = create_leaderboard_reply(ctx.data_ref(), game).await?;
+        ctx.send(reply).await?;
+        Ok(())
+    }
+
+    /// Play the daily word puzzle.
+    #[sub_command]
+    mod wordle {
+        /// Guess today's word. Everyone in the server gets the same one.
+        #[sub_command]
+        async fn play(ctx: Context<'_>) -> Result {
+            use crate::modules::minigame::buttons::wordle::View;
+
+            let guild = ctx.require_guild_id()?;
+            ctx.defer_as(Ephemeral).await?;
+
+            let reply = View::new(guild)
+                .create_reply(ctx.data_ref(), ctx.user().id)
+                .await?;
+
+            ctx.send(reply).await?;
+            Ok(())
+        }
+
+        /// Shows the server's best Wordle players.
+        #[sub_command]
+        async fn top(
+            ctx: Context<'_>,
+            /// Whether to show the response only to yourself.
+            ephemeral: Option<bool>,
+        ) -> Result {
+            use crate::modules::minigame::buttons::wordle::create_top_reply;
+
+            let guild = ctx.require_guild_id()?;
+            ctx.defer_as(ephemeral).await?;
+
+            let reply = create_top_reply(ctx.data_ref(), guild).await?;
+            ctx.send(reply).await?;
+            Ok(())
+        }
+    }
+}
+
+enum Opponent {
+    User([UserId; 2]),
+    Bot(UserId, Difficulty),
+}
+
+/// Gets who to play against in games that support the bot as an opponent.
+fn get_opponent(
+    ctx: &Context<'_>,
+    opponent: Option<&User>,
+    difficulty: Option<Difficulty>,
+) -> Result<Opponent> {
+    match opponent {
+        Some(opponent) => {
+            anyhow::ensure!(
+                difficulty.is_none(),
+                HArgError::new_const("The difficulty only applies when playing against the bot.")
+            );
+
+            check_user(ctx, opponent)?;
+            Ok(Opponent::User([ctx.user().id, opponent.id]))
+        },
+        None => {
+            let bot_user = ctx.data_ref().current_user()?.id;
+            let difficulty = difficulty.unwrap_or(Difficulty::Normal);
+            Ok(Opponent::Bot(bot_user, difficulty))
+        },
+    }
 }
 
 fn check_user(ctx: &Context<'_>, user: &User) -> Result {
@@ -66,3 +280,34 @@ fn check_user(ctx: &Context<'_>, user: &User) -> Result {
     );
     Ok(())
 }
+
+/// Sends a turn-based game and starts tracking it for turn timeouts.
+async fn send_timed(ctx: Context<'_>, game: TimedGame) -> Result {
+    let reply = game.clone().create_next_reply(ctx.data_ref());
+    ctx.send(reply).await?;
+
+    let message = ctx.interaction.get_response(ctx.http()).await?;
+    timeout::track(ctx.data_ref(), &message, &ctx.interaction.token, game).await;
+    Ok(())
+}
+
+async fn autocomplete_trivia_category<'a>(
+    ctx: Context<'a>,
+    partial: &'a str,
+) -> CreateAutocompleteResponse<'a> {
+    let choices: Vec<_> = ctx
+        .data_ref()
+        .config()
+        .minigame
+        .trivia
+        .iter()
+        .flat_map(|t| &t.categories)
+        .filter(|(_, category)| category.name.contains(partial))
+        .take(25)
+        .map(|(key, category)| {
+            AutocompleteChoice::new(category.name.clone(), Cow::Owned(key.clone()))
+        })
+        .collect();
+
+    CreateAutocompleteResponse::new().set_choices(choices)
+}
@@ -0,0 +1,388 @@
+//! ## Tracks the results of finished minigames.
+//!
+//! Stats are global per user rather than per server, since most games can
+//! also be played in DMs. Versus games additionally keep an Elo rating.
+//! Every finished game is also kept in the history.
+//!
+//! Recording is best-effort: without a database, nothing is tracked, and
+//! failures are only logged so they don't break the game itself.
+
+use bson::doc;
+use chrono::{DateTime, Utc};
+use utils::text::write_str::*;
+
+use crate::fmt::discord::TimeMentionable as _;
+use crate::fmt::time::HumanDuration;
+use crate::modules::minigame::model::*;
+use crate::prelude::*;
+
+/// The rating of players without any rated games.
+pub const DEFAULT_RATING: i32 = 1000;
+
+/// The maximum rating change from a single game.
+const K_FACTOR: f64 = 32.0;
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, houston_cmd::ChoiceArg,
+)]
+pub enum GameKind {
+    #[name = "Tic-Tac-Toe"]
+    TicTacToe,
+    #[name = "Rock-Paper-Scissors"]
+    RockPaperScissors,
+    #[name = "Chess"]
+    Chess,
+    #[name = "Othello"]
+    Othello,
+    #[name = "Blackjack"]
+    Blackjack,
+    #[name = "Minesweeper"]
+    Minesweeper,
+}
+
+impl GameKind {
+    pub fn all() -> &'static [Self] {
+        &[
+            Self::TicTacToe,
+            Self::RockPaperScissors,
+            Self::Chess,
+            Self::Othello,
+            Self::Blackjack,
+            Self::Minesweeper,
+        ]
+    }
+
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::TicTacToe => "Tic-Tac-Toe",
+            Self::RockPaperScissors => "Rock-Paper-Scissors",
+            Self::Chess => "Chess",
+            Self::Othello => "Othello",
+            Self::Blackjack => "Blackjack",
+            Self::Minesweeper => "Minesweeper",
+        }
+    }
+
+    /// Whether this game is played against another user and thus rated.
+    pub const fn is_versus(self) -> bool {
+        matches!(
+            self,
+            Self::TicTacToe | Self::RockPaperScissors | Self::Chess | Self::Othello
+        )
+    }
+}
+
+/// The outcome of a game from one player's view.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum Outcome {
+    Win,
+    Loss,
+    Draw,
+}
+
+impl Outcome {
+    /// Gets the outcome from the opponent's view.
+    pub const fn reverse(self) -> Self {
+        match self {
+            Self::Win => Self::Loss,
+            Self::Loss => Self::Win,
+            Self::Draw => Self::Draw,
+        }
+    }
+
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Win => "Win",
+            Self::Loss => "Loss",
+            Self::Draw => "Draw",
+        }
+    }
+
+    const fn score(self) -> f64 {
+        match self {
+            Self::Win => 1.0,
+            Self::Loss => 0.0,
+            Self::Draw => 0.5,
+        }
+    }
+}
+
+/// Calculates the new Elo rating of a player after a game.
+fn next_rating(rating: i32, opponent: i32, outcome: Outcome) -> i32 {
+    let diff = f64::from(opponent - rating) / 400.0;
+    let expected = 1.0 / (1.0 + 10f64.powf(diff));
+    let change = K_FACTOR * (outcome.score() - expected);
+
+    // the change is bounded by the K-factor, so this can't truncate
+    #[allow(clippy::cast_possible_truncation)]
+    let change = change.round() as i32;
+    rating + change
+}
+
+/// Records the result of a single-player game that started at `started`.
+pub async fn record_solo(
+    data: &HBotData,
+    kind: GameKind,
+    user: UserId,
+    outcome: Outcome,
+    started: DateTime<Utc>,
+) {
+    if data.config().mongodb_uri.is_none() {
+        return;
+    }
+
+    let result = async {
+        let db = data.database()?;
+        GameStats::collection(db)
+            .record_result(user, kind, outcome, None)
+            .await?;
+
+        let players = vec![GameRecordPlayer { user, outcome }];
+        insert_record(db, kind, players, started).await
+    };
+
+    if let Err(why) = result.await {
+        log::error!("Failed to record {kind:?} result: {why:?}");
+    }
+}
+
+/// Records the result of a versus game that started at `started` and updates
+/// both players' ratings.
+///
+/// The `outcome` is from the view of the first player.
+pub async fn record_versus(
+    data: &HBotData,
+    kind: GameKind,
+    players: [UserId; 2],
+    outcome: Outcome,
+    started: DateTime<Utc>,
+) {
+    if data.config().mongodb_uri.is_none() {
+        return;
+    }
+
+    let result = async {
+        let db = data.database()?;
+        let collection = GameStats::collection(db);
+
+        let r1 = collection.find_rating(players[0], kind).await?;
+        let r2 = collection.find_rating(players[1], kind).await?;
+
+        let outcome2 = outcome.reverse();
+        let r1_next = next_rating(r1, r2, outcome);
+        let r2_next = next_rating(r2, r1, outcome2);
+
+        collection
+            .record_result(players[0], kind, outcome, Some(r1_next))
+            .await?;
+        collection
+            .record_result(players[1], kind, outcome2, Some(r2_next))
+            .await?;
+
+        let players = vec![
+            GameRecordPlayer {
+                user: players[0],
+                outcome,
+            },
+            GameRecordPlayer {
+                user: players[1],
+                outcome: outcome2,
+            },
+        ];
+
+        insert_record(db, kind, players, started).await
+    };
+
+    if let Err(why) = result.await {
+        log::error!("Failed to record {kind:?} result: {why:?}");
+    }
+}
+
+async fn insert_record(
+    db: &mongodb::Database,
+    game: GameKind,
+    players: Vec<GameRecordPlayer>,
+    started: DateTime<Utc>,
+) -> Result {
+    let record = GameRecord {
+        _id: bson::oid::ObjectId::new(),
+        game,
+        players,
+        started,
+        ended: Utc::now(),
+    };
+
+    GameRecord::collection(db).insert_one(record).await?;
+    Ok(())
+}
+
+/// Creates the reply listing a user's stats for every game.
+pub async fn create_stats_reply(data: &HBotData, user: &User) -> Result<CreateReply<'static>> {
+    let db = data.database()?;
+    let stats = GameStats::collection(db).find_user_stats(user.id).await?;
+
+    let mut description = String::new();
+    for &kind in GameKind::all() {
+        let Some(item) = stats.iter().find(|s| s.game == kind) else {
+            continue;
+        };
+
+        write_str!(
+            description,
+            "**{}:** {}W / {}L / {}D",
+            kind.name(),
+            item.wins,
+            item.losses,
+            item.draws,
+        );
+
+        if let Some(rating) = item.rating {
+            write_str!(description, " \u{2E31} Elo {rating}");
+        }
+
+        description.push('\n');
+    }
+
+    let description = crate::fmt::written_or(description, "No games played yet.");
+
+    let author = format!("{}: Minigame Stats", user.display_name());
+    let author = CreateEmbedAuthor::new(author).icon_url(user.face());
+
+    let embed = CreateEmbed::new()
+        .author(author)
+        .color(data.config().embed_color)
+        .description(description);
+
+    Ok(CreateReply::new().embed(embed))
+}
+
+/// Creates the reply listing a user's most recent games.
+pub async fn create_history_reply(data: &HBotData, user: &User) -> Result<CreateReply<'static>> {
+    const HISTORY_COUNT: i64 = 10;
+
+    let db = data.database()?;
+    let history = GameRecord::collection(db)
+        .find_history(user.id, HISTORY_COUNT)
+        .await?;
+
+    let mut description = String::new();
+    for record in history {
+        let Some(this) = record.players.iter().find(|p| p.user == user.id) else {
+            continue;
+        };
+
+        write_str!(
+            description,
+            "{} **{}**: {}",
+            record.ended.relative(),
+            record.game.name(),
+            this.outcome.name(),
+        );
+
+        for other in record.players.iter().filter(|p| p.user != user.id) {
+            write_str!(description, " vs <@{}>", other.user);
+        }
+
+        let duration = HumanDuration::new(record.ended - record.started);
+        writeln_str!(description, " \u{2E31} {duration}");
+    }
+
+    let description = crate::fmt::written_or(description, "No games played yet.");
+
+    let author = format!("{}: Minigame History", user.display_name());
+    let author = CreateEmbedAuthor::new(author).icon_url(user.face());
+
+    let embed = CreateEmbed::new()
+        .author(author)
+        .color(data.config().embed_color)
+        .description(description);
+
+    Ok(CreateReply::new().embed(embed))
+}
+
+/// Creates the reply for a game's leaderboard.
+///
+/// Versus games are ranked by rating, others by their wins.
+pub async fn create_leaderboard_reply(
+    data: &HBotData,
+    kind: GameKind,
+) -> Result<CreateReply<'static>> {
+    const TOP_COUNT: i64 = 15;
+
+    let db = data.database()?;
+
+    let filter = doc! {
+        "game": bson::ser::to_bson(&kind)?,
+    };
+
+    let sort = if kind.is_versus() {
+        doc! {
+            "rating": -1,
+            "wins": -1,
+        }
+    } else {
+        doc! {
+            "wins": -1,
+            "losses": 1,
+        }
+    };
+
+    let mut cursor = GameStats::collection(db)
+        .find(filter)
+        .sort(sort)
+        .limit(TOP_COUNT)
+        .await?;
+
+    let mut description = String::new();
+    let mut index = 0u32;
+
+    while let Some(item) = cursor.try_next().await? {
+        index += 1;
+        write_str!(
+            description,
+            "{index}. <@{}>: {}W / {}L / {}D",
+            item.user,
+            item.wins,
+            item.losses,
+            item.draws,
+        );
+
+        if let Some(rating) = item.rating {
+            write_str!(description, " \u{2E31} Elo {rating}");
+        }
+
+        description.push('\n');
+    }
+
+    let description = crate::fmt::written_or(description, "<None>");
+
+    let embed = CreateEmbed::new()
+        .title(format!("{} Leaderboards", kind.name()))
+        .color(data.config().embed_color)
+        .description(description);
+
+    Ok(CreateReply::new().embed(embed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn equal_ratings() {
+        assert_eq!(next_rating(1000, 1000, Outcome::Win), 1016);
+        assert_eq!(next_rating(1000, 1000, Outcome::Loss), 984);
+        assert_eq!(next_rating(1000, 1000, Outcome::Draw), 1000);
+    }
+
+    #[test]
+    fn uneven_ratings() {
+        // the favorite gains little from a win, the underdog a lot
+        assert_eq!(next_rating(1400, 1000, Outcome::Win), 1403);
+        assert_eq!(next_rating(1000, 1400, Outcome::Win), 1029);
+
+        // draws pull the ratings closer together
+        assert_eq!(next_rating(1400, 1000, Outcome::Draw), 1387);
+        assert_eq!(next_rating(1000, 1400, Outcome::Draw), 1013);
+    }
+}
@@ -1,15 +1,28 @@
+use std::collections::BTreeMap;
+
 use serenity::futures::future::always_ready;
 use serenity::prelude::*;
 
 use crate::prelude::*;
 
+pub mod analytics;
+pub mod auto_publish;
 pub mod azur;
 pub mod core;
+pub mod guild_log;
 pub mod media_react;
 pub mod minigame;
+pub mod moderation;
 pub mod perks;
+pub mod polls;
 pub mod profile;
+pub mod quotes;
+pub mod scheduler;
 pub mod starboard;
+pub mod sticky;
+pub mod tags;
+pub mod translate;
+pub mod welcome;
 
 mod prelude {
     pub use serenity::prelude::*;
@@ -31,7 +44,7 @@ mod model_prelude {
     pub use serde::{Deserialize, Serialize};
     pub use serenity::model::id::*;
 
-    pub(crate) use crate::helper::bson::{bson_id, id_as_i64};
+    pub(crate) use crate::helper::bson::{bson_id, id_as_i64, opt_id_as_i64};
     pub use crate::prelude::*;
 }
 
@@ -46,6 +59,8 @@ pub struct Info {
     pub commands: Vec<HCommand>,
     /// DB initializer functions.
     pub db_init: Vec<DbInitFn>,
+    /// Names of the enabled modules.
+    pub modules: Vec<&'static str>,
 }
 
 impl Info {
@@ -54,19 +69,109 @@ impl Info {
             intents: GatewayIntents::empty(),
             commands: Vec::new(),
             db_init: Vec::new(),
+            modules: Vec::new(),
         }
     }
 
     pub fn load(&mut self, config: &config::HBotConfig) -> Result {
         core::Module.apply(self, config)?;
+        scheduler::Module.apply(self, config)?;
         azur::Module.apply(self, config)?;
         minigame::Module.apply(self, config)?;
         perks::Module.apply(self, config)?;
         media_react::Module.apply(self, config)?;
         profile::Module.apply(self, config)?;
         starboard::Module.apply(self, config)?;
+        tags::Module.apply(self, config)?;
+        polls::Module.apply(self, config)?;
+        moderation::Module.apply(self, config)?;
+        guild_log::Module.apply(self, config)?;
+        welcome::Module.apply(self, config)?;
+        quotes::Module.apply(self, config)?;
+        sticky::Module.apply(self, config)?;
+        auto_publish::Module.apply(self, config)?;
+        translate::Module.apply(self, config)?;
+        analytics::Module.apply(self, config)?;
         Ok(())
     }
+
+    /// Lists the differences to `new` that can only be applied by restarting.
+    pub fn restart_changes(&self, new: &Self) -> Vec<String> {
+        let mut changes = Vec::new();
+
+        if self.intents != new.intents {
+            changes.push(format!(
+                "Gateway intents changed from `{:?}` to `{:?}`.",
+                self.intents, new.intents
+            ));
+        }
+
+        for module in new.modules.iter().filter(|m| !self.modules.contains(m)) {
+            changes.push(format!("Module `{module}` was enabled."));
+        }
+
+        for module in self.modules.iter().filter(|m| !new.modules.contains(m)) {
+            changes.push(format!("Module `{module}` was disabled."));
+        }
+
+        let old_commands = command_descriptions(&self.commands);
+        let new_commands = command_descriptions(&new.commands);
+
+        for (name, description) in &new_commands {
+            match old_commands.get(name) {
+                None => changes.push(format!("Command `{name}` was added.")),
+                Some(old) if old != description => {
+                    changes.push(format!("Command `{name}` changed its description."));
+                },
+                Some(_) => {},
+            }
+        }
+
+        for name in old_commands
+            .keys()
+            .filter(|n| !new_commands.contains_key(*n))
+        {
+            changes.push(format!("Command `{name}` was removed."));
+        }
+
+        changes
+    }
+}
+
+/// Collects the full names of all commands and sub-commands, mapped to their
+/// descriptions.
+fn command_descriptions(commands: &[HCommand]) -> BTreeMap<String, &str> {
+    use houston_cmd::model::{CommandOption, CommandOptionData};
+
+    fn collect<'a>(map: &mut BTreeMap<String, &'a str>, prefix: &str, option: &'a CommandOption) {
+        let name = if prefix.is_empty() {
+            option.name.as_ref().to_owned()
+        } else {
+            format!("{prefix} {}", option.name)
+        };
+
+        if let CommandOptionData::Group(group) = &option.data {
+            for sub in group.sub_commands.as_ref() {
+                collect(map, &name, sub);
+            }
+        }
+
+        map.insert(name, &option.description);
+    }
+
+    let mut map = BTreeMap::new();
+    for command in commands {
+        collect(&mut map, "", &command.data);
+    }
+
+    map
+}
+
+/// Gets a short name for the module, i.e. its path within [`crate::modules`].
+fn module_name<M: ?Sized>() -> &'static str {
+    let name = std::any::type_name::<M>();
+    let name = name.strip_suffix("::Module").unwrap_or(name);
+    name.rsplit_once("::modules::").map_or(name, |(_, n)| n)
 }
 
 pub trait Module {
@@ -102,6 +207,7 @@ pub trait Module {
             self.validate(config)?;
             init.intents |= self.intents(config);
             init.commands.extend(self.commands(config));
+            init.modules.push(module_name::<Self>());
 
             if config.mongodb_uri.is_some() {
                 init.db_init.push(Self::db_init);
@@ -110,3 +216,15 @@ pub trait Module {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn module_name() {
+        assert_eq!(super::module_name::<super::core::Module>(), "core");
+        assert_eq!(
+            super::module_name::<super::auto_publish::Module>(),
+            "auto_publish"
+        );
+    }
+}
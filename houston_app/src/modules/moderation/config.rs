@@ -0,0 +1,45 @@
+use chrono::TimeDelta;
+
+use crate::helper::time::serde_time_delta;
+
+fn default_warn_notice() -> String {
+    "You were warned in **{guild}**: {reason}".to_owned()
+}
+
+fn default_timeout_notice() -> String {
+    "You were timed out in **{guild}** until {until}: {reason}".to_owned()
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct Config {
+    /// The notice to DM to warned members. Empty to not send any.
+    ///
+    /// `{guild}` and `{reason}` are replaced.
+    #[serde(default = "default_warn_notice")]
+    pub warn_notice: String,
+    /// The notice to DM to timed out members. Empty to not send any.
+    ///
+    /// `{guild}`, `{reason}`, and `{until}` are replaced.
+    #[serde(default = "default_timeout_notice")]
+    pub timeout_notice: String,
+    /// Timeouts applied automatically once members reach a warn count.
+    #[serde(default)]
+    pub escalation: Vec<Escalation>,
+}
+
+/// A timeout applied automatically at a certain warn count.
+#[derive(Debug, serde::Deserialize)]
+pub struct Escalation {
+    /// The warn count that triggers this step.
+    pub warns: u32,
+    /// How long to time out the member for.
+    #[serde(with = "serde_time_delta")]
+    pub timeout: TimeDelta,
+}
+
+impl Config {
+    /// Gets the escalation step for a member that just reached a warn count.
+    pub fn escalation_for(&self, warns: u64) -> Option<&Escalation> {
+        self.escalation.iter().find(|e| u64::from(e.warns) == warns)
+    }
+}
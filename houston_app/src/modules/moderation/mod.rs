@@ -0,0 +1,222 @@
+use bson::oid::ObjectId;
+use chrono::{DateTime, TimeDelta, Utc};
+use utils::text::write_str::*;
+
+use super::prelude::*;
+use crate::fmt::discord::TimeMentionable as _;
+use crate::fmt::replace_holes;
+
+pub mod config;
+pub mod model;
+mod slashies;
+
+pub use config::Config;
+use model::*;
+
+/// The longest timeout Discord allows.
+const MAX_TIMEOUT: TimeDelta = TimeDelta::days(28);
+
+pub struct Module;
+
+impl super::Module for Module {
+    fn enabled(&self, config: &HBotConfig) -> bool {
+        config.moderation.is_some()
+    }
+
+    fn commands(&self, _config: &HBotConfig) -> impl IntoIterator<Item = HCommand> {
+        [slashies::warn(), slashies::timeout(), slashies::modlog()]
+    }
+
+    fn db_init(db: &mongodb::Database) -> mongodb::BoxFuture<'_, Result> {
+        use crate::helper::bson::update_indices;
+        Box::pin(async move {
+            update_indices(Case::collection(db), Case::indices()).await?;
+            Ok(())
+        })
+    }
+
+    fn validate(&self, config: &HBotConfig) -> Result {
+        anyhow::ensure!(
+            config.mongodb_uri.is_some(),
+            "moderation requires a mongodb_uri"
+        );
+
+        let moderation = config.moderation()?;
+        for (index, step) in moderation.escalation.iter().enumerate() {
+            anyhow::ensure!(
+                step.warns != 0,
+                "moderation.escalation[{index}].warns must be at least 1"
+            );
+
+            anyhow::ensure!(
+                step.timeout >= TimeDelta::minutes(1) && step.timeout <= MAX_TIMEOUT,
+                "moderation.escalation[{index}].timeout must be between 1 minute and 28 days"
+            );
+
+            anyhow::ensure!(
+                moderation.escalation[..index]
+                    .iter()
+                    .all(|s| s.warns != step.warns),
+                "moderation.escalation has multiple steps for {} warns",
+                step.warns
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Records a warn for a member, notifies them, and applies any escalation.
+///
+/// Returns the member's new warn count and the end of the escalation timeout,
+/// if one was applied.
+async fn warn_member(
+    ctx: &Context,
+    guild_id: GuildId,
+    user_id: UserId,
+    moderator: UserId,
+    reason: &str,
+) -> Result<(u64, Option<DateTime<Utc>>)> {
+    let data = ctx.data_ref::<HContextData>();
+    let config = data.config();
+    let moderation = config.moderation()?;
+    let db = data.database()?;
+
+    let case = Case {
+        _id: ObjectId::new(),
+        guild: guild_id,
+        user: user_id,
+        moderator,
+        kind: CaseKind::Warn,
+        reason: reason.to_owned(),
+        duration: None,
+        time: Utc::now(),
+    };
+
+    Case::collection(db).insert_one(&case).await?;
+    log::info!("{moderator} warned {user_id} in {guild_id}: {reason}");
+
+    send_notice(
+        ctx,
+        guild_id,
+        user_id,
+        &moderation.warn_notice,
+        reason,
+        None,
+    )
+    .await;
+
+    let warns = Case::collection(db).count_warns(guild_id, user_id).await?;
+
+    let Some(step) = moderation.escalation_for(warns) else {
+        return Ok((warns, None));
+    };
+
+    let reason = format!("Reached {warns} warns.");
+    let until = timeout_member(ctx, guild_id, user_id, moderator, step.timeout, &reason).await?;
+    Ok((warns, Some(until)))
+}
+
+/// Times out a member, records the case, and notifies them.
+///
+/// Returns when the timeout ends.
+async fn timeout_member(
+    ctx: &Context,
+    guild_id: GuildId,
+    user_id: UserId,
+    moderator: UserId,
+    duration: TimeDelta,
+    reason: &str,
+) -> Result<DateTime<Utc>> {
+    let data = ctx.data_ref::<HContextData>();
+    let config = data.config();
+    let moderation = config.moderation()?;
+    let db = data.database()?;
+
+    let now = Utc::now();
+    let until = now
+        .checked_add_signed(duration)
+        .context("duration lasts beyond the end of time")?;
+
+    let edit = EditMember::new()
+        .disable_communication_until(until.into())
+        .audit_log_reason(reason);
+
+    if let Err(why) = guild_id.edit_member(&ctx.http, user_id, edit).await {
+        log::warn!("Failed to time out {user_id} in {guild_id}: {why:?}");
+        return Err(HArgError::new_const(
+            "I can't time out that member. Check my permissions and role position.",
+        )
+        .into());
+    }
+
+    let case = Case {
+        _id: ObjectId::new(),
+        guild: guild_id,
+        user: user_id,
+        moderator,
+        kind: CaseKind::Timeout,
+        reason: reason.to_owned(),
+        duration: Some(duration.num_seconds()),
+        time: now,
+    };
+
+    Case::collection(db).insert_one(&case).await?;
+    log::info!("{moderator} timed out {user_id} in {guild_id} until {until}: {reason}");
+
+    send_notice(
+        ctx,
+        guild_id,
+        user_id,
+        &moderation.timeout_notice,
+        reason,
+        Some(until),
+    )
+    .await;
+
+    Ok(until)
+}
+
+/// DMs a moderation notice to a user.
+///
+/// Failures are only logged since users may have their DMs closed.
+async fn send_notice(
+    ctx: &Context,
+    guild_id: GuildId,
+    user_id: UserId,
+    notice: &str,
+    reason: &str,
+    until: Option<DateTime<Utc>>,
+) {
+    if notice.is_empty() {
+        return;
+    }
+
+    let guild_name = match guild_id.to_partial_guild(&ctx.http).await {
+        Ok(guild) => guild.name.to_string(),
+        Err(why) => {
+            log::warn!("Failed to get guild {guild_id} for notice: {why:?}");
+            "the server".to_owned()
+        },
+    };
+
+    let notice = replace_holes(notice, |out, n| match n {
+        "guild" => out.push_str(&guild_name),
+        "reason" => out.push_str(reason),
+        "until" => match until {
+            Some(until) => write_str!(out, "{}", until.short_date_time()),
+            None => out.push(char::REPLACEMENT_CHARACTER),
+        },
+        _ => out.push(char::REPLACEMENT_CHARACTER),
+    });
+
+    let embed = CreateEmbed::new()
+        .description(notice)
+        .color(ctx.data_ref::<HContextData>().config().embed_color);
+
+    let dm = CreateMessage::new().embed(embed);
+
+    if let Err(why) = user_id.direct_message(&ctx.http, dm).await {
+        log::trace!("Could not DM {user_id} moderation notice: {why:?}");
+    }
+}
@@ -0,0 +1,102 @@
+use crate::modules::model_prelude::*;
+
+/// The kind of moderation action a case records.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CaseKind {
+    Warn,
+    Timeout,
+}
+
+impl CaseKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Warn => "Warn",
+            Self::Timeout => "Timeout",
+        }
+    }
+}
+
+/// A recorded moderation action against a user.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Case {
+    pub _id: ObjectId,
+    #[serde(with = "id_as_i64")]
+    pub guild: GuildId,
+    #[serde(with = "id_as_i64")]
+    pub user: UserId,
+    #[serde(with = "id_as_i64")]
+    pub moderator: UserId,
+    pub kind: CaseKind,
+    pub reason: String,
+    /// For timeouts, how long the timeout lasts, in seconds.
+    pub duration: Option<i64>,
+    #[serde(with = "chrono_datetime_as_bson_datetime")]
+    pub time: DateTime<Utc>,
+}
+
+fn name(name: &str) -> IndexOptions {
+    IndexOptions::builder().name(name.to_owned()).build()
+}
+
+impl Case {
+    pub fn collection(db: &Database) -> Collection<Self> {
+        db.collection("moderation.cases")
+    }
+
+    pub fn indices() -> Vec<IndexModel> {
+        vec![IndexModel::builder()
+            .options(name("guild-user-time"))
+            .keys(doc! {
+                "guild": 1,
+                "user": 1,
+                "time": -1,
+            })
+            .build()]
+    }
+}
+
+pub trait CaseExt {
+    async fn count_warns(&self, guild_id: GuildId, user_id: UserId) -> Result<u64>;
+
+    async fn find_recent(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        limit: i64,
+    ) -> Result<Vec<Case>>;
+}
+
+impl CaseExt for Collection<Case> {
+    async fn count_warns(&self, guild_id: GuildId, user_id: UserId) -> Result<u64> {
+        let filter = doc! {
+            "guild": bson_id!(guild_id),
+            "user": bson_id!(user_id),
+            "kind": "Warn",
+        };
+
+        let count = self.count_documents(filter).await?;
+        Ok(count)
+    }
+
+    async fn find_recent(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        limit: i64,
+    ) -> Result<Vec<Case>> {
+        let filter = doc! {
+            "guild": bson_id!(guild_id),
+            "user": bson_id!(user_id),
+        };
+
+        let cases = self
+            .find(filter)
+            .sort(doc! { "time": -1 })
+            .limit(limit)
+            .await?
+            .try_collect()
+            .await?;
+
+        Ok(cases)
+    }
+}
@@ -0,0 +1,181 @@
+use chrono::TimeDelta;
+use utils::text::write_str::*;
+
+use super::model::*;
+use crate::fmt::discord::TimeMentionable as _;
+use crate::slashies::prelude::*;
+
+/// The most cases shown by `/modlog`.
+const MODLOG_LIMIT: i64 = 20;
+
+/// Warns a member.
+#[chat_command(
+    default_member_permissions = "MODERATE_MEMBERS",
+    contexts = "Guild",
+    integration_types = "Guild"
+)]
+pub async fn warn(
+    ctx: Context<'_>,
+    /// The member to warn.
+    member: SlashMember<'_>,
+    /// Why the member is warned. Shown to them.
+    #[max_length = 512]
+    reason: &str,
+) -> Result {
+    let data = ctx.data_ref();
+    let guild_id = ctx.require_guild_id()?;
+    check_target(ctx, member)?;
+
+    ctx.defer_as(Ephemeral).await?;
+
+    let (warns, until) = super::warn_member(
+        ctx.serenity,
+        guild_id,
+        member.user.id,
+        ctx.user().id,
+        reason,
+    )
+    .await?;
+
+    let mut description = format!(
+        "Warned {}. They now have **{warns}** warn(s).",
+        member.mention()
+    );
+
+    if let Some(until) = until {
+        write_str!(
+            description,
+            "\nThey were automatically timed out until {}.",
+            until.short_date_time()
+        );
+    }
+
+    let embed = CreateEmbed::new()
+        .color(data.config().embed_color)
+        .description(description);
+
+    ctx.send(CreateReply::new().embed(embed)).await?;
+    Ok(())
+}
+
+/// Times out a member.
+#[chat_command(
+    default_member_permissions = "MODERATE_MEMBERS",
+    contexts = "Guild",
+    integration_types = "Guild"
+)]
+pub async fn timeout(
+    ctx: Context<'_>,
+    /// The member to time out.
+    member: SlashMember<'_>,
+    /// How long to time out the member for, in minutes.
+    #[min = 1]
+    #[max = 40320]
+    duration: u32,
+    /// Why the member is timed out. Shown to them.
+    #[max_length = 512]
+    reason: &str,
+) -> Result {
+    let data = ctx.data_ref();
+    let guild_id = ctx.require_guild_id()?;
+    check_target(ctx, member)?;
+
+    let duration = TimeDelta::try_minutes(i64::from(duration)).context("too many minutes")?;
+
+    ctx.defer_as(Ephemeral).await?;
+
+    let until = super::timeout_member(
+        ctx.serenity,
+        guild_id,
+        member.user.id,
+        ctx.user().id,
+        duration,
+        reason,
+    )
+    .await?;
+
+    let description = format!(
+        "Timed out {} until {}.",
+        member.mention(),
+        until.short_date_time()
+    );
+
+    let embed = CreateEmbed::new()
+        .color(data.config().embed_color)
+        .description(description);
+
+    ctx.send(CreateReply::new().embed(embed)).await?;
+    Ok(())
+}
+
+/// Shows a user's moderation history.
+#[chat_command(
+    default_member_permissions = "MODERATE_MEMBERS",
+    contexts = "Guild",
+    integration_types = "Guild"
+)]
+pub async fn modlog(
+    ctx: Context<'_>,
+    /// The user to show the history of.
+    user: SlashUser<'_>,
+) -> Result {
+    let data = ctx.data_ref();
+    let guild_id = ctx.require_guild_id()?;
+    let db = data.database()?;
+
+    ctx.defer_as(Ephemeral).await?;
+
+    let cases = Case::collection(db);
+    let warns = cases.count_warns(guild_id, user.user.id).await?;
+    let recent = cases
+        .find_recent(guild_id, user.user.id, MODLOG_LIMIT)
+        .await?;
+
+    let mut description = String::new();
+    for case in &recent {
+        write_str!(
+            description,
+            "- **{}** {} by <@{}>",
+            case.kind.name(),
+            case.time.short_date(),
+            case.moderator,
+        );
+
+        if let Some(duration) = case.duration {
+            write_str!(description, " for {} minutes", duration / 60);
+        }
+
+        writeln_str!(description, "\n  -# {}", case.reason);
+    }
+
+    if description.is_empty() {
+        description.push_str("No cases recorded.");
+    }
+
+    let title = format!("Moderation log for {}", user.user.name);
+    let footer = format!("{warns} warn(s) in total");
+
+    let embed = CreateEmbed::new()
+        .title(title)
+        .description(description)
+        .footer(CreateEmbedFooter::new(footer))
+        .color(data.config().embed_color);
+
+    ctx.send(CreateReply::new().embed(embed)).await?;
+    Ok(())
+}
+
+/// Ensures a member may be targeted by moderation commands.
+fn check_target(ctx: Context<'_>, member: SlashMember<'_>) -> Result {
+    anyhow::ensure!(
+        member.user.id != ctx.user().id,
+        HArgError::new_const("You can't do this to yourself.")
+    );
+
+    anyhow::ensure!(
+        !member.user.bot(),
+        HArgError::new_const("You can't do this to bots.")
+    );
+
+    Ok(())
+}
@@ -0,0 +1,96 @@
+use crate::buttons::prelude::*;
+use crate::helper::discord::id_as_u64;
+use crate::modules::perks::effects::Args;
+use crate::modules::perks::model::*;
+use crate::modules::perks::Item;
+
+/// Confirms gifting items to another member.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Confirm {
+    #[serde(with = "id_as_u64")]
+    to: UserId,
+    item: Item,
+    amount: u32,
+}
+
+impl Confirm {
+    pub fn new(to: UserId, item: Item, amount: u32) -> Self {
+        Self { to, item, amount }
+    }
+}
+
+impl ButtonArgsReply for Confirm {
+    async fn reply(self, ctx: ButtonContext<'_>) -> Result {
+        let guild_id = ctx.interaction.guild_id.context("requires guild")?;
+        let from = ctx.interaction.user.id;
+        let config = ctx.data.config();
+        let perks = config.perks()?;
+        let db = ctx.data.database()?;
+
+        // the config may have changed since the gift was started
+        let gift = perks
+            .gift
+            .as_ref()
+            .ok_or(HArgError::new_const("Gifting is not enabled."))?;
+
+        gift.check(self.item, self.amount)?;
+
+        ctx.acknowledge().await?;
+
+        let received = Wallet::collection(db)
+            .transfer_items(
+                guild_id,
+                from,
+                self.to,
+                self.item,
+                self.amount.into(),
+                perks,
+            )
+            .await?;
+
+        if let Some(wallet) = received {
+            let args = Args::new(ctx.serenity, guild_id, self.to);
+            let owned = wallet.item(self.item);
+            self.item.on_gain(args, owned, self.amount.into()).await?;
+        }
+
+        let name = self.item.info(perks).name;
+        log::info!(
+            "{} gifted {} {} to {} in {}.",
+            from,
+            self.amount,
+            name,
+            self.to,
+            guild_id
+        );
+
+        let description = format!(
+            "<@{from}> gifted **{} {name}** to <@{}>.",
+            self.amount, self.to
+        );
+
+        if let Some(channel) = gift.log_channel {
+            let embed = CreateEmbed::new()
+                .description(description.as_str())
+                .color(config.embed_color)
+                .timestamp(Timestamp::now());
+
+            let message = CreateMessage::new()
+                .embed(embed)
+                .allowed_mentions(CreateAllowedMentions::new());
+
+            if let Err(why) = channel.send_message(&ctx.serenity.http, message).await {
+                log::warn!("Failed to log gift in {channel}: {why:?}");
+            }
+        }
+
+        let embed = CreateEmbed::new()
+            .description(description)
+            .color(config.embed_color);
+
+        let reply = EditReply::new().embed(embed).components(&[]);
+
+        ctx.edit(reply).await?;
+        Ok(())
+    }
+}
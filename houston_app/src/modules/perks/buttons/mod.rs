@@ -1,2 +1,4 @@
 pub mod birthday;
+pub mod gift;
+pub mod history;
 pub mod shop;
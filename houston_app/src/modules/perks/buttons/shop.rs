@@ -67,7 +67,8 @@ impl View {
         user_id: UserId,
     ) -> Result<CreateReply<'_>> {
         let data = ctx.data_ref::<HContextData>();
-        let perks = data.config().perks()?;
+        let config = data.config();
+        let perks = config.perks()?;
         let db = data.database()?;
 
         let wallet = Wallet::collection(db)
@@ -102,7 +103,7 @@ impl View {
             let info = effect.info(perks);
 
             let custom_id = Self::with_action(Action::ViewEffect(effect)).to_custom_id();
-            let button = CreateButton::new(custom_id).label(truncate(info.name, 25));
+            let button = CreateButton::new(custom_id).label(truncate(info.name.to_owned(), 25));
 
             buttons.push(button);
 
@@ -134,7 +135,7 @@ impl View {
             let info = item.info(perks);
 
             let custom_id = Self::with_action(Action::ViewItem(item)).to_custom_id();
-            let button = CreateButton::new(custom_id).label(truncate(info.name, 25));
+            let button = CreateButton::new(custom_id).label(truncate(info.name.to_owned(), 25));
 
             buttons.push(button);
 
@@ -181,7 +182,8 @@ impl View {
         effect: Effect,
     ) -> Result<CreateReply<'_>> {
         let data = ctx.data_ref::<HContextData>();
-        let perks = data.config().perks()?;
+        let config = data.config();
+        let perks = config.perks()?;
         let db = data.database()?;
 
         let st = effect.price(perks).context("effect cannot be bought")?;
@@ -219,9 +221,9 @@ impl View {
         }
 
         let embed = base_shop_embed(perks, &wallet)
-            .title(truncate(info.name, 100))
+            .title(truncate(info.name.to_owned(), 100))
             .description(description)
-            .color(data.config().embed_color);
+            .color(config.embed_color);
 
         let back = Self::new().to_custom_id();
         let back = CreateButton::new(back).emoji('⏪').label("Back");
@@ -245,7 +247,8 @@ impl View {
         item: Item,
     ) -> Result<CreateReply<'_>> {
         let data = ctx.data_ref::<HContextData>();
-        let perks = data.config().perks()?;
+        let config = data.config();
+        let perks = config.perks()?;
         let db = data.database()?;
 
         let st = item.price(perks).context("effect cannot be bought")?;
@@ -272,9 +275,9 @@ impl View {
         }
 
         let embed = base_shop_embed(perks, &wallet)
-            .title(truncate(info.name, 100))
+            .title(truncate(info.name.to_owned(), 100))
             .description(description)
-            .color(data.config().embed_color);
+            .color(config.embed_color);
 
         let back = Self::new().to_custom_id();
         let back = CreateButton::new(back).emoji('⏪').label("Back");
@@ -324,7 +327,8 @@ impl View {
         effect: Effect,
     ) -> Result<CreateReply<'_>> {
         let data = ctx.data_ref::<HContextData>();
-        let perks = data.config().perks()?;
+        let config = data.config();
+        let perks = config.perks()?;
         let db = data.database()?;
 
         let args = Args::new(ctx, guild_id, user_id);
@@ -335,7 +339,14 @@ impl View {
         let st = effect.price(perks).context("effect cannot be bought")?;
 
         Wallet::collection(db)
-            .take_items(guild_id, user_id, Item::Cash, st.cost.into(), perks)
+            .take_items(
+                guild_id,
+                user_id,
+                Item::Cash,
+                st.cost.into(),
+                TxReason::new("perks", &format!("bought {}", effect.info(perks).name)),
+                perks,
+            )
             .await?;
 
         let until = Utc::now()
@@ -361,24 +372,28 @@ impl View {
         mult: u16,
     ) -> Result<CreateReply<'_>> {
         let data = ctx.data_ref::<HContextData>();
-        let perks = data.config().perks()?;
+        let config = data.config();
+        let perks = config.perks()?;
         let db = data.database()?;
 
         let st = item.price(perks).context("effect cannot be bought")?;
 
+        let reason = format!("bought {}", item.info(perks).name);
+        let reason = TxReason::new("perks", &reason);
+
         let cost = i64::from(st.cost) * i64::from(mult);
         Wallet::collection(db)
-            .take_items(guild_id, user_id, Item::Cash, cost, perks)
+            .take_items(guild_id, user_id, Item::Cash, cost, reason, perks)
             .await?;
 
         let amount = i64::from(st.amount) * i64::from(mult);
         let wallet = Wallet::collection(db)
-            .add_items(guild_id, user_id, item, amount)
+            .add_items(guild_id, user_id, item, amount, reason)
             .await?;
 
         let owned = wallet.item(item);
         let args = Args::new(ctx, guild_id, user_id);
-        item.on_buy(args, owned).await?;
+        item.on_gain(args, owned, amount).await?;
 
         self.action = Action::ViewItem(item);
         self.view_item(ctx, guild_id, user_id, item).await
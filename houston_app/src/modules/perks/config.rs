@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::sync::Arc;
 
 use chrono::{DateTime, NaiveDate, TimeDelta, Utc};
 use indexmap::IndexMap;
@@ -13,9 +14,9 @@ fn default_cash_name() -> String {
 }
 
 fn default_check_interval() -> TimeDelta {
-    // 2 minutes is about the minimum safe interval for constant role updates
-    // we go a little higher since we use this interval for other stuff too
-    const { TimeDelta::minutes(3) }
+    // effects with rate-limited updates, like the rainbow role,
+    // track their own interval, so this can be fairly short
+    const { TimeDelta::minutes(1) }
 }
 
 #[derive(Debug, serde::Deserialize)]
@@ -25,13 +26,31 @@ pub struct Config {
     #[serde(with = "serde_time_delta", default = "default_check_interval")]
     pub check_interval: TimeDelta,
     pub rainbow: Option<RainbowConfig>,
+    pub name_color: Option<NameColorConfig>,
     pub pushpin: Option<PushpinConfig>,
     pub role_edit: Option<RoleEditConfig>,
     pub collectible: Option<CollectibleConfig>,
     pub birthday: Option<BirthdayConfig>,
+    pub gift: Option<GiftConfig>,
+    #[serde(default)]
+    pub recipes: Vec<RecipeConfig>,
+}
 
-    #[serde(skip, default)]
-    pub last_check: RwLock<DateTime<Utc>>,
+impl Config {
+    /// Shares the runtime state of a previously loaded config.
+    pub fn carry_state_from(&mut self, previous: &Self) {
+        if let (Some(rainbow), Some(previous)) = (&mut self.rainbow, &previous.rainbow) {
+            rainbow.state = Arc::clone(&previous.state);
+        }
+
+        if let (Some(birthday), Some(previous)) = (&mut self.birthday, &previous.birthday) {
+            for region in &mut birthday.regions {
+                if let Some(previous) = previous.regions.iter().find(|r| r.name == region.name) {
+                    region.last_check = Arc::clone(&previous.last_check);
+                }
+            }
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, serde::Deserialize)]
@@ -60,6 +79,15 @@ fn default_rainbow_description() -> String {
     "A role with regularly changing color.".to_owned()
 }
 
+fn default_rainbow_interval() -> TimeDelta {
+    // 2 minutes is about the minimum safe interval for constant role updates
+    const { TimeDelta::minutes(3) }
+}
+
+fn default_rainbow_max_interval() -> TimeDelta {
+    const { TimeDelta::minutes(15) }
+}
+
 #[derive(Debug, serde::Deserialize)]
 pub struct RainbowConfig {
     #[serde(default = "default_rainbow_name")]
@@ -68,8 +96,28 @@ pub struct RainbowConfig {
     pub description: String,
     #[serde(flatten)]
     pub price: EffectPrice,
+    /// The minimum time between role color updates.
+    #[serde(with = "serde_time_delta", default = "default_rainbow_interval")]
+    pub interval: TimeDelta,
+    /// The longest the interval may grow to when rate limits are close.
+    #[serde(with = "serde_time_delta", default = "default_rainbow_max_interval")]
+    pub max_interval: TimeDelta,
     #[serde(flatten)]
     pub guilds: IndexMap<GuildId, RainbowRoleEntry>,
+
+    #[serde(skip, default)]
+    pub state: Arc<RwLock<RainbowState>>,
+}
+
+/// The runtime state of the rainbow role updates.
+#[derive(Debug, Default)]
+pub struct RainbowState {
+    /// The current interval, adjusted based on rate limit pressure.
+    ///
+    /// This is never less than [`RainbowConfig::interval`].
+    pub interval: TimeDelta,
+    /// When the role colors were last updated.
+    pub last_update: DateTime<Utc>,
 }
 
 #[derive(Debug, serde::Deserialize)]
@@ -77,6 +125,43 @@ pub struct RainbowRoleEntry {
     pub role: RoleId,
 }
 
+fn default_name_color_name() -> String {
+    "Name Color".to_owned()
+}
+
+fn default_name_color_description() -> String {
+    "Gives your name a color.".to_owned()
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct NameColorConfig {
+    #[serde(default = "default_name_color_name")]
+    pub name: String,
+    #[serde(default = "default_name_color_description")]
+    pub description: String,
+    #[serde(flatten)]
+    pub price: EffectPrice,
+    #[serde(flatten)]
+    pub guilds: IndexMap<GuildId, NameColorEntry>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct NameColorEntry {
+    pub palette: Vec<RoleId>,
+}
+
+impl NameColorEntry {
+    /// Gets the palette role for a user.
+    ///
+    /// Each user always gets the same color as long as the palette is
+    /// unchanged.
+    pub fn role_for(&self, user_id: UserId) -> Option<RoleId> {
+        let len = u64::try_from(self.palette.len()).ok()?;
+        let index = user_id.get().checked_rem(len)?;
+        self.palette.get(usize::try_from(index).ok()?).copied()
+    }
+}
+
 fn default_pushpin_name() -> String {
     "Pushpin".to_owned()
 }
@@ -155,7 +240,7 @@ pub struct BirthdayRegionConfig {
     pub time_offset: TimeDelta,
 
     #[serde(skip, default)]
-    pub last_check: RwLock<NaiveDate>,
+    pub last_check: Arc<RwLock<NaiveDate>>,
 }
 
 #[derive(Debug, serde::Deserialize)]
@@ -170,4 +255,46 @@ pub struct BirthdayGuildConfig {
 pub struct BirthdayNotice {
     pub channel: ChannelId,
     pub text: String,
+    /// A role to ping along with the announcement.
+    pub role: Option<RoleId>,
+}
+
+fn default_gift_max_amount() -> u32 {
+    1000
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct GiftConfig {
+    /// The most of an item that can be gifted at once.
+    #[serde(default = "default_gift_max_amount")]
+    pub max_amount: u32,
+    /// Items that cannot be gifted.
+    #[serde(default)]
+    pub blocked_items: Vec<Item>,
+    /// The channel to log gifts to.
+    pub log_channel: Option<ChannelId>,
+}
+
+impl GiftConfig {
+    /// Checks whether a gift is allowed by the config.
+    pub fn check(&self, item: Item, amount: u32) -> Result<(), HArgError> {
+        if self.blocked_items.contains(&item) {
+            return Err(HArgError::new_const("This item cannot be gifted."));
+        }
+
+        if amount > self.max_amount {
+            let msg = format!("You can gift at most {} at once.", self.max_amount);
+            return Err(HArgError::new(msg));
+        }
+
+        Ok(())
+    }
+}
+
+/// Combines items into another item.
+#[derive(Debug, serde::Deserialize)]
+pub struct RecipeConfig {
+    pub name: String,
+    pub ingredients: Vec<(Item, u32)>,
+    pub result: (Item, u32),
 }
@@ -33,6 +33,29 @@ impl DayOfYear {
         res
     }
 
+    /// Gets the date in the year this day next falls on, starting at `from`.
+    ///
+    /// In non-leap years, February 29th falls on March 1st.
+    pub fn next_date(self, from: NaiveDate) -> Option<NaiveDate> {
+        let (month, day) = self.into_month_day()?;
+        let in_year = |year| {
+            NaiveDate::from_ymd_opt(year, month.number_from_month(), day)
+                .or_else(|| NaiveDate::from_ymd_opt(year, 3, 1))
+        };
+
+        let date = in_year(from.year())?;
+        if date >= from {
+            Some(date)
+        } else {
+            in_year(from.year() + 1)
+        }
+    }
+
+    /// Gets the ordinal of this day in a leap year.
+    pub const fn ordinal(self) -> u16 {
+        self.0.get()
+    }
+
     fn into_date(self) -> Option<NaiveDate> {
         NaiveDate::from_yo_opt(Self::REF_YEAR, self.0.get().into())
     }
@@ -45,7 +68,7 @@ impl DayOfYear {
         Some((month, date.day()))
     }
 
-    fn from_date(date: NaiveDate) -> Option<Self> {
+    pub fn from_date(date: NaiveDate) -> Option<Self> {
         let date = date.with_year(Self::REF_YEAR)?;
         Self::from_ordinal(date.ordinal())
     }
@@ -122,6 +145,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn next_date() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        let dec_8 = DayOfYear::from_md(Month::December, 8).unwrap();
+
+        assert_eq!(dec_8.next_date(date(2024, 12, 8)), Some(date(2024, 12, 8)));
+        assert_eq!(dec_8.next_date(date(2024, 12, 9)), Some(date(2025, 12, 8)));
+        assert_eq!(
+            DayOfYear::FEB_29.next_date(date(2022, 1, 1)),
+            Some(date(2022, 3, 1))
+        );
+        assert_eq!(
+            DayOfYear::FEB_29.next_date(date(2023, 3, 2)),
+            Some(date(2024, 2, 29))
+        );
+    }
+
     #[test]
     fn non_leap_day_adjustment() {
         assert_eq!(
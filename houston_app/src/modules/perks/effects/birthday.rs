@@ -6,8 +6,9 @@ use chrono::prelude::*;
 use utils::text::write_str::*;
 
 use super::*;
+use crate::config::HBotConfig;
 use crate::fmt::replace_holes;
-use crate::modules::perks::config::BirthdayGuildConfig;
+use crate::modules::perks::config::{BirthdayGuildConfig, BirthdayRegionConfig};
 use crate::modules::perks::model::{self, *};
 use crate::modules::perks::DayOfYear;
 
@@ -16,16 +17,19 @@ pub struct Birthday;
 impl Shape for Birthday {
     async fn supported(&self, args: Args<'_>) -> Result<bool> {
         // this only errors if there is no role
-        Ok(get_guild_config(&args).is_ok())
+        let config = args.ctx.data_ref::<HContextData>().config();
+        Ok(get_guild_config(&config, &args).is_ok())
     }
 
     async fn enable(&self, args: Args<'_>, _state: Option<Bson>) -> Result {
-        let config = get_guild_config(&args)?;
-        let db = args.ctx.data_ref::<HContextData>().database()?;
+        let data = args.ctx.data_ref::<HContextData>();
+        let config = data.config();
+        let guild_config = get_guild_config(&config, &args)?;
+        let db = data.database()?;
 
         log::info!("Start birthday of {} in {}.", args.user_id, args.guild_id);
 
-        if let Some(role) = config.role {
+        if let Some(role) = guild_config.role {
             args.ctx
                 .http
                 .add_member_role(
@@ -37,36 +41,25 @@ impl Shape for Birthday {
                 .await?;
         }
 
-        for &(item, amount) in &config.gifts {
+        for &(item, amount) in &guild_config.gifts {
             Wallet::collection(db)
-                .add_items(args.guild_id, args.user_id, item, amount.into())
+                .add_items(
+                    args.guild_id,
+                    args.user_id,
+                    item,
+                    amount.into(),
+                    TxReason::new("perks", "birthday gift"),
+                )
                 .await?;
         }
 
-        if let Some(notice) = &config.notice {
-            let message = replace_holes(&notice.text, |out, n| match n {
-                "user" => write_str!(out, "<@{}>", args.user_id),
-                _ => out.push(char::REPLACEMENT_CHARACTER),
-            });
-
-            // ping the user but _no_ roles
-            let allowed_mentions = CreateAllowedMentions::new()
-                .users(slice::from_ref(&args.user_id))
-                .empty_roles();
-
-            let message = CreateMessage::new()
-                .content(message)
-                .allowed_mentions(allowed_mentions);
-
-            notice.channel.send_message(&args.ctx.http, message).await?;
-        }
-
         Ok(())
     }
 
     async fn disable(&self, args: Args<'_>) -> Result {
-        if let Ok(config) = get_guild_config(&args) {
-            if let Some(role) = config.role {
+        let config = args.ctx.data_ref::<HContextData>().config();
+        if let Ok(guild_config) = get_guild_config(&config, &args) {
+            if let Some(role) = guild_config.role {
                 let result = args
                     .ctx
                     .http
@@ -89,7 +82,8 @@ impl Shape for Birthday {
 
     async fn update(&self, ctx: &Context, now: DateTime<Utc>) -> Result {
         let data = ctx.data::<HContextData>();
-        let perks = data.config().perks()?;
+        let config = data.config();
+        let perks = config.perks()?;
         let Some(birthday) = &perks.birthday else {
             return Ok(());
         };
@@ -154,6 +148,10 @@ impl Shape for Birthday {
                         ActivePerk::collection(db)
                             .set_enabled(guild, user, Effect::Birthday, tomorrow)
                             .await?;
+
+                        if let Err(why) = announce(args, region).await {
+                            log::warn!("Failed to announce birthday of {user} in {guild}: {why:?}");
+                        }
                     } else {
                         log::trace!("User {user} not in {guild}");
                     }
@@ -167,14 +165,45 @@ impl Shape for Birthday {
     }
 }
 
+/// Posts the birthday announcement for a user, if one is configured.
+///
+/// This is only done when the birthday starts for the user's region, not
+/// when the perk is enabled manually.
+async fn announce(args: Args<'_>, region: &BirthdayRegionConfig) -> Result {
+    let config = args.ctx.data_ref::<HContextData>().config();
+    let Some(notice) = &get_guild_config(&config, &args)?.notice else {
+        return Ok(());
+    };
+
+    let message = replace_holes(&notice.text, |out, n| match (n, notice.role) {
+        ("user", _) => write_str!(out, "<@{}>", args.user_id),
+        ("role", Some(role)) => write_str!(out, "<@&{role}>"),
+        ("region", _) => out.push_str(&region.name),
+        _ => out.push(char::REPLACEMENT_CHARACTER),
+    });
+
+    // ping the user and only the configured role
+    let allowed_mentions = CreateAllowedMentions::new()
+        .users(slice::from_ref(&args.user_id))
+        .roles(notice.role.as_slice());
+
+    let message = CreateMessage::new()
+        .content(message)
+        .allowed_mentions(allowed_mentions);
+
+    notice.channel.send_message(&args.ctx.http, message).await?;
+    Ok(())
+}
+
 #[derive(Debug, Clone, thiserror::Error)]
 #[error("birthday rewards not configured for this guild")]
 struct NoBirthday;
 
-fn get_guild_config<'a>(args: &Args<'a>) -> Result<&'a BirthdayGuildConfig, NoBirthday> {
-    args.ctx
-        .data_ref::<HContextData>()
-        .config()
+fn get_guild_config<'a>(
+    config: &'a HBotConfig,
+    args: &Args<'_>,
+) -> Result<&'a BirthdayGuildConfig, NoBirthday> {
+    config
         .perks
         .as_ref()
         .ok_or(NoBirthday)?
@@ -5,6 +5,7 @@ use super::config::{Config, EffectPrice};
 use crate::modules::prelude::*;
 
 mod birthday;
+mod name_color;
 mod rainbow_role;
 
 #[derive(
@@ -13,6 +14,7 @@ mod rainbow_role;
 pub enum Effect {
     RainbowRole,
     Birthday,
+    NameColor,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -68,6 +70,7 @@ macro_rules! impl_kind_fn {
             match self {
                 Self::RainbowRole => rainbow_role::RainbowRole.$name($($args),*).await,
                 Self::Birthday => birthday::Birthday.$name($($args),*).await,
+                Self::NameColor => name_color::NameColor.$name($($args),*).await,
             }
         }
     };
@@ -80,7 +83,7 @@ impl Effect {
     impl_kind_fn!(update(args: &Context, now: DateTime<Utc>) -> Result);
 
     pub fn all() -> &'static [Self] {
-        &[Self::RainbowRole, Self::Birthday]
+        &[Self::RainbowRole, Self::Birthday, Self::NameColor]
     }
 
     pub fn info(self, perks: &Config) -> EffectInfo<'_> {
@@ -102,6 +105,14 @@ impl Effect {
                 name: "Birthday Haver",
                 description: "Party time.",
             },
+            Self::NameColor => perks
+                .name_color
+                .as_ref()
+                .map(|r| EffectInfo {
+                    name: &r.name,
+                    description: &r.description,
+                })
+                .unwrap_or(UNSET),
         }
     }
 
@@ -109,6 +120,7 @@ impl Effect {
         match self {
             Self::RainbowRole => perks.rainbow.as_ref().map(|r| r.price),
             Self::Birthday => None,
+            Self::NameColor => perks.name_color.as_ref().map(|r| r.price),
         }
     }
 }
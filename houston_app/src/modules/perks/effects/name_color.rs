@@ -0,0 +1,85 @@
+use anyhow::Context as _;
+
+use super::*;
+use crate::config::HBotConfig;
+use crate::modules::perks::config::{NameColorConfig, NameColorEntry};
+
+pub struct NameColor;
+
+impl Shape for NameColor {
+    async fn supported(&self, args: Args<'_>) -> Result<bool> {
+        // this only errors if there is no palette
+        let config = args.ctx.data_ref::<HContextData>().config();
+        Ok(find_palette(&config, &args).is_ok())
+    }
+
+    async fn enable(&self, args: Args<'_>, _state: Option<Bson>) -> Result {
+        let config = args.ctx.data_ref::<HContextData>().config();
+        let role = find_palette(&config, &args)?
+            .role_for(args.user_id)
+            .context("name color palette is empty")?;
+
+        args.ctx
+            .http
+            .add_member_role(
+                args.guild_id,
+                args.user_id,
+                role,
+                Some("enabled name color perk"),
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn disable(&self, args: Args<'_>) -> Result {
+        let config = args.ctx.data_ref::<HContextData>().config();
+        let Ok(entry) = find_palette(&config, &args) else {
+            return Ok(());
+        };
+
+        // the palette may have changed since it was enabled,
+        // so remove every palette role the member has
+        let member = args.ctx.http.get_member(args.guild_id, args.user_id).await;
+        let Some(member) = super::ok_allowed_discord_error(member)? else {
+            return Ok(());
+        };
+
+        for &role in entry.palette.iter().filter(|r| member.roles.contains(r)) {
+            let result = args
+                .ctx
+                .http
+                .remove_member_role(
+                    args.guild_id,
+                    args.user_id,
+                    role,
+                    Some("disabled name color perk"),
+                )
+                .await;
+
+            super::ok_allowed_discord_error(result)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("name color not configured")]
+struct NoNameColor;
+
+fn get_config(config: &HBotConfig) -> Result<&NameColorConfig, NoNameColor> {
+    config
+        .perks
+        .as_ref()
+        .ok_or(NoNameColor)?
+        .name_color
+        .as_ref()
+        .ok_or(NoNameColor)
+}
+
+fn find_palette<'a>(config: &'a HBotConfig, args: &Args<'_>) -> Result<&'a NameColorEntry> {
+    get_config(config)?
+        .guilds
+        .get(&args.guild_id)
+        .context("name color not configured for guild")
+}
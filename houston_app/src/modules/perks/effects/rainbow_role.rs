@@ -1,10 +1,15 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
 use anyhow::Context as _;
 use bson::doc;
 use chrono::prelude::*;
+use tokio::task::JoinSet;
 
 use super::*;
+use crate::config::HBotConfig;
 use crate::helper::bson::bson_id;
-use crate::modules::perks::config::{RainbowConfig, RainbowRoleEntry};
+use crate::modules::perks::config::RainbowConfig;
 use crate::modules::perks::model::*;
 
 pub struct RainbowRole;
@@ -23,7 +28,7 @@ impl Shape for RainbowRole {
             .add_member_role(
                 args.guild_id,
                 args.user_id,
-                role.role,
+                role,
                 Some("enabled rainbow role perk"),
             )
             .await?;
@@ -38,7 +43,7 @@ impl Shape for RainbowRole {
                 .remove_member_role(
                     args.guild_id,
                     args.user_id,
-                    role.role,
+                    role,
                     Some("disabled rainbow role perk"),
                 )
                 .await;
@@ -49,58 +54,129 @@ impl Shape for RainbowRole {
         Ok(())
     }
 
-    async fn update(&self, ctx: &Context, _now: DateTime<Utc>) -> Result {
-        const LOOP_TIME: i64 = 2400;
+    async fn update(&self, ctx: &Context, now: DateTime<Utc>) -> Result {
+        let config = ctx.data_ref::<HContextData>().config();
+        let Ok(rainbow) = get_config(&config) else {
+            return Ok(());
+        };
 
-        let Ok(rainbow) = get_config(ctx) else {
+        // if another update is still running, just skip this one
+        let Ok(mut state) = rainbow.state.try_write() else {
             return Ok(());
         };
 
-        let loop_sec = Utc::now()
-            .time()
-            .signed_duration_since(NaiveTime::MIN)
-            .num_seconds()
-            .rem_euclid(LOOP_TIME);
+        let interval = state.interval.max(rainbow.interval);
+        let next = state
+            .last_update
+            .checked_add_signed(interval)
+            .context("time has broken")?;
 
-        let loop_rel = loop_sec as f32 / LOOP_TIME as f32;
+        if now < next {
+            return Ok(());
+        }
 
-        let h = loop_rel * 360.0;
-        let s = match h {
-            220.0..240.0 => 1.0 - (h - 220.0) / 100.0,
-            240.0..260.0 => 0.8,
-            260.0..280.0 => 1.0 - (280.0 - h) / 100.0,
-            _ => 1.0,
-        };
-        let v = 1.0;
+        state.last_update = now;
 
-        let color = hsv_to_color(h, s, v);
+        let roles = find_active_roles(ctx, rainbow).await?;
+        if roles.is_empty() {
+            return Ok(());
+        }
 
-        for (&guild, entry) in &rainbow.guilds {
-            if has_any_rainbow_role(ctx, guild).await? {
+        let color = cycle_color(now);
+        let start = Instant::now();
+        let mut limited = false;
+
+        // edit all roles at once. they are in different guilds,
+        // so they don't share a rate limit bucket anyway.
+        let mut tasks = JoinSet::new();
+        for (guild, role) in roles {
+            let http = Arc::clone(&ctx.http);
+            tasks.spawn(async move {
                 let edit = EditRole::new()
                     .colour(color)
                     .audit_log_reason("rainbow role cycle");
 
-                let role = guild.edit_role(&ctx.http, entry.role, edit).await?;
-                log::trace!(
+                (guild, guild.edit_role(&http, role, edit).await)
+            });
+        }
+
+        while let Some(task) = tasks.join_next().await {
+            match task? {
+                (_, Ok(role)) => log::trace!(
                     "Updated rainbow role {} to color #{:06X}",
                     role.name,
                     color.0
-                );
+                ),
+                (guild, Err(why)) => {
+                    limited |= is_rate_limited(&why);
+                    log::warn!("Failed to update rainbow role in {guild}: {why:?}");
+                },
             }
         }
 
+        // serenity holds back requests when the rate limit headers say the bucket
+        // is exhausted, so a slow batch means we're sending updates too often
+        let pressure = limited || start.elapsed() > PRESSURE_DELAY;
+        state.interval = if pressure {
+            (interval * 2).min(rainbow.max_interval)
+        } else {
+            (interval / 2).max(rainbow.interval)
+        };
+
+        if state.interval != interval {
+            log::info!(
+                "Rainbow role interval changed to {}s.",
+                state.interval.num_seconds()
+            );
+        }
+
         Ok(())
     }
 }
 
+/// How long a batch of role edits may take before it is considered to be
+/// under rate limit pressure.
+const PRESSURE_DELAY: Duration = Duration::from_secs(5);
+
+fn cycle_color(now: DateTime<Utc>) -> Color {
+    const LOOP_TIME: i64 = 2400;
+
+    let loop_sec = now
+        .time()
+        .signed_duration_since(NaiveTime::MIN)
+        .num_seconds()
+        .rem_euclid(LOOP_TIME);
+
+    let loop_rel = loop_sec as f32 / LOOP_TIME as f32;
+
+    let h = loop_rel * 360.0;
+    let s = match h {
+        220.0..240.0 => 1.0 - (h - 220.0) / 100.0,
+        240.0..260.0 => 0.8,
+        260.0..280.0 => 1.0 - (280.0 - h) / 100.0,
+        _ => 1.0,
+    };
+    let v = 1.0;
+
+    hsv_to_color(h, s, v)
+}
+
+fn is_rate_limited(why: &serenity::Error) -> bool {
+    use serenity::http::HttpError;
+
+    matches!(
+        why,
+        serenity::Error::Http(HttpError::UnsuccessfulRequest(why))
+            if why.status_code.as_u16() == 429
+    )
+}
+
 #[derive(Debug, Clone, thiserror::Error)]
 #[error("rainbow role not configured")]
 struct NoRainbowRole;
 
-fn get_config(ctx: &Context) -> Result<&RainbowConfig, NoRainbowRole> {
-    ctx.data_ref::<HContextData>()
-        .config()
+fn get_config(config: &HBotConfig) -> Result<&RainbowConfig, NoRainbowRole> {
+    config
         .perks
         .as_ref()
         .ok_or(NoRainbowRole)?
@@ -109,24 +185,37 @@ fn get_config(ctx: &Context) -> Result<&RainbowConfig, NoRainbowRole> {
         .ok_or(NoRainbowRole)
 }
 
-fn find_rainbow_role<'a>(args: &Args<'a>) -> Result<&'a RainbowRoleEntry> {
-    get_config(args.ctx)?
+fn find_rainbow_role(args: &Args<'_>) -> Result<RoleId> {
+    let config = args.ctx.data_ref::<HContextData>().config();
+    let entry = get_config(&config)?
         .guilds
         .get(&args.guild_id)
-        .context("rainbow role not configured for guild")
+        .context("rainbow role not configured for guild")?;
+
+    Ok(entry.role)
 }
 
-async fn has_any_rainbow_role(ctx: &Context, guild_id: GuildId) -> Result<bool> {
+/// Finds the configured roles for all guilds with at least one active holder.
+async fn find_active_roles(
+    ctx: &Context,
+    rainbow: &RainbowConfig,
+) -> Result<Vec<(GuildId, RoleId)>> {
     let db = ctx.data_ref::<HContextData>().database()?;
 
     let filter = doc! {
-        "guild": bson_id!(guild_id),
         "effect": bson::ser::to_bson(&Effect::RainbowRole)?,
     };
 
-    let exists = ActivePerk::collection(db).find_one(filter).await?.is_some();
+    let active = ActivePerk::collection(db).distinct("guild", filter).await?;
+
+    let roles = rainbow
+        .guilds
+        .iter()
+        .filter(|(guild, _)| active.contains(&bson_id!(guild)))
+        .map(|(&guild, entry)| (guild, entry.role))
+        .collect();
 
-    Ok(exists)
+    Ok(roles)
 }
 
 #[allow(clippy::cast_possible_truncation)]
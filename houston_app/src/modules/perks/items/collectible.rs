@@ -8,18 +8,16 @@ use crate::fmt::replace_holes;
 pub struct Collectible;
 
 impl Shape for Collectible {
-    async fn on_buy(&self, args: Args<'_>, owned: i64) -> Result {
-        let config = args
-            .ctx
-            .data_ref::<HContextData>()
-            .config()
+    async fn on_gain(&self, args: Args<'_>, owned: i64, gained: i64) -> Result {
+        let config = args.ctx.data_ref::<HContextData>().config();
+        let collectible = config
             .perks()?
             .collectible
             .as_ref()
             .context("expected collectible config")?;
 
-        if let Some(guild_config) = config.guilds.get(&args.guild_id) {
-            let start = owned - i64::from(config.price.amount) + 1;
+        if let Some(guild_config) = collectible.guilds.get(&args.guild_id) {
+            let start = owned - gained + 1;
             let roles = guild_config
                 .prize_roles
                 .iter()
@@ -15,9 +15,11 @@ pub enum Item {
 }
 
 trait Shape {
-    async fn on_buy(&self, args: Args<'_>, owned: i64) -> Result {
+    /// Called after the user gained `gained` of this item, now owning `owned`.
+    async fn on_gain(&self, args: Args<'_>, owned: i64, gained: i64) -> Result {
         _ = args;
         _ = owned;
+        _ = gained;
         Ok(())
     }
 }
@@ -40,7 +42,7 @@ macro_rules! impl_kind_fn {
 }
 
 impl Item {
-    impl_kind_fn!(on_buy(args: Args<'_>, owned: i64) -> Result);
+    impl_kind_fn!(on_gain(args: Args<'_>, owned: i64, gained: i64) -> Result);
 
     pub fn all() -> &'static [Self] {
         &[Self::Cash, Self::Pushpin, Self::RoleEdit, Self::Collectible]
@@ -1,8 +1,10 @@
 use bson::{doc, Bson};
 use chrono::prelude::*;
+use chrono::TimeDelta;
 
 use super::prelude::*;
 use crate::helper::bson::doc_object_id;
+use crate::helper::discord::log_event_error;
 
 pub mod buttons;
 pub mod config;
@@ -52,6 +54,14 @@ impl super::Module for Module {
             c.push(edit);
         }
 
+        if perks.gift.is_some() {
+            c.push(slashies::gift::gift());
+        }
+
+        if !perks.recipes.is_empty() {
+            c.push(slashies::craft::craft());
+        }
+
         if perks.birthday.is_some() {
             c.push(slashies::birthday::birthday());
         }
@@ -67,6 +77,8 @@ impl super::Module for Module {
             update_indices(ActivePerk::collection(db), ActivePerk::indices()).await?;
             update_indices(UniqueRole::collection(db), UniqueRole::indices()).await?;
             update_indices(Birthday::collection(db), Birthday::indices()).await?;
+            update_indices(Transaction::collection(db), Transaction::indices()).await?;
+            update_indices(Transfer::collection(db), Transfer::indices()).await?;
             Ok(())
         })
     }
@@ -78,44 +90,92 @@ impl super::Module for Module {
         log::info!("Perks are enabled.");
 
         if let Some(r) = &perks.rainbow {
+            anyhow::ensure!(
+                r.interval >= TimeDelta::minutes(2),
+                "perks.rainbow.interval must be at least 2 minutes to avoid rate limits",
+            );
+
+            anyhow::ensure!(
+                r.max_interval >= r.interval,
+                "perks.rainbow.max_interval must not be less than the interval",
+            );
+
             log::trace!("Rainbow Role is enabled: {} guild(s)", r.guilds.len());
         }
 
+        if let Some(n) = &perks.name_color {
+            anyhow::ensure!(
+                n.guilds.values().all(|g| !g.palette.is_empty()),
+                "perks.name_color palettes must not be empty",
+            );
+
+            log::trace!("Name Color is enabled: {} guild(s)", n.guilds.len());
+        }
+
+        for recipe in &perks.recipes {
+            anyhow::ensure!(
+                !recipe.ingredients.is_empty()
+                    && recipe.ingredients.iter().all(|i| i.1 != 0)
+                    && recipe.result.1 != 0,
+                "perks recipe `{}` must have ingredients and all amounts must be non-zero",
+                recipe.name,
+            );
+        }
+
         Ok(())
     }
 }
 
-pub fn dispatch_check_perks(ctx: &Context) {
+/// Registers the recurring perk check with the scheduler.
+pub async fn register_jobs(ctx: &Context) -> Result {
+    use super::scheduler::{cancel_recurring, schedule_recurring, JobKind};
+
+    const KEY: &str = "perks.check";
+
     let data = ctx.data_ref::<HContextData>();
-    if Module.enabled(data.config()) {
-        tokio::task::spawn(check_perks_impl(ctx.clone()));
-    }
-}
+    let Ok(db) = data.database() else {
+        return Ok(());
+    };
 
-async fn check_perks_impl(ctx: Context) {
-    if let Err(why) = check_perks_core(ctx).await {
-        log::error!("Perk check failed: {why:?}");
+    match data.config().perks.as_ref() {
+        Some(perks) => {
+            schedule_recurring(db, KEY, &JobKind::PerksCheck, perks.check_interval).await
+        },
+        None => cancel_recurring(db, KEY).await,
     }
 }
 
-async fn check_perks_core(ctx: Context) -> Result {
-    let data = ctx.data_ref::<HContextData>();
-    let perks = data.config().perks()?;
-    let last = *perks.last_check.read().await;
-    let next = last
-        .checked_add_signed(perks.check_interval)
-        .context("time has broken")?;
+/// Finishes or drops item transfers that were interrupted by a restart.
+pub async fn recover_transfers(ctx: &Context) -> Result {
+    use model::{Wallet, WalletExt as _};
 
-    let now = Utc::now();
-    if now < next {
-        // no need to check yet
+    let data = ctx.data_ref::<HContextData>();
+    if data.config().perks.is_none() {
         return Ok(());
     }
 
-    // we hold this lock for the entire process
-    // so we can avoid others racing within this method
-    let mut last_check = perks.last_check.try_write()?;
-    *last_check = now;
+    // transfers from before the bot started can't still be in progress
+    let before = crate::helper::time::get_startup_time();
+    let db = data.database()?;
+    let received = Wallet::collection(db).recover_transfers(before).await?;
+
+    for (transfer, wallet) in received {
+        let args = effects::Args::new(ctx, transfer.guild, transfer.to);
+        let owned = wallet.item(transfer.item);
+        if let Err(why) = transfer.item.on_gain(args, owned, transfer.amount).await {
+            log::warn!("Failed to apply gift for {}: {why:?}", transfer.to);
+        }
+    }
+
+    Ok(())
+}
+
+/// Expires active perks and updates perk effects.
+///
+/// This is run by the scheduler.
+pub async fn check_perks(ctx: &Context) -> Result {
+    let data = ctx.data_ref::<HContextData>();
+    let now = Utc::now();
 
     // handle updates to the effects in parallel
     tokio::spawn({
@@ -123,7 +183,7 @@ async fn check_perks_core(ctx: Context) -> Result {
         async move {
             for kind in effects::Effect::all() {
                 if let Err(why) = kind.update(&ctx, now).await {
-                    log::error!("Failed update for perk effect {kind:?}: {why:?}");
+                    log_event_error!(why, "Failed update for perk effect {kind:?}");
                 }
             }
         }
@@ -141,7 +201,7 @@ async fn check_perks_core(ctx: Context) -> Result {
     let mut query = model::ActivePerk::collection(db).find(filter).await?;
 
     while let Some(perk) = query.try_next().await? {
-        let args = effects::Args::new(&ctx, perk.guild, perk.user);
+        let args = effects::Args::new(ctx, perk.guild, perk.user);
         perk.effect.disable(args).await?;
 
         model::ActivePerk::collection(db)
@@ -2,6 +2,7 @@ use super::effects::Effect;
 use super::items::Item;
 use super::DayOfYear;
 use crate::data::HArgError;
+use crate::helper::bson::doc_object_id;
 use crate::modules::model_prelude::*;
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -20,6 +21,9 @@ pub struct Wallet {
     pub role_edit: i64,
     #[serde(default)]
     pub crab: i64,
+    /// Transfers applied to this wallet that haven't finished yet.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pending: Vec<ObjectId>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -55,6 +59,59 @@ pub struct Birthday {
     pub day_of_year: DayOfYear,
 }
 
+/// A single change to a wallet.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Transaction {
+    pub _id: ObjectId,
+    #[serde(with = "id_as_i64")]
+    pub guild: GuildId,
+    #[serde(with = "id_as_i64")]
+    pub user: UserId,
+    pub item: Item,
+    pub delta: i64,
+    pub source: String,
+    pub reason: String,
+    #[serde(with = "chrono_datetime_as_bson_datetime")]
+    pub time: DateTime<Utc>,
+}
+
+/// A transfer of items between two wallets.
+///
+/// Both wallet updates are tagged with the transfer's ID, so a transfer that
+/// was interrupted can be finished later without applying either one twice.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Transfer {
+    pub _id: ObjectId,
+    #[serde(with = "id_as_i64")]
+    pub guild: GuildId,
+    #[serde(with = "id_as_i64")]
+    pub from: UserId,
+    #[serde(with = "id_as_i64")]
+    pub to: UserId,
+    pub item: Item,
+    pub amount: i64,
+    /// Whether both wallets have been updated.
+    #[serde(default)]
+    pub done: bool,
+    #[serde(with = "chrono_datetime_as_bson_datetime")]
+    pub created: DateTime<Utc>,
+}
+
+/// Describes why a wallet changed, for its transaction history.
+#[derive(Debug, Clone, Copy)]
+pub struct TxReason<'a> {
+    /// The module that changed the wallet.
+    pub source: &'static str,
+    /// What the change was for.
+    pub reason: &'a str,
+}
+
+impl<'a> TxReason<'a> {
+    pub const fn new(source: &'static str, reason: &'a str) -> Self {
+        Self { source, reason }
+    }
+}
+
 fn name(name: &str) -> IndexOptions {
     IndexOptions::builder().name(name.to_owned()).build()
 }
@@ -123,6 +180,38 @@ impl UniqueRole {
     }
 }
 
+impl Transaction {
+    pub fn collection(db: &Database) -> Collection<Self> {
+        db.collection("perks.transactions")
+    }
+
+    pub fn indices() -> Vec<IndexModel> {
+        vec![IndexModel::builder()
+            .options(name("guild-user-time"))
+            .keys(doc! {
+                "guild": 1,
+                "user": 1,
+                "time": -1,
+            })
+            .build()]
+    }
+}
+
+impl Transfer {
+    pub fn collection(db: &Database) -> Collection<Self> {
+        db.collection("perks.transfers")
+    }
+
+    pub fn indices() -> Vec<IndexModel> {
+        vec![IndexModel::builder()
+            .options(name("created"))
+            .keys(doc! {
+                "created": 1,
+            })
+            .build()]
+    }
+}
+
 impl Birthday {
     pub fn collection(db: &Database) -> Collection<Self> {
         db.collection("perks.birthday")
@@ -154,6 +243,7 @@ pub trait WalletExt {
         user_id: UserId,
         item: Item,
         amount: i64,
+        reason: TxReason<'_>,
     ) -> Result<Wallet>;
 
     async fn take_items(
@@ -162,8 +252,31 @@ pub trait WalletExt {
         user_id: UserId,
         item: Item,
         amount: i64,
+        reason: TxReason<'_>,
         perks: &super::config::Config,
     ) -> Result<Wallet>;
+
+    async fn transfer_items(
+        &self,
+        guild_id: GuildId,
+        from: UserId,
+        to: UserId,
+        item: Item,
+        amount: i64,
+        perks: &super::config::Config,
+    ) -> Result<Option<Wallet>>;
+
+    async fn finish_transfer(&self, transfer: &Transfer) -> Result<Option<Wallet>>;
+
+    async fn recover_transfers(&self, before: DateTime<Utc>) -> Result<Vec<(Transfer, Wallet)>>;
+
+    async fn craft_items(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        recipe: &super::config::RecipeConfig,
+        times: u32,
+    ) -> Result<Wallet>;
 }
 
 macro_rules! make_item_accessors {
@@ -191,6 +304,41 @@ make_item_accessors!(
     Collectible => crab,
 );
 
+/// Gets the database a wallet collection belongs to.
+fn database_of(wallets: &Collection<Wallet>) -> Database {
+    let namespace = wallets.namespace();
+    wallets.client().database(&namespace.db)
+}
+
+/// Records a change to a wallet in its transaction history.
+///
+/// The change itself already happened, so failing to record it only logs.
+async fn record_transaction(
+    wallets: &Collection<Wallet>,
+    guild_id: GuildId,
+    user_id: UserId,
+    item: Item,
+    delta: i64,
+    reason: TxReason<'_>,
+) {
+    let db = database_of(wallets);
+
+    let transaction = Transaction {
+        _id: ObjectId::new(),
+        guild: guild_id,
+        user: user_id,
+        item,
+        delta,
+        source: reason.source.to_owned(),
+        reason: reason.reason.to_owned(),
+        time: Utc::now(),
+    };
+
+    if let Err(why) = Transaction::collection(&db).insert_one(transaction).await {
+        log::warn!("Failed to record transaction for {user_id} in {guild_id}: {why:?}");
+    }
+}
+
 impl WalletExt for Collection<Wallet> {
     async fn add_items(
         &self,
@@ -198,6 +346,7 @@ impl WalletExt for Collection<Wallet> {
         user_id: UserId,
         item: Item,
         amount: i64,
+        reason: TxReason<'_>,
     ) -> Result<Wallet> {
         let key = item_to_key(item);
 
@@ -223,6 +372,7 @@ impl WalletExt for Collection<Wallet> {
             .await?
             .context("cannot return none after upsert")?;
 
+        record_transaction(self, guild_id, user_id, item, amount, reason).await;
         Ok(doc)
     }
 
@@ -232,6 +382,7 @@ impl WalletExt for Collection<Wallet> {
         user_id: UserId,
         item: Item,
         amount: i64,
+        reason: TxReason<'_>,
         perks: &super::config::Config,
     ) -> Result<Wallet> {
         let key = item_to_key(item);
@@ -262,6 +413,277 @@ impl WalletExt for Collection<Wallet> {
                 ))
             })?;
 
+        record_transaction(self, guild_id, user_id, item, -amount, reason).await;
+        Ok(doc)
+    }
+
+    /// Moves items from one wallet to another.
+    ///
+    /// The items are only taken if the sender has enough of them. The transfer
+    /// is recorded first, so if giving them to the receiver is interrupted, it
+    /// is finished by [`Self::recover_transfers`].
+    ///
+    /// Returns the receiver's wallet after they were given the items.
+    async fn transfer_items(
+        &self,
+        guild_id: GuildId,
+        from: UserId,
+        to: UserId,
+        item: Item,
+        amount: i64,
+        perks: &super::config::Config,
+    ) -> Result<Option<Wallet>> {
+        // both updates are tagged with the same id, which would skip the second
+        anyhow::ensure!(from != to, "cannot transfer items to the same wallet");
+
+        let transfers = Transfer::collection(&database_of(self));
+        let transfer = Transfer {
+            _id: ObjectId::new(),
+            guild: guild_id,
+            from,
+            to,
+            item,
+            amount,
+            done: false,
+            created: Utc::now(),
+        };
+
+        transfers.insert_one(&transfer).await?;
+
+        let key = item_to_key(item);
+        let filter = doc! {
+            "guild": bson_id!(guild_id),
+            "user": bson_id!(from),
+            key: {
+                "$gte": amount,
+            },
+            "pending": {
+                "$ne": transfer._id,
+            },
+        };
+
+        let update = doc! {
+            "$inc": {
+                key: -amount,
+            },
+            "$push": {
+                "pending": transfer._id,
+            },
+        };
+
+        // if this fails, it's unknown whether the items were taken,
+        // so the transfer is left for recovery
+        let taken = self.update_one(filter, update).await?;
+        if taken.modified_count == 0 {
+            // nothing was taken, so the transfer can just be dropped
+            transfers.delete_one(doc_object_id!(transfer)).await?;
+            return Err(HArgError::new(format!(
+                "You need {} {} to do this.",
+                amount,
+                item.info(perks).name,
+            ))
+            .into());
+        }
+
+        let sent = format!("gift to {to}");
+        let reason = TxReason::new("perks", &sent);
+        record_transaction(self, guild_id, from, item, -amount, reason).await;
+
+        self.finish_transfer(&transfer).await
+    }
+
+    /// Gives the items of a transfer to the receiver, unless that already
+    /// happened, and then removes the transfer.
+    ///
+    /// The items must already have been taken from the sender. Returns the
+    /// receiver's wallet if this call gave them the items.
+    async fn finish_transfer(&self, transfer: &Transfer) -> Result<Option<Wallet>> {
+        let transfers = Transfer::collection(&database_of(self));
+        let mut received = None;
+
+        if !transfer.done {
+            // create the wallet first, since the update below must not upsert
+            // a second wallet if the receiver already got the items
+            let filter = doc! {
+                "guild": bson_id!(transfer.guild),
+                "user": bson_id!(transfer.to),
+            };
+
+            let update = doc! {
+                "$setOnInsert": {
+                    "guild": bson_id!(transfer.guild),
+                    "user": bson_id!(transfer.to),
+                },
+            };
+
+            self.update_one(filter.clone(), update).upsert(true).await?;
+
+            let mut filter = filter;
+            filter.insert("pending", doc! { "$ne": transfer._id });
+
+            let key = item_to_key(transfer.item);
+            let update = doc! {
+                "$inc": {
+                    key: transfer.amount,
+                },
+                "$push": {
+                    "pending": transfer._id,
+                },
+            };
+
+            received = self
+                .find_one_and_update(filter, update)
+                .return_document(ReturnDocument::After)
+                .await?;
+
+            if received.is_some() {
+                let reason = format!("gift from {}", transfer.from);
+                let reason = TxReason::new("perks", &reason);
+                record_transaction(
+                    self,
+                    transfer.guild,
+                    transfer.to,
+                    transfer.item,
+                    transfer.amount,
+                    reason,
+                )
+                .await;
+            }
+
+            let update = doc! {
+                "$set": {
+                    "done": true,
+                },
+            };
+
+            transfers
+                .update_one(doc_object_id!(transfer), update)
+                .await?;
+        }
+
+        // both wallets are updated, so the tags aren't needed anymore
+        let filter = doc! {
+            "guild": bson_id!(transfer.guild),
+            "user": {
+                "$in": [bson_id!(transfer.from), bson_id!(transfer.to)],
+            },
+        };
+
+        let update = doc! {
+            "$pull": {
+                "pending": transfer._id,
+            },
+        };
+
+        self.update_many(filter, update).await?;
+        transfers.delete_one(doc_object_id!(transfer)).await?;
+        Ok(received)
+    }
+
+    /// Finishes or drops transfers created before `before` that were
+    /// interrupted.
+    ///
+    /// `before` must be early enough that no transfer created before it can
+    /// still be in progress, f.e. the time the bot started. Returns the
+    /// transfers whose receiver was given the items, along with their wallet.
+    async fn recover_transfers(&self, before: DateTime<Utc>) -> Result<Vec<(Transfer, Wallet)>> {
+        let transfers = Transfer::collection(&database_of(self));
+        let mut received = Vec::new();
+
+        let filter = doc! {
+            "created": {
+                "$lt": Bson::DateTime(before.into()),
+            },
+        };
+
+        let mut cursor = transfers.find(filter).await?;
+        while let Some(transfer) = cursor.try_next().await? {
+            // if the sender is tagged, the items were taken and need to be given
+            let filter = doc! {
+                "guild": bson_id!(transfer.guild),
+                "user": bson_id!(transfer.from),
+                "pending": transfer._id,
+            };
+
+            if transfer.done || self.find_one(filter).await?.is_some() {
+                log::info!("Finishing interrupted transfer {}.", transfer._id);
+                if let Some(wallet) = self.finish_transfer(&transfer).await? {
+                    received.push((transfer, wallet));
+                }
+            } else {
+                log::info!("Dropping interrupted transfer {}.", transfer._id);
+                transfers.delete_one(doc_object_id!(transfer)).await?;
+            }
+        }
+
+        Ok(received)
+    }
+
+    /// Consumes the ingredients of a recipe `times` times and adds the result.
+    ///
+    /// This is a single update, so either the user has every ingredient and
+    /// the whole recipe is applied, or nothing changes.
+    async fn craft_items(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        recipe: &super::config::RecipeConfig,
+        times: u32,
+    ) -> Result<Wallet> {
+        let times = i64::from(times);
+
+        // sum up the changes per key first
+        // the same item may be listed more than once or also be the result
+        let mut need = Document::new();
+        let mut change = Document::new();
+        let mut deltas: Vec<(Item, i64)> = Vec::new();
+        let mut add_delta = |item: Item, delta: i64| match deltas.iter_mut().find(|d| d.0 == item) {
+            Some(entry) => entry.1 += delta,
+            None => deltas.push((item, delta)),
+        };
+
+        for &(item, amount) in &recipe.ingredients {
+            let key = item_to_key(item);
+            let amount = i64::from(amount) * times;
+            let total = need.get_i64(key).unwrap_or(0) + amount;
+            need.insert(key, total);
+            change.insert(key, -total);
+            add_delta(item, -amount);
+        }
+
+        let (result, amount) = recipe.result;
+        let key = item_to_key(result);
+        let total = change.get_i64(key).unwrap_or(0) + i64::from(amount) * times;
+        change.insert(key, total);
+        add_delta(result, i64::from(amount) * times);
+
+        let mut filter = doc! {
+            "guild": bson_id!(guild_id),
+            "user": bson_id!(user_id),
+        };
+
+        for (key, amount) in need {
+            filter.insert(key, doc! { "$gte": amount });
+        }
+
+        let update = doc! {
+            "$inc": change,
+        };
+
+        let doc = self
+            .find_one_and_update(filter, update)
+            .return_document(ReturnDocument::After)
+            .await?
+            .ok_or(HArgError::new_const(
+                "You don't have enough ingredients to do this.",
+            ))?;
+
+        let text = format!("crafted {}", recipe.name);
+        for (item, delta) in deltas.into_iter().filter(|d| d.1 != 0) {
+            let reason = TxReason::new("perks", &text);
+            record_transaction(self, guild_id, user_id, item, delta, reason).await;
+        }
+
         Ok(doc)
     }
 }
@@ -1,14 +1,19 @@
 use bson::doc;
 use chrono::*;
 use mongodb::options::ReturnDocument;
+use utils::text::write_str::*;
 
 use crate::buttons::ToCustomData;
+use crate::config::HBotConfig;
 use crate::helper::bson::bson_id;
 use crate::modules::perks::config::BirthdayRegionConfig;
 use crate::modules::perks::model::*;
 use crate::modules::perks::DayOfYear;
 use crate::slashies::prelude::*;
 
+/// How long the birthday confirmation stays usable.
+const CONFIRM_TTL: TimeDelta = TimeDelta::minutes(15);
+
 /// Manage your birthday.
 #[chat_command(contexts = "Guild | BotDm", integration_types = "Guild")]
 pub mod birthday {
@@ -44,7 +49,7 @@ pub mod birthday {
         }
 
         let region = region.unwrap_or(0);
-        _ = get_region(ctx, region)?;
+        _ = get_region(&data.config(), region)?;
 
         let day_of_year = DayOfYear::from_md(month.convert(), day)
             .ok_or(HArgError::new_const("That date is not valid."))?;
@@ -64,7 +69,7 @@ pub mod birthday {
         use crate::modules::perks::buttons::birthday::Set;
 
         let components = CreateActionRow::buttons(vec![
-            CreateButton::new(Set::new(day_of_year, region).to_custom_id())
+            CreateButton::new(Set::new(day_of_year, region).to_expiring_custom_id(CONFIRM_TTL))
                 .label("Confirm")
                 .style(ButtonStyle::Success),
             CreateButton::new(Delete.to_custom_id())
@@ -101,8 +106,9 @@ pub mod birthday {
             return Err(HArgError::new(msg).into());
         };
 
+        let config = data.config();
         let day_of_year = birthday.day_of_year;
-        let region_name = get_region(ctx, birthday.region).map_or("<unknown>", |r| &r.name);
+        let region_name = get_region(&config, birthday.region).map_or("<unknown>", |r| &r.name);
 
         let description = format!(
             "**Birthday:** {day_of_year}\n\
@@ -111,7 +117,7 @@ pub mod birthday {
 
         let embed = CreateEmbed::new()
             .description(description)
-            .color(data.config().embed_color);
+            .color(config.embed_color);
 
         let reply = CreateReply::new().embed(embed);
 
@@ -119,6 +125,20 @@ pub mod birthday {
         Ok(())
     }
 
+    /// Lists the next birthdays in this server.
+    #[sub_command]
+    async fn upcoming(
+        ctx: Context<'_>,
+        /// How many birthdays to list. Defaults to 10.
+        #[min = 1]
+        #[max = 25]
+        count: Option<u8>,
+        /// Whether to show the response only to yourself.
+        ephemeral: Option<bool>,
+    ) -> Result {
+        list_upcoming(ctx, count.unwrap_or(10), ephemeral).await
+    }
+
     /// Sets your birthday time zone.
     #[sub_command(name = "time-zone")]
     async fn time_zone(
@@ -132,7 +152,8 @@ pub mod birthday {
 
         ctx.defer_as(Ephemeral).await?;
 
-        let region_info = get_region(ctx, region)?;
+        let config = data.config();
+        let region_info = get_region(&config, region)?;
 
         let filter = doc! {
             "user": bson_id!(ctx.user().id),
@@ -158,7 +179,7 @@ pub mod birthday {
         let description = format!("Set your region to **{}**.", region_info.name);
 
         let embed = CreateEmbed::new()
-            .color(data.config().embed_color)
+            .color(config.embed_color)
             .description(description);
 
         let reply = CreateReply::new().embed(embed);
@@ -167,6 +188,116 @@ pub mod birthday {
     }
 }
 
+/// The most birthdays to check for server membership per listing.
+const MAX_UPCOMING_LOOKUPS: i64 = 100;
+
+async fn list_upcoming(ctx: Context<'_>, count: u8, ephemeral: Option<bool>) -> Result {
+    let data = ctx.data_ref();
+    let guild_id = ctx.require_guild_id()?;
+    let db = data.database()?;
+    let config = data.config();
+    let birthday = config
+        .perks()?
+        .birthday
+        .as_ref()
+        .context("birthday feature must be enabled")?;
+
+    ctx.defer_as(ephemeral).await?;
+
+    let now = Utc::now();
+    let today = DayOfYear::from_date(now.date_naive()).context("today must be a valid day")?;
+
+    // sort by how many days are left until each birthday, wrapping around the year
+    let pipeline = [
+        doc! {
+            "$addFields": {
+                "days_until": {
+                    "$mod": [
+                        {
+                            "$add": [
+                                { "$subtract": ["$day_of_year", i32::from(today.ordinal())] },
+                                366,
+                            ],
+                        },
+                        366,
+                    ],
+                },
+            },
+        },
+        doc! {
+            "$sort": {
+                "days_until": 1,
+                "user": 1,
+            },
+        },
+        doc! {
+            "$limit": MAX_UPCOMING_LOOKUPS,
+        },
+    ];
+
+    let mut query = Birthday::collection(db)
+        .aggregate(pipeline)
+        .with_type::<Birthday>()
+        .await?;
+
+    // birthdays are global, so only keep the ones of members of this server
+    let mut found = Vec::new();
+    while let Some(entry) = query.try_next().await? {
+        if ctx.http().get_member(guild_id, entry.user).await.is_ok() {
+            found.push(entry);
+            if found.len() >= usize::from(count) {
+                break;
+            }
+        }
+    }
+
+    let mut description = String::new();
+
+    for (index, region) in birthday.regions.iter().enumerate() {
+        let local_today = now
+            .checked_add_signed(region.time_offset)
+            .context("birthday time offset breaks the date")?
+            .date_naive();
+
+        let mut lines = String::new();
+        let entries = found
+            .iter()
+            .filter(|e| usize::from(e.region) == index)
+            .filter_map(|e| Some((e, e.day_of_year.next_date(local_today)?)));
+
+        for (entry, date) in entries {
+            let days = (date - local_today).num_days();
+            let when = match days {
+                0 => "🎂 today".to_owned(),
+                1 => "tomorrow".to_owned(),
+                _ => format!("in {days} days"),
+            };
+
+            writeln_str!(
+                lines,
+                "- **{}** <@{}> · {when}",
+                entry.day_of_year,
+                entry.user
+            );
+        }
+
+        if !lines.is_empty() {
+            writeln_str!(description, "### {}\n{lines}", region.name);
+        }
+    }
+
+    let description =
+        crate::fmt::written_or(description, "No one in this server has set a birthday yet.");
+
+    let embed = CreateEmbed::new()
+        .title("Upcoming Birthdays")
+        .color(config.embed_color)
+        .description(description);
+
+    ctx.send(CreateReply::new().embed(embed)).await?;
+    Ok(())
+}
+
 #[derive(houston_cmd::ChoiceArg)]
 enum EMonth {
     January,
@@ -202,10 +333,8 @@ impl EMonth {
     }
 }
 
-fn get_region(ctx: Context<'_>, region: u16) -> Result<&BirthdayRegionConfig> {
-    let region = ctx
-        .data_ref()
-        .config()
+fn get_region(config: &HBotConfig, region: u16) -> Result<&BirthdayRegionConfig> {
+    let region = config
         .perks()?
         .birthday
         .as_ref()
@@ -236,7 +365,10 @@ async fn autocomplete_region<'a>(
         .filter(|(_, region)| region.name.contains(partial))
         // map it to an autocomplete choice with the region index as the value
         .map(|(index, region)| {
-            AutocompleteChoice::new(&region.name, AutocompleteValue::Integer(index as u64))
+            AutocompleteChoice::new(
+                region.name.clone(),
+                AutocompleteValue::Integer(index as u64),
+            )
         })
         .collect();
 
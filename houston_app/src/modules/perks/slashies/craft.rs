@@ -0,0 +1,83 @@
+use utils::text::write_str::*;
+
+use crate::modules::perks::effects::Args;
+use crate::modules::perks::model::*;
+use crate::slashies::prelude::*;
+
+/// Combine items into another item.
+#[chat_command(contexts = "Guild", integration_types = "Guild")]
+pub async fn craft(
+    ctx: Context<'_>,
+    /// The recipe to use.
+    #[autocomplete = "autocomplete_recipe"]
+    recipe: u32,
+    /// How many times to use the recipe. Defaults to 1.
+    #[min = 1]
+    #[max = 100]
+    times: Option<u32>,
+) -> Result {
+    let times = times.unwrap_or(1);
+    let data = ctx.data_ref();
+    let guild_id = ctx.require_guild_id()?;
+    let user_id = ctx.user().id;
+    let config = data.config();
+    let perks = config.perks()?;
+    let db = data.database()?;
+
+    let recipe = usize::try_from(recipe)
+        .ok()
+        .and_then(|r| perks.recipes.get(r))
+        .ok_or(HArgError::new_const("Unknown recipe."))?;
+
+    ctx.defer_as(Ephemeral).await?;
+
+    let wallet = Wallet::collection(db)
+        .craft_items(guild_id, user_id, recipe, times)
+        .await?;
+
+    let (item, amount) = recipe.result;
+    let gained = i64::from(amount) * i64::from(times);
+    let owned = wallet.item(item);
+    item.on_gain(Args::new(ctx.serenity, guild_id, user_id), owned, gained)
+        .await?;
+
+    let mut used = String::new();
+    for &(ingredient, amount) in &recipe.ingredients {
+        let amount = i64::from(amount) * i64::from(times);
+        write_str!(used, "\n-# Used {amount} {}.", ingredient.info(perks).name);
+    }
+
+    let description = format!(
+        "Crafted **{gained} {}** with {}.{used}",
+        item.info(perks).name,
+        recipe.name,
+    );
+
+    let embed = CreateEmbed::new()
+        .color(config.embed_color)
+        .description(description);
+
+    ctx.send(CreateReply::new().embed(embed)).await?;
+    Ok(())
+}
+
+async fn autocomplete_recipe<'a>(
+    ctx: Context<'a>,
+    partial: &'a str,
+) -> CreateAutocompleteResponse<'a> {
+    let config = ctx.data_ref().config();
+    let Ok(perks) = config.perks() else {
+        return CreateAutocompleteResponse::new();
+    };
+
+    let choices: Vec<_> = (0u64..)
+        .zip(&perks.recipes)
+        .filter(|(_, recipe)| recipe.name.contains(partial))
+        .take(25)
+        .map(|(index, recipe)| {
+            AutocompleteChoice::new(recipe.name.clone(), AutocompleteValue::Integer(index))
+        })
+        .collect();
+
+    CreateAutocompleteResponse::new().set_choices(choices)
+}
@@ -0,0 +1,73 @@
+use chrono::TimeDelta;
+
+use crate::modules::perks::Item;
+use crate::slashies::prelude::*;
+
+/// How long the gift confirmation stays usable.
+const CONFIRM_TTL: TimeDelta = TimeDelta::minutes(15);
+
+/// Gift items from your wallet to another member.
+#[chat_command(contexts = "Guild", integration_types = "Guild")]
+pub async fn gift(
+    ctx: Context<'_>,
+    /// The member to gift to.
+    member: SlashMember<'_>,
+    /// The item to gift.
+    item: Item,
+    /// How many to gift.
+    #[min = 1]
+    amount: u32,
+) -> Result {
+    use crate::buttons::ToCustomData as _;
+    use crate::modules::core::buttons::Delete;
+    use crate::modules::perks::buttons::gift::Confirm;
+
+    let config = ctx.data_ref().config();
+    let perks = config.perks()?;
+    let gift = perks
+        .gift
+        .as_ref()
+        .ok_or(HArgError::new_const("Gifting is not enabled."))?;
+
+    gift.check(item, amount)?;
+
+    anyhow::ensure!(
+        member.user.id != ctx.user().id,
+        HArgError::new_const("You can't gift items to yourself.")
+    );
+
+    anyhow::ensure!(
+        !member.user.bot(),
+        HArgError::new_const("You can't gift items to bots.")
+    );
+
+    ctx.defer_as(Ephemeral).await?;
+
+    let description = format!(
+        "Confirm that you want to gift this:\n\
+         - **{amount} {}** to {}\n\
+         -# You cannot undo this.",
+        item.info(perks).name,
+        member.mention(),
+    );
+
+    let embed = CreateEmbed::new()
+        .description(description)
+        .color(ERROR_EMBED_COLOR);
+
+    let components = CreateActionRow::buttons(vec![
+        CreateButton::new(
+            Confirm::new(member.user.id, item, amount).to_expiring_custom_id(CONFIRM_TTL),
+        )
+        .label("Confirm")
+        .style(ButtonStyle::Success),
+        CreateButton::new(Delete.to_custom_id())
+            .label("Cancel")
+            .style(ButtonStyle::Danger),
+    ]);
+
+    let reply = CreateReply::new().embed(embed).components(vec![components]);
+
+    ctx.send(reply).await?;
+    Ok(())
+}
@@ -1,4 +1,6 @@
 pub mod birthday;
+pub mod craft;
+pub mod gift;
 pub mod perk_admin;
 pub mod pushpin;
 pub mod role_edit;
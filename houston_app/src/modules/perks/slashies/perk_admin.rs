@@ -1,3 +1,5 @@
+use std::pin::pin;
+
 use bson::doc;
 use chrono::*;
 use utils::text::write_str::*;
@@ -30,7 +32,8 @@ pub mod perk_admin {
     ) -> Result {
         let data = ctx.data_ref();
         let guild_id = ctx.require_guild_id()?;
-        let perks = data.config().perks()?;
+        let config = data.config();
+        let perks = config.perks()?;
         let db = data.database()?;
         let args = Args::new(ctx.serenity, guild_id, member.user.id);
 
@@ -55,7 +58,7 @@ pub mod perk_admin {
         );
 
         let embed = CreateEmbed::new()
-            .color(data.config().embed_color)
+            .color(config.embed_color)
             .description(description);
 
         ctx.send(CreateReply::new().embed(embed)).await?;
@@ -73,7 +76,8 @@ pub mod perk_admin {
     ) -> Result {
         let data = ctx.data_ref();
         let guild_id = ctx.require_guild_id()?;
-        let perks = data.config().perks()?;
+        let config = data.config();
+        let perks = config.perks()?;
         let db = data.database()?;
         let args = Args::new(ctx.serenity, guild_id, member.user.id);
 
@@ -91,7 +95,7 @@ pub mod perk_admin {
         );
 
         let embed = CreateEmbed::new()
-            .color(data.config().embed_color)
+            .color(config.embed_color)
             .description(description);
 
         ctx.send(CreateReply::new().embed(embed)).await?;
@@ -107,7 +111,8 @@ pub mod perk_admin {
     ) -> Result {
         let data = ctx.data_ref();
         let guild_id = ctx.require_guild_id()?;
-        let perks = data.config().perks()?;
+        let config = data.config();
+        let perks = config.perks()?;
         let db = data.database()?;
         ctx.defer_as(Ephemeral).await?;
 
@@ -135,7 +140,7 @@ pub mod perk_admin {
 
         let embed = CreateEmbed::new()
             .title(title)
-            .color(data.config().embed_color)
+            .color(config.embed_color)
             .description(description);
 
         ctx.send(CreateReply::new().embed(embed)).await?;
@@ -155,12 +160,20 @@ pub mod perk_admin {
     ) -> Result {
         let data = ctx.data_ref();
         let guild_id = ctx.require_guild_id()?;
-        let perks = data.config().perks()?;
+        let config = data.config();
+        let perks = config.perks()?;
         let db = data.database()?;
         ctx.defer_as(Ephemeral).await?;
 
+        let reason = format!("admin grant by {}", ctx.user().id);
         let wallet = Wallet::collection(db)
-            .add_items(guild_id, member.user.id, item, amount.into())
+            .add_items(
+                guild_id,
+                member.user.id,
+                item,
+                amount.into(),
+                TxReason::new("perks", &reason),
+            )
             .await?;
 
         let description = format!(
@@ -171,13 +184,34 @@ pub mod perk_admin {
         );
 
         let embed = CreateEmbed::new()
-            .color(data.config().embed_color)
+            .color(config.embed_color)
             .description(description);
 
         ctx.send(CreateReply::new().embed(embed)).await?;
         Ok(())
     }
 
+    /// Grants a perk and/or items to many members at once.
+    #[sub_command(name = "bulk-grant")]
+    async fn bulk_grant(
+        ctx: Context<'_>,
+        /// Grant to all members with this role.
+        role: Option<&Role>,
+        /// Grant to the user IDs in this CSV file, in the first column.
+        file: Option<&Attachment>,
+        /// The perk to enable or extend.
+        perk: Option<Effect>,
+        /// How long to enable or extend the perk for, in hours.
+        duration: Option<u32>,
+        /// The item to hand out.
+        item: Option<Item>,
+        /// How many items to give. Negative to remove.
+        amount: Option<i32>,
+    ) -> Result {
+        let grant = Grant::new(perk, duration, item, amount)?;
+        bulk_grant_to(ctx, role, file, grant).await
+    }
+
     /// Sets a user's unique role. Can be omitted to delete the association.
     #[sub_command(name = "unique-role")]
     async fn unique_role(
@@ -232,3 +266,252 @@ pub mod perk_admin {
         Ok(())
     }
 }
+
+/// The most users a single bulk grant may affect.
+const MAX_BULK_USERS: usize = 5000;
+
+/// The largest CSV file accepted by a bulk grant.
+const MAX_CSV_SIZE: u32 = 256 * 1024;
+
+/// What to grant to every user in a bulk grant.
+#[derive(Debug, Clone, Copy)]
+struct Grant {
+    perk: Option<(Effect, TimeDelta)>,
+    item: Option<(Item, i64)>,
+}
+
+impl Grant {
+    fn new(
+        perk: Option<Effect>,
+        duration: Option<u32>,
+        item: Option<Item>,
+        amount: Option<i32>,
+    ) -> Result<Self> {
+        let perk = match (perk, duration) {
+            (Some(perk), Some(duration)) => {
+                let duration =
+                    TimeDelta::try_hours(i64::from(duration)).context("too many hours")?;
+                Some((perk, duration))
+            },
+            (None, None) => None,
+            _ => Err(HArgError::new_const(
+                "A perk and its duration must be specified together.",
+            ))?,
+        };
+
+        let item = match (item, amount) {
+            (Some(item), Some(amount)) => Some((item, i64::from(amount))),
+            (None, None) => None,
+            _ => Err(HArgError::new_const(
+                "An item and its amount must be specified together.",
+            ))?,
+        };
+
+        anyhow::ensure!(
+            perk.is_some() || item.is_some(),
+            HArgError::new_const("Specify a perk, an item, or both to grant.")
+        );
+
+        Ok(Self { perk, item })
+    }
+}
+
+async fn bulk_grant_to(
+    ctx: Context<'_>,
+    role: Option<&Role>,
+    file: Option<&Attachment>,
+    grant: Grant,
+) -> Result {
+    let data = ctx.data_ref();
+    let guild_id = ctx.require_guild_id()?;
+    let config = data.config();
+    let perks = config.perks()?;
+
+    ctx.defer_as(Ephemeral).await?;
+
+    let mut users = match (role, file) {
+        (Some(role), None) => find_role_members(ctx, guild_id, role.id).await?,
+        (None, Some(file)) => {
+            anyhow::ensure!(
+                file.size <= MAX_CSV_SIZE,
+                HArgError::new_const("The file is too large.")
+            );
+
+            let bytes = file.download().await?;
+            let text = String::from_utf8(bytes)
+                .map_err(|_| HArgError::new_const("The file is not valid text."))?;
+            parse_user_csv(&text)?
+        },
+        _ => Err(HArgError::new_const(
+            "Specify either a role or a CSV file, but not both.",
+        ))?,
+    };
+
+    users.sort_unstable();
+    users.dedup();
+
+    anyhow::ensure!(
+        !users.is_empty(),
+        HArgError::new_const("There is no one to grant to.")
+    );
+
+    anyhow::ensure!(
+        users.len() <= MAX_BULK_USERS,
+        HArgError::new(format!(
+            "Bulk grants are limited to {MAX_BULK_USERS} users, but {} were specified.",
+            users.len()
+        ))
+    );
+
+    let mut what = String::new();
+    if let Some((perk, duration)) = grant.perk {
+        write_str!(
+            what,
+            "\n- **{}** for {} hour(s)",
+            perk.info(perks).name,
+            duration.num_hours()
+        );
+    }
+
+    if let Some((item, amount)) = grant.item {
+        write_str!(what, "\n- **{amount} {}**", item.info(perks).name);
+    }
+
+    let total = users.len();
+    let create_reply = |done: usize, failed: usize| {
+        let description = format!(
+            "Granting to {total} user(s):{what}\n\n\
+             Done: {done}/{total}\n\
+             Failed: {failed}"
+        );
+
+        let embed = CreateEmbed::new()
+            .title("Bulk Grant")
+            .color(config.embed_color)
+            .description(description);
+
+        CreateReply::new().embed(embed)
+    };
+
+    let handle = ctx.send(create_reply(0, 0)).await?;
+
+    let reason = format!("admin bulk grant by {}", ctx.user().id);
+    let mut failed = 0usize;
+
+    for (index, &user_id) in users.iter().enumerate() {
+        if let Err(why) = grant_to(ctx, guild_id, user_id, grant, &reason).await {
+            log::warn!("Bulk grant to {user_id} in {guild_id} failed: {why:?}");
+            failed += 1;
+        }
+
+        let done = index + 1;
+        if done % 25 == 0 && done != total {
+            // progress is best-effort, the interaction may expire on long grants
+            if let Err(why) = handle.edit(create_reply(done, failed).into()).await {
+                log::warn!("Failed to show bulk grant progress: {why:?}");
+            }
+        }
+    }
+
+    log::info!(
+        "{} bulk granted to {total} user(s) in {guild_id}, {failed} failed.",
+        ctx.user().id,
+    );
+
+    handle.edit(create_reply(total, failed).into()).await?;
+    Ok(())
+}
+
+/// Grants a perk and/or items to a single user.
+///
+/// Active perks are extended by the duration instead of being reset.
+async fn grant_to(
+    ctx: Context<'_>,
+    guild_id: GuildId,
+    user_id: UserId,
+    grant: Grant,
+    reason: &str,
+) -> Result {
+    let db = ctx.data_ref().database()?;
+
+    if let Some((perk, duration)) = grant.perk {
+        let active = ActivePerk::collection(db)
+            .find_enabled(guild_id, user_id, perk)
+            .await?;
+
+        let now = Utc::now();
+        let start = active.as_ref().map_or(now, |a| a.until.max(now));
+        let until = start
+            .checked_add_signed(duration)
+            .context("duration lasts beyond the end of time")?;
+
+        if active.is_none() {
+            let args = Args::new(ctx.serenity, guild_id, user_id);
+            perk.enable(args, None).await?;
+        }
+
+        ActivePerk::collection(db)
+            .set_enabled(guild_id, user_id, perk, until)
+            .await?;
+    }
+
+    if let Some((item, amount)) = grant.item {
+        Wallet::collection(db)
+            .add_items(
+                guild_id,
+                user_id,
+                item,
+                amount,
+                TxReason::new("perks", reason),
+            )
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// Finds all non-bot members with a role.
+async fn find_role_members(
+    ctx: Context<'_>,
+    guild_id: GuildId,
+    role_id: RoleId,
+) -> Result<Vec<UserId>> {
+    let mut members = pin!(guild_id.members_iter(ctx.http()));
+    let mut users = Vec::new();
+
+    while let Some(member) = members.try_next().await? {
+        if !member.user.bot() && member.roles.contains(&role_id) {
+            users.push(member.user.id);
+        }
+    }
+
+    Ok(users)
+}
+
+/// Parses user IDs from the first column of a CSV file.
+///
+/// The first line may be a header.
+fn parse_user_csv(text: &str) -> Result<Vec<UserId>, HArgError> {
+    let mut users = Vec::new();
+
+    for (index, line) in text.lines().enumerate() {
+        let field = line.split(',').next().unwrap_or_default();
+        let field = field.trim().trim_matches('"');
+        if field.is_empty() {
+            continue;
+        }
+
+        match field.parse::<u64>() {
+            Ok(id) if id != 0 => users.push(UserId::new(id)),
+            _ if index == 0 => {},
+            _ => {
+                return Err(HArgError::new(format!(
+                    "Line {} does not start with a user ID.",
+                    index + 1
+                )))
+            },
+        }
+    }
+
+    Ok(users)
+}
@@ -12,7 +12,8 @@ use crate::slashies::prelude::*;
 pub async fn pushpin_pin(ctx: Context<'_>, message: &Message) -> Result {
     let data = ctx.data_ref();
     let guild_id = ctx.require_guild_id()?;
-    let perks = data.config().perks()?;
+    let config = data.config();
+    let perks = config.perks()?;
     let db = data.database()?;
 
     if message.pinned() {
@@ -23,10 +24,18 @@ pub async fn pushpin_pin(ctx: Context<'_>, message: &Message) -> Result {
         ctx.send(CreateReply::new().embed(embed).ephemeral(true))
             .await?;
     } else {
+        ctx.require_bot_permissions(Permissions::MANAGE_MESSAGES)?;
         ctx.defer_as(Ephemeral).await?;
 
         Wallet::collection(db)
-            .take_items(guild_id, ctx.user().id, Item::Pushpin, 1, perks)
+            .take_items(
+                guild_id,
+                ctx.user().id,
+                Item::Pushpin,
+                1,
+                TxReason::new("perks", "used pushpin"),
+                perks,
+            )
             .await?;
 
         match message
@@ -41,14 +50,20 @@ pub async fn pushpin_pin(ctx: Context<'_>, message: &Message) -> Result {
                 let description = format!("Pinned!\n-# Used 1 {name}.");
 
                 let embed = CreateEmbed::new()
-                    .color(data.config().embed_color)
+                    .color(config.embed_color)
                     .description(description);
 
                 ctx.send(CreateReply::new().embed(embed)).await?;
             },
             Err(_) => {
                 Wallet::collection(db)
-                    .add_items(guild_id, ctx.user().id, Item::Pushpin, 1)
+                    .add_items(
+                        guild_id,
+                        ctx.user().id,
+                        Item::Pushpin,
+                        1,
+                        TxReason::new("perks", "pushpin refund"),
+                    )
                     .await?;
 
                 let embed = CreateEmbed::new()
@@ -73,7 +88,8 @@ pub async fn pushpin_pin(ctx: Context<'_>, message: &Message) -> Result {
 pub async fn pushpin_unpin(ctx: Context<'_>, message: &Message) -> Result {
     let data = ctx.data_ref();
     let guild_id = ctx.require_guild_id()?;
-    let perks = data.config().perks()?;
+    let config = data.config();
+    let perks = config.perks()?;
     let db = data.database()?;
 
     if !message.pinned() {
@@ -84,10 +100,18 @@ pub async fn pushpin_unpin(ctx: Context<'_>, message: &Message) -> Result {
         ctx.send(CreateReply::new().embed(embed).ephemeral(true))
             .await?;
     } else {
+        ctx.require_bot_permissions(Permissions::MANAGE_MESSAGES)?;
         ctx.defer_as(Ephemeral).await?;
 
         Wallet::collection(db)
-            .take_items(guild_id, ctx.user().id, Item::Pushpin, 1, perks)
+            .take_items(
+                guild_id,
+                ctx.user().id,
+                Item::Pushpin,
+                1,
+                TxReason::new("perks", "used pushpin"),
+                perks,
+            )
             .await?;
 
         match message
@@ -102,14 +126,20 @@ pub async fn pushpin_unpin(ctx: Context<'_>, message: &Message) -> Result {
                 let description = format!("Unpinned!\n-# Used 1 {name}.");
 
                 let embed = CreateEmbed::new()
-                    .color(data.config().embed_color)
+                    .color(config.embed_color)
                     .description(description);
 
                 ctx.send(CreateReply::new().embed(embed)).await?;
             },
             Err(_) => {
                 Wallet::collection(db)
-                    .add_items(guild_id, ctx.user().id, Item::Pushpin, 1)
+                    .add_items(
+                        guild_id,
+                        ctx.user().id,
+                        Item::Pushpin,
+                        1,
+                        TxReason::new("perks", "pushpin refund"),
+                    )
                     .await?;
 
                 let embed = CreateEmbed::new()
@@ -23,9 +23,11 @@ pub async fn role_edit(
 ) -> Result {
     let data = ctx.data_ref();
     let guild_id = ctx.require_guild_id()?;
-    let perks = data.config().perks()?;
+    let config = data.config();
+    let perks = config.perks()?;
     let db = data.database()?;
 
+    ctx.require_bot_permissions(Permissions::MANAGE_ROLES)?;
     ctx.defer_as(Ephemeral).await?;
 
     let filter = doc! {
@@ -47,7 +49,14 @@ pub async fn role_edit(
     }
 
     Wallet::collection(db)
-        .take_items(guild_id, ctx.user().id, Item::RoleEdit, 1, perks)
+        .take_items(
+            guild_id,
+            ctx.user().id,
+            Item::RoleEdit,
+            1,
+            TxReason::new("perks", "used role edit"),
+            perks,
+        )
         .await?;
 
     match guild_id.edit_role(ctx.http(), unique.role, edit).await {
@@ -59,14 +68,20 @@ pub async fn role_edit(
             );
 
             let embed = CreateEmbed::new()
-                .color(data.config().embed_color)
+                .color(config.embed_color)
                 .description(description);
 
             ctx.send(CreateReply::new().embed(embed)).await?;
         },
         Err(_) => {
             Wallet::collection(db)
-                .add_items(guild_id, ctx.user().id, Item::RoleEdit, 1)
+                .add_items(
+                    guild_id,
+                    ctx.user().id,
+                    Item::RoleEdit,
+                    1,
+                    TxReason::new("perks", "role edit refund"),
+                )
                 .await?;
 
             let embed = CreateEmbed::new()
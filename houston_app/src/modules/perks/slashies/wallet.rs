@@ -6,16 +6,45 @@ use crate::modules::perks::model::Wallet;
 use crate::modules::perks::Item;
 use crate::slashies::prelude::*;
 
-/// View your server wallet.
+/// Manage your server wallet.
 #[chat_command(contexts = "Guild", integration_types = "Guild")]
-pub async fn wallet(
-    ctx: Context<'_>,
-    /// Whether to show the response only to yourself.
-    ephemeral: Option<bool>,
-) -> Result {
+pub mod wallet {
+    /// View your server wallet.
+    #[sub_command]
+    async fn view(
+        ctx: Context<'_>,
+        /// Whether to show the response only to yourself.
+        ephemeral: Option<bool>,
+    ) -> Result {
+        view_wallet(ctx, ephemeral).await
+    }
+
+    /// View the recent changes to your server wallet.
+    #[sub_command]
+    async fn history(
+        ctx: Context<'_>,
+        /// Whether to show the response only to yourself.
+        ephemeral: Option<bool>,
+    ) -> Result {
+        use crate::modules::perks::buttons::history::View;
+
+        let guild_id = ctx.require_guild_id()?;
+
+        ctx.defer_as(ephemeral).await?;
+
+        let reply = View::new(guild_id, ctx.user().id)
+            .create_reply(ctx.data_ref())
+            .await?;
+        ctx.send(reply).await?;
+        Ok(())
+    }
+}
+
+async fn view_wallet(ctx: Context<'_>, ephemeral: Option<bool>) -> Result {
     let data = ctx.data_ref();
     let guild_id = ctx.require_guild_id()?;
-    let perks = data.config().perks()?;
+    let config = data.config();
+    let perks = config.perks()?;
     let db = data.database()?;
 
     ctx.defer_as(ephemeral).await?;
@@ -48,7 +77,7 @@ pub async fn wallet(
 
     let embed = CreateEmbed::new()
         .author(author)
-        .color(data.config().embed_color)
+        .color(config.embed_color)
         .description(description);
 
     ctx.send(CreateReply::new().embed(embed)).await?;
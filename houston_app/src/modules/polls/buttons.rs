@@ -0,0 +1,64 @@
+use bson::doc;
+use chrono::Utc;
+
+use super::model::*;
+use crate::buttons::prelude::*;
+use crate::fmt::discord::escape_markdown;
+use crate::helper::bson::bson_id;
+
+/// Votes for an option of the poll on the source message.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Vote {
+    option: u8,
+}
+
+impl Vote {
+    pub fn new(option: u8) -> Self {
+        Self { option }
+    }
+}
+
+impl ButtonArgsReply for Vote {
+    async fn reply(self, ctx: ButtonContext<'_>) -> Result {
+        let db = ctx.data.database()?;
+        let user_id = ctx.interaction.user.id;
+
+        let filter = doc! {
+            "message": bson_id!(ctx.interaction.message.id),
+        };
+
+        let poll = Poll::collection(db)
+            .find_one(filter)
+            .await?
+            .ok_or(HArgError::new_const("This poll no longer exists."))?;
+
+        anyhow::ensure!(
+            !poll.closed && poll.closes > Utc::now(),
+            HArgError::new_const("This poll is already closed.")
+        );
+
+        let option = poll
+            .options
+            .get(usize::from(self.option))
+            .ok_or(HArgError::new_const("This option no longer exists."))?;
+
+        ctx.acknowledge().await?;
+
+        let votes = PollVote::collection(db);
+        let added = votes.toggle_vote(poll._id, user_id, self.option).await?;
+        let votes = votes.find_votes(poll._id).await?;
+
+        let embed = super::create_poll_embed(ctx.data, &poll, &votes);
+        ctx.edit(EditReply::new().embed(embed)).await?;
+
+        let content = if added {
+            format!("You voted for **{}**.", escape_markdown(option))
+        } else {
+            format!("You removed your vote for **{}**.", escape_markdown(option))
+        };
+
+        ctx.reply(CreateReply::new().content(content).ephemeral(true))
+            .await?;
+        Ok(())
+    }
+}
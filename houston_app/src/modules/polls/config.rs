@@ -0,0 +1,14 @@
+use chrono::TimeDelta;
+
+use crate::helper::time::serde_time_delta;
+
+fn default_max_duration() -> TimeDelta {
+    const { TimeDelta::days(7) }
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct Config {
+    /// The longest a poll may stay open.
+    #[serde(with = "serde_time_delta", default = "default_max_duration")]
+    pub max_duration: TimeDelta,
+}
@@ -0,0 +1,247 @@
+use bson::doc;
+use bson::oid::ObjectId;
+use mongodb::options::ReturnDocument;
+use utils::text::truncate;
+use utils::text::write_str::*;
+
+use super::prelude::*;
+use crate::buttons::ToCustomData as _;
+use crate::fmt::discord::{escape_markdown, TimeMentionable as _};
+use crate::helper::discord::{create_string_select_menu_row, is_unknown_message};
+
+pub mod buttons;
+pub mod config;
+pub mod model;
+mod slashies;
+
+pub use config::Config;
+use model::*;
+
+/// Polls with more options than this use a select menu instead of buttons.
+const MAX_BUTTONS: usize = 5;
+
+/// The width of the result bars, in characters.
+const BAR_WIDTH: usize = 10;
+
+/// The most voters listed per option in public results.
+const MAX_LISTED_VOTERS: usize = 15;
+
+pub struct Module;
+
+impl super::Module for Module {
+    fn enabled(&self, config: &HBotConfig) -> bool {
+        config.polls.is_some()
+    }
+
+    fn commands(&self, _config: &HBotConfig) -> impl IntoIterator<Item = HCommand> {
+        [slashies::poll()]
+    }
+
+    fn db_init(db: &mongodb::Database) -> mongodb::BoxFuture<'_, Result> {
+        use crate::helper::bson::update_indices;
+        Box::pin(async move {
+            update_indices(Poll::collection(db), Poll::indices()).await?;
+            update_indices(PollVote::collection(db), PollVote::indices()).await?;
+            Ok(())
+        })
+    }
+
+    fn validate(&self, config: &HBotConfig) -> Result {
+        anyhow::ensure!(config.mongodb_uri.is_some(), "polls requires a mongodb_uri");
+
+        let polls = config.polls()?;
+        anyhow::ensure!(
+            polls.max_duration >= chrono::TimeDelta::minutes(1),
+            "polls.max_duration must be at least 1 minute"
+        );
+
+        Ok(())
+    }
+}
+
+/// Closes a poll and edits its message to show the final results.
+///
+/// This is run by the scheduler once the poll's time is up. Polls that are
+/// already closed are left alone.
+pub async fn close(ctx: &Context, id: ObjectId) -> Result {
+    let data = ctx.data_ref::<HContextData>();
+    let db = data.database()?;
+
+    let filter = doc! {
+        "_id": id,
+        "closed": false,
+    };
+
+    // mark it closed first so a message that can't be edited isn't retried
+    let update = doc! {
+        "$set": {
+            "closed": true,
+        },
+    };
+
+    let Some(poll) = Poll::collection(db)
+        .find_one_and_update(filter, update)
+        .return_document(ReturnDocument::After)
+        .await?
+    else {
+        log::trace!("Poll {id} was already closed.");
+        return Ok(());
+    };
+
+    let votes = PollVote::collection(db).find_votes(poll._id).await?;
+    let embed = create_poll_embed(data, &poll, &votes);
+    let edit = EditMessage::new().embed(embed).components(&[]);
+
+    let result = poll
+        .channel
+        .edit_message(&ctx.http, poll.message, edit)
+        .await;
+
+    if is_unknown_message(&result) {
+        log::trace!("Poll message {} was deleted before closing.", poll.message);
+        return Ok(());
+    }
+
+    result?;
+    log::info!("Closed poll {} in {}.", poll._id, poll.guild);
+    Ok(())
+}
+
+/// Creates the embed for a poll, showing the current or final results.
+fn create_poll_embed<'a>(data: &HBotData, poll: &'a Poll, votes: &[PollVote]) -> CreateEmbed<'a> {
+    let mut voters = vec![Vec::new(); poll.options.len()];
+    for vote in votes {
+        if let Some(list) = voters.get_mut(usize::from(vote.option)) {
+            list.push(vote.user);
+        }
+    }
+
+    let total: usize = voters.iter().map(Vec::len).sum();
+    let mut description = String::new();
+
+    for (index, (option, voters)) in poll.options.iter().zip(&voters).enumerate() {
+        let count = voters.len();
+        writeln_str!(
+            description,
+            "**{}. {}**\n`{}` {}% ({count})",
+            index + 1,
+            escape_markdown(option),
+            result_bar(count, total),
+            percent(count, total),
+        );
+
+        if poll.closed && !poll.anonymous && !voters.is_empty() {
+            description.push_str("-# ");
+            for user in voters.iter().take(MAX_LISTED_VOTERS) {
+                write_str!(description, "<@{user}> ");
+            }
+
+            if let Some(more) = count.checked_sub(MAX_LISTED_VOTERS).filter(|&m| m != 0) {
+                write_str!(description, "and {more} more");
+            }
+
+            description.push('\n');
+        }
+    }
+
+    if poll.closed {
+        write_str!(description, "\nClosed {}.", poll.closes.relative());
+    } else {
+        write_str!(description, "\nCloses {}.", poll.closes.relative());
+    }
+
+    let mode = if poll.anonymous {
+        "Anonymous"
+    } else {
+        "Public"
+    };
+    let footer = format!("{mode} poll • {total} vote(s)");
+
+    CreateEmbed::new()
+        .title(poll.question.as_str())
+        .description(description)
+        .footer(CreateEmbedFooter::new(footer))
+        .color(data.config().embed_color)
+}
+
+/// Creates the vote buttons or select menu for an open poll.
+fn create_vote_components(poll: &Poll) -> Vec<CreateActionRow<'_>> {
+    use buttons::Vote;
+
+    use crate::modules::core::buttons::None;
+
+    // options are limited to 20, so the indices always fit
+    #[allow(clippy::cast_possible_truncation)]
+    let votes = poll
+        .options
+        .iter()
+        .enumerate()
+        .map(|(index, option)| (Vote::new(index as u8), index + 1, option));
+
+    if poll.options.len() <= MAX_BUTTONS {
+        let buttons = votes
+            .map(|(vote, number, option)| {
+                CreateButton::new(vote.to_custom_id())
+                    .label(truncate(format!("{number}. {option}"), 80))
+                    .style(ButtonStyle::Secondary)
+            })
+            .collect();
+
+        vec![CreateActionRow::buttons(buttons)]
+    } else {
+        let options: Vec<_> = votes
+            .map(|(vote, number, option)| {
+                CreateSelectMenuOption::new(
+                    truncate(format!("{number}. {option}"), 100),
+                    vote.to_custom_id(),
+                )
+            })
+            .collect();
+
+        let custom_id = None::new(0, 0).to_custom_id();
+        vec![create_string_select_menu_row(custom_id, options, "Vote...")]
+    }
+}
+
+/// Gets the rounded percentage of votes for an option.
+fn percent(count: usize, total: usize) -> usize {
+    if total == 0 {
+        0
+    } else {
+        (count * 100 + total / 2) / total
+    }
+}
+
+/// Renders a bar showing the share of votes for an option.
+fn result_bar(count: usize, total: usize) -> String {
+    let filled = if total == 0 {
+        0
+    } else {
+        (count * BAR_WIDTH + total / 2) / total
+    };
+
+    let mut bar = "█".repeat(filled);
+    bar.push_str(&"░".repeat(BAR_WIDTH - filled));
+    bar
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{percent, result_bar};
+
+    #[test]
+    fn bars() {
+        assert_eq!(result_bar(0, 0), "░░░░░░░░░░");
+        assert_eq!(result_bar(1, 2), "█████░░░░░");
+        assert_eq!(result_bar(2, 3), "███████░░░");
+        assert_eq!(result_bar(4, 4), "██████████");
+    }
+
+    #[test]
+    fn percentages() {
+        assert_eq!(percent(0, 0), 0);
+        assert_eq!(percent(1, 3), 33);
+        assert_eq!(percent(2, 3), 67);
+        assert_eq!(percent(5, 5), 100);
+    }
+}
@@ -0,0 +1,120 @@
+use crate::modules::model_prelude::*;
+
+/// A poll posted to a channel.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Poll {
+    pub _id: ObjectId,
+    #[serde(with = "id_as_i64")]
+    pub guild: GuildId,
+    #[serde(with = "id_as_i64")]
+    pub channel: ChannelId,
+    #[serde(with = "id_as_i64")]
+    pub message: MessageId,
+    #[serde(with = "id_as_i64")]
+    pub author: UserId,
+    pub question: String,
+    pub options: Vec<String>,
+    /// Whether to hide who voted for which option.
+    #[serde(default)]
+    pub anonymous: bool,
+    #[serde(with = "chrono_datetime_as_bson_datetime")]
+    pub closes: DateTime<Utc>,
+    #[serde(default)]
+    pub closed: bool,
+}
+
+/// A single user's vote on a poll.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PollVote {
+    pub _id: ObjectId,
+    pub poll: ObjectId,
+    #[serde(with = "id_as_i64")]
+    pub user: UserId,
+    pub option: u8,
+}
+
+fn name(name: &str) -> IndexOptions {
+    IndexOptions::builder().name(name.to_owned()).build()
+}
+
+impl Poll {
+    pub fn collection(db: &Database) -> Collection<Self> {
+        db.collection("polls.polls")
+    }
+
+    pub fn indices() -> Vec<IndexModel> {
+        vec![IndexModel::builder()
+            .options(name("message"))
+            .keys(doc! {
+                "message": 1,
+            })
+            .build()]
+    }
+}
+
+impl PollVote {
+    pub fn collection(db: &Database) -> Collection<Self> {
+        db.collection("polls.votes")
+    }
+
+    pub fn indices() -> Vec<IndexModel> {
+        vec![IndexModel::builder()
+            .options(
+                IndexOptions::builder()
+                    .name("poll-user".to_owned())
+                    .unique(true)
+                    .build(),
+            )
+            .keys(doc! {
+                "poll": 1,
+                "user": 1,
+            })
+            .build()]
+    }
+}
+
+pub trait PollVoteExt {
+    /// Toggles a user's vote for an option.
+    ///
+    /// Voting for another option replaces the previous vote. Returns whether
+    /// the user now has a vote for the option.
+    async fn toggle_vote(&self, poll: ObjectId, user: UserId, option: u8) -> Result<bool>;
+
+    /// Loads all votes on a poll.
+    async fn find_votes(&self, poll: ObjectId) -> Result<Vec<PollVote>>;
+}
+
+impl PollVoteExt for Collection<PollVote> {
+    async fn toggle_vote(&self, poll: ObjectId, user: UserId, option: u8) -> Result<bool> {
+        let filter = doc! {
+            "poll": poll,
+            "user": bson_id!(user),
+        };
+
+        let mut remove = filter.clone();
+        remove.insert("option", i32::from(option));
+
+        let removed = self.delete_one(remove).await?;
+        if removed.deleted_count != 0 {
+            return Ok(false);
+        }
+
+        let update = doc! {
+            "$set": {
+                "option": i32::from(option),
+            },
+        };
+
+        self.update_one(filter, update).upsert(true).await?;
+        Ok(true)
+    }
+
+    async fn find_votes(&self, poll: ObjectId) -> Result<Vec<PollVote>> {
+        let filter = doc! {
+            "poll": poll,
+        };
+
+        let votes = self.find(filter).await?.try_collect().await?;
+        Ok(votes)
+    }
+}
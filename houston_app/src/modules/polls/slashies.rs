@@ -0,0 +1,117 @@
+use bson::oid::ObjectId;
+use chrono::{TimeDelta, Utc};
+
+use super::model::*;
+use crate::fmt::discord::TimeMentionable as _;
+use crate::modules::scheduler::{self, JobKind};
+use crate::slashies::prelude::*;
+
+/// The most options a poll may have.
+const MAX_OPTIONS: usize = 20;
+
+/// Starts a poll in this channel.
+#[chat_command(contexts = "Guild", integration_types = "Guild")]
+pub async fn poll(
+    ctx: Context<'_>,
+    /// The question to ask.
+    #[max_length = 256]
+    question: &str,
+    /// The options to vote on, separated by ";".
+    #[max_length = 2000]
+    options: &str,
+    /// How many minutes the poll stays open.
+    #[min = 1]
+    duration: u32,
+    /// Whether to hide who voted for which option. Defaults to false.
+    anonymous: Option<bool>,
+) -> Result {
+    let data = ctx.data_ref();
+    let guild_id = ctx.require_guild_id()?;
+    let config = data.config();
+    let polls = config.polls()?;
+    let db = data.database()?;
+
+    let options = parse_options(options)?;
+    let duration = TimeDelta::try_minutes(i64::from(duration))
+        .filter(|d| *d <= polls.max_duration)
+        .ok_or_else(|| {
+            HArgError::new(format!(
+                "Polls can stay open for at most {} minutes.",
+                polls.max_duration.num_minutes()
+            ))
+        })?;
+
+    let closes = Utc::now()
+        .checked_add_signed(duration)
+        .context("duration lasts beyond the end of time")?;
+
+    ctx.require_bot_permissions(Permissions::SEND_MESSAGES | Permissions::EMBED_LINKS)?;
+    ctx.defer_as(Ephemeral).await?;
+
+    let mut poll = Poll {
+        _id: ObjectId::new(),
+        guild: guild_id,
+        channel: ctx.channel_id(),
+        message: MessageId::new(1),
+        author: ctx.user().id,
+        question: question.to_owned(),
+        options,
+        anonymous: anonymous.unwrap_or(false),
+        closes,
+        closed: false,
+    };
+
+    let embed = super::create_poll_embed(data, &poll, &[]).author(
+        CreateEmbedAuthor::new(ctx.user().display_name().to_owned()).icon_url(ctx.user().face()),
+    );
+
+    let message = CreateMessage::new()
+        .embed(embed)
+        .components(super::create_vote_components(&poll));
+
+    poll.message = poll
+        .channel
+        .send_message(ctx.http(), message)
+        .await
+        .map_err(|_| HArgError::new_const("I can't post messages in this channel."))?
+        .id;
+
+    Poll::collection(db).insert_one(&poll).await?;
+
+    let job = JobKind::PollClose { poll: poll._id };
+    scheduler::schedule(db, &job, closes).await?;
+
+    log::info!("{} started poll {} in {}.", poll.author, poll._id, guild_id);
+
+    let embed = CreateEmbed::new()
+        .color(config.embed_color)
+        .description(format!(
+            "Started the poll. It closes {}.",
+            closes.relative()
+        ));
+
+    ctx.send(CreateReply::new().embed(embed)).await?;
+    Ok(())
+}
+
+/// Parses the `;`-separated poll options.
+fn parse_options(options: &str) -> Result<Vec<String>> {
+    let options: Vec<String> = options
+        .split(';')
+        .map(str::trim)
+        .filter(|o| !o.is_empty())
+        .map(str::to_owned)
+        .collect();
+
+    anyhow::ensure!(
+        options.len() >= 2,
+        HArgError::new_const("A poll needs at least 2 options.")
+    );
+
+    anyhow::ensure!(
+        options.len() <= MAX_OPTIONS,
+        HArgError::new_const("A poll can have at most 20 options.")
+    );
+
+    Ok(options)
+}
@@ -0,0 +1,163 @@
+use bson::doc;
+
+use crate::buttons::prelude::*;
+use crate::helper::bson::bson_id;
+use crate::helper::discord::id_as_u64;
+use crate::modules::profile::model::Profile;
+
+const MAX_PRONOUNS_LEN: u16 = 40;
+const MAX_BIO_LEN: u16 = 300;
+const MAX_LINKS_LEN: u16 = 300;
+const MAX_LINKS: usize = 3;
+const MAX_SHIP_LEN: u16 = 50;
+
+/// Opens the modal to edit your own server profile.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Edit {
+    #[serde(with = "id_as_u64")]
+    guild: GuildId,
+}
+
+/// The fields submitted through the edit modal.
+///
+/// Empty fields are treated as unset.
+#[derive(Debug, houston_cmd::ModalFields)]
+struct ProfileFields {
+    pronouns: Option<String>,
+    bio: Option<String>,
+    #[modal(default, parse_with = parse_links)]
+    links: Vec<String>,
+    #[modal(custom_id = "ship")]
+    favorite_ship: Option<String>,
+}
+
+/// Parses one link per line, only allowing web links.
+fn parse_links(value: &str) -> Result<Vec<String>, HArgError> {
+    let links: Vec<String> = value
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .map(|l| match url::Url::parse(l) {
+            Ok(url) if matches!(url.scheme(), "http" | "https") => Ok(url.into()),
+            _ => Err(HArgError::new_const(
+                "Links must be full web links, one per line.",
+            )),
+        })
+        .collect::<Result<_, _>>()?;
+
+    if links.len() > MAX_LINKS {
+        return Err(HArgError::new(format!(
+            "You can only add up to {MAX_LINKS} links."
+        )));
+    }
+
+    Ok(links)
+}
+
+impl Edit {
+    pub fn new(guild: GuildId) -> Self {
+        Self { guild }
+    }
+}
+
+impl ButtonArgsReply for Edit {
+    async fn reply(self, ctx: ButtonContext<'_>) -> Result {
+        let db = ctx.data.database()?;
+
+        let filter = doc! {
+            "guild": bson_id!(self.guild),
+            "user": bson_id!(ctx.interaction.user.id),
+        };
+
+        let profile = Profile::collection(db)
+            .find_one(filter)
+            .await?
+            .unwrap_or_default();
+
+        let input = |style, label, custom_id, max_length, value: Option<String>| {
+            let mut input = CreateInputText::new(style, label, custom_id)
+                .max_length(max_length)
+                .required(false);
+
+            if let Some(value) = value {
+                input = input.value(value);
+            }
+
+            CreateActionRow::input_text(input)
+        };
+
+        let links = (!profile.links.is_empty()).then(|| profile.links.join("\n"));
+
+        let components = vec![
+            input(
+                InputTextStyle::Short,
+                "Pronouns",
+                "pronouns",
+                MAX_PRONOUNS_LEN,
+                profile.pronouns,
+            ),
+            input(
+                InputTextStyle::Paragraph,
+                "Bio",
+                "bio",
+                MAX_BIO_LEN,
+                profile.bio,
+            ),
+            input(
+                InputTextStyle::Paragraph,
+                "Links (one per line)",
+                "links",
+                MAX_LINKS_LEN,
+                links,
+            ),
+            input(
+                InputTextStyle::Short,
+                "Favorite Ship",
+                "ship",
+                MAX_SHIP_LEN,
+                profile.favorite_ship,
+            ),
+        ];
+
+        let custom_id = self.to_custom_id();
+        let modal = CreateModal::new(custom_id, "Edit Profile").components(components);
+
+        ctx.modal(modal).await
+    }
+
+    async fn modal_reply(self, ctx: ModalContext<'_>) -> Result {
+        let fields: ProfileFields = ctx.parse_fields()?;
+        let user = ctx.interaction.user.id;
+        let db = ctx.data.database()?;
+
+        let filter = doc! {
+            "guild": bson_id!(self.guild),
+            "user": bson_id!(user),
+        };
+
+        let update = doc! {
+            "$setOnInsert": {
+                "guild": bson_id!(self.guild),
+                "user": bson_id!(user),
+            },
+            "$set": {
+                "pronouns": fields.pronouns,
+                "bio": fields.bio,
+                "links": fields.links,
+                "favorite_ship": fields.favorite_ship,
+            },
+        };
+
+        Profile::collection(db)
+            .update_one(filter, update)
+            .upsert(true)
+            .await?;
+
+        let embed = CreateEmbed::new()
+            .description("Your profile has been updated.")
+            .color(ctx.data.config().embed_color);
+
+        ctx.reply(CreateReply::new().ephemeral(true).embed(embed))
+            .await
+    }
+}
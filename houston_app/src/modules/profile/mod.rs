@@ -1,5 +1,7 @@
 use super::prelude::*;
 
+pub mod buttons;
+pub mod model;
 mod slashies;
 
 pub struct Module;
@@ -12,4 +14,13 @@ impl super::Module for Module {
     fn commands(&self, _config: &HBotConfig) -> impl IntoIterator<Item = HCommand> {
         [slashies::profile_context(), slashies::profile()]
     }
+
+    fn db_init(db: &mongodb::Database) -> mongodb::BoxFuture<'_, Result> {
+        use crate::helper::bson::update_indices;
+        Box::pin(async move {
+            use model::*;
+            update_indices(Profile::collection(db), Profile::indices()).await?;
+            Ok(())
+        })
+    }
 }
@@ -0,0 +1,36 @@
+use crate::modules::model_prelude::*;
+
+/// User-editable fields of a server profile.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Profile {
+    pub _id: ObjectId,
+    #[serde(with = "id_as_i64")]
+    pub guild: GuildId,
+    #[serde(with = "id_as_i64")]
+    pub user: UserId,
+    pub pronouns: Option<String>,
+    pub bio: Option<String>,
+    #[serde(default)]
+    pub links: Vec<String>,
+    pub favorite_ship: Option<String>,
+}
+
+fn name(name: &str) -> IndexOptions {
+    IndexOptions::builder().name(name.to_owned()).build()
+}
+
+impl Profile {
+    pub fn collection(db: &Database) -> Collection<Self> {
+        db.collection("profile.fields")
+    }
+
+    pub fn indices() -> Vec<IndexModel> {
+        vec![IndexModel::builder()
+            .options(name("guild-user"))
+            .keys(doc! {
+                "guild": 1,
+                "user": 1,
+            })
+            .build()]
+    }
+}
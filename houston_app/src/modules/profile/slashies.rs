@@ -1,6 +1,11 @@
 use bson::doc;
+use chrono::Utc;
 use utils::text::write_str::*;
 
+use super::buttons::Edit;
+use super::model::Profile;
+use crate::buttons::ToCustomData as _;
+use crate::fmt::discord::escape_markdown;
 use crate::helper::bson::bson_id;
 use crate::modules::perks::DayOfYear;
 use crate::modules::Module as _;
@@ -46,20 +51,56 @@ async fn profile_core(
         .color(data.config().embed_color);
 
     let mut description = String::new();
+    let guild_id = ctx.require_guild_id()?;
+
+    let profile = profile_fields(ctx, member).await?;
+    if let Some(profile) = &profile {
+        if let Some(pronouns) = &profile.pronouns {
+            writeln_str!(description, "-# {}", escape_markdown(pronouns));
+        }
+
+        if let Some(bio) = &profile.bio {
+            writeln_str!(description, "{}", escape_markdown(bio));
+        }
+
+        if let Some(ship) = &profile.favorite_ship {
+            writeln_str!(
+                description,
+                "-# **Favorite Ship:** {}",
+                escape_markdown(ship)
+            );
+        }
 
-    if crate::modules::starboard::Module.enabled(data.config()) {
+        if !profile.links.is_empty() {
+            let mut links = String::new();
+            for link in &profile.links {
+                writeln_str!(links, "- <{link}>");
+            }
+
+            embed = embed.field("Links", links, false);
+        }
+    }
+
+    if crate::modules::starboard::Module.enabled(&data.config()) {
         if let Some(starboard) = starboard_info(ctx, member).await? {
             embed = embed.field("Starboard", starboard, true);
         }
     }
 
-    if crate::modules::perks::Module.enabled(data.config()) {
+    if crate::modules::perks::Module.enabled(&data.config()) {
         if let Some(unique_role) = perks_unique_role(ctx, member).await? {
             writeln_str!(description, "-# <@&{unique_role}>");
         }
 
         if let Some(birthday) = perks_birthday(ctx, member).await? {
-            writeln_str!(description, "-# **Birthday:** {birthday}");
+            let today = Utc::now().date_naive();
+            match birthday.next_date(today).map(|d| (d - today).num_days()) {
+                Some(0) => writeln_str!(description, "-# **Birthday:** {birthday} 🎂"),
+                Some(days) => {
+                    writeln_str!(description, "-# **Birthday:** {birthday} (in {days} days)")
+                },
+                None => writeln_str!(description, "-# **Birthday:** {birthday}"),
+            }
         }
 
         if let Some(info) = perks_collectible_info(ctx, member).await? {
@@ -68,12 +109,36 @@ async fn profile_core(
     }
 
     embed = embed.description(description);
-    let reply = CreateReply::new().embed(embed);
+    let mut reply = CreateReply::new().embed(embed);
+
+    // only offer editing when looking at your own profile
+    if member.user.id == ctx.user().id {
+        let edit = CreateButton::new(Edit::new(guild_id).to_custom_id())
+            .label("Edit Profile")
+            .emoji('✏')
+            .style(ButtonStyle::Secondary);
+
+        reply = reply.components(vec![CreateActionRow::buttons(vec![edit])]);
+    }
 
     ctx.send(reply).await?;
     Ok(())
 }
 
+async fn profile_fields(ctx: Context<'_>, member: SlashMember<'_>) -> Result<Option<Profile>> {
+    let data = ctx.data_ref();
+    let db = data.database()?;
+    let guild_id = ctx.require_guild_id()?;
+
+    let filter = doc! {
+        "guild": bson_id!(guild_id),
+        "user": bson_id!(member.user.id),
+    };
+
+    let profile = Profile::collection(db).find_one(filter).await?;
+    Ok(profile)
+}
+
 async fn perks_unique_role(ctx: Context<'_>, member: SlashMember<'_>) -> Result<Option<RoleId>> {
     use crate::modules::perks::model;
 
@@ -118,7 +183,8 @@ async fn perks_collectible_info(
 
     let data = ctx.data_ref();
     let db = data.database()?;
-    let perks = data.config().perks()?;
+    let config = data.config();
+    let perks = config.perks()?;
     let guild_id = ctx.require_guild_id()?;
 
     let Some(collectible) = perks.collectible.as_ref() else {
@@ -161,7 +227,8 @@ async fn starboard_info(ctx: Context<'_>, member: SlashMember<'_>) -> Result<Opt
     let db = data.database()?;
     let guild_id = ctx.require_guild_id()?;
 
-    let guild_config = data.config().starboard.get(&guild_id);
+    let config = data.config();
+    let guild_config = config.starboard.get(&guild_id);
 
     let Some(guild_config) = guild_config else {
         return Ok(None);
@@ -0,0 +1,10 @@
+fn default_max_quotes() -> u64 {
+    5000
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct Config {
+    /// The most quotes a single server may save.
+    #[serde(default = "default_max_quotes")]
+    pub max_quotes: u64,
+}
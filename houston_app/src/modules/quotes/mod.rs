@@ -0,0 +1,36 @@
+use super::prelude::*;
+
+pub mod config;
+pub mod model;
+mod slashies;
+
+pub use config::Config;
+
+pub struct Module;
+
+impl super::Module for Module {
+    fn enabled(&self, config: &HBotConfig) -> bool {
+        config.quotes.is_some()
+    }
+
+    fn commands(&self, _config: &HBotConfig) -> impl IntoIterator<Item = HCommand> {
+        [slashies::save_quote(), slashies::quote_collection()]
+    }
+
+    fn db_init(db: &mongodb::Database) -> mongodb::BoxFuture<'_, Result> {
+        use crate::helper::bson::update_indices;
+        Box::pin(async move {
+            use model::*;
+            update_indices(Quote::collection(db), Quote::indices()).await?;
+            Ok(())
+        })
+    }
+
+    fn validate(&self, config: &HBotConfig) -> Result {
+        anyhow::ensure!(
+            config.mongodb_uri.is_some(),
+            "quotes requires a mongodb_uri"
+        );
+        Ok(())
+    }
+}
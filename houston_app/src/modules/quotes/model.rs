@@ -0,0 +1,121 @@
+use crate::modules::model_prelude::*;
+
+/// A message saved as a quote.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Quote {
+    pub _id: ObjectId,
+    #[serde(with = "id_as_i64")]
+    pub guild: GuildId,
+    #[serde(with = "id_as_i64")]
+    pub channel: ChannelId,
+    #[serde(with = "id_as_i64")]
+    pub message: MessageId,
+    #[serde(with = "id_as_i64")]
+    pub author: UserId,
+    #[serde(with = "id_as_i64")]
+    pub saved_by: UserId,
+    pub content: String,
+    /// When the message was sent.
+    #[serde(with = "chrono_datetime_as_bson_datetime")]
+    pub time: DateTime<Utc>,
+}
+
+impl Quote {
+    pub fn collection(db: &Database) -> Collection<Self> {
+        db.collection("quotes.quotes")
+    }
+
+    pub fn indices() -> Vec<IndexModel> {
+        vec![IndexModel::builder()
+            .options(
+                IndexOptions::builder()
+                    .name("guild-message".to_owned())
+                    .unique(true)
+                    .build(),
+            )
+            .keys(doc! {
+                "guild": 1,
+                "message": 1,
+            })
+            .build()]
+    }
+
+    /// Gets a link to the quoted message.
+    pub fn link(&self) -> String {
+        format!(
+            "https://discord.com/channels/{}/{}/{}",
+            self.guild, self.channel, self.message
+        )
+    }
+}
+
+pub trait QuoteExt {
+    async fn save_quote(&self, quote: &Quote, max_quotes: u64) -> Result;
+
+    async fn random_quote(
+        &self,
+        guild_id: GuildId,
+        author: Option<UserId>,
+    ) -> Result<Option<Quote>>;
+}
+
+impl QuoteExt for Collection<Quote> {
+    async fn save_quote(&self, quote: &Quote, max_quotes: u64) -> Result {
+        let filter = doc! {
+            "guild": bson_id!(quote.guild),
+            "message": bson_id!(quote.message),
+        };
+
+        if self.count_documents(filter).await? != 0 {
+            return Err(HArgError::new_const("This message is already saved as a quote.").into());
+        }
+
+        let count = self
+            .count_documents(doc! { "guild": bson_id!(quote.guild) })
+            .await?;
+
+        if count >= max_quotes {
+            return Err(HArgError::new(format!(
+                "This server can only save up to {max_quotes} quotes."
+            ))
+            .into());
+        }
+
+        self.insert_one(quote).await?;
+        Ok(())
+    }
+
+    async fn random_quote(
+        &self,
+        guild_id: GuildId,
+        author: Option<UserId>,
+    ) -> Result<Option<Quote>> {
+        let mut filter = doc! {
+            "guild": bson_id!(guild_id),
+        };
+
+        if let Some(author) = author {
+            filter.insert("author", bson_id!(author));
+        }
+
+        let pipeline = [
+            doc! {
+                "$match": filter,
+            },
+            doc! {
+                "$sample": {
+                    "size": 1,
+                },
+            },
+        ];
+
+        let quote = self
+            .aggregate(pipeline)
+            .with_type::<Quote>()
+            .await?
+            .try_next()
+            .await?;
+
+        Ok(quote)
+    }
+}
@@ -0,0 +1,162 @@
+use bson::doc;
+use bson::oid::ObjectId;
+use utils::fuzzy::Search;
+use utils::text::truncate;
+use utils::text::write_str::*;
+
+use super::model::*;
+use crate::fmt::discord::TimeMentionable as _;
+use crate::helper::bson::bson_id;
+use crate::slashies::prelude::*;
+
+/// The most message content saved per quote.
+const MAX_CONTENT_LEN: usize = 2000;
+
+/// The most results shown by `/quote search`.
+const MAX_SEARCH_RESULTS: usize = 5;
+
+/// Saves the message to the server's quote collection.
+#[context_command(
+    message,
+    name = "Save as Quote",
+    contexts = "Guild",
+    integration_types = "Guild"
+)]
+pub async fn save_quote(ctx: Context<'_>, message: &Message) -> Result {
+    let data = ctx.data_ref();
+    let guild_id = ctx.require_guild_id()?;
+    let config = data.config();
+    let quotes = config.quotes()?;
+    let db = data.database()?;
+
+    anyhow::ensure!(
+        !message.content.trim().is_empty(),
+        HArgError::new_const("Only messages with text can be saved as quotes.")
+    );
+
+    ctx.defer_as(Ephemeral).await?;
+
+    // the guild and channel of resolved messages aren't always set
+    let quote = Quote {
+        _id: ObjectId::new(),
+        guild: guild_id,
+        channel: ctx.channel_id(),
+        message: message.id,
+        author: message.author.id,
+        saved_by: ctx.user().id,
+        content: truncate(message.content.as_str(), MAX_CONTENT_LEN).into_owned(),
+        time: *message.timestamp,
+    };
+
+    Quote::collection(db)
+        .save_quote(&quote, quotes.max_quotes)
+        .await?;
+
+    let embed = CreateEmbed::new()
+        .color(config.embed_color)
+        .description(format!("Saved quote by <@{}>.", quote.author));
+
+    ctx.send(CreateReply::new().embed(embed)).await?;
+    Ok(())
+}
+
+/// Browse the server's saved quotes.
+#[chat_command(name = "quote", contexts = "Guild", integration_types = "Guild")]
+pub mod quote_collection {
+    /// Shows a random saved quote.
+    #[sub_command]
+    async fn random(
+        ctx: Context<'_>,
+        /// Only show quotes by this user.
+        user: Option<SlashUser<'_>>,
+        /// Whether to show the response only to yourself.
+        ephemeral: Option<bool>,
+    ) -> Result {
+        let data = ctx.data_ref();
+        let guild_id = ctx.require_guild_id()?;
+        let db = data.database()?;
+
+        ctx.defer_as(ephemeral).await?;
+
+        let quote = Quote::collection(db)
+            .random_quote(guild_id, user.map(|u| u.user.id))
+            .await?
+            .ok_or(HArgError::new_const("There are no matching quotes yet."))?;
+
+        let embed = create_quote_embed(data, &quote);
+        ctx.send(CreateReply::new().embed(embed)).await?;
+        Ok(())
+    }
+
+    /// Searches the saved quotes by their text.
+    #[sub_command]
+    async fn search(
+        ctx: Context<'_>,
+        /// The text to search for.
+        #[max_length = 100]
+        text: &str,
+        /// Whether to show the response only to yourself.
+        ephemeral: Option<bool>,
+    ) -> Result {
+        let data = ctx.data_ref();
+        let guild_id = ctx.require_guild_id()?;
+        let db = data.database()?;
+
+        ctx.defer_as(ephemeral).await?;
+
+        let filter = doc! {
+            "guild": bson_id!(guild_id),
+        };
+
+        let quotes: Vec<Quote> = Quote::collection(db)
+            .find(filter)
+            .await?
+            .try_collect()
+            .await?;
+
+        let mut search = Search::<usize>::new();
+        for (index, quote) in quotes.iter().enumerate() {
+            search.insert(&quote.content, index);
+        }
+
+        let mut description = String::new();
+        for m in search.search(text).take(MAX_SEARCH_RESULTS) {
+            let quote = &quotes[*m.data];
+            writeln_str!(
+                description,
+                "- {}\n  -# by <@{}> {} • {}",
+                truncate(quote.content.replace('\n', " "), 200),
+                quote.author,
+                quote.time.short_date(),
+                quote.link(),
+            );
+        }
+
+        if description.is_empty() {
+            return Err(HArgError::new_const("No quotes match that text.").into());
+        }
+
+        let embed = CreateEmbed::new()
+            .title("Quote Search")
+            .description(description)
+            .color(data.config().embed_color);
+
+        ctx.send(CreateReply::new().embed(embed)).await?;
+        Ok(())
+    }
+}
+
+/// Creates the embed showing a single quote.
+fn create_quote_embed<'a>(data: &HBotData, quote: &'a Quote) -> CreateEmbed<'a> {
+    let description = format!(
+        "{}\n\n— <@{}> • {}",
+        quote.content,
+        quote.author,
+        quote.link()
+    );
+
+    CreateEmbed::new()
+        .description(description)
+        .color(data.config().embed_color)
+        .timestamp(Timestamp::from(quote.time))
+}
@@ -0,0 +1,224 @@
+//! Runs persistent one-shot and recurring jobs.
+//!
+//! Jobs are stored in the database along with when they are next due. A
+//! single background task claims due jobs by pushing their due time back by a
+//! lease, runs them, and then either deletes them or schedules their next run.
+//! If the bot stops while a job is running, it is run again once the lease
+//! expires.
+
+use std::time::Duration;
+
+use bson::oid::ObjectId;
+use bson::{doc, Bson};
+use chrono::prelude::*;
+use chrono::TimeDelta;
+use tokio::sync::Notify;
+
+use super::prelude::*;
+use crate::helper::bson::id_as_i64;
+
+pub mod model;
+
+use model::*;
+
+/// How long a claimed job is kept from running again.
+const LEASE: TimeDelta = TimeDelta::minutes(5);
+
+/// The longest time to wait before checking for due jobs again.
+const MAX_SLEEP: Duration = Duration::from_secs(60);
+
+/// How often a one-shot job is tried before it is dropped.
+const MAX_ATTEMPTS: u32 = 3;
+
+/// Notified when a job is scheduled, in case it is due before the next check.
+static WAKE: Notify = Notify::const_new();
+
+pub struct Module;
+
+impl super::Module for Module {
+    fn enabled(&self, config: &HBotConfig) -> bool {
+        config.mongodb_uri.is_some()
+    }
+
+    fn db_init(db: &mongodb::Database) -> mongodb::BoxFuture<'_, Result> {
+        use crate::helper::bson::update_indices;
+        Box::pin(async move {
+            update_indices(Job::collection(db), Job::indices()).await?;
+            Ok(())
+        })
+    }
+}
+
+/// The kinds of jobs, along with their arguments.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "kind", content = "args")]
+pub enum JobKind {
+    /// Expires active perks and updates perk effects.
+    PerksCheck,
+    /// Deletes old usage records.
+    AnalyticsPrune,
+    /// Closes a poll once its time is up.
+    PollClose { poll: ObjectId },
+    /// Forfeits a minigame if its active player has run out of time.
+    GameTimeout {
+        #[serde(with = "id_as_i64")]
+        message: MessageId,
+    },
+    /// Refunds the stake of a blackjack game that has been idle for too long.
+    BlackjackExpire { game: i64 },
+}
+
+impl JobKind {
+    async fn run(self, ctx: &Context) -> Result {
+        match self {
+            Self::PerksCheck => super::perks::check_perks(ctx).await,
+            Self::AnalyticsPrune => super::analytics::prune(ctx).await,
+            Self::PollClose { poll } => super::polls::close(ctx, poll).await,
+            Self::GameTimeout { message } => {
+                super::minigame::buttons::timeout::check_timeout(ctx, message).await
+            },
+            Self::BlackjackExpire { game } => {
+                super::minigame::buttons::blackjack::expire_wager(ctx, game).await
+            },
+        }
+    }
+}
+
+/// Schedules a job to run once at the given time.
+pub async fn schedule(
+    db: &mongodb::Database,
+    job: &JobKind,
+    due: DateTime<Utc>,
+) -> Result<ObjectId> {
+    let job = Job {
+        _id: ObjectId::new(),
+        key: None,
+        job: bson::to_bson(job)?,
+        due,
+        interval: None,
+        attempts: 0,
+    };
+
+    Job::collection(db).insert_one(&job).await?;
+    WAKE.notify_one();
+    Ok(job._id)
+}
+
+/// Schedules a job to run repeatedly, identified by a unique key.
+///
+/// Registering the same key again updates the job and its interval, but keeps
+/// the time of its next run.
+pub async fn schedule_recurring(
+    db: &mongodb::Database,
+    key: &str,
+    job: &JobKind,
+    interval: TimeDelta,
+) -> Result {
+    Job::collection(db)
+        .upsert_recurring(key, bson::to_bson(job)?, interval.num_seconds(), Utc::now())
+        .await?;
+
+    WAKE.notify_one();
+    Ok(())
+}
+
+/// Cancels a job scheduled with [`schedule`].
+pub async fn cancel(db: &mongodb::Database, id: ObjectId) -> Result {
+    Job::collection(db).delete_one(doc! { "_id": id }).await?;
+    Ok(())
+}
+
+/// Cancels a job scheduled with [`schedule_recurring`].
+pub async fn cancel_recurring(db: &mongodb::Database, key: &str) -> Result {
+    Job::collection(db).delete_one(doc! { "key": key }).await?;
+    Ok(())
+}
+
+/// Starts the background task that runs due jobs.
+pub fn spawn_scheduler(ctx: &Context) {
+    let data = ctx.data_ref::<HContextData>();
+    if !Module.enabled(&data.config()) {
+        return;
+    }
+
+    let ctx = ctx.clone();
+    tokio::task::spawn(async move {
+        loop {
+            let sleep = match run_due_jobs(&ctx).await {
+                Ok(Some(next)) => (next - Utc::now())
+                    .to_std()
+                    .unwrap_or(Duration::ZERO)
+                    .min(MAX_SLEEP),
+                Ok(None) => MAX_SLEEP,
+                Err(why) => {
+                    log::error!("Scheduler check failed: {why:?}");
+                    MAX_SLEEP
+                },
+            };
+
+            tokio::select! {
+                () = tokio::time::sleep(sleep) => {},
+                () = WAKE.notified() => {},
+            }
+        }
+    });
+}
+
+/// Starts all due jobs and returns when the next one is due.
+async fn run_due_jobs(ctx: &Context) -> Result<Option<DateTime<Utc>>> {
+    let data = ctx.data_ref::<HContextData>();
+    let jobs = Job::collection(data.database()?);
+
+    loop {
+        let now = Utc::now();
+        let Some(job) = jobs.claim_due(now, now + LEASE).await? else {
+            break;
+        };
+
+        tokio::task::spawn(run_job(ctx.clone(), job));
+    }
+
+    jobs.next_due().await
+}
+
+async fn run_job(ctx: Context, job: Job) {
+    // jobs may create components, so they need the custom id signer
+    let data = ctx.data_ref::<HContextData>();
+    let result = crate::buttons::scope(data, run_job_core(&ctx, job)).await;
+    if let Err(why) = result {
+        log::error!("Failed to finish job: {why:?}");
+    }
+}
+
+async fn run_job_core(ctx: &Context, job: Job) -> Result {
+    let data = ctx.data_ref::<HContextData>();
+    let jobs = Job::collection(data.database()?);
+
+    let kind = match bson::from_bson::<JobKind>(job.job) {
+        Ok(kind) => kind,
+        Err(why) => {
+            log::warn!("Dropping job {} that cannot be loaded: {why:?}", job._id);
+            jobs.delete_one(doc! { "_id": job._id }).await?;
+            return Ok(());
+        },
+    };
+
+    log::trace!("Running job {}: {kind:?}", job._id);
+
+    let result = kind.run(ctx).await;
+    if let Err(why) = &result {
+        log::error!("Job {} failed: {why:?}", job._id);
+    }
+
+    if let Some(interval) = job.interval {
+        // skip runs that were missed instead of catching up on them
+        let now = Utc::now();
+        let next = job.due + TimeDelta::seconds(interval);
+        jobs.reschedule(job._id, next.max(now)).await?;
+    } else if result.is_ok() || job.attempts + 1 >= MAX_ATTEMPTS {
+        jobs.delete_one(doc! { "_id": job._id }).await?;
+    }
+
+    // otherwise, the job is tried again once the lease expires
+    Ok(())
+}
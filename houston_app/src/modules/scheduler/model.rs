@@ -0,0 +1,155 @@
+use crate::modules::model_prelude::*;
+
+/// A job waiting to be run by the scheduler.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Job {
+    pub _id: ObjectId,
+    /// Unique key of recurring jobs, so they are only registered once.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    /// The serialized [`JobKind`](super::JobKind).
+    ///
+    /// Kept as raw BSON so jobs that can no longer be loaded can be dropped.
+    pub job: Bson,
+    #[serde(with = "chrono_datetime_as_bson_datetime")]
+    pub due: DateTime<Utc>,
+    /// Seconds between runs, if the job is recurring.
+    #[serde(default)]
+    pub interval: Option<i64>,
+    /// Failed attempts to run the job.
+    #[serde(default)]
+    pub attempts: u32,
+}
+
+impl Job {
+    pub fn collection(db: &Database) -> Collection<Self> {
+        db.collection("scheduler.jobs")
+    }
+
+    pub fn indices() -> Vec<IndexModel> {
+        vec![
+            IndexModel::builder()
+                .options(IndexOptions::builder().name("due".to_owned()).build())
+                .keys(doc! {
+                    "due": 1,
+                })
+                .build(),
+            IndexModel::builder()
+                .options(
+                    IndexOptions::builder()
+                        .name("key".to_owned())
+                        .unique(true)
+                        .sparse(true)
+                        .build(),
+                )
+                .keys(doc! {
+                    "key": 1,
+                })
+                .build(),
+        ]
+    }
+}
+
+pub trait JobExt {
+    /// Claims the job that is due the longest, if any.
+    ///
+    /// Its due time is pushed back to `lease_until`, so it isn't claimed again
+    /// while it runs. Returns the job as it was before being claimed.
+    async fn claim_due(
+        &self,
+        now: DateTime<Utc>,
+        lease_until: DateTime<Utc>,
+    ) -> Result<Option<Job>>;
+
+    /// Gets the time the next job is due, if any.
+    async fn next_due(&self) -> Result<Option<DateTime<Utc>>>;
+
+    /// Sets when a job is next due and resets its attempts.
+    async fn reschedule(&self, id: ObjectId, due: DateTime<Utc>) -> Result;
+
+    /// Inserts or updates a recurring job by its key.
+    ///
+    /// When it already exists, its next due time is kept.
+    async fn upsert_recurring(
+        &self,
+        key: &str,
+        job: Bson,
+        interval: i64,
+        due: DateTime<Utc>,
+    ) -> Result;
+}
+
+impl JobExt for Collection<Job> {
+    async fn claim_due(
+        &self,
+        now: DateTime<Utc>,
+        lease_until: DateTime<Utc>,
+    ) -> Result<Option<Job>> {
+        let filter = doc! {
+            "due": {
+                "$lte": Bson::DateTime(now.into()),
+            },
+        };
+
+        let update = doc! {
+            "$set": {
+                "due": Bson::DateTime(lease_until.into()),
+            },
+            "$inc": {
+                "attempts": 1,
+            },
+        };
+
+        let job = self
+            .find_one_and_update(filter, update)
+            .sort(doc! { "due": 1 })
+            .return_document(ReturnDocument::Before)
+            .await?;
+
+        Ok(job)
+    }
+
+    async fn next_due(&self) -> Result<Option<DateTime<Utc>>> {
+        let job = self.find_one(doc! {}).sort(doc! { "due": 1 }).await?;
+
+        Ok(job.map(|j| j.due))
+    }
+
+    async fn reschedule(&self, id: ObjectId, due: DateTime<Utc>) -> Result {
+        let update = doc! {
+            "$set": {
+                "due": Bson::DateTime(due.into()),
+                "attempts": 0,
+            },
+        };
+
+        self.update_one(doc! { "_id": id }, update).await?;
+        Ok(())
+    }
+
+    async fn upsert_recurring(
+        &self,
+        key: &str,
+        job: Bson,
+        interval: i64,
+        due: DateTime<Utc>,
+    ) -> Result {
+        let filter = doc! {
+            "key": key,
+        };
+
+        let update = doc! {
+            "$set": {
+                "job": job,
+                "interval": interval,
+            },
+            "$setOnInsert": {
+                "due": Bson::DateTime(due.into()),
+                "attempts": 0,
+            },
+        };
+
+        self.update_one(filter, update).upsert(true).await?;
+        Ok(())
+    }
+}
@@ -1,2 +1,3 @@
+pub mod random;
 pub mod top;
 pub mod top_posts;
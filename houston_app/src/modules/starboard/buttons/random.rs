@@ -0,0 +1,159 @@
+use bson::{doc, Bson};
+use serenity::prelude::*;
+use utils::text::truncate;
+
+use crate::buttons::prelude::*;
+use crate::fmt::discord::get_unique_username;
+use crate::helper::discord::id_as_u64;
+use crate::modules::starboard::config::StarboardEntry;
+use crate::modules::starboard::{create_link_embed, get_board, is_nsfw, model, BoardId};
+
+// View a random pinned post.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct View {
+    #[serde(with = "id_as_u64")]
+    pub guild: GuildId,
+    pub board: Option<BoardId>,
+}
+
+impl View {
+    pub fn new(guild: GuildId, board: Option<BoardId>) -> Self {
+        Self { guild, board }
+    }
+
+    pub async fn create_reply<'new>(self, ctx: &Context) -> Result<CreateReply<'new>> {
+        let data = ctx.data_ref::<HContextData>();
+        let config = data.config();
+        let db = data.database()?;
+
+        let boards = match self.board {
+            Some(board) => {
+                get_board(&config, self.guild, board)?;
+                Bson::from(vec![board.get()])
+            },
+            None => config
+                .starboard
+                .get(&self.guild)
+                .context("starboard not configured for this guild")?
+                .board_db_keys(),
+        };
+
+        let pipeline = [
+            doc! {
+                "$match": {
+                    "board": {
+                        "$in": boards,
+                    },
+                    "pinned": true,
+                },
+            },
+            doc! {
+                "$sample": {
+                    "size": 1,
+                },
+            },
+        ];
+
+        let item = model::Message::collection(db)
+            .aggregate(pipeline)
+            .with_type::<model::Message>()
+            .await?
+            .try_next()
+            .await?
+            .ok_or(HArgError::new_const("There are no pinned posts yet."))?;
+
+        let board = get_board(&config, self.guild, item.board)?;
+        let embed = self.create_embed(ctx, data, board, &item).await;
+
+        let button = CreateButton::new(self.to_custom_id())
+            .label("Another one")
+            .emoji('🎲')
+            .style(ButtonStyle::Secondary);
+
+        let components = vec![CreateActionRow::buttons(vec![button])];
+        let reply = CreateReply::new().embed(embed).components(components);
+
+        Ok(reply)
+    }
+
+    /// Renders a post much like it was forwarded to the board.
+    ///
+    /// If the source message is gone or from an nsfw channel, only the link
+    /// is shown.
+    async fn create_embed(
+        &self,
+        ctx: &Context,
+        data: &HBotData,
+        board: &StarboardEntry,
+        item: &model::Message,
+    ) -> CreateEmbed<'static> {
+        let link = format!(
+            "https://discord.com/channels/{}/{}/{}",
+            self.guild, item.channel, item.message,
+        );
+
+        let footer = format!("{} {}", board.emoji.name(), item.max_reacts);
+        let fallback = || {
+            CreateEmbed::new()
+                .description(format!("{link}\n-# by <@{}>", item.user))
+                .footer(CreateEmbedFooter::new(footer.clone()))
+                .color(data.config().embed_color)
+        };
+
+        // nsfw messages are only linked, so their content doesn't matter
+        let is_nsfw = is_nsfw(ctx, self.guild, item.channel).await.unwrap_or(true);
+        if is_nsfw {
+            return create_link_embed(
+                data,
+                self.guild,
+                item.channel,
+                item.message,
+                board,
+                item.max_reacts,
+            );
+        }
+
+        let Ok(message) = item.channel.message(&ctx.http, item.message).await else {
+            return fallback();
+        };
+
+        let mut description = truncate(message.content.to_string(), 3900);
+        if !description.is_empty() {
+            description.push_str("\n\n");
+        }
+
+        description.push_str(&link);
+
+        let author = get_unique_username(&message.author).into_owned();
+        let author = CreateEmbedAuthor::new(author).icon_url(message.author.face());
+
+        let mut embed = CreateEmbed::new()
+            .author(author)
+            .description(description)
+            .footer(CreateEmbedFooter::new(footer))
+            .color(data.config().embed_color)
+            .timestamp(message.timestamp);
+
+        let image = message.attachments.iter().find(|a| {
+            a.content_type
+                .as_deref()
+                .is_some_and(|t| t.starts_with("image/"))
+        });
+
+        if let Some(image) = image {
+            embed = embed.image(image.url.to_string());
+        }
+
+        embed
+    }
+}
+
+impl ButtonArgsReply for View {
+    async fn reply(self, ctx: ButtonContext<'_>) -> Result {
+        ctx.acknowledge().await?;
+
+        let reply = self.create_reply(ctx.serenity).await?;
+        ctx.edit(reply.into()).await?;
+        Ok(())
+    }
+}
@@ -12,27 +12,45 @@ pub struct View {
     #[serde(with = "id_as_u64")]
     pub guild: GuildId,
     pub board: BoardId,
+    pub season: Option<u32>,
     pub page: u16,
 }
 
 impl View {
-    pub fn new(guild: GuildId, board: BoardId) -> Self {
+    pub fn new(guild: GuildId, board: BoardId, season: Option<u32>) -> Self {
         Self {
             guild,
             board,
+            season,
             page: 0,
         }
     }
 
+    /// Gets the filter for the scores of the viewed board and season.
+    fn filter(&self) -> bson::Document {
+        let mut filter = doc! {
+            "board": self.board.get(),
+        };
+
+        if let Some(season) = self.season {
+            filter.insert("season", i64::from(season));
+        }
+
+        filter
+    }
+
     pub async fn create_reply<'new>(mut self, data: &HBotData) -> Result<CreateReply<'new>> {
         const PAGE_SIZE: u32 = 15;
         const MAX_PAGE: u16 = 50;
 
+        let config = data.config();
         let db = data.database()?;
-        let board = get_board(data.config(), self.guild, self.board)?;
+        let board = get_board(&config, self.guild, self.board)?;
 
-        let filter = doc! {
-            "board": self.board.get(),
+        // past seasons have the same shape, just in the archive
+        let collection = match self.season {
+            None => model::Score::collection(db),
+            Some(_) => model::ScoreArchive::collection(db).clone_with_type(),
         };
 
         let sort = doc! {
@@ -41,8 +59,8 @@ impl View {
         };
 
         let offset = u64::from(PAGE_SIZE) * u64::from(self.page);
-        let mut cursor = model::Score::collection(db)
-            .find(filter)
+        let mut cursor = collection
+            .find(self.filter())
             .sort(sort)
             .limit((PAGE_SIZE + 1).into())
             .skip(offset)
@@ -74,12 +92,8 @@ impl View {
 
         let has_more = index >= u64::from(PAGE_SIZE);
         let page_count = if has_more {
-            let filter = doc! {
-                "board": self.board.get(),
-            };
-
-            model::Score::collection(db)
-                .count_documents(filter)
+            collection
+                .count_documents(self.filter())
                 .limit((u64::from(MAX_PAGE) + 1) * u64::from(PAGE_SIZE))
                 .await?
                 .div_ceil(PAGE_SIZE.into())
@@ -90,9 +104,14 @@ impl View {
 
         let description = crate::fmt::written_or(description, "<None>");
 
+        let title = match self.season {
+            Some(season) => format!("{} Leaderboards: Season {season}", board.emoji),
+            None => format!("{} Leaderboards", board.emoji),
+        };
+
         let embed = CreateEmbed::new()
-            .title(format!("{} Leaderboards", board.emoji))
-            .color(data.config().embed_color)
+            .title(title)
+            .color(config.embed_color)
             .description(description);
 
         let components = ToPage::build_row(&mut self, |s| &mut s.page)
@@ -28,8 +28,9 @@ impl View {
         const PAGE_SIZE: u32 = 15;
         const MAX_PAGE: u16 = 50;
 
+        let config = data.config();
         let db = data.database()?;
-        let board = get_board(data.config(), self.guild, self.board)?;
+        let board = get_board(&config, self.guild, self.board)?;
 
         let filter = doc! {
             "board": self.board.get(),
@@ -94,7 +95,7 @@ impl View {
 
         let embed = CreateEmbed::new()
             .title(format!("{} Top Posts", board.emoji))
-            .color(data.config().embed_color)
+            .color(config.embed_color)
             .description(description);
 
         let components = ToPage::build_row(&mut self, |s| &mut s.page)
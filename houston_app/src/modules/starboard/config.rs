@@ -33,6 +33,12 @@ impl From<i64> for BoardId {
 pub struct StarboardGuild {
     #[serde(default)]
     pub remove_score_on_delete: bool,
+    #[serde(default)]
+    pub ignore_channels: Vec<ChannelId>,
+    #[serde(default)]
+    pub ignore_categories: Vec<ChannelId>,
+    #[serde(default)]
+    pub ignore_roles: Vec<RoleId>,
     #[serde(with = "board_order_fix")]
     pub boards: IndexMap<BoardId, StarboardEntry>,
 }
@@ -52,6 +58,11 @@ mod board_order_fix {
 }
 
 impl StarboardGuild {
+    /// Whether any of the roles are ignored.
+    pub fn has_ignored_role(&self, roles: &[RoleId]) -> bool {
+        roles.iter().any(|r| self.ignore_roles.contains(r))
+    }
+
     /// Gets the BSON database keys for this guild.
     ///
     /// This is intended to be used with an `$in` filter.
@@ -74,12 +85,49 @@ pub struct StarboardEntry {
     pub cash_pin_gain: i32,
     #[serde(default)]
     pub sort: i8,
+    #[serde(default = "Vec::new")]
+    pub tiers: Vec<StarboardTier>,
+    /// Once a message has this many reacts, the bot adds the emoji itself to
+    /// nudge others into finishing the pin.
+    #[serde(default)]
+    pub nudge_reacts: Option<u32>,
+}
+
+/// An additional threshold a post may reach after being pinned.
+///
+/// Tiers are identified by their position in the list, so they shouldn't be
+/// reordered once posts have reached them.
+#[derive(Debug, serde::Deserialize)]
+pub struct StarboardTier {
+    pub reacts: u32,
+    pub channel: Option<ChannelId>,
+    #[serde(default = "Vec::new")]
+    pub notices: Vec<String>,
 }
 
 impl StarboardEntry {
     pub fn any_cash_gain(&self) -> bool {
         self.cash_gain != 0 || self.cash_pin_gain != 0
     }
+
+    /// Gets all channels this board posts to, including its tiers.
+    pub fn channels(&self) -> impl Iterator<Item = ChannelId> + '_ {
+        std::iter::once(self.channel).chain(self.tiers.iter().map(|t| self.tier_channel(t)))
+    }
+
+    /// Gets the channel a tier posts to.
+    pub fn tier_channel(&self, tier: &StarboardTier) -> ChannelId {
+        tier.channel.unwrap_or(self.channel)
+    }
+
+    /// Gets the notices used for a tier.
+    pub fn tier_notices<'a>(&'a self, tier: &'a StarboardTier) -> &'a [String] {
+        if tier.notices.is_empty() {
+            &self.notices
+        } else {
+            &tier.notices
+        }
+    }
 }
 
 #[derive(Debug)]
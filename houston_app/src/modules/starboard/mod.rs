@@ -1,6 +1,8 @@
 use std::char;
+use std::sync::LazyLock;
 
-use bson::doc;
+use bson::{doc, Bson};
+use dashmap::DashMap;
 use mongodb::options::ReturnDocument;
 use rand::prelude::*;
 use utils::text::write_str::*;
@@ -8,6 +10,7 @@ use utils::text::write_str::*;
 use super::prelude::*;
 use crate::fmt::replace_holes;
 use crate::helper::bson::{bson_id, doc_object_id};
+use crate::helper::discord::log_event_error;
 use crate::helper::is_unique_set;
 
 pub mod buttons;
@@ -38,6 +41,7 @@ impl super::Module for Module {
             use model::*;
             update_indices(Message::collection(db), Message::indices()).await?;
             update_indices(Score::collection(db), Score::indices()).await?;
+            update_indices(ScoreArchive::collection(db), ScoreArchive::indices()).await?;
             Ok(())
         })
     }
@@ -53,6 +57,22 @@ impl super::Module for Module {
             "starboard requires a mongodb_uri",
         );
 
+        for (id, board) in config.starboard.values().flat_map(|g| &g.boards) {
+            anyhow::ensure!(
+                board.tiers.iter().all(|t| t.reacts > board.reacts),
+                "starboard {} tiers must require more reacts than the board itself",
+                id.get(),
+            );
+
+            anyhow::ensure!(
+                board
+                    .nudge_reacts
+                    .is_none_or(|n| n != 0 && n < board.reacts),
+                "starboard {} nudge_reacts must be less than its reacts",
+                id.get(),
+            );
+        }
+
         log::info!("Starboard is enabled: {} guild(s)", config.starboard.len());
 
         Ok(())
@@ -75,7 +95,13 @@ fn get_board(
 
 pub async fn reaction_add(ctx: Context, reaction: Reaction) {
     if let Err(why) = reaction_add_inner(ctx, reaction).await {
-        log::error!("Reaction handling failed: {why:?}");
+        log_event_error!(why, "Reaction handling failed");
+    }
+}
+
+pub async fn reaction_remove(ctx: Context, reaction: Reaction) {
+    if let Err(why) = reaction_remove_inner(ctx, reaction).await {
+        log_event_error!(why, "Reaction removal handling failed");
     }
 }
 
@@ -90,7 +116,7 @@ pub async fn message_delete(
     };
 
     if let Err(why) = message_delete_inner(ctx, guild_id, channel_id, message_id).await {
-        log::error!("Message delete handling failed: {why:?}");
+        log_event_error!(why, "Message delete handling failed");
     }
 }
 
@@ -106,18 +132,19 @@ async fn reaction_add_inner(ctx: Context, reaction: Reaction) -> Result {
     let data = ctx.data_ref::<HContextData>();
 
     // grab the config for the current guild
-    let guild_config = data.config().starboard.get(&guild_id);
+    let config = data.config();
+    let guild_config = config.starboard.get(&guild_id);
     let Some(guild_config) = guild_config else {
         return Ok(());
     };
 
-    // ignore messages in board channels
-    let is_board = guild_config
-        .boards
-        .values()
-        .any(|b| b.channel == reaction.channel_id);
+    // ignore reactions by members with ignored roles
+    let by_ignored_role = reaction
+        .member
+        .as_ref()
+        .is_some_and(|m| guild_config.has_ignored_role(&m.roles));
 
-    if is_board {
+    if by_ignored_role {
         return Ok(());
     }
 
@@ -130,6 +157,15 @@ async fn reaction_add_inner(ctx: Context, reaction: Reaction) -> Result {
         return Ok(());
     };
 
+    // ignore messages in board channels
+    if is_board_channel(&ctx, guild_config, guild_id, reaction.channel_id).await? {
+        return Ok(());
+    }
+
+    if is_channel_ignored(&ctx, guild_config, guild_id, reaction.channel_id).await? {
+        return Ok(());
+    }
+
     // avoid using the cache here even if it is enabled
     // we want to ensure that we have the fresh current state
     let message = reaction.message(&ctx.http).await?;
@@ -140,6 +176,16 @@ async fn reaction_add_inner(ctx: Context, reaction: Reaction) -> Result {
         return Ok(());
     }
 
+    // messages by members with ignored roles never count
+    if !guild_config.ignore_roles.is_empty() {
+        let author = ctx.http.get_member(guild_id, message.author.id).await;
+
+        // if they left the server, they don't have any roles either
+        if author.is_ok_and(|m| guild_config.has_ignored_role(&m.roles)) {
+            return Ok(());
+        }
+    }
+
     let reaction = message
         .reactions
         .iter()
@@ -151,39 +197,19 @@ async fn reaction_add_inner(ctx: Context, reaction: Reaction) -> Result {
     let score_increase = {
         // update the message document, if we have enough reacts
         let required_reacts = i64::from(board.reacts);
-
-        // get the current reaction count
-        // discount the bot's own reactions including supers,
-        // even though bots can't add them anymore
-        let mut now_reacts = i64::try_from(reaction.count)?;
-        if reaction.me || reaction.me_burst {
-            now_reacts -= 1;
-        }
-
+        let nudge_reacts = board.nudge_reacts.map(i64::from);
+        let count_from = nudge_reacts.unwrap_or(required_reacts);
+        let now_reacts =
+            count_reacts(&ctx, guild_id, guild_config, &message, reaction, count_from).await?;
         if now_reacts < required_reacts {
-            return Ok(());
-        }
-
-        // if the author of this message has reacted, we subtract 1 from the count
-        // so their own reaction does not contribute score
-        // if there are super reactions, also check there
-        let has_self_reaction = |burst| {
-            has_reaction_by_user(
-                &ctx,
-                &message,
-                &reaction.reaction_type,
-                message.author.id,
-                burst,
-            )
-        };
-        let has_self_reaction = has_self_reaction(false).await?
-            || (reaction.count_details.burst != 0 && has_self_reaction(true).await?);
-
-        if has_self_reaction {
-            now_reacts -= 1;
-            if now_reacts < required_reacts {
-                return Ok(());
+            // the bot's own react is discounted, so this doesn't count towards the pin
+            if nudge_reacts.is_some_and(|n| now_reacts >= n) && !reaction.me {
+                message
+                    .react(&ctx.http, board.emoji.as_emoji().clone())
+                    .await?;
             }
+
+            return Ok(());
         }
 
         let filter = doc! {
@@ -202,6 +228,9 @@ async fn reaction_add_inner(ctx: Context, reaction: Reaction) -> Result {
             "$max": {
                 "max_reacts": now_reacts,
             },
+            "$set": {
+                "reacts": now_reacts,
+            },
         };
 
         let record = model::Message::collection(db)
@@ -210,7 +239,15 @@ async fn reaction_add_inner(ctx: Context, reaction: Reaction) -> Result {
             .return_document(ReturnDocument::Before)
             .await?;
 
-        let (pinned, old_reacts) = record.map(|r| (r.pinned, r.max_reacts)).unwrap_or_default();
+        let (pinned, old_reacts) = record
+            .as_ref()
+            .map(|r| (r.pinned, r.max_reacts))
+            .unwrap_or_default();
+
+        // existing link embeds show the live count
+        if let Some(record) = record.filter(|r| r.reacts != now_reacts) {
+            sync_link_pins(&ctx, guild_id, board, &record, now_reacts).await;
+        }
 
         // we already checked that we have the required reacts,
         // this just for my sanity
@@ -232,72 +269,97 @@ async fn reaction_add_inner(ctx: Context, reaction: Reaction) -> Result {
             if !record.pinned {
                 new_post = true;
 
-                let notice = board
-                    .notices
-                    .choose(&mut thread_rng())
-                    .map(String::as_str)
-                    .unwrap_or("{user}, your post made it! Wow!");
-
-                let notice = replace_holes(notice, |out, n| match n {
-                    "user" => write_str!(out, "<@{}>", message.author.id),
-                    _ => out.push(char::REPLACEMENT_CHARACTER),
-                });
-
-                let notice = CreateMessage::new().content(notice);
-
-                let pin_messages;
-
-                // unless it's nsfw-to-sfw, actually forward the message
-                // otherwise, generate an embed with a link
-                if is_forwarding_allowed(&ctx, &message, board)
-                    .await
-                    .unwrap_or(false)
-                {
-                    let mut forward = MessageReference::from(&message);
-                    forward.kind = MessageReferenceKind::Forward;
-
-                    let forward = CreateMessage::new().reference_message(forward);
+                let pin = post_pin(&ctx, guild_id, &message, board, None, now_reacts).await?;
 
-                    let notice = board.channel.send_message(&ctx.http, notice).await?.id;
-                    let forward = board.channel.send_message(&ctx.http, forward).await?.id;
-                    pin_messages = vec![bson_id!(notice), bson_id!(forward)];
-                    log::info!("Pinned message {} to {}.", message.id, board.emoji.name());
-                } else {
-                    // nsfw-to-sfw
-                    let forward = format!(
-                        "🔞 https://discord.com/channels/{}/{}/{}",
-                        guild_id, message.channel_id, message.id,
-                    );
-
-                    let forward = CreateEmbed::new()
-                        .description(forward)
-                        .color(data.config().embed_color)
-                        .timestamp(message.timestamp);
-
-                    let notice = notice.embed(forward);
-
-                    let notice = board.channel.send_message(&ctx.http, notice).await?.id;
-                    pin_messages = vec![bson_id!(notice)];
-                    log::info!(
-                        "Pinned message {} to {}. (Link)",
-                        message.id,
-                        board.emoji.name()
-                    );
-                }
+                log::info!("Pinned message {} to {}.", message.id, board.emoji.name());
 
                 // also associate what messages are the pins
                 let update = doc! {
                     "$set": {
-                        "pin_messages": pin_messages,
+                        "pin_messages": pin.messages,
+                        "link_pin": pin.link,
                     },
                 };
 
                 model::Message::collection(db)
-                    .update_one(filter, update)
+                    .update_one(filter.clone(), update)
                     .await?;
             }
         }
 
+        // also pin to every additional tier that was just reached
+        for (index, tier) in (0u32..).zip(&board.tiers) {
+            if now_reacts < i64::from(tier.reacts) {
+                continue;
+            }
+
+            // claim the tier first so concurrent reactions don't pin it twice
+            let mut tier_filter = filter.clone();
+            tier_filter.insert("tier_pins.tier", doc! { "$ne": index });
+
+            let update = doc! {
+                "$push": {
+                    "tier_pins": {
+                        "tier": index,
+                        "channel": bson_id!(board.tier_channel(tier)),
+                        "messages": [],
+                    },
+                },
+            };
+
+            let claimed = model::Message::collection(db)
+                .update_one(tier_filter, update)
+                .await?
+                .modified_count
+                != 0;
+
+            if !claimed {
+                continue;
+            }
+
+            let pin = post_pin(&ctx, guild_id, &message, board, Some(tier), now_reacts).await;
+            let pin = match pin {
+                Ok(pin) => pin,
+                Err(why) => {
+                    // release the claim so a later reaction can try again
+                    let update = doc! {
+                        "$pull": {
+                            "tier_pins": {
+                                "tier": index,
+                            },
+                        },
+                    };
+
+                    model::Message::collection(db)
+                        .update_one(filter.clone(), update)
+                        .await?;
+
+                    return Err(why);
+                },
+            };
+
+            log::info!(
+                "Pinned message {} to {} tier {}.",
+                message.id,
+                board.emoji.name(),
+                index + 1
+            );
+
+            let mut tier_filter = filter.clone();
+            tier_filter.insert("tier_pins.tier", index);
+
+            let update = doc! {
+                "$set": {
+                    "tier_pins.$.messages": pin.messages,
+                    "tier_pins.$.link": pin.link,
+                },
+            };
+
+            model::Message::collection(db)
+                .update_one(tier_filter, update)
+                .await?;
+        }
+
         // the score is the new amount compared to the old one
         // if it's now less, we return it as zero
         now_reacts.saturating_sub(old_reacts)
@@ -333,8 +395,8 @@ async fn reaction_add_inner(ctx: Context, reaction: Reaction) -> Result {
             board.emoji.name()
         );
 
-        if board.any_cash_gain() && super::perks::Module.enabled(data.config()) {
-            use super::perks::model::{Wallet, WalletExt};
+        if board.any_cash_gain() && super::perks::Module.enabled(&config) {
+            use super::perks::model::{TxReason, Wallet, WalletExt};
             use super::perks::Item;
 
             let amount = score_increase
@@ -346,7 +408,13 @@ async fn reaction_add_inner(ctx: Context, reaction: Reaction) -> Result {
                 });
 
             Wallet::collection(db)
-                .add_items(guild_id, message.author.id, Item::Cash, amount)
+                .add_items(
+                    guild_id,
+                    message.author.id,
+                    Item::Cash,
+                    amount,
+                    TxReason::new("starboard", board.name.as_str()),
+                )
                 .await?;
 
             log::trace!("{} gained {} cash.", message.author.name, amount);
@@ -356,6 +424,79 @@ async fn reaction_add_inner(ctx: Context, reaction: Reaction) -> Result {
     Ok(())
 }
 
+async fn reaction_remove_inner(ctx: Context, reaction: Reaction) -> Result {
+    // only in guilds
+    let Some(guild_id) = reaction.guild_id else {
+        return Ok(());
+    };
+
+    let data = ctx.data_ref::<HContextData>();
+
+    // grab the config for the current guild
+    let config = data.config();
+    let guild_config = config.starboard.get(&guild_id);
+    let Some(guild_config) = guild_config else {
+        return Ok(());
+    };
+
+    let board = guild_config
+        .boards
+        .iter()
+        .find(|b| b.1.emoji.equivalent_to(&reaction.emoji));
+
+    let Some((board_id, board)) = board else {
+        return Ok(());
+    };
+
+    // only messages that already reached the board are tracked
+    let db = data.database()?;
+    let filter = doc! {
+        "board": board_id.get(),
+        "message": bson_id!(reaction.message_id),
+    };
+
+    let record = model::Message::collection(db).find_one(filter).await?;
+
+    let Some(record) = record else {
+        return Ok(());
+    };
+
+    // recount from the message so the stored count can't drift
+    let message = reaction.message(&ctx.http).await?;
+
+    // the last reaction of this kind may have just been removed
+    let reaction = message
+        .reactions
+        .iter()
+        .find(|r| board.emoji.equivalent_to(&r.reaction_type));
+
+    let now_reacts = match reaction {
+        Some(reaction) => count_reacts(&ctx, guild_id, guild_config, &message, reaction, 0)
+            .await?
+            .max(0),
+        None => 0,
+    };
+
+    if record.reacts == now_reacts {
+        return Ok(());
+    }
+
+    // only the live count goes down
+    // the score stays based on the highest count the message ever had
+    let update = doc! {
+        "$set": {
+            "reacts": now_reacts,
+        },
+    };
+
+    model::Message::collection(db)
+        .update_one(doc_object_id!(record), update)
+        .await?;
+
+    sync_link_pins(&ctx, guild_id, board, &record, now_reacts).await;
+    Ok(())
+}
+
 async fn message_delete_inner(
     ctx: Context,
     guild_id: GuildId,
@@ -365,7 +506,8 @@ async fn message_delete_inner(
     let data = ctx.data_ref::<HContextData>();
 
     // grab the config for the current guild
-    let guild_config = data.config().starboard.get(&guild_id);
+    let config = data.config();
+    let guild_config = config.starboard.get(&guild_id);
 
     let Some(guild_config) = guild_config else {
         return Ok(());
@@ -436,9 +578,27 @@ async fn message_delete_inner(
             }
         }
 
+        // and the pins for any additional tiers
+        for tier_pin in item.tier_pins {
+            for pin_id in tier_pin.messages {
+                let res = tier_pin
+                    .channel
+                    .delete_message(&ctx.http, pin_id, Some("pin source deleted"))
+                    .await;
+
+                if let Err(why) = res {
+                    log::warn!(
+                        "Failed to delete message {pin_id} in {} tier {}: {why:?}",
+                        board.emoji,
+                        tier_pin.tier + 1
+                    );
+                }
+            }
+        }
+
         // also remove cash if it's configured
-        if board.any_cash_gain() && super::perks::Module.enabled(data.config()) {
-            use super::perks::model::{Wallet, WalletExt};
+        if board.any_cash_gain() && super::perks::Module.enabled(&config) {
+            use super::perks::model::{TxReason, Wallet, WalletExt};
             use super::perks::Item;
 
             let amount = item
@@ -451,7 +611,13 @@ async fn message_delete_inner(
                 });
 
             Wallet::collection(db)
-                .add_items(guild_id, item.user, Item::Cash, -amount)
+                .add_items(
+                    guild_id,
+                    item.user,
+                    Item::Cash,
+                    -amount,
+                    TxReason::new("starboard", "deleted post"),
+                )
                 .await?;
 
             log::trace!("{} lost {} cash.", item.user, amount);
@@ -461,6 +627,361 @@ async fn message_delete_inner(
     Ok(())
 }
 
+/// The messages posted for a pin.
+struct PostedPin {
+    /// The IDs of the posted messages.
+    messages: Vec<Bson>,
+    /// Whether the message was linked in an embed rather than forwarded.
+    link: bool,
+}
+
+/// Posts a notice and the message itself to a board channel, or the channel of
+/// one of its tiers.
+async fn post_pin(
+    ctx: &Context,
+    guild_id: GuildId,
+    message: &Message,
+    board: &config::StarboardEntry,
+    tier: Option<&config::StarboardTier>,
+    reacts: i64,
+) -> Result<PostedPin> {
+    let data = ctx.data_ref::<HContextData>();
+    let (channel, notices) = match tier {
+        Some(tier) => (board.tier_channel(tier), board.tier_notices(tier)),
+        None => (board.channel, board.notices.as_slice()),
+    };
+
+    let notice = notices
+        .choose(&mut thread_rng())
+        .map(String::as_str)
+        .unwrap_or("{user}, your post made it! Wow!");
+
+    let notice = replace_holes(notice, |out, n| match n {
+        "user" => write_str!(out, "<@{}>", message.author.id),
+        _ => out.push(char::REPLACEMENT_CHARACTER),
+    });
+
+    let notice = CreateMessage::new().content(notice);
+
+    // unless it's nsfw-to-sfw, actually forward the message
+    // otherwise, generate an embed with a link
+    if is_forwarding_allowed(ctx, guild_id, message, channel)
+        .await
+        .unwrap_or(false)
+    {
+        let mut forward = MessageReference::from(message);
+        forward.kind = MessageReferenceKind::Forward;
+
+        let forward = CreateMessage::new().reference_message(forward);
+
+        let notice = channel.send_message(&ctx.http, notice).await?.id;
+        let forward = channel.send_message(&ctx.http, forward).await?.id;
+        Ok(PostedPin {
+            messages: vec![bson_id!(notice), bson_id!(forward)],
+            link: false,
+        })
+    } else {
+        // nsfw-to-sfw
+        let forward = create_link_embed(
+            data,
+            guild_id,
+            message.channel_id,
+            message.id,
+            board,
+            reacts,
+        );
+        let notice = notice.embed(forward);
+
+        let notice = channel.send_message(&ctx.http, notice).await?.id;
+        Ok(PostedPin {
+            messages: vec![bson_id!(notice)],
+            link: true,
+        })
+    }
+}
+
+/// Creates the embed linking to a message that couldn't be forwarded.
+///
+/// Unlike forwards, this doesn't update by itself, so it is edited whenever
+/// the reaction count changes. To avoid leaking nsfw content, it never
+/// includes the message's content.
+fn create_link_embed(
+    data: &HBotData,
+    guild_id: GuildId,
+    channel_id: ChannelId,
+    message_id: MessageId,
+    board: &config::StarboardEntry,
+    reacts: i64,
+) -> CreateEmbed<'static> {
+    let description = format!(
+        "🔞 https://discord.com/channels/{}/{}/{}",
+        guild_id, channel_id, message_id,
+    );
+
+    let footer = format!("{} {}", board.emoji.name(), reacts);
+
+    CreateEmbed::new()
+        .description(description)
+        .footer(CreateEmbedFooter::new(footer))
+        .color(data.config().embed_color)
+        .timestamp(message_id.created_at())
+}
+
+/// Updates the link embeds of a pinned message to show the current reaction
+/// count.
+async fn sync_link_pins(
+    ctx: &Context,
+    guild_id: GuildId,
+    board: &config::StarboardEntry,
+    record: &model::Message,
+    reacts: i64,
+) {
+    let data = ctx.data_ref::<HContextData>();
+
+    // the link embed is always on the notice, the first message of a pin
+    let mut pins = Vec::new();
+    if record.link_pin {
+        pins.extend(record.pin_messages.first().map(|&m| (board.channel, m)));
+    }
+
+    for tier_pin in record.tier_pins.iter().filter(|t| t.link) {
+        pins.extend(tier_pin.messages.first().map(|&m| (tier_pin.channel, m)));
+    }
+
+    for (channel, pin_id) in pins {
+        let embed = create_link_embed(
+            data,
+            guild_id,
+            record.channel,
+            record.message,
+            board,
+            reacts,
+        );
+        let edit = EditMessage::new().embed(embed);
+
+        if let Err(why) = channel.edit_message(&ctx.http, pin_id, edit).await {
+            log::warn!(
+                "Failed to update message {pin_id} in {}: {why:?}",
+                board.emoji
+            );
+        }
+    }
+}
+
+/// Gets the parent channel of a thread, or [`None`] if the channel isn't a
+/// thread.
+///
+/// Threads can't be moved and channels can't turn into threads, so the
+/// result is cached for the lifetime of the bot.
+async fn thread_parent(
+    ctx: &Context,
+    guild_id: GuildId,
+    channel_id: ChannelId,
+) -> Result<Option<ChannelId>> {
+    static THREAD_PARENTS: LazyLock<DashMap<ChannelId, Option<ChannelId>>> =
+        LazyLock::new(DashMap::new);
+
+    if let Some(parent) = THREAD_PARENTS.get(&channel_id) {
+        return Ok(*parent);
+    }
+
+    let channel = channel_id.to_guild_channel(ctx, Some(guild_id)).await?;
+    let parent = channel
+        .thread_metadata
+        .is_some()
+        .then_some(channel.parent_id)
+        .flatten();
+
+    THREAD_PARENTS.insert(channel_id, parent);
+    Ok(parent)
+}
+
+/// Checks whether a channel is a board channel or a thread within one.
+///
+/// Board channels may also be threads themselves, f.e. a forum post.
+async fn is_board_channel(
+    ctx: &Context,
+    guild_config: &config::StarboardGuild,
+    guild_id: GuildId,
+    channel_id: ChannelId,
+) -> Result<bool> {
+    let is_board = |channel_id| {
+        guild_config
+            .boards
+            .values()
+            .any(|b| b.channels().any(|c| c == channel_id))
+    };
+
+    if is_board(channel_id) {
+        return Ok(true);
+    }
+
+    let parent = thread_parent(ctx, guild_id, channel_id).await?;
+    Ok(parent.is_some_and(is_board))
+}
+
+/// Checks whether a channel is ignored, either directly, via its parent
+/// channel if it is a thread, or via its category.
+async fn is_channel_ignored(
+    ctx: &Context,
+    guild_config: &config::StarboardGuild,
+    guild_id: GuildId,
+    channel_id: ChannelId,
+) -> Result<bool> {
+    if guild_config.ignore_channels.contains(&channel_id) {
+        return Ok(true);
+    }
+
+    // avoid looking up the channel if there's nothing else to check
+    if guild_config.ignore_channels.is_empty() && guild_config.ignore_categories.is_empty() {
+        return Ok(false);
+    }
+
+    // threads belong to a channel, which may in turn be in a category
+    let channel_id = match thread_parent(ctx, guild_id, channel_id).await? {
+        Some(parent) if guild_config.ignore_channels.contains(&parent) => return Ok(true),
+        Some(parent) => parent,
+        None => channel_id,
+    };
+
+    if guild_config.ignore_categories.is_empty() {
+        return Ok(false);
+    }
+
+    let channel = channel_id.to_guild_channel(ctx, Some(guild_id)).await?;
+    Ok(channel
+        .parent_id
+        .is_some_and(|c| guild_config.ignore_categories.contains(&c)))
+}
+
+/// Checks whether a channel is nsfw. Threads are nsfw if their parent is.
+async fn is_nsfw(ctx: &Context, guild_id: GuildId, channel_id: ChannelId) -> Result<bool> {
+    let channel_id = thread_parent(ctx, guild_id, channel_id)
+        .await?
+        .unwrap_or(channel_id);
+
+    let channel = channel_id.to_guild_channel(ctx, Some(guild_id)).await?;
+    Ok(channel.nsfw)
+}
+
+/// Counts the reactions on a message that count towards the author's score.
+///
+/// The bot's own reactions, the author's reaction, and reactions by members
+/// with ignored roles don't count. The latter two are only looked up if the
+/// count could still reach `required`.
+async fn count_reacts(
+    ctx: &Context,
+    guild_id: GuildId,
+    guild_config: &config::StarboardGuild,
+    message: &Message,
+    reaction: &MessageReaction,
+    required: i64,
+) -> Result<i64> {
+    // get the current reaction count
+    // discount the bot's own reactions including supers,
+    // even though bots can't add them anymore
+    let mut now_reacts = i64::try_from(reaction.count)?;
+    if reaction.me || reaction.me_burst {
+        now_reacts -= 1;
+    }
+
+    if now_reacts < required {
+        return Ok(now_reacts);
+    }
+
+    // if the author of this message has reacted, we subtract 1 from the count
+    // so their own reaction does not contribute score
+    // if there are super reactions, also check there
+    let has_self_reaction = |burst| {
+        has_reaction_by_user(
+            ctx,
+            message,
+            &reaction.reaction_type,
+            message.author.id,
+            burst,
+        )
+    };
+    let has_self_reaction = has_self_reaction(false).await?
+        || (reaction.count_details.burst != 0 && has_self_reaction(true).await?);
+
+    if has_self_reaction {
+        now_reacts -= 1;
+    }
+
+    // reactions by members with ignored roles don't count either
+    // this needs every reacting user, so skip it when no roles are ignored
+    if !guild_config.ignore_roles.is_empty() {
+        now_reacts -=
+            count_ignored_reacts(ctx, guild_id, guild_config, message, reaction, false).await?;
+        if reaction.count_details.burst != 0 {
+            now_reacts -=
+                count_ignored_reacts(ctx, guild_id, guild_config, message, reaction, true).await?;
+        }
+    }
+
+    Ok(now_reacts)
+}
+
+/// Counts the reactions by members with ignored roles.
+///
+/// The author and the bot are skipped since they are already discounted.
+async fn count_ignored_reacts(
+    ctx: &Context,
+    guild_id: GuildId,
+    guild_config: &config::StarboardGuild,
+    message: &Message,
+    reaction: &MessageReaction,
+    burst: bool,
+) -> Result<i64> {
+    use serenity::http::{LightMethod, Request, Route};
+    use to_arraystring::ToArrayString;
+
+    // the maximum page size discord allows for reaction users
+    const PAGE_SIZE: usize = 100;
+
+    let bot_id = ctx.data_ref::<HContextData>().current_user()?.id;
+    let reaction_data = reaction.reaction_type.as_data();
+    let mut after = UserId::new(1);
+    let mut ignored = 0i64;
+
+    loop {
+        let after_str = after.to_arraystring();
+        let params = [
+            ("limit", "100"),
+            ("after", &after_str),
+            ("type", if burst { "1" } else { "0" }),
+        ];
+
+        let route = Route::ChannelMessageReactionEmoji {
+            channel_id: message.channel_id,
+            message_id: message.id,
+            reaction: &reaction_data,
+        };
+
+        let request = Request::new(route, LightMethod::Get).params(&params);
+        let users: Vec<User> = ctx.http.fire(request).await?;
+
+        for user in &users {
+            if user.id == bot_id || user.id == message.author.id {
+                continue;
+            }
+
+            // members that left the server can't have ignored roles
+            let member = ctx.http.get_member(guild_id, user.id).await;
+            if member.is_ok_and(|m| guild_config.has_ignored_role(&m.roles)) {
+                ignored += 1;
+            }
+        }
+
+        match users.last() {
+            Some(last) if users.len() >= PAGE_SIZE => after = last.id,
+            _ => break,
+        }
+    }
+
+    Ok(ignored)
+}
+
 async fn has_reaction_by_user(
     ctx: &Context,
     message: &Message,
@@ -497,24 +1018,15 @@ async fn has_reaction_by_user(
 
 async fn is_forwarding_allowed(
     ctx: &Context,
+    guild_id: GuildId,
     message: &Message,
-    board: &config::StarboardEntry,
+    channel: ChannelId,
 ) -> Result<bool> {
-    let source = message
-        .channel_id
-        .to_guild_channel(ctx, message.guild_id)
-        .await?;
-
-    if !source.nsfw {
+    if !is_nsfw(ctx, guild_id, message.channel_id).await? {
         return Ok(true);
     }
 
-    let target = board
-        .channel
-        .to_guild_channel(ctx, message.guild_id)
-        .await?;
-
     // at this point, the source channel is nsfw,
     // so to allow forwarding, the target must also be nsfw
-    Ok(target.nsfw)
+    is_nsfw(ctx, guild_id, channel).await
 }
@@ -13,10 +13,30 @@ pub struct Message {
     pub user: UserId,
     #[serde(default)]
     pub max_reacts: i64,
+    /// The current amount of reacts, which may be lower than the max.
+    #[serde(default)]
+    pub reacts: i64,
     #[serde(default)]
     pub pinned: bool,
     #[serde(default)]
     pub pin_messages: Vec<MessageId>,
+    /// Whether the pin links to the message in an embed instead of forwarding.
+    #[serde(default)]
+    pub link_pin: bool,
+    #[serde(default)]
+    pub tier_pins: Vec<TierPin>,
+}
+
+/// The pin of a message for one of a board's additional tiers.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TierPin {
+    pub tier: u32,
+    #[serde(with = "id_as_i64")]
+    pub channel: ChannelId,
+    #[serde(default)]
+    pub messages: Vec<MessageId>,
+    #[serde(default)]
+    pub link: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -31,6 +51,20 @@ pub struct Score {
     pub post_count: i64,
 }
 
+/// A user's score in a board from a past season.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScoreArchive {
+    pub _id: ObjectId,
+    pub board: BoardId,
+    pub season: u32,
+    #[serde(with = "id_as_i64")]
+    pub user: UserId,
+    #[serde(default)]
+    pub score: i64,
+    #[serde(default)]
+    pub post_count: i64,
+}
+
 fn name(name: &str) -> IndexOptions {
     IndexOptions::builder().name(name.to_owned()).build()
 }
@@ -86,3 +120,21 @@ impl Score {
         ]
     }
 }
+
+impl ScoreArchive {
+    pub fn collection(db: &Database) -> Collection<Self> {
+        db.collection("starboard.score_archive")
+    }
+
+    pub fn indices() -> Vec<IndexModel> {
+        vec![IndexModel::builder()
+            .options(name("top-sort"))
+            .keys(doc! {
+                "board": 1,
+                "season": 1,
+                "score": 1,
+                "post_count": 1,
+            })
+            .build()]
+    }
+}
@@ -0,0 +1,254 @@
+use std::collections::HashMap;
+
+use bson::doc;
+use chrono::{TimeDelta, Utc};
+use mongodb::options::ReturnDocument;
+
+use crate::fmt::discord::TimeMentionable as _;
+use crate::helper::bson::bson_id;
+use crate::modules::starboard::{
+    config, count_reacts, is_board_channel, is_channel_ignored, model, BoardId,
+};
+use crate::slashies::prelude::*;
+
+/// How many messages to request per page of history.
+const PAGE_SIZE: u8 = 100;
+
+/// Running totals of a backfill.
+#[derive(Debug, Default)]
+struct Progress {
+    scanned: u32,
+    posts: u32,
+}
+
+pub async fn backfill(ctx: Context<'_>, channel: &PartialChannel, days: u32) -> Result {
+    let guild = ctx.require_guild_id()?;
+    super::check_manage_guild(ctx)?;
+
+    let data = ctx.data_ref();
+    let db = data.database()?;
+    let config = data.config();
+    let guild_config = config.starboard.get(&guild).ok_or(HArgError::new_const(
+        "Starboard is not enabled for this server.",
+    ))?;
+
+    anyhow::ensure!(
+        !is_board_channel(ctx.serenity, guild_config, guild, channel.id).await?,
+        HArgError::new_const("Board channels cannot be backfilled.")
+    );
+
+    anyhow::ensure!(
+        !is_channel_ignored(ctx.serenity, guild_config, guild, channel.id).await?,
+        HArgError::new_const("This channel is ignored by the starboard.")
+    );
+
+    ctx.defer_as(Ephemeral).await?;
+
+    let cutoff = Utc::now() - TimeDelta::days(days.into());
+    let create_reply = |description: String| {
+        let embed = CreateEmbed::new()
+            .title(format!("Backfilling <#{}>", channel.id))
+            .color(config.embed_color)
+            .description(description);
+
+        CreateReply::new().embed(embed)
+    };
+
+    let handle = ctx
+        .send(create_reply("Reading message history...".to_owned()))
+        .await?;
+
+    let mut progress = Progress::default();
+    let mut authors = HashMap::new();
+    let mut before = None;
+
+    'pages: loop {
+        let mut request = GetMessages::new().limit(PAGE_SIZE);
+        if let Some(before) = before {
+            request = request.before(before);
+        }
+
+        let messages = channel.id.messages(ctx.http(), request).await?;
+        let Some(last) = messages.last() else {
+            break;
+        };
+
+        before = Some(last.id);
+        let is_last_page = messages.len() < usize::from(PAGE_SIZE);
+
+        for message in &messages {
+            if *message.timestamp < cutoff {
+                break 'pages;
+            }
+
+            progress.scanned += 1;
+            if message.reactions.is_empty() || message.author.bot() {
+                continue;
+            }
+
+            for (&board_id, board) in &guild_config.boards {
+                let reaction = message
+                    .reactions
+                    .iter()
+                    .find(|r| board.emoji.equivalent_to(&r.reaction_type));
+
+                let Some(reaction) = reaction else {
+                    continue;
+                };
+
+                let required_reacts = i64::from(board.reacts);
+                let now_reacts = count_reacts(
+                    ctx.serenity,
+                    guild,
+                    guild_config,
+                    message,
+                    reaction,
+                    required_reacts,
+                )
+                .await?;
+
+                if now_reacts < required_reacts {
+                    continue;
+                }
+
+                // messages by members with ignored roles never count
+                if !guild_config.ignore_roles.is_empty() {
+                    let author = message.author.id;
+                    let ignored = match authors.get(&author) {
+                        Some(&ignored) => ignored,
+                        None => {
+                            let member = ctx.http().get_member(guild, author).await;
+                            let ignored =
+                                member.is_ok_and(|m| guild_config.has_ignored_role(&m.roles));
+                            authors.insert(author, ignored);
+                            ignored
+                        },
+                    };
+
+                    if ignored {
+                        continue;
+                    }
+                }
+
+                if record_post(db, board_id, board, message, now_reacts).await? {
+                    progress.posts += 1;
+                }
+            }
+        }
+
+        if is_last_page {
+            break;
+        }
+
+        let description = format!(
+            "Scanned {} message(s) and found {} new post(s) so far.\n\
+             -# Currently at messages from {}.",
+            progress.scanned,
+            progress.posts,
+            last.timestamp.short_date_time(),
+        );
+
+        // progress is best-effort, the interaction may expire on long backfills
+        if let Err(why) = handle.edit(create_reply(description).into()).await {
+            log::warn!("Failed to show backfill progress: {why:?}");
+        }
+    }
+
+    log::info!(
+        "Backfilled starboard for {} in {guild}: scanned {}, found {} post(s).",
+        channel.id,
+        progress.scanned,
+        progress.posts
+    );
+
+    let description = format!(
+        "Done! Scanned {} message(s) from the last {days} day(s) and found {} new post(s).",
+        progress.scanned, progress.posts,
+    );
+
+    handle.edit(create_reply(description).into()).await?;
+    Ok(())
+}
+
+/// Records a message that reached a board before it was tracked.
+///
+/// The message isn't posted to the board, but counts towards the author's
+/// score. Returns whether the message was new to the board.
+async fn record_post(
+    db: &mongodb::Database,
+    board_id: BoardId,
+    board: &config::StarboardEntry,
+    message: &Message,
+    now_reacts: i64,
+) -> Result<bool> {
+    let filter = doc! {
+        "board": board_id.get(),
+        "message": bson_id!(message.id),
+    };
+
+    // mark the board and every reached tier as already pinned,
+    // so later reactions don't post old messages
+    let tier_pins: Vec<_> = (0u32..)
+        .zip(&board.tiers)
+        .filter(|(_, t)| now_reacts >= i64::from(t.reacts))
+        .map(|(index, tier)| {
+            doc! {
+                "tier": index,
+                "channel": bson_id!(board.tier_channel(tier)),
+                "messages": [],
+            }
+        })
+        .collect();
+
+    let update = doc! {
+        "$setOnInsert": {
+            "board": board_id.get(),
+            "channel": bson_id!(message.channel_id),
+            "message": bson_id!(message.id),
+            "user": bson_id!(message.author.id),
+            "pinned": true,
+            "tier_pins": tier_pins,
+        },
+        "$max": {
+            "max_reacts": now_reacts,
+        },
+        "$set": {
+            "reacts": now_reacts,
+        },
+    };
+
+    let record = model::Message::collection(db)
+        .find_one_and_update(filter, update)
+        .upsert(true)
+        .return_document(ReturnDocument::Before)
+        .await?;
+
+    let new_post = record.is_none();
+    let old_reacts = record.map_or(0, |r| r.max_reacts);
+    let score_increase = now_reacts.saturating_sub(old_reacts);
+
+    if score_increase > 0 {
+        let filter = doc! {
+            "board": board_id.get(),
+            "user": bson_id!(message.author.id),
+        };
+
+        let update = doc! {
+            "$setOnInsert": {
+                "board": board_id.get(),
+                "user": bson_id!(message.author.id),
+            },
+            "$inc": {
+                "score": score_increase,
+                "post_count": i64::from(new_post),
+            },
+        };
+
+        model::Score::collection(db)
+            .update_one(filter, update)
+            .upsert(true)
+            .await?;
+    }
+
+    Ok(new_post)
+}
@@ -0,0 +1,223 @@
+use bson::{doc, Document};
+use mongodb::Collection;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use utils::text::write_str::*;
+
+use crate::helper::bson::id_as_i64;
+use crate::modules::starboard::{get_board, model, BoardId};
+use crate::slashies::prelude::*;
+
+/// What to export from a board.
+#[derive(Debug, Clone, Copy, houston_cmd::ChoiceArg)]
+pub enum ExportKind {
+    Scores,
+    Posts,
+}
+
+/// The file format to export to.
+#[derive(Debug, Clone, Copy, houston_cmd::ChoiceArg)]
+pub enum ExportFormat {
+    #[name = "CSV"]
+    Csv,
+    #[name = "JSON"]
+    Json,
+}
+
+/// A row of exported data.
+///
+/// IDs are read as integers from the database, but written as strings.
+trait ExportRow: DeserializeOwned + Serialize {
+    /// The header line for CSV exports.
+    const CSV_HEADER: &'static str;
+
+    /// Writes this row as a CSV line.
+    fn write_csv(&self, out: &mut String);
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+struct ScoreRow {
+    #[serde(deserialize_with = "id_as_i64::deserialize")]
+    user: UserId,
+    #[serde(default)]
+    score: i64,
+    #[serde(default)]
+    post_count: i64,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+struct PostRow {
+    #[serde(deserialize_with = "id_as_i64::deserialize")]
+    channel: ChannelId,
+    #[serde(deserialize_with = "id_as_i64::deserialize")]
+    message: MessageId,
+    #[serde(deserialize_with = "id_as_i64::deserialize")]
+    user: UserId,
+    #[serde(default)]
+    max_reacts: i64,
+    #[serde(default)]
+    pinned: bool,
+}
+
+impl ExportRow for ScoreRow {
+    const CSV_HEADER: &'static str = "user,score,post_count";
+
+    fn write_csv(&self, out: &mut String) {
+        writeln_str!(out, "{},{},{}", self.user, self.score, self.post_count);
+    }
+}
+
+impl ExportRow for PostRow {
+    const CSV_HEADER: &'static str = "channel,message,user,max_reacts,pinned";
+
+    fn write_csv(&self, out: &mut String) {
+        writeln_str!(
+            out,
+            "{},{},{},{},{}",
+            self.channel,
+            self.message,
+            self.user,
+            self.max_reacts,
+            self.pinned,
+        );
+    }
+}
+
+pub async fn export(
+    ctx: Context<'_>,
+    guild: GuildId,
+    board: BoardId,
+    kind: ExportKind,
+    format: ExportFormat,
+) -> Result {
+    super::check_manage_guild(ctx)?;
+
+    let data = ctx.data_ref();
+    let config = data.config();
+    let db = data.database()?;
+    let board_config = get_board(&config, guild, board)?;
+
+    ctx.defer_as(Ephemeral).await?;
+
+    let filter = doc! {
+        "board": board.get(),
+    };
+
+    let (name, content) = match kind {
+        ExportKind::Scores => {
+            let pipeline = [
+                doc! {
+                    "$match": filter,
+                },
+                doc! {
+                    "$sort": {
+                        "score": -1,
+                        "post_count": -1,
+                    },
+                },
+                doc! {
+                    "$project": {
+                        "_id": 0,
+                        "user": 1,
+                        "score": 1,
+                        "post_count": 1,
+                    },
+                },
+            ];
+
+            let collection = model::Score::collection(db);
+            let content = export_rows::<_, ScoreRow>(collection, pipeline, format).await?;
+            ("scores", content)
+        },
+        ExportKind::Posts => {
+            let pipeline = [
+                doc! {
+                    "$match": filter,
+                },
+                doc! {
+                    "$sort": {
+                        "max_reacts": -1,
+                        "message": -1,
+                    },
+                },
+                doc! {
+                    "$project": {
+                        "_id": 0,
+                        "channel": 1,
+                        "message": 1,
+                        "user": 1,
+                        "max_reacts": 1,
+                        "pinned": 1,
+                    },
+                },
+            ];
+
+            let collection = model::Message::collection(db);
+            let content = export_rows::<_, PostRow>(collection, pipeline, format).await?;
+            ("posts", content)
+        },
+    };
+
+    let extension = match format {
+        ExportFormat::Csv => "csv",
+        ExportFormat::Json => "json",
+    };
+
+    let filename = format!("{}-{name}.{extension}", board_config.name);
+    let attachment = CreateAttachment::bytes(content, filename);
+
+    let description = format!(
+        "Exported the {name} of {} {}.",
+        board_config.emoji, board_config.name
+    );
+
+    let embed = CreateEmbed::new()
+        .color(config.embed_color)
+        .description(description);
+
+    let reply = CreateReply::new().embed(embed).attachment(attachment);
+    ctx.send(reply).await?;
+    Ok(())
+}
+
+/// Runs the aggregation and writes every resulting row in the given format.
+async fn export_rows<T, R>(
+    collection: Collection<T>,
+    pipeline: [Document; 3],
+    format: ExportFormat,
+) -> Result<Vec<u8>>
+where
+    T: Send + Sync,
+    R: ExportRow + Send + Sync + Unpin,
+{
+    let mut cursor = collection.aggregate(pipeline).with_type::<R>().await?;
+
+    let content = match format {
+        ExportFormat::Csv => {
+            let mut out = String::new();
+            writeln_str!(out, "{}", R::CSV_HEADER);
+            while let Some(row) = cursor.try_next().await? {
+                row.write_csv(&mut out);
+            }
+
+            out.into_bytes()
+        },
+        ExportFormat::Json => {
+            let mut out = b"[".to_vec();
+            let mut first = true;
+            while let Some(row) = cursor.try_next().await? {
+                if !first {
+                    out.push(b',');
+                }
+
+                first = false;
+                serde_json::to_writer(&mut out, &row)?;
+            }
+
+            out.push(b']');
+            out
+        },
+    };
+
+    Ok(content)
+}
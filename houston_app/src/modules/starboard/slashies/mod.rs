@@ -1,7 +1,10 @@
 use super::BoardId;
 use crate::slashies::prelude::*;
 
+mod backfill;
+mod export;
 mod overview;
+mod seasons;
 
 /// Access starboard info.
 #[chat_command(contexts = "Guild", integration_types = "Guild")]
@@ -13,13 +16,16 @@ pub mod starboard {
         /// What board to look for.
         #[autocomplete = "autocomplete_board"]
         board: u64,
+        /// The past season to show. Shows the current one if not set.
+        #[min = 1]
+        season: Option<u32>,
         /// Whether to show the response only to yourself.
         ephemeral: Option<bool>,
     ) -> Result {
         use super::buttons::top::View;
 
         let (guild, board) = find_board(ctx, board)?;
-        let view = View::new(guild, board);
+        let view = View::new(guild, board, season);
 
         ctx.defer_as(ephemeral).await?;
         ctx.send(view.create_reply(ctx.data_ref()).await?).await?;
@@ -48,6 +54,31 @@ pub mod starboard {
         Ok(())
     }
 
+    /// Shows a random pinned post.
+    #[sub_command]
+    async fn random(
+        ctx: Context<'_>,
+        /// What board to pick from. Picks from all boards if not set.
+        #[autocomplete = "autocomplete_board"]
+        board: Option<u64>,
+        /// Whether to show the response only to yourself.
+        ephemeral: Option<bool>,
+    ) -> Result {
+        use super::buttons::random::View;
+
+        let guild = ctx.require_guild_id()?;
+        let board = board
+            .map(|b| find_board(ctx, b))
+            .transpose()?
+            .map(|(_, b)| b);
+        let view = View::new(guild, board);
+
+        ctx.defer_as(ephemeral).await?;
+        ctx.send(view.create_reply(ctx.serenity).await?).await?;
+
+        Ok(())
+    }
+
     /// Shows an overview of all boards.
     #[sub_command]
     async fn overview(
@@ -57,6 +88,48 @@ pub mod starboard {
     ) -> Result {
         overview::overview(ctx, ephemeral).await
     }
+
+    /// Exports a board's full leaderboard as a file.
+    #[sub_command]
+    async fn export(
+        ctx: Context<'_>,
+        /// What board to export.
+        #[autocomplete = "autocomplete_board"]
+        board: u64,
+        /// What to export.
+        kind: export::ExportKind,
+        /// The file format. Defaults to CSV.
+        format: Option<export::ExportFormat>,
+    ) -> Result {
+        let (guild, board) = find_board(ctx, board)?;
+        let format = format.unwrap_or(export::ExportFormat::Csv);
+        export::export(ctx, guild, board, kind, format).await
+    }
+
+    /// Scans a channel's history for posts that reached a board before it was
+    /// tracked.
+    #[sub_command]
+    async fn backfill(
+        ctx: Context<'_>,
+        /// The channel to scan.
+        channel: &PartialChannel,
+        /// How many days to look back. Defaults to 30.
+        #[min = 1]
+        #[max = 365]
+        days: Option<u32>,
+    ) -> Result {
+        backfill::backfill(ctx, channel, days.unwrap_or(30)).await
+    }
+
+    /// Manages starboard seasons.
+    #[sub_command]
+    mod season {
+        /// Archives the current scores and resets them for a new season.
+        #[sub_command]
+        async fn end(ctx: Context<'_>) -> Result {
+            seasons::end(ctx).await
+        }
+    }
 }
 
 fn find_board(ctx: Context<'_>, board: u64) -> Result<(GuildId, BoardId)> {
@@ -79,6 +152,24 @@ fn find_board(ctx: Context<'_>, board: u64) -> Result<(GuildId, BoardId)> {
     Ok((guild_id, board))
 }
 
+/// Checks that the invoking member can manage the server.
+///
+/// The admin sub commands are part of a public command, so Discord can't
+/// restrict them by itself.
+fn check_manage_guild(ctx: Context<'_>) -> Result {
+    let allowed = ctx
+        .member()
+        .and_then(|m| m.permissions)
+        .is_some_and(|p| p.manage_guild());
+
+    anyhow::ensure!(
+        allowed,
+        HArgError::new_const("You need the Manage Server permission to use this command.")
+    );
+
+    Ok(())
+}
+
 async fn autocomplete_board<'a>(
     ctx: Context<'a>,
     partial: &'a str,
@@ -98,7 +189,7 @@ async fn autocomplete_board<'a>(
             // map it to an autocomplete choice with the board id as the value
             .map(|(id, board)| {
                 AutocompleteChoice::new(
-                    board.name.as_str(),
+                    board.name.clone(),
                     #[allow(clippy::cast_sign_loss)]
                     AutocompleteValue::Integer(id.get() as u64),
                 )
@@ -35,13 +35,10 @@ pub async fn overview(ctx: Context<'_>, ephemeral: Option<bool>) -> Result {
     let guild = ctx.require_guild_id()?;
     let data = ctx.data_ref();
     let db = data.database()?;
-    let guild_config = data
-        .config()
-        .starboard
-        .get(&guild)
-        .ok_or(HArgError::new_const(
-            "Starboard is not enabled for this server.",
-        ))?;
+    let config = data.config();
+    let guild_config = config.starboard.get(&guild).ok_or(HArgError::new_const(
+        "Starboard is not enabled for this server.",
+    ))?;
 
     ctx.defer_as(ephemeral).await?;
 
@@ -104,7 +101,7 @@ pub async fn overview(ctx: Context<'_>, ephemeral: Option<bool>) -> Result {
 
     let mut embed = CreateEmbed::new()
         .title("Starboard Overview")
-        .color(data.config().embed_color);
+        .color(config.embed_color);
 
     for (id, board) in &guild_config.boards {
         let mut value = String::with_capacity(256);
@@ -0,0 +1,73 @@
+use bson::doc;
+use bson::oid::ObjectId;
+
+use crate::modules::starboard::model;
+use crate::slashies::prelude::*;
+
+pub async fn end(ctx: Context<'_>) -> Result {
+    let guild = ctx.require_guild_id()?;
+    super::check_manage_guild(ctx)?;
+
+    let data = ctx.data_ref();
+    let db = data.database()?;
+    let config = data.config();
+    let guild_config = config.starboard.get(&guild).ok_or(HArgError::new_const(
+        "Starboard is not enabled for this server.",
+    ))?;
+
+    ctx.defer_as(Ephemeral).await?;
+
+    let filter = doc! {
+        "board": {
+            "$in": guild_config.board_db_keys(),
+        },
+    };
+
+    // seasons are numbered per server, starting at 1
+    let last = model::ScoreArchive::collection(db)
+        .find_one(filter.clone())
+        .sort(doc! { "season": -1 })
+        .await?;
+
+    let season = last.map_or(1, |s| s.season + 1);
+
+    let archive: Vec<_> = model::Score::collection(db)
+        .find(filter.clone())
+        .await?
+        .map_ok(|s| model::ScoreArchive {
+            _id: ObjectId::new(),
+            board: s.board,
+            season,
+            user: s.user,
+            score: s.score,
+            post_count: s.post_count,
+        })
+        .try_collect()
+        .await?;
+
+    anyhow::ensure!(
+        !archive.is_empty(),
+        HArgError::new_const("There are no scores to archive yet.")
+    );
+
+    let count = archive.len();
+    model::ScoreArchive::collection(db)
+        .insert_many(archive)
+        .await?;
+
+    model::Score::collection(db).delete_many(filter).await?;
+
+    log::info!("Ended starboard season {season} in {guild}: archived {count} score(s).");
+
+    let description = format!(
+        "Ended season **{season}** and archived {count} score(s).\n\
+         -# Use `/starboard top` with `season: {season}` to view it."
+    );
+
+    let embed = CreateEmbed::new()
+        .color(config.embed_color)
+        .description(description);
+
+    ctx.send(CreateReply::new().embed(embed)).await?;
+    Ok(())
+}
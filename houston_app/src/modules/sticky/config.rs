@@ -0,0 +1,22 @@
+use chrono::TimeDelta;
+
+use crate::helper::time::serde_time_delta;
+
+fn default_messages() -> u32 {
+    5
+}
+
+fn default_delay() -> TimeDelta {
+    const { TimeDelta::seconds(30) }
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct Config {
+    /// Re-post the sticky message after this many new messages.
+    #[serde(default = "default_messages")]
+    pub messages: u32,
+    /// Re-post the sticky message this long after the first new message, even
+    /// if fewer than [`Self::messages`] were sent.
+    #[serde(with = "serde_time_delta", default = "default_delay")]
+    pub delay: TimeDelta,
+}
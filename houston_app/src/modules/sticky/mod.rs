@@ -0,0 +1,260 @@
+//! Keeps messages at the bottom of channels.
+//!
+//! The stickies are loaded into memory on startup so that new messages don't
+//! need a database lookup. Each channel counts the messages since the last
+//! copy of the sticky was posted and re-posts it once enough messages were
+//! sent or enough time has passed since the first one.
+
+use std::sync::LazyLock;
+
+use bson::doc;
+use dashmap::DashMap;
+
+use super::prelude::*;
+use crate::helper::discord::is_unknown_message;
+
+pub mod config;
+pub mod model;
+mod slashies;
+
+pub use config::Config;
+use model::*;
+
+/// The stickies, by the channel they are in.
+static STICKIES: LazyLock<DashMap<ChannelId, StickyState>> = LazyLock::new(DashMap::new);
+
+struct StickyState {
+    content: String,
+    last_message: Option<MessageId>,
+    /// The amount of new messages since the last copy.
+    pending: u32,
+    /// Whether a delayed re-post is scheduled.
+    scheduled: bool,
+    /// Whether a copy is being posted right now.
+    posting: bool,
+}
+
+impl StickyState {
+    fn new(content: String, last_message: Option<MessageId>) -> Self {
+        Self {
+            content,
+            last_message,
+            pending: 0,
+            scheduled: false,
+            posting: false,
+        }
+    }
+}
+
+pub struct Module;
+
+impl super::Module for Module {
+    fn enabled(&self, config: &HBotConfig) -> bool {
+        config.sticky.is_some()
+    }
+
+    fn intents(&self, _config: &HBotConfig) -> GatewayIntents {
+        GatewayIntents::GUILD_MESSAGES
+    }
+
+    fn commands(&self, _config: &HBotConfig) -> impl IntoIterator<Item = HCommand> {
+        [slashies::sticky()]
+    }
+
+    fn db_init(db: &mongodb::Database) -> mongodb::BoxFuture<'_, Result> {
+        use crate::helper::bson::update_indices;
+        Box::pin(async move {
+            update_indices(Sticky::collection(db), Sticky::indices()).await?;
+            Ok(())
+        })
+    }
+
+    fn validate(&self, config: &HBotConfig) -> Result {
+        anyhow::ensure!(
+            config.mongodb_uri.is_some(),
+            "sticky requires a mongodb_uri"
+        );
+
+        let sticky = config.sticky()?;
+        anyhow::ensure!(sticky.messages != 0, "sticky.messages must be at least 1");
+        anyhow::ensure!(
+            sticky.delay >= chrono::TimeDelta::seconds(5),
+            "sticky.delay must be at least 5 seconds to avoid rate limits"
+        );
+
+        Ok(())
+    }
+}
+
+/// Loads the stickies from the database.
+pub async fn load_stickies(ctx: &Context) -> Result {
+    let data = ctx.data_ref::<HContextData>();
+    if !Module.enabled(&data.config()) {
+        return Ok(());
+    }
+
+    let db = data.database()?;
+    let mut query = Sticky::collection(db).find(doc! {}).await?;
+
+    while let Some(sticky) = query.try_next().await? {
+        let state = StickyState::new(sticky.content, sticky.last_message);
+        STICKIES.insert(sticky.channel, state);
+    }
+
+    log::info!("Loaded {} sticky message(s).", STICKIES.len());
+    Ok(())
+}
+
+/// Gets the amount of stickies held in memory.
+pub fn cache_len() -> usize {
+    STICKIES.len()
+}
+
+/// Counts a new message and re-posts the sticky if needed.
+pub fn message(ctx: &Context, message: &Message) {
+    let Some(mut state) = STICKIES.get_mut(&message.channel_id) else {
+        return;
+    };
+
+    // don't count our own messages, including the copies of the sticky
+    let data = ctx.data_ref::<HContextData>();
+    if data.current_user().is_ok_and(|u| u.id == message.author.id) {
+        return;
+    }
+
+    let config = data.config();
+    let Ok(sticky) = config.sticky() else {
+        return;
+    };
+
+    state.pending += 1;
+    let channel_id = message.channel_id;
+
+    if state.pending >= sticky.messages {
+        drop(state);
+        tokio::task::spawn(repost_impl(ctx.clone(), channel_id));
+    } else if !state.scheduled {
+        state.scheduled = true;
+        drop(state);
+
+        let delay = sticky.delay.to_std().unwrap_or_default();
+        let ctx = ctx.clone();
+        tokio::task::spawn(async move {
+            tokio::time::sleep(delay).await;
+            repost_impl(ctx, channel_id).await;
+        });
+    }
+}
+
+async fn repost_impl(ctx: Context, channel_id: ChannelId) {
+    if let Err(why) = repost(&ctx, channel_id).await {
+        log::warn!("Failed to re-post sticky in {channel_id}: {why:?}");
+    }
+}
+
+/// Re-posts the sticky if there were new messages since the last copy.
+async fn repost(ctx: &Context, channel_id: ChannelId) -> Result {
+    let (content, old) = {
+        let Some(mut state) = STICKIES.get_mut(&channel_id) else {
+            return Ok(());
+        };
+
+        state.scheduled = false;
+
+        // if a copy is being posted, the next message will try again
+        if state.pending == 0 || state.posting {
+            return Ok(());
+        }
+
+        state.pending = 0;
+        state.posting = true;
+        (state.content.clone(), state.last_message.take())
+    };
+
+    let result = post(ctx, channel_id, &content, old).await;
+
+    if let Some(mut state) = STICKIES.get_mut(&channel_id) {
+        state.posting = false;
+    }
+
+    result
+}
+
+/// Starts tracking a sticky and posts it right away.
+async fn enable(ctx: &Context, sticky: Sticky) -> Result {
+    let old = {
+        let mut state = STICKIES
+            .entry(sticky.channel)
+            .or_insert_with(|| StickyState::new(String::new(), sticky.last_message));
+
+        state.content.clone_from(&sticky.content);
+        state.pending = 0;
+        state.last_message.take()
+    };
+
+    post(ctx, sticky.channel, &sticky.content, old).await
+}
+
+/// Stops tracking a sticky and deletes its last copy.
+async fn disable(ctx: &Context, channel_id: ChannelId, last_message: Option<MessageId>) -> Result {
+    let old = STICKIES
+        .remove(&channel_id)
+        .and_then(|(_, s)| s.last_message)
+        .or(last_message);
+
+    delete_copy(ctx, channel_id, old).await
+}
+
+/// Replaces the last copy of a sticky with a new one.
+async fn post(
+    ctx: &Context,
+    channel_id: ChannelId,
+    content: &str,
+    old: Option<MessageId>,
+) -> Result {
+    let data = ctx.data_ref::<HContextData>();
+    let db = data.database()?;
+
+    if let Err(why) = delete_copy(ctx, channel_id, old).await {
+        log::warn!("Failed to delete old sticky in {channel_id}: {why:?}");
+    }
+
+    let embed = CreateEmbed::new()
+        .description(content)
+        .footer(CreateEmbedFooter::new("📌 Sticky message"))
+        .color(data.config().embed_color);
+
+    let message = CreateMessage::new()
+        .embed(embed)
+        .allowed_mentions(CreateAllowedMentions::new());
+
+    let new = channel_id.send_message(&ctx.http, message).await?.id;
+
+    if let Some(mut state) = STICKIES.get_mut(&channel_id) {
+        state.last_message = Some(new);
+    }
+
+    Sticky::collection(db)
+        .set_last_message(channel_id, new)
+        .await?;
+
+    Ok(())
+}
+
+async fn delete_copy(ctx: &Context, channel_id: ChannelId, message: Option<MessageId>) -> Result {
+    let Some(message) = message else {
+        return Ok(());
+    };
+
+    let result = channel_id
+        .delete_message(&ctx.http, message, Some("re-posting sticky message"))
+        .await;
+
+    // someone else may have deleted it already
+    if is_unknown_message(&result) {
+        return Ok(());
+    }
+
+    result?;
+    Ok(())
+}
@@ -0,0 +1,103 @@
+use crate::modules::model_prelude::*;
+
+/// A message kept at the bottom of a channel.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Sticky {
+    pub _id: ObjectId,
+    #[serde(with = "id_as_i64")]
+    pub guild: GuildId,
+    #[serde(with = "id_as_i64")]
+    pub channel: ChannelId,
+    pub content: String,
+    /// The most recently posted copy.
+    #[serde(default, with = "opt_id_as_i64")]
+    pub last_message: Option<MessageId>,
+}
+
+impl Sticky {
+    pub fn collection(db: &Database) -> Collection<Self> {
+        db.collection("sticky.messages")
+    }
+
+    pub fn indices() -> Vec<IndexModel> {
+        vec![IndexModel::builder()
+            .options(
+                IndexOptions::builder()
+                    .name("channel".to_owned())
+                    .unique(true)
+                    .build(),
+            )
+            .keys(doc! {
+                "channel": 1,
+            })
+            .build()]
+    }
+}
+
+pub trait StickyExt {
+    async fn set_content(
+        &self,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+        content: &str,
+    ) -> Result<Sticky>;
+
+    async fn set_last_message(&self, channel_id: ChannelId, message_id: MessageId) -> Result;
+
+    async fn delete_sticky(&self, channel_id: ChannelId) -> Result<Option<Sticky>>;
+}
+
+impl StickyExt for Collection<Sticky> {
+    async fn set_content(
+        &self,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+        content: &str,
+    ) -> Result<Sticky> {
+        let filter = doc! {
+            "channel": bson_id!(channel_id),
+        };
+
+        let update = doc! {
+            "$set": {
+                "content": content,
+            },
+            "$setOnInsert": {
+                "guild": bson_id!(guild_id),
+            },
+        };
+
+        let doc = self
+            .find_one_and_update(filter, update)
+            .upsert(true)
+            .return_document(ReturnDocument::After)
+            .await?
+            .context("sticky must exist after upsert")?;
+
+        Ok(doc)
+    }
+
+    async fn set_last_message(&self, channel_id: ChannelId, message_id: MessageId) -> Result {
+        let filter = doc! {
+            "channel": bson_id!(channel_id),
+        };
+
+        let update = doc! {
+            "$set": {
+                "last_message": bson_id!(message_id),
+            },
+        };
+
+        self.update_one(filter, update).await?;
+        Ok(())
+    }
+
+    async fn delete_sticky(&self, channel_id: ChannelId) -> Result<Option<Sticky>> {
+        let filter = doc! {
+            "channel": bson_id!(channel_id),
+        };
+
+        let doc = self.find_one_and_delete(filter).await?;
+        Ok(doc)
+    }
+}
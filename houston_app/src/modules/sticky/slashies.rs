@@ -0,0 +1,75 @@
+use super::model::*;
+use crate::slashies::prelude::*;
+
+/// Manages the sticky message in this channel.
+#[chat_command(
+    default_member_permissions = "MANAGE_MESSAGES",
+    contexts = "Guild",
+    integration_types = "Guild"
+)]
+pub mod sticky {
+    /// Sets the message to keep at the bottom of this channel.
+    #[sub_command]
+    async fn set(
+        ctx: Context<'_>,
+        /// The text to keep at the bottom. Use "\n" for line breaks.
+        #[max_length = 2000]
+        content: &str,
+    ) -> Result {
+        let data = ctx.data_ref();
+        let guild_id = ctx.require_guild_id()?;
+        let channel_id = ctx.channel_id();
+        let db = data.database()?;
+
+        let content = content.replace("\\n", "\n");
+        anyhow::ensure!(
+            !content.trim().is_empty(),
+            HArgError::new_const("The sticky message cannot be empty.")
+        );
+
+        ctx.require_bot_permissions(Permissions::SEND_MESSAGES)?;
+        ctx.defer_as(Ephemeral).await?;
+
+        let sticky = Sticky::collection(db)
+            .set_content(guild_id, channel_id, &content)
+            .await?;
+
+        super::enable(ctx.serenity, sticky).await?;
+        log::info!("{} set the sticky in {channel_id}.", ctx.user().id);
+
+        let embed = CreateEmbed::new()
+            .color(data.config().embed_color)
+            .description("Set the sticky message for this channel.");
+
+        ctx.send(CreateReply::new().embed(embed)).await?;
+        Ok(())
+    }
+
+    /// Removes the sticky message from this channel.
+    #[sub_command]
+    async fn remove(ctx: Context<'_>) -> Result {
+        let data = ctx.data_ref();
+        let channel_id = ctx.channel_id();
+        let db = data.database()?;
+
+        ctx.defer_as(Ephemeral).await?;
+
+        let sticky = Sticky::collection(db)
+            .delete_sticky(channel_id)
+            .await?
+            .ok_or(HArgError::new_const("This channel has no sticky message."))?;
+
+        if let Err(why) = super::disable(ctx.serenity, channel_id, sticky.last_message).await {
+            log::warn!("Failed to delete sticky in {channel_id}: {why:?}");
+        }
+
+        log::info!("{} removed the sticky in {channel_id}.", ctx.user().id);
+
+        let embed = CreateEmbed::new()
+            .color(data.config().embed_color)
+            .description("Removed the sticky message from this channel.");
+
+        ctx.send(CreateReply::new().embed(embed)).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,10 @@
+fn default_max_tags() -> u64 {
+    200
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct Config {
+    /// The most tags a single server may have.
+    #[serde(default = "default_max_tags")]
+    pub max_tags: u64,
+}
@@ -0,0 +1,140 @@
+use crate::modules::model_prelude::*;
+
+/// A named text snippet in a guild.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Tag {
+    pub _id: ObjectId,
+    #[serde(with = "id_as_i64")]
+    pub guild: GuildId,
+    /// The lowercase name of the tag.
+    pub name: String,
+    pub content: String,
+    /// Whether to show the content in an embed.
+    #[serde(default)]
+    pub embed: bool,
+    #[serde(with = "id_as_i64")]
+    pub author: UserId,
+    #[serde(default)]
+    pub uses: i64,
+}
+
+fn name(name: &str) -> IndexOptions {
+    IndexOptions::builder().name(name.to_owned()).build()
+}
+
+impl Tag {
+    pub fn collection(db: &Database) -> Collection<Self> {
+        db.collection("tags.tags")
+    }
+
+    pub fn indices() -> Vec<IndexModel> {
+        vec![IndexModel::builder()
+            .options(name("guild-name"))
+            .keys(doc! {
+                "guild": 1,
+                "name": 1,
+            })
+            .build()]
+    }
+}
+
+pub trait TagExt {
+    async fn add_tag(&self, guild_id: GuildId, tag: &Tag, max_tags: u64) -> Result;
+
+    async fn edit_tag(
+        &self,
+        guild_id: GuildId,
+        name: &str,
+        content: &str,
+        embed: Option<bool>,
+    ) -> Result<bool>;
+
+    async fn use_tag(&self, guild_id: GuildId, name: &str) -> Result<Option<Tag>>;
+
+    async fn delete_tag(&self, guild_id: GuildId, name: &str) -> Result<bool>;
+}
+
+impl TagExt for Collection<Tag> {
+    async fn add_tag(&self, guild_id: GuildId, tag: &Tag, max_tags: u64) -> Result {
+        let filter = doc! {
+            "guild": bson_id!(guild_id),
+            "name": &tag.name,
+        };
+
+        if self.count_documents(filter).await? != 0 {
+            return Err(HArgError::new_const("A tag with that name already exists.").into());
+        }
+
+        let count = self
+            .count_documents(doc! { "guild": bson_id!(guild_id) })
+            .await?;
+
+        if count >= max_tags {
+            return Err(HArgError::new(format!(
+                "This server can only have up to {max_tags} tags. Remove one first."
+            ))
+            .into());
+        }
+
+        self.insert_one(tag).await?;
+        Ok(())
+    }
+
+    async fn edit_tag(
+        &self,
+        guild_id: GuildId,
+        name: &str,
+        content: &str,
+        embed: Option<bool>,
+    ) -> Result<bool> {
+        let filter = doc! {
+            "guild": bson_id!(guild_id),
+            "name": name,
+        };
+
+        let mut set = doc! {
+            "content": content,
+        };
+
+        if let Some(embed) = embed {
+            set.insert("embed", embed);
+        }
+
+        let update = doc! {
+            "$set": set,
+        };
+
+        let result = self.update_one(filter, update).await?;
+        Ok(result.matched_count != 0)
+    }
+
+    async fn use_tag(&self, guild_id: GuildId, name: &str) -> Result<Option<Tag>> {
+        let filter = doc! {
+            "guild": bson_id!(guild_id),
+            "name": name,
+        };
+
+        let update = doc! {
+            "$inc": {
+                "uses": 1,
+            },
+        };
+
+        let doc = self
+            .find_one_and_update(filter, update)
+            .return_document(ReturnDocument::After)
+            .await?;
+
+        Ok(doc)
+    }
+
+    async fn delete_tag(&self, guild_id: GuildId, name: &str) -> Result<bool> {
+        let filter = doc! {
+            "guild": bson_id!(guild_id),
+            "name": name,
+        };
+
+        let result = self.delete_one(filter).await?;
+        Ok(result.deleted_count != 0)
+    }
+}
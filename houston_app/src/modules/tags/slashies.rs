@@ -0,0 +1,240 @@
+use bson::doc;
+use bson::oid::ObjectId;
+
+use super::model::*;
+use crate::fmt::discord::escape_markdown;
+use crate::helper::bson::bson_id;
+use crate::slashies::prelude::*;
+
+/// Shows a tag, a saved text snippet for this server.
+#[chat_command(contexts = "Guild", integration_types = "Guild")]
+pub async fn tag(
+    ctx: Context<'_>,
+    /// The tag's name.
+    #[autocomplete = "autocomplete_tag_name"]
+    name: &str,
+    /// Whether to show the response only to yourself.
+    ephemeral: Option<bool>,
+) -> Result {
+    let data = ctx.data_ref();
+    let guild_id = ctx.require_guild_id()?;
+    let db = data.database()?;
+
+    let tag = Tag::collection(db)
+        .use_tag(guild_id, &name.to_lowercase())
+        .await?
+        .ok_or(HArgError::new_const("There is no tag with that name."))?;
+
+    let reply = if tag.embed {
+        let footer = format!("Used {} times", tag.uses);
+        let embed = CreateEmbed::new()
+            .title(tag.name)
+            .description(tag.content)
+            .footer(CreateEmbedFooter::new(footer))
+            .color(data.config().embed_color);
+
+        CreateReply::new().embed(embed)
+    } else {
+        CreateReply::new()
+            .content(tag.content)
+            .allowed_mentions(CreateAllowedMentions::new())
+    };
+
+    ctx.send(reply.ephemeral(ephemeral.into_ephemeral())).await?;
+    Ok(())
+}
+
+/// Manages this server's tags.
+#[chat_command(
+    name = "tag-admin",
+    default_member_permissions = "MANAGE_MESSAGES",
+    contexts = "Guild",
+    integration_types = "Guild"
+)]
+pub mod tag_admin {
+    /// Adds a new tag.
+    #[sub_command]
+    async fn add(
+        ctx: Context<'_>,
+        /// The tag's name.
+        #[max_length = 32]
+        name: &str,
+        /// The text to show. Use "\n" for line breaks.
+        #[max_length = 2000]
+        content: &str,
+        /// Whether to show the content in an embed.
+        embed: Option<bool>,
+    ) -> Result {
+        let data = ctx.data_ref();
+        let guild_id = ctx.require_guild_id()?;
+        let config = data.config();
+        let tags = config.tags()?;
+        let db = data.database()?;
+
+        let name = normalize_name(name)?;
+        let content = unescape_content(content)?;
+
+        ctx.defer_as(Ephemeral).await?;
+
+        let tag = Tag {
+            _id: ObjectId::new(),
+            guild: guild_id,
+            name,
+            content,
+            embed: embed.unwrap_or(false),
+            author: ctx.user().id,
+            uses: 0,
+        };
+
+        Tag::collection(db)
+            .add_tag(guild_id, &tag, tags.max_tags)
+            .await?;
+
+        log::info!("{} added tag {} in {}.", ctx.user().id, tag.name, guild_id);
+
+        let embed = CreateEmbed::new()
+            .color(config.embed_color)
+            .description(format!("Added tag **{}**.", escape_markdown(&tag.name)));
+
+        ctx.send(CreateReply::new().embed(embed)).await?;
+        Ok(())
+    }
+
+    /// Replaces the content of a tag.
+    #[sub_command]
+    async fn edit(
+        ctx: Context<'_>,
+        /// The tag's name.
+        #[autocomplete = "autocomplete_tag_name"]
+        name: &str,
+        /// The text to show. Use "\n" for line breaks.
+        #[max_length = 2000]
+        content: &str,
+        /// Whether to show the content in an embed. Unchanged if not set.
+        embed: Option<bool>,
+    ) -> Result {
+        let data = ctx.data_ref();
+        let guild_id = ctx.require_guild_id()?;
+        let db = data.database()?;
+
+        let name = name.to_lowercase();
+        let content = unescape_content(content)?;
+
+        ctx.defer_as(Ephemeral).await?;
+
+        let found = Tag::collection(db)
+            .edit_tag(guild_id, &name, &content, embed)
+            .await?;
+
+        if !found {
+            return Err(HArgError::new_const("There is no tag with that name.").into());
+        }
+
+        log::info!("{} edited tag {} in {}.", ctx.user().id, name, guild_id);
+
+        let embed = CreateEmbed::new()
+            .color(data.config().embed_color)
+            .description(format!("Edited tag **{}**.", escape_markdown(&name)));
+
+        ctx.send(CreateReply::new().embed(embed)).await?;
+        Ok(())
+    }
+
+    /// Removes a tag.
+    #[sub_command]
+    async fn remove(
+        ctx: Context<'_>,
+        /// The tag's name.
+        #[autocomplete = "autocomplete_tag_name"]
+        name: &str,
+    ) -> Result {
+        let data = ctx.data_ref();
+        let guild_id = ctx.require_guild_id()?;
+        let db = data.database()?;
+
+        let name = name.to_lowercase();
+
+        ctx.defer_as(Ephemeral).await?;
+
+        let deleted = Tag::collection(db).delete_tag(guild_id, &name).await?;
+
+        if !deleted {
+            return Err(HArgError::new_const("There is no tag with that name.").into());
+        }
+
+        log::info!("{} removed tag {} in {}.", ctx.user().id, name, guild_id);
+
+        let embed = CreateEmbed::new()
+            .color(data.config().embed_color)
+            .description(format!("Removed tag **{}**.", escape_markdown(&name)));
+
+        ctx.send(CreateReply::new().embed(embed)).await?;
+        Ok(())
+    }
+}
+
+/// Validates a tag name and converts it to lowercase.
+fn normalize_name(name: &str) -> Result<String> {
+    let name = name.trim().to_lowercase();
+    anyhow::ensure!(
+        !name.is_empty() && !name.contains(char::is_whitespace),
+        HArgError::new_const("Tag names must be a single word.")
+    );
+
+    Ok(name)
+}
+
+/// Replaces the `\n` escapes in tag content with line breaks.
+fn unescape_content(content: &str) -> Result<String> {
+    let content = content.replace("\\n", "\n");
+    anyhow::ensure!(
+        !content.trim().is_empty(),
+        HArgError::new_const("Tags cannot be empty.")
+    );
+
+    Ok(content)
+}
+
+async fn autocomplete_tag_name<'a>(
+    ctx: Context<'a>,
+    partial: &'a str,
+) -> CreateAutocompleteResponse<'a> {
+    async fn inner<'a>(ctx: Context<'a>, partial: &str) -> Result<Vec<AutocompleteChoice<'a>>> {
+        let guild_id = ctx.require_guild_id()?;
+        let db = ctx.data_ref().database()?;
+        let filter = doc! {
+            "guild": bson_id!(guild_id),
+        };
+
+        let mut query = Tag::collection(db)
+            .find(filter)
+            .sort(doc! { "uses": -1 })
+            .await?;
+
+        let partial = partial.to_lowercase();
+
+        let mut choices = Vec::new();
+        while let Some(tag) = query.try_next().await? {
+            if tag.name.contains(&partial) {
+                choices.push(AutocompleteChoice::new(
+                    tag.name.clone(),
+                    Cow::Owned(tag.name),
+                ));
+
+                if choices.len() >= 25 {
+                    break;
+                }
+            }
+        }
+
+        Ok(choices)
+    }
+
+    match inner(ctx, partial).await {
+        Ok(choices) => CreateAutocompleteResponse::new().set_choices(choices),
+        Err(why) => {
+            log::warn!("Tag autocomplete failed: {why:?}");
+            CreateAutocompleteResponse::new()
+        },
+    }
+}
@@ -0,0 +1,36 @@
+use serenity::secrets::SecretString;
+
+fn default_target() -> String {
+    "en".to_owned()
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct Config {
+    /// The language code to translate messages into.
+    #[serde(default = "default_target")]
+    pub target: String,
+    /// The translation service to use.
+    #[serde(flatten)]
+    pub provider: ProviderConfig,
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(tag = "provider", rename_all = "snake_case")]
+pub enum ProviderConfig {
+    Deepl(DeeplConfig),
+    LibreTranslate(LibreTranslateConfig),
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct DeeplConfig {
+    pub api_key: SecretString,
+    /// The API base URL. If unset, it is chosen based on the key.
+    pub url: Option<String>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct LibreTranslateConfig {
+    /// The base URL of the LibreTranslate instance.
+    pub url: String,
+    pub api_key: Option<SecretString>,
+}
@@ -0,0 +1,28 @@
+use super::prelude::*;
+
+pub mod config;
+mod provider;
+mod slashies;
+
+pub use config::Config;
+
+pub struct Module;
+
+impl super::Module for Module {
+    fn enabled(&self, config: &HBotConfig) -> bool {
+        config.translate.is_some()
+    }
+
+    fn commands(&self, _config: &HBotConfig) -> impl IntoIterator<Item = HCommand> {
+        [slashies::translate_message()]
+    }
+
+    fn validate(&self, config: &HBotConfig) -> Result {
+        let translate = config.translate()?;
+        anyhow::ensure!(
+            !translate.target.is_empty(),
+            "translate target language must not be empty"
+        );
+        Ok(())
+    }
+}
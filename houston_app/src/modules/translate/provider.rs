@@ -0,0 +1,149 @@
+use std::sync::LazyLock;
+
+use serde::{Deserialize, Serialize};
+use serenity::secrets::SecretString;
+
+use super::config::{DeeplConfig, LibreTranslateConfig, ProviderConfig};
+use crate::prelude::*;
+
+static CLIENT: LazyLock<reqwest::Client> = LazyLock::new(reqwest::Client::new);
+
+/// The result of a translation.
+#[derive(Debug)]
+pub struct Translation {
+    /// The detected source language code.
+    pub source_lang: String,
+    /// The translated text.
+    pub text: String,
+}
+
+/// A service that can translate text.
+pub trait Provider {
+    /// Translates the text into the target language, detecting the source.
+    async fn translate(&self, text: &str, target: &str) -> Result<Translation>;
+}
+
+impl Provider for ProviderConfig {
+    async fn translate(&self, text: &str, target: &str) -> Result<Translation> {
+        match self {
+            Self::Deepl(deepl) => deepl.translate(text, target).await,
+            Self::LibreTranslate(libre) => libre.translate(text, target).await,
+        }
+    }
+}
+
+impl DeeplConfig {
+    fn base_url(&self) -> &str {
+        if let Some(url) = &self.url {
+            url.trim_end_matches('/')
+        } else if self.api_key.expose_secret().ends_with(":fx") {
+            // keys for the free api are suffixed with ":fx"
+            "https://api-free.deepl.com"
+        } else {
+            "https://api.deepl.com"
+        }
+    }
+}
+
+impl Provider for DeeplConfig {
+    async fn translate(&self, text: &str, target: &str) -> Result<Translation> {
+        #[derive(Serialize)]
+        struct Request<'a> {
+            text: [&'a str; 1],
+            target_lang: &'a str,
+        }
+
+        #[derive(Deserialize)]
+        struct Response {
+            translations: Vec<ResponseItem>,
+        }
+
+        #[derive(Deserialize)]
+        struct ResponseItem {
+            detected_source_language: String,
+            text: String,
+        }
+
+        let target = target.to_ascii_uppercase();
+        let request = Request {
+            text: [text],
+            target_lang: &target,
+        };
+
+        let response: Response = CLIENT
+            .post(format!("{}/v2/translate", self.base_url()))
+            .header(
+                "Authorization",
+                format!("DeepL-Auth-Key {}", self.api_key.expose_secret()),
+            )
+            .json(&request)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        let item = response
+            .translations
+            .into_iter()
+            .next()
+            .context("deepl returned no translation")?;
+
+        Ok(Translation {
+            source_lang: item.detected_source_language,
+            text: item.text,
+        })
+    }
+}
+
+impl Provider for LibreTranslateConfig {
+    async fn translate(&self, text: &str, target: &str) -> Result<Translation> {
+        #[derive(Serialize)]
+        struct Request<'a> {
+            q: &'a str,
+            source: &'a str,
+            target: &'a str,
+            format: &'a str,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            api_key: Option<&'a str>,
+        }
+
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Response {
+            translated_text: String,
+            detected_language: Option<DetectedLanguage>,
+        }
+
+        #[derive(Deserialize)]
+        struct DetectedLanguage {
+            language: String,
+        }
+
+        let request = Request {
+            q: text,
+            source: "auto",
+            target,
+            format: "text",
+            api_key: self.api_key.as_ref().map(SecretString::expose_secret),
+        };
+
+        let response: Response = CLIENT
+            .post(format!("{}/translate", self.url.trim_end_matches('/')))
+            .json(&request)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        let source_lang = response
+            .detected_language
+            .map_or_else(|| "unknown".to_owned(), |d| d.language);
+
+        Ok(Translation {
+            source_lang,
+            text: response.translated_text,
+        })
+    }
+}
@@ -0,0 +1,40 @@
+use utils::text::truncate;
+
+use super::provider::Provider as _;
+use crate::slashies::prelude::*;
+
+/// Translates the message into the configured language.
+#[context_command(message, name = "Translate Message")]
+pub async fn translate_message(ctx: Context<'_>, message: &Message) -> Result {
+    let data = ctx.data_ref();
+    let config = data.config();
+    let translate = config.translate()?;
+
+    let content = message.content.trim();
+    anyhow::ensure!(
+        !content.is_empty(),
+        HArgError::new_const("This message has no text to translate.")
+    );
+
+    ctx.defer_as(Ephemeral).await?;
+
+    let translation = translate
+        .provider
+        .translate(content, &translate.target)
+        .await
+        .context("could not translate message")?;
+
+    let title = format!(
+        "{} → {}",
+        translation.source_lang.to_ascii_uppercase(),
+        translate.target.to_ascii_uppercase(),
+    );
+
+    let embed = CreateEmbed::new()
+        .title(title)
+        .description(truncate(translation.text, 4000))
+        .color(config.embed_color);
+
+    ctx.send(CreateReply::new().embed(embed)).await?;
+    Ok(())
+}
@@ -0,0 +1,25 @@
+use std::collections::HashMap;
+
+use crate::prelude::*;
+
+pub type Config = HashMap<GuildId, WelcomeEntry>;
+
+/// Configures welcome and farewell messages for a guild.
+#[derive(Debug, serde::Deserialize)]
+pub struct WelcomeEntry {
+    /// The channel to post the messages to.
+    pub channel: ChannelId,
+    /// The message posted when a member joins. Not posted if unset.
+    ///
+    /// `{user}`, `{guild}`, and `{count}` are replaced.
+    pub join: Option<String>,
+    /// The message posted when a member leaves. Not posted if unset.
+    ///
+    /// `{user}`, `{guild}`, and `{count}` are replaced.
+    pub leave: Option<String>,
+    /// An image URL to attach to the join message.
+    pub image: Option<String>,
+    /// Roles given to members when they join.
+    #[serde(default)]
+    pub auto_roles: Vec<RoleId>,
+}
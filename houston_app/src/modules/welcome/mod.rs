@@ -0,0 +1,144 @@
+use utils::text::write_str::*;
+
+use super::prelude::*;
+use crate::fmt::discord::escape_markdown;
+use crate::fmt::replace_holes;
+
+pub mod config;
+
+pub use config::Config;
+
+pub struct Module;
+
+impl super::Module for Module {
+    fn enabled(&self, config: &HBotConfig) -> bool {
+        !config.welcome.is_empty()
+    }
+
+    fn intents(&self, _config: &HBotConfig) -> GatewayIntents {
+        GatewayIntents::GUILD_MEMBERS
+    }
+
+    fn validate(&self, config: &HBotConfig) -> Result {
+        for (guild, entry) in &config.welcome {
+            if let Some(image) = &entry.image {
+                anyhow::ensure!(
+                    image.starts_with("https://") || image.starts_with("http://"),
+                    "welcome.{guild}.image must be an http(s) URL"
+                );
+            }
+        }
+
+        log::info!("Welcome is enabled: {} guild(s)", config.welcome.len());
+        Ok(())
+    }
+}
+
+pub async fn guild_member_addition(ctx: Context, member: Member) {
+    if let Err(why) = guild_member_addition_inner(&ctx, &member).await {
+        log::error!("Welcome handling failed: {why:?}");
+    }
+}
+
+pub async fn guild_member_removal(ctx: Context, guild_id: GuildId, user: User) {
+    if let Err(why) = guild_member_removal_inner(&ctx, guild_id, &user).await {
+        log::error!("Farewell handling failed: {why:?}");
+    }
+}
+
+async fn guild_member_addition_inner(ctx: &Context, member: &Member) -> Result {
+    let data = ctx.data_ref::<HContextData>();
+    let config = data.config();
+    let Some(entry) = config.welcome.get(&member.guild_id) else {
+        return Ok(());
+    };
+
+    for &role in &entry.auto_roles {
+        let result = member
+            .guild_id
+            .add_member_role(&ctx.http, member.user.id, role, Some("welcome auto-role"))
+            .await;
+
+        if let Err(why) = result {
+            log::warn!(
+                "Failed to give auto-role {role} to {} in {}: {why:?}",
+                member.user.id,
+                member.guild_id
+            );
+        }
+    }
+
+    if let Some(join) = &entry.join {
+        let text = format_message(ctx, member.guild_id, &member.user, join).await;
+        let mut embed = CreateEmbed::new()
+            .description(text)
+            .thumbnail(member.user.face())
+            .color(config.embed_color);
+
+        if let Some(image) = &entry.image {
+            embed = embed.image(image.as_str());
+        }
+
+        send(ctx, entry.channel, embed).await?;
+    }
+
+    Ok(())
+}
+
+async fn guild_member_removal_inner(ctx: &Context, guild_id: GuildId, user: &User) -> Result {
+    let data = ctx.data_ref::<HContextData>();
+    let config = data.config();
+    let Some(entry) = config.welcome.get(&guild_id) else {
+        return Ok(());
+    };
+
+    if let Some(leave) = &entry.leave {
+        let text = format_message(ctx, guild_id, user, leave).await;
+        let embed = CreateEmbed::new()
+            .description(text)
+            .thumbnail(user.face())
+            .color(config.embed_color);
+
+        send(ctx, entry.channel, embed).await?;
+    }
+
+    Ok(())
+}
+
+async fn send(ctx: &Context, channel: ChannelId, embed: CreateEmbed<'_>) -> Result {
+    let message = CreateMessage::new()
+        .embed(embed)
+        .allowed_mentions(CreateAllowedMentions::new());
+
+    channel.send_message(&ctx.http, message).await?;
+    Ok(())
+}
+
+/// Replaces the holes in a welcome or farewell message.
+///
+/// The guild is only requested if the message needs its name or member count.
+async fn format_message(ctx: &Context, guild_id: GuildId, user: &User, template: &str) -> String {
+    let guild = if template.contains("{guild}") || template.contains("{count}") {
+        match guild_id.to_partial_guild_with_counts(&ctx.http).await {
+            Ok(guild) => Some(guild),
+            Err(why) => {
+                log::warn!("Failed to get guild {guild_id} for welcome: {why:?}");
+                None
+            },
+        }
+    } else {
+        None
+    };
+
+    replace_holes(template, |out, n| match (n, &guild) {
+        ("user", _) => write_str!(out, "<@{}>", user.id),
+        ("guild", Some(guild)) => write_str!(out, "{}", escape_markdown(&guild.name)),
+        ("guild", None) => out.push_str("the server"),
+        ("count", Some(guild)) => match guild.approximate_member_count {
+            Some(count) => write_str!(out, "{count}"),
+            None => out.push('?'),
+        },
+        ("count", None) => out.push('?'),
+        _ => out.push(char::REPLACEMENT_CHARACTER),
+    })
+}
@@ -24,7 +24,25 @@ pub async fn pre_command(ctx: Context<'_>) {
         DisplayResolvedArgs::Target,
     );
 
-    log::info!("{}: /{name} {options}", ctx.user().name)
+    log::info!("{}: /{name} {options}", ctx.user().name);
+    crate::metrics::record_command(&name.to_string());
+}
+
+/// Post-command execution hook.
+pub async fn post_command(ctx: Context<'_>, success: bool) {
+    use crate::modules::analytics::model::UsageKind;
+    use crate::modules::analytics::{self};
+
+    let name = DisplayCommandName::from(&ctx.interaction.data).to_string();
+    let latency = analytics::interaction_latency(ctx.interaction.id);
+    analytics::record(
+        ctx.serenity,
+        ctx.guild_id(),
+        UsageKind::Command,
+        name,
+        success,
+        latency,
+    );
 }
 
 /// Command execution error handler.
@@ -89,6 +107,12 @@ pub trait ContextExt<'a> {
     fn data_ref(self) -> &'a HBotData;
 
     fn require_guild_id(self) -> Result<GuildId>;
+
+    /// Ensures the bot has all the `needed` permissions in the current channel.
+    fn require_bot_permissions(self, needed: Permissions) -> Result;
+
+    /// Checks whether the invoking user owns the bot application.
+    async fn is_owner(self) -> Result<bool>;
 }
 
 impl<'a> ContextExt<'a> for Context<'a> {
@@ -104,6 +128,25 @@ impl<'a> ContextExt<'a> for Context<'a> {
     fn require_guild_id(self) -> Result<GuildId> {
         self.guild_id().context("must be used in guild")
     }
+
+    fn require_bot_permissions(self, needed: Permissions) -> Result {
+        crate::helper::discord::require_permissions(
+            self.interaction.app_permissions,
+            self.channel_id(),
+            needed,
+        )
+    }
+
+    async fn is_owner(self) -> Result<bool> {
+        let info = self.http().get_current_application_info().await?;
+        let user_id = self.user().id;
+        let is_owner = info.owner.is_some_and(|o| o.id == user_id)
+            || info
+                .team
+                .is_some_and(|t| t.members.iter().any(|m| m.user.id == user_id));
+
+        Ok(is_owner)
+    }
 }
 
 pub trait SlashUserExt<'a>: Sized {
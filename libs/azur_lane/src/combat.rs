@@ -0,0 +1,344 @@
+//! Provides helpers for combat calculations.
+//!
+//! These implement the commonly known formulas for hit rate, crits, reload,
+//! and damage. Effects that aren't part of the data model, such as skills,
+//! buffs, or enemy-specific modifiers, are not considered. The results are
+//! meant as estimates for comparisons rather than exact simulations.
+
+use crate::equip::*;
+use crate::ship::*;
+
+/// The base critical hit chance.
+pub const BASE_CRIT_RATE: f64 = 0.05;
+
+/// The base damage multiplier on critical hits.
+pub const BASE_CRIT_DAMAGE: f64 = 1.5;
+
+/// The minimum chance for an attack to hit.
+pub const MIN_HIT_RATE: f64 = 0.1;
+
+/// Additional reload time multiplier for strike aircraft.
+const AIRCRAFT_RELOAD_MULT: f64 = 2.2;
+
+/// The stats of one side of an attack that affect hit and crit chances.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Combatant {
+    /// The level.
+    pub level: u32,
+    /// The ACC stat.
+    pub acc: f64,
+    /// The EVA stat.
+    pub eva: f64,
+    /// The LCK stat.
+    pub lck: f64,
+}
+
+impl Combatant {
+    /// Creates a combatant from a ship's computed stats.
+    #[must_use]
+    pub fn from_stats(stats: &ComputedStats, params: StatParams) -> Self {
+        Self {
+            level: params.effective_level(),
+            acc: stats.acc,
+            eva: stats.eva,
+            lck: stats.lck,
+        }
+    }
+
+    /// The level and luck difference to another combatant, as used in the
+    /// hit and crit formulas.
+    fn luck_diff(&self, other: &Self) -> f64 {
+        self.lck - other.lck + f64::from(self.level) - f64::from(other.level)
+    }
+}
+
+/// Calculates the chance for an attack to hit.
+///
+/// The result is clamped to the range of [`MIN_HIT_RATE`] to `1.0`.
+#[must_use]
+pub fn hit_rate(attacker: &Combatant, defender: &Combatant) -> f64 {
+    let rate = MIN_HIT_RATE
+        + attacker.acc / (attacker.acc + defender.eva + 2.0)
+        + attacker.luck_diff(defender) / 1000.0;
+
+    rate.clamp(MIN_HIT_RATE, 1.0)
+}
+
+/// Calculates the chance for an attack to be a critical hit.
+#[must_use]
+pub fn crit_rate(attacker: &Combatant, defender: &Combatant) -> f64 {
+    let rate = BASE_CRIT_RATE
+        + attacker.acc / (attacker.acc + defender.eva + 2000.0)
+        + attacker.luck_diff(defender) / 5000.0;
+
+    rate.clamp(0.0, 1.0)
+}
+
+/// Calculates the average damage multiplier from critical hits.
+#[must_use]
+pub fn crit_mult(crit_rate: f64) -> f64 {
+    1.0 + crit_rate * (BASE_CRIT_DAMAGE - 1.0)
+}
+
+/// Calculates the multiplier for a weapon's reload time based on the RLD
+/// stat.
+///
+/// This is `1.0` at 100 RLD, which is the value [`Weapon::reload_time`] is
+/// stored at.
+#[must_use]
+pub fn reload_mult(rld: f64) -> f64 {
+    (200.0 / (100.0 + rld.max(0.0))).sqrt()
+}
+
+/// Calculates the effective HP, i.e. the raw damage needed to sink a ship.
+///
+/// This considers the armor modifiers of the attack and the chance to evade
+/// it. Since evasion is random, this is only the expected value.
+#[must_use]
+pub fn effective_hp(hp: f64, armor_mod: f64, hit_rate: f64) -> f64 {
+    hp / (armor_mod * hit_rate)
+}
+
+impl Weapon {
+    /// The reload time at the given RLD stat.
+    ///
+    /// This excludes fixed delays. See [`Weapon::cycle_time`].
+    #[must_use]
+    pub fn reload_time_at(&self, rld: f64) -> f64 {
+        let kind_mult = if self.kind == WeaponKind::StrikeAircraft {
+            AIRCRAFT_RELOAD_MULT
+        } else {
+            1.0
+        };
+
+        self.reload_time * kind_mult * reload_mult(rld)
+    }
+
+    /// The total fixed delay between reloads, including the salvo time.
+    #[must_use]
+    pub fn total_fixed_delay(&self) -> f64 {
+        self.fixed_delay + self.barrage().map_or(0.0, |b| b.salvo_time)
+    }
+
+    /// The time between the starts of two consecutive volleys at the given RLD
+    /// stat.
+    #[must_use]
+    pub fn cycle_time(&self, rld: f64) -> f64 {
+        self.reload_time_at(rld) + self.total_fixed_delay()
+    }
+
+    /// Gets the barrage this weapon fires directly, if any.
+    ///
+    /// This excludes barrages fired by launched aircraft.
+    #[must_use]
+    pub fn barrage(&self) -> Option<&Barrage> {
+        match &self.data {
+            WeaponData::Bullets(b) | WeaponData::AntiAir(b) => Some(b),
+            WeaponData::Aircraft(_) => None,
+        }
+    }
+}
+
+impl Barrage {
+    /// Calculates the damage of a single hit before armor modifiers.
+    ///
+    /// `stat` is the value of the [`Barrage::scaling_stat`] and `efficiency`
+    /// is the slot efficiency, with `1.0` equal to 100%.
+    #[must_use]
+    pub fn hit_damage(&self, stat: f64, efficiency: f64) -> f64 {
+        self.damage * self.coefficient * efficiency * (1.0 + stat * self.scaling / 100.0)
+    }
+
+    /// The amount of hits a full volley can deal to a single target.
+    ///
+    /// Beams count once per tick.
+    #[must_use]
+    pub fn hit_count(&self) -> f64 {
+        self.bullets.iter().map(Bullet::hit_count).sum()
+    }
+
+    /// Calculates the expected damage of a full volley against a kind of
+    /// armor, assuming every bullet hits.
+    ///
+    /// Multiply with [`hit_rate`] to account for evasion.
+    #[must_use]
+    pub fn volley_damage(
+        &self,
+        stat: f64,
+        efficiency: f64,
+        armor: ShipArmor,
+        crit_rate: f64,
+    ) -> f64 {
+        let damage = self.hit_damage(stat, efficiency) * crit_mult(crit_rate);
+        self.bullets
+            .iter()
+            .map(|b| damage * b.hit_count() * b.modifiers.modifier(armor))
+            .sum()
+    }
+}
+
+impl Bullet {
+    /// The amount of hits this bullet entry can deal to a single target.
+    ///
+    /// Beams count once per tick.
+    #[must_use]
+    pub fn hit_count(&self) -> f64 {
+        let ticks = match &self.extra {
+            BulletExtra::Beam(beam) if beam.tick_delay > 0.0 => {
+                (beam.duration / beam.tick_delay).max(1.0)
+            },
+            _ => 1.0,
+        };
+
+        f64::from(self.amount) * ticks
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Asserts that two values are equal, allowing for rounding errors.
+    fn assert_near(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-6,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    fn bullet(amount: u32, modifiers: [f64; 3], extra: BulletExtra) -> Bullet {
+        Bullet {
+            bullet_id: 1,
+            amount,
+            kind: BulletKind::Cannon,
+            ammo: AmmoKind::HE,
+            pierce: 0,
+            velocity: 20.0,
+            modifiers: modifiers.into(),
+            flags: BulletFlags::empty(),
+            attach_buff: Vec::new(),
+            extra,
+        }
+    }
+
+    fn barrage(bullets: Vec<Bullet>) -> Barrage {
+        Barrage {
+            damage: 50.0,
+            coefficient: 1.1,
+            scaling: 1.0,
+            scaling_stat: StatKind::FP,
+            range: 70.0,
+            firing_angle: 180.0,
+            salvo_time: 0.5,
+            bullets,
+        }
+    }
+
+    fn weapon(kind: WeaponKind) -> Weapon {
+        Weapon {
+            weapon_id: 1,
+            name: None,
+            reload_time: 10.0,
+            fixed_delay: 0.2,
+            kind,
+            data: WeaponData::Bullets(barrage(vec![bullet(1, [1.0, 1.0, 1.0], BulletExtra::None)])),
+        }
+    }
+
+    fn combatant(level: u32, acc: f64, eva: f64, lck: f64) -> Combatant {
+        Combatant {
+            level,
+            acc,
+            eva,
+            lck,
+        }
+    }
+
+    #[test]
+    fn reload_mult_values() {
+        // reload times are stored at 100 RLD
+        assert_near(reload_mult(100.0), 1.0);
+        assert_near(reload_mult(0.0), 2f64.sqrt());
+        assert_near(reload_mult(150.0), 0.894_427_191);
+        assert_near(reload_mult(300.0), 0.5f64.sqrt());
+
+        // negative RLD counts as 0
+        assert_near(reload_mult(-50.0), 2f64.sqrt());
+    }
+
+    #[test]
+    fn weapon_reload() {
+        let gun = weapon(WeaponKind::MainGun);
+        assert_near(gun.reload_time_at(100.0), 10.0);
+        assert_near(gun.reload_time_at(150.0), 8.944_271_91);
+        assert_near(gun.total_fixed_delay(), 0.7);
+        assert_near(gun.cycle_time(150.0), 9.644_271_91);
+
+        let plane = weapon(WeaponKind::StrikeAircraft);
+        assert_near(plane.reload_time_at(100.0), 22.0);
+    }
+
+    #[test]
+    fn hit_and_crit_rates() {
+        let attacker = combatant(120, 200.0, 0.0, 50.0);
+        let defender = combatant(120, 0.0, 100.0, 0.0);
+
+        // 0.1 + 200 / 302 + 50 / 1000
+        assert_near(hit_rate(&attacker, &defender), 0.812_251_656);
+        // 0.05 + 200 / 2300 + 50 / 5000
+        assert_near(crit_rate(&attacker, &defender), 0.146_956_522);
+    }
+
+    #[test]
+    fn hit_rate_is_clamped() {
+        let weak = combatant(1, 0.0, 0.0, 0.0);
+        let strong = combatant(125, 1000.0, 1000.0, 100.0);
+
+        assert_near(hit_rate(&weak, &strong), MIN_HIT_RATE);
+        assert_near(hit_rate(&strong, &weak), 1.0);
+    }
+
+    #[test]
+    fn crit_damage() {
+        assert_near(crit_mult(0.0), 1.0);
+        assert_near(crit_mult(0.2), 1.1);
+        assert_near(crit_mult(1.0), BASE_CRIT_DAMAGE);
+    }
+
+    #[test]
+    fn barrage_damage() {
+        let beam = BulletExtra::Beam(BulletBeam {
+            duration: 2.0,
+            tick_delay: 0.5,
+        });
+
+        let barrage = barrage(vec![
+            bullet(3, [0.75, 1.1, 0.65], BulletExtra::None),
+            bullet(1, [1.0, 0.8, 0.6], beam),
+        ]);
+
+        // 50 * 1.1 * 125% * (1 + 400 FP * 100% / 100)
+        assert_near(barrage.hit_damage(400.0, 1.25), 343.75);
+
+        // 3 shells plus a beam with 4 ticks
+        assert_near(barrage.hit_count(), 7.0);
+
+        // 343.75 * (3 * 1.1 + 4 * 0.8)
+        assert_near(
+            barrage.volley_damage(400.0, 1.25, ShipArmor::Medium, 0.0),
+            2234.375,
+        );
+
+        // crits raise the damage by the average crit multiplier
+        assert_near(
+            barrage.volley_damage(400.0, 1.25, ShipArmor::Medium, 0.2),
+            2234.375 * 1.1,
+        );
+    }
+
+    #[test]
+    fn effective_hp_values() {
+        assert_near(effective_hp(10000.0, 1.0, 1.0), 10000.0);
+        assert_near(effective_hp(10000.0, 0.8, 0.5), 25000.0);
+    }
+}
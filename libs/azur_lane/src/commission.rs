@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+
+use crate::data_def::skip_empty;
+use crate::ship::HullType;
+use crate::Reward;
+
+/// A commission a fleet can be sent on.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Commission {
+    // "id"
+    pub commission_id: u32,
+    pub name: String,
+    /// The duration in seconds.
+    // "time"
+    pub duration: u32,
+    /// The oil needed to start the commission.
+    // "oil"
+    pub oil_cost: u32,
+    /// The requirements for the fleet.
+    pub requirements: CommissionRequirements,
+    /// The possible rewards.
+    // "drop_display"
+    #[serde(default, skip_serializing_if = "skip_empty")]
+    pub rewards: Vec<Reward>,
+}
+
+/// The requirements a fleet must fulfill to be sent on a commission.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CommissionRequirements {
+    /// The minimum amount of ships.
+    // "ship_num"
+    pub ship_count: u32,
+    /// The minimum level of the flagship.
+    // "ship_lv"
+    pub flagship_level: u32,
+    /// The hull types of which at least one ship must be present.
+    ///
+    /// If empty, any hull type is allowed.
+    // "ship_type"
+    #[serde(default, skip_serializing_if = "skip_empty")]
+    pub hull_types: Vec<HullType>,
+}
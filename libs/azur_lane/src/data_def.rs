@@ -1,9 +1,19 @@
+use std::cell::Cell;
+use std::fmt;
+use std::marker::PhantomData;
+
+use serde::de::{self, Deserializer, EnumAccess, VariantAccess, Visitor};
+
+// Every variant is assigned an explicit id. Formats that aren't human-readable,
+// like `serde_steph`, store that id rather than the variant's position, so
+// variants may be added or reordered without breaking previously saved data.
+// Once assigned, an id must never change or be reused.
 macro_rules! define_data_enum {
     {
         $(#[$attr:meta])*
         $v:vis enum $name:ident for $vd:vis $data:ident {
             $($(#[$data_attr:meta])* $data_vis:vis $data_name:ident : $data_type:ty),* ;
-            $($(#[$field_attr:meta])* $field:ident $arg:tt),*
+            $($(#[$field_attr:meta])* $field:ident $arg:tt = $id:literal),*
         }
     } => {
         $(#[$attr])*
@@ -17,15 +27,19 @@ macro_rules! define_data_enum {
         }
 
         $(#[$attr])*
-        #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize)]
+        #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+        #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
         $v enum $name {
             $(
                 $(#[$field_attr])*
-                $field
+                $field = $id
             ),*
         }
 
         impl $name {
+            /// All variants, in declaration order.
+            $v const ALL: &'static [Self] = &[$(Self::$field),*];
+
             /// Gets the entire associated data structure.
             #[must_use]
             $vd const fn data(self) -> &'static $data {
@@ -43,6 +57,30 @@ macro_rules! define_data_enum {
                 }
             }
 
+            /// Gets the stable id of the variant.
+            ///
+            /// This is used in place of the variant name when serializing to
+            /// formats that aren't human-readable.
+            #[must_use]
+            #[inline]
+            $v const fn id(self) -> u32 {
+                self as u32
+            }
+
+            /// Gets the variant with a specific stable id.
+            #[must_use]
+            $v fn from_id(id: u32) -> Option<Self> {
+                Self::ALL.iter().copied().find(|v| v.id() == id)
+            }
+
+            /// Gets the name of the variant as used in human-readable formats.
+            #[must_use]
+            $v const fn variant_name(self) -> &'static str {
+                match self {
+                    $($name::$field => stringify!($field)),*
+                }
+            }
+
             $(
                 $(#[$data_attr])*
                 #[must_use]
@@ -52,12 +90,153 @@ macro_rules! define_data_enum {
                 }
             )*
         }
+
+        impl $crate::data_def::DataEnum for $name {
+            const NAME: &'static str = stringify!($name);
+            const VARIANTS: &'static [&'static str] = &[$(stringify!($field)),*];
+
+            fn from_id(id: u32) -> Option<Self> {
+                Self::from_id(id)
+            }
+
+            fn from_variant_name(name: &str) -> Option<Self> {
+                Self::ALL.iter().copied().find(|v| v.variant_name() == name)
+            }
+        }
+
+        impl serde::Serialize for $name {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_unit_variant(stringify!($name), self.id(), self.variant_name())
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for $name {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                $crate::data_def::deserialize_data_enum(deserializer)
+            }
+        }
     };
 }
 
 pub(crate) use define_data_enum;
 
+thread_local! {
+    static KEEP_ALL_FIELDS: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Runs `f` while forcing all fields to be serialized.
+///
+/// Formats that aren't self-describing, like `serde_steph`, cannot represent
+/// skipped fields, so saving to them needs to be wrapped in this.
+pub fn with_all_fields<R>(f: impl FnOnce() -> R) -> R {
+    struct Reset(bool);
+    impl Drop for Reset {
+        fn drop(&mut self) {
+            KEEP_ALL_FIELDS.set(self.0);
+        }
+    }
+
+    let _reset = Reset(KEEP_ALL_FIELDS.replace(true));
+    f()
+}
+
+/// Values that may be left out of the serialized output when empty.
+pub trait IsEmpty {
+    /// Whether the value is empty.
+    fn is_empty_value(&self) -> bool;
+}
+
+impl<T> IsEmpty for Vec<T> {
+    fn is_empty_value(&self) -> bool {
+        self.is_empty()
+    }
+}
+
+impl<T> IsEmpty for Option<T> {
+    fn is_empty_value(&self) -> bool {
+        self.is_none()
+    }
+}
+
+#[must_use]
+pub fn skip_empty<T: IsEmpty>(value: &T) -> bool {
+    !KEEP_ALL_FIELDS.get() && value.is_empty_value()
+}
+
 #[must_use]
 pub fn is_default<T: Default + PartialEq>(value: &T) -> bool {
-    *value == T::default()
+    !KEEP_ALL_FIELDS.get() && *value == T::default()
+}
+
+/// Enums defined via `define_data_enum!`.
+pub trait DataEnum: Copy + 'static {
+    /// The name of the enum.
+    const NAME: &'static str;
+    /// The names of all variants.
+    const VARIANTS: &'static [&'static str];
+
+    /// Gets the variant with a specific stable id.
+    fn from_id(id: u32) -> Option<Self>;
+
+    /// Gets the variant with a specific name.
+    fn from_variant_name(name: &str) -> Option<Self>;
+}
+
+/// Deserializes a data enum either from its stable id or its variant name.
+pub fn deserialize_data_enum<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: DataEnum,
+{
+    struct EnumVisitor<T>(PhantomData<T>);
+    struct VariantVisitor<T>(PhantomData<T>);
+
+    impl<'de, T: DataEnum> Visitor<'de> for EnumVisitor<T> {
+        type Value = T;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(formatter, "enum {}", T::NAME)
+        }
+
+        fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+        where
+            A: EnumAccess<'de>,
+        {
+            let (value, variant) = data.variant_seed(VariantVisitor(PhantomData))?;
+            variant.unit_variant()?;
+            Ok(value)
+        }
+    }
+
+    impl<'de, T: DataEnum> de::DeserializeSeed<'de> for VariantVisitor<T> {
+        type Value = T;
+
+        fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_identifier(self)
+        }
+    }
+
+    impl<T: DataEnum> Visitor<'_> for VariantVisitor<T> {
+        type Value = T;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(formatter, "variant id or name of {}", T::NAME)
+        }
+
+        fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+            u32::try_from(v)
+                .ok()
+                .and_then(T::from_id)
+                .ok_or_else(|| E::invalid_value(de::Unexpected::Unsigned(v), &self))
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+            T::from_variant_name(v).ok_or_else(|| E::unknown_variant(v, T::VARIANTS))
+        }
+    }
+
+    deserializer.deserialize_enum(T::NAME, T::VARIANTS, EnumVisitor(PhantomData))
 }
@@ -0,0 +1,339 @@
+//! Provides loading and saving of [`DefinitionData`] in its supported file
+//! formats.
+
+use std::path::Path;
+use std::{fs, io};
+
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+
+use crate::data_def::with_all_fields;
+use crate::DefinitionData;
+
+/// The serialization format of a data file.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum DataFormat {
+    /// Human-readable JSON.
+    #[default]
+    Json,
+    /// Compact `serde_steph` binary.
+    Steph,
+}
+
+/// The kind of data file, that is, its format and whether it is compressed.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DataFileKind {
+    /// The serialization format.
+    pub format: DataFormat,
+    /// Whether the file is gzip-compressed.
+    pub compressed: bool,
+}
+
+/// Error when loading or saving a data file.
+#[derive(Debug, thiserror::Error)]
+pub enum DataFileError {
+    #[error("io error: {0}")]
+    Io(#[from] io::Error),
+    #[error("json error: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("steph error: {0}")]
+    Steph(#[from] serde_steph::Error),
+    #[error("no data file found")]
+    NotFound,
+}
+
+impl DataFileKind {
+    /// All known kinds, in the order they are preferred when loading.
+    pub const ALL: [Self; 4] = [
+        Self::new(DataFormat::Steph, true),
+        Self::new(DataFormat::Steph, false),
+        Self::new(DataFormat::Json, true),
+        Self::new(DataFormat::Json, false),
+    ];
+
+    /// Creates a new data file kind.
+    #[must_use]
+    pub const fn new(format: DataFormat, compressed: bool) -> Self {
+        Self { format, compressed }
+    }
+
+    /// Guesses the kind of a data file from its file name.
+    ///
+    /// Files ending in `.gz` are considered compressed, and files with a
+    /// `.steph` extension before that are considered `serde_steph` data.
+    /// Anything else is assumed to be JSON.
+    #[must_use]
+    pub fn from_file_name(name: &str) -> Self {
+        let (name, compressed) = match name.strip_suffix(".gz") {
+            Some(name) => (name, true),
+            None => (name, false),
+        };
+
+        let format = if name.ends_with(".steph") {
+            DataFormat::Steph
+        } else {
+            DataFormat::Json
+        };
+
+        Self::new(format, compressed)
+    }
+
+    /// Gets the name of the main data file of this kind.
+    #[must_use]
+    pub const fn file_name(self) -> &'static str {
+        match (self.format, self.compressed) {
+            (DataFormat::Json, false) => "main.json",
+            (DataFormat::Json, true) => "main.json.gz",
+            (DataFormat::Steph, false) => "main.steph",
+            (DataFormat::Steph, true) => "main.steph.gz",
+        }
+    }
+}
+
+impl DefinitionData {
+    /// Loads the data from a reader with the given kind.
+    pub fn load<R: io::Read>(reader: R, kind: DataFileKind) -> Result<Self, DataFileError> {
+        if kind.compressed {
+            Self::load_uncompressed(GzDecoder::new(reader), kind.format)
+        } else {
+            Self::load_uncompressed(reader, kind.format)
+        }
+    }
+
+    /// Loads the main data file from a directory.
+    ///
+    /// If there are multiple data files, the first one in the order of
+    /// [`DataFileKind::ALL`] is used.
+    pub fn load_from_dir(dir: &Path) -> Result<Self, DataFileError> {
+        for kind in DataFileKind::ALL {
+            let file = match fs::File::open(dir.join(kind.file_name())) {
+                Ok(file) => file,
+                Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+                Err(err) => return Err(err.into()),
+            };
+
+            return Self::load(io::BufReader::new(file), kind);
+        }
+
+        Err(DataFileError::NotFound)
+    }
+
+    /// Loads the data from a path.
+    ///
+    /// If the path is a directory, this is equivalent to
+    /// [`DefinitionData::load_from_dir`]. Otherwise, the kind is guessed with
+    /// [`DataFileKind::from_file_name`].
+    pub fn load_from_path(path: &Path) -> Result<Self, DataFileError> {
+        if path.is_dir() {
+            return Self::load_from_dir(path);
+        }
+
+        let name = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or_default();
+        let kind = DataFileKind::from_file_name(name);
+        let file = fs::File::open(path)?;
+        Self::load(io::BufReader::new(file), kind)
+    }
+
+    /// Generates the JSON schema for the data in the JSON format.
+    ///
+    /// The schema describes the structure consumers can rely on. Fields that
+    /// may be omitted when empty are not marked as required.
+    #[cfg(feature = "schema")]
+    #[must_use]
+    pub fn json_schema() -> schemars::Schema {
+        schemars::schema_for!(Self)
+    }
+
+    /// Saves the data to a writer with the given kind.
+    ///
+    /// `pretty` only affects JSON output.
+    pub fn save<W: io::Write>(
+        &self,
+        writer: W,
+        kind: DataFileKind,
+        pretty: bool,
+    ) -> Result<(), DataFileError> {
+        if kind.compressed {
+            let mut writer = GzEncoder::new(writer, flate2::Compression::best());
+            self.save_uncompressed(&mut writer, kind.format, pretty)?;
+            writer.finish()?;
+            Ok(())
+        } else {
+            self.save_uncompressed(writer, kind.format, pretty)
+        }
+    }
+
+    fn load_uncompressed<R: io::Read>(
+        reader: R,
+        format: DataFormat,
+    ) -> Result<Self, DataFileError> {
+        Ok(match format {
+            DataFormat::Json => serde_json::from_reader(reader)?,
+            DataFormat::Steph => serde_steph::from_reader(reader)?,
+        })
+    }
+
+    fn save_uncompressed<W: io::Write>(
+        &self,
+        writer: W,
+        format: DataFormat,
+        pretty: bool,
+    ) -> Result<(), DataFileError> {
+        match format {
+            DataFormat::Json if pretty => serde_json::to_writer_pretty(writer, self)?,
+            DataFormat::Json => serde_json::to_writer(writer, self)?,
+            DataFormat::Steph => with_all_fields(|| serde_steph::to_writer(writer, self))?,
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commission::{Commission, CommissionRequirements};
+    use crate::data_def::skip_empty;
+    use crate::juustagram::{Chat, ChatContent, ChatEntry, ChatOption, ChatUnlock};
+    use crate::ship::{HullType, ShipLevel};
+    use crate::stage::Stage;
+    use crate::{Reward, RewardKind};
+
+    /// Creates data with both set and empty values in skippable fields.
+    fn sample_data() -> DefinitionData {
+        let stage = |stage_id, required_stage_id, drops| Stage {
+            stage_id,
+            code: format!("1-{stage_id}"),
+            name: "Stage".to_owned(),
+            required_stage_id,
+            commander_level: 1,
+            drops,
+        };
+
+        let option = |favor| ChatOption {
+            flag: 1,
+            value: "Option".to_owned(),
+            favor,
+        };
+
+        DefinitionData {
+            stages: vec![
+                stage(1, None, Vec::new()),
+                stage(
+                    2,
+                    Some(1),
+                    vec![Reward {
+                        kind: RewardKind::Ship,
+                        id: 10,
+                    }],
+                ),
+            ],
+            commissions: vec![Commission {
+                commission_id: 1,
+                name: "Commission".to_owned(),
+                duration: 3600,
+                oil_cost: 10,
+                requirements: CommissionRequirements {
+                    ship_count: 2,
+                    flagship_level: 0,
+                    hull_types: vec![HullType::Destroyer, HullType::Battleship],
+                },
+                rewards: Vec::new(),
+            }],
+            juustagram_chats: vec![Chat {
+                chat_id: 1,
+                group_id: 10,
+                name: "Chat".to_owned(),
+                unlock_desc: String::new(),
+                unlock: Some(ChatUnlock::Affinity { amount: 60 }),
+                entries: vec![
+                    ChatEntry {
+                        entry_id: 1,
+                        content: ChatContent::System {
+                            text: "Hello".to_owned(),
+                        },
+                        flag: 0,
+                        options: None,
+                    },
+                    ChatEntry {
+                        entry_id: 2,
+                        content: ChatContent::Message {
+                            sender_id: 10,
+                            text: "Hi".to_owned(),
+                        },
+                        flag: 1,
+                        options: Some(vec![option(0), option(5)]),
+                    },
+                ],
+            }],
+            ship_levels: vec![ShipLevel {
+                level: 1,
+                exp: 100,
+                exp_ur: 120,
+            }],
+            ..DefinitionData::default()
+        }
+    }
+
+    /// Converts the data to a JSON value with every field present, so that
+    /// skipped and default fields are compared too.
+    fn to_value(data: &DefinitionData) -> serde_json::Value {
+        with_all_fields(|| serde_json::to_value(data)).expect("must convert to json value")
+    }
+
+    #[test]
+    fn round_trip_all_kinds() {
+        let data = sample_data();
+        let expected = to_value(&data);
+
+        for kind in DataFileKind::ALL {
+            for pretty in [false, true] {
+                let mut buf = Vec::new();
+                data.save(&mut buf, kind, pretty).expect("must save data");
+
+                let loaded = DefinitionData::load(buf.as_slice(), kind).expect("must load data");
+                assert_eq!(to_value(&loaded), expected, "{kind:?} must round-trip");
+            }
+        }
+    }
+
+    #[test]
+    fn json_skips_empty_fields() {
+        let mut buf = Vec::new();
+        sample_data()
+            .save(&mut buf, DataFileKind::new(DataFormat::Json, false), false)
+            .expect("must save data");
+
+        let json = String::from_utf8(buf).expect("json must be utf-8");
+        assert!(!json.contains("null"), "empty options must be skipped");
+        assert!(
+            !json.contains("\"favor\":0"),
+            "default favor must be skipped"
+        );
+        assert!(!json.contains("\"equips\""), "empty lists must be skipped");
+    }
+
+    #[test]
+    fn with_all_fields_is_scoped() {
+        let empty = None::<u32>;
+        assert!(skip_empty(&empty));
+
+        with_all_fields(|| {
+            assert!(!skip_empty(&empty));
+            with_all_fields(|| assert!(!skip_empty(&empty)));
+            assert!(!skip_empty(&empty), "nested call must not reset early");
+        });
+
+        assert!(skip_empty(&empty), "must reset after the call");
+    }
+
+    #[test]
+    fn file_names_match_kinds() {
+        for kind in DataFileKind::ALL {
+            assert_eq!(DataFileKind::from_file_name(kind.file_name()), kind);
+        }
+    }
+}
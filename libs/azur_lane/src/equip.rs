@@ -2,17 +2,22 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::data_def::{is_default, skip_empty, IsEmpty};
 use crate::ship::*;
 use crate::skill::*;
-use crate::{define_data_enum, Faction};
+use crate::{define_data_enum, Faction, LocalizedNames};
 
 /// Represents a piece of equipment.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Equip {
     /// The equipment's ID. This differs per upgrade step.
     pub equip_id: u32,
     /// The equipment's display name.
     pub name: String,
+    /// The display name in each known locale.
+    #[serde(default, skip_serializing_if = "skip_empty")]
+    pub names: LocalizedNames,
     /// The equipment's description.
     ///
     /// This is not the skill description. Instead, it is the description shown
@@ -26,30 +31,36 @@ pub struct Equip {
     pub rarity: EquipRarity,
     /// The manufacturer faction.
     pub faction: Faction,
+    /// The key of the equipment's icon.
+    ///
+    /// Upgrade steps of the same equipment usually share their icon.
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub icon: String,
     /// The weapons this equipment carries.
     ///
     /// This will usually just hold a single element.
     /// The most common case where this doesn't hold is aircraft with intercept;
     /// the strike and intercept versions are different weapons.
-    #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "skip_empty")]
     pub weapons: Vec<Weapon>,
     /// Skills this equipment activates when equipped.
-    #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "skip_empty")]
     pub skills: Vec<Skill>,
     /// The stat bonuses provided when equipped.
-    #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "skip_empty")]
     pub stat_bonuses: Vec<EquipStatBonus>,
     /// Hull types that this equipment cannot be equipped on, even if the
     /// [`Equip::kind`] would allow it.
     ///
     /// Data on "allowed hull types" is excluded since it's purely informative,
     /// and not accurately at that.
-    #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "skip_empty")]
     pub hull_disallowed: Vec<HullType>,
 }
 
 /// A weapon that is part of [`Equip`] or [`Skill`].
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Weapon {
     /// The weapon's ID. This differs per upgrade step/skill level.
     pub weapon_id: u32,
@@ -72,6 +83,7 @@ pub struct Weapon {
 
 /// A bullet barrage pattern for a [`Weapon`].
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Barrage {
     /// The damage per bullet.
     pub damage: f64,
@@ -98,6 +110,7 @@ pub struct Barrage {
 
 /// Bullet information for a [`Barrage`].
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Bullet {
     /// The bullet's ID.
     pub bullet_id: u32,
@@ -121,16 +134,17 @@ pub struct Bullet {
     pub flags: BulletFlags,
 
     /// Buffs caused by the bullet hit.
-    #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "skip_empty")]
     pub attach_buff: Vec<BuffInfo>,
 
     /// Extra data depending on the bullet type.
-    #[serde(default, skip_serializing_if = "BulletExtra::is_none")]
+    #[serde(default, skip_serializing_if = "skip_empty")]
     pub extra: BulletExtra,
 }
 
 /// Additional bullet data.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum BulletExtra {
     /// No extra data.
     #[default]
@@ -144,6 +158,7 @@ pub enum BulletExtra {
 /// How far a bullet's hit spread and AOE is. Only applicable to main gun fire
 /// and bombs.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct BulletSpread {
     /// Horizontal spread.
     pub spread_x: f64,
@@ -155,6 +170,7 @@ pub struct BulletSpread {
 
 /// Additional information about a beam.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct BulletBeam {
     /// The total duration of the beam.
     pub duration: f64,
@@ -164,6 +180,7 @@ pub struct BulletBeam {
 
 /// Aircraft data for a [`Weapon`].
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Aircraft {
     /// The aircraft's ID. This differs per upgrade step/skill level.
     pub aircraft_id: u32,
@@ -181,6 +198,7 @@ pub struct Aircraft {
 
 /// The possible data a [`Weapon`] can hold.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum WeaponData {
     /// The weapon fires bullets as a [`Barrage`].
     Bullets(Barrage),
@@ -192,6 +210,7 @@ pub enum WeaponData {
 
 /// Armor modifiers to apply to the damage.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ArmorModifiers(
     /// Modifier to Light armor.
     pub f64,
@@ -203,6 +222,7 @@ pub struct ArmorModifiers(
 
 /// Bonus stats gained by equipping the associated equipment.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct EquipStatBonus {
     /// The stat increased.
     pub stat_kind: StatKind,
@@ -212,11 +232,15 @@ pub struct EquipStatBonus {
 
 /// Represents an Augment Module.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Augment {
     /// The augment's ID.
     pub augment_id: u32,
     /// The augment's display name.
     pub name: String,
+    /// The display name in each known locale.
+    #[serde(default, skip_serializing_if = "skip_empty")]
+    pub names: LocalizedNames,
     /// The augment's rarity and star rating.
     pub rarity: AugmentRarity,
     /// The stat bonuses provided by the augment.
@@ -224,15 +248,36 @@ pub struct Augment {
     /// Who can equip this augment.
     pub usability: AugmentUsability,
     /// The augment's primary effect skill.
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "skip_empty")]
     pub effect: Option<Skill>,
     /// The augment's skill upgrade.
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "skip_empty")]
     pub skill_upgrade: Option<AugmentSkillUpgrade>,
+    /// The augment's data at each enhancement level, starting at the
+    /// unenhanced state.
+    ///
+    /// The other fields hold the data at the max level.
+    #[serde(default, skip_serializing_if = "skip_empty")]
+    pub levels: Vec<AugmentLevel>,
+}
+
+/// The data of an augment at a specific enhancement level.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct AugmentLevel {
+    /// The stat bonuses at this level.
+    pub stat_bonuses: Vec<AugmentStatBonus>,
+    /// The description of the effect skill at this level.
+    #[serde(default, skip_serializing_if = "skip_empty")]
+    pub effect_description: Option<String>,
+    /// The description of the upgraded skill at this level.
+    #[serde(default, skip_serializing_if = "skip_empty")]
+    pub skill_upgrade_description: Option<String>,
 }
 
 /// Represents who an Augment Module can be used on.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum AugmentUsability {
     /// Only certain hull types are allowed.
     HullTypes(Vec<HullType>),
@@ -242,6 +287,7 @@ pub enum AugmentUsability {
 
 /// Bonus stats gained by equipping the associated augment.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct AugmentStatBonus {
     /// The stat increased.
     pub stat_kind: StatKind,
@@ -253,6 +299,7 @@ pub struct AugmentStatBonus {
 
 /// A skill upgraded by an augment module.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct AugmentSkillUpgrade {
     /// The ID of the original skill to replace.
     pub original_id: u32,
@@ -280,25 +327,25 @@ define_data_enum! {
         /// A friendly name for the equipment kind.
         pub name: &'static str;
 
-        DestroyerGun("DD Gun"),
-        LightCruiserGun("CL Gun"),
-        HeavyCruiserGun("CA Gun"),
-        LargeCruiserGun("CB Gun"),
-        BattleshipGun("BB Gun"),
-        SurfaceTorpedo("Torpedo (Surface)"),
-        SubmarineTorpedo("Torpedo (Submarine)"),
-        AntiAirGun("Anti-Air Gun"),
-        FuzeAntiAirGun("Anti-Air Gun (Fuze)"),
-        Fighter("Fighter"),
-        DiveBomber("Dive Bomber"),
-        TorpedoBomber("Torpedo Bomber"),
-        SeaPlane("Seaplane"),
-        AntiSubWeapon("Anti-Sub Weapon"),
-        AntiSubAircraft("Anti-Sub Aircraft"),
-        Helicopter("Helicopter"),
-        Missile("Missile"),
-        Cargo("Cargo"),
-        Auxiliary("Auxiliary")
+        DestroyerGun("DD Gun") = 0,
+        LightCruiserGun("CL Gun") = 1,
+        HeavyCruiserGun("CA Gun") = 2,
+        LargeCruiserGun("CB Gun") = 3,
+        BattleshipGun("BB Gun") = 4,
+        SurfaceTorpedo("Torpedo (Surface)") = 5,
+        SubmarineTorpedo("Torpedo (Submarine)") = 6,
+        AntiAirGun("Anti-Air Gun") = 7,
+        FuzeAntiAirGun("Anti-Air Gun (Fuze)") = 8,
+        Fighter("Fighter") = 9,
+        DiveBomber("Dive Bomber") = 10,
+        TorpedoBomber("Torpedo Bomber") = 11,
+        SeaPlane("Seaplane") = 12,
+        AntiSubWeapon("Anti-Sub Weapon") = 13,
+        AntiSubAircraft("Anti-Sub Aircraft") = 14,
+        Helicopter("Helicopter") = 15,
+        Missile("Missile") = 16,
+        Cargo("Cargo") = 17,
+        Auxiliary("Auxiliary") = 18
     }
 }
 
@@ -308,22 +355,22 @@ define_data_enum! {
         /// A friendly name for the bullet kind.
         pub name: &'static str;
 
-        Cannon("Cannon"),
-        Bomb("Bomb"),
-        Torpedo("Torpedo"),
-        Direct("Direct"),
-        Shrapnel("Shrapnel"),
-        AntiAir("Anti-Air"),
-        AntiSea("Anti-Submarine"),
-        Effect("Effect"),
-        Beam("Beam"),
-        GBullet("GBullet"),
-        EletricArc("Eletric Arc"),
-        Missile("Missile"),
-        SpaceLaser("Space Laser"),
-        Scale("Scale"),
-        TriggerBomb("Trigger Bomb"),
-        AAMissile("AA Missile")
+        Cannon("Cannon") = 0,
+        Bomb("Bomb") = 1,
+        Torpedo("Torpedo") = 2,
+        Direct("Direct") = 3,
+        Shrapnel("Shrapnel") = 4,
+        AntiAir("Anti-Air") = 5,
+        AntiSea("Anti-Submarine") = 6,
+        Effect("Effect") = 7,
+        Beam("Beam") = 8,
+        GBullet("GBullet") = 9,
+        EletricArc("Eletric Arc") = 10,
+        Missile("Missile") = 11,
+        SpaceLaser("Space Laser") = 12,
+        Scale("Scale") = 13,
+        TriggerBomb("Trigger Bomb") = 14,
+        AAMissile("AA Missile") = 15
     }
 }
 
@@ -335,15 +382,15 @@ define_data_enum! {
         /// A shorter ammo name.
         pub short_name: &'static str;
 
-        Normal("Normal", "Nor."),
-        AP("AP", "AP"),
-        HE("HE", "HE"),
-        Torpedo("Torpedo", "Tor."),
-        AirToAir("Air-to-Air", "Air."),
-        Bomb("Bomb", "Bomb"),
-        SAP("SAP", "SAP"),
-        Unknown8("8", "?"),
-        Unknown9("9", "?")
+        Normal("Normal", "Nor.") = 0,
+        AP("AP", "AP") = 1,
+        HE("HE", "HE") = 2,
+        Torpedo("Torpedo", "Tor.") = 3,
+        AirToAir("Air-to-Air", "Air.") = 4,
+        Bomb("Bomb", "Bomb") = 5,
+        SAP("SAP", "SAP") = 6,
+        Unknown8("8", "?") = 7,
+        Unknown9("9", "?") = 8
     }
 }
 
@@ -356,38 +403,38 @@ define_data_enum! {
         /// A friendly name for the weapon kind.
         pub name: &'static str;
 
-        MainGun("Main Gun"),
-        SubGun("Auto Gun"),
-        Torpedo("Torpedo"),
-        AirToAir("Anti-Air"),
-        Armor("Armor"),
-        Engine("Engine"),
-        Radar("Radar"),
-        StrikeAircraft("Aircraft"),
-        InterceptAircraft("Aircraft (Intercept)"),
-        Crew("Crew"),
-        Charge("Charge"),
-        Special("Special"),
-        MegaCharge("Mega Charge"),
-        ManualTorpedo("Torpedo (Manual)"),
-        AntiSub("Aircraft (Anti-Sub)"),
-        HammerHead("Hammer Head"),
-        BomberPreCastAlert("Bomber Pre-Cast Alert"),
-        MultiLock("Multi-Lock"),
-        ManualSub("Anti-Sub (Manual)"),
-        AntiAir("Anti-Air"),
-        Bracketing("Main Gun (Bracketing)"),
-        Beam("Beam"),
-        DepthCharge("Depth Charge"),
-        AntiAirRepeater("Anti-Air (Repeater)"),
-        DisposableTorpedo("Torpedo (Disposable)"),
-        SpaceLaser("Space Laser"),
-        Missile("Missile??"),
-        AntiAirFuze("Anti-Air (Fuze)"),
-        ManualMissile("Missile (Manual)"),
-        AutoMissile("Missile (Auto)"),
-        Meteor("Meteor"),
-        Unknown("Unknown")
+        MainGun("Main Gun") = 0,
+        SubGun("Auto Gun") = 1,
+        Torpedo("Torpedo") = 2,
+        AirToAir("Anti-Air") = 3,
+        Armor("Armor") = 4,
+        Engine("Engine") = 5,
+        Radar("Radar") = 6,
+        StrikeAircraft("Aircraft") = 7,
+        InterceptAircraft("Aircraft (Intercept)") = 8,
+        Crew("Crew") = 9,
+        Charge("Charge") = 10,
+        Special("Special") = 11,
+        MegaCharge("Mega Charge") = 12,
+        ManualTorpedo("Torpedo (Manual)") = 13,
+        AntiSub("Aircraft (Anti-Sub)") = 14,
+        HammerHead("Hammer Head") = 15,
+        BomberPreCastAlert("Bomber Pre-Cast Alert") = 16,
+        MultiLock("Multi-Lock") = 17,
+        ManualSub("Anti-Sub (Manual)") = 18,
+        AntiAir("Anti-Air") = 19,
+        Bracketing("Main Gun (Bracketing)") = 20,
+        Beam("Beam") = 21,
+        DepthCharge("Depth Charge") = 22,
+        AntiAirRepeater("Anti-Air (Repeater)") = 23,
+        DisposableTorpedo("Torpedo (Disposable)") = 24,
+        SpaceLaser("Space Laser") = 25,
+        Missile("Missile??") = 26,
+        AntiAirFuze("Anti-Air (Fuze)") = 27,
+        ManualMissile("Missile (Manual)") = 28,
+        AutoMissile("Missile (Auto)") = 29,
+        Meteor("Meteor") = 30,
+        Unknown("Unknown") = 31
     }
 }
 
@@ -401,17 +448,17 @@ define_data_enum! {
         pub color_rgb: u32;
 
         /// 1* (Common)
-        N1(1, "N", 0xC0C0C0),
+        N1(1, "N", 0xC0C0C0) = 0,
         /// 2* (Common)
-        N2(2, "N", 0xC0C0C0),
+        N2(2, "N", 0xC0C0C0) = 1,
         /// 3* R (Rare)
-        R(3, "R", 0x9FE8FF),
+        R(3, "R", 0x9FE8FF) = 2,
         /// 4* E (Elite)
-        E(4, "E", 0xC4ADFF),
+        E(4, "E", 0xC4ADFF) = 3,
         /// 5* SR (Super Rare)
-        SR(5, "SR", 0xEDDD76),
+        SR(5, "SR", 0xEDDD76) = 4,
         /// 6* UR (Ultra Rare)
-        UR(6, "UR", 0xFF8D8D)
+        UR(6, "UR", 0xFF8D8D) = 5
     }
 }
 
@@ -425,11 +472,11 @@ define_data_enum! {
         pub color_rgb: u32;
 
         /// 2* R (Rare)
-        R(2, "R", 0x9FE8FF),
+        R(2, "R", 0x9FE8FF) = 0,
         /// 3* E (Elite)
-        E(3, "E", 0xC4ADFF),
+        E(3, "E", 0xC4ADFF) = 1,
         /// 4* SR (Super Rare)
-        SR(4, "SR", 0xEDDD76)
+        SR(4, "SR", 0xEDDD76) = 2
     }
 }
 
@@ -440,6 +487,12 @@ impl BulletExtra {
     }
 }
 
+impl IsEmpty for BulletExtra {
+    fn is_empty_value(&self) -> bool {
+        self.is_none()
+    }
+}
+
 impl BulletFlags {
     /// Filters to the flags that are relevant for the dive filter,
     /// i.e. which targets the bullet _can't_ hit.
@@ -449,6 +502,19 @@ impl BulletFlags {
     }
 }
 
+// bitflags serializes the flags as a string of their names, separated by `|`,
+// for human-readable formats. just describe this as a string.
+#[cfg(feature = "schema")]
+impl schemars::JsonSchema for BulletFlags {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "BulletFlags".into()
+    }
+
+    fn json_schema(generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        String::json_schema(generator)
+    }
+}
+
 impl AugmentUsability {
     /// If restricted by hull types, gets the hull types. Otherwise, returns
     /// [`None`].
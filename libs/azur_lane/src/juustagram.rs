@@ -1,6 +1,9 @@
 use serde::{Deserialize, Serialize};
 
+use crate::data_def::{is_default, skip_empty};
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Chat {
     // "id"
     pub chat_id: u32,
@@ -8,21 +11,49 @@ pub struct Chat {
     pub group_id: u32,
     pub name: String,
     pub unlock_desc: String,
+    // "trigger_type" & "trigger_param"
+    #[serde(default, skip_serializing_if = "skip_empty")]
+    pub unlock: Option<ChatUnlock>,
     // "content"
     pub entries: Vec<ChatEntry>,
 }
 
+/// The requirement to unlock a chat.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum ChatUnlock {
+    // trigger_type = 1
+    /// Reach a certain affinity with the ship.
+    Affinity {
+        // "trigger_param"
+        amount: u32,
+    },
+    // trigger_type = 2
+    /// Own a certain skin of the ship.
+    Skin {
+        // "trigger_param"
+        skin_id: u32,
+    },
+    /// Some other requirement. Refer to the chat's unlock description.
+    Other {
+        // "trigger_type"
+        kind: u32,
+    },
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ChatEntry {
     // "id"
     pub entry_id: u32,
     pub content: ChatContent,
     pub flag: u8,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "skip_empty")]
     pub options: Option<Vec<ChatOption>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum ChatContent {
     // type = 1
     Message {
@@ -45,7 +76,11 @@ pub enum ChatContent {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ChatOption {
     pub flag: u8,
     pub value: String,
+    /// The change in affinity when choosing this option.
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub favor: i32,
 }
@@ -4,33 +4,139 @@
 
 use serde::{Deserialize, Serialize};
 
+pub mod combat;
+pub mod commission;
 mod data_def;
+pub mod data_file;
 pub mod equip;
 pub mod juustagram;
 pub mod secretary;
 pub mod ship;
 pub mod skill;
+pub mod stage;
 
-use data_def::define_data_enum;
+use data_def::{define_data_enum, skip_empty, IsEmpty};
 
 /// Definition data to be saved/loaded in bulk.
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct DefinitionData {
     /// All known ships.
-    #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "skip_empty")]
     pub ships: Vec<ship::ShipData>,
     /// All known equips.
-    #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "skip_empty")]
     pub equips: Vec<equip::Equip>,
     /// All known augments.
-    #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "skip_empty")]
     pub augments: Vec<equip::Augment>,
     /// All known Juustagram chats.
-    #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "skip_empty")]
     pub juustagram_chats: Vec<juustagram::Chat>,
     /// All special secretary variants.
-    #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "skip_empty")]
     pub special_secretaries: Vec<secretary::SpecialSecretary>,
+    /// All known commissions.
+    #[serde(default, skip_serializing_if = "skip_empty")]
+    pub commissions: Vec<commission::Commission>,
+    /// All known stages.
+    #[serde(default, skip_serializing_if = "skip_empty")]
+    pub stages: Vec<stage::Stage>,
+    /// The EXP curve for ship levels.
+    #[serde(default, skip_serializing_if = "skip_empty")]
+    pub ship_levels: Vec<ship::ShipLevel>,
+}
+
+/// Names of an entity in the different game locales.
+///
+/// Only present for locales whose data was included in the dataset.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct LocalizedNames {
+    /// The name on the English server.
+    #[serde(default, skip_serializing_if = "skip_empty")]
+    pub en: Option<String>,
+    /// The name on the Japanese server.
+    #[serde(default, skip_serializing_if = "skip_empty")]
+    pub jp: Option<String>,
+    /// The name on the Chinese server.
+    #[serde(default, skip_serializing_if = "skip_empty")]
+    pub cn: Option<String>,
+}
+
+impl LocalizedNames {
+    /// Whether no localized name is known.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.en.is_none() && self.jp.is_none() && self.cn.is_none()
+    }
+
+    /// Gets the name for a specific locale, if known.
+    #[must_use]
+    pub fn get(&self, locale: Locale) -> Option<&str> {
+        match locale {
+            Locale::EN => self.en.as_deref(),
+            Locale::JP => self.jp.as_deref(),
+            Locale::CN => self.cn.as_deref(),
+        }
+    }
+
+    /// Sets the name for a specific locale.
+    pub fn set(&mut self, locale: Locale, name: String) {
+        *self.slot_mut(locale) = Some(name);
+    }
+
+    /// Fills in the names from `other` that aren't known yet.
+    pub fn add_missing(&mut self, other: Self) {
+        self.en = self.en.take().or(other.en);
+        self.jp = self.jp.take().or(other.jp);
+        self.cn = self.cn.take().or(other.cn);
+    }
+
+    /// Iterates over all known names and their locales.
+    pub fn iter(&self) -> impl Iterator<Item = (Locale, &str)> {
+        [Locale::EN, Locale::JP, Locale::CN]
+            .into_iter()
+            .filter_map(|l| Some((l, self.get(l)?)))
+    }
+
+    fn slot_mut(&mut self, locale: Locale) -> &mut Option<String> {
+        match locale {
+            Locale::EN => &mut self.en,
+            Locale::JP => &mut self.jp,
+            Locale::CN => &mut self.cn,
+        }
+    }
+}
+
+impl IsEmpty for LocalizedNames {
+    fn is_empty_value(&self) -> bool {
+        self.is_empty()
+    }
+}
+
+/// A reward, f.e. for a commission or a stage.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Reward {
+    /// The kind of reward.
+    pub kind: RewardKind,
+    /// The ID of the reward within its kind.
+    ///
+    /// For ships, this is the group ID.
+    pub id: u32,
+}
+
+define_data_enum! {
+    /// A game locale, matching a server region.
+    pub enum Locale for LocaleData {
+        /// The display name of the locale.
+        pub name: &'static str;
+
+        EN("English") = 0,
+        JP("Japanese") = 1,
+        CN("Chinese") = 2
+    }
 }
 
 define_data_enum! {
@@ -41,31 +147,46 @@ define_data_enum! {
         /// The prefix usually used by ships of the faction.
         pub prefix: Option<&'static str>;
 
-        Unknown("Unknown", None),
-        Universal("Universal", Some("UNIV")),
-        EagleUnion("Eagle Union", Some("USS")),
-        RoyalNavy("Royal Navy", Some("HMS")),
-        SakuraEmpire("Sakura Empire", Some("IJN")),
-        IronBlood("Iron Blood", Some("KMS")),
-        DragonEmpery("Dragon Empery", Some("ROC")),
-        SardegnaEmpire("Sardegna Empire", Some("RN")),
-        NorthernParliament("Northern Parliament", Some("SN")),
-        IrisLibre("Iris Libre", Some("FFNF")),
-        VichyaDominion("Vichya Dominion", Some("MNF")),
-        IrisOrthodoxy("Iris Orthodoxy", Some("NF")),
-        Tempesta("Tempesta", Some("MOT")),
-        Meta("META", Some("META")),
-        Siren("Siren", None),
-        CollabNeptunia("Neptunia", None),
-        CollabBilibili("Bilibili", None),
-        CollabUtawarerumono("Utawarerumono", None),
-        CollabKizunaAI("Kizuna AI", None),
-        CollabHololive("Hololive", None),
-        CollabVenusVacation("Venus Vacation", None),
-        CollabIdolmaster("Idolm@ster", None),
-        CollabSSSS("SSSS", None),
-        CollabAtelierRyza("Atelier Ryza", None),
-        CollabSenranKagura("Senran Kagura", None),
-        CollabToLoveRu("To LOVE-Ru", None)
+        Unknown("Unknown", None) = 0,
+        Universal("Universal", Some("UNIV")) = 1,
+        EagleUnion("Eagle Union", Some("USS")) = 2,
+        RoyalNavy("Royal Navy", Some("HMS")) = 3,
+        SakuraEmpire("Sakura Empire", Some("IJN")) = 4,
+        IronBlood("Iron Blood", Some("KMS")) = 5,
+        DragonEmpery("Dragon Empery", Some("ROC")) = 6,
+        SardegnaEmpire("Sardegna Empire", Some("RN")) = 7,
+        NorthernParliament("Northern Parliament", Some("SN")) = 8,
+        IrisLibre("Iris Libre", Some("FFNF")) = 9,
+        VichyaDominion("Vichya Dominion", Some("MNF")) = 10,
+        IrisOrthodoxy("Iris Orthodoxy", Some("NF")) = 11,
+        Tempesta("Tempesta", Some("MOT")) = 12,
+        Meta("META", Some("META")) = 13,
+        Siren("Siren", None) = 14,
+        CollabNeptunia("Neptunia", None) = 15,
+        CollabBilibili("Bilibili", None) = 16,
+        CollabUtawarerumono("Utawarerumono", None) = 17,
+        CollabKizunaAI("Kizuna AI", None) = 18,
+        CollabHololive("Hololive", None) = 19,
+        CollabVenusVacation("Venus Vacation", None) = 20,
+        CollabIdolmaster("Idolm@ster", None) = 21,
+        CollabSSSS("SSSS", None) = 22,
+        CollabAtelierRyza("Atelier Ryza", None) = 23,
+        CollabSenranKagura("Senran Kagura", None) = 24,
+        CollabToLoveRu("To LOVE-Ru", None) = 25
+    }
+}
+
+define_data_enum! {
+    /// The kind of a reward.
+    pub enum RewardKind for RewardKindData {
+        /// The display name of the kind.
+        pub name: &'static str;
+
+        Resource("Resource") = 0,
+        Item("Item") = 1,
+        Equip("Equipment") = 2,
+        Ship("Ship") = 3,
+        Furniture("Furniture") = 4,
+        Other("Other") = 5
     }
 }
@@ -5,17 +5,22 @@ use std::ops::{Add, AddAssign};
 
 use serde::{Deserialize, Serialize};
 
+use crate::data_def::skip_empty;
 use crate::equip::*;
 use crate::skill::*;
-use crate::{define_data_enum, Faction};
+use crate::{define_data_enum, Faction, LocalizedNames};
 
 /// Provides data for a singular ship or a retrofit.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ShipData {
     /// The group ID. This is the same for the base and its retrofits.
     pub group_id: u32,
     /// The ship's display name.
     pub name: String,
+    /// The display name in each known locale.
+    #[serde(default, skip_serializing_if = "skip_empty")]
+    pub names: LocalizedNames,
     /// The ship's rarity.
     ///
     /// For its star rating, see [`ShipData::stars`].
@@ -41,28 +46,109 @@ pub struct ShipData {
     /// Additional shadow or hidden equipment that's fixed to the ship.
     ///
     /// Most commonly, this is a secondary gun for torpedo CLs or CAs.
-    #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "skip_empty")]
     pub shadow_equip: Vec<ShadowEquip>,
     /// Default equipped depth charges.
-    #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "skip_empty")]
     pub depth_charges: Vec<Equip>,
+    /// The equipment the ship comes with when obtained.
+    #[serde(default, skip_serializing_if = "skip_empty")]
+    pub default_equips: Vec<DefaultEquip>,
     /// The list of skills. Excludes inactive or hidden skills.
     pub skills: Vec<Skill>,
+    /// The costs for each limit break, in order.
+    ///
+    /// This will be empty for retrofits.
+    #[serde(default, skip_serializing_if = "skip_empty")]
+    pub limit_break_costs: Vec<LimitBreakCost>,
+    /// The costs to fully enhance the ship, if known.
+    #[serde(default, skip_serializing_if = "skip_empty")]
+    pub enhance_cost: Option<EnhanceCost>,
     /// Available retrofits for this ship in their maxed-out state.
     ///
     /// As of now, only DDGs have "multiple" retrofits, with their vanguard
     /// and main fleet states being considered different ones.
-    #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
-    pub retrofits: Vec<ShipData>,
+    #[serde(default, skip_serializing_if = "skip_empty")]
+    pub retrofits: Vec<Self>,
     /// The ship's skins, including their default and all retrofit skins.
     ///
     /// This will be empty for nested retrofits. Access the base's skins.
-    #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "skip_empty")]
     pub skins: Vec<ShipSkin>,
 }
 
+/// The cost of a single limit break.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct LimitBreakCost {
+    /// The minimum level required.
+    pub level: u32,
+    /// The amount of coins needed.
+    pub coins: u32,
+    /// The amount of duplicate ships needed.
+    ///
+    /// For most ships, generic cubes may be used instead.
+    pub ships: u32,
+    /// Additional items needed.
+    #[serde(default, skip_serializing_if = "skip_empty")]
+    pub items: Vec<ItemCost>,
+}
+
+/// An amount of an item.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ItemCost {
+    /// The item's ID.
+    pub item_id: u32,
+    /// How many of the item are needed.
+    pub amount: u32,
+}
+
+/// The costs to fully enhance a ship.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum EnhanceCost {
+    /// Enhancement EXP needed per stat, gained by feeding other ships.
+    Normal(Vec<EnhanceStatExp>),
+    /// Research dev levels, gained by using blueprints.
+    Research(Vec<ResearchLevelCost>),
+}
+
+/// The enhancement EXP needed to max a single stat.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct EnhanceStatExp {
+    /// The enhanced stat.
+    pub stat_kind: StatKind,
+    /// The EXP needed to max the stat.
+    pub exp: u32,
+}
+
+/// The cost for a single research dev level.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ResearchLevelCost {
+    /// The minimum ship level required.
+    pub level: u32,
+    /// The blueprint EXP needed.
+    pub exp: u32,
+}
+
+/// The EXP curve data for a single ship level.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ShipLevel {
+    /// The level this applies to.
+    pub level: u32,
+    /// The EXP needed to reach the next level.
+    pub exp: u32,
+    /// The EXP needed to reach the next level for UR ships.
+    pub exp_ur: u32,
+}
+
 /// Provides stat block information for a ship.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ShipStatBlock {
     pub hp: ShipStat,
     pub armor: ShipArmor,
@@ -81,22 +167,59 @@ pub struct ShipStatBlock {
     pub amo: u32,
 }
 
+/// The parameters to compute a ship's stats with.
+///
+/// See [`ShipStatBlock::compute`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StatParams {
+    /// The ship's level.
+    ///
+    /// This is clamped to the level cap for the limit break state.
+    pub level: u32,
+    /// The ship's affinity.
+    pub affinity: Affinity,
+    /// The amount of limit breaks, from 0 to [`StatParams::MAX_LIMIT_BREAK`].
+    ///
+    /// For research and META ships, this is the equivalent stage of their
+    /// development.
+    pub limit_break: u8,
+}
+
+/// A ship's stats, computed for specific [`StatParams`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ComputedStats {
+    pub hp: f64,
+    pub rld: f64,
+    pub fp: f64,
+    pub trp: f64,
+    pub eva: f64,
+    pub aa: f64,
+    pub avi: f64,
+    pub acc: f64,
+    pub asw: f64,
+    pub spd: f64,
+    pub lck: f64,
+}
+
 /// Represents a single ship stat. Its value can be calculated on demand.
 #[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ShipStat(f64, f64, f64);
 
 /// A singular normal equipment slot of a ship.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct EquipSlot {
     /// Which kinds of equipment can be equipped in the slot.
     pub allowed: Vec<EquipKind>,
     /// If a weapon slot, the data for the mount.
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "skip_empty")]
     pub mount: Option<EquipWeaponMount>,
 }
 
 /// Mount information for an [`EquipSlot`].
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct EquipWeaponMount {
     /// The mount efficiency, as displayed in-game.
     pub efficiency: f64,
@@ -116,6 +239,7 @@ pub struct EquipWeaponMount {
 /// Provides information about "shadow" equipment; inherent gear that is not
 /// displayed in-game.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ShadowEquip {
     /// The name of the associated equipment.
     pub name: String,
@@ -125,8 +249,19 @@ pub struct ShadowEquip {
     pub weapons: Vec<Weapon>,
 }
 
+/// Equipment a ship comes with when obtained.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct DefaultEquip {
+    /// The index of the slot within [`ShipData::equip_slots`].
+    pub slot: u8,
+    /// The equipment itself.
+    pub equip: Equip,
+}
+
 /// Data for a ship skin. This may represent the default skin.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ShipSkin {
     /// The skin's ID. [`ShipData::skin_by_id`] searches for this.
     pub skin_id: u32,
@@ -142,73 +277,87 @@ pub struct ShipSkin {
     /// The default dialogue lines.
     pub words: ShipSkinWords,
     /// Replacement dialogue lines, usually after oath.
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "skip_empty")]
     pub words_extra: Option<Box<ShipSkinWords>>,
+    /// The names of the alternative expressions available for the skin.
+    ///
+    /// Only filled if the collector had access to the game assets. When
+    /// extracted, the images are stored in a directory named after
+    /// [`Self::image_key`], with each file named after the face.
+    #[serde(default, skip_serializing_if = "skip_empty")]
+    pub faces: Vec<String>,
 }
 
 /// The block of dialogue for a given skin.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ShipSkinWords {
+    /// The key for the voice line audio, if the lines are voiced.
+    ///
+    /// The collector extracts the audio clips into a directory of this name,
+    /// with each clip named after the game's key for the line.
+    #[serde(default, skip_serializing_if = "skip_empty")]
+    pub voice_key: Option<String>,
     /// The skin's description.
     ///
     /// Note that [`ShipSkin::description`] originates from the skin's template,
     /// whereas this field is actually part of the skin's words.
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "skip_empty")]
     pub description: Option<String>,
     /// The "introduction". In-game, this is the profile text in the archive.
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "skip_empty")]
     pub introduction: Option<String>,
     /// Dialogue played when the ship is obtained.
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "skip_empty")]
     pub acquisition: Option<String>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "skip_empty")]
     pub login: Option<String>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "skip_empty")]
     pub details: Option<String>,
-    #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "skip_empty")]
     pub main_screen: Vec<ShipMainScreenLine>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "skip_empty")]
     pub touch: Option<String>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "skip_empty")]
     pub special_touch: Option<String>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "skip_empty")]
     pub rub: Option<String>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "skip_empty")]
     pub mission_reminder: Option<String>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "skip_empty")]
     pub mission_complete: Option<String>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "skip_empty")]
     pub mail_reminder: Option<String>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "skip_empty")]
     pub return_to_port: Option<String>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "skip_empty")]
     pub commission_complete: Option<String>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "skip_empty")]
     pub enhance: Option<String>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "skip_empty")]
     pub flagship_fight: Option<String>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "skip_empty")]
     pub victory: Option<String>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "skip_empty")]
     pub defeat: Option<String>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "skip_empty")]
     pub skill: Option<String>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "skip_empty")]
     pub low_health: Option<String>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "skip_empty")]
     pub disappointed: Option<String>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "skip_empty")]
     pub stranger: Option<String>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "skip_empty")]
     pub friendly: Option<String>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "skip_empty")]
     pub crush: Option<String>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "skip_empty")]
     pub love: Option<String>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "skip_empty")]
     pub oath: Option<String>,
     /// Voices lines that may be played when sortieing other specific ships.
-    #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "skip_empty")]
     pub couple_encourage: Vec<ShipCoupleEncourage>,
 }
 
@@ -216,11 +365,13 @@ pub struct ShipSkinWords {
 ///
 /// Also see [`ShipSkinWords::main_screen`].
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ShipMainScreenLine(usize, String);
 
 /// Data for voices lines that may be played when sortieing other specific
 /// ships.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ShipCoupleEncourage {
     /// The line to be played.
     pub line: String,
@@ -232,6 +383,7 @@ pub struct ShipCoupleEncourage {
 
 /// Condition for [`ShipCoupleEncourage`].
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum ShipCouple {
     /// Triggered when other specific ships are present.
     /// Holds a vector of ship group IDs.
@@ -258,20 +410,21 @@ define_data_enum! {
         pub color_rgb: u32;
 
         /// N (Common)
-        N("N", 0xC0C0C0),
+        N("N", 0xC0C0C0) = 0,
         /// R (Rare)
-        R("R", 0x9FE8FF),
+        R("R", 0x9FE8FF) = 1,
         /// E (Elite)
-        E("E", 0xC4ADFF),
+        E("E", 0xC4ADFF) = 2,
         /// SR (Super Rare) / Priority
-        SR("SR", 0xEDDD76),
+        SR("SR", 0xEDDD76) = 3,
         /// UR (Ultra Rare) / Decisive
-        UR("UR", 0xFF8D8D)
+        UR("UR", 0xFF8D8D) = 4
     }
 }
 
 /// The enhancement mode kind.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum EnhanceKind {
     /// Normal. Enhancement by feeding spare duplicate ships.
     #[default]
@@ -290,17 +443,17 @@ define_data_enum! {
         /// The in-game display name.
         pub name: &'static str;
 
-        HP("HP"),
-        RLD("RLD"),
-        FP("FP"),
-        TRP("TRP"),
-        EVA("EVA"),
-        AA("AA"),
-        AVI("AVI"),
-        ACC("ACC"),
-        ASW("ASW"),
-        SPD("SPD"),
-        LCK("LCK")
+        HP("HP") = 0,
+        RLD("RLD") = 1,
+        FP("FP") = 2,
+        TRP("TRP") = 3,
+        EVA("EVA") = 4,
+        AA("AA") = 5,
+        AVI("AVI") = 6,
+        ACC("ACC") = 7,
+        ASW("ASW") = 8,
+        SPD("SPD") = 9,
+        LCK("LCK") = 10
     }
 }
 
@@ -314,26 +467,26 @@ define_data_enum! {
         /// Which team type this hull type gets sortied in.
         pub team_type: TeamType;
 
-        Unknown("??", "Unknown", TeamType::Vanguard),
-        Destroyer("DD", "Destroyer", TeamType::Vanguard),
-        LightCruiser("CL", "Light Cruiser", TeamType::Vanguard),
-        HeavyCruiser("CA", "Heavy Cruiser", TeamType::Vanguard),
-        Battlecruiser("BC", "Battlecruiser", TeamType::MainFleet),
-        Battleship("BB", "Battleship", TeamType::MainFleet),
-        LightCarrier("CVL", "Light Carrier", TeamType::MainFleet),
-        AircraftCarrier("CV", "Aircraft Carrier", TeamType::MainFleet),
-        Submarine("SS", "Submarine", TeamType::Submarine),
-        AviationBattleship("BBV", "Aviation Battleship", TeamType::MainFleet),
-        RepairShip("AR", "Repair Ship", TeamType::MainFleet),
-        Monitor("BM", "Monitor", TeamType::MainFleet),
-        AviationSubmarine("SSV", "Aviation Submarine", TeamType::Submarine),
-        LargeCruiser("CB", "Large Cruiser", TeamType::Vanguard),
-        MunitionShip("AE", "Munition Ship", TeamType::Vanguard),
-        MissileDestroyerV("DDGv", "Missile Destroyer V", TeamType::Vanguard),
-        MissileDestroyerM("DDGm", "Missile Destroyer M", TeamType::MainFleet),
-        FrigateS("IXs", "Sailing Frigate S", TeamType::Submarine),
-        FrigateV("IXv", "Sailing Frigate V", TeamType::Vanguard),
-        FrigateM("IXm", "Sailing Frigate M", TeamType::MainFleet)
+        Unknown("??", "Unknown", TeamType::Vanguard) = 0,
+        Destroyer("DD", "Destroyer", TeamType::Vanguard) = 1,
+        LightCruiser("CL", "Light Cruiser", TeamType::Vanguard) = 2,
+        HeavyCruiser("CA", "Heavy Cruiser", TeamType::Vanguard) = 3,
+        Battlecruiser("BC", "Battlecruiser", TeamType::MainFleet) = 4,
+        Battleship("BB", "Battleship", TeamType::MainFleet) = 5,
+        LightCarrier("CVL", "Light Carrier", TeamType::MainFleet) = 6,
+        AircraftCarrier("CV", "Aircraft Carrier", TeamType::MainFleet) = 7,
+        Submarine("SS", "Submarine", TeamType::Submarine) = 8,
+        AviationBattleship("BBV", "Aviation Battleship", TeamType::MainFleet) = 9,
+        RepairShip("AR", "Repair Ship", TeamType::MainFleet) = 10,
+        Monitor("BM", "Monitor", TeamType::MainFleet) = 11,
+        AviationSubmarine("SSV", "Aviation Submarine", TeamType::Submarine) = 12,
+        LargeCruiser("CB", "Large Cruiser", TeamType::Vanguard) = 13,
+        MunitionShip("AE", "Munition Ship", TeamType::Vanguard) = 14,
+        MissileDestroyerV("DDGv", "Missile Destroyer V", TeamType::Vanguard) = 15,
+        MissileDestroyerM("DDGm", "Missile Destroyer M", TeamType::MainFleet) = 16,
+        FrigateS("IXs", "Sailing Frigate S", TeamType::Submarine) = 17,
+        FrigateV("IXv", "Sailing Frigate V", TeamType::Vanguard) = 18,
+        FrigateM("IXm", "Sailing Frigate M", TeamType::MainFleet) = 19
     }
 }
 
@@ -343,9 +496,28 @@ define_data_enum! {
         /// The display name for the armor type.
         pub name: &'static str;
 
-        Light("Light"),
-        Medium("Medium"),
-        Heavy("Heavy")
+        Light("Light") = 0,
+        Medium("Medium") = 1,
+        Heavy("Heavy") = 2
+    }
+}
+
+define_data_enum! {
+    /// The affinity states that affect a ship's stats.
+    pub enum Affinity for AffinityData {
+        /// The display name for the affinity state.
+        pub name: &'static str,
+        /// The multiplier applied to stats affected by affinity.
+        pub stat_mult: f64;
+
+        /// Disappointed or Stranger. No stat bonus.
+        Neutral("Neutral", 1.0) = 0,
+        Friendly("Friendly", 1.01) = 1,
+        Crush("Crush", 1.03) = 2,
+        /// Love, at 100 affinity.
+        Love("Love", 1.06) = 3,
+        /// Oath, at the maximum of 200 affinity.
+        Oath("Oath", 1.12) = 4
     }
 }
 
@@ -355,9 +527,9 @@ define_data_enum! {
         /// The display name for the team type.
         pub name: &'static str;
 
-        Vanguard("Vanguard"),
-        MainFleet("Main Fleet"),
-        Submarine("Submarine")
+        Vanguard("Vanguard") = 0,
+        MainFleet("Main Fleet") = 1,
+        Submarine("Submarine") = 2
     }
 }
 
@@ -377,7 +549,136 @@ impl ShipData {
     }
 }
 
+impl ShipLevel {
+    /// Gets the EXP needed to reach the next level for a ship of the given
+    /// rarity.
+    #[must_use]
+    pub fn exp_for(&self, rarity: ShipRarity) -> u32 {
+        match rarity {
+            ShipRarity::UR => self.exp_ur,
+            _ => self.exp,
+        }
+    }
+
+    /// Calculates the total EXP needed to go from one level to another.
+    ///
+    /// `levels` should be the full level table, such as
+    /// [`DefinitionData::ship_levels`](crate::DefinitionData::ship_levels).
+    /// Returns [`None`] if the table doesn't contain all needed levels.
+    #[must_use]
+    pub fn total_exp(levels: &[Self], rarity: ShipRarity, from: u32, to: u32) -> Option<u64> {
+        (from..to)
+            .map(|level| {
+                levels
+                    .iter()
+                    .find(|l| l.level == level)
+                    .map(|l| u64::from(l.exp_for(rarity)))
+            })
+            .sum()
+    }
+}
+
+impl StatParams {
+    /// The maximum amount of limit breaks.
+    pub const MAX_LIMIT_BREAK: u8 = 3;
+
+    /// Creates parameters for a fully limit broken ship.
+    #[must_use]
+    pub const fn new(level: u32, affinity: Affinity) -> Self {
+        Self {
+            level,
+            affinity,
+            limit_break: Self::MAX_LIMIT_BREAK,
+        }
+    }
+
+    /// Sets the amount of limit breaks.
+    #[must_use]
+    pub const fn with_limit_break(mut self, limit_break: u8) -> Self {
+        self.limit_break = limit_break;
+        self
+    }
+
+    /// The maximum level reachable with the limit break state.
+    #[must_use]
+    pub const fn level_cap(&self) -> u32 {
+        match self.limit_break {
+            0 => 70,
+            1 => 80,
+            2 => 90,
+            _ => 125,
+        }
+    }
+
+    /// The level used for the computation, clamped to the valid range.
+    #[must_use]
+    pub fn effective_level(&self) -> u32 {
+        self.level.clamp(1, self.level_cap())
+    }
+}
+
+impl ComputedStats {
+    /// Gets a certain stat value.
+    #[must_use]
+    pub fn get(&self, kind: StatKind) -> f64 {
+        match kind {
+            StatKind::HP => self.hp,
+            StatKind::RLD => self.rld,
+            StatKind::FP => self.fp,
+            StatKind::TRP => self.trp,
+            StatKind::EVA => self.eva,
+            StatKind::AA => self.aa,
+            StatKind::AVI => self.avi,
+            StatKind::ACC => self.acc,
+            StatKind::ASW => self.asw,
+            StatKind::SPD => self.spd,
+            StatKind::LCK => self.lck,
+        }
+    }
+
+    /// Gets a mutable reference to a certain stat value.
+    pub fn get_mut(&mut self, kind: StatKind) -> &mut f64 {
+        match kind {
+            StatKind::HP => &mut self.hp,
+            StatKind::RLD => &mut self.rld,
+            StatKind::FP => &mut self.fp,
+            StatKind::TRP => &mut self.trp,
+            StatKind::EVA => &mut self.eva,
+            StatKind::AA => &mut self.aa,
+            StatKind::AVI => &mut self.avi,
+            StatKind::ACC => &mut self.acc,
+            StatKind::ASW => &mut self.asw,
+            StatKind::SPD => &mut self.spd,
+            StatKind::LCK => &mut self.lck,
+        }
+    }
+}
+
 impl ShipStatBlock {
+    /// Computes all stat values for the given parameters.
+    ///
+    /// Refer to [`ShipStat::calc`] for potential caveats. In particular, stats
+    /// from limit breaks are always included, so the limit break state only
+    /// affects the level cap.
+    #[must_use]
+    pub fn compute(&self, params: StatParams) -> ComputedStats {
+        let level = params.effective_level();
+        let affinity = params.affinity.stat_mult();
+        ComputedStats {
+            hp: self.hp.calc(level, affinity),
+            rld: self.rld.calc(level, affinity),
+            fp: self.fp.calc(level, affinity),
+            trp: self.trp.calc(level, affinity),
+            eva: self.eva.calc(level, affinity),
+            aa: self.aa.calc(level, affinity),
+            avi: self.avi.calc(level, affinity),
+            acc: self.acc.calc(level, affinity),
+            asw: self.asw.calc(level, affinity),
+            spd: self.spd,
+            lck: self.lck,
+        }
+    }
+
     /// Gets and calculates a certain stat value.
     ///
     /// Refer to [`ShipStat::calc`] for potential caveats.
@@ -505,3 +806,126 @@ impl ShipRarity {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Asserts that two values are equal, allowing for rounding errors.
+    fn assert_near(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-6,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    /// A stat block shaped like a battleship's, with values in the same units
+    /// as the game data: growth is per 1000 levels.
+    fn stat_block() -> ShipStatBlock {
+        let stat = |base, growth, fixed| {
+            ShipStat::new()
+                .with_base(base)
+                .with_growth(growth)
+                .with_fixed(fixed)
+        };
+
+        ShipStatBlock {
+            hp: stat(1500.0, 60000.0, 250.0),
+            armor: ShipArmor::Heavy,
+            rld: stat(60.0, 1500.0, 0.0),
+            fp: stat(80.0, 3500.0, 45.0),
+            trp: ShipStat::new(),
+            eva: stat(10.0, 200.0, 0.0),
+            aa: stat(40.0, 1800.0, 25.0),
+            avi: ShipStat::new(),
+            acc: stat(30.0, 350.0, 0.0),
+            asw: ShipStat::new(),
+            spd: 24.0,
+            lck: 40.0,
+            cost: 16,
+            oxy: 0,
+            amo: 4,
+        }
+    }
+
+    #[test]
+    fn affinity_mults() {
+        assert_near(Affinity::Neutral.stat_mult(), 1.0);
+        assert_near(Affinity::Friendly.stat_mult(), 1.01);
+        assert_near(Affinity::Crush.stat_mult(), 1.03);
+        assert_near(Affinity::Love.stat_mult(), 1.06);
+        assert_near(Affinity::Oath.stat_mult(), 1.12);
+    }
+
+    #[test]
+    fn stat_calc() {
+        let hp = stat_block().hp;
+
+        // level 1 only has the base value
+        assert_near(hp.calc(1, 1.0), 1750.0);
+
+        // (1500 + 60000 * 119 / 1000) * 1.0 + 250
+        assert_near(hp.calc(120, 1.0), 8890.0);
+
+        // the fixed part isn't affected by affinity
+        // (1500 + 60000 * 119 / 1000) * 1.12 + 250
+        assert_near(hp.calc(120, 1.12), 9926.8);
+    }
+
+    #[test]
+    fn level_caps() {
+        let params = StatParams::new(125, Affinity::Love);
+        assert_eq!(params.level_cap(), 125);
+
+        let caps: Vec<u32> = (0..=StatParams::MAX_LIMIT_BREAK)
+            .map(|lb| params.with_limit_break(lb).level_cap())
+            .collect();
+        assert_eq!(caps, [70, 80, 90, 125]);
+
+        assert_eq!(params.with_limit_break(0).effective_level(), 70);
+        assert_eq!(StatParams::new(0, Affinity::Love).effective_level(), 1);
+        assert_eq!(StatParams::new(200, Affinity::Love).effective_level(), 125);
+    }
+
+    #[test]
+    fn compute_stats() {
+        let block = stat_block();
+        let stats = block.compute(StatParams::new(120, Affinity::Love));
+
+        // (80 + 3500 * 119 / 1000) * 1.06 + 45
+        assert_near(stats.fp, 571.29);
+        // (60 + 1500 * 119 / 1000) * 1.06
+        assert_near(stats.rld, 252.81);
+        // (30 + 350 * 119 / 1000) * 1.06
+        assert_near(stats.acc, 75.949);
+
+        // speed and luck don't scale
+        assert_near(stats.spd, 24.0);
+        assert_near(stats.lck, 40.0);
+        assert_near(stats.trp, 0.0);
+    }
+
+    #[test]
+    fn compute_caps_level_by_limit_break() {
+        let block = stat_block();
+        let params = StatParams::new(120, Affinity::Neutral).with_limit_break(1);
+
+        // only goes up to level 80 without further limit breaks
+        let stats = block.compute(params);
+        assert_near(stats.hp, block.hp.calc(80, 1.0));
+        // (1500 + 60000 * 79 / 1000) + 250
+        assert_near(stats.hp, 6490.0);
+    }
+
+    #[test]
+    fn calc_stat_matches_compute() {
+        let block = stat_block();
+        let params = StatParams::new(110, Affinity::Oath);
+        let stats = block.compute(params);
+
+        for &kind in StatKind::ALL {
+            let value = block.calc_stat(kind, 110, Affinity::Oath.stat_mult());
+            assert_near(stats.get(kind), value);
+        }
+    }
+}
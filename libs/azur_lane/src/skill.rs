@@ -2,11 +2,13 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::data_def::skip_empty;
 use crate::define_data_enum;
 use crate::equip::Weapon;
 
 /// Represents a single skill.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Skill {
     /// The skill's ID.
     ///
@@ -19,16 +21,20 @@ pub struct Skill {
     pub description: String,
     /// The category of this skill.
     pub category: SkillCategory,
+    /// The key of the skill's icon, if known.
+    #[serde(default, skip_serializing_if = "skip_empty")]
+    pub icon: Option<String>,
     /// Barrages this skill can fire.
-    #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "skip_empty")]
     pub barrages: Vec<SkillBarrage>,
     /// Weapons this skill may add to the ship.
-    #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "skip_empty")]
     pub new_weapons: Vec<BuffWeapon>,
 }
 
 /// Represents a skill barrage.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct SkillBarrage {
     /// The ID of the skill that fires this barrage.
     pub skill_id: u32,
@@ -38,6 +44,7 @@ pub struct SkillBarrage {
 
 /// Represents a skill barrage's attack.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct SkillAttack {
     /// The target this attack fires at.
     pub target: SkillAttackTarget,
@@ -47,6 +54,7 @@ pub struct SkillAttack {
 
 /// Represents a buff's bonus weapon.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct BuffWeapon {
     /// How long this weapon lasts.
     ///
@@ -64,11 +72,11 @@ define_data_enum! {
         /// A short-hand name.
         pub short_name: &'static str;
 
-        Random("Random", "Rand."),
-        PriorityTarget("Priority Target", "Prio."),
-        Nearest("Nearest", "Near."),
-        Farthest("Farthest", "Far."),
-        Fixed("Fixed", "Fix.")
+        Random("Random", "Rand.") = 0,
+        PriorityTarget("Priority Target", "Prio.") = 1,
+        Nearest("Nearest", "Near.") = 2,
+        Farthest("Farthest", "Far.") = 3,
+        Fixed("Fixed", "Fix.") = 4
     }
 }
 
@@ -82,14 +90,15 @@ define_data_enum! {
         /// An emoji for the category.
         pub emoji: char;
 
-        Offense("Offense", 0xDD2E44, '🟥'),
-        Defense("Defense", 0x55ACEE, '🟦'),
-        Support("Support", 0xFDCB58, '🟨')
+        Offense("Offense", 0xDD2E44, '🟥') = 0,
+        Defense("Defense", 0x55ACEE, '🟦') = 1,
+        Support("Support", 0xFDCB58, '🟨') = 2
     }
 }
 
 /// Represents basic information about a buff, to be extended later if needed.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct BuffInfo {
     pub buff_id: u32,
     pub probability: f64,
@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+
+use crate::data_def::skip_empty;
+use crate::{Reward, RewardKind};
+
+/// A stage within a chapter.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Stage {
+    // "id"
+    pub stage_id: u32,
+    /// The short code, f.e. `3-4`.
+    // "chapter_name"
+    pub code: String,
+    pub name: String,
+    /// The stage that needs to be cleared to unlock this one.
+    // "pre_chapter"
+    #[serde(default, skip_serializing_if = "skip_empty")]
+    pub required_stage_id: Option<u32>,
+    /// The minimum commander level.
+    // "unlocklevel"
+    pub commander_level: u32,
+    /// The possible drops.
+    // "awards"
+    #[serde(default, skip_serializing_if = "skip_empty")]
+    pub drops: Vec<Reward>,
+}
+
+impl Stage {
+    /// Whether the ship with the given group ID drops in this stage.
+    #[must_use]
+    pub fn drops_ship(&self, group_id: u32) -> bool {
+        self.drops_reward(RewardKind::Ship, group_id)
+    }
+
+    /// Whether the equipment with the given ID drops in this stage.
+    #[must_use]
+    pub fn drops_equip(&self, equip_id: u32) -> bool {
+        self.drops_reward(RewardKind::Equip, equip_id)
+    }
+
+    fn drops_reward(&self, kind: RewardKind, id: u32) -> bool {
+        self.drops.iter().any(|d| d.kind == kind && d.id == id)
+    }
+}
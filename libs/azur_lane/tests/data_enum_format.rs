@@ -0,0 +1,57 @@
+#![allow(unused_crate_dependencies)]
+use azur_lane::equip::*;
+use azur_lane::ship::*;
+use azur_lane::skill::*;
+use azur_lane::{Faction, Locale, RewardKind};
+use serde::{Deserialize, Serialize};
+
+const ENUMS_JSON: &str = include_str!("assets/enums.json");
+const ENUMS_STEPH: &[u8] = include_bytes!("assets/enums.steph");
+
+/// Every variant of every data enum, as present when the fixtures were created.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Enums {
+    faction: Vec<Faction>,
+    locale: Vec<Locale>,
+    reward_kind: Vec<RewardKind>,
+    ship_rarity: Vec<ShipRarity>,
+    stat_kind: Vec<StatKind>,
+    hull_type: Vec<HullType>,
+    team_type: Vec<TeamType>,
+    ship_armor: Vec<ShipArmor>,
+    affinity: Vec<Affinity>,
+    equip_kind: Vec<EquipKind>,
+    equip_rarity: Vec<EquipRarity>,
+    augment_rarity: Vec<AugmentRarity>,
+    weapon_kind: Vec<WeaponKind>,
+    bullet_kind: Vec<BulletKind>,
+    ammo_kind: Vec<AmmoKind>,
+    skill_category: Vec<SkillCategory>,
+    skill_attack_target: Vec<SkillAttackTarget>,
+}
+
+#[test]
+fn json_round_trip() {
+    let enums: Enums = serde_json::from_str(ENUMS_JSON).expect("must load json fixture");
+    let json = serde_json::to_string_pretty(&enums).expect("must serialize to json");
+    assert_eq!(
+        json.trim_end(),
+        ENUMS_JSON.trim_end(),
+        "json must match the fixture"
+    );
+}
+
+#[test]
+fn steph_round_trip() {
+    let enums: Enums = serde_steph::from_slice(ENUMS_STEPH).expect("must load steph fixture");
+    let steph = serde_steph::to_vec(&enums).expect("must serialize to steph");
+    assert_eq!(steph, ENUMS_STEPH, "steph must match the fixture");
+}
+
+#[test]
+fn json_matches_steph() {
+    let json: Enums = serde_json::from_str(ENUMS_JSON).expect("must load json fixture");
+    let steph: Enums = serde_steph::from_slice(ENUMS_STEPH).expect("must load steph fixture");
+    assert_eq!(json, steph, "names and ids must refer to the same variants");
+}
+
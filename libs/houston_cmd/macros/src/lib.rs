@@ -6,6 +6,7 @@ mod args;
 mod chat_command_impl;
 mod choice_arg_impl;
 mod context_command_impl;
+mod modal_fields_impl;
 mod util;
 
 /// Turns a function into a chat command or a module into a chat command group.
@@ -49,3 +50,12 @@ pub fn derive_choice_arg(input: StdTokenStream) -> StdTokenStream {
         .unwrap_or_else(|e| e.into_compile_error())
         .into()
 }
+
+/// Derives [`ModalFields`] for a struct.
+#[proc_macro_derive(ModalFields, attributes(modal))]
+pub fn derive_modal_fields(input: StdTokenStream) -> StdTokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+    modal_fields_impl::entry_point(input)
+        .unwrap_or_else(|e| e.into_compile_error())
+        .into()
+}
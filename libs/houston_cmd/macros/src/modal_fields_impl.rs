@@ -0,0 +1,136 @@
+use darling::FromField;
+use proc_macro2::TokenStream;
+use syn::ext::IdentExt;
+use syn::{Data, Fields, GenericArgument, PathArguments, Type};
+
+#[derive(Debug, darling::FromField)]
+#[darling(attributes(modal))]
+struct FieldArgs {
+    ident: Option<syn::Ident>,
+    ty: Type,
+    custom_id: Option<String>,
+    #[darling(default)]
+    default: bool,
+    parse_with: Option<syn::Path>,
+    min_length: Option<usize>,
+    max_length: Option<usize>,
+}
+
+pub fn entry_point(input: syn::DeriveInput) -> syn::Result<TokenStream> {
+    let Data::Struct(data) = input.data else {
+        return Err(syn::Error::new_spanned(
+            input,
+            "modal fields must be structs",
+        ));
+    };
+
+    let Fields::Named(fields) = data.fields else {
+        return Err(syn::Error::new_spanned(
+            data.fields,
+            "modal fields must have named fields",
+        ));
+    };
+
+    let mut idents = Vec::new();
+    let mut values = Vec::new();
+
+    for field in &fields.named {
+        let args = FieldArgs::from_field(field)?;
+        let ident = args.ident.expect("named fields have idents");
+
+        let custom_id = args.custom_id.unwrap_or_else(|| ident.unraw().to_string());
+
+        let optional_inner = option_inner(&args.ty);
+        let value_ty = optional_inner.unwrap_or(&args.ty);
+
+        let check_length = if args.min_length.is_some() || args.max_length.is_some() {
+            let min = args.min_length.unwrap_or(0);
+            let max = args.max_length.unwrap_or(usize::MAX);
+            quote::quote! {
+                ::houston_cmd::private::check_modal_length(#custom_id, value, #min, #max)?;
+            }
+        } else {
+            TokenStream::new()
+        };
+
+        let parse = match &args.parse_with {
+            Some(parse_with) => quote::quote! {
+                ::houston_cmd::private::map_modal_parse(#custom_id, #parse_with(value))?
+            },
+            None => quote::quote! {
+                ::houston_cmd::private::map_modal_parse(
+                    #custom_id,
+                    <#value_ty as ::std::str::FromStr>::from_str(value),
+                )?
+            },
+        };
+
+        let (present, missing) = if optional_inner.is_some() {
+            (
+                quote::quote! { ::std::option::Option::Some(#parse) },
+                quote::quote! { ::std::option::Option::None },
+            )
+        } else if args.default {
+            (parse, quote::quote! { ::std::default::Default::default() })
+        } else {
+            (
+                parse,
+                quote::quote! {
+                    return ::std::result::Result::Err(::houston_cmd::ModalFieldError::Missing {
+                        custom_id: #custom_id,
+                    })
+                },
+            )
+        };
+
+        idents.push(ident);
+        values.push(quote::quote! {
+            match ::houston_cmd::find_modal_value(data, #custom_id) {
+                ::std::option::Option::Some(value) => {
+                    #check_length
+                    #present
+                },
+                ::std::option::Option::None => #missing,
+            }
+        });
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote::quote! {
+        #[automatically_derived]
+        impl #impl_generics ::houston_cmd::ModalFields for #ident #ty_generics #where_clause {
+            fn from_modal(
+                data: &::houston_cmd::private::serenity::ModalInteractionData,
+            ) -> ::std::result::Result<Self, ::houston_cmd::ModalFieldError> {
+                ::std::result::Result::Ok(Self {
+                    #(
+                        #idents: #values,
+                    )*
+                })
+            }
+        }
+    })
+}
+
+/// Gets the inner type if `ty` is an [`Option`].
+fn option_inner(ty: &Type) -> Option<&Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+
+    let segment = path.path.segments.last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+
+    match args.args.first()? {
+        GenericArgument::Type(inner) if args.args.len() == 1 => Some(inner),
+        _ => None,
+    }
+}
@@ -14,6 +14,7 @@ use crate::model::{Command, CommandOptionData, Invoke, SubCommandData};
 use crate::BoxFuture;
 
 type PreCommandFn = fn(Context<'_>) -> BoxFuture<'_, ()>;
+type PostCommandFn = fn(Context<'_>, bool) -> BoxFuture<'_, ()>;
 type OnErrorFn = fn(Error<'_>) -> BoxFuture<'_, ()>;
 
 /// The command framework itself.
@@ -25,6 +26,7 @@ type OnErrorFn = fn(Error<'_>) -> BoxFuture<'_, ()>;
 pub struct Framework {
     commands: HashMap<Cow<'static, str>, Command>,
     pre_command: Option<PreCommandFn>,
+    post_command: Option<PostCommandFn>,
     on_error: Option<OnErrorFn>,
     auto_register: AtomicBool,
 }
@@ -77,6 +79,16 @@ impl Framework {
         self
     }
 
+    /// Sets a function to call after every command invocation.
+    ///
+    /// The second argument is whether the command succeeded. This is called
+    /// before the error handler.
+    #[must_use]
+    pub fn post_command(mut self, post_command: PostCommandFn) -> Self {
+        self.post_command = Some(post_command);
+        self
+    }
+
     /// Sets the error handler function.
     #[must_use]
     pub fn on_error(mut self, on_error: OnErrorFn) -> Self {
@@ -139,7 +151,13 @@ impl Framework {
         };
 
         ctx.options = &options;
-        if let Err(why) = self.run_command_or(ctx, command).await {
+        let res = self.run_command_or(ctx, command).await;
+
+        if let Some(post_command) = self.post_command {
+            post_command(ctx, res.is_ok()).await;
+        }
+
+        if let Err(why) = res {
             self.handle_error(why).await;
         }
     }
@@ -134,6 +134,7 @@ mod context;
 mod error;
 mod framework;
 mod macros;
+mod modal;
 pub mod model;
 #[doc(hidden)]
 pub mod private;
@@ -144,6 +145,7 @@ pub use args::{ChoiceArg, MessageContextArg, SlashArg, UserContextArg};
 pub use context::Context;
 pub use error::Error;
 pub use framework::Framework;
+pub use modal::{find_modal_value, ModalFieldError, ModalFields};
 pub use reply::{CreateReply, EditReply, ReplyHandle};
 
 pub type BoxFuture<'a, T> = serenity::futures::future::BoxFuture<'a, T>;
@@ -0,0 +1,80 @@
+/// Derives [`ModalFields`] for a struct with named fields.
+///
+/// Every field is read from the text input with the same custom ID as the
+/// field's name and parsed via [`FromStr`](std::str::FromStr). Values are
+/// trimmed and empty inputs are treated as missing. Missing inputs are an
+/// error, unless the field is an [`Option`] or has the `default` attribute.
+///
+/// Fields can have the following attributes applied via `#[modal(...)]`:
+///
+/// | Name                      | Meaning |
+/// |:------------------------- |:------- |
+/// | `custom_id`               | The custom ID of the text input, if it differs from the field name. |
+/// | `default`                 | Uses [`Default::default`] when the input is missing. |
+/// | `parse_with`              | The path to a function to parse the value with instead of `FromStr`. |
+/// | `min_length`/`max_length` | Limits to the length of the value, in characters. |
+///
+/// A `parse_with` function takes a `&str` and returns a [`Result`] with any
+/// error that can be converted into an [`anyhow::Error`].
+pub use ::houston_cmd_macros::ModalFields;
+use serenity::model::prelude::*;
+
+/// Enables a type to be parsed from the text inputs of a submitted modal.
+///
+/// Prefer using the [derive macro](macro@ModalFields) over implementing this
+/// manually.
+pub trait ModalFields: Sized {
+    /// Parses the fields from the modal data.
+    fn from_modal(data: &ModalInteractionData) -> Result<Self, ModalFieldError>;
+}
+
+/// An error that can occur when parsing [`ModalFields`].
+#[derive(Debug, thiserror::Error)]
+pub enum ModalFieldError {
+    /// A required input was missing or empty.
+    #[error("field `{custom_id}` is required")]
+    Missing { custom_id: &'static str },
+    /// The input's length was out of range.
+    #[error("field `{custom_id}` must be {min} to {max} characters long")]
+    Length {
+        custom_id: &'static str,
+        min: usize,
+        max: usize,
+    },
+    /// Parsing the input failed.
+    #[error("field `{custom_id}` is invalid: {error}")]
+    Parse {
+        custom_id: &'static str,
+        #[source]
+        error: anyhow::Error,
+    },
+}
+
+impl ModalFieldError {
+    /// Gets the custom ID of the input that caused the error.
+    pub fn custom_id(&self) -> &'static str {
+        match self {
+            Self::Missing { custom_id }
+            | Self::Length { custom_id, .. }
+            | Self::Parse { custom_id, .. } => custom_id,
+        }
+    }
+}
+
+/// Finds the trimmed value of a text input by its custom ID.
+///
+/// Returns [`None`] if there is no such input or its value is empty.
+pub fn find_modal_value<'a>(data: &'a ModalInteractionData, custom_id: &str) -> Option<&'a str> {
+    data.components
+        .iter()
+        .flat_map(|r| &r.components)
+        .find_map(|c| match c {
+            ActionRowComponent::InputText(InputText {
+                value: Some(value),
+                custom_id: id,
+                ..
+            }) if id.as_str() == custom_id => Some(value.trim()),
+            _ => None,
+        })
+        .filter(|v| !v.is_empty())
+}
@@ -3,3 +3,38 @@
 //! Exposed for use by macros.
 
 pub use serenity::all as serenity;
+
+use crate::modal::ModalFieldError;
+
+/// Checks the length of a modal input value.
+pub fn check_modal_length(
+    custom_id: &'static str,
+    value: &str,
+    min: usize,
+    max: usize,
+) -> Result<(), ModalFieldError> {
+    let len = value.chars().count();
+    if (min..=max).contains(&len) {
+        Ok(())
+    } else {
+        Err(ModalFieldError::Length {
+            custom_id,
+            min,
+            max,
+        })
+    }
+}
+
+/// Maps the error of parsing a modal input value.
+pub fn map_modal_parse<T, E>(
+    custom_id: &'static str,
+    result: Result<T, E>,
+) -> Result<T, ModalFieldError>
+where
+    E: Into<anyhow::Error>,
+{
+    result.map_err(|error| ModalFieldError::Parse {
+        custom_id,
+        error: error.into(),
+    })
+}
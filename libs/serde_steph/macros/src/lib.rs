@@ -0,0 +1,15 @@
+use proc_macro::TokenStream as StdTokenStream;
+use syn::DeriveInput;
+
+mod migrate_impl;
+
+/// Derives `Migrate` for a type.
+///
+/// See the docs on the `serde_steph::migrate` module.
+#[proc_macro_derive(Migrate, attributes(steph))]
+pub fn derive_migrate(input: StdTokenStream) -> StdTokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+    migrate_impl::entry_point(input)
+        .unwrap_or_else(|e| e.into_compile_error())
+        .into()
+}
@@ -0,0 +1,56 @@
+use darling::FromDeriveInput;
+use proc_macro2::TokenStream;
+
+#[derive(Debug, darling::FromDeriveInput)]
+#[darling(attributes(steph))]
+struct MigrateArgs {
+    ident: syn::Ident,
+    generics: syn::Generics,
+    version: u32,
+    previous: Option<syn::Type>,
+}
+
+pub fn entry_point(input: syn::DeriveInput) -> syn::Result<TokenStream> {
+    let args = MigrateArgs::from_derive_input(&input)?;
+
+    let ident = &args.ident;
+    let version = args.version;
+    let (impl_generics, ty_generics, where_clause) = args.generics.split_for_impl();
+
+    let read_previous = match &args.previous {
+        Some(previous) => quote::quote! {
+            if version < #version {
+                const {
+                    ::std::assert!(
+                        <#previous as ::serde_steph::migrate::Migrate>::VERSION < #version,
+                        "the previous version must be lower than the current one",
+                    );
+                }
+
+                let previous = <#previous as ::serde_steph::migrate::Migrate>::read_version(version, de)?;
+                return ::std::result::Result::Ok(<Self as ::std::convert::From<#previous>>::from(previous));
+            }
+        },
+        None => TokenStream::new(),
+    };
+
+    Ok(quote::quote! {
+        #[automatically_derived]
+        impl #impl_generics ::serde_steph::migrate::Migrate for #ident #ty_generics #where_clause {
+            const VERSION: u32 = #version;
+
+            fn read_version<'de, R: ::serde_steph::de::Read<'de>>(
+                version: u32,
+                de: &mut ::serde_steph::Deserializer<R>,
+            ) -> ::serde_steph::Result<Self> {
+                if version == #version {
+                    return <Self as ::serde_steph::migrate::Migrate>::read_current(de);
+                }
+
+                #read_previous
+
+                ::std::result::Result::Err(::serde_steph::Error::UnknownVersion(version))
+            }
+        }
+    })
+}
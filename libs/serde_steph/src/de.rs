@@ -49,6 +49,77 @@ where
 #[derive(Debug)]
 pub struct Deserializer<R> {
     reader: R,
+    options: DeserializerOptions,
+    /// How many more nesting levels are allowed.
+    depth: usize,
+}
+
+/// Limits applied by a [`Deserializer`] to guard against malicious input.
+///
+/// The default only limits the nesting depth to 128. Sequence and byte buffer
+/// lengths are unlimited by default, although readers will never allocate
+/// more than a small buffer ahead of the data actually read.
+///
+/// # Examples
+///
+/// ```
+/// # use serde_steph::{Deserializer, DeserializerOptions, Error};
+/// # use serde::de::Deserialize;
+/// let options = DeserializerOptions::new().max_seq_len(2);
+/// let mut de = Deserializer::from_slice(&[3, 1, 2, 3]).with_options(options);
+/// let res = Vec::<u8>::deserialize(&mut de);
+/// assert!(matches!(res, Err(Error::SeqTooLong)));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct DeserializerOptions {
+    max_depth: usize,
+    max_seq_len: usize,
+    max_bytes_len: usize,
+}
+
+impl DeserializerOptions {
+    /// Creates the default options.
+    pub const fn new() -> Self {
+        Self {
+            max_depth: 128,
+            max_seq_len: usize::MAX,
+            max_bytes_len: usize::MAX,
+        }
+    }
+
+    /// Sets how deeply values may be nested.
+    ///
+    /// Every option, newtype, sequence, tuple, struct, map, and enum counts as
+    /// one level. Exceeding this returns [`Error::DepthLimitExceeded`].
+    #[must_use]
+    pub const fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Sets the maximum length of length-prefixed sequences and maps.
+    ///
+    /// Exceeding this returns [`Error::SeqTooLong`].
+    #[must_use]
+    pub const fn max_seq_len(mut self, max_seq_len: usize) -> Self {
+        self.max_seq_len = max_seq_len;
+        self
+    }
+
+    /// Sets the maximum length of strings and byte buffers.
+    ///
+    /// Exceeding this returns [`Error::BytesTooLong`].
+    #[must_use]
+    pub const fn max_bytes_len(mut self, max_bytes_len: usize) -> Self {
+        self.max_bytes_len = max_bytes_len;
+        self
+    }
+}
+
+impl Default for DeserializerOptions {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl<'de, R: Read<'de>> Deserializer<R> {
@@ -58,7 +129,22 @@ impl<'de, R: Read<'de>> Deserializer<R> {
     /// [`Self::from_reader`] instead, or perhaps one of the standalone
     /// functions in this module are sufficient.
     pub fn new(reader: R) -> Self {
-        Self { reader }
+        let options = DeserializerOptions::new();
+        Self {
+            reader,
+            options,
+            depth: options.max_depth,
+        }
+    }
+
+    /// Replaces the limits applied while deserializing.
+    ///
+    /// This should be called before deserializing any values.
+    #[must_use]
+    pub fn with_options(mut self, options: DeserializerOptions) -> Self {
+        self.options = options;
+        self.depth = options.max_depth;
+        self
     }
 
     /// Should be called to indicate that the object has been fully
@@ -85,9 +171,53 @@ impl<'de, R: Read<'de>> Deserializer<R> {
         Ok(value)
     }
 
-    fn read_leb128<T: leb128::Leb128>(&mut self) -> Result<T> {
+    pub(crate) fn read_byte(&mut self) -> Result<u8> {
+        let [b] = self.reader.read_bytes()?;
+        Ok(b)
+    }
+
+    pub(crate) fn read_leb128<T: leb128::Leb128>(&mut self) -> Result<T> {
         leb128::read(&mut self.reader)
     }
+
+    /// Reads the length prefix of a sequence or map.
+    pub(crate) fn read_seq_len(&mut self) -> Result<usize> {
+        let len = self.read_leb128()?;
+        if len > self.options.max_seq_len {
+            return Err(Error::SeqTooLong);
+        }
+
+        Ok(len)
+    }
+
+    /// Reads the length prefix of a string or byte buffer.
+    fn read_bytes_len(&mut self) -> Result<usize> {
+        let len = self.read_leb128()?;
+        if len > self.options.max_bytes_len {
+            return Err(Error::BytesTooLong);
+        }
+
+        Ok(len)
+    }
+
+    /// Enters a nested value. Every call must be matched by [`Self::leave`].
+    pub(crate) fn enter(&mut self) -> Result<()> {
+        self.depth = self.depth.checked_sub(1).ok_or(Error::DepthLimitExceeded)?;
+        Ok(())
+    }
+
+    /// Leaves a nested value entered with [`Self::enter`].
+    pub(crate) fn leave(&mut self) {
+        self.depth += 1;
+    }
+
+    /// Runs `f` one nesting level deeper.
+    fn nested<T>(&mut self, f: impl FnOnce(&mut Self) -> Result<T>) -> Result<T> {
+        self.enter()?;
+        let res = f(self);
+        self.leave();
+        res
+    }
 }
 
 impl<'de> Deserializer<SliceRead<'de>> {
@@ -303,7 +433,7 @@ impl<'de, R: Read<'de>> de::Deserializer<'de> for &mut Deserializer<R> {
     where
         V: de::Visitor<'de>,
     {
-        let len: usize = self.read_leb128()?;
+        let len = self.read_bytes_len()?;
         match self.reader.try_read_bytes_borrow(len) {
             Some(v) => {
                 let v = std::str::from_utf8(v?).map_err(|_| Error::InvalidUtf8)?;
@@ -320,7 +450,7 @@ impl<'de, R: Read<'de>> de::Deserializer<'de> for &mut Deserializer<R> {
     where
         V: de::Visitor<'de>,
     {
-        let len: usize = self.read_leb128()?;
+        let len = self.read_bytes_len()?;
         let v = self.reader.read_byte_vec(len)?;
         let v = String::from_utf8(v).map_err(|_| Error::InvalidUtf8)?;
         visitor.visit_string(v)
@@ -330,7 +460,7 @@ impl<'de, R: Read<'de>> de::Deserializer<'de> for &mut Deserializer<R> {
     where
         V: de::Visitor<'de>,
     {
-        let len: usize = self.read_leb128()?;
+        let len = self.read_bytes_len()?;
         match self.reader.try_read_bytes_borrow(len) {
             Some(v) => visitor.visit_borrowed_bytes(v?),
             None => self.reader.read_byte_view(len, |v| visitor.visit_bytes(v)),
@@ -341,7 +471,7 @@ impl<'de, R: Read<'de>> de::Deserializer<'de> for &mut Deserializer<R> {
     where
         V: de::Visitor<'de>,
     {
-        let len: usize = self.read_leb128()?;
+        let len = self.read_bytes_len()?;
         let v = self.reader.read_byte_vec(len)?;
         visitor.visit_byte_buf(v)
     }
@@ -353,7 +483,7 @@ impl<'de, R: Read<'de>> de::Deserializer<'de> for &mut Deserializer<R> {
         let [b] = self.reader.read_bytes()?;
         match b {
             0 => visitor.visit_none(),
-            1 => visitor.visit_some(self),
+            1 => self.nested(|de| visitor.visit_some(de)),
             _ => Err(Error::InvalidOption),
         }
     }
@@ -376,17 +506,19 @@ impl<'de, R: Read<'de>> de::Deserializer<'de> for &mut Deserializer<R> {
     where
         V: de::Visitor<'de>,
     {
-        visitor.visit_newtype_struct(self)
+        self.nested(|de| visitor.visit_newtype_struct(de))
     }
 
     fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value>
     where
         V: de::Visitor<'de>,
     {
-        let len: usize = self.read_leb128()?;
-        visitor.visit_seq(ListAccess {
-            deserializer: self,
-            len,
+        let len = self.read_seq_len()?;
+        self.nested(|de| {
+            visitor.visit_seq(ListAccess {
+                deserializer: de,
+                len,
+            })
         })
     }
 
@@ -394,9 +526,11 @@ impl<'de, R: Read<'de>> de::Deserializer<'de> for &mut Deserializer<R> {
     where
         V: de::Visitor<'de>,
     {
-        visitor.visit_seq(ListAccess {
-            deserializer: self,
-            len,
+        self.nested(|de| {
+            visitor.visit_seq(ListAccess {
+                deserializer: de,
+                len,
+            })
         })
     }
 
@@ -409,9 +543,11 @@ impl<'de, R: Read<'de>> de::Deserializer<'de> for &mut Deserializer<R> {
     where
         V: de::Visitor<'de>,
     {
-        visitor.visit_seq(ListAccess {
-            deserializer: self,
-            len,
+        self.nested(|de| {
+            visitor.visit_seq(ListAccess {
+                deserializer: de,
+                len,
+            })
         })
     }
 
@@ -419,10 +555,12 @@ impl<'de, R: Read<'de>> de::Deserializer<'de> for &mut Deserializer<R> {
     where
         V: de::Visitor<'de>,
     {
-        let len: usize = self.read_leb128()?;
-        visitor.visit_map(ListAccess {
-            deserializer: self,
-            len,
+        let len = self.read_seq_len()?;
+        self.nested(|de| {
+            visitor.visit_map(ListAccess {
+                deserializer: de,
+                len,
+            })
         })
     }
 
@@ -435,7 +573,7 @@ impl<'de, R: Read<'de>> de::Deserializer<'de> for &mut Deserializer<R> {
     where
         V: de::Visitor<'de>,
     {
-        visitor.visit_seq(TupleAccess { deserializer: self })
+        self.nested(|de| visitor.visit_seq(TupleAccess { deserializer: de }))
     }
 
     fn deserialize_enum<V>(
@@ -447,7 +585,7 @@ impl<'de, R: Read<'de>> de::Deserializer<'de> for &mut Deserializer<R> {
     where
         V: de::Visitor<'de>,
     {
-        visitor.visit_enum(TupleAccess { deserializer: self })
+        self.nested(|de| visitor.visit_enum(TupleAccess { deserializer: de }))
     }
 
     fn deserialize_identifier<V>(self, visitor: V) -> Result<V::Value>
@@ -37,6 +37,9 @@ pub enum Error {
     /// Tried to deserialize an [`Option`] with an invalid discriminator.
     #[error("invalid option discriminator")]
     InvalidOption,
+    /// Self-describing data contained an unknown or unexpected type tag.
+    #[error("invalid type tag {0}")]
+    InvalidTag(u8),
     /// A type tried to use [`de::Deserializer::deserialize_any`].
     #[error("types deserializing via any are unsupported")]
     AnyUnsupported,
@@ -44,6 +47,21 @@ pub enum Error {
     /// type.
     #[error("LEB encoded integer overflows target type")]
     IntegerOverflow,
+    /// Values were nested deeper than allowed by the
+    /// [`DeserializerOptions`](crate::DeserializerOptions).
+    #[error("values are nested too deeply")]
+    DepthLimitExceeded,
+    /// A sequence or map was longer than allowed by the
+    /// [`DeserializerOptions`](crate::DeserializerOptions).
+    #[error("sequence is too long")]
+    SeqTooLong,
+    /// A string or byte buffer was longer than allowed by the
+    /// [`DeserializerOptions`](crate::DeserializerOptions).
+    #[error("byte buffer is too long")]
+    BytesTooLong,
+    /// Versioned data had a version unknown to the target type.
+    #[error("unknown data version {0}")]
+    UnknownVersion(u32),
     /// Past the expected end of the deserialized object were trailing bytes.
     #[error("trailing bytes past the end of the deserialized value")]
     TrailingBytes,
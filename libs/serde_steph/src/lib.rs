@@ -4,6 +4,7 @@
 //!
 //! Custom binary serialization format, vaguely inspired by [BARE][^bare]. This
 //! format is not self-describing and as such deserializing any is disallowed.
+//! If that is needed, the [`tagged`] module provides a self-describing variant.
 //!
 //! The types in this serialization format are as follows:
 //!
@@ -29,17 +30,25 @@
 //! When deserializing from a byte slice, deserializing borrowed data is
 //! supported.
 //!
+//! To work with data without a concrete Rust type, [`Value`] models the types
+//! listed above and can be converted to and from other types.
+//!
 //! [bare]: <https://baremessages.org/>
 //! [^bare]: No, I did not really read the spec and the output likely isn't compatible.
 
 pub mod de;
 mod error;
 mod leb128;
+pub mod migrate;
 mod read;
 pub mod ser;
+pub mod tagged;
+pub mod testing;
 #[cfg(test)]
 mod tests;
+mod value;
 
-pub use de::{from_reader, from_slice, Deserializer};
+pub use de::{from_reader, from_slice, Deserializer, DeserializerOptions};
 pub use error::{Error, Result};
 pub use ser::{to_vec, to_writer, Serializer};
+pub use value::{from_value, to_value, Value};
@@ -0,0 +1,114 @@
+//! Support for reading data written by older versions of a type.
+//!
+//! Since the format isn't self-describing, data can only be read with exactly
+//! the type it was written with. To still be able to change types over time,
+//! values can be written with a version prefix via [`to_vec_versioned`] and
+//! read back with [`read_any_version`], which upgrades data from older
+//! versions through a chain of [`From`] conversions.
+//!
+//! The chain is usually declared with the [`Migrate`](macro@Migrate) derive:
+//!
+//! ```
+//! use serde::{Deserialize, Serialize};
+//! use serde_steph::migrate::{self, Migrate};
+//!
+//! #[derive(Deserialize, Migrate)]
+//! #[steph(version = 1)]
+//! struct ArgsV1 {
+//!     page: u16,
+//! }
+//!
+//! #[derive(Debug, PartialEq, Serialize, Deserialize, Migrate)]
+//! #[steph(version = 2, previous = "ArgsV1")]
+//! struct Args {
+//!     page: u16,
+//!     ephemeral: bool,
+//! }
+//!
+//! impl From<ArgsV1> for Args {
+//!     fn from(value: ArgsV1) -> Self {
+//!         Self {
+//!             page: value.page,
+//!             ephemeral: false,
+//!         }
+//!     }
+//! }
+//!
+//! // data written by the first version: version 1, then page 5
+//! let args: Args = migrate::read_any_version(&[1, 5]).expect("must read v1");
+//! assert_eq!(args, Args { page: 5, ephemeral: false });
+//! ```
+
+use std::io;
+
+use serde::{de, ser};
+/// Derives [`Migrate`](trait@Migrate) for a type.
+///
+/// The type must be annotated with `#[steph(version = N)]` to declare its
+/// version. If it has older versions, add `previous = "Type"` to name the
+/// type for the version before it. That type must implement
+/// [`Migrate`](trait@Migrate) itself and there must be a [`From`] conversion
+/// from it. The previous version must be lower than the current one.
+pub use serde_steph_macros::Migrate;
+
+use crate::de::Read;
+use crate::error::Result;
+use crate::Deserializer;
+
+/// A type that can read data from its older versions.
+///
+/// Prefer using the [derive macro](macro@Migrate) over implementing this
+/// manually.
+pub trait Migrate: de::DeserializeOwned {
+    /// The current version of this type.
+    const VERSION: u32;
+
+    /// Reads data with the given `version`.
+    ///
+    /// If `version` is older than [`Self::VERSION`], this reads the older type
+    /// and converts it. Returns [`Error::UnknownVersion`] if the version isn't
+    /// known.
+    ///
+    /// [`Error::UnknownVersion`]: crate::Error::UnknownVersion
+    fn read_version<'de, R: Read<'de>>(version: u32, de: &mut Deserializer<R>) -> Result<Self>;
+
+    /// Reads data of the current version.
+    fn read_current<'de, R: Read<'de>>(de: &mut Deserializer<R>) -> Result<Self> {
+        Self::deserialize(de)
+    }
+}
+
+/// Serializes a value with a version prefix to a [`Vec<u8>`].
+pub fn to_vec_versioned<T>(value: &T) -> Result<Vec<u8>>
+where
+    T: Migrate + ser::Serialize,
+{
+    let mut buf = Vec::new();
+    to_writer_versioned(&mut buf, value)?;
+    Ok(buf)
+}
+
+/// Serializes a value with a version prefix to a [`io::Write`].
+pub fn to_writer_versioned<T, W>(mut writer: W, value: &T) -> Result<()>
+where
+    T: Migrate + ser::Serialize,
+    W: io::Write,
+{
+    crate::to_writer(&mut writer, &T::VERSION)?;
+    crate::to_writer(writer, value)
+}
+
+/// Deserializes a value with a version prefix from a byte slice, upgrading it
+/// from older versions as needed.
+///
+/// This assumes that `buf` contains just one object. If there are trailing
+/// bytes, this function returns [`Error::TrailingBytes`].
+///
+/// [`Error::TrailingBytes`]: crate::Error::TrailingBytes
+pub fn read_any_version<T: Migrate>(buf: &[u8]) -> Result<T> {
+    let mut de = Deserializer::from_slice(buf);
+    let version: u32 = de.read_leb128()?;
+    let value = T::read_version(version, &mut de)?;
+    de.end()?;
+    Ok(value)
+}
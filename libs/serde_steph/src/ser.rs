@@ -48,11 +48,11 @@ impl<W: io::Write> Serializer<W> {
         self.writer
     }
 
-    fn write_byte(&mut self, v: u8) -> Result<()> {
+    pub(crate) fn write_byte(&mut self, v: u8) -> Result<()> {
         Ok(self.writer.write_all(&[v])?)
     }
 
-    fn write_leb128(&mut self, v: impl leb128::Leb128) -> Result<()> {
+    pub(crate) fn write_leb128(&mut self, v: impl leb128::Leb128) -> Result<()> {
         leb128::write(&mut self.writer, v)
     }
 }
@@ -0,0 +1,304 @@
+//! Exposes a deserializer for the self-describing format.
+
+use std::io;
+
+use serde::de::{self, Deserializer as _};
+
+use super::tag;
+use crate::de::{DeserializerOptions, IoRead, Read, SliceRead};
+use crate::error::{Error, Result};
+
+/// Deserializes a value in the self-describing format from a byte slice.
+///
+/// This assumes that `buf` contains just one object. If there are trailing
+/// bytes, this function returns [`Error::TrailingBytes`].
+pub fn from_slice<'de, T>(buf: &'de [u8]) -> Result<T>
+where
+    T: de::Deserialize<'de>,
+{
+    Deserializer::from_slice(buf).read_to_end()
+}
+
+/// Deserializes a value in the self-describing format from a [`io::Read`].
+///
+/// This assumes that `reader` yields only one object. If it provides trailing
+/// bytes, this function returns [`Error::TrailingBytes`].
+pub fn from_reader<T, R>(reader: R) -> Result<T>
+where
+    T: de::DeserializeOwned,
+    R: io::Read,
+{
+    Deserializer::from_reader(reader).read_to_end()
+}
+
+/// A [`Deserializer`] for the self-describing format. The trait is only
+/// implemented by `&mut`.
+///
+/// Unlike the [plain deserializer](crate::Deserializer), this supports
+/// [`deserialize_any`](de::Deserializer::deserialize_any).
+///
+/// [`Deserializer`]: serde::de::Deserializer
+#[derive(Debug)]
+pub struct Deserializer<R> {
+    inner: crate::Deserializer<R>,
+}
+
+impl<'de, R: Read<'de>> Deserializer<R> {
+    /// Creates a new deserializer that reads a value from a [`Read`].
+    pub fn new(reader: R) -> Self {
+        Self {
+            inner: crate::Deserializer::new(reader),
+        }
+    }
+
+    /// Replaces the limits applied while deserializing.
+    ///
+    /// See [`crate::Deserializer::with_options`] for details.
+    #[must_use]
+    pub fn with_options(self, options: DeserializerOptions) -> Self {
+        Self {
+            inner: self.inner.with_options(options),
+        }
+    }
+
+    /// Should be called to indicate that the object has been fully
+    /// deserialized. Returns an error if there are more bytes left.
+    ///
+    /// See [`crate::Deserializer::end`] for details.
+    pub fn end(&mut self) -> Result<()> {
+        self.inner.end()
+    }
+
+    /// Helper method to deserialize one object and then call `end`.
+    fn read_to_end<T: de::Deserialize<'de>>(&mut self) -> Result<T> {
+        let value = T::deserialize(&mut *self)?;
+        self.end()?;
+        Ok(value)
+    }
+
+    /// Runs `f` one nesting level deeper.
+    fn nested<T>(&mut self, f: impl FnOnce(&mut Self) -> Result<T>) -> Result<T> {
+        self.inner.enter()?;
+        let res = f(self);
+        self.inner.leave();
+        res
+    }
+}
+
+impl<'de> Deserializer<SliceRead<'de>> {
+    /// Creates a new deserializer that reads a value from a slice.
+    pub fn from_slice(buf: &'de [u8]) -> Self {
+        Self::new(SliceRead::new(buf))
+    }
+
+    /// Gets the remaining unread part of the slice.
+    pub fn remainder(&self) -> &'de [u8] {
+        self.inner.remainder()
+    }
+}
+
+impl<R: io::Read> Deserializer<IoRead<R>> {
+    /// Creates a new deserializer that reads a value from a [`io::Read`].
+    ///
+    /// If you're working with a byte slice, it is more efficient to use
+    /// [`from_slice`].
+    pub fn from_reader(reader: R) -> Self {
+        Self::new(IoRead::new(reader))
+    }
+
+    /// Unwraps the deserializer into its inner reader.
+    pub fn into_reader(self) -> R {
+        self.inner.into_reader()
+    }
+}
+
+impl<'de, R: Read<'de>> de::Deserializer<'de> for &mut Deserializer<R> {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        // the payload after the tag is just the plain format
+        let inner = &mut self.inner;
+        match inner.read_byte()? {
+            tag::UNIT => visitor.visit_unit(),
+            tag::BOOL => inner.deserialize_bool(visitor),
+            tag::U8 => inner.deserialize_u8(visitor),
+            tag::U16 => inner.deserialize_u16(visitor),
+            tag::U32 => inner.deserialize_u32(visitor),
+            tag::U64 => inner.deserialize_u64(visitor),
+            tag::U128 => inner.deserialize_u128(visitor),
+            tag::I8 => inner.deserialize_i8(visitor),
+            tag::I16 => inner.deserialize_i16(visitor),
+            tag::I32 => inner.deserialize_i32(visitor),
+            tag::I64 => inner.deserialize_i64(visitor),
+            tag::I128 => inner.deserialize_i128(visitor),
+            tag::F32 => inner.deserialize_f32(visitor),
+            tag::F64 => inner.deserialize_f64(visitor),
+            tag::CHAR => inner.deserialize_char(visitor),
+            tag::STR => inner.deserialize_str(visitor),
+            tag::BYTES => inner.deserialize_bytes(visitor),
+            tag::NONE => visitor.visit_none(),
+            tag::SOME => self.nested(|de| visitor.visit_some(de)),
+            tag::SEQ => {
+                let len = inner.read_seq_len()?;
+                self.nested(|de| {
+                    visitor.visit_seq(ListAccess {
+                        deserializer: de,
+                        len,
+                    })
+                })
+            },
+            tag::MAP => {
+                let len = inner.read_seq_len()?;
+                self.nested(|de| {
+                    visitor.visit_map(ListAccess {
+                        deserializer: de,
+                        len,
+                    })
+                })
+            },
+            // present enums as a map with a single entry
+            tag::ENUM => self.nested(|de| {
+                visitor.visit_map(ListAccess {
+                    deserializer: de,
+                    len: 1,
+                })
+            }),
+            tag => Err(Error::InvalidTag(tag)),
+        }
+    }
+
+    fn deserialize_newtype_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.nested(|de| visitor.visit_newtype_struct(de))
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self.inner.read_byte()? {
+            tag::ENUM => self.nested(|de| visitor.visit_enum(EnumAccess { deserializer: de })),
+            tag => Err(Error::InvalidTag(tag)),
+        }
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+/// Provides access to a `seq` or `map` with length prefix.
+struct ListAccess<'a, R> {
+    deserializer: &'a mut Deserializer<R>,
+    len: usize,
+}
+
+/// Provides access to an `enum` variant and its data.
+struct EnumAccess<'a, R> {
+    deserializer: &'a mut Deserializer<R>,
+}
+
+impl<'de, R: Read<'de>> de::SeqAccess<'de> for ListAccess<'_, R> {
+    type Error = Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>>
+    where
+        T: de::DeserializeSeed<'de>,
+    {
+        if self.len == 0 {
+            Ok(None)
+        } else {
+            self.len -= 1;
+            Ok(Some(seed.deserialize(&mut *self.deserializer)?))
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.len)
+    }
+}
+
+impl<'de, R: Read<'de>> de::MapAccess<'de> for ListAccess<'_, R> {
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>>
+    where
+        K: de::DeserializeSeed<'de>,
+    {
+        if self.len == 0 {
+            Ok(None)
+        } else {
+            self.len -= 1;
+            Ok(Some(seed.deserialize(&mut *self.deserializer)?))
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        seed.deserialize(&mut *self.deserializer)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.len)
+    }
+}
+
+impl<'de, R: Read<'de>> de::EnumAccess<'de> for EnumAccess<'_, R> {
+    type Error = Error;
+    type Variant = Self;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant)>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        let v = seed.deserialize(&mut *self.deserializer)?;
+        Ok((v, self))
+    }
+}
+
+impl<'de, R: Read<'de>> de::VariantAccess<'de> for EnumAccess<'_, R> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<()> {
+        de::Deserialize::deserialize(self.deserializer)
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value>
+    where
+        T: de::DeserializeSeed<'de>,
+    {
+        seed.deserialize(self.deserializer)
+    }
+
+    fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserializer.deserialize_any(visitor)
+    }
+
+    fn struct_variant<V>(self, _fields: &'static [&'static str], visitor: V) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserializer.deserialize_any(visitor)
+    }
+}
@@ -0,0 +1,68 @@
+//! Self-describing variant of the format.
+//!
+//! Every value is prefixed with a single tag byte that describes its type, and
+//! structs and enums carry their field and variant names. This makes the
+//! output considerably larger than the plain format, but it means that
+//! [`deserialize_any`](serde::de::Deserializer::deserialize_any) is supported
+//! and data can be inspected without knowing the type it was serialized from,
+//! f.e. by deserializing it as a [`Value`].
+//!
+//! The payload after each tag uses the same encoding as the plain format. The
+//! tagged types are as follows:
+//!
+//! - `unit`: no payload; units, unit structs, and empty tuples
+//! - `bool`, `u8`, `i8`: single byte
+//! - `u16` through `u128`: `uint`
+//! - `i16` through `i128`: `sint`
+//! - `f32`, `f64`: little-endian bytes
+//! - `char`: `uint`
+//! - `str`, `bytes`: `list` of bytes
+//! - `none`: no payload
+//! - `some`: the tagged inner value
+//! - `seq`: `uint` length followed by that many tagged values; sequences,
+//!   tuples, and tuple structs
+//! - `map`: `uint` length followed by that many tagged key-value pairs; maps
+//!   and structs, which use their field names as `str` keys
+//! - `enum`: tagged `str` variant name followed by the tagged variant data,
+//!   which is `unit`, the newtype value, a `seq`, or a `map` respectively
+//!
+//! Newtype structs are transparent.
+//!
+//! When deserialized via `any`, an `enum` is presented as a map with a single
+//! entry from the variant name to its data.
+
+pub mod de;
+pub mod ser;
+#[cfg(test)]
+mod tests;
+mod value;
+
+pub use de::{from_reader, from_slice, Deserializer};
+pub use ser::{to_vec, to_writer, Serializer};
+pub use value::Value;
+
+/// The type tags written before every value.
+mod tag {
+    pub const UNIT: u8 = 0;
+    pub const BOOL: u8 = 1;
+    pub const U8: u8 = 2;
+    pub const U16: u8 = 3;
+    pub const U32: u8 = 4;
+    pub const U64: u8 = 5;
+    pub const U128: u8 = 6;
+    pub const I8: u8 = 7;
+    pub const I16: u8 = 8;
+    pub const I32: u8 = 9;
+    pub const I64: u8 = 10;
+    pub const I128: u8 = 11;
+    pub const F32: u8 = 12;
+    pub const F64: u8 = 13;
+    pub const CHAR: u8 = 14;
+    pub const STR: u8 = 15;
+    pub const BYTES: u8 = 16;
+    pub const NONE: u8 = 17;
+    pub const SOME: u8 = 18;
+    pub const SEQ: u8 = 19;
+    pub const MAP: u8 = 20;
+    pub const ENUM: u8 = 21;
+}
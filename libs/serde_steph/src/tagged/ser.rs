@@ -0,0 +1,335 @@
+//! Exposes a serializer for the self-describing format.
+
+use std::io;
+
+use serde::ser::{self, Serializer as _};
+
+use super::tag;
+use crate::error::{Error, Result};
+
+/// Serializes a value to a [`Vec<u8>`] in the self-describing format.
+pub fn to_vec<T>(value: &T) -> Result<Vec<u8>>
+where
+    T: ser::Serialize,
+{
+    let mut buf = Vec::new();
+    to_writer(&mut buf, value)?;
+    Ok(buf)
+}
+
+/// Serializes a value to a [`io::Write`] in the self-describing format.
+pub fn to_writer<T, W>(writer: W, value: &T) -> Result<()>
+where
+    T: ser::Serialize,
+    W: io::Write,
+{
+    value.serialize(&mut Serializer::from_writer(writer))
+}
+
+/// A [`Serializer`] for the self-describing format. The trait is only
+/// implemented by `&mut`.
+///
+/// [`Serializer`]: serde::ser::Serializer
+#[derive(Debug)]
+pub struct Serializer<W> {
+    inner: crate::Serializer<W>,
+}
+
+impl<W: io::Write> Serializer<W> {
+    /// Creates a new serializer that writes a value to a [`io::Write`].
+    pub fn from_writer(writer: W) -> Self {
+        Self {
+            inner: crate::Serializer::from_writer(writer),
+        }
+    }
+
+    /// Unwraps the serializer into its inner writer.
+    pub fn into_writer(self) -> W {
+        self.inner.into_writer()
+    }
+
+    fn write_tag(&mut self, tag: u8) -> Result<()> {
+        self.inner.write_byte(tag)
+    }
+
+    fn write_len(&mut self, tag: u8, len: usize) -> Result<Compound<'_, W>> {
+        self.write_tag(tag)?;
+        self.inner.write_leb128(len)?;
+        Ok(Compound(self))
+    }
+
+    fn write_variant(&mut self, variant: &str) -> Result<()> {
+        self.write_tag(tag::ENUM)?;
+        self.serialize_str(variant)
+    }
+}
+
+/// Writes a tag and then the value via the plain format.
+macro_rules! serialize_tagged {
+    ($($fn:ident($ty:ty) => $tag:ident;)*) => { $(
+        fn $fn(self, v: $ty) -> Result<()> {
+            self.write_tag(tag::$tag)?;
+            self.inner.$fn(v)
+        }
+    )* };
+}
+
+impl<'a, W: io::Write> ser::Serializer for &'a mut Serializer<W> {
+    type Ok = ();
+    type Error = Error;
+
+    type SerializeSeq = Compound<'a, W>;
+    type SerializeTuple = Compound<'a, W>;
+    type SerializeTupleStruct = Compound<'a, W>;
+    type SerializeTupleVariant = Compound<'a, W>;
+    type SerializeMap = Compound<'a, W>;
+    type SerializeStruct = Compound<'a, W>;
+    type SerializeStructVariant = Compound<'a, W>;
+
+    serialize_tagged! {
+        serialize_bool(bool) => BOOL;
+        serialize_i8(i8) => I8;
+        serialize_i16(i16) => I16;
+        serialize_i32(i32) => I32;
+        serialize_i64(i64) => I64;
+        serialize_i128(i128) => I128;
+        serialize_u8(u8) => U8;
+        serialize_u16(u16) => U16;
+        serialize_u32(u32) => U32;
+        serialize_u64(u64) => U64;
+        serialize_u128(u128) => U128;
+        serialize_f32(f32) => F32;
+        serialize_f64(f64) => F64;
+        serialize_char(char) => CHAR;
+        serialize_str(&str) => STR;
+        serialize_bytes(&[u8]) => BYTES;
+    }
+
+    fn serialize_none(self) -> Result<()> {
+        self.write_tag(tag::NONE)
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<()>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        self.write_tag(tag::SOME)?;
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<()> {
+        self.write_tag(tag::UNIT)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<()> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<()> {
+        self.write_variant(variant)?;
+        self.serialize_unit()
+    }
+
+    fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T) -> Result<()>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<()>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        self.write_variant(variant)?;
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq> {
+        let len = len.ok_or(Error::LengthRequired)?;
+        self.write_len(tag::SEQ, len)
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple> {
+        self.write_len(tag::SEQ, len)
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleStruct> {
+        self.write_len(tag::SEQ, len)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleVariant> {
+        self.write_variant(variant)?;
+        self.write_len(tag::SEQ, len)
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap> {
+        let len = len.ok_or(Error::LengthRequired)?;
+        self.write_len(tag::MAP, len)
+    }
+
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<Self::SerializeStruct> {
+        self.write_len(tag::MAP, len)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStructVariant> {
+        self.write_variant(variant)?;
+        self.write_len(tag::MAP, len)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+/// Allows serializing the elements of a `seq` or the entries of a `map`.
+#[doc(hidden)]
+pub struct Compound<'a, W>(&'a mut Serializer<W>);
+
+impl<W: io::Write> ser::SerializeSeq for Compound<'_, W> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        value.serialize(&mut *self.0)
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl<W: io::Write> ser::SerializeTuple for Compound<'_, W> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        value.serialize(&mut *self.0)
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl<W: io::Write> ser::SerializeTupleStruct for Compound<'_, W> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        value.serialize(&mut *self.0)
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl<W: io::Write> ser::SerializeTupleVariant for Compound<'_, W> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        value.serialize(&mut *self.0)
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl<W: io::Write> ser::SerializeMap for Compound<'_, W> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_key<T>(&mut self, key: &T) -> Result<()>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        key.serialize(&mut *self.0)
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        value.serialize(&mut *self.0)
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl<W: io::Write> ser::SerializeStruct for Compound<'_, W> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<()>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        self.0.serialize_str(key)?;
+        value.serialize(&mut *self.0)
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl<W: io::Write> ser::SerializeStructVariant for Compound<'_, W> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<()>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        self.0.serialize_str(key)?;
+        value.serialize(&mut *self.0)
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
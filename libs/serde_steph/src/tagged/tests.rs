@@ -0,0 +1,150 @@
+// checks that values round-trip through the self-describing format and can be
+// inspected without knowing the original type
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::*;
+use crate::Error;
+
+fn round_trip<T>(value: &T) -> Vec<u8>
+where
+    T: PartialEq + fmt::Debug + Serialize + serde::de::DeserializeOwned,
+{
+    let buf = to_vec(value).expect("serializing must work");
+    let rev: T = from_slice(&buf).expect("deserializing must work");
+    assert_eq!(*value, rev, "serialization messed up data");
+
+    let rev: T = from_reader(buf.as_slice()).expect("deserializing from reader must work");
+    assert_eq!(*value, rev, "serialization messed up data");
+    buf
+}
+
+fn str(s: &str) -> Value {
+    Value::Str(s.to_owned())
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct NewType(u64);
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Struct {
+    a: i32,
+    b: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+enum Enum {
+    Unit,
+    NewType(u64),
+    Struct { a: i32, b: u16 },
+    Tuple(i32, u16),
+}
+
+#[test]
+fn round_trip_values() {
+    round_trip(&());
+    round_trip(&true);
+    round_trip(&-5i8);
+    round_trip(&u128::MAX);
+    round_trip(&1.5f64);
+    round_trip(&'x');
+    round_trip(&"abcd".to_owned());
+    round_trip(&vec![1u32, 2, 3]);
+    round_trip(&NewType(87654321));
+    round_trip(&Struct {
+        a: -87654321,
+        b: Some("abcd".to_owned()),
+    });
+    round_trip(&Enum::Unit);
+    round_trip(&Enum::NewType(87654321));
+    round_trip(&Enum::Struct { a: 1, b: 2 });
+    round_trip(&Enum::Tuple(1, 2));
+}
+
+#[test]
+fn struct_fields_reordered() {
+    #[derive(Serialize)]
+    struct Reordered<'a> {
+        extra: (),
+        b: Option<&'a str>,
+        a: i32,
+    }
+
+    let buf = to_vec(&Reordered {
+        extra: (),
+        b: None,
+        a: 5,
+    })
+    .expect("serializing must work");
+
+    let rev: Struct = from_slice(&buf).expect("deserializing must work");
+    assert_eq!(
+        rev,
+        Struct { a: 5, b: None },
+        "fields must be matched by name"
+    );
+}
+
+#[test]
+fn value_struct() {
+    let buf = round_trip(&Struct {
+        a: 87654321,
+        b: Some("abcd".to_owned()),
+    });
+
+    let value: Value = from_slice(&buf).expect("must deserialize as value");
+    assert_eq!(
+        value,
+        Value::Map(vec![
+            (str("a"), Value::SInt(87654321)),
+            (str("b"), Value::Option(Some(Box::new(str("abcd"))))),
+        ]),
+        "value must match"
+    );
+}
+
+#[test]
+fn value_enum() {
+    let buf = round_trip(&vec![
+        Enum::Unit,
+        Enum::NewType(5),
+        Enum::Struct { a: 1, b: 2 },
+        Enum::Tuple(3, 4),
+    ]);
+
+    let value: Value = from_slice(&buf).expect("must deserialize as value");
+    let variant = |name: &str, data| Value::Map(vec![(str(name), data)]);
+    assert_eq!(
+        value,
+        Value::Seq(vec![
+            variant("Unit", Value::Unit),
+            variant("NewType", Value::UInt(5)),
+            variant(
+                "Struct",
+                Value::Map(vec![(str("a"), Value::SInt(1)), (str("b"), Value::UInt(2))])
+            ),
+            variant("Tuple", Value::Seq(vec![Value::SInt(3), Value::UInt(4)])),
+        ]),
+        "value must match"
+    );
+}
+
+#[test]
+fn error_invalid_tag() {
+    let res = from_slice::<Value>(&[0xFF]).expect_err("must be invalid");
+    assert!(
+        matches!(res, Error::InvalidTag(0xFF)),
+        "must be invalid tag error"
+    );
+}
+
+#[test]
+fn plain_any_unsupported() {
+    let buf = crate::to_vec(&5u32).expect("serializing must work");
+    let res = crate::from_slice::<Value>(&buf).expect_err("must be unsupported");
+    assert!(
+        matches!(res, Error::AnyUnsupported),
+        "must be any unsupported error"
+    );
+}
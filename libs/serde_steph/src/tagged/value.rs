@@ -0,0 +1,148 @@
+use std::fmt;
+
+use serde::de;
+
+/// A dynamically typed value, able to hold any data from the self-describing
+/// format.
+///
+/// This is mostly useful to inspect data without knowing the type it was
+/// serialized from. Enums are represented as a [`Value::Map`] with a single
+/// entry from the variant name to its data.
+///
+/// # Examples
+///
+/// ```
+/// # use serde_steph::tagged::{self, Value};
+/// let buf = tagged::to_vec(&(1u8, "hello")).expect("must serialize");
+/// let value: Value = tagged::from_slice(&buf).expect("must deserialize");
+/// assert_eq!(
+///     value,
+///     Value::Seq(vec![Value::UInt(1), Value::Str("hello".to_owned())])
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Unit,
+    Bool(bool),
+    UInt(u128),
+    SInt(i128),
+    Float(f64),
+    Char(char),
+    Str(String),
+    Bytes(Vec<u8>),
+    Option(Option<Box<Self>>),
+    Seq(Vec<Self>),
+    /// Map entries in their serialized order.
+    Map(Vec<(Self, Self)>),
+}
+
+impl<'de> de::Deserialize<'de> for Value {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_any(ValueVisitor)
+    }
+}
+
+struct ValueVisitor;
+
+impl<'de> de::Visitor<'de> for ValueVisitor {
+    type Value = Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("any value")
+    }
+
+    fn visit_unit<E>(self) -> Result<Value, E> {
+        Ok(Value::Unit)
+    }
+
+    fn visit_bool<E>(self, v: bool) -> Result<Value, E> {
+        Ok(Value::Bool(v))
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Value, E> {
+        Ok(Value::UInt(v.into()))
+    }
+
+    fn visit_u128<E>(self, v: u128) -> Result<Value, E> {
+        Ok(Value::UInt(v))
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Value, E> {
+        Ok(Value::SInt(v.into()))
+    }
+
+    fn visit_i128<E>(self, v: i128) -> Result<Value, E> {
+        Ok(Value::SInt(v))
+    }
+
+    fn visit_f64<E>(self, v: f64) -> Result<Value, E> {
+        Ok(Value::Float(v))
+    }
+
+    fn visit_char<E>(self, v: char) -> Result<Value, E> {
+        Ok(Value::Char(v))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Value, E> {
+        Ok(Value::Str(v.to_owned()))
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<Value, E> {
+        Ok(Value::Str(v))
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Value, E> {
+        Ok(Value::Bytes(v.to_vec()))
+    }
+
+    fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<Value, E> {
+        Ok(Value::Bytes(v))
+    }
+
+    fn visit_none<E>(self) -> Result<Value, E> {
+        Ok(Value::Option(None))
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        let inner = de::Deserialize::deserialize(deserializer)?;
+        Ok(Value::Option(Some(Box::new(inner))))
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        de::Deserialize::deserialize(deserializer)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Value, A::Error>
+    where
+        A: de::SeqAccess<'de>,
+    {
+        // don't trust the size hint too much or bad data could allocate a lot
+        let mut items = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(0x100));
+        while let Some(item) = seq.next_element()? {
+            items.push(item);
+        }
+
+        Ok(Value::Seq(items))
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Value, A::Error>
+    where
+        A: de::MapAccess<'de>,
+    {
+        let mut entries = Vec::with_capacity(map.size_hint().unwrap_or(0).min(0x100));
+        while let Some(entry) = map.next_entry()? {
+            entries.push(entry);
+        }
+
+        Ok(Value::Map(entries))
+    }
+}
@@ -0,0 +1,126 @@
+//! Helpers to verify that types work correctly with this format.
+//!
+//! These are meant to be used in tests of downstream crates. The assertions
+//! panic with a descriptive message on failure.
+//!
+//! With the `arbitrary` feature enabled, this also provides
+//! [`check_arbitrary`] and [`check_arbitrary_many`], which generate values via
+//! the [`arbitrary`] crate. [`check_arbitrary`] is suitable as the body of a
+//! fuzz target.
+//!
+//! # Examples
+//!
+//! ```
+//! use serde::{Deserialize, Serialize};
+//! use serde_steph::testing;
+//!
+//! #[derive(Debug, PartialEq, Serialize, Deserialize)]
+//! struct Args {
+//!     page: u16,
+//!     name: String,
+//! }
+//!
+//! let args = Args {
+//!     page: 5,
+//!     name: "test".to_owned(),
+//! };
+//!
+//! testing::assert_round_trip(&args);
+//! testing::assert_truncation_detected(&args);
+//! ```
+
+use std::fmt;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// Asserts that a value is equal to itself after serializing and deserializing
+/// it again, both from a slice and a reader.
+///
+/// Returns the serialized bytes.
+#[track_caller]
+pub fn assert_round_trip<T>(value: &T) -> Vec<u8>
+where
+    T: PartialEq + fmt::Debug + Serialize + DeserializeOwned,
+{
+    let buf = match crate::to_vec(value) {
+        Ok(buf) => buf,
+        Err(why) => panic!("failed to serialize {value:?}: {why}"),
+    };
+
+    match crate::from_slice::<T>(&buf) {
+        Ok(rev) => assert_eq!(*value, rev, "value changed in round-trip via slice"),
+        Err(why) => panic!("failed to deserialize {value:?} from slice: {why}"),
+    }
+
+    match crate::from_reader::<T, _>(buf.as_slice()) {
+        Ok(rev) => assert_eq!(*value, rev, "value changed in round-trip via reader"),
+        Err(why) => panic!("failed to deserialize {value:?} from reader: {why}"),
+    }
+
+    buf
+}
+
+/// Asserts that deserializing any truncated form of a value's serialized data
+/// fails rather than producing a value.
+#[track_caller]
+pub fn assert_truncation_detected<T>(value: &T)
+where
+    T: fmt::Debug + Serialize + DeserializeOwned,
+{
+    let buf = match crate::to_vec(value) {
+        Ok(buf) => buf,
+        Err(why) => panic!("failed to serialize {value:?}: {why}"),
+    };
+
+    for len in 0..buf.len() {
+        if let Ok(rev) = crate::from_slice::<T>(&buf[..len]) {
+            panic!("truncated data for {value:?} at {len} bytes deserialized as {rev:?}");
+        }
+    }
+}
+
+/// Generates a value from `data` and asserts that it round-trips and detects
+/// truncation.
+///
+/// This is intended to be used as the body of a fuzz target. Returns an error
+/// if `data` cannot be used to generate a value.
+#[cfg(feature = "arbitrary")]
+#[track_caller]
+pub fn check_arbitrary<T>(data: &[u8]) -> arbitrary::Result<()>
+where
+    T: for<'a> arbitrary::Arbitrary<'a> + PartialEq + fmt::Debug + Serialize + DeserializeOwned,
+{
+    let mut data = arbitrary::Unstructured::new(data);
+    let value = T::arbitrary(&mut data)?;
+    assert_round_trip(&value);
+    assert_truncation_detected(&value);
+    Ok(())
+}
+
+/// Calls [`check_arbitrary`] with `iterations` deterministic pseudo-random
+/// inputs.
+///
+/// This provides a quick property test without a fuzzing setup.
+#[cfg(feature = "arbitrary")]
+#[track_caller]
+pub fn check_arbitrary_many<T>(iterations: usize)
+where
+    T: for<'a> arbitrary::Arbitrary<'a> + PartialEq + fmt::Debug + Serialize + DeserializeOwned,
+{
+    // xorshift is plenty for generating inputs and keeps failures reproducible
+    let mut state = 0x2545_F491_4F6C_DD1D_u64;
+    let mut buf = [0u8; 256];
+
+    for _ in 0..iterations {
+        for chunk in buf.chunks_exact_mut(8) {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            chunk.copy_from_slice(&state.to_le_bytes());
+        }
+
+        // inputs that can't produce a value are just skipped
+        _ = check_arbitrary::<T>(&buf);
+    }
+}
@@ -170,3 +170,149 @@ fn from_slice_excess() {
         "must be trailing bytes error"
     );
 }
+
+#[test]
+fn from_reader_owned() {
+    let res: String = from_reader(&b"\x04abcd"[..]).expect("must deserialize");
+    assert_eq!(res, "abcd", "expected match");
+
+    assert!(
+        matches!(
+            from_reader::<String, _>(&b"\x05abcd"[..]),
+            Err(Error::Io(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof
+        ),
+        "expected eof error"
+    );
+}
+
+#[test]
+fn limit_depth() {
+    let options = DeserializerOptions::new().max_depth(2);
+    let buf = to_vec(&Some(Some(5u8))).expect("serializing must work");
+    let res = Option::<Option<u8>>::deserialize(
+        &mut Deserializer::from_slice(&buf).with_options(options),
+    );
+    assert!(res.is_ok(), "2 levels must be allowed");
+
+    let buf = to_vec(&Some(Some(Some(5u8)))).expect("serializing must work");
+    let res = Option::<Option<Option<u8>>>::deserialize(
+        &mut Deserializer::from_slice(&buf).with_options(options),
+    );
+    assert!(
+        matches!(res, Err(Error::DepthLimitExceeded)),
+        "must be depth limit error"
+    );
+}
+
+#[test]
+fn limit_seq_len() {
+    let options = DeserializerOptions::new().max_seq_len(2);
+
+    // a long sequence of units doesn't need any bytes to deserialize
+    let res = Vec::<()>::deserialize(
+        &mut Deserializer::from_slice(b"\xFF\xFF\xFF\xFF\x0F").with_options(options),
+    );
+    assert!(
+        matches!(res, Err(Error::SeqTooLong)),
+        "must be seq too long error"
+    );
+}
+
+#[test]
+fn limit_bytes_len() {
+    let options = DeserializerOptions::new().max_bytes_len(3);
+    let res =
+        String::deserialize(&mut Deserializer::from_reader(&b"\x04abcd"[..]).with_options(options));
+    assert!(
+        matches!(res, Err(Error::BytesTooLong)),
+        "must be bytes too long error"
+    );
+}
+
+#[test]
+fn limit_depth_tagged() {
+    let options = DeserializerOptions::new().max_depth(4);
+    let buf = tagged::to_vec(&vec![vec![vec![vec![vec![5u8]]]]]).expect("serializing must work");
+    let res = tagged::Value::deserialize(
+        &mut tagged::Deserializer::from_slice(&buf).with_options(options),
+    );
+    assert!(
+        matches!(res, Err(Error::DepthLimitExceeded)),
+        "must be depth limit error"
+    );
+}
+
+#[test]
+fn value_round_trip() {
+    fn check<T>(value: &T)
+    where
+        T: PartialEq + fmt::Debug + Serialize + serde::de::DeserializeOwned,
+    {
+        let dynamic = to_value(value).expect("converting to value must work");
+        assert_eq!(
+            to_vec(&dynamic).expect("serializing value must work"),
+            to_vec(value).expect("serializing must work"),
+            "value must serialize the same"
+        );
+
+        let rev: T = from_value(&dynamic).expect("converting from value must work");
+        assert_eq!(*value, rev, "conversion messed up data");
+    }
+
+    check(&());
+    check(&(true, -5i8, 200u8, -87654321i64, u128::MAX, 1.5f32, 'x'));
+    check(&Some("abcd".to_owned()));
+    check(&None::<u32>);
+    check(&vec![87654321, 54321, 321]);
+    check(&NewType(87654321));
+    check(&Struct { a: -1, b: 2 });
+    check(&Enum::Unit);
+    check(&Enum::NewType(87654321));
+    check(&Enum::Struct { a: 1, b: 2 });
+    check(&Enum::Tuple(1, 2));
+    check(&indexmap::IndexMap::<String, char>::from_iter([
+        ("a".to_owned(), 'A'),
+        ("b".to_owned(), 'B'),
+    ]));
+}
+
+#[test]
+fn value_from_tagged() {
+    let buf = tagged::to_vec(&(5u32, Some("hi"))).expect("serializing must work");
+    let value: Value = tagged::from_slice(&buf).expect("deserializing must work");
+    assert_eq!(
+        value,
+        Value::List(vec![
+            Value::UInt(5),
+            Value::Tuple(vec![
+                Value::Byte(1),
+                Value::List(vec![Value::Byte(b'h'), Value::Byte(b'i')]),
+            ]),
+        ]),
+        "value must match"
+    );
+}
+
+#[test]
+fn testing_detects_truncation() {
+    testing::assert_truncation_detected(&Enum::Struct { a: -87654321, b: 2 });
+    testing::assert_truncation_detected(&vec!["abc".to_owned(), "def".to_owned()]);
+    testing::assert_truncation_detected(&(Some(5u128), 'x', [1u8, 2, 3]));
+}
+
+#[test]
+#[should_panic = "deserialized as"]
+fn testing_reports_undetected_truncation() {
+    // deserializing ignores the field, so dropping its bytes goes unnoticed
+    #[derive(Debug, Serialize, Deserialize)]
+    struct Skipped(#[serde(skip_deserializing)] u32, u32);
+
+    testing::assert_truncation_detected(&Skipped(1, 2));
+}
+
+#[cfg(feature = "arbitrary")]
+#[test]
+fn testing_arbitrary() {
+    testing::check_arbitrary_many::<(u32, i64, String, Option<Vec<u16>>)>(64);
+    testing::check_arbitrary_many::<std::collections::BTreeMap<char, bool>>(64);
+}
@@ -0,0 +1,239 @@
+//! Provides a dynamic type for the plain format.
+
+use std::fmt;
+
+use serde::de;
+use serde::ser::{self, SerializeTuple as _};
+
+mod serializer;
+
+/// A dynamically typed value, modelling the types of the plain format.
+///
+/// Serializing a [`Value`] with this crate's [`Serializer`](crate::Serializer)
+/// produces exactly the same output as serializing the value it was created
+/// from via [`to_value`]. Since the plain format isn't self-describing, it
+/// cannot be read back as a [`Value`] directly, but a [`Value`] can be
+/// deserialized from self-describing formats and converted into concrete types
+/// via [`from_value`].
+///
+/// Rust types map to variants the same way they map to the format's types, as
+/// described in the [crate root](crate). Other values map as follows:
+///
+/// - [`bool`] becomes a [`Value::Byte`].
+/// - [`Option`] becomes a [`Value::Byte`] with 0 for [`None`], or a
+///   [`Value::Tuple`] of [`Value::Byte`] 1 and the inner value for [`Some`].
+/// - [`f32`] and [`f64`] become a [`Value::Tuple`] of their little-endian
+///   bytes.
+/// - [`str`] and byte slices become a [`Value::List`] of [`Value::Byte`].
+///
+/// # Examples
+///
+/// ```
+/// # use serde_steph::Value;
+/// let value = serde_steph::to_value(&(5u32, "hi")).expect("must convert");
+/// assert_eq!(
+///     value,
+///     Value::Tuple(vec![
+///         Value::UInt(5),
+///         Value::List(vec![Value::Byte(b'h'), Value::Byte(b'i')]),
+///     ])
+/// );
+///
+/// let (n, s): (u32, String) = serde_steph::from_value(&value).expect("must convert");
+/// assert_eq!((n, s.as_str()), (5, "hi"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Value {
+    /// No data.
+    Unit,
+    /// A single byte.
+    Byte(u8),
+    /// An unsigned LEB128 integer.
+    UInt(u128),
+    /// A signed LEB128 integer.
+    SInt(i128),
+    /// A sequence with length prefix.
+    List(Vec<Self>),
+    /// A sequence without length prefix.
+    Tuple(Vec<Self>),
+    /// A sequence of key-value pairs with length prefix.
+    Map(Vec<(Self, Self)>),
+    /// A variant index followed by the variant data.
+    Enum(u32, Box<Self>),
+}
+
+/// Converts a value into a [`Value`].
+///
+/// # Errors
+///
+/// Returns an error if the value's [`Serialize`](ser::Serialize)
+/// implementation fails or it would fail to serialize to the plain format.
+pub fn to_value<T>(value: &T) -> crate::Result<Value>
+where
+    T: ?Sized + ser::Serialize,
+{
+    value.serialize(serializer::ValueSerializer)
+}
+
+/// Converts a [`Value`] into a concrete type.
+///
+/// This is equivalent to serializing the value and deserializing the output as
+/// `T`, and as such is subject to the same restrictions.
+pub fn from_value<T>(value: &Value) -> crate::Result<T>
+where
+    T: de::DeserializeOwned,
+{
+    let buf = crate::to_vec(value)?;
+    crate::from_slice(&buf)
+}
+
+impl ser::Serialize for Value {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        match self {
+            Self::Unit => serializer.serialize_unit(),
+            Self::Byte(v) => serializer.serialize_u8(*v),
+            Self::UInt(v) => serializer.serialize_u128(*v),
+            Self::SInt(v) => serializer.serialize_i128(*v),
+            Self::List(items) => serializer.collect_seq(items),
+            Self::Tuple(items) => {
+                let mut tuple = serializer.serialize_tuple(items.len())?;
+                for item in items {
+                    tuple.serialize_element(item)?;
+                }
+                tuple.end()
+            },
+            Self::Map(entries) => serializer.collect_map(entries.iter().map(|(k, v)| (k, v))),
+            // the plain format doesn't write names, so they can be left empty
+            Self::Enum(index, data) => {
+                serializer.serialize_newtype_variant("Value", *index, "", data)
+            },
+        }
+    }
+}
+
+impl<'de> de::Deserialize<'de> for Value {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_any(ValueVisitor)
+    }
+}
+
+fn byte_list(bytes: &[u8]) -> Value {
+    Value::List(bytes.iter().copied().map(Value::Byte).collect())
+}
+
+fn byte_tuple(bytes: &[u8]) -> Value {
+    Value::Tuple(bytes.iter().copied().map(Value::Byte).collect())
+}
+
+struct ValueVisitor;
+
+impl<'de> de::Visitor<'de> for ValueVisitor {
+    type Value = Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("any value")
+    }
+
+    fn visit_unit<E>(self) -> Result<Value, E> {
+        Ok(Value::Unit)
+    }
+
+    fn visit_bool<E>(self, v: bool) -> Result<Value, E> {
+        Ok(Value::Byte(v.into()))
+    }
+
+    fn visit_u8<E>(self, v: u8) -> Result<Value, E> {
+        Ok(Value::Byte(v))
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Value, E> {
+        Ok(Value::UInt(v.into()))
+    }
+
+    fn visit_u128<E>(self, v: u128) -> Result<Value, E> {
+        Ok(Value::UInt(v))
+    }
+
+    #[allow(clippy::cast_sign_loss)]
+    fn visit_i8<E>(self, v: i8) -> Result<Value, E> {
+        Ok(Value::Byte(v as u8))
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Value, E> {
+        Ok(Value::SInt(v.into()))
+    }
+
+    fn visit_i128<E>(self, v: i128) -> Result<Value, E> {
+        Ok(Value::SInt(v))
+    }
+
+    fn visit_f32<E>(self, v: f32) -> Result<Value, E> {
+        Ok(byte_tuple(&v.to_le_bytes()))
+    }
+
+    fn visit_f64<E>(self, v: f64) -> Result<Value, E> {
+        Ok(byte_tuple(&v.to_le_bytes()))
+    }
+
+    fn visit_char<E>(self, v: char) -> Result<Value, E> {
+        Ok(Value::UInt(u32::from(v).into()))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Value, E> {
+        Ok(byte_list(v.as_bytes()))
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Value, E> {
+        Ok(byte_list(v))
+    }
+
+    fn visit_none<E>(self) -> Result<Value, E> {
+        Ok(Value::Byte(0))
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        let inner = de::Deserialize::deserialize(deserializer)?;
+        Ok(Value::Tuple(vec![Value::Byte(1), inner]))
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        de::Deserialize::deserialize(deserializer)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Value, A::Error>
+    where
+        A: de::SeqAccess<'de>,
+    {
+        // don't trust the size hint too much or bad data could allocate a lot
+        let mut items = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(0x100));
+        while let Some(item) = seq.next_element()? {
+            items.push(item);
+        }
+
+        Ok(Value::List(items))
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Value, A::Error>
+    where
+        A: de::MapAccess<'de>,
+    {
+        let mut entries = Vec::with_capacity(map.size_hint().unwrap_or(0).min(0x100));
+        while let Some(entry) = map.next_entry()? {
+            entries.push(entry);
+        }
+
+        Ok(Value::Map(entries))
+    }
+}
@@ -0,0 +1,356 @@
+use serde::ser;
+
+use super::{byte_list, byte_tuple, Value};
+use crate::error::{Error, Result};
+
+/// Serializer that produces a [`Value`].
+pub(super) struct ValueSerializer;
+
+impl ser::Serializer for ValueSerializer {
+    type Ok = Value;
+    type Error = Error;
+
+    type SerializeSeq = SerializeVec;
+    type SerializeTuple = SerializeVec;
+    type SerializeTupleStruct = SerializeVec;
+    type SerializeTupleVariant = SerializeVec;
+    type SerializeMap = SerializeMap;
+    type SerializeStruct = SerializeVec;
+    type SerializeStructVariant = SerializeVec;
+
+    fn serialize_bool(self, v: bool) -> Result<Value> {
+        Ok(Value::Byte(v.into()))
+    }
+
+    #[allow(clippy::cast_sign_loss)]
+    fn serialize_i8(self, v: i8) -> Result<Value> {
+        Ok(Value::Byte(v as u8))
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Value> {
+        Ok(Value::SInt(v.into()))
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Value> {
+        Ok(Value::SInt(v.into()))
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Value> {
+        Ok(Value::SInt(v.into()))
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<Value> {
+        Ok(Value::SInt(v))
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Value> {
+        Ok(Value::Byte(v))
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Value> {
+        Ok(Value::UInt(v.into()))
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Value> {
+        Ok(Value::UInt(v.into()))
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Value> {
+        Ok(Value::UInt(v.into()))
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<Value> {
+        Ok(Value::UInt(v))
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Value> {
+        Ok(byte_tuple(&v.to_le_bytes()))
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Value> {
+        Ok(byte_tuple(&v.to_le_bytes()))
+    }
+
+    fn serialize_char(self, v: char) -> Result<Value> {
+        self.serialize_u32(v.into())
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Value> {
+        Ok(byte_list(v.as_bytes()))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Value> {
+        Ok(byte_list(v))
+    }
+
+    fn serialize_none(self) -> Result<Value> {
+        Ok(Value::Byte(0))
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<Value>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        Ok(Value::Tuple(vec![Value::Byte(1), value.serialize(self)?]))
+    }
+
+    fn serialize_unit(self) -> Result<Value> {
+        Ok(Value::Unit)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Value> {
+        Ok(Value::Unit)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+    ) -> Result<Value> {
+        Ok(Value::Enum(variant_index, Box::new(Value::Unit)))
+    }
+
+    fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T) -> Result<Value>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        value: &T,
+    ) -> Result<Value>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        let value = value.serialize(self)?;
+        Ok(Value::Enum(variant_index, Box::new(value)))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq> {
+        let len = len.ok_or(Error::LengthRequired)?;
+        Ok(SerializeVec::new(len, Wrap::List))
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple> {
+        Ok(SerializeVec::new(len, Wrap::Tuple))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleStruct> {
+        Ok(SerializeVec::new(len, Wrap::Tuple))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleVariant> {
+        Ok(SerializeVec::new(len, Wrap::Variant(variant_index)))
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap> {
+        let len = len.ok_or(Error::LengthRequired)?;
+        Ok(SerializeMap {
+            entries: Vec::with_capacity(len),
+            key: None,
+        })
+    }
+
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<Self::SerializeStruct> {
+        Ok(SerializeVec::new(len, Wrap::Tuple))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStructVariant> {
+        Ok(SerializeVec::new(len, Wrap::Variant(variant_index)))
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+/// How to wrap the items collected by a [`SerializeVec`].
+enum Wrap {
+    List,
+    Tuple,
+    Variant(u32),
+}
+
+/// Collects the elements of a sequence into a [`Value`].
+pub(super) struct SerializeVec {
+    items: Vec<Value>,
+    wrap: Wrap,
+}
+
+/// Collects the entries of a map into a [`Value::Map`].
+pub(super) struct SerializeMap {
+    entries: Vec<(Value, Value)>,
+    key: Option<Value>,
+}
+
+impl SerializeVec {
+    fn new(len: usize, wrap: Wrap) -> Self {
+        Self {
+            items: Vec::with_capacity(len),
+            wrap,
+        }
+    }
+
+    fn push<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        self.items.push(value.serialize(ValueSerializer)?);
+        Ok(())
+    }
+
+    fn finish(self) -> Value {
+        match self.wrap {
+            Wrap::List => Value::List(self.items),
+            Wrap::Tuple => Value::Tuple(self.items),
+            Wrap::Variant(index) => Value::Enum(index, Box::new(Value::Tuple(self.items))),
+        }
+    }
+}
+
+impl ser::SerializeSeq for SerializeVec {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value> {
+        Ok(self.finish())
+    }
+}
+
+impl ser::SerializeTuple for SerializeVec {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value> {
+        Ok(self.finish())
+    }
+}
+
+impl ser::SerializeTupleStruct for SerializeVec {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value> {
+        Ok(self.finish())
+    }
+}
+
+impl ser::SerializeTupleVariant for SerializeVec {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value> {
+        Ok(self.finish())
+    }
+}
+
+impl ser::SerializeStruct for SerializeVec {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, _key: &'static str, value: &T) -> Result<()>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value> {
+        Ok(self.finish())
+    }
+}
+
+impl ser::SerializeStructVariant for SerializeVec {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, _key: &'static str, value: &T) -> Result<()>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value> {
+        Ok(self.finish())
+    }
+}
+
+impl ser::SerializeMap for SerializeMap {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_key<T>(&mut self, key: &T) -> Result<()>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        self.key = Some(key.serialize(ValueSerializer)?);
+        Ok(())
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        let key = self.key.take().ok_or_else(|| {
+            <Error as ser::Error>::custom("serialize_value called before serialize_key")
+        })?;
+
+        self.entries.push((key, value.serialize(ValueSerializer)?));
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value> {
+        Ok(Value::Map(self.entries))
+    }
+}
@@ -0,0 +1,103 @@
+#![allow(unused_crate_dependencies)]
+use serde::{Deserialize, Serialize};
+use serde_steph::migrate::{self, Migrate};
+use serde_steph::Error;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Migrate)]
+#[steph(version = 1)]
+struct ArgsV1 {
+    page: u16,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Migrate)]
+#[steph(version = 2, previous = "ArgsV1")]
+struct ArgsV2 {
+    page: u16,
+    ephemeral: bool,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Migrate)]
+#[steph(version = 4, previous = "ArgsV2")]
+struct Args {
+    page: u32,
+    ephemeral: bool,
+    filter: Option<String>,
+}
+
+impl From<ArgsV1> for ArgsV2 {
+    fn from(value: ArgsV1) -> Self {
+        Self {
+            page: value.page,
+            ephemeral: false,
+        }
+    }
+}
+
+impl From<ArgsV2> for Args {
+    fn from(value: ArgsV2) -> Self {
+        Self {
+            page: value.page.into(),
+            ephemeral: value.ephemeral,
+            filter: None,
+        }
+    }
+}
+
+#[test]
+fn read_current() {
+    let args = Args {
+        page: 87654321,
+        ephemeral: true,
+        filter: Some("abc".to_owned()),
+    };
+
+    let buf = migrate::to_vec_versioned(&args).expect("serializing must work");
+    let rev: Args = migrate::read_any_version(&buf).expect("deserializing must work");
+    assert_eq!(args, rev, "serialization messed up data");
+}
+
+#[test]
+fn read_old_versions() {
+    let buf = migrate::to_vec_versioned(&ArgsV1 { page: 5 }).expect("serializing must work");
+    let rev: Args = migrate::read_any_version(&buf).expect("must upgrade from v1");
+    assert_eq!(
+        rev,
+        Args {
+            page: 5,
+            ephemeral: false,
+            filter: None,
+        },
+        "must upgrade through the chain"
+    );
+
+    let buf = migrate::to_vec_versioned(&ArgsV2 {
+        page: 6,
+        ephemeral: true,
+    })
+    .expect("serializing must work");
+    let rev: Args = migrate::read_any_version(&buf).expect("must upgrade from v2");
+    assert_eq!(
+        rev,
+        Args {
+            page: 6,
+            ephemeral: true,
+            filter: None,
+        },
+        "must upgrade from v2"
+    );
+}
+
+#[test]
+fn unknown_versions() {
+    // version 3 was skipped, 5 is newer, and 0 is older than the oldest
+    for version in [0u32, 3, 5] {
+        let mut buf = serde_steph::to_vec(&version).expect("serializing must work");
+        buf.extend_from_slice(&[5, 1, 0]);
+
+        let res = migrate::read_any_version::<Args>(&buf).expect_err("must be unknown");
+        assert!(
+            matches!(res, Error::UnknownVersion(v) if v == version),
+            "must be unknown version error"
+        );
+    }
+}
@@ -0,0 +1,81 @@
+use num_enum::FromPrimitive;
+
+use super::StreamedResource;
+use crate::define_unity_class;
+use crate::unity_fs::UnityFsFile;
+
+define_unity_class! {
+    /// Data for Unity's AudioClip class.
+    pub class AudioClip = "AudioClip" {
+        pub name: String = "m_Name",
+        pub load_type: i32 = "m_LoadType",
+        pub channels: i32 = "m_Channels",
+        pub frequency: i32 = "m_Frequency",
+        pub bits_per_sample: i32 = "m_BitsPerSample",
+        pub length: f32 = "m_Length",
+        pub compression_format: i32 = "m_CompressionFormat",
+        pub resource: StreamedResource = "m_Resource",
+    }
+}
+
+/// Loaded data for an [`AudioClip`].
+#[derive(Debug, Clone)]
+pub struct AudioClipData<'t> {
+    clip: &'t AudioClip,
+    data: &'t [u8],
+}
+
+impl AudioClip {
+    /// Gets the compression format.
+    pub fn compression_format(&self) -> AudioCompressionFormat {
+        AudioCompressionFormat::from_primitive(self.compression_format)
+    }
+
+    /// Reads the audio data.
+    pub fn read_data<'t, 'fs: 't>(
+        &'t self,
+        fs: &'fs UnityFsFile<'fs>,
+    ) -> crate::Result<AudioClipData<'t>> {
+        Ok(AudioClipData {
+            clip: self,
+            data: self.resource.load_data(fs)?,
+        })
+    }
+}
+
+impl AudioClipData<'_> {
+    /// Gets the block of data.
+    ///
+    /// For any Unity version with the `m_Resource` field, this is an FMOD
+    /// sound bank (FSB5) holding the encoded clip, regardless of the
+    /// [`AudioCompressionFormat`].
+    #[must_use]
+    pub fn data(&self) -> &[u8] {
+        self.data
+    }
+
+    /// Gets the clip this data belongs to.
+    #[must_use]
+    pub fn clip(&self) -> &AudioClip {
+        self.clip
+    }
+}
+
+/// Well-known audio compression formats.
+#[derive(Debug, Eq, PartialEq, FromPrimitive, Clone, Copy, Default, Hash)]
+#[repr(i32)]
+#[non_exhaustive]
+pub enum AudioCompressionFormat {
+    #[default]
+    UnknownType = -1,
+    PCM = 0,
+    Vorbis = 1,
+    ADPCM = 2,
+    MP3 = 3,
+    VAG = 4,
+    HEVAG = 5,
+    XMA = 6,
+    AAC = 7,
+    GCADPCM = 8,
+    ATRAC9 = 9,
+}
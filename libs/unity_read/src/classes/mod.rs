@@ -8,6 +8,7 @@ use crate::serialized_file::TypeTreeNode;
 use crate::{BinReadEndian, SeekRead};
 
 mod asset_bundle;
+mod audio_clip;
 mod class_id;
 mod mesh;
 mod streaming_info;
@@ -15,6 +16,7 @@ mod text_asset;
 mod texture2d;
 
 pub use asset_bundle::*;
+pub use audio_clip::*;
 pub use class_id::*;
 pub use mesh::*;
 pub use streaming_info::*;
@@ -15,6 +15,15 @@ define_unity_class! {
     }
 }
 
+define_unity_class! {
+    /// Streamed resource information, used by audio clips.
+    pub class StreamedResource = "StreamedResource" {
+        pub source: String = "m_Source",
+        pub offset: Offset = "m_Offset",
+        pub size: u64 = "m_Size",
+    }
+}
+
 #[derive(Debug, Default, Clone, Copy)]
 pub struct Offset(pub u64);
 
@@ -40,29 +49,7 @@ impl StreamingInfo {
 
     /// Loads the streaming data.
     pub fn load_data<'a>(&self, fs: &'a UnityFsFile<'a>) -> crate::Result<&'a [u8]> {
-        let path = self
-            .path
-            .split('/')
-            .last()
-            .ok_or(Error::InvalidData("streaming data path incorrect"))?
-            .as_bytes();
-
-        let node = fs
-            .entries()
-            .find(|e| e.path_raw() == path)
-            .ok_or(Error::InvalidData("streaming data file not found"))?;
-
-        let offset = usize::from_int(self.offset.0)?;
-        let size = usize::from_int(self.size)?;
-
-        let slice = node
-            .read_raw()?
-            .get(offset..)
-            .ok_or(Error::InvalidData("streaming data offset out of bounds"))?
-            .get(..size)
-            .ok_or(Error::InvalidData("streaming data size out of bounds"))?;
-
-        Ok(slice)
+        load_streamed_slice(fs, &self.path, self.offset.0, u64::from(self.size))
     }
 
     pub fn load_data_or_else<'t, 'fs: 't>(
@@ -77,3 +64,46 @@ impl StreamingInfo {
         }
     }
 }
+
+impl StreamedResource {
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.source.is_empty()
+    }
+
+    /// Loads the resource data.
+    pub fn load_data<'a>(&self, fs: &'a UnityFsFile<'a>) -> crate::Result<&'a [u8]> {
+        load_streamed_slice(fs, &self.source, self.offset.0, self.size)
+    }
+}
+
+/// Loads a slice of data from another node within the same file.
+fn load_streamed_slice<'a>(
+    fs: &'a UnityFsFile<'a>,
+    path: &str,
+    offset: u64,
+    size: u64,
+) -> crate::Result<&'a [u8]> {
+    let path = path
+        .split('/')
+        .last()
+        .ok_or(Error::InvalidData("streaming data path incorrect"))?
+        .as_bytes();
+
+    let node = fs
+        .entries()
+        .find(|e| e.path_raw() == path)
+        .ok_or(Error::InvalidData("streaming data file not found"))?;
+
+    let offset = usize::from_int(offset)?;
+    let size = usize::from_int(size)?;
+
+    let slice = node
+        .read_raw()?
+        .get(offset..)
+        .ok_or(Error::InvalidData("streaming data offset out of bounds"))?
+        .get(..size)
+        .ok_or(Error::InvalidData("streaming data size out of bounds"))?;
+
+    Ok(slice)
+}
@@ -183,6 +183,30 @@ impl<T, const MIN: usize, const MAX: usize> Search<T, MIN, MAX> {
     ///
     /// Check [`Match::score`] for more details.
     pub fn search<'st>(&'st self, value: &str) -> MatchIter<'st, T> {
+        self.search_impl(value, self.min_match_score)
+    }
+
+    /// Searches for a given text, overriding the minimum matching score.
+    ///
+    /// This is useful to be more lenient about typos in specific cases, such
+    /// as suggestions, without affecting other searches.
+    ///
+    /// Check [`Self::search`] and [`Self::with_min_match_score`] for more
+    /// details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the provided score is less than `0.0` or greater than `1.0`.
+    pub fn search_with_min_score<'st>(&'st self, value: &str, score: f64) -> MatchIter<'st, T> {
+        assert!(
+            (0.0..=1.0).contains(&score),
+            "score must be within 0.0..=1.0, but was {score}"
+        );
+
+        self.search_impl(value, score)
+    }
+
+    fn search_impl<'st>(&'st self, value: &str, min_score: f64) -> MatchIter<'st, T> {
         let norm = norm_str(value);
         let mut results = MatchIter::default();
 
@@ -190,7 +214,7 @@ impl<T, const MIN: usize, const MAX: usize> Search<T, MIN, MAX> {
             let upper = MAX.min(norm.len());
 
             for size in (MIN..=upper).rev() {
-                results = self.find_with_segment_size(&norm, size);
+                results = self.find_with_segment_size(&norm, size, min_score);
                 if !results.is_empty() {
                     break;
                 }
@@ -226,7 +250,12 @@ impl<T, const MIN: usize, const MAX: usize> Search<T, MIN, MAX> {
         }
     }
 
-    fn find_with_segment_size<'st>(&'st self, norm: &[u16], size: usize) -> MatchIter<'st, T> {
+    fn find_with_segment_size<'st>(
+        &'st self,
+        norm: &[u16],
+        size: usize,
+        min_score: f64,
+    ) -> MatchIter<'st, T> {
         const MAX_MATCHES: usize = 32;
 
         let mut results = <ArrayVec<MatchInfoLen, MAX_MATCHES>>::new();
@@ -261,7 +290,7 @@ impl<T, const MIN: usize, const MAX: usize> Search<T, MIN, MAX> {
         }
 
         let total = total as f64;
-        let match_count = total * self.min_match_score;
+        let match_count = total * min_score;
 
         results.retain(|r| f64::from(r.count) >= match_count);
         results.sort_unstable();
@@ -631,6 +660,32 @@ mod test {
         }
     }
 
+    #[test]
+    fn search_with_min_score() {
+        let search = {
+            let mut search = TSearch::new();
+            search.insert("Bismarck", 1u8);
+            search.insert("Bismarck Zwei", 2);
+            search.insert("Belfast", 3);
+            search.insert("Enterprise", 4);
+            search
+        };
+
+        assert_eq!(first_data(search.search("Bismark")), Some(1));
+        assert_eq!(first_data(search.search("Enterprize")), Some(4));
+
+        // too few segments match for the default score
+        assert_eq!(first_data(search.search("Bizmrk")), None);
+        assert_eq!(
+            first_data(search.search_with_min_score("Bizmrk", 0.25)),
+            Some(1)
+        );
+
+        fn first_data(mut v: MatchIter<'_, u8>) -> Option<u8> {
+            v.next().map(|p| *p.data)
+        }
+    }
+
     #[test]
     fn norm_str_equality() {
         assert_eq!(norm_str("hello-world"), norm_str("Hello World!"));